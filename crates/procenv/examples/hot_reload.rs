@@ -12,6 +12,8 @@
 //! # Edit crates/procenv/data/hot_reload.toml to see changes
 //! ```
 
+#![allow(clippy::result_large_err)]
+
 use std::fs;
 use std::io::Write;
 use std::thread;
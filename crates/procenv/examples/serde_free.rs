@@ -6,6 +6,8 @@
 //! Run with:
 //!   `cargo run --example serde_free --features file,toml`
 
+#![allow(clippy::result_large_err)]
+
 use procenv::EnvConfig;
 
 // NO serde import needed!
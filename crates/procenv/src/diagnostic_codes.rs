@@ -18,10 +18,20 @@
 //! | `procenv::missing_var` | Required variable not set |
 //! | `procenv::invalid_utf8` | Variable contains non-UTF8 bytes |
 //! | `procenv::parse_error` | Value failed type conversion |
+//! | `procenv::not_one_of` | Value not in `one_of` allowlist |
+//! | `procenv::invalid_length` | Value violates `min_len`/`max_len` |
+//! | `procenv::regex_mismatch` | Value does not match `validate_regex` |
+//! | `procenv::invalid_regex` | `validate_regex` pattern does not compile |
+//! | `procenv::file_var_read` | `from_file_var` file cannot be read |
+//! | `procenv::after_parse` | `after_parse` hook rejected the value |
+//! | `procenv::undefined_default_var` | `default` references an unset `${VAR}` |
 //! | `procenv::multiple_errors` | Multiple errors occurred |
+//! | `procenv::struct_errors` | Errors of one config struct among several |
 //! | `procenv::invalid_profile` | Invalid profile name |
+//! | `procenv::dotenv_missing` | Required dotenv file not found |
 //! | `procenv::provider_error` | Provider operation failed |
 //! | `procenv::validation_error` | Validation constraint violated |
+//! | `procenv::custom_validation_error` | Custom field validator failed |
 //! | `procenv::cli_error` | CLI argument parsing failed |
 //! | `procenv::file_*` | File-related errors |
 //!
@@ -45,23 +55,51 @@ pub const INVALID_UTF8: &str = "procenv::invalid_utf8";
 /// Value failed to parse as expected type.
 pub const PARSE_ERROR: &str = "procenv::parse_error";
 
+/// Value is not in the field's `one_of` allowlist.
+pub const NOT_ONE_OF: &str = "procenv::not_one_of";
+
+/// Value is shorter or longer than the field's `min_len`/`max_len`.
+pub const INVALID_LENGTH: &str = "procenv::invalid_length";
+
+/// Value does not match the field's `validate_regex` pattern.
+pub const REGEX_MISMATCH: &str = "procenv::regex_mismatch";
+
+/// The field's `validate_regex` pattern is not a valid regex.
+pub const INVALID_REGEX: &str = "procenv::invalid_regex";
+
+/// The file named by a `from_file_var` variable cannot be read.
+pub const FILE_VAR_READ: &str = "procenv::file_var_read";
+
+/// A field's `after_parse` hook rejected the parsed value.
+pub const AFTER_PARSE: &str = "procenv::after_parse";
+
+/// A field's `default` references an unset `${VAR}`.
+pub const UNDEFINED_DEFAULT_VAR: &str = "procenv::undefined_default_var";
+
 /// Multiple configuration errors occurred.
 pub const MULTIPLE_ERRORS: &str = "procenv::multiple_errors";
 
+/// Errors of one config struct, when several are loaded together.
+pub const STRUCT_ERRORS: &str = "procenv::struct_errors";
+
 /// Invalid profile name specified.
 pub const INVALID_PROFILE: &str = "procenv::invalid_profile";
 
+/// Required dotenv file not found.
+pub const DOTENV_MISSING: &str = "procenv::dotenv_missing";
+
 /// Provider operation failed.
 pub const PROVIDER_ERROR: &str = "procenv::provider_error";
 
 /// Validation constraint violated.
-#[cfg(feature = "validator")]
 pub const VALIDATION_ERROR: &str = "procenv::validation_error";
 
 /// Individual field validation error.
-#[cfg(feature = "validator")]
 pub const FIELD_VALIDATION_ERROR: &str = "procenv::field_validation_error";
 
+/// Custom field validator failed.
+pub const CUSTOM_VALIDATION_ERROR: &str = "procenv::custom_validation_error";
+
 /// CLI argument parsing failed.
 #[cfg(feature = "clap")]
 pub const CLI_ERROR: &str = "procenv::cli_error";
@@ -82,6 +120,14 @@ pub const FILE_MISSING_FIELD: &str = "procenv::file::missing_field";
 #[cfg(feature = "file")]
 pub const FILE_TYPE_ERROR: &str = "procenv::file::type_error";
 
+/// Config file declares a schema version newer than supported.
+#[cfg(feature = "file")]
+pub const FILE_UNSUPPORTED_SCHEMA_VERSION: &str = "procenv::file::unsupported_schema_version";
+
+/// Config file schema version is not a whole number.
+#[cfg(feature = "file")]
+pub const FILE_INVALID_SCHEMA_VERSION: &str = "procenv::file::invalid_schema_version";
+
 /// Provider key not found.
 pub const PROVIDER_NOT_FOUND: &str = "procenv::provider::not_found";
 
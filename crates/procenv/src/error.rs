@@ -10,11 +10,19 @@
 //! | [`Error::Missing`] | Required environment variable not set |
 //! | [`Error::InvalidUtf8`] | Variable contains non-UTF8 bytes |
 //! | [`Error::Parse`] | Value failed to parse as expected type |
+//! | [`Error::NotOneOf`] | Value not in the field's `one_of` allowlist |
+//! | [`Error::InvalidLength`] | Value shorter or longer than `min_len`/`max_len` |
+//! | [`Error::RegexMismatch`] | Value does not match `validate_regex` |
+//! | [`Error::InvalidRegex`] | `validate_regex` pattern failed to compile |
+//! | [`Error::FileVarRead`] | File named by a `from_file_var` variable is unreadable |
+//! | [`Error::AfterParse`] | A field's `after_parse` hook rejected the parsed value |
+//! | [`Error::UndefinedDefaultVar`] | A `default` references an unset `${VAR}` |
 //! | [`Error::Multiple`] | Multiple configuration errors accumulated |
+//! | [`Error::Struct`] | One struct's errors when several are loaded with [`load_all!`](crate::load_all) |
 //! | [`Error::File`] | Configuration file error (with `file` feature) |
 //! | [`Error::InvalidProfile`] | Invalid profile name specified |
 //! | [`Error::Provider`] | Custom provider operation failed |
-//! | [`Error::Validation`] | Validation constraint violated (with `validator` feature) |
+//! | [`Error::Validation`] | Validation constraint violated |
 //! | [`Error::Cli`] | CLI argument parsing failed (with `clap` feature) |
 //!
 //! # Error Accumulation
@@ -47,14 +55,17 @@
 //! ```
 
 use std::error::Error as StdError;
+use std::ffi::OsStr;
 use std::fmt::{self, Debug, Display, Formatter};
+use std::io;
+use std::num::{IntErrorKind, ParseIntError};
 
 use miette::Diagnostic;
 
 #[cfg(feature = "file")]
 use crate::file;
+use crate::runtime::REDACTED;
 
-#[cfg(feature = "validator")]
 use crate::validation::ValidationFieldError;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -125,13 +136,54 @@ impl MaybeRedacted {
     pub const fn is_redacted(&self) -> bool {
         matches!(self, Self::Redacted)
     }
+
+    /// Create a value with the parts captured by `pattern` masked.
+    ///
+    /// Every capture group that takes part in a match is replaced with
+    /// `<redacted>`; a pattern without groups masks its whole match. The
+    /// rest of the value is kept, so only the secret part is never stored.
+    ///
+    /// ```rust
+    /// use procenv::MaybeRedacted;
+    ///
+    /// let pattern = regex::Regex::new(r"://[^:]+:([^@]+)@").unwrap();
+    /// let dsn = MaybeRedacted::partial("postgres://app:hunter2@db/main", &pattern);
+    /// assert_eq!(dsn.as_str(), Some("postgres://app:<redacted>@db/main"));
+    /// ```
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn partial(value: &str, pattern: &regex::Regex) -> Self {
+        let mut masked = String::with_capacity(value.len());
+        let mut end = 0;
+
+        for captures in pattern.captures_iter(value) {
+            let groups: Vec<_> = if captures.len() > 1 {
+                captures.iter().skip(1).flatten().collect()
+            } else {
+                captures.get(0).into_iter().collect()
+            };
+
+            // Nested groups fall inside the outer one already masked
+            for group in groups {
+                if group.start() < end {
+                    continue;
+                }
+                masked.push_str(&value[end..group.start()]);
+                masked.push_str(REDACTED);
+                end = group.end();
+            }
+        }
+        masked.push_str(&value[end..]);
+
+        Self::Plain(masked)
+    }
 }
 
 impl Debug for MaybeRedacted {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::Plain(s) => write!(f, "{s:?}"),
-            Self::Redacted => write!(f, "<redacted>"),
+            Self::Redacted => f.write_str(REDACTED),
         }
     }
 }
@@ -140,7 +192,7 @@ impl Display for MaybeRedacted {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::Plain(s) => write!(f, "{s:?}"),
-            Self::Redacted => write!(f, "<redacted>"),
+            Self::Redacted => f.write_str(REDACTED),
         }
     }
 }
@@ -187,8 +239,17 @@ impl Display for MaybeRedacted {
 /// | `procenv::missing_var` | Required environment variable not set |
 /// | `procenv::invalid_utf8` | Variable contains non-UTF8 bytes |
 /// | `procenv::parse_error` | Value failed to parse as expected type |
+/// | `procenv::not_one_of` | Value is not in the field's `one_of` allowlist |
+/// | `procenv::invalid_length` | Value violates the field's `min_len`/`max_len` |
+/// | `procenv::regex_mismatch` | Value does not match the field's `validate_regex` |
+/// | `procenv::invalid_regex` | The field's `validate_regex` pattern is not a valid regex |
+/// | `procenv::file_var_read` | The file named by a `from_file_var` variable cannot be read |
+/// | `procenv::after_parse` | A field's `after_parse` hook rejected the parsed value |
+/// | `procenv::undefined_default_var` | A field's `default` references an unset `${VAR}` |
 /// | `procenv::multiple_errors` | Multiple configuration errors occurred |
+/// | `procenv::struct_errors` | Errors of one config struct among several |
 /// | `procenv::invalid_profile` | Invalid profile name specified |
+/// | `procenv::dotenv_missing` | A `dotenv_required` file does not exist |
 #[derive(Diagnostic)]
 #[non_exhaustive]
 pub enum Error {
@@ -209,14 +270,21 @@ pub enum Error {
     },
 
     /// An environment variable contains invalid UTF-8.
-    #[diagnostic(
-        code(procenv::invalid_utf8),
-        help("ensure the variable contains valid UTF-8 text")
-    )]
+    ///
+    /// Fields may be added to this variant; match it with `..` and build it
+    /// with [`Error::invalid_utf8`].
+    #[diagnostic(code(procenv::invalid_utf8), help("{INVALID_UTF8_HELP}"))]
+    #[non_exhaustive]
     InvalidUtf8 {
         /// The name of the environment variable with invalid UTF-8.
         /// Uses String to support runtime-constructed var names (e.g., with prefixes).
         var: String,
+
+        /// A lossy, truncated preview of the raw value.
+        ///
+        /// Invalid bytes appear as `U+FFFD`. For secret fields, this is always
+        /// [`MaybeRedacted::Redacted`].
+        preview: MaybeRedacted,
     },
 
     /// A field extraction error occurred during config loading.
@@ -268,6 +336,144 @@ pub enum Error {
         source: Box<dyn StdError + Send + Sync>,
     },
 
+    /// An environment variable value is not one of the allowed values.
+    ///
+    /// Produced by fields declared with `#[env(one_of = [...])]`.
+    #[diagnostic(code(procenv::not_one_of))]
+    NotOneOf {
+        /// The name of the environment variable.
+        var: String,
+
+        /// The rejected value (redacted for secret fields).
+        value: MaybeRedacted,
+
+        /// The accepted values.
+        allowed: Vec<String>,
+
+        /// Help message listing the accepted values.
+        #[help]
+        help: String,
+    },
+
+    /// An environment variable value is shorter or longer than allowed.
+    ///
+    /// Produced by fields declared with `#[env(min_len = N)]` and/or
+    /// `#[env(max_len = N)]`. Lengths count characters; the value itself is
+    /// never included, so secrets are safe to report.
+    #[diagnostic(code(procenv::invalid_length))]
+    InvalidLength {
+        /// The name of the environment variable.
+        var: String,
+
+        /// Minimum accepted length in characters, if any.
+        min: Option<usize>,
+
+        /// Maximum accepted length in characters, if any.
+        max: Option<usize>,
+
+        /// Help message suggesting a fix.
+        #[help]
+        help: String,
+    },
+
+    /// An environment variable value does not match the field's pattern.
+    ///
+    /// Produced by fields declared with `#[env(validate_regex = "...")]`.
+    #[diagnostic(code(procenv::regex_mismatch))]
+    RegexMismatch {
+        /// The name of the environment variable.
+        var: String,
+
+        /// The rejected value (redacted for secret fields).
+        value: MaybeRedacted,
+
+        /// The pattern the value must match.
+        pattern: String,
+
+        /// Help message naming the pattern.
+        #[help]
+        help: String,
+    },
+
+    /// A field's `validate_regex` pattern is not a valid regular expression.
+    ///
+    /// Patterns are compiled on first use, so this surfaces at load time
+    /// rather than at compile time. It is a bug in the config struct, not
+    /// in the environment.
+    #[diagnostic(code(procenv::invalid_regex))]
+    InvalidRegex {
+        /// The name of the environment variable being checked.
+        var: String,
+
+        /// The pattern that failed to compile.
+        pattern: String,
+
+        /// The regex compiler's error message.
+        message: String,
+
+        /// Help message suggesting a fix.
+        #[help]
+        help: String,
+    },
+
+    /// The file named by a `from_file_var` variable could not be read.
+    ///
+    /// Produced by fields declared with `#[env(from_file_var = "...")]` when
+    /// that variable is set. Only the path is recorded, never the contents.
+    #[diagnostic(code(procenv::file_var_read))]
+    FileVarRead {
+        /// The variable naming the file (e.g. `DB_PASSWORD_FILE`).
+        var: String,
+
+        /// The path the variable named.
+        path: String,
+
+        /// Help message suggesting a fix.
+        #[help]
+        help: String,
+
+        /// The underlying I/O error.
+        source: io::Error,
+    },
+
+    /// A field's `after_parse` hook rejected the parsed value.
+    ///
+    /// Produced by fields declared with `#[env(after_parse = "...")]` when
+    /// the hook returns `Err`. The value is not recorded, since it has
+    /// already been parsed out of its string form.
+    #[diagnostic(code(procenv::after_parse))]
+    AfterParse {
+        /// The name of the environment variable.
+        var: String,
+
+        /// Path of the hook function, as written in the attribute.
+        function: String,
+
+        /// Help message suggesting a fix.
+        #[help]
+        help: String,
+
+        /// The error returned by the hook.
+        source: Box<dyn StdError + Send + Sync>,
+    },
+
+    /// A field's `default` references a variable that isn't set.
+    ///
+    /// Defaults may contain `${VAR}` references, expanded when the default
+    /// is used. This is produced when one of them names an unset variable.
+    #[diagnostic(code(procenv::undefined_default_var))]
+    UndefinedDefaultVar {
+        /// The name of the environment variable whose default was used.
+        var: String,
+
+        /// The variable the default references.
+        reference: String,
+
+        /// Help message suggesting a fix.
+        #[help]
+        help: String,
+    },
+
     /// Multiple configuration errors occurred.
     ///
     /// Uses miette's `#[related]` to render all errors together
@@ -280,7 +486,21 @@ pub enum Error {
         /// All accumulated errors.
         /// miette renders these as related diagnostics.
         #[related]
-        errors: Vec<Error>,
+        errors: Vec<Self>,
+    },
+
+    /// Errors from one config struct, when several are loaded together.
+    ///
+    /// Built by [`Errors::accumulate`] and [`load_all!`](crate::load_all)
+    /// so a combined report says which struct each error belongs to.
+    #[diagnostic(code(procenv::struct_errors), help("fix the errors listed for {name}"))]
+    Struct {
+        /// Name of the config struct, e.g. `DatabaseConfig`.
+        name: String,
+
+        /// The struct's errors, with any [`Error::Multiple`] unwrapped.
+        #[related]
+        errors: Vec<Self>,
     },
 
     /// An error occurred while loading a configuration file.
@@ -315,6 +535,17 @@ pub enum Error {
         help: String,
     },
 
+    /// A dotenv file declared with `dotenv_required` does not exist.
+    #[diagnostic(code(procenv::dotenv_missing))]
+    DotenvMissing {
+        /// The dotenv file path as declared in the attribute.
+        path: String,
+
+        /// Help message.
+        #[help]
+        help: String,
+    },
+
     /// An error occured in a configuration provider.
     #[diagnostic(code(procenv::provider_error))]
     Provider {
@@ -331,15 +562,15 @@ pub enum Error {
 
     /// A validation error occurred after loading configuration.
     ///
-    /// This variant wraps errors from the `validator` crate and provides
-    /// structured information about which fields failed validation.
-    #[cfg(feature = "validator")]
+    /// This variant wraps errors from the `validator` crate, or from custom
+    /// validators via [`Error::validation`], and provides structured
+    /// information about which fields failed validation.
     #[diagnostic(
         code(procenv::validation_error),
         help("fix the validation errors listed above")
     )]
     Validation {
-        /// The validation errors from the validator crate.
+        /// The validation errors, one per failed rule.
         ///
         /// Each entry maps a field name to a list of validation error messages.
         #[related]
@@ -402,14 +633,18 @@ impl From<file::FileError> for Error {
 // Manual Display impl for secret masking
 // Note: For fancy formatted output, use `miette::Report::from(error)`
 impl Display for Error {
+    #[expect(clippy::too_many_lines)]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::Missing { var, .. } => {
                 write!(f, "missing required environment variable: {var}")
             }
 
-            Self::InvalidUtf8 { var } => {
-                write!(f, "environment variable {var} contains invalid UTF-8")
+            Self::InvalidUtf8 { var, preview } => {
+                write!(
+                    f,
+                    "environment variable {var} contains invalid UTF-8: {preview}"
+                )
             }
 
             Self::Parse {
@@ -425,10 +660,64 @@ impl Display for Error {
                 )
             }
 
+            Self::NotOneOf { var, value, .. } => {
+                write!(f, "{var} has value {value}, which is not an allowed value")
+            }
+
+            Self::InvalidLength { var, min, max, .. } => {
+                write!(f, "{var} must be {}", length_constraint(*min, *max))
+            }
+
+            Self::RegexMismatch {
+                var,
+                value,
+                pattern,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{var} has value {value}, which does not match /{pattern}/"
+                )
+            }
+
+            Self::InvalidRegex { var, pattern, .. } => {
+                write!(f, "invalid validate_regex pattern /{pattern}/ for {var}")
+            }
+
+            Self::FileVarRead {
+                var, path, source, ..
+            } => {
+                write!(f, "failed to read {path} (named by {var}): {source}")
+            }
+
+            Self::AfterParse {
+                var,
+                function,
+                source,
+                ..
+            } => {
+                write!(f, "{function} rejected the value of {var}: {source}")
+            }
+
+            Self::UndefinedDefaultVar { var, reference, .. } => {
+                write!(
+                    f,
+                    "default for {var} references unset variable ${{{reference}}}"
+                )
+            }
+
             Self::Multiple { errors } => {
                 write!(f, "{} configuration error(s) occurred", errors.len())
             }
 
+            Self::Struct { name, errors } => {
+                write!(
+                    f,
+                    "{name}: {} configuration error(s) occurred",
+                    errors.len()
+                )
+            }
+
             #[cfg(feature = "file")]
             Self::File { source } => {
                 write!(f, "configuration file error: {source}")
@@ -438,13 +727,16 @@ impl Display for Error {
                 write!(f, "invalid profile '{profile}' for {var}")
             }
 
+            Self::DotenvMissing { path, .. } => {
+                write!(f, "required dotenv file not found: {path}")
+            }
+
             Self::Provider {
                 provider, message, ..
             } => {
                 write!(f, "error connecting to {provider}: {message}")
             }
 
-            #[cfg(feature = "validator")]
             Self::Validation { errors } => {
                 write!(f, "{} validation error(s) occurred", errors.len())
             }
@@ -502,11 +794,12 @@ impl Debug for Error {
                 write!(f, "  help: {help}")
             }
 
-            Self::InvalidUtf8 { var } => {
+            Self::InvalidUtf8 { var, preview } => {
                 writeln!(f, "procenv::invalid_utf8")?;
                 writeln!(f)?;
                 writeln!(f, "  x environment variable contains invalid UTF-8: {var}")?;
-                write!(f, "  help: ensure the variable contains valid UTF-8 text")
+                writeln!(f, "  value: {preview}")?;
+                write!(f, "  help: {INVALID_UTF8_HELP}")
             }
 
             Self::Parse {
@@ -524,6 +817,102 @@ impl Debug for Error {
                 write!(f, "  help: {help}")
             }
 
+            Self::NotOneOf {
+                var,
+                value,
+                allowed,
+                help,
+            } => {
+                writeln!(f, "procenv::not_one_of")?;
+                writeln!(f)?;
+                writeln!(f, "  x {var} is not one of the allowed values")?;
+                writeln!(f, "  | value: {value:?}")?;
+                writeln!(f, "  | allowed: {}", allowed.join(", "))?;
+                write!(f, "  help: {help}")
+            }
+
+            Self::InvalidLength {
+                var,
+                min,
+                max,
+                help,
+            } => {
+                writeln!(f, "procenv::invalid_length")?;
+                writeln!(f)?;
+                writeln!(f, "  x {var} has an invalid length")?;
+                writeln!(f, "  | required: {}", length_constraint(*min, *max))?;
+                write!(f, "  help: {help}")
+            }
+
+            Self::RegexMismatch {
+                var,
+                value,
+                pattern,
+                help,
+            } => {
+                writeln!(f, "procenv::regex_mismatch")?;
+                writeln!(f)?;
+                writeln!(f, "  x {var} does not match the required pattern")?;
+                writeln!(f, "  | value: {value:?}")?;
+                writeln!(f, "  | pattern: {pattern}")?;
+                write!(f, "  help: {help}")
+            }
+
+            Self::InvalidRegex {
+                var,
+                pattern,
+                message,
+                help,
+            } => {
+                writeln!(f, "procenv::invalid_regex")?;
+                writeln!(f)?;
+                writeln!(f, "  x invalid validate_regex pattern for {var}")?;
+                writeln!(f, "  | pattern: {pattern}")?;
+                writeln!(f, "  | error: {message}")?;
+                write!(f, "  help: {help}")
+            }
+
+            Self::FileVarRead {
+                var,
+                path,
+                help,
+                source,
+            } => {
+                writeln!(f, "procenv::file_var_read")?;
+                writeln!(f)?;
+                writeln!(f, "  x failed to read the file named by {var}")?;
+                writeln!(f, "  | path: {path}")?;
+                writeln!(f, "  | error: {source}")?;
+                write!(f, "  help: {help}")
+            }
+
+            Self::AfterParse {
+                var,
+                function,
+                help,
+                source,
+            } => {
+                writeln!(f, "procenv::after_parse")?;
+                writeln!(f)?;
+                writeln!(f, "  x {function} rejected the value of {var}")?;
+                writeln!(f, "  | error: {source}")?;
+                write!(f, "  help: {help}")
+            }
+
+            Self::UndefinedDefaultVar {
+                var,
+                reference,
+                help,
+            } => {
+                writeln!(f, "procenv::undefined_default_var")?;
+                writeln!(f)?;
+                writeln!(
+                    f,
+                    "  x default for {var} references unset variable ${{{reference}}}"
+                )?;
+                write!(f, "  help: {help}")
+            }
+
             Self::Multiple { errors } => {
                 writeln!(f, "procenv::multiple_errors")?;
                 writeln!(f)?;
@@ -538,6 +927,24 @@ impl Debug for Error {
                 Ok(())
             }
 
+            Self::Struct { name, errors } => {
+                writeln!(f, "procenv::struct_errors")?;
+                writeln!(f)?;
+                writeln!(
+                    f,
+                    "  x {name}: {} configuration error(s) occurred",
+                    errors.len()
+                )?;
+                writeln!(f, "  help: fix the errors listed for {name}")?;
+
+                for error in errors {
+                    writeln!(f)?;
+                    write!(f, "{error:?}")?;
+                }
+
+                Ok(())
+            }
+
             #[cfg(feature = "file")]
             Self::File { source } => write!(f, "{source:?}"),
 
@@ -554,6 +961,13 @@ impl Debug for Error {
                 write!(f, "  help: {help}")
             }
 
+            Self::DotenvMissing { path, help } => {
+                writeln!(f, "procenv::dotenv_missing")?;
+                writeln!(f)?;
+                writeln!(f, "  x required dotenv file not found: {path}")?;
+                write!(f, "  help: {help}")
+            }
+
             Self::Provider {
                 provider,
                 message,
@@ -565,7 +979,6 @@ impl Debug for Error {
                 write!(f, "  help: {help}")
             }
 
-            #[cfg(feature = "validator")]
             Self::Validation { errors } => {
                 writeln!(f, "procenv::validation_error")?;
                 writeln!(f)?;
@@ -632,6 +1045,10 @@ impl StdError for Error {
         match self {
             Self::Parse { source, .. } => Some(source.as_ref()),
 
+            Self::FileVarRead { source, .. } => Some(source),
+
+            Self::AfterParse { source, .. } => Some(source.as_ref()),
+
             #[cfg(feature = "file")]
             Self::File { source } => Some(source),
 
@@ -655,6 +1072,39 @@ impl Error {
         Self::Missing { var, help }
     }
 
+    /// Creates a Missing error for a `when_profile` field unset in `profile`.
+    pub fn missing_in_profile(var: impl Into<String>, profile: impl AsRef<str>) -> Self {
+        let var = var.into();
+        let help = format!(
+            "set {var} in your environment or .env file; it is required in the `{}` profile",
+            profile.as_ref()
+        );
+        Self::Missing { var, help }
+    }
+
+    /// Creates an `InvalidUtf8` error with a preview of the raw value.
+    ///
+    /// The preview is the first 16 bytes of the value, decoded lossily. As
+    /// with [`Error::parse`], nothing is kept for secret fields.
+    pub fn invalid_utf8(var: impl Into<String>, value: &OsStr, secret: bool) -> Self {
+        let preview = if secret {
+            MaybeRedacted::Redacted
+        } else {
+            let bytes = value.as_encoded_bytes();
+            let head = &bytes[..bytes.len().min(INVALID_UTF8_PREVIEW_BYTES)];
+            let mut preview = String::from_utf8_lossy(head).into_owned();
+            if head.len() < bytes.len() {
+                preview.push('…');
+            }
+            MaybeRedacted::Plain(preview)
+        };
+
+        Self::InvalidUtf8 {
+            var: var.into(),
+            preview,
+        }
+    }
+
     /// Creates a Parse error with appropriate help text.
     ///
     /// Accepts any type that can be converted to String for var and `expected_type`,
@@ -674,8 +1124,9 @@ impl Error {
         source: Box<dyn StdError + Send + Sync>,
     ) -> Self {
         let var = var.into();
+        let value = value.into();
         let expected_type = expected_type.into();
-        let help = format!("expected a valid {expected_type}");
+        let help = parse_help(&expected_type, &value, source.as_ref());
 
         Self::Parse {
             var,
@@ -686,38 +1137,243 @@ impl Error {
         }
     }
 
-    /// Collects multiple errors into a single Multiple error.
-    /// Returns None if the input is empty.
-    #[must_use]
-    pub fn multiple(errors: Vec<Self>) -> Option<Self> {
-        if errors.is_empty() {
-            None
-        } else if errors.len() == 1 {
-            // Unwrap single error instead of wrapping
-            errors.into_iter().next()
-        } else {
-            Some(Self::Multiple { errors })
-        }
-    }
-
-    /// Creates an `InvalidProfile` error.
-    #[must_use]
-    pub fn invalid_profile(
-        profile: String,
-        var: &'static str,
-        valid_profiles: Vec<&'static str>,
+    /// Creates a `NotOneOf` error for a value outside a field's allowlist.
+    ///
+    /// As with [`Error::parse`], a secret value is discarded immediately.
+    pub fn not_one_of(
+        var: impl Into<String>,
+        value: impl Into<String>,
+        secret: bool,
+        allowed: &[&str],
     ) -> Self {
-        let valid_list = valid_profiles.join(", ");
+        let allowed: Vec<String> = allowed.iter().map(|&v| v.to_string()).collect();
+        let help = format!("set it to one of: {}", allowed.join(", "));
 
-        Self::InvalidProfile {
-            profile,
-            var,
-            help: format!("valid profiles are: {valid_list}"),
-            valid_profiles,
+        Self::NotOneOf {
+            var: var.into(),
+            value: MaybeRedacted::new(value, secret),
+            allowed,
+            help,
         }
     }
 
-    /// Creates a `KeyNotFound` error.
+    /// Creates an `InvalidLength` error for a `min_len`/`max_len` violation.
+    ///
+    /// Only the bounds are recorded, never the value.
+    pub fn invalid_length(var: impl Into<String>, min: Option<usize>, max: Option<usize>) -> Self {
+        let var = var.into();
+        let help = format!("check that {var} is complete and not truncated or padded");
+        Self::InvalidLength {
+            var,
+            min,
+            max,
+            help,
+        }
+    }
+
+    /// Creates a `RegexMismatch` error for a value failing `validate_regex`.
+    ///
+    /// As with [`Error::parse`], a secret value is discarded immediately.
+    pub fn regex_mismatch(
+        var: impl Into<String>,
+        value: impl Into<String>,
+        secret: bool,
+        pattern: impl Into<String>,
+    ) -> Self {
+        let pattern = pattern.into();
+        let help = format!("set it to a value matching /{pattern}/");
+
+        Self::RegexMismatch {
+            var: var.into(),
+            value: MaybeRedacted::new(value, secret),
+            pattern,
+            help,
+        }
+    }
+
+    /// Creates an `InvalidRegex` error for a pattern that failed to compile.
+    pub fn invalid_regex(
+        var: impl Into<String>,
+        pattern: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::InvalidRegex {
+            var: var.into(),
+            pattern: pattern.into(),
+            message: message.into(),
+            help: "fix the `validate_regex` pattern on the config struct".to_string(),
+        }
+    }
+
+    /// Creates a `FileVarRead` error for an unreadable `from_file_var` file.
+    pub fn file_var_read(
+        var: impl Into<String>,
+        path: impl Into<String>,
+        source: io::Error,
+    ) -> Self {
+        let var = var.into();
+        let help = format!("check that the file exists and is readable, or unset {var}");
+
+        Self::FileVarRead {
+            var,
+            path: path.into(),
+            help,
+            source,
+        }
+    }
+
+    /// Creates an `AfterParse` error for a failed `after_parse` hook.
+    ///
+    /// `source` is the hook's error; anything convertible to a boxed error
+    /// works, including `String` and `&str` messages.
+    pub fn after_parse(
+        var: impl Into<String>,
+        function: impl Into<String>,
+        source: impl Into<Box<dyn StdError + Send + Sync>>,
+    ) -> Self {
+        let var = var.into();
+        let help = format!("set {var} to a value that `after_parse` accepts");
+
+        Self::AfterParse {
+            var,
+            function: function.into(),
+            help,
+            source: source.into(),
+        }
+    }
+
+    /// Creates an `UndefinedDefaultVar` error for a `default` of `var` that
+    /// references the unset variable `reference`.
+    pub fn undefined_default_var(var: impl Into<String>, reference: impl Into<String>) -> Self {
+        let reference = reference.into();
+        let help = format!("set {reference}, or write `$${{{reference}}}` for a literal `${{`");
+
+        Self::UndefinedDefaultVar {
+            var: var.into(),
+            reference,
+            help,
+        }
+    }
+
+    /// Creates a `Parse` error for a float that parsed to NaN or infinity.
+    ///
+    /// Float fields reject non-finite values unless marked `allow_non_finite`.
+    pub fn non_finite(
+        var: impl Into<String>,
+        value: impl Into<String>,
+        secret: bool,
+        expected_type: impl Into<String>,
+    ) -> Self {
+        Self::parse(var, value, secret, expected_type, Box::new(NonFiniteError))
+    }
+
+    /// Labels `error` with the config struct it came from.
+    ///
+    /// An [`Error::Multiple`] is unwrapped, so the struct lists its errors
+    /// directly.
+    #[must_use]
+    pub fn in_struct(name: impl Into<String>, error: Self) -> Self {
+        let errors = match error {
+            Self::Multiple { errors } => errors,
+            error => vec![error],
+        };
+
+        Self::Struct {
+            name: name.into(),
+            errors,
+        }
+    }
+
+    /// Collects multiple errors into a single Multiple error.
+    /// Returns None if the input is empty.
+    #[must_use]
+    pub fn multiple(errors: Vec<Self>) -> Option<Self> {
+        if errors.is_empty() {
+            None
+        } else if errors.len() == 1 {
+            // Unwrap single error instead of wrapping
+            errors.into_iter().next()
+        } else {
+            Some(Self::Multiple { errors })
+        }
+    }
+
+    /// Creates an `InvalidProfile` error.
+    #[must_use]
+    pub fn invalid_profile(
+        profile: String,
+        var: &'static str,
+        valid_profiles: Vec<&'static str>,
+    ) -> Self {
+        let valid_list = valid_profiles.join(", ");
+
+        Self::InvalidProfile {
+            profile,
+            var,
+            help: format!("valid profiles are: {valid_list}"),
+            valid_profiles,
+        }
+    }
+
+    /// Creates a `DotenvMissing` error for a `dotenv_required` file.
+    pub fn dotenv_missing(path: impl Into<String>) -> Self {
+        let path = path.into();
+        let help =
+            format!("create or mount {path}, or use `dotenv = \"{path}\"` if the file is optional");
+        Self::DotenvMissing { path, help }
+    }
+
+    /// Creates a `Validation` error for a custom field validator.
+    ///
+    /// The message doubles as the diagnostic help; use
+    /// [`validation_with_help`](Self::validation_with_help) to suggest a fix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use procenv::Error;
+    ///
+    /// let err = Error::validation("port", "privileged ports are not allowed");
+    /// assert!(err.is_validation());
+    /// ```
+    pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Validation {
+            errors: vec![ValidationFieldError::new(
+                field,
+                ValidationFieldError::CUSTOM,
+                message,
+            )],
+        }
+    }
+
+    /// Creates a `Validation` error for a custom field validator with help text.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use procenv::Error;
+    ///
+    /// let err = Error::validation_with_help(
+    ///     "port",
+    ///     "privileged ports are not allowed",
+    ///     "use a port of 1024 or above",
+    /// );
+    /// assert!(err.is_validation());
+    /// ```
+    pub fn validation_with_help(
+        field: impl Into<String>,
+        message: impl Into<String>,
+        help: impl Into<String>,
+    ) -> Self {
+        Self::Validation {
+            errors: vec![
+                ValidationFieldError::new(field, ValidationFieldError::CUSTOM, message)
+                    .with_help(help),
+            ],
+        }
+    }
+
+    /// Creates a `KeyNotFound` error.
     pub fn key_not_found(key: impl Into<String>, available: Vec<String>) -> Self {
         let available_str = if available.is_empty() {
             "none".to_string()
@@ -763,6 +1419,474 @@ impl Error {
             help: format!("check that the config value is a valid {expected_type}"),
         }
     }
+
+    /// Creates an `Extraction` error for a `#[env(flatten, serde)]` field.
+    ///
+    /// Such fields are deserialized from config files only, which the help
+    /// text spells out since their nested env vars are silently not read.
+    pub fn file_only(
+        field: impl Into<String>,
+        expected_type: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        let field = field.into();
+
+        Self::Extraction {
+            help: format!(
+                "`{field}` is flattened with `serde`, so it is only read from config files \
+                 by `from_config()`; environment variables cannot set its fields"
+            ),
+            field,
+            expected_type: expected_type.into(),
+            message: message.into(),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Predicates for programmatic error handling
+// ─────────────────────────────────────────────────────────────────────────────
+
+impl Error {
+    /// Returns `true` if this is a [`Error::Missing`] error.
+    ///
+    /// A [`Error::Multiple`] is not itself missing; use
+    /// [`Error::missing_vars`] to look inside it.
+    ///
+    /// ```rust
+    /// use procenv::Error;
+    ///
+    /// assert!(Error::missing("DATABASE_URL").is_missing());
+    /// ```
+    #[must_use]
+    pub const fn is_missing(&self) -> bool {
+        matches!(self, Self::Missing { .. })
+    }
+
+    /// Returns `true` if this is a [`Error::Parse`] error.
+    ///
+    /// ```rust
+    /// use procenv::Error;
+    ///
+    /// let source = "x".parse::<u16>().unwrap_err();
+    /// let err = Error::parse("PORT", "x", false, "u16", Box::new(source));
+    /// assert!(err.is_parse());
+    /// assert!(!err.is_missing());
+    /// ```
+    #[must_use]
+    pub const fn is_parse(&self) -> bool {
+        matches!(self, Self::Parse { .. })
+    }
+
+    /// Returns `true` if this is a validation error.
+    ///
+    /// ```rust
+    /// use procenv::Error;
+    ///
+    /// assert!(!Error::missing("PORT").is_validation());
+    /// ```
+    #[must_use]
+    pub const fn is_validation(&self) -> bool {
+        matches!(self, Self::Validation { .. })
+    }
+
+    /// Returns `true` if this is a configuration file error.
+    ///
+    /// Always `false` without the `file` feature.
+    ///
+    /// ```rust
+    /// use procenv::Error;
+    ///
+    /// assert!(!Error::missing("PORT").is_file());
+    /// ```
+    #[must_use]
+    pub const fn is_file(&self) -> bool {
+        match self {
+            #[cfg(feature = "file")]
+            Self::File { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the names of all missing variables, looking inside
+    /// [`Error::Multiple`] and [`Error::Struct`].
+    ///
+    /// ```rust
+    /// use procenv::Error;
+    ///
+    /// let err = Error::multiple(vec![
+    ///     Error::missing("DATABASE_URL"),
+    ///     Error::dotenv_missing(".env.prod"),
+    ///     Error::missing("PORT"),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(err.missing_vars(), ["DATABASE_URL", "PORT"]);
+    /// ```
+    #[must_use]
+    pub fn missing_vars(&self) -> Vec<&str> {
+        match self {
+            Self::Missing { var, .. } => vec![var.as_str()],
+            Self::Multiple { errors } | Self::Struct { errors, .. } => {
+                errors.iter().flat_map(Self::missing_vars).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the first error of type `E` in this error's source chain.
+    ///
+    /// For [`Error::Parse`] this reaches the original `FromStr` error, so
+    /// callers can branch on the concrete failure:
+    ///
+    /// ```rust
+    /// use std::num::{IntErrorKind, ParseIntError};
+    ///
+    /// use procenv::Error;
+    ///
+    /// let source = "99999".parse::<u16>().unwrap_err();
+    /// let err = Error::parse("PORT", "99999", false, "u16", Box::new(source));
+    ///
+    /// let kind = err.source_downcast_ref::<ParseIntError>().map(ParseIntError::kind);
+    /// assert_eq!(kind, Some(&IntErrorKind::PosOverflow));
+    /// ```
+    #[must_use]
+    pub fn source_downcast_ref<E: StdError + 'static>(&self) -> Option<&E> {
+        let mut source = StdError::source(self);
+
+        while let Some(err) = source {
+            if let Some(found) = err.downcast_ref::<E>() {
+                return Some(found);
+            }
+            source = err.source();
+        }
+
+        None
+    }
+
+    /// Returns the offending value this error carries, if any.
+    #[cfg(feature = "regex")]
+    pub(crate) const fn value_mut(&mut self) -> Option<&mut MaybeRedacted> {
+        match self {
+            Self::InvalidUtf8 { preview: value, .. }
+            | Self::Parse { value, .. }
+            | Self::NotOneOf { value, .. }
+            | Self::RegexMismatch { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Accumulation across config structs
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Collects the errors of several config structs into one report.
+///
+/// Each struct's `from_env()` already reports all of its own problems at
+/// once; `Errors` does the same across structs. Every failure is kept as an
+/// [`Error::Struct`] naming the struct it came from, and
+/// [`finish()`](Self::finish) returns them together. [`load_all!`](crate::load_all)
+/// wraps this for the common case.
+///
+/// ```rust
+/// use procenv::{EnvConfig, Errors};
+///
+/// #[derive(EnvConfig)]
+/// struct AppConfig {
+///     #[env(var = "ERRORS_DOC_APP_PORT")]
+///     port: u16,
+/// }
+///
+/// #[derive(EnvConfig)]
+/// struct DbConfig {
+///     #[env(var = "ERRORS_DOC_DB_URL")]
+///     url: String,
+/// }
+///
+/// let mut errors = Errors::new();
+/// let app = errors.accumulate("AppConfig", AppConfig::from_env());
+/// let db = errors.accumulate("DbConfig", DbConfig::from_env());
+///
+/// let err = errors.finish().unwrap_err();
+/// assert!(app.is_none() && db.is_none());
+/// assert_eq!(err.missing_vars(), ["ERRORS_DOC_APP_PORT", "ERRORS_DOC_DB_URL"]);
+/// ```
+#[derive(Debug, Default)]
+#[must_use = "call `finish()` to report the collected errors"]
+pub struct Errors {
+    errors: Vec<Error>,
+}
+
+impl Errors {
+    /// Creates an empty collector.
+    pub const fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    /// Records the error of a struct named `name`, if loading it failed.
+    ///
+    /// Returns the loaded value, or `None` after recording the error.
+    pub fn accumulate<T>(
+        &mut self,
+        name: impl Into<String>,
+        result: Result<T, Error>,
+    ) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.errors.push(Error::in_struct(name, error));
+                None
+            }
+        }
+    }
+
+    /// Returns `true` if no errors have been recorded.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns every recorded error at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`Error::Struct`] of the one struct that failed, or an
+    /// [`Error::Multiple`] holding one per failed struct.
+    #[allow(clippy::result_large_err)]
+    pub fn finish(self) -> Result<(), Error> {
+        Error::multiple(self.errors).map_or(Ok(()), Err)
+    }
+}
+
+/// Loads several config structs with `from_env()`, reporting every
+/// struct's errors together.
+///
+/// Evaluates to `Result<(A, B, ...), Error>`. Every struct is loaded even
+/// after one fails; the error is built as by [`Errors`], so each struct's
+/// problems stay grouped under its name.
+///
+/// ```rust
+/// use procenv::EnvConfig;
+///
+/// #[derive(EnvConfig)]
+/// struct AppConfig {
+///     #[env(var = "LOAD_ALL_DOC_PORT", default = "8080")]
+///     port: u16,
+/// }
+///
+/// #[derive(EnvConfig)]
+/// struct DbConfig {
+///     #[env(var = "LOAD_ALL_DOC_POOL", default = "4")]
+///     pool: u32,
+/// }
+///
+/// let (app, db) = procenv::load_all!(AppConfig, DbConfig).unwrap();
+/// assert_eq!((app.port, db.pool), (8080, 4));
+/// ```
+#[macro_export]
+macro_rules! load_all {
+    ($($config:ty),+ $(,)?) => {{
+        let mut __errors = $crate::Errors::new();
+        $crate::load_all!(@load __errors [] $($config,)+)
+    }};
+
+    // Each step binds its result to a fresh (hygienic) `__loaded`
+    (@load $errors:ident [$($loaded:ident)*] $config:ty, $($rest:ty,)*) => {{
+        let __loaded = $errors.accumulate(::core::stringify!($config), <$config>::from_env());
+        $crate::load_all!(@load $errors [$($loaded)* __loaded] $($rest,)*)
+    }};
+
+    (@load $errors:ident [$($loaded:ident)*]) => {
+        $errors.finish().map(|()| {
+            ($($loaded.expect("finish() succeeded, so every struct loaded"),)*)
+        })
+    };
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Rendering
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Output format for [`Error::render`].
+///
+/// Chosen per struct with `#[env_config(error_format = "json")]`, which
+/// the generated `from_env_formatted()` renders errors with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// miette's graphical report, with help text and error codes.
+    #[default]
+    Pretty,
+
+    /// miette's JSON report, for log pipelines.
+    Json,
+}
+
+impl Error {
+    /// Renders this error as a string in `format`.
+    ///
+    /// ```rust
+    /// use procenv::{Error, ErrorFormat};
+    ///
+    /// let json = Error::missing("PORT").render(ErrorFormat::Json);
+    /// assert!(json.contains(r#""code": "procenv::missing_var""#));
+    /// ```
+    #[must_use]
+    pub fn render(&self, format: ErrorFormat) -> String {
+        let mut out = String::new();
+
+        let rendered = match format {
+            ErrorFormat::Pretty => {
+                miette::GraphicalReportHandler::new().render_report(&mut out, self)
+            }
+            ErrorFormat::Json => miette::JSONReportHandler::new().render_report(&mut out, self),
+        };
+
+        // Writing to a `String` only fails if a `Display` impl does
+        if rendered.is_err() {
+            return self.to_string();
+        }
+
+        out
+    }
+
+    /// Renders this error as miette's JSON report.
+    ///
+    /// Shorthand for `render(ErrorFormat::Json)`.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.render(ErrorFormat::Json)
+    }
+}
+
+/// Builds the help text for a [`Error::Parse`], special-casing common mistakes.
+fn parse_help(
+    expected_type: &str,
+    value: &str,
+    source: &(dyn StdError + Send + Sync + 'static),
+) -> String {
+    // Overflow/underflow is the common case for integers (e.g. PORT=99999),
+    // so spell out the range instead of echoing "number too large".
+    let out_of_range = source.downcast_ref::<ParseIntError>().is_some_and(|e| {
+        matches!(
+            e.kind(),
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow
+        )
+    });
+
+    if out_of_range && let Some(range) = integer_range(expected_type) {
+        return format!("expected {expected_type} ({range})");
+    }
+
+    // `NonZeroU16` parses "0" as a valid u16 first, then rejects it tersely
+    let zero = source
+        .downcast_ref::<ParseIntError>()
+        .is_some_and(|e| *e.kind() == IntErrorKind::Zero);
+
+    if zero && let Some(int) = non_zero_integer(expected_type) {
+        return format!("expected a non-zero {int}, got 0");
+    }
+
+    if source.is::<NonFiniteError>() {
+        return format!(
+            "expected a finite {expected_type}; \
+             add `allow_non_finite` to the field to accept NaN and infinity"
+        );
+    }
+
+    // `HOST=localhost:8080` fails because address types never resolve DNS.
+    // The help deliberately doesn't echo the host, which may be secret.
+    let short_type = expected_type
+        .rsplit("::")
+        .next()
+        .unwrap_or(expected_type)
+        .trim();
+    let host = match short_type {
+        "SocketAddr" | "SocketAddrV4" | "SocketAddrV6" => {
+            value.rsplit_once(':').map(|(host, _)| host)
+        }
+        "IpAddr" | "Ipv4Addr" | "Ipv6Addr" => Some(value),
+        _ => None,
+    };
+
+    if host.is_some_and(looks_like_hostname) {
+        return format!(
+            "{short_type} requires an IP address, not a hostname (e.g. 127.0.0.1); \
+             use a String host and a separate port field to configure a hostname"
+        );
+    }
+
+    format!("expected a valid {expected_type}")
+}
+
+/// Describes a `min_len`/`max_len` pair, e.g. "at least 32 characters long".
+fn length_constraint(min: Option<usize>, max: Option<usize>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) if min == max => format!("exactly {min} characters long"),
+        (Some(min), Some(max)) => format!("between {min} and {max} characters long"),
+        (Some(min), None) => format!("at least {min} characters long"),
+        (None, Some(max)) => format!("at most {max} characters long"),
+        (None, None) => "of any length".to_string(),
+    }
+}
+
+/// Maximum number of raw bytes shown in an [`Error::InvalidUtf8`] preview.
+const INVALID_UTF8_PREVIEW_BYTES: usize = 16;
+
+/// Help text for [`Error::InvalidUtf8`].
+const INVALID_UTF8_HELP: &str = "the value likely contains non-UTF-8 bytes, often from a shell \
+     or terminal with a misconfigured locale; re-export it as UTF-8 text";
+
+/// Source error for floats that parse successfully but are NaN or infinite.
+#[derive(Debug)]
+struct NonFiniteError;
+
+impl Display for NonFiniteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("value is not a finite number")
+    }
+}
+
+impl StdError for NonFiniteError {}
+
+/// Whether `host` looks like a DNS name rather than an IP literal.
+fn looks_like_hostname(host: &str) -> bool {
+    !host.is_empty()
+        && host.chars().any(|c| c.is_ascii_alphabetic())
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        && !host.starts_with(['-', '.'])
+}
+
+/// Returns the integer type behind a `NonZero` type name, e.g. `"u16"` for
+/// `NonZeroU16`, `std::num::NonZeroU16` or `NonZero<u16>`.
+fn non_zero_integer(type_name: &str) -> Option<String> {
+    let type_name: String = type_name.split_whitespace().collect();
+    let short_type = type_name.rsplit("::").next().unwrap_or(&type_name);
+
+    let int = match short_type.strip_prefix("NonZero<") {
+        Some(generic) => generic.strip_suffix('>')?.to_string(),
+        None => short_type.strip_prefix("NonZero")?.to_lowercase(),
+    };
+
+    integer_range(&int).map(|_| int)
+}
+
+/// Returns the valid range of a standard integer type, e.g. `"0..=65535"` for `u16`.
+fn integer_range(type_name: &str) -> Option<String> {
+    macro_rules! range {
+        ($($ty:ident),*) => {
+            match type_name {
+                $(stringify!($ty) => Some(format!("{}..={}", $ty::MIN, $ty::MAX)),)*
+                _ => None,
+            }
+        };
+    }
+
+    range!(
+        u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+    )
 }
 
 #[cfg(test)]
@@ -855,6 +1979,344 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_non_finite() {
+        let err = Error::non_finite("RATE", "inf", false, "f64");
+
+        let Error::Parse { help, source, .. } = &err else {
+            panic!("Expected Parse variant");
+        };
+        assert_eq!(
+            help,
+            "expected a finite f64; add `allow_non_finite` to the field to accept NaN and infinity"
+        );
+        assert_eq!(source.to_string(), "value is not a finite number");
+    }
+
+    #[test]
+    fn test_error_parse_integer_overflow_states_range() {
+        let source = "99999".parse::<u16>().unwrap_err();
+        let err = Error::parse("PORT", "99999", false, "u16", Box::new(source));
+
+        let Error::Parse { help, .. } = &err else {
+            panic!("Expected Parse variant");
+        };
+        assert_eq!(help, "expected u16 (0..=65535)");
+
+        let source = "-200".parse::<i8>().unwrap_err();
+        let err = Error::parse("OFFSET", "-200", false, "i8", Box::new(source));
+        assert!(format!("{err:?}").contains("expected i8 (-128..=127)"));
+    }
+
+    #[test]
+    fn test_error_parse_non_zero_zero() {
+        let source = "0".parse::<std::num::NonZeroU16>().unwrap_err();
+        let err = Error::parse("PORT", "0", false, "NonZeroU16", Box::new(source));
+
+        let Error::Parse { help, .. } = &err else {
+            panic!("Expected Parse variant");
+        };
+        assert_eq!(help, "expected a non-zero u16, got 0");
+
+        assert_eq!(
+            non_zero_integer("std :: num :: NonZero < i64 >").as_deref(),
+            Some("i64")
+        );
+        assert_eq!(non_zero_integer("NonZeroUsize").as_deref(), Some("usize"));
+        assert_eq!(non_zero_integer("NonZeroFoo"), None);
+        assert_eq!(non_zero_integer("u16"), None);
+    }
+
+    #[test]
+    fn test_error_parse_invalid_digit_keeps_generic_help() {
+        let source = "abc".parse::<u16>().unwrap_err();
+        let err = Error::parse("PORT", "abc", false, "u16", Box::new(source));
+
+        let Error::Parse { help, .. } = &err else {
+            panic!("Expected Parse variant");
+        };
+        assert_eq!(help, "expected a valid u16");
+    }
+
+    #[test]
+    fn test_error_parse_socket_addr_hostname_hint() {
+        let source = "localhost:8080"
+            .parse::<std::net::SocketAddr>()
+            .unwrap_err();
+        let err = Error::parse(
+            "BIND",
+            "localhost:8080",
+            false,
+            "SocketAddr",
+            Box::new(source),
+        );
+
+        let Error::Parse { help, .. } = &err else {
+            panic!("Expected Parse variant");
+        };
+        assert!(help.contains("IP address, not a hostname"));
+        assert!(help.contains("separate port"));
+
+        let source = "db.internal".parse::<std::net::IpAddr>().unwrap_err();
+        let err = Error::parse(
+            "DB_IP",
+            "db.internal",
+            true,
+            "std::net::IpAddr",
+            Box::new(source),
+        );
+        assert!(format!("{err:?}").contains("IpAddr requires an IP address"));
+        assert!(!format!("{err:?}").contains("db.internal"));
+    }
+
+    #[test]
+    fn test_error_parse_socket_addr_malformed_ip_keeps_generic_help() {
+        let source = "10.0.0:80".parse::<std::net::SocketAddr>().unwrap_err();
+        let err = Error::parse("BIND", "10.0.0:80", false, "SocketAddr", Box::new(source));
+
+        let Error::Parse { help, .. } = &err else {
+            panic!("Expected Parse variant");
+        };
+        assert_eq!(help, "expected a valid SocketAddr");
+    }
+
+    #[test]
+    fn test_error_invalid_utf8_preview() {
+        #[cfg(unix)]
+        let value = {
+            use std::os::unix::ffi::OsStrExt;
+            OsStr::from_bytes(b"caf\xe9")
+        };
+        #[cfg(not(unix))]
+        let value = OsStr::new("caf\u{fffd}");
+
+        let err = Error::invalid_utf8("NAME", value, false);
+        let Error::InvalidUtf8 { var, preview } = &err else {
+            panic!("Expected InvalidUtf8 variant");
+        };
+        assert_eq!(var, "NAME");
+        assert_eq!(preview.as_str(), Some("caf\u{fffd}"));
+        assert!(err.to_string().contains("NAME"));
+        assert!(format!("{err:?}").contains("locale"));
+        assert!(err.help().unwrap().to_string().contains("locale"));
+    }
+
+    #[test]
+    fn test_error_invalid_utf8_truncated_and_secret() {
+        let long = OsStr::new("abcdefghijklmnopqrstuvwxyz");
+        let err = Error::invalid_utf8("LONG", long, false);
+        let Error::InvalidUtf8 { preview, .. } = &err else {
+            panic!("Expected InvalidUtf8 variant");
+        };
+        assert_eq!(preview.as_str(), Some("abcdefghijklmnop…"));
+
+        let err = Error::invalid_utf8("TOKEN", OsStr::new("hunter2"), true);
+        assert!(!err.to_string().contains("hunter2"));
+        assert!(err.to_string().contains("<redacted>"));
+    }
+
+    #[test]
+    fn test_error_not_one_of() {
+        let err = Error::not_one_of("TIER", "gold", false, &["free", "pro"]);
+        assert!(err.to_string().contains("TIER"));
+        assert!(err.to_string().contains("gold"));
+
+        let debug = format!("{err:?}");
+        assert!(debug.contains("procenv::not_one_of"));
+        assert!(debug.contains("free, pro"));
+
+        let Error::NotOneOf { allowed, .. } = &err else {
+            panic!("Expected NotOneOf variant");
+        };
+        assert_eq!(allowed, &["free", "pro"]);
+    }
+
+    #[test]
+    fn test_error_invalid_length() {
+        let err = Error::invalid_length("API_KEY", Some(32), None);
+        assert_eq!(
+            err.to_string(),
+            "API_KEY must be at least 32 characters long"
+        );
+
+        let debug = format!("{err:?}");
+        assert!(debug.contains("procenv::invalid_length"));
+        assert!(debug.contains("required: at least 32 characters long"));
+
+        let err = Error::invalid_length("CODE", Some(4), Some(8));
+        assert_eq!(
+            err.to_string(),
+            "CODE must be between 4 and 8 characters long"
+        );
+
+        let err = Error::invalid_length("PIN", Some(6), Some(6));
+        assert!(err.to_string().ends_with("exactly 6 characters long"));
+    }
+
+    #[test]
+    fn test_error_regex_mismatch_and_invalid_regex() {
+        let err = Error::regex_mismatch("API_VERSION", "2", false, r"^v\d+$");
+        assert_eq!(
+            err.to_string(),
+            r#"API_VERSION has value "2", which does not match /^v\d+$/"#
+        );
+        assert!(format!("{err:?}").contains("procenv::regex_mismatch"));
+
+        let err = Error::regex_mismatch("TOKEN", "hunter2", true, "^tok_");
+        assert!(!format!("{err:?}").contains("hunter2"));
+        assert!(!err.to_string().contains("hunter2"));
+
+        let err = Error::invalid_regex("API_VERSION", "(v", "unclosed group");
+        let debug = format!("{err:?}");
+        assert!(debug.contains("procenv::invalid_regex"));
+        assert!(debug.contains("error: unclosed group"));
+    }
+
+    #[test]
+    fn test_error_file_var_read() {
+        let source = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
+        let err = Error::file_var_read("DB_PASSWORD_FILE", "/run/secrets/db", source);
+        assert_eq!(
+            err.to_string(),
+            "failed to read /run/secrets/db (named by DB_PASSWORD_FILE): not found"
+        );
+        assert!(format!("{err:?}").contains("procenv::file_var_read"));
+        assert!(StdError::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_error_after_parse() {
+        let err = Error::after_parse("BASE_URL", "normalize_url", "missing scheme");
+        assert_eq!(
+            err.to_string(),
+            "normalize_url rejected the value of BASE_URL: missing scheme"
+        );
+        assert!(format!("{err:?}").contains("procenv::after_parse"));
+        assert!(StdError::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_error_undefined_default_var() {
+        let err = Error::undefined_default_var("DATA_DIR", "HOME");
+        assert_eq!(
+            err.to_string(),
+            "default for DATA_DIR references unset variable ${HOME}"
+        );
+        assert!(format!("{err:?}").contains("procenv::undefined_default_var"));
+        assert!(format!("{err:?}").contains("`$${HOME}`"));
+    }
+
+    #[test]
+    fn test_error_source_downcast_ref() {
+        #[derive(Debug)]
+        struct Wrapped(ParseIntError);
+
+        impl Display for Wrapped {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "wrapped: {}", self.0)
+            }
+        }
+
+        impl StdError for Wrapped {
+            fn source(&self) -> Option<&(dyn StdError + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let source = "abc".parse::<u16>().unwrap_err();
+        let err = Error::parse("PORT", "abc", false, "u16", Box::new(source));
+        assert_eq!(
+            err.source_downcast_ref::<ParseIntError>()
+                .map(ParseIntError::kind),
+            Some(&IntErrorKind::InvalidDigit)
+        );
+        assert!(err.source_downcast_ref::<std::io::Error>().is_none());
+
+        // Walks past wrapping errors to the cause
+        let source = Wrapped("".parse::<u16>().unwrap_err());
+        let err = Error::parse("PORT", "", false, "u16", Box::new(source));
+        assert!(err.source_downcast_ref::<Wrapped>().is_some());
+        assert_eq!(
+            err.source_downcast_ref::<ParseIntError>()
+                .map(ParseIntError::kind),
+            Some(&IntErrorKind::Empty)
+        );
+
+        assert!(
+            Error::missing("PORT")
+                .source_downcast_ref::<std::io::Error>()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_error_not_one_of_secret_redacted() {
+        let err = Error::not_one_of("MODE", "hunter2", true, &["a", "b"]);
+        assert!(!err.to_string().contains("hunter2"));
+        assert!(!format!("{err:?}").contains("hunter2"));
+    }
+
+    #[test]
+    fn test_error_predicates() {
+        let missing = Error::missing("PORT");
+        assert!(missing.is_missing());
+        assert!(!missing.is_parse());
+        assert!(!missing.is_validation());
+        assert!(!missing.is_file());
+
+        let parse = Error::non_finite("RATIO", "NaN", false, "f64");
+        assert!(parse.is_parse());
+        assert!(parse.missing_vars().is_empty());
+
+        let nested = Error::Multiple {
+            errors: vec![
+                Error::missing("A"),
+                parse,
+                Error::Multiple {
+                    errors: vec![Error::missing("B")],
+                },
+            ],
+        };
+        assert!(!nested.is_missing());
+        assert_eq!(nested.missing_vars(), ["A", "B"]);
+    }
+
+    #[test]
+    fn test_error_validation_constructors() {
+        let err = Error::validation("port", "too low");
+        let Error::Validation { errors } = &err else {
+            panic!("expected Validation error");
+        };
+        assert_eq!(errors[0].field, "port");
+        assert_eq!(errors[0].code, ValidationFieldError::CUSTOM);
+        assert_eq!(errors[0].help().unwrap().to_string(), "too low");
+
+        let err = Error::validation_with_help("port", "too low", "use 1024+");
+        let Error::Validation { errors } = &err else {
+            panic!("expected Validation error");
+        };
+        assert_eq!(
+            errors[0].code().unwrap().to_string(),
+            crate::diagnostic_codes::CUSTOM_VALIDATION_ERROR
+        );
+        assert_eq!(errors[0].help().unwrap().to_string(), "use 1024+");
+
+        // Built-in rules keep the generic field code
+        let builtin = ValidationFieldError::new("email", "email", "invalid email");
+        assert_eq!(
+            builtin.code().unwrap().to_string(),
+            crate::diagnostic_codes::FIELD_VALIDATION_ERROR
+        );
+    }
+
+    #[test]
+    fn test_error_dotenv_missing() {
+        let err = Error::dotenv_missing(".env.prod");
+        assert_eq!(err.to_string(), "required dotenv file not found: .env.prod");
+        assert!(format!("{err:?}").contains("procenv::dotenv_missing"));
+        assert!(matches!(err, Error::DotenvMissing { ref path, .. } if path == ".env.prod"));
+    }
+
     #[test]
     fn test_maybe_redacted_plain() {
         let plain = MaybeRedacted::new("visible", false);
@@ -872,6 +2334,24 @@ mod tests {
         assert!(format!("{secret:?}").contains("<redacted>"));
     }
 
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_maybe_redacted_partial() {
+        let pattern = regex::Regex::new(r"key=(\w+)").unwrap();
+        let masked = MaybeRedacted::partial("key=abc&x=1&key=def", &pattern);
+        assert_eq!(masked.as_str(), Some("key=<redacted>&x=1&key=<redacted>"));
+
+        // Without groups the whole match is masked
+        let pattern = regex::Regex::new(r"\d{4}").unwrap();
+        let masked = MaybeRedacted::partial("card 1234 5678", &pattern);
+        assert_eq!(masked.as_str(), Some("card <redacted> <redacted>"));
+
+        // Nested groups mask the outer one once
+        let pattern = regex::Regex::new(r":((\w+))@").unwrap();
+        let masked = MaybeRedacted::partial("u:pw@h", &pattern);
+        assert_eq!(masked.as_str(), Some("u:<redacted>@h"));
+    }
+
     #[test]
     fn test_error_multiple() {
         let errors = vec![Error::missing("VAR1"), Error::missing("VAR2")];
@@ -898,4 +2378,41 @@ mod tests {
         let result = Error::multiple(vec![]);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_error_in_struct_unwraps_multiple() {
+        let inner = Error::multiple(vec![Error::missing("A"), Error::missing("B")]).unwrap();
+        let err = Error::in_struct("AppConfig", inner);
+
+        let Error::Struct { name, errors } = &err else {
+            panic!("Expected Struct variant");
+        };
+        assert_eq!(name, "AppConfig");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            err.to_string(),
+            "AppConfig: 2 configuration error(s) occurred"
+        );
+        assert_eq!(err.missing_vars(), ["A", "B"]);
+    }
+
+    #[test]
+    fn test_errors_accumulate() {
+        let mut errors = Errors::new();
+        assert_eq!(errors.accumulate("Ok", Ok::<_, Error>(1)), Some(1));
+        assert!(errors.is_empty());
+
+        let failed: Option<u8> = errors.accumulate("AppConfig", Err(Error::missing("A")));
+        assert_eq!(failed, None);
+        assert!(!errors.is_empty());
+
+        // One failed struct is returned on its own
+        let err = errors.finish().unwrap_err();
+        assert!(matches!(&err, Error::Struct { name, .. } if name == "AppConfig"));
+    }
+
+    #[test]
+    fn test_errors_finish_empty_is_ok() {
+        assert!(Errors::new().finish().is_ok());
+    }
 }
@@ -8,9 +8,35 @@ use serde_json as SJSON;
 use crate::Error;
 
 use super::error::FileError;
-use super::origin::OriginTracker;
+use super::format::FileFormat;
+use super::origin::{OriginTracker, ValueOrigin};
 use super::utils::FileUtils;
 
+/// Predicate deciding which prefixed env vars are merged.
+type EnvFilter = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
+type SecretCheck = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// The version check set by [`ConfigBuilder::schema_version`].
+struct SchemaVersion {
+    /// Dotted path of the version key in the merged value
+    key: String,
+    /// Newest version the caller understands
+    supported: u64,
+    /// Version assumed when the key is absent
+    missing: u64,
+}
+
+/// A file supplying one field, set by [`ConfigBuilder::field_file`].
+struct FieldFile {
+    /// Dotted path of the field in the merged value
+    field: String,
+    /// File to read
+    path: PathBuf,
+    /// Dotted path of the value inside the file
+    key: String,
+}
+
 /// Builder for layered configuration loading.
 ///
 /// `ConfigBuilder` provides a fluent API for loading configuration from
@@ -20,8 +46,16 @@ use super::utils::FileUtils;
 /// # Layering Order
 ///
 /// 1. **Defaults** - Initial values set via [`defaults()`](Self::defaults)
-/// 2. **Config files** - Added via [`file()`](Self::file) or [`file_optional()`](Self::file_optional)
-/// 3. **Environment variables** - Filtered by [`env_prefix()`](Self::env_prefix)
+/// 2. **Low values** - Programmatic layers added via [`merge_value_low()`](Self::merge_value_low)
+/// 3. **Embedded config** - Compiled-in content added via [`embedded()`](Self::embedded)
+/// 4. **Config files** - Added via [`file()`](Self::file) or [`file_optional()`](Self::file_optional),
+///    then per-field files added via [`field_file()`](Self::field_file)
+/// 5. **Values** - Programmatic layers added via [`merge_value()`](Self::merge_value)
+/// 6. **Environment variables** - Filtered by [`env_prefix()`](Self::env_prefix)
+///
+/// [`files_override_env()`](Self::files_override_env) moves environment
+/// variables below layers 4-5, so files win instead. Embedded config stays
+/// below the environment.
 ///
 /// # Example
 ///
@@ -65,14 +99,48 @@ use super::utils::FileUtils;
 /// ```
 ///
 /// The result will have `database.host = "localhost"` and `database.port = 5433`.
+///
+/// # Loaded Files
+///
+/// [`merge()`](Self::merge) and [`build_with_origins()`](Self::build_with_origins)
+/// consume the builder, so the files a merge actually read are reported on the
+/// [`OriginTracker`] they return rather than on the builder:
+/// [`loaded_files()`](OriginTracker::loaded_files) lists them in load order and
+/// [`skipped_files()`](OriginTracker::skipped_files) lists the missing
+/// optional ones. Derived structs expose the same list as `config_loaded_files()`.
+///
+/// ```rust,ignore
+/// let (config, origins) = ConfigBuilder::new()
+///     .file_optional("config.toml")
+///     .file_optional("config.prod.toml")
+///     .build_with_origins::<Config>()?;
+///
+/// // loaded: ["config.toml"], skipped: ["config.prod.toml"]
+/// println!("loaded: {:?}, skipped: {:?}", origins.loaded_files(), origins.skipped_files());
+/// ```
 pub struct ConfigBuilder {
     base: SJSON::Value,
+    embedded: Vec<(String, FileFormat)>,
     files: Vec<(PathBuf, bool)>,
+    /// Files each supplying a single field, layered over `files`
+    field_files: Vec<FieldFile>,
+    /// Values merged below the embedded and file layers
+    low_values: Vec<SJSON::Value>,
+    /// Values merged above the file layers
+    values: Vec<SJSON::Value>,
     env_prefix: Option<String>,
     env_separator: String,
+    /// Predicate on prefix-stripped env var names; `None` accepts all
+    env_filter: Option<EnvFilter>,
     origins: OriginTracker,
     /// Direct field-to-env-var mappings for custom var names (`field_path`, `env_var`)
     env_mappings: Vec<(String, String)>,
+    /// Apply environment variables before (rather than after) file layers
+    files_override_env: bool,
+    /// Predicate on dotted field paths whose values are kept out of errors
+    secret_fields: Option<SecretCheck>,
+    /// Reject merged values declaring a newer schema version
+    schema_version: Option<SchemaVersion>,
 }
 
 impl Default for ConfigBuilder {
@@ -87,11 +155,19 @@ impl ConfigBuilder {
     pub fn new() -> Self {
         Self {
             base: SJSON::Value::Object(SJSON::Map::new()),
+            embedded: Vec::new(),
             files: Vec::new(),
+            field_files: Vec::new(),
+            low_values: Vec::new(),
+            values: Vec::new(),
             env_prefix: None,
             env_separator: "_".to_string(),
+            env_filter: None,
             origins: OriginTracker::new(),
             env_mappings: Vec::new(),
+            files_override_env: false,
+            secret_fields: None,
+            schema_version: None,
         }
     }
 
@@ -183,6 +259,31 @@ impl ConfigBuilder {
         self
     }
 
+    /// Adds an embedded configuration string.
+    ///
+    /// Embedded content is merged above the defaults but below every config
+    /// file, so runtime files and environment variables still override it.
+    /// Pair it with `include_str!` to ship defaults inside the binary.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The configuration content
+    /// * `format` - The format of `content`
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let builder = ConfigBuilder::new()
+    ///     .embedded(include_str!("../config.default.toml"), FileFormat::Toml)
+    ///     .file_optional("config.toml");
+    /// ```
+    #[must_use]
+    pub fn embedded(mut self, content: &str, format: FileFormat) -> Self {
+        self.embedded.push((content.to_string(), format));
+
+        self
+    }
+
     /// Adds a required configuration file.
     ///
     /// If this file does not exist, [`build()`](Self::build) will return
@@ -228,6 +329,73 @@ impl ConfigBuilder {
         self
     }
 
+    /// Reads one field from `key` of its own required file.
+    ///
+    /// For layouts where a single value, such as a license or a secret
+    /// managed by another system, lives apart from the main config. The
+    /// dotted `key` is read from `path` and replaces `field_path` after the
+    /// config files are merged, so it wins over them but not over the
+    /// environment. A file without `key` leaves the field to the other
+    /// layers.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let builder = ConfigBuilder::new()
+    ///     .file("config.toml")
+    ///     .field_file("license", "/etc/app/license.json", "key")
+    ///     .env_prefix("APP_");  // APP_LICENSE still wins
+    /// ```
+    #[must_use]
+    pub fn field_file<P: AsRef<Path>>(
+        mut self,
+        field_path: impl Into<String>,
+        path: P,
+        key: impl Into<String>,
+    ) -> Self {
+        self.field_files.push(FieldFile {
+            field: field_path.into(),
+            path: path.as_ref().to_path_buf(),
+            key: key.into(),
+        });
+
+        self
+    }
+
+    /// Deep-merges a value above the config files but below the environment.
+    ///
+    /// Use this to inject configuration computed at runtime (for example,
+    /// fetched from a remote service) without implementing a
+    /// [`Provider`](crate::provider::Provider). Values are merged in the
+    /// order added, and their keys are attributed to the synthetic source
+    /// `<merge_value>`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let builder = ConfigBuilder::new()
+    ///     .file_optional("config.toml")
+    ///     .merge_value(serde_json::json!({ "pool": { "size": 32 } }))
+    ///     .env_prefix("APP_");  // APP_POOL_SIZE still wins
+    /// ```
+    #[must_use]
+    pub fn merge_value(mut self, value: SJSON::Value) -> Self {
+        self.values.push(value);
+
+        self
+    }
+
+    /// Deep-merges a value above the defaults but below embedded config and files.
+    ///
+    /// Like [`merge_value()`](Self::merge_value), but every config file
+    /// overrides it. Keys are attributed to `<merge_value_low>`.
+    #[must_use]
+    pub fn merge_value_low(mut self, value: SJSON::Value) -> Self {
+        self.low_values.push(value);
+
+        self
+    }
+
     /// Sets the environment variable prefix for overlay.
     ///
     /// Only environment variables starting with this prefix will be
@@ -255,6 +423,11 @@ impl ConfigBuilder {
     ///
     /// With separator "_" and prefix "APP_":
     /// - `APP_DATABASE_HOST` becomes `database.host`
+    ///
+    /// Multi-character separators split only on the exact string. With
+    /// separator "__" and prefix "APP":
+    /// - `APP__DB__MAX_CONNECTIONS` becomes `db.max_connections`
+    /// - `APP__DATABASE_URL` becomes `database_url`
     #[must_use]
     pub fn env_separator(mut self, separator: impl Into<String>) -> Self {
         self.env_separator = separator.into();
@@ -262,6 +435,61 @@ impl ConfigBuilder {
         self
     }
 
+    /// Only merge prefixed environment variables accepted by `filter`.
+    ///
+    /// The filter sees each variable name with the prefix already stripped,
+    /// in its original case. Rejected variables are left out of the merge,
+    /// which lets another subsystem (such as a secrets provider) own part of
+    /// the prefix. Direct [`env_mapping`](Self::env_mapping)s are not
+    /// filtered. Calling this again replaces the previous filter.
+    ///
+    /// # Example
+    ///
+    /// With prefix `"APP_"` and a filter rejecting `SECRET_*`:
+    /// - `APP_PORT` → `port`
+    /// - `APP_SECRET_TOKEN` → ignored
+    ///
+    /// ```rust,ignore
+    /// let config: MyConfig = ConfigBuilder::new()
+    ///     .file("config.toml")
+    ///     .env_prefix("APP_")
+    ///     .env_filter(|key| !key.starts_with("SECRET_"))
+    ///     .build()?;
+    /// ```
+    #[must_use]
+    pub fn env_filter(mut self, filter: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        self.env_filter = Some(Box::new(filter));
+
+        self
+    }
+
+    /// Mark fields whose values must not appear in errors.
+    ///
+    /// `is_secret` receives dotted field paths such as `"database.password"`.
+    /// When one of them fails to deserialize, the [`build()`](Self::build)
+    /// error points at the field but shows `<redacted>` in place of the
+    /// file line's value, and leaves out the deserializer's message, which
+    /// can quote the value. Derived `from_config()` errors never include
+    /// file snippets, so this only matters for direct builder use.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let config: MyConfig = ConfigBuilder::new()
+    ///     .file("config.toml")
+    ///     .secret_fields(|path| path == "database.password")
+    ///     .build()?;
+    /// ```
+    #[must_use]
+    pub fn secret_fields(
+        mut self,
+        is_secret: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.secret_fields = Some(Box::new(is_secret));
+
+        self
+    }
+
     /// Register a direct mapping from a field path to an environment variable.
     ///
     /// This allows overriding specific fields with custom environment variables
@@ -293,6 +521,55 @@ impl ConfigBuilder {
         self
     }
 
+    /// Lets config files take precedence over environment variables.
+    ///
+    /// By default environment variables are the top layer. When enabled, the
+    /// environment overlay (prefix and direct mappings) is applied right after
+    /// the embedded content, so config files and
+    /// [`merge_value()`](Self::merge_value) layers override it while compiled-in
+    /// defaults still don't. Use this for pinned configuration that the
+    /// environment must not change.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let builder = ConfigBuilder::new()
+    ///     .env_prefix("APP_")
+    ///     .file("/etc/app/pinned.toml")
+    ///     .files_override_env(true);  // pinned.toml beats APP_* vars
+    /// ```
+    #[must_use]
+    pub const fn files_override_env(mut self, enabled: bool) -> Self {
+        self.files_override_env = enabled;
+
+        self
+    }
+
+    /// Rejects configurations whose schema version is newer than `supported`.
+    ///
+    /// After merging, the dotted `key` is read from the result as a whole
+    /// number; a config without it counts as version `missing`. A version
+    /// above `supported` fails with [`FileError::UnsupportedSchemaVersion`],
+    /// so a binary never half-understands a config written for a later one.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let builder = ConfigBuilder::new()
+    ///     .file("config.toml")
+    ///     .schema_version("version", 2, 1);  // accepts versions 0-2, absent = 1
+    /// ```
+    #[must_use]
+    pub fn schema_version(mut self, key: impl Into<String>, supported: u64, missing: u64) -> Self {
+        self.schema_version = Some(SchemaVersion {
+            key: key.into(),
+            supported,
+            missing,
+        });
+
+        self
+    }
+
     /// Merges all configuration sources and returns the raw JSON value.
     ///
     /// This is a lower-level method that returns the merged JSON value
@@ -303,12 +580,90 @@ impl ConfigBuilder {
     ///
     /// A tuple of:
     /// - The merged JSON value
-    /// - An [`OriginTracker`] with source information for each path
+    /// - An [`OriginTracker`] with source information for each path, including
+    ///   which files were [loaded](OriginTracker::loaded_files) or
+    ///   [skipped](OriginTracker::skipped_files)
     ///
     /// # Errors
     ///
-    /// Returns a [`FileError`] if a required file is missing or cannot be parsed.
+    /// Returns a [`FileError`] if a required file is missing or cannot be parsed,
+    /// or if the result fails the [`schema_version()`](Self::schema_version) check.
     pub fn merge(mut self) -> Result<(SJSON::Value, OriginTracker), FileError> {
+        self.merge_embedded()?;
+
+        if self.files_override_env {
+            self.merge_env();
+            self.merge_files()?;
+        } else {
+            self.merge_files()?;
+            self.merge_env();
+        }
+
+        self.check_schema_version()?;
+
+        Ok((self.base, self.origins))
+    }
+
+    /// Checks the merged value's schema version, if one was requested.
+    fn check_schema_version(&self) -> Result<(), FileError> {
+        let Some(schema) = &self.schema_version else {
+            return Ok(());
+        };
+
+        let value = schema
+            .key
+            .split('.')
+            .try_fold(&self.base, |value, part| value.get(part));
+
+        let version = match value {
+            None => Some(schema.missing),
+            Some(SJSON::Value::Number(n)) => n.as_u64(),
+            // Quoted in a file, or an env var that didn't coerce to a number
+            Some(SJSON::Value::String(s)) => s.parse().ok(),
+            Some(_) => None,
+        };
+
+        let Some(version) = version else {
+            return Err(FileError::InvalidSchemaVersion {
+                key: schema.key.clone(),
+                value: value.map(ToString::to_string).unwrap_or_default(),
+                supported: schema.supported,
+            });
+        };
+
+        if version > schema.supported {
+            return Err(FileError::UnsupportedSchemaVersion {
+                key: schema.key.clone(),
+                version,
+                supported: schema.supported,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Layers low merged values and embedded content onto the base value.
+    fn merge_embedded(&mut self) -> Result<(), FileError> {
+        for value in std::mem::take(&mut self.low_values) {
+            self.merge_value_layer("<merge_value_low>", value);
+        }
+
+        // Layer embedded content (lowest file layer)
+        for (content, format) in std::mem::take(&mut self.embedded) {
+            let embedded_value = FileUtils::parse_str(&content, format)?;
+
+            self.origins
+                .add_source("<embedded>".to_string(), content, format);
+            self.origins.track_value(&embedded_value, "");
+
+            FileUtils::deep_merge(&mut self.base, embedded_value);
+        }
+
+        Ok(())
+    }
+
+    /// Layers config files and merged values onto the base value.
+    fn merge_files(&mut self) -> Result<(), FileError> {
         // Layer files
         for (path, required) in self.files.clone() {
             if let Some((file_value, content, format)) =
@@ -318,14 +673,83 @@ impl ConfigBuilder {
                 self.origins
                     .add_source(path.display().to_string(), content, format);
                 self.origins.track_value(&file_value, "");
+                self.origins.loaded_files.push(path);
 
                 FileUtils::deep_merge(&mut self.base, file_value);
+            } else {
+                // Only optional files can be skipped; missing required files error above
+                self.origins.skipped_files.push(path);
             }
         }
 
+        for field_file in std::mem::take(&mut self.field_files) {
+            self.merge_field_file(field_file)?;
+        }
+
+        for value in std::mem::take(&mut self.values) {
+            self.merge_value_layer("<merge_value>", value);
+        }
+
+        Ok(())
+    }
+
+    /// Replaces one field with the value at its file's key, if present.
+    fn merge_field_file(&mut self, field_file: FieldFile) -> Result<(), FileError> {
+        // Required, so a missing file errors instead of returning `None`
+        let Some((file_value, content, format)) =
+            FileUtils::parse_file_with_content(&field_file.path, true)?
+        else {
+            return Ok(());
+        };
+
+        let value = field_file
+            .key
+            .split('.')
+            .try_fold(&file_value, |value, part| value.get(part));
+
+        if let Some(value) = value {
+            // Tracked by path only, so the file never becomes the fallback
+            // origin for fields it doesn't supply
+            let source = ValueOrigin {
+                file_path: field_file.path.display().to_string(),
+                content,
+                format,
+            };
+            self.origins.track_path(&field_file.field, value, &source);
+
+            let parts: Vec<&str> = field_file.field.split('.').collect();
+            if let SJSON::Value::Object(ref mut map) = self.base {
+                FileUtils::insert_nested(map, &parts, value.clone());
+            }
+        }
+
+        self.origins.loaded_files.push(field_file.path);
+
+        Ok(())
+    }
+
+    /// Layers a programmatic value, tracking its keys under `source`.
+    fn merge_value_layer(&mut self, source: &str, value: SJSON::Value) {
+        // Pretty JSON gives type mismatch errors a line to point at
+        let content = SJSON::to_string_pretty(&value).unwrap_or_default();
+
+        self.origins
+            .add_source(source.to_string(), content, FileFormat::Json);
+        self.origins.track_value(&value, "");
+
+        FileUtils::deep_merge(&mut self.base, value);
+    }
+
+    /// Layers prefixed environment variables and direct env mappings onto the base value.
+    fn merge_env(&mut self) {
         // Layer environment variables using prefix/separator convention
         if let Some(prefix) = &self.env_prefix {
-            let env_value = FileUtils::env_to_value(prefix, &self.env_separator);
+            let env_value = match &self.env_filter {
+                Some(filter) => {
+                    FileUtils::env_to_value_filtered(prefix, &self.env_separator, filter)
+                }
+                None => FileUtils::env_to_value(prefix, &self.env_separator),
+            };
 
             if let SJSON::Value::Object(map) = &env_value
                 && !map.is_empty()
@@ -346,8 +770,6 @@ impl ConfigBuilder {
                 }
             }
         }
-
-        Ok((self.base, self.origins))
     }
 
     /// Merges all configuration sources and returns the raw JSON value.
@@ -428,34 +850,94 @@ impl ConfigBuilder {
     ///
     /// Returns an error if a required file is missing, a file has invalid syntax,
     /// or the merged configuration cannot be deserialized to `T`.
-    pub fn build_with_origins<T: DeserializeOwned>(self) -> Result<(T, OriginTracker), Error> {
+    pub fn build_with_origins<T: DeserializeOwned>(mut self) -> Result<(T, OriginTracker), Error> {
         use serde::de::IntoDeserializer;
 
+        let secret_fields = self.secret_fields.take();
         let (merged, origins) = self.merge()?;
 
         // Use serde_path_to_error to get exact path on failure
         let deserializer = merged.into_deserializer();
 
-        let result = serde_path_to_error::deserialize(deserializer).map_err(|e| {
-            let path = e.path().to_string();
-            let inner_msg = e.inner().to_string();
+        let result = serde_path_to_error::deserialize(deserializer)
+            .map_err(|e| Self::deserialize_error(&e, secret_fields.as_ref(), &origins))?;
 
-            // Try to find the origin and create a span error
-            if let Some(origin) = origins.find_origin(&path)
-                && let Some(file_error) = FileUtils::type_mismatch_error(&path, &inner_msg, origin)
-            {
-                return Error::from(file_error);
-            }
+        Ok((result, origins))
+    }
 
-            // Fallback to no span
-            Error::from(FileError::ParseNoSpan {
-                format: "JSON",
-                message: format!("at `{path}`: {inner_msg}"),
-                help: "check that the config file values match the expected types".to_string(),
-            })
-        })?;
+    /// Builds the configuration into an existing value.
+    ///
+    /// Equivalent to [`build()`](Self::build), but deserializes the merged
+    /// configuration into `target` via [`Deserialize::deserialize_in_place`]
+    /// so types that support it can reuse their existing allocations. This
+    /// is intended for reload paths that rebuild the same config repeatedly.
+    /// Types without an in-place implementation fall back to serde's default,
+    /// which deserializes a fresh value and replaces `*target`.
+    ///
+    /// [`Deserialize::deserialize_in_place`]: serde::Deserialize::deserialize_in_place
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`build()`](Self::build). If deserialization
+    /// fails, `target` may have been partially updated and should not be
+    /// relied upon.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use procenv::file::ConfigBuilder;
+    ///
+    /// let mut config = MyConfig::default();
+    /// ConfigBuilder::new()
+    ///     .file("config.toml")
+    ///     .build_into(&mut config)?;
+    /// ```
+    pub fn build_into<T: DeserializeOwned>(mut self, target: &mut T) -> Result<(), Error> {
+        use serde::de::IntoDeserializer;
 
-        Ok((result, origins))
+        let secret_fields = self.secret_fields.take();
+        let (merged, origins) = self.merge()?;
+
+        let mut track = serde_path_to_error::Track::new();
+        let deserializer =
+            serde_path_to_error::Deserializer::new(merged.into_deserializer(), &mut track);
+
+        T::deserialize_in_place(deserializer, target).map_err(|e| {
+            let e = serde_path_to_error::Error::new(track.path(), e);
+            Self::deserialize_error(&e, secret_fields.as_ref(), &origins)
+        })
+    }
+
+    /// Converts a path-tracked deserialization error into a [`Error`],
+    /// redacting secret values and attaching a source span when the
+    /// failing path can be traced back to a file.
+    fn deserialize_error(
+        e: &serde_path_to_error::Error<SJSON::Error>,
+        secret_fields: Option<&SecretCheck>,
+        origins: &OriginTracker,
+    ) -> Error {
+        let path = e.path().to_string();
+        let secret = secret_fields.is_some_and(|is_secret| is_secret(&path));
+        let inner_msg = if secret {
+            "invalid secret value (redacted)".to_string()
+        } else {
+            e.inner().to_string()
+        };
+
+        // Try to find the origin and create a span error
+        if let Some(origin) = origins.find_origin(&path)
+            && let Some(file_error) =
+                FileUtils::type_mismatch_error(&path, &inner_msg, origin, secret)
+        {
+            return Error::from(file_error);
+        }
+
+        // Fallback to no span
+        Error::from(FileError::ParseNoSpan {
+            format: "JSON",
+            message: format!("at `{path}`: {inner_msg}"),
+            help: "check that the config file values match the expected types".to_string(),
+        })
     }
 }
 
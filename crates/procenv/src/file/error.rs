@@ -131,4 +131,38 @@ pub enum FileError {
         #[help]
         help: String,
     },
+
+    /// Config declares a schema version newer than the binary understands
+    #[error("configuration schema version {version} is newer than supported version {supported}")]
+    #[diagnostic(
+        code(procenv::file::unsupported_schema_version),
+        help("upgrade the application, or write a config for version {supported} or lower")
+    )]
+    UnsupportedSchemaVersion {
+        /// The key holding the version (e.g., "version")
+        key: String,
+
+        /// The version the config declares
+        version: u64,
+
+        /// The newest version this binary supports
+        supported: u64,
+    },
+
+    /// The schema version key holds something other than a whole number
+    #[error("configuration schema version `{key}` must be a non-negative integer, found {value}")]
+    #[diagnostic(
+        code(procenv::file::invalid_schema_version),
+        help("set `{key}` to a whole number such as {supported}")
+    )]
+    InvalidSchemaVersion {
+        /// The key holding the version
+        key: String,
+
+        /// The offending value, as JSON
+        value: String,
+
+        /// The newest version this binary supports
+        supported: u64,
+    },
 }
@@ -157,6 +157,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normalize_content() {
+        assert_eq!(
+            FileUtils::normalize_content("\u{FEFF}a = 1\r\nb = 2\r\n"),
+            "a = 1\nb = 2\n"
+        );
+        assert!(matches!(
+            FileUtils::normalize_content("a = 1\n"),
+            std::borrow::Cow::Borrowed("a = 1\n")
+        ));
+    }
+
     #[test]
     fn test_deep_merge_objects() {
         let mut base = SJSON::json!({
@@ -187,6 +199,45 @@ mod tests {
         assert_eq!(base["a"], SJSON::json!([4, 5]));
     }
 
+    #[test]
+    fn test_deep_merge_indexed_array_elements() {
+        let mut base = SJSON::json!({"servers": [
+            {"host": "a", "port": 1},
+            {"host": "b", "port": 2}
+        ]});
+        let overlay = SJSON::json!({"servers": {
+            "1": {"host": "b2"},
+            "2": {"host": "c", "port": 3}
+        }});
+
+        FileUtils::deep_merge(&mut base, overlay);
+
+        assert_eq!(
+            base["servers"],
+            SJSON::json!([
+                {"host": "a", "port": 1},
+                {"host": "b2", "port": 2},
+                {"host": "c", "port": 3}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_deep_merge_indexed_array_gaps_and_bounds() {
+        let mut base = SJSON::json!({"a": [1]});
+        FileUtils::deep_merge(&mut base, SJSON::json!({"a": {"2": 3, "10": 11}}));
+        // "10" is out of range, so the whole overlay replaces the array
+        assert_eq!(base["a"], SJSON::json!({"2": 3, "10": 11}));
+
+        let mut base = SJSON::json!({"a": [1]});
+        FileUtils::deep_merge(&mut base, SJSON::json!({"a": {"0": 5, "2": 3}}));
+        assert_eq!(base["a"], SJSON::json!([5, null, 3]));
+
+        let mut base = SJSON::json!({"a": [1]});
+        FileUtils::deep_merge(&mut base, SJSON::json!({"a": {"x": 2}}));
+        assert_eq!(base["a"], SJSON::json!({"x": 2}));
+    }
+
     #[test]
     fn test_insert_nested() {
         let mut map = SJSON::Map::new();
@@ -204,6 +255,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_split_env_key_double_underscore() {
+        assert_eq!(
+            FileUtils::split_env_key("db__max_connections", "__"),
+            vec!["db", "max_connections"]
+        );
+        assert_eq!(
+            FileUtils::split_env_key("database_url", "__"),
+            vec!["database_url"]
+        );
+        // Leading separator left over from a prefix without a trailing separator
+        assert_eq!(
+            FileUtils::split_env_key("__db__host", "__"),
+            vec!["db", "host"]
+        );
+    }
+
+    #[test]
+    fn test_split_env_key_single_char() {
+        assert_eq!(
+            FileUtils::split_env_key("database_host", "_"),
+            vec!["database", "host"]
+        );
+        assert!(FileUtils::split_env_key("", "_").is_empty());
+    }
+
     #[test]
     fn test_file_format_detection() {
         assert_eq!(
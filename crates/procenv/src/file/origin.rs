@@ -79,6 +79,12 @@ pub struct OriginTracker {
 
     /// List of all source files in priority order (last = highest priority).
     pub(crate) sources: Vec<ValueOrigin>,
+
+    /// Config files that existed and were merged, in load order.
+    pub(crate) loaded_files: Vec<PathBuf>,
+
+    /// Optional config files that were skipped because they don't exist.
+    pub(crate) skipped_files: Vec<PathBuf>,
 }
 
 impl OriginTracker {
@@ -105,6 +111,15 @@ impl OriginTracker {
         self.track_value_recursive(value, prefix, &source);
     }
 
+    /// Record `path` and everything below it as coming from `source`.
+    ///
+    /// Unlike [`Self::track_value`], `source` is not added to the sources,
+    /// so it never becomes the fallback of [`Self::find_origin`].
+    pub(crate) fn track_path(&mut self, path: &str, value: &SJSON::Value, source: &ValueOrigin) {
+        self.origins.insert(path.to_string(), source.clone());
+        self.track_value_recursive(value, path, source);
+    }
+
     fn track_value_recursive(&mut self, value: &SJSON::Value, prefix: &str, source: &ValueOrigin) {
         match value {
             SJSON::Value::Object(map) => {
@@ -160,25 +175,37 @@ impl OriginTracker {
     /// Check if a field came from a configuration file.
     ///
     /// Returns the file path if the field was explicitly loaded from a file.
-    /// Does not return a fallback - only returns Some if the field is actually tracked.
+    /// Only exact paths count: a file that defines `[database]` but not
+    /// `database.port` does not claim `database.port`, so a nested default
+    /// for it is still reported as a default.
     #[must_use]
     pub fn get_file_source(&self, field_name: &str) -> Option<PathBuf> {
-        // Only return if we have an exact match or parent match in origins
-        // Do NOT use the fallback to most recent source
-        if let Some(origin) = self.origins.get(field_name) {
-            return Some(PathBuf::from(&origin.file_path));
-        }
+        self.origins
+            .get(field_name)
+            .map(|origin| PathBuf::from(&origin.file_path))
+    }
 
-        // Try parent paths (for nested fields)
-        let mut current = field_name.to_string();
-        while let Some(dot_pos) = current.rfind('.') {
-            current = current[..dot_pos].to_string();
-            if let Some(origin) = self.origins.get(&current) {
-                return Some(PathBuf::from(&origin.file_path));
-            }
-        }
+    /// Like [`get_file_source()`](Self::get_file_source), but only for
+    /// config files read from disk, so values from embedded content don't
+    /// count.
+    #[must_use]
+    pub fn get_loaded_file_source(&self, field_name: &str) -> Option<PathBuf> {
+        self.get_file_source(field_name)
+            .filter(|path| self.loaded_files.contains(path))
+    }
 
-        None
+    /// Config files that were actually loaded, in load order.
+    ///
+    /// Embedded content is not included since it has no path.
+    #[must_use]
+    pub fn loaded_files(&self) -> &[PathBuf] {
+        &self.loaded_files
+    }
+
+    /// Optional config files that were skipped because they don't exist.
+    #[must_use]
+    pub fn skipped_files(&self) -> &[PathBuf] {
+        &self.skipped_files
     }
 
     /// Check if any files were loaded.
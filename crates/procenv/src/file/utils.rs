@@ -2,7 +2,8 @@
 //!
 //! This module provides the [`FileUtils`] struct with static methods for:
 //!
-//! - **Parsing** - Reading and parsing configuration files (JSON, TOML, YAML)
+//! - **Parsing** - Reading and parsing configuration files (JSON, TOML, YAML),
+//!   tolerating a UTF-8 BOM and CRLF line endings
 //! - **Merging** - Deep merging JSON values with proper object recursion
 //! - **Coercion** - Converting string values to appropriate JSON types
 //! - **Error Location** - Finding field offsets for precise error reporting
@@ -44,6 +45,7 @@
 //! - `"3.14"` → `Number` (float, only if contains `.`)
 //! - Everything else → `String`
 
+use std::borrow::Cow;
 use std::path::Path;
 
 use miette::{NamedSource, SourceSpan};
@@ -58,6 +60,7 @@ use toml as TOML;
 use super::error::FileError;
 use super::format::FileFormat;
 use super::origin::ValueOrigin;
+use crate::runtime::REDACTED;
 
 /// Utilities for file parsing and value manipulation.
 ///
@@ -336,18 +339,41 @@ impl FileUtils {
     }
 
     /// Create a type mismatch error with source location.
+    ///
+    /// For `secret` fields the rest of the value's line is replaced with
+    /// [`REDACTED`] in the snippet; `message` must not quote the value.
     pub(crate) fn type_mismatch_error(
         path: &str,
         message: &str,
         origin: &ValueOrigin,
+        secret: bool,
     ) -> Option<FileError> {
         let offset = Self::find_field_offset(&origin.content, path, origin.format)?;
 
+        let (content, span) = if secret {
+            let offset = Self::floor_char_boundary(&origin.content, offset);
+            let line_end = origin.content[offset..]
+                .find('\n')
+                .map_or(origin.content.len(), |end| offset + end);
+            let content = format!(
+                "{}{REDACTED}{}",
+                &origin.content[..offset],
+                &origin.content[line_end..]
+            );
+
+            (content, SourceSpan::new(offset.into(), REDACTED.len()))
+        } else {
+            (
+                origin.content.clone(),
+                Self::offset_to_span(offset, &origin.content),
+            )
+        };
+
         Some(FileError::TypeMismatch {
             path_str: path.into(),
             file_path: origin.file_path.clone(),
-            src: NamedSource::new(origin.file_path.clone(), origin.content.clone()),
-            span: Self::offset_to_span(offset, &origin.content),
+            src: NamedSource::new(origin.file_path.clone(), content),
+            span,
             message: message.to_string(),
             help: "check that the value matches the expected type".to_string(),
         })
@@ -412,6 +438,7 @@ impl FileUtils {
             path: path_str.clone(),
             source: e,
         })?;
+        let content = Self::normalize_content(&content).into_owned();
 
         let format = FileFormat::from_path(path).ok_or_else(|| FileError::UnknownFormat {
             extension: path
@@ -468,6 +495,7 @@ impl FileUtils {
     /// let value = FileUtils::parse_str(toml_content, FileFormat::Toml)?;
     /// ```
     pub fn parse_str(content: &str, format: FileFormat) -> Result<SJSON::Value, FileError> {
+        let content = &*Self::normalize_content(content);
         let dummy_path = Path::new("<string>");
         match format {
             FileFormat::Json => serde_json::from_str(content)
@@ -486,6 +514,22 @@ impl FileUtils {
         }
     }
 
+    /// Strips a leading UTF-8 BOM and converts CRLF line endings to LF.
+    ///
+    /// Files saved on Windows or by editors that write a BOM would otherwise
+    /// have `\u{FEFF}` glued to the first key and `\r` left in multi-line
+    /// strings. Error spans refer to the normalized content.
+    #[must_use]
+    pub fn normalize_content(content: &str) -> Cow<'_, str> {
+        let content = content.strip_prefix('\u{FEFF}').unwrap_or(content);
+
+        if content.contains("\r\n") {
+            Cow::Owned(content.replace("\r\n", "\n"))
+        } else {
+            Cow::Borrowed(content)
+        }
+    }
+
     // ============================================================================
     // Format Conversion
     // ============================================================================
@@ -529,6 +573,12 @@ impl FileUtils {
     /// For objects, keys from `overlay` are recursively merged into `base`.
     /// For all other types (arrays, primitives), `overlay` replaces `base`.
     ///
+    /// The exception is an object with only numeric keys merged into an
+    /// array, which is what an indexed env var like `APP_SERVERS_0_HOST`
+    /// produces. Each key merges into the element at that index, so elements
+    /// no env var mentions are kept. Indices past the end append elements,
+    /// filling any gap with `null`.
+    ///
     /// # Arguments
     ///
     /// * `base` - The base value (modified in place)
@@ -562,12 +612,40 @@ impl FileUtils {
                     }
                 }
             }
+            (SJSON::Value::Array(base_arr), SJSON::Value::Object(overlay_map))
+                if Self::is_index_map(&overlay_map, base_arr.len()) =>
+            {
+                let mut indexed: Vec<(usize, SJSON::Value)> = overlay_map
+                    .into_iter()
+                    .filter_map(|(key, value)| Some((key.parse().ok()?, value)))
+                    .collect();
+                indexed.sort_by_key(|(index, _)| *index);
+
+                for (index, overlay_value) in indexed {
+                    if index >= base_arr.len() {
+                        base_arr.resize(index + 1, SJSON::Value::Null);
+                    }
+                    Self::deep_merge(&mut base_arr[index], overlay_value);
+                }
+            }
             (base, overlay) => {
                 *base = overlay;
             }
         }
     }
 
+    /// Whether every key of `map` is an index that can merge into an array.
+    ///
+    /// Indices may run past `len` by at most the number of keys, which bounds
+    /// how far a stray env var like `APP_SERVERS_99999_HOST` can grow the array.
+    fn is_index_map(map: &SJSON::Map<String, SJSON::Value>, len: usize) -> bool {
+        !map.is_empty()
+            && map.keys().all(|key| {
+                key.parse::<usize>()
+                    .is_ok_and(|index| index < len + map.len())
+            })
+    }
+
     // ============================================================================
     // Environment Variable Conversion
     // ============================================================================
@@ -618,14 +696,35 @@ impl FileUtils {
     }
 
     /// Convert environment variables to a nested JSON Value.
+    ///
+    /// Variable names are split on the exact `separator` string, so a
+    /// multi-character separator such as `"__"` nests on `APP__DB__HOST`
+    /// while leaving single underscores in `APP__DATABASE_URL` intact.
+    ///
+    /// Numeric segments such as the `0` in `APP_SERVERS_0_HOST` become object
+    /// keys here; [`deep_merge`](Self::deep_merge) applies them to the element
+    /// at that index when the base value is an array.
     #[must_use]
     pub fn env_to_value(prefix: &str, separator: &str) -> SJSON::Value {
+        Self::env_to_value_filtered(prefix, separator, |_| true)
+    }
+
+    /// Like [`env_to_value`](Self::env_to_value), but only includes variables
+    /// whose prefix-stripped name (original case) passes `filter`.
+    #[must_use]
+    pub fn env_to_value_filtered(
+        prefix: &str,
+        separator: &str,
+        filter: impl Fn(&str) -> bool,
+    ) -> SJSON::Value {
         let mut root = serde_json::Map::new();
 
         for (key, value) in std::env::vars() {
-            if let Some(stripped) = key.strip_prefix(prefix) {
+            if let Some(stripped) = key.strip_prefix(prefix)
+                && filter(stripped)
+            {
                 let lowered = stripped.to_lowercase();
-                let parts: Vec<&str> = lowered.split(separator).collect();
+                let parts = Self::split_env_key(&lowered, separator);
                 let typed_value = Self::coerce_value(&value);
                 Self::insert_nested(&mut root, &parts, typed_value);
             }
@@ -634,6 +733,33 @@ impl FileUtils {
         SJSON::Value::Object(root)
     }
 
+    /// Split a prefix-stripped environment key into nested path segments.
+    ///
+    /// Splits only on the exact `separator` string. Empty segments (e.g. a
+    /// leading separator left over when the prefix is `APP` and the separator
+    /// is `__`) are dropped.
+    ///
+    /// ```
+    /// use procenv::file::FileUtils;
+    ///
+    /// assert_eq!(
+    ///     FileUtils::split_env_key("db__max_connections", "__"),
+    ///     vec!["db", "max_connections"]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn split_env_key<'a>(key: &'a str, separator: &str) -> Vec<&'a str> {
+        if separator.is_empty() {
+            return if key.is_empty() {
+                Vec::new()
+            } else {
+                vec![key]
+            };
+        }
+
+        key.split(separator).filter(|s| !s.is_empty()).collect()
+    }
+
     /// Insert a value into a nested map structure.
     ///
     /// Used for building nested JSON objects from flat key paths like "database.host".
@@ -658,4 +784,37 @@ impl FileUtils {
             }
         }
     }
+
+    /// Replace the values of secret fields with [`REDACTED`].
+    ///
+    /// Walks nested objects, calling `is_secret` with the dotted path of each
+    /// key (e.g. `"database.password"`). The generated `config_merged_value()`
+    /// passes the struct's `__is_secret`, the same policy `get_str()` uses.
+    pub fn redact_secrets(value: &mut SJSON::Value, is_secret: impl Fn(&str) -> bool) {
+        Self::redact_secrets_at(value, "", &is_secret);
+    }
+
+    fn redact_secrets_at(
+        value: &mut SJSON::Value,
+        prefix: &str,
+        is_secret: &impl Fn(&str) -> bool,
+    ) {
+        let SJSON::Value::Object(map) = value else {
+            return;
+        };
+
+        for (key, val) in map.iter_mut() {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+
+            if is_secret(&path) {
+                *val = SJSON::Value::String(REDACTED.to_string());
+            } else {
+                Self::redact_secrets_at(val, &path, is_secret);
+            }
+        }
+    }
 }
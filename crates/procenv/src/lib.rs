@@ -18,7 +18,8 @@
 //!
 //! ## Features
 //!
-//! - **Type-safe parsing** - Automatic conversion using `FromStr` or serde deserialization
+//! - **Type-safe parsing** - Automatic conversion using `FromStr` or serde deserialization,
+//!   plus `Box<str>`, `Arc<str>` and `Cow<'static, str>` string fields
 //! - **Error accumulation** - Reports all configuration errors at once, not just the first
 //! - **Secret masking** - Protects sensitive values in `Debug` output and error messages
 //! - **Multiple sources** - Supports env vars, `.env` files, config files, and CLI arguments
@@ -80,12 +81,69 @@
 //! | Attribute | Description |
 //! |-----------|-------------|
 //! | `var = "NAME"` | Environment variable name (required) |
-//! | `default = "value"` | Default value if env var is missing |
+//! | `default = "value"` | Default value if env var is missing; `${VAR}` references expand when it is used (`$$` is a literal `$`) |
+//! | `default_from = "OTHER_VAR"` | Read another env var (also prefixed) if this one is missing |
+//! | `from_file_var = "VAR_FILE"` | Read the value from the file that env var names (also prefixed), trimming trailing newlines |
+//! | `file = "license.json"` | In `from_config()`, read this field from its own required file, over the struct's files but below env vars |
+//! | `file_key = "key"` | Dotted key read from the field's `file` (default: the field's config key) |
 //! | `optional` | Field becomes `Option<T>`, `None` if missing |
-//! | `secret` | Masks value in Debug output and errors |
+//! | `when_profile = "prod"` | Field is `Option<T>`, required only while one of the listed profiles (a name or a list) is active |
+//! | `distinguish_null` | With `optional`, the field is `Option<Option<T>>`: `None` when unset, `Some(None)` for an explicit `null` in a config file, `Some(Some(v))` otherwise |
+//! | `secret` | Masks value in Debug output and errors (scalars, strings, collections and `format` fields; use `SecretBox<T>` for other types) |
 //! | `no_prefix` | Skip struct-level prefix for this field |
 //! | `flatten` | Embed nested config struct |
-//! | `format = "json"` | Parse value as JSON/TOML/YAML |
+//! | `prefix = "DB_"` | Prefix for a `flatten` field's nested vars |
+//! | `inherit_prefix` | Apply the struct prefix to a `flatten` field's nested vars |
+//! | `serde` | With `flatten`, deserialize a `Deserialize`-only type from the config files in `from_config()`; its fields cannot be set by env vars, and env-only loaders report an error |
+//! | `skip` | Not configuration (e.g. `PhantomData<T>`); set to `Default::default()` |
+//! | `format = "json"` | Parse value as JSON/TOML/YAML (`"auto"` tries each) |
+//! | `one_of = ["a", "b"]` | Reject values outside the allowlist |
+//! | `allow_non_finite` | Accept NaN/infinity in `f32`/`f64` fields (rejected by default) |
+//! | `sensitive_log` | Report loads to the struct's `audit` hook (implied by `secret`) |
+//! | `base64` | Decode the value (standard or URL-safe) into `Vec<u8>` or `String` |
+//! | `pointer_access` | With `format = "json"`, `get_str("field/a/b")` returns the leaf at `/a/b` (type must be `Serialize`) |
+//! | `merge_env` | With `format = "json"`, `VAR_KEY` (and `VAR_KEY_SUBKEY`) env vars override keys of the parsed object; overrides win (needs the `serde` feature) |
+//! | `deserialize_with = "path"` | With `format`, deserialize through a serde-style `fn(D) -> Result<T, D::Error>` instead of `T: Deserialize`; it receives the parsed value (needs the `serde` feature) |
+//! | `sep_env = "LIST_SEP"` | Parse a `Vec<T>` field by splitting on the separator read from `LIST_SEP` (default `,`) |
+//! | `reload_immutable` | Reject watch reloads that change this field (type must be `PartialEq`) |
+//! | `min_len = 32` / `max_len = 64` | Bound a `String`/`SecretString` value's length in characters |
+//! | `validate_regex = "^v\\d+$"` | Require a `String`/`SecretString` value to match a regex (requires the `regex` feature) |
+//! | `redact_pattern = ":([^:@]+)@"` | Show a `String` value in `Debug` and errors with each regex capture group replaced by `<redacted>` (requires the `regex` feature) |
+//! | `example = "value"` | Sample value written to `env_example()` when there is no default; documentation only, and secrets show a `<secret>` placeholder instead |
+//! | `type_label = "duration"` | Name the expected type in parse errors (`expected duration`); by default `std::`/`alloc::`/`core::` paths are shortened to the type name |
+//! | `presence_bool` | A `bool` field is `true` whenever the var is set, even to `""` or `0`, and `default` (or `false`) when unset |
+//! | `coerce_bool_numeric` | A `bool` (or `Option<bool>`) field also accepts `1`/`0`, from env strings and from numbers in config files |
+//! | `trim` | Strip surrounding whitespace from the env value before parsing |
+//! | `trim_matches = "\"'"` | Strip matching pairs of these characters from both ends of the env value (after `trim`), so `"8080"` parses as `8080`; unbalanced quotes are kept |
+//! | `sensitive_eq` | Generate `verify_<field>(&self, candidate: &str) -> bool`, a constant-time comparison for string and `SecretString` fields |
+//! | `log_fingerprint` | Generate `<field>_fingerprint(&self)`, the first 12 hex digits of the SHA-256 of a `secret` string or `SecretString` field (requires the `fingerprint` feature) |
+//! | `after_parse = "fn"` | Run `fn(T) -> Result<T, E>` on the parsed (or default) value before `one_of`/length checks; an `Err` is reported as `procenv::after_parse` |
+//!
+//! A struct-level `prefix` applies to `format` fields like any other field:
+//! with `prefix = "APP_"`, `#[env(var = "MATRIX", format = "json")]` reads
+//! `APP_MATRIX`, and `no_prefix` turns that off. The prefix only affects the
+//! variable name; keys inside the JSON/TOML/YAML value are deserialized as-is.
+//!
+//! `format = "auto"` tries JSON, then YAML (with the `yaml` feature), then TOML
+//! (with the `toml` feature), and keeps the first that deserializes into the
+//! field's type; if all fail, the parse error lists each attempt. Detection is
+//! by trial, so it can guess wrong: YAML is a superset of JSON and reads most
+//! plain text as a string, so a `String` field takes TOML text verbatim. Prefer
+//! a fixed format whenever the source is known.
+//!
+//! `presence_bool` follows the shell idiom where `DEBUG=` or `DEBUG=0` still
+//! means "debug is on": the value is never parsed, only whether the variable
+//! is set. Without it, a `bool` field parses its value with `FromStr`, so only
+//! `true` and `false` are accepted. `default` applies only when the variable
+//! is unset, and `default = "true"` makes a flag that can never turn off via
+//! the environment. Presence is checked by `from_env()` and `apply_env()`;
+//! `from_config()` layers still parse the value.
+//!
+//! `coerce_bool_numeric` is for flags that arrive with different types from
+//! different sources: a file may hold `debug = false` or `debug: 0`, while an
+//! `APP_DEBUG=1` override is a string. With it, every loader accepts
+//! `true`/`false` (any case) and `1`/`0`, whether stored as a boolean, a
+//! number or a string; anything else is still a parse error.
 //!
 //! ## Struct Attributes
 //!
@@ -93,16 +151,259 @@
 //! #[derive(EnvConfig)]
 //! #[env_config(
 //!     prefix = "APP_",                           // Prefix all env vars
+//!     prefix_env = "APP_NAMESPACE",              // Runtime prefix from $APP_NAMESPACE
+//!     env_prefix_separator = "_",                // One `_` between prefix and name
 //!     dotenv,                                    // Load .env file
 //!     file_optional = "config.toml",             // Optional config file
 //!     profile_env = "APP_ENV",                   // Profile selection var
-//!     profiles = ["dev", "staging", "prod"]      // Valid profiles
+//!     profiles = ["dev", "staging", "prod"],     // Valid profiles
+//!     default_profile = "dev",                   // Profile when $APP_ENV is unset
+//!     strict_profiles                            // #[profile] must cover all of them
 //! )]
 //! struct Config {
 //!     // ...
 //! }
 //! ```
 //!
+//! With `profile_env`, `Config::active_profile()` returns the profile loading
+//! resolves to, including the `default_profile` fallback, and the
+//! `ConfigSources` from `from_env_with_sources()` or `from_config_with_sources()`
+//! record it as `sources.profile()`.
+//!
+//! A field that is mandatory in some profiles only, like a TLS certificate
+//! in production, can be declared once with `when_profile`. It loads as an
+//! `Option<T>`; in the listed profiles a missing value is reported like any
+//! missing required variable, and elsewhere it is `None`.
+//!
+//! ```rust,ignore
+//! #[derive(EnvConfig)]
+//! #[env_config(profile_env = "APP_ENV", profiles = ["dev", "staging", "prod"])]
+//! struct Config {
+//!     #[env(var = "TLS_CERT", when_profile = ["staging", "prod"])]
+//!     tls_cert: Option<PathBuf>,
+//! }
+//! ```
+//!
+//! ## Secret Access Auditing
+//!
+//! `#[env_config(audit = "audit_fn")]` calls `audit_fn(var_name: &str, present: bool)`
+//! each time a `secret` or `sensitive_log` field is loaded from the environment
+//! (`from_env()`, `from_args()`, `from_env_with_prefix()` and friends). The hook
+//! never receives the value and runs regardless of the `tracing` feature, so it
+//! can feed a compliance audit log.
+//!
+//! ## Unmasking Secrets for Local Debugging
+//!
+//! `#[env_config(allow_unmask)]` lets `PROCENV_UNMASK=1` turn masking off at
+//! runtime: the generated `Debug` impl prints `secret` fields in the clear and
+//! errors keep their values instead of redacting them. `SecretString` and
+//! `SecretBox` fields stay masked.
+//!
+//! **This trades safety for convenience.** Anyone who can set the process
+//! environment of an opted-in binary can make it log its secrets, so only
+//! enable `allow_unmask` in builds that never reach production, for example
+//! behind `#[cfg_attr(debug_assertions, env_config(allow_unmask))]`. Structs
+//! without the attribute ignore `PROCENV_UNMASK`, and nested structs decide
+//! for their own fields.
+//!
+//! `#[env_config(unmask_in_debug)]` makes the same choice at compile time
+//! instead: `secret` fields are shown whenever the crate is built with
+//! `debug_assertions` (a default `cargo build` or `cargo test`) and are
+//! always masked in release builds, whatever the environment says. The
+//! check compiles to a constant, so a release binary cannot be talked into
+//! printing its secrets. The price is that every debug build prints them
+//! unconditionally, including debug builds shared with other people or
+//! run against real credentials. It can't be combined with `allow_unmask`,
+//! which would reopen release builds to `PROCENV_UNMASK`.
+//!
+//! ## Config Schema Versions
+//!
+//! `#[env_config(schema_version = 2)]` makes `from_config()` read the
+//! `version` key of the merged configuration and fail with
+//! `FileError::UnsupportedSchemaVersion` when it is greater than 2, so an
+//! older binary refuses a config written for a newer one instead of silently
+//! ignoring what it doesn't understand. `version_key = "meta.version"` reads
+//! a different (dotted) key, and a config without the key counts as
+//! `missing_version` (default `1`).
+//!
+//! ```rust,ignore
+//! #[derive(EnvConfig)]
+//! #[env_config(file = "config.toml", schema_version = 2, version_key = "schema")]
+//! struct Config {
+//!     #[env(var = "PORT")]
+//!     port: u16,
+//! }
+//! ```
+//!
+//! ## Per-Field Config Files
+//!
+//! When one value is managed apart from the main config, for example a
+//! license or a secret written by another system, `#[env(file = "...")]`
+//! reads that field from its own file in `from_config()`. `file_key` names
+//! the dotted key inside it, defaulting to the field's own config key. The
+//! value replaces whatever the struct's files set for the field, and env
+//! vars still override it. Like a struct-level `file`, the file must exist;
+//! a file without the key leaves the field to the other layers. The struct
+//! needs a `file`, `file_optional` or `embedded` of its own, and fields of
+//! flattened structs are not read from their files.
+//!
+//! ```rust,ignore
+//! #[derive(EnvConfig)]
+//! #[env_config(file = "config.toml")]
+//! struct Config {
+//!     #[env(var = "LICENSE", secret, file = "/etc/app/license.json", file_key = "key")]
+//!     license: String,
+//! }
+//! ```
+//!
+//! ## Case-Insensitive Variable Names
+//!
+//! `#[env_config(case_insensitive_env)]` lets `APP_PORT` also be read from
+//! `app_port` or `App_Port`. An exact match always wins; otherwise the name is
+//! compared, ignoring ASCII case, against a snapshot of the environment taken
+//! once per load. If several variables fold to the same name, the one that
+//! sorts first by byte is used (`App_Port` before `app_port`), since the
+//! environment has no portable order. A lookup that hits such a collision
+//! prints a warning naming every match to stderr, or logs it with
+//! `tracing::warn!` when the `tracing` feature is enabled.
+//!
+//! On platforms with case-sensitive environments (Linux, macOS) this changes
+//! which variables match, so it is opt-in. It applies to `from_env()`,
+//! `from_env_with_sources()`, `from_args()`, `apply_env()` and the other
+//! env loaders, but not to the env layer of `from_config()`.
+//!
+//! ## Fail-Fast Loading
+//!
+//! `#[env_config(no_accumulate)]` makes `from_env()` return the first error
+//! instead of reading every field and collecting all errors into
+//! `Error::Multiple`. It suits small configs where complete diagnostics aren't
+//! needed; `from_env_with_sources()` and the other loaders still accumulate.
+//!
+//! ## Optional Subsystems
+//!
+//! `#[env_config(allow_empty_struct)]` on a nested config makes it load as
+//! `Default::default()` when loading fails and none of its own variables are
+//! set, so a flattened plugin section can be left out entirely. Once any of
+//! its variables is set the subsystem counts as present and loads normally,
+//! reporting whatever else is missing. The struct must implement `Default`;
+//! `#[env_config(derive_default)]` generates it from the field attributes.
+//! Variables of structs flattened inside it are not checked. This applies to
+//! `from_env()`, `from_env_with_sources()` and `from_env_with_prefix()`.
+//!
+//! ## Generated Defaults
+//!
+//! A hand-written or derived `Default` repeats every `default = "..."` and
+//! drifts from it over time. `#[env_config(derive_default)]` generates
+//! `impl Default` from the attributes instead: each `default` is parsed as
+//! it would be for an unset variable, optional fields are `None`, and
+//! flattened structs and `skip` fields use their type's `Default`. A field
+//! with none of these is a compile error, since there is nothing to fill it
+//! with. A `default` that fails to parse makes `default()` panic.
+//!
+//! ## Required Dotenv Files
+//!
+//! `#[env_config(dotenv = ".env.prod")]` is best-effort: a missing file is
+//! ignored. `#[env_config(dotenv_required = ".env.prod")]` instead makes the
+//! loaders return `Error::DotenvMissing` (`procenv::dotenv_missing`) with the
+//! path, so a forgotten env file mount fails at startup rather than running
+//! half-configured.
+//!
+//! ## Runtime Prefixes
+//!
+//! `#[env_config(prefix_env = "APP_NAMESPACE")]` lets one binary serve several
+//! namespaces. When `APP_NAMESPACE` is set, `from_env()` and
+//! `from_env_with_sources()` load as `from_env_with_prefix($APP_NAMESPACE)`
+//! would, so with `prefix = "APP_"` and `APP_NAMESPACE=BLUE_` the `PORT` field
+//! reads `BLUE_APP_PORT`, and `ConfigSources` reports that name. When it is
+//! unset, only the compiled `prefix` applies. The variable is read from the
+//! process environment before any dotenv file is loaded.
+//!
+//! ## Prefix Separators
+//!
+//! Prefixes are concatenated with var names as written, so they usually end
+//! in `_`. `#[env_config(env_prefix_separator = "_")]` instead joins every
+//! prefix and name with exactly one separator, whether or not the prefix
+//! already ends with it:
+//!
+//! | Case | Without separator | With `env_prefix_separator = "_"` |
+//! |------|-------------------|-----------------------------------|
+//! | `prefix = "APP_"`, `var = "PORT"` | `APP_PORT` | `APP_PORT` |
+//! | `prefix = "APP"`, `var = "PORT"` | `APPPORT` | `APP_PORT` |
+//! | `from_env_with_prefix("BLUE")` with `prefix = "APP"` | `BLUEAPPPORT` | `BLUE_APP_PORT` |
+//! | `#[env(flatten, prefix = "DB")]`, nested `var = "URL"` | `DBURL` | `DB_URL` |
+//!
+//! The separator belongs to the struct doing the joining: a flattened
+//! struct's own setting decides how its vars join the `prefix` given on the
+//! `flatten` field, while the parent's setting decides how that `prefix`
+//! joins the parent's own `prefix` (`APP` + `DB` → `APP_DB`) or a runtime
+//! prefix. An empty prefix adds no separator.
+//!
+//! ## Serde Renames in Config Files
+//!
+//! `from_config()` reads each field from the key serde would deserialize it
+//! from: `#[serde(rename = "...")]` (or `rename(deserialize = "...")`) on the
+//! field, otherwise the struct's `#[serde(rename_all = "...")]` rule, otherwise
+//! the field name. The file key and `env(var)` are independent: `env(var)`
+//! only names the env var, and a serde rename only names the file key, so
+//! neither takes precedence over the other. `ConfigSources` keeps top-level
+//! fields under their Rust names, while nested paths follow the file keys.
+//!
+//! ## Feature-Gated Fields
+//!
+//! Fields under `#[cfg(...)]` are honored: when the predicate is false the
+//! field is gone before the derive runs, so it is not read by `from_env()`,
+//! listed by `keys()` or `env_example()`, or tracked in `ConfigSources`.
+//!
+//! ```rust,ignore
+//! #[derive(EnvConfig)]
+//! struct Config {
+//!     #[cfg(feature = "metrics")]
+//!     #[env(var = "METRICS_PORT", default = "9090")]
+//!     metrics_port: u16,
+//! }
+//! ```
+//!
+//! ## Generic Structs
+//!
+//! Structs may take lifetime and type parameters. Loaded values must be
+//! owned, so `&str` fields are rejected in favor of `String`. A marker such
+//! as `PhantomData<T>` is excluded with `#[env(skip)]` and set to
+//! `Default::default()`:
+//!
+//! ```rust,ignore
+//! #[derive(EnvConfig)]
+//! struct Config<T> {
+//!     #[env(var = "PORT", default = "8080")]
+//!     port: u16,
+//!
+//!     #[env(skip)]
+//!     _marker: PhantomData<T>,
+//! }
+//! ```
+//!
+//! ## Loading from a String
+//!
+//! `from_dotenv_str()` parses `.env`-formatted text and loads from it alone,
+//! without reading or modifying the process environment. It suits tests and
+//! embedded defaults (requires the `dotenv` feature):
+//!
+//! ```rust
+//! use procenv::EnvConfig;
+//!
+//! #[derive(EnvConfig)]
+//! struct Config {
+//!     #[env(var = "PORT", default = "8080")]
+//!     port: u16,
+//!
+//!     #[env(var = "LOG_LEVEL")]
+//!     log_level: String,
+//! }
+//!
+//! let config = Config::from_dotenv_str("LOG_LEVEL=debug\n").unwrap();
+//! assert_eq!(config.port, 8080);
+//! assert_eq!(config.log_level, "debug");
+//! ```
+//!
 //! ## Generated Methods
 //!
 //! The derive macro generates several methods on your struct:
@@ -110,15 +411,30 @@
 //! | Method | Description |
 //! |--------|-------------|
 //! | `from_env()` | Load from environment variables |
+//! | `from_dotenv_str(content)` | Load from `.env`-formatted text, without touching the environment |
 //! | `from_env_with_sources()` | Load with source attribution |
+//! | `from_env_verbose()` | Load, printing `field <- source (VAR)` per field to stderr |
+//! | `from_env_formatted()` | Load, returning errors already rendered as a `String` |
+//! | `from_env_with_prefix(prefix)` | Load with a runtime prefix on every var name |
+//! | `apply_env(&mut self)` | Overwrite only fields whose env vars are set; unset required vars are not errors |
+//! | `builder()` | `<Struct>Builder` with a setter per field; `build()` fills defaults and reports unset required fields, without reading env vars |
 //! | `from_config()` | Load from files + env vars (layered) |
 //! | `from_config_with_sources()` | Layered loading with source attribution |
+//! | `config_merged_value()` | Raw merged value behind `from_config()` |
+//! | `config_loaded_files()` | Config files `from_config()` actually loads |
+//! | `from_json_env()` | Load from one JSON env var + env overrides (with `from_json_var`) |
 //! | `from_args()` | Load from CLI arguments + env |
+//! | `from_arg_matches(&matches)` | Load from `ArgMatches` of an existing clap `Command` + env |
+//! | `clap_args()` | The `Arg`s `from_args()` defines, to add to your own `Command` |
 //! | `from_env_validated()` | Load + validate (requires `validator` feature) |
+//! | `from_env_timed()` | Load + per-field timings (requires `timing` feature) |
 //! | `env_example()` | Generate `.env.example` template |
-//! | `keys()` | List all field names |
-//! | `get_str(&self, key)` | Get field value as string |
-//! | `has_key(key)` | Check if field exists |
+//! | `keys()` | List all field names, sorted (`const`) |
+//! | `get_str(&self, key)` | Get field value as string (`field/a/b` into `pointer_access` fields) |
+//! | `has_key(key)` | Check if field exists (`const`) |
+//! | `reload_immutable_changed(&self, &other)` | First `reload_immutable` field that differs, for `WatchBuilder::reject_immutable_changes` |
+//! | `changed_fields(&self, &other)` | Fields whose values differ, without `PartialEq`, for `WatchBuilder::diff_fields` |
+//! | `digest(&self)` | Hash of every field value, secrets included (`SecretString`/`SecretBox` via `ExposeSecret`) |
 //!
 //! ## Feature Flags
 //!
@@ -134,7 +450,11 @@
 //! | `validator` | Validation via [`validator`] crate | No |
 //! | `provider` | Custom provider extensibility | No |
 //! | `watch` | Hot reload with file watching | No |
-//! | `full` | Enable all features | No |
+//! | `timing` | Per-field load timing via `from_env_timed()` | No |
+//! | `regex` | `validate_regex` pattern checks and `redact_pattern` masking | No |
+//! | `fingerprint` | `log_fingerprint` SHA-256 fingerprints of secrets | No |
+//! | `test-util` | [`test_util`] helpers for setting env vars in tests | No |
+//! | `full` | Enable all features except `test-util` | No |
 //!
 //! ## Secret Handling
 //!
@@ -146,6 +466,11 @@
 //! 2. **Runtime protection** (requires `secrecy` feature): Use [`SecretString`]
 //!    for values that should be protected in memory and Debug output.
 //!
+//! `secret` works on scalars, strings, the std collections (`Vec`, `HashMap`,
+//! ...) and `format` fields, which `Debug` masks whole or element by element.
+//! Other types, such as a `Url`, are a compile error: wrap them in
+//! `SecretBox<T>` instead.
+//!
 //! ```rust,ignore
 //! #[derive(EnvConfig)]
 //! struct Config {
@@ -154,6 +479,17 @@
 //! }
 //! ```
 //!
+//! For shared-secret tokens, `#[env(sensitive_eq)]` on a string or
+//! `SecretString` field generates `verify_<field>(&self, candidate)`, which
+//! compares in constant time so checking an incoming admin token or webhook
+//! secret doesn't need `==` on the exposed value.
+//!
+//! To see which secret a deployment picked up, `#[env(log_fingerprint)]`
+//! (with the `fingerprint` feature) generates `<field>_fingerprint(&self)`,
+//! a truncated SHA-256 that can be logged and compared across hosts. The
+//! hash can't be reversed, but it does confirm a correct guess, so it only
+//! suits secrets that aren't guessable.
+//!
 //! ## Error Handling
 //!
 //! All errors are reported through the [`Error`] type, which integrates with
@@ -168,6 +504,39 @@
 //!     }
 //! }
 //! ```
+//!
+//! `Error::render` produces the same report as a string, or miette's JSON
+//! report with `ErrorFormat::Json`. `from_env_formatted()` renders through
+//! it in the format picked by `#[env_config(error_format = "json")]`
+//! (`"pretty"` by default), for a `main` that just wants a message:
+//!
+//! ```rust,ignore
+//! #[derive(EnvConfig)]
+//! #[env_config(error_format = "json")]
+//! struct Config {
+//!     #[env(var = "PORT")]
+//!     port: u16,
+//! }
+//!
+//! fn main() -> Result<(), String> {
+//!     let config = Config::from_env_formatted()?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! An application split into several config structs can report all of their
+//! errors in one go with [`load_all!`], which calls `from_env()` on each and
+//! groups every failure under an `Error::Struct` naming its struct. [`Errors`]
+//! does the same for loaders other than `from_env()`:
+//!
+//! ```rust,ignore
+//! let (app, db) = procenv::load_all!(AppConfig, DbConfig)?;
+//!
+//! let mut errors = procenv::Errors::new();
+//! let app = errors.accumulate("AppConfig", AppConfig::from_config());
+//! let db = errors.accumulate("DbConfig", DbConfig::from_config());
+//! errors.finish()?;
+//! ```
 
 #![deny(missing_docs)]
 #![warn(clippy::pedantic)]
@@ -240,7 +609,7 @@ pub mod diagnostic_codes;
 
 // Error types
 mod error;
-pub use error::{Error, MaybeRedacted};
+pub use error::{Error, ErrorFormat, Errors, MaybeRedacted};
 
 /// A Result type that displays errors with miette's fancy formatting.
 ///
@@ -259,19 +628,33 @@ pub type Result<T> = miette::Result<T>;
 
 // Source attribution types
 mod source;
-pub use source::{ConfigSources, Source, ValueSource};
+pub use source::{ConfigSources, Source, SourceDiff, ValueSource};
 
-// Validation support (feature-gated)
-#[cfg(feature = "validator")]
+// Validation support (the `validator` integration is feature-gated)
 mod validation;
+pub use validation::ValidationFieldError;
 #[cfg(feature = "validator")]
-pub use validation::{ValidationFieldError, validation_errors_to_procenv};
+pub use validation::{CustomValidationError, validation_errors_to_procenv};
 // Re-export Validate trait for macro-generated code
 // This allows the macro to reference ::procenv::Validate instead of ::validator::Validate
 // which provides clear compile errors when the validator feature isn't enabled
 #[cfg(feature = "validator")]
 pub use validator::Validate;
 
+// Support for macro-generated runtime access methods
+#[doc(hidden)]
+pub mod runtime;
+
+// Environment fixtures for downstream tests (feature-gated)
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
+// Load timing (feature-gated)
+#[cfg(feature = "timing")]
+pub mod timing;
+#[cfg(feature = "timing")]
+pub use timing::{FieldTiming, LoadTimings};
+
 // ============================================================================
 // File Configuration Support
 // ============================================================================
@@ -295,13 +678,16 @@ pub use value::ConfigValue;
 pub use provider::DotenvProvider;
 #[cfg(feature = "file")]
 pub use provider::FileProvider;
+#[cfg(feature = "provider")]
+pub use provider::FnProvider;
 #[cfg(feature = "async")]
 pub use provider::{AsyncProvider, BlockingAdapter, BoxFuture};
 pub use provider::{
-    EnvProvider, Provider, ProviderError, ProviderResult, ProviderSource, ProviderValue,
+    DirProvider, EnvProvider, Provider, ProviderError, ProviderResult, ProviderSource,
+    ProviderValue,
 };
 
-pub use loader::ConfigLoader;
+pub use loader::{ConfigLoader, LoaderBuilder};
 
 // ============================================================================
 // Hot Reload Support (Phase E)
@@ -312,6 +698,6 @@ pub mod watch;
 
 #[cfg(feature = "watch")]
 pub use watch::{
-    ChangeTrigger, ChangedField, ConfigChange, ConfigHandle, WatchBuilder, WatchCommand,
-    WatchError, WatchedConfig,
+    ChangeTrigger, ChangedField, ConfigChange, ConfigHandle, MappedHandle, WatchBuilder,
+    WatchCommand, WatchError, WatchedConfig,
 };
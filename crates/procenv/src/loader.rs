@@ -19,26 +19,55 @@
 //!
 //! // Or load a full struct
 //! let config: MyConfig = loader.load()?;
+//!
+//! // Show which provider won each key, with secrets hidden
+//! for (key, (value, source)) in loader.snapshot_redacted(&["PORT", "DB_PASSWORD"]) {
+//!     println!("{key} = {} ({source})", value.value);
+//! }
+//! ```
+//!
+//! # Builder
+//!
+//! [`ConfigLoader::builder()`] wires the same chain fluently. Providers are
+//! created in [`build()`](LoaderBuilder::build), sorted by priority, and
+//! registered once each, so the order of the calls doesn't matter:
+//!
+//! ```rust,ignore
+//! use procenv::loader::ConfigLoader;
+//!
+//! let mut loader = ConfigLoader::builder()
+//!     .file("config.toml")
+//!     .dotenv()
+//!     .env()
+//!     .provider(Box::new(VaultProvider::new()))
+//!     .build()?;
+//!
+//! // Queried as env, .env, config.toml, then the vault (custom providers
+//! // default to the lowest priority)
+//! let port = loader.get_parsed::<u16>("PORT")?;
 //! ```
 
 use std::collections::HashMap;
 use std::string::String;
 
 use crate::provider::{Provider, ProviderError, ProviderSource, ProviderValue};
+use crate::runtime::REDACTED;
 use crate::{ConfigSources, Error, Source, ValueSource};
 
 /// Orchestrates configuration loading from multiple providers.
 ///
 /// Providers are queried in priority order (lower priority number = higher priority).
-/// The first provider to return a value for a key wins. Errors from providers
-/// are accumulated and reported together.
+/// Providers that share a priority are queried in registration order, so the
+/// one added first wins ties. The first provider to return a value for a key
+/// wins, even if the value is empty. Errors from providers are accumulated
+/// and reported together, except that optional providers which are down are
+/// skipped rather than failing the load, and an error from an
+/// [`authoritative`](Provider::authoritative) provider ends the search.
 pub struct ConfigLoader {
     providers: Vec<Box<dyn Provider>>,
     cache: HashMap<String, ProviderValue>,
     sources: ConfigSources,
     errors: Vec<Error>,
-    /// Whether providers have been sorted by priority.
-    sorted: bool,
 }
 
 impl ConfigLoader {
@@ -50,20 +79,33 @@ impl ConfigLoader {
             cache: HashMap::new(),
             sources: ConfigSources::new(),
             errors: Vec::new(),
-            sorted: false,
         }
     }
 
+    /// Creates a [`LoaderBuilder`] for wiring the provider chain fluently.
+    pub const fn builder() -> LoaderBuilder {
+        LoaderBuilder::new()
+    }
+
     /// Adds a provider to the loader.
     ///
-    /// Providers are automatically sorted by priority when values are retrieved.
+    /// The provider is inserted after every provider with the same or a
+    /// higher priority, keeping the chain sorted by priority with ties
+    /// broken by registration order.
     #[must_use]
     pub fn with_provider(mut self, provider: Box<dyn Provider>) -> Self {
-        self.providers.push(provider);
-        self.sorted = false; // Mark as unsorted when new provider added
+        let priority = provider.priority();
+        let index = self.providers.partition_point(|p| p.priority() <= priority);
+        self.providers.insert(index, provider);
         self
     }
 
+    /// Returns the providers in the order they are queried.
+    #[must_use]
+    pub fn providers(&self) -> &[Box<dyn Provider>] {
+        &self.providers
+    }
+
     /// Adds an environment provider.
     #[must_use]
     pub fn with_env(self) -> Self {
@@ -101,6 +143,15 @@ impl ConfigLoader {
         Ok(self.with_provider(Box::new(provider)))
     }
 
+    /// Adds a provider serving each key from a file in `dir`, such as
+    /// `/run/secrets`.
+    ///
+    /// See [`DirProvider`](crate::provider::DirProvider).
+    #[must_use]
+    pub fn with_secrets_dir(self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.with_provider(Box::new(crate::provider::DirProvider::new(dir)))
+    }
+
     /// Adds a file provider from a required file.
     ///
     /// # Errors
@@ -134,40 +185,34 @@ impl ConfigLoader {
         }
     }
 
-    /// Sorts providers by priority (lower = higher priority).
-    fn sort_providers(&mut self) {
-        if !self.sorted {
-            self.providers.sort_by_key(|p| p.priority());
-            self.sorted = true;
-        }
-    }
-
     /// Gets a raw value from the provider chain.
     ///
     /// Returns `None` if no provider has the key. Errors are accumulated
     /// internally and can be retrieved with [`errors()`](Self::errors).
+    ///
+    /// A provider that is down (`is_available()` is `false`, or `get` fails
+    /// with a connection or unavailability error) is skipped unless it is
+    /// [`required`](Provider::required), in which case the error is recorded
+    /// and lower-priority providers are not consulted. Skipped providers are
+    /// listed in [`ConfigSources::skipped_providers`].
     pub fn get(&mut self, key: &str) -> Option<ProviderValue> {
         // Check cache first
         if let Some(cached) = self.cache.get(key) {
             return Some(cached.clone());
         }
 
-        self.sort_providers();
-
         for provider in &self.providers {
             if !provider.is_available() {
-                continue;
+                if Self::provider_down(provider.as_ref(), None, &mut self.errors, &mut self.sources)
+                {
+                    continue;
+                }
+                break;
             }
 
             match provider.get(key) {
                 Ok(Some(value)) => {
-                    // Record source attribution
-                    let source = value.source.to_source();
-                    self.sources.add(key, ValueSource::new(key, source));
-
-                    // Cache the value
-                    self.cache.insert(key.to_string(), value.clone());
-
+                    self.record(key, &value);
                     return Some(value);
                 }
                 Ok(None) => {
@@ -176,9 +221,23 @@ impl ConfigLoader {
                         break;
                     }
                 }
+                Err(e) if Self::is_outage(&e) => {
+                    if !Self::provider_down(
+                        provider.as_ref(),
+                        Some(&e),
+                        &mut self.errors,
+                        &mut self.sources,
+                    ) {
+                        break;
+                    }
+                }
                 Err(e) => {
-                    // Accumulate error but continue trying other providers
+                    // Accumulate error; only an authoritative provider's
+                    // failure keeps lower providers from supplying the key
                     self.errors.push(Self::provider_error_to_error(&e));
+                    if provider.authoritative() {
+                        break;
+                    }
                 }
             }
         }
@@ -186,6 +245,167 @@ impl ConfigLoader {
         None
     }
 
+    /// Gets several raw values from the provider chain in one pass.
+    ///
+    /// Keys that are not already cached are handed to each provider's
+    /// [`Provider::get_many`] as a single batch, walking providers in
+    /// priority order and only asking each one for the keys still
+    /// unresolved. This keeps remote providers to one round-trip each
+    /// instead of one per key.
+    ///
+    /// Resolution follows the same rules as [`get()`](Self::get): the
+    /// highest-priority value wins, even if empty, optional providers that
+    /// are down are skipped while required ones end the search with an
+    /// error, a provider that does not fall through ends the search for the
+    /// keys it lacks, and other provider errors are accumulated while the
+    /// search continues unless the provider is authoritative.
+    /// Keys that no provider resolves are absent from the returned map.
+    pub fn get_many(&mut self, keys: &[&str]) -> HashMap<String, ProviderValue> {
+        let mut found = HashMap::new();
+        let mut pending: Vec<&str> = Vec::new();
+
+        for &key in keys {
+            if let Some(cached) = self.cache.get(key) {
+                found.insert(key.to_string(), cached.clone());
+            } else if !pending.contains(&key) {
+                pending.push(key);
+            }
+        }
+
+        let mut hits = Vec::new();
+
+        for provider in &self.providers {
+            if pending.is_empty() {
+                break;
+            }
+
+            if !provider.is_available() {
+                if !Self::provider_down(
+                    provider.as_ref(),
+                    None,
+                    &mut self.errors,
+                    &mut self.sources,
+                ) {
+                    pending.clear();
+                }
+                continue;
+            }
+
+            let mut results = provider.get_many(&pending);
+            // Whether the search goes on past an outage, recorded once however
+            // many keys the outage failed
+            let mut outage = None;
+
+            pending.retain(|&key| match results.remove(key).unwrap_or(Ok(None)) {
+                Ok(Some(value)) => {
+                    hits.push((key, value));
+                    false
+                }
+                // Not found here: keep searching unless this provider is terminal
+                Ok(None) => provider.fallthrough(),
+                Err(e) if Self::is_outage(&e) => *outage.get_or_insert_with(|| {
+                    Self::provider_down(
+                        provider.as_ref(),
+                        Some(&e),
+                        &mut self.errors,
+                        &mut self.sources,
+                    )
+                }),
+                Err(e) => {
+                    self.errors.push(Self::provider_error_to_error(&e));
+                    !provider.authoritative()
+                }
+            });
+        }
+
+        for (key, value) in hits {
+            self.record(key, &value);
+            found.insert(key.to_string(), value);
+        }
+
+        found
+    }
+
+    /// Resolves `keys` through the chain and returns each winning value
+    /// with the source of the provider that supplied it.
+    ///
+    /// Meant for diagnosing precedence across providers: this is the
+    /// provider-chain counterpart of `from_env_with_sources()`. Keys are
+    /// resolved as by [`get_many()`](Self::get_many), so they are cached and
+    /// attributed in [`sources()`](Self::sources) too, and keys no provider
+    /// has are left out. Secret values keep their `secret` flag; use
+    /// [`snapshot_redacted()`](Self::snapshot_redacted) to print them.
+    pub fn snapshot(&mut self, keys: &[&str]) -> HashMap<String, (ProviderValue, ProviderSource)> {
+        self.get_many(keys)
+            .into_iter()
+            .map(|(key, value)| {
+                let source = value.source.clone();
+                (key, (value, source))
+            })
+            .collect()
+    }
+
+    /// Like [`snapshot()`](Self::snapshot), with secret values replaced by
+    /// [`REDACTED`].
+    pub fn snapshot_redacted(
+        &mut self,
+        keys: &[&str],
+    ) -> HashMap<String, (ProviderValue, ProviderSource)> {
+        let mut snapshot = self.snapshot(keys);
+        for (value, _) in snapshot.values_mut() {
+            if value.secret {
+                value.value = REDACTED.to_string();
+            }
+        }
+        snapshot
+    }
+
+    /// Whether `e` means the provider is down rather than the key being bad.
+    const fn is_outage(e: &ProviderError) -> bool {
+        matches!(
+            e,
+            ProviderError::Connection { .. } | ProviderError::Unavailable { .. }
+        )
+    }
+
+    /// Handles a provider that is down, returning whether the search goes on.
+    ///
+    /// An optional provider is skipped and listed in the sources; a
+    /// [`required`](Provider::required) one records an error instead, which
+    /// ends the search. `error` is the failure from `get`, or `None` when
+    /// [`is_available()`](Provider::is_available) returned `false`.
+    fn provider_down(
+        provider: &dyn Provider,
+        error: Option<&ProviderError>,
+        errors: &mut Vec<Error>,
+        sources: &mut ConfigSources,
+    ) -> bool {
+        if !provider.required() {
+            sources.add_skipped_provider(provider.name());
+            return true;
+        }
+
+        let error = error.map_or_else(
+            || {
+                Self::provider_error_to_error(&ProviderError::Unavailable {
+                    provider: provider.name().to_string(),
+                    message: "is_available() returned false".to_string(),
+                })
+            },
+            Self::provider_error_to_error,
+        );
+        errors.push(error);
+
+        false
+    }
+
+    /// Records source attribution for a resolved value and caches it.
+    fn record(&mut self, key: &str, value: &ProviderValue) {
+        let source = value.source.to_source();
+        self.sources.add(key, ValueSource::new(key, source));
+        self.cache.insert(key.to_string(), value.clone());
+    }
+
     /// Gets a required value, recording a Missing error if not found.
     pub fn get_required(&mut self, key: &str, env_var_name: &'static str) -> Option<ProviderValue> {
         if let Some(v) = self.get(key) {
@@ -349,6 +569,165 @@ impl Default for ConfigLoader {
     }
 }
 
+/// A provider registered with a [`LoaderBuilder`], created by `build()`.
+enum PendingProvider {
+    Env,
+    #[cfg(feature = "dotenv")]
+    Dotenv,
+    #[cfg(feature = "file")]
+    File {
+        path: std::path::PathBuf,
+        required: bool,
+    },
+    Custom(Box<dyn Provider>),
+}
+
+impl PendingProvider {
+    /// Identity used to drop repeated registrations.
+    ///
+    /// Files are told apart by path, everything else by provider name.
+    fn key(&self) -> String {
+        match self {
+            Self::Env => "environment".to_string(),
+            #[cfg(feature = "dotenv")]
+            Self::Dotenv => "dotenv".to_string(),
+            #[cfg(feature = "file")]
+            Self::File { path, .. } => format!("file:{}", path.display()),
+            Self::Custom(provider) => provider.name().to_string(),
+        }
+    }
+
+    /// Creates the provider; `None` for an optional file that doesn't exist.
+    #[allow(clippy::result_large_err)]
+    #[cfg_attr(
+        not(any(feature = "dotenv", feature = "file")),
+        allow(clippy::unnecessary_wraps)
+    )]
+    fn into_provider(self) -> Result<Option<Box<dyn Provider>>, Error> {
+        match self {
+            Self::Env => Ok(Some(Box::new(crate::provider::EnvProvider::new()))),
+            #[cfg(feature = "dotenv")]
+            Self::Dotenv => match crate::provider::DotenvProvider::new() {
+                Ok(provider) => Ok(Some(Box::new(provider))),
+                Err(e) => Err(Error::Provider {
+                    provider: "dotenv".to_string(),
+                    message: e.to_string(),
+                    help: "check that .env is readable".to_string(),
+                }),
+            },
+            #[cfg(feature = "file")]
+            Self::File {
+                path,
+                required: true,
+            } => {
+                let provider = crate::provider::FileProvider::from_file(path)?;
+                Ok(Some(Box::new(provider)))
+            }
+            #[cfg(feature = "file")]
+            Self::File {
+                path,
+                required: false,
+            } => {
+                let provider = crate::provider::FileProvider::from_file_optional(path)?;
+                Ok(provider.map(|p| Box::new(p) as Box<dyn Provider>))
+            }
+            Self::Custom(provider) => Ok(Some(provider)),
+        }
+    }
+}
+
+/// Fluent builder for a [`ConfigLoader`], created by [`ConfigLoader::builder()`].
+///
+/// Providers are only created in [`build()`](Self::build), which reports
+/// every file or dotenv failure at once. The chain is sorted by
+/// [`Provider::priority`] no matter the call order, and a provider registered
+/// twice (the same file path, or the same [`Provider::name`]) is kept once,
+/// at its first registration.
+#[must_use]
+pub struct LoaderBuilder {
+    providers: Vec<PendingProvider>,
+}
+
+impl LoaderBuilder {
+    /// Creates an empty builder.
+    pub const fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+        }
+    }
+
+    /// Adds an environment variable provider.
+    pub fn env(self) -> Self {
+        self.push(PendingProvider::Env)
+    }
+
+    /// Adds a provider for the `.env` file in the current directory, if any.
+    #[cfg(feature = "dotenv")]
+    pub fn dotenv(self) -> Self {
+        self.push(PendingProvider::Dotenv)
+    }
+
+    /// Adds a provider for a required config file.
+    #[cfg(feature = "file")]
+    pub fn file(self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.push(PendingProvider::File {
+            path: path.into(),
+            required: true,
+        })
+    }
+
+    /// Adds a provider for a config file that is skipped if missing.
+    #[cfg(feature = "file")]
+    pub fn file_optional(self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.push(PendingProvider::File {
+            path: path.into(),
+            required: false,
+        })
+    }
+
+    /// Adds a custom provider.
+    pub fn provider(self, provider: Box<dyn Provider>) -> Self {
+        self.push(PendingProvider::Custom(provider))
+    }
+
+    fn push(mut self, pending: PendingProvider) -> Self {
+        let key = pending.key();
+        if !self.providers.iter().any(|p| p.key() == key) {
+            self.providers.push(pending);
+        }
+        self
+    }
+
+    /// Creates the providers and returns the loader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for each required file that is missing, file that
+    /// cannot be parsed, or `.env` file that cannot be read, combined with
+    /// [`Error::multiple`].
+    #[allow(clippy::result_large_err)]
+    pub fn build(self) -> Result<ConfigLoader, Error> {
+        let mut loader = ConfigLoader::new();
+        let mut errors = Vec::new();
+
+        for pending in self.providers {
+            match pending.into_provider() {
+                Ok(Some(provider)) => loader = loader.with_provider(provider),
+                Ok(None) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+
+        Error::multiple(errors).map_or(Ok(loader), Err)
+    }
+}
+
+impl Default for LoaderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
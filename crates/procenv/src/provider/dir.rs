@@ -0,0 +1,201 @@
+//! Directory-of-files provider (Docker/Kubernetes secrets).
+
+use super::{Provider, ProviderError, ProviderResult, ProviderSource, ProviderValue, priority};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Provider that serves each key from a file of the same name in a directory.
+///
+/// This is the secrets-as-files layout used by Docker (`/run/secrets`) and
+/// Kubernetes secret volumes: getting `"DATABASE_URL"` reads
+/// `<dir>/DATABASE_URL`. Contents are trimmed, so the trailing newline most
+/// tools write is dropped, and every value is marked secret.
+///
+/// Keys that are not plain file names (containing a path separator or
+/// starting with `.`) are never looked up, so a key cannot escape the
+/// directory. A missing file or directory means the key is not set.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use procenv::provider::{DirProvider, priority};
+///
+/// let provider = DirProvider::new("/run/secrets");
+///
+/// // Let mounted secrets win over the environment
+/// let provider = DirProvider::new("/run/secrets").with_priority(priority::ENVIRONMENT - 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DirProvider {
+    dir: PathBuf,
+    priority: u32,
+}
+
+impl DirProvider {
+    /// Creates a provider that reads keys from files in `dir`.
+    ///
+    /// The priority defaults to [`priority::CONFIG_FILE`].
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            priority: priority::CONFIG_FILE,
+        }
+    }
+
+    /// Sets the priority (lower = queried earlier).
+    #[must_use]
+    pub const fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+
+        self
+    }
+
+    /// Returns the directory this provider reads from.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Lists the keys available in the directory, sorted.
+    ///
+    /// Only regular files (or symlinks to them, as Kubernetes mounts them)
+    /// count; hidden entries such as Kubernetes' `..data` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read.
+    pub fn list_keys(&self) -> std::io::Result<Vec<String>> {
+        let mut keys = Vec::new();
+
+        for entry in std::fs::read_dir(&self.dir)? {
+            let entry = entry?;
+
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+
+            if Self::is_key_name(&name) && entry.path().is_file() {
+                keys.push(name);
+            }
+        }
+
+        keys.sort_unstable();
+
+        Ok(keys)
+    }
+
+    /// Whether `key` names a file directly inside the directory.
+    fn is_key_name(key: &str) -> bool {
+        !key.is_empty() && !key.starts_with('.') && !key.contains(['/', '\\'])
+    }
+}
+
+impl Provider for DirProvider {
+    fn name(&self) -> &'static str {
+        "directory"
+    }
+
+    fn get(&self, key: &str) -> ProviderResult<ProviderValue> {
+        if !Self::is_key_name(key) {
+            return Ok(None);
+        }
+
+        let path = self.dir.join(key);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => Ok(Some(
+                ProviderValue::new(contents.trim(), ProviderSource::config_file(Some(path)))
+                    .with_secret(true),
+            )),
+
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+
+            Err(e) if e.kind() == ErrorKind::InvalidData => Err(ProviderError::InvalidValue {
+                key: key.to_string(),
+                provider: self.name().to_string(),
+                message: format!("{} contains invalid UTF-8", path.display()),
+            }),
+
+            Err(e) => Err(ProviderError::Other {
+                provider: self.name().to_string(),
+                message: format!("failed to read {}", path.display()),
+                source: Some(Box::new(e)),
+            }),
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        self.dir.is_dir()
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dir_provider_reads_trimmed_secret() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("DATABASE_URL"), "postgres://db\n").unwrap();
+
+        let provider = DirProvider::new(dir.path());
+        let value = provider.get("DATABASE_URL").unwrap().unwrap();
+
+        assert_eq!(value.value, "postgres://db");
+        assert!(value.secret);
+        assert_eq!(
+            value.source,
+            ProviderSource::config_file(Some(dir.path().join("DATABASE_URL")))
+        );
+        assert!(provider.get("MISSING").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_dir_provider_rejects_paths_outside_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("nested").join("KEY"), "value").unwrap();
+        std::fs::write(dir.path().join(".hidden"), "value").unwrap();
+
+        let provider = DirProvider::new(dir.path().join("nested"));
+        assert!(provider.get("../nested/KEY").unwrap().is_none());
+        assert!(provider.get("..").unwrap().is_none());
+
+        let provider = DirProvider::new(dir.path());
+        assert!(provider.get("nested").unwrap().is_none());
+        assert!(provider.get(".hidden").unwrap().is_none());
+        assert!(provider.get("nested/KEY").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_dir_provider_list_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("PORT"), "8080").unwrap();
+        std::fs::write(dir.path().join("API_KEY"), "hunter2").unwrap();
+        std::fs::write(dir.path().join("..data"), "").unwrap();
+        std::fs::create_dir(dir.path().join("subdir")).unwrap();
+
+        let provider = DirProvider::new(dir.path());
+        assert_eq!(provider.list_keys().unwrap(), ["API_KEY", "PORT"]);
+        assert!(provider.is_available());
+
+        let missing = DirProvider::new(dir.path().join("absent"));
+        assert!(!missing.is_available());
+        assert!(missing.list_keys().is_err());
+        assert!(missing.get("PORT").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_dir_provider_priority() {
+        let provider = DirProvider::new("/run/secrets");
+        assert_eq!(provider.priority(), priority::CONFIG_FILE);
+        assert_eq!(provider.with_priority(5).priority(), 5);
+    }
+}
@@ -95,8 +95,11 @@ impl DotenvProvider {
     }
 
     /// Parse dotenv content (for testing).
+    ///
+    /// A leading UTF-8 BOM is ignored; `lines()` already handles CRLF.
     fn parse_dotenv_content(content: &str) -> HashMap<String, String> {
         let mut values = HashMap::new();
+        let content = content.strip_prefix('\u{FEFF}').unwrap_or(content);
 
         for line in content.lines() {
             let line = line.trim();
@@ -192,6 +195,16 @@ SINGLE='single quoted'
         assert_eq!(values.get("SINGLE"), Some(&"single quoted".to_string()));
     }
 
+    #[test]
+    fn test_parse_dotenv_content_bom_and_crlf() {
+        let content = "\u{FEFF}PORT=8080\r\nQUOTED=\"a b\"\r\nLAST=end\r";
+        let values = DotenvProvider::parse_dotenv_content(content);
+
+        assert_eq!(values.get("PORT"), Some(&"8080".to_string()));
+        assert_eq!(values.get("QUOTED"), Some(&"a b".to_string()));
+        assert_eq!(values.get("LAST"), Some(&"end".to_string()));
+    }
+
     #[test]
     fn test_dotenv_priority() {
         let provider = DotenvProvider::default();
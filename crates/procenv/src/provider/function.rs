@@ -0,0 +1,98 @@
+//! Closure-backed provider.
+
+use super::{Provider, ProviderResult, ProviderSource, ProviderValue, priority};
+
+/// Provider that wraps a lookup function.
+///
+/// Bridges an existing key lookup (for example a `config` crate source or
+/// an in-house registry) into the loader without a full [`Provider`] impl.
+/// Values are attributed to a custom source named after the provider.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use procenv::provider::{FnProvider, priority};
+///
+/// let settings = legacy_settings();
+/// let provider = FnProvider::new("legacy", move |key| settings.get(key).cloned())
+///     .with_priority(priority::CONFIG_FILE);
+/// ```
+pub struct FnProvider<F> {
+    name: String,
+    priority: u32,
+    lookup: F,
+}
+
+impl<F> FnProvider<F>
+where
+    F: Fn(&str) -> Option<String> + Send + Sync,
+{
+    /// Creates a provider named `name` that answers keys with `lookup`.
+    ///
+    /// The priority defaults to [`priority::CUSTOM`].
+    pub fn new(name: impl Into<String>, lookup: F) -> Self {
+        Self {
+            name: name.into(),
+            priority: priority::CUSTOM,
+            lookup,
+        }
+    }
+
+    /// Sets the priority (lower = queried earlier).
+    #[must_use]
+    pub const fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+
+        self
+    }
+}
+
+impl<F> Provider for FnProvider<F>
+where
+    F: Fn(&str) -> Option<String> + Send + Sync,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn get(&self, key: &str) -> ProviderResult<ProviderValue> {
+        Ok((self.lookup)(key).map(|value| {
+            ProviderValue::new(
+                value,
+                ProviderSource::custom(&self.name, Some(key.to_string())),
+            )
+        }))
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fn_provider_get() {
+        let provider = FnProvider::new("legacy", |key| (key == "PORT").then(|| "8080".to_string()));
+
+        let value = provider.get("PORT").unwrap().unwrap();
+        assert_eq!(value.value, "8080");
+        assert_eq!(
+            value.source,
+            ProviderSource::custom("legacy", Some("PORT".to_string()))
+        );
+        assert!(provider.get("HOST").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_fn_provider_priority() {
+        let provider = FnProvider::new("legacy", |_| None);
+        assert_eq!(provider.priority(), priority::CUSTOM);
+
+        let provider = provider.with_priority(priority::ENVIRONMENT);
+        assert_eq!(provider.priority(), priority::ENVIRONMENT);
+        assert_eq!(provider.name(), "legacy");
+    }
+}
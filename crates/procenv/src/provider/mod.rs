@@ -9,6 +9,8 @@
 //! - [`EnvProvider`] - Loads from environment variables
 //! - [`DotenvProvider`] - Loads from `.env` files
 //! - [`FileProvider`] - Loads from config files (TOML/JSON/YAML)
+//! - [`DirProvider`] - Loads one file per key from a directory (Docker/Kubernetes secrets)
+//! - [`FnProvider`] - Wraps a lookup closure (requires the `provider` feature)
 //!
 //! # Custom Providers
 //!
@@ -35,21 +37,27 @@
 
 #[cfg(feature = "async")]
 mod adapter;
+mod dir;
 #[cfg(feature = "dotenv")]
 mod dotenv;
 mod env;
 #[cfg(feature = "file")]
 mod file;
+#[cfg(feature = "provider")]
+mod function;
 
 #[cfg(feature = "dotenv")]
 pub use self::dotenv::DotenvProvider;
 #[cfg(feature = "async")]
 pub use adapter::BlockingAdapter;
+pub use dir::DirProvider;
 pub use env::EnvProvider;
 #[cfg(feature = "file")]
 pub use file::FileProvider;
+#[cfg(feature = "provider")]
+pub use function::FnProvider;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error as StdError;
 use std::fmt::{self, Display, Formatter, Result as FmtResult};
 use std::path::PathBuf;
@@ -66,7 +74,8 @@ use crate::Source;
 /// Centralized provider priority constants.
 ///
 /// Lower values = higher priority. Providers are queried in priority order,
-/// and the first one to return a value wins.
+/// and the first one to return a value wins. Providers sharing a priority
+/// are queried in the order they were registered with the loader.
 ///
 /// # Priority Hierarchy
 ///
@@ -170,6 +179,11 @@ pub enum ProviderSource {
 
         /// Optional path or key within the provider.
         path: Option<String>,
+
+        /// Extra details reported by the provider (e.g., lease TTL, version).
+        ///
+        /// Empty for providers that don't report any.
+        metadata: BTreeMap<String, String>,
     },
 }
 
@@ -177,9 +191,32 @@ impl ProviderSource {
     /// Creates a custom provider source.
     #[must_use]
     pub fn custom(provider: impl Into<String>, path: Option<String>) -> Self {
+        Self::custom_with_meta(provider, path, BTreeMap::new())
+    }
+
+    /// Creates a custom provider source carrying structured metadata.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let source = ProviderSource::custom_with_meta(
+    ///     "vault",
+    ///     Some("secret/app/db".into()),
+    ///     BTreeMap::from([("lease_ttl".into(), "3600".into()), ("version".into(), "3".into())]),
+    /// );
+    ///
+    /// assert_eq!(source.to_string(), "vault (secret/app/db) [lease_ttl=3600, version=3]");
+    /// ```
+    #[must_use]
+    pub fn custom_with_meta(
+        provider: impl Into<String>,
+        path: Option<String>,
+        metadata: BTreeMap<String, String>,
+    ) -> Self {
         Self::Custom {
             provider: provider.into(),
             path,
+            metadata,
         }
     }
 
@@ -208,16 +245,43 @@ impl ProviderSource {
     }
 
     /// Converts this provider source to a [`Source`] for compatibility.
+    ///
+    /// Non-empty metadata is appended to the provider name so it still shows
+    /// up in [`ConfigSources`](crate::ConfigSources).
     #[must_use]
     pub fn to_source(&self) -> Source {
         match self {
             Self::BuiltIn(s) => s.clone(),
 
-            Self::Custom { provider, .. } => Source::CustomProvider(provider.clone()),
+            Self::Custom {
+                provider, metadata, ..
+            } if metadata.is_empty() => Source::CustomProvider(provider.clone()),
+
+            Self::Custom {
+                provider, metadata, ..
+            } => Source::CustomProvider(format!("{provider} {}", MetadataDisplay(metadata))),
         }
     }
 }
 
+/// Compact `[key=value, ...]` rendering of provider metadata.
+struct MetadataDisplay<'a>(&'a BTreeMap<String, String>);
+
+impl Display for MetadataDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[")?;
+
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key}={value}")?;
+        }
+
+        write!(f, "]")
+    }
+}
+
 impl Display for ProviderSource {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
@@ -225,16 +289,20 @@ impl Display for ProviderSource {
 
             Self::Custom {
                 provider,
-                path: Some(path),
+                path,
+                metadata,
             } => {
-                write!(f, "{provider} ({path})")
-            }
+                write!(f, "{provider}")?;
 
-            Self::Custom {
-                provider,
-                path: None,
-            } => {
-                write!(f, "{provider}")
+                if let Some(path) = path {
+                    write!(f, " ({path})")?;
+                }
+
+                if !metadata.is_empty() {
+                    write!(f, " {}", MetadataDisplay(metadata))?;
+                }
+
+                Ok(())
             }
         }
     }
@@ -450,9 +518,49 @@ pub trait Provider: Send + Sync {
     ///
     /// If `true` (default), a `Ok(None)` result causes the loader to try
     /// the next provider. If `false`, the loader stops and uses the default.
+    ///
+    /// See [`authoritative()`](Self::authoritative) for how the two interact.
     fn fallthrough(&self) -> bool {
         true
     }
+
+    /// Returns whether this provider's answer for a key is final.
+    ///
+    /// A value found by any provider, empty or not, ends the chain and keeps
+    /// compiled defaults out, so present-but-empty means "explicitly empty".
+    /// An authoritative provider's answer is final even when `get` fails: the
+    /// error is recorded and lower-priority providers are not consulted, so
+    /// they cannot stand in for the source that owns the key.
+    ///
+    /// How the loader resolves a key against this provider:
+    ///
+    /// | Result | `fallthrough` | `authoritative` | Outcome |
+    /// |--------|---------------|-----------------|---------|
+    /// | value (even empty) | any | any | Value used, chain stops, no default |
+    /// | `Ok(None)` | `true` | any | Next provider is queried |
+    /// | `Ok(None)` | `false` | any | Chain stops, default used |
+    /// | `Err(_)` | any | `false` | Error recorded, next provider is queried |
+    /// | `Err(_)` | any | `true` | Error recorded, chain stops |
+    ///
+    /// Connection and unavailability failures depend on
+    /// [`required()`](Self::required) instead.
+    fn authoritative(&self) -> bool {
+        false
+    }
+
+    /// Returns whether loading must fail when this provider is down.
+    ///
+    /// By default (`false`) the provider is optional: when
+    /// [`is_available()`](Self::is_available) returns `false`, or `get`
+    /// fails with [`ProviderError::Connection`] or
+    /// [`ProviderError::Unavailable`], the loader skips it, queries the
+    /// lower-priority providers instead and lists it in
+    /// [`ConfigSources::skipped_providers`](crate::ConfigSources::skipped_providers).
+    /// A required provider that is down records an error and ends the search
+    /// for the key.
+    fn required(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -528,6 +636,13 @@ pub trait AsyncProvider: Send + Sync {
     fn priority(&self) -> u32 {
         priority::CUSTOM
     }
+
+    /// Returns whether loading must fail when this provider is down.
+    ///
+    /// See [`Provider::required`].
+    fn required(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -600,6 +715,33 @@ mod tests {
         assert_eq!(custom.to_string(), "vault (secret/app/db)");
     }
 
+    #[test]
+    fn test_provider_source_metadata() {
+        let metadata = BTreeMap::from([
+            ("version".to_string(), "3".to_string()),
+            ("lease_ttl".to_string(), "3600".to_string()),
+        ]);
+        let custom =
+            ProviderSource::custom_with_meta("vault", Some("secret/app/db".into()), metadata);
+
+        assert_eq!(
+            custom.to_string(),
+            "vault (secret/app/db) [lease_ttl=3600, version=3]"
+        );
+        assert_eq!(
+            custom.to_source(),
+            Source::CustomProvider("vault [lease_ttl=3600, version=3]".to_string())
+        );
+
+        // No metadata keeps the plain provider name
+        let plain = ProviderSource::custom_with_meta("vault", None, BTreeMap::new());
+        assert_eq!(plain.to_string(), "vault");
+        assert_eq!(
+            plain.to_source(),
+            Source::CustomProvider("vault".to_string())
+        );
+    }
+
     #[test]
     fn test_provider_value_builder() {
         let value = ProviderValue::new("test", ProviderSource::environment()).with_secret(true);
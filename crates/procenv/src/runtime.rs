@@ -0,0 +1,1005 @@
+//! Support code for macro-generated methods.
+//!
+//! The derive macro generates `keys()` and `has_key()` as `const fn` so
+//! they can be used in const contexts. String comparison and prefix
+//! stripping aren't `const` in `std`, so the generated code calls these
+//! helpers instead. Loaders for `default_from` fields call [`resolve_var`],
+//! `from_file_var` fields read through [`var_or_file`], `no_accumulate`
+//! loaders keep their first error in [`FirstError`], `base64` fields
+//! decode through [`decode_base64`], `sep_env` fields split through
+//! [`parse_list`], `coerce_bool_numeric` fields parse through
+//! [`parse_bool_numeric`], `merge_env` fields apply overrides through
+//! `merge_env_json`, `format = "auto"` fields deserialize through
+//! `deserialize_auto`, `validate_regex` fields match through `LazyRegex`,
+//! `redact_pattern` fields mask their errors through `redact_errors`,
+//! `from_env_verbose()` reports through [`write_source_lines`], and the
+//! generated `Debug` impl uses [`MaskedSeq`] and [`MaskedMap`] for secret
+//! collection fields. `allow_unmask` structs check [`unmask_requested`] in
+//! `Debug` and pass secret flags to errors through [`redact`].
+//! `allow_empty_struct` structs check [`any_var_set`] and fall back to
+//! [`empty_struct`], `sensitive_eq` fields verify through [`SensitiveEq`],
+//! `log_fingerprint` fields hash through `fingerprint`, and defaults
+//! containing `$` expand through [`expand_default`]. None of this is part
+//! of the public API.
+//!
+//! Generated loaders read variables through [`var`] rather than `std::env`,
+//! so `from_dotenv_str()` can swap in a parsed map for the current thread
+//! via [`with_dotenv_str`] without touching the process environment, and
+//! `case_insensitive_env` structs can ignore case through
+//! [`case_insensitive_scope`]. Loaders call [`refresh_folded_vars`] after
+//! loading dotenv files.
+//!
+//! The helpers that don't touch the environment (key lookups, prefix
+//! joining, trimming, base64 and list parsing) live in the `no_std`
+//! [`procenv_core`] crate and are re-exported here unchanged.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::env::VarError;
+use std::ffi::{OsStr, OsString};
+use std::fmt::{self, Debug, Formatter};
+use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+#[cfg(feature = "regex")]
+use crate::MaybeRedacted;
+use crate::{ConfigSources, ConfigValue, Error as ProcenvError};
+
+pub use procenv_core::{
+    Base64Error, DEFAULT_LIST_SEPARATOR, NumericBoolError, compare, constant_time_eq,
+    decode_base64, decode_base64_string, expand_vars, join_prefix, len_in_bounds,
+    parse_bool_numeric, parse_list, sorted_contains, strip_prefix, trim_value,
+};
+
+thread_local! {
+    /// Variables standing in for the process environment, if any.
+    static VARS: RefCell<Option<HashMap<String, String>>> = const { RefCell::new(None) };
+
+    /// Whether [`var`] falls back to matching names ignoring ASCII case.
+    static CASE_INSENSITIVE: Cell<bool> = const { Cell::new(false) };
+
+    /// Case-folded snapshot of the variables, built on the first miss.
+    static FOLDED: RefCell<Option<HashMap<String, FoldedVar>>> = const { RefCell::new(None) };
+}
+
+/// Reads a variable like `std::env::var`, honouring [`with_dotenv_str`]
+/// and [`case_insensitive_scope`].
+///
+/// # Errors
+///
+/// Returns [`VarError::NotPresent`] if the variable is unset, or
+/// [`VarError::NotUnicode`] if it isn't valid UTF-8.
+pub fn var<K: AsRef<OsStr>>(key: K) -> Result<String, VarError> {
+    let key = key.as_ref();
+    let result = VARS.with_borrow(|vars| {
+        vars.as_ref().map_or_else(
+            || std::env::var(key),
+            |vars| {
+                key.to_str()
+                    .and_then(|key| vars.get(key))
+                    .cloned()
+                    .ok_or(VarError::NotPresent)
+            },
+        )
+    });
+
+    match result {
+        Err(VarError::NotPresent) if case_insensitive_env() => folded_var(key)
+            .ok_or(VarError::NotPresent)?
+            .into_string()
+            .map_err(VarError::NotUnicode),
+        result => result,
+    }
+}
+
+/// Reads a variable like `std::env::var_os`, honouring [`with_dotenv_str`]
+/// and [`case_insensitive_scope`].
+#[must_use]
+pub fn var_os<K: AsRef<OsStr>>(key: K) -> Option<OsString> {
+    let key = key.as_ref();
+    let value = VARS.with_borrow(|vars| {
+        vars.as_ref().map_or_else(
+            || std::env::var_os(key),
+            |vars| {
+                key.to_str()
+                    .and_then(|key| vars.get(key))
+                    .map(OsString::from)
+            },
+        )
+    });
+
+    if value.is_none() && case_insensitive_env() {
+        return folded_var(key);
+    }
+
+    value
+}
+
+/// Whether [`var`] currently matches names ignoring ASCII case.
+#[must_use]
+pub fn case_insensitive_env() -> bool {
+    CASE_INSENSITIVE.get()
+}
+
+/// Makes [`var`] and [`var_os`] ignore ASCII case on this thread until the
+/// returned guard is dropped.
+///
+/// Backs `#[env_config(case_insensitive_env)]`. An exact match is always
+/// preferred; otherwise the name is matched against a case-folded snapshot
+/// of all variables, taken on the first miss. Dropping the guard restores
+/// the previous state, so scopes nest; the outermost one also drops the
+/// snapshot.
+pub fn case_insensitive_scope() -> CaseInsensitiveScope {
+    CaseInsensitiveScope(CASE_INSENSITIVE.replace(true))
+}
+
+/// Guard returned by [`case_insensitive_scope`].
+#[must_use = "case-insensitive lookup ends when the guard is dropped"]
+pub struct CaseInsensitiveScope(bool);
+
+impl Drop for CaseInsensitiveScope {
+    fn drop(&mut self) {
+        CASE_INSENSITIVE.set(self.0);
+        if !self.0 {
+            refresh_folded_vars();
+        }
+    }
+}
+
+/// A variable in the case-folded snapshot.
+#[derive(Debug)]
+struct FoldedVar {
+    /// The name whose value is used.
+    name: String,
+
+    /// The value of `name`.
+    value: OsString,
+
+    /// Other names that fold to the same key, in sorted order.
+    hidden: Vec<String>,
+}
+
+/// Finds `key` ignoring ASCII case among the current variables.
+///
+/// Names are folded into a snapshot on the first miss and the snapshot is
+/// reused until [`refresh_folded_vars`] or the end of the outermost
+/// [`case_insensitive_scope`]. When `key` matches several names, a warning
+/// names them all.
+fn folded_var(key: &OsStr) -> Option<OsString> {
+    let key = key.to_str()?;
+
+    FOLDED.with_borrow_mut(|folded| {
+        let var = folded
+            .get_or_insert_with(fold_vars)
+            .get(&key.to_ascii_lowercase())?;
+        if !var.hidden.is_empty() {
+            warn_case_collision(key, &var.name, &var.hidden);
+        }
+
+        Some(var.value.clone())
+    })
+}
+
+/// Folds the names of all current variables to ASCII lowercase.
+///
+/// The environment has no portable order, so when several names fold
+/// together the one that sorts first (by byte, so `App_Port` before
+/// `app_port`) wins, and the rest are kept as its hidden names.
+fn fold_vars() -> HashMap<String, FoldedVar> {
+    let mut vars: Vec<(String, OsString)> = VARS.with_borrow(|vars| {
+        vars.as_ref().map_or_else(
+            || {
+                std::env::vars_os()
+                    .filter_map(|(name, value)| Some((name.into_string().ok()?, value)))
+                    .collect()
+            },
+            |vars| {
+                vars.iter()
+                    .map(|(name, value)| (name.clone(), OsString::from(value)))
+                    .collect()
+            },
+        )
+    });
+    vars.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut folded: HashMap<String, FoldedVar> = HashMap::with_capacity(vars.len());
+    for (name, value) in vars {
+        match folded.entry(name.to_ascii_lowercase()) {
+            Entry::Occupied(mut winner) => winner.get_mut().hidden.push(name),
+            Entry::Vacant(slot) => {
+                slot.insert(FoldedVar {
+                    name,
+                    value,
+                    hidden: Vec::new(),
+                });
+            }
+        }
+    }
+
+    folded
+}
+
+/// Formats the warning for a lookup of `key` that matched several names.
+fn case_collision_message(key: &str, winner: &str, hidden: &[String]) -> String {
+    format!(
+        "procenv: `{key}` matches {winner}, {} ignoring case; using {winner}",
+        hidden.join(", ")
+    )
+}
+
+/// Reports a case-insensitive lookup that matched several names.
+#[cfg(feature = "tracing")]
+fn warn_case_collision(key: &str, winner: &str, hidden: &[String]) {
+    tracing::warn!("{}", case_collision_message(key, winner, hidden));
+}
+
+/// Without the `tracing` feature the warning goes to stderr.
+#[cfg(not(feature = "tracing"))]
+fn warn_case_collision(key: &str, winner: &str, hidden: &[String]) {
+    eprintln!("{}", case_collision_message(key, winner, hidden));
+}
+
+/// Drops the case-folded snapshot, so the next case-insensitive lookup sees
+/// variables set since it was taken.
+///
+/// Generated loaders call this after loading dotenv files.
+pub fn refresh_folded_vars() {
+    FOLDED.set(None);
+}
+
+/// Whether [`var`] currently reads from a [`with_dotenv_str`] map.
+///
+/// Generated loaders skip dotenv files while this is `true`.
+#[must_use]
+pub fn vars_overridden() -> bool {
+    VARS.with_borrow(Option::is_some)
+}
+
+/// Runs `f` with [`var`] reading only from `vars` on this thread.
+///
+/// The previous state is restored afterwards, even if `f` panics.
+fn with_vars<T>(vars: HashMap<String, String>, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<HashMap<String, String>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            VARS.with_borrow_mut(|vars| *vars = previous);
+            refresh_folded_vars();
+        }
+    }
+
+    let _restore = Restore(VARS.with_borrow_mut(|current| current.replace(vars)));
+    refresh_folded_vars();
+
+    f()
+}
+
+/// Parses `.env`-formatted `content` and runs `load` against it.
+///
+/// Backs the generated `from_dotenv_str()`: the parsed variables replace the
+/// process environment on this thread for the duration of `load`.
+///
+/// # Errors
+///
+/// Returns an error if `content` isn't valid dotenv syntax, or whatever
+/// `load` returns.
+#[allow(clippy::result_large_err)]
+pub fn with_dotenv_str<T>(
+    content: &str,
+    load: impl FnOnce() -> Result<T, ProcenvError>,
+) -> Result<T, ProcenvError> {
+    let vars = parse_dotenv_str(content)?;
+
+    with_vars(vars, load)
+}
+
+/// Parses dotenv text with `dotenvy`'s parser.
+#[allow(clippy::result_large_err)]
+#[cfg(feature = "dotenv")]
+fn parse_dotenv_str(content: &str) -> Result<HashMap<String, String>, ProcenvError> {
+    dotenvy::from_read_iter(content.as_bytes())
+        .collect::<Result<_, _>>()
+        .map_err(|e| ProcenvError::Provider {
+            provider: "dotenv".to_string(),
+            message: e.to_string(),
+            help: "use one KEY=value pair per line".to_string(),
+        })
+}
+
+/// Without the `dotenv` feature there is no parser to use.
+#[allow(clippy::result_large_err)]
+#[cfg(not(feature = "dotenv"))]
+fn parse_dotenv_str(_content: &str) -> Result<HashMap<String, String>, ProcenvError> {
+    Err(ProcenvError::Provider {
+        provider: "dotenv".to_string(),
+        message: "dotenv parsing is unavailable".to_string(),
+        help: "enable the `dotenv` feature of procenv".to_string(),
+    })
+}
+
+/// Names the variable a `default_from` field reads.
+///
+/// This is `primary` unless it is unset and `fallback` is set, so a field
+/// with neither var set still reports `primary` as missing.
+#[must_use]
+pub fn resolve_var<'a>(primary: &'a str, fallback: &'a str) -> &'a str {
+    if var_os(primary).is_none() && var_os(fallback).is_some() {
+        fallback
+    } else {
+        primary
+    }
+}
+
+/// Expands `${VAR}` references in the `default` of the field read from `key`.
+///
+/// References resolve through [`var`], and `$$` is a literal `$`.
+///
+/// # Errors
+///
+/// Returns [`ProcenvError::UndefinedDefaultVar`] naming the first reference
+/// that isn't set.
+#[allow(clippy::result_large_err)]
+pub fn expand_default(key: &str, default: &str) -> Result<String, ProcenvError> {
+    expand_vars(default, |name| var(name).ok())
+        .map_err(|reference| ProcenvError::undefined_default_var(key, reference))
+}
+
+/// Placeholder that replaces a secret value wherever one is emitted as text.
+///
+/// The same marker appears in `Debug` output, error messages and exported
+/// values.
+pub const REDACTED: &str = "<redacted>";
+
+/// Reads `key` for a `from_file_var` field, preferring the file named by `file_key`.
+///
+/// When `file_key` is set, the file's contents are returned with trailing
+/// newlines trimmed. If the file cannot be read, the error is recorded in
+/// `errors` and `key` is read instead, so an unset `key` is still reported.
+///
+/// # Errors
+///
+/// Returns the [`VarError`] from reading `key` when the file isn't used.
+pub fn var_or_file(
+    key: &str,
+    file_key: &str,
+    errors: &mut impl Extend<ProcenvError>,
+) -> Result<String, VarError> {
+    match var(file_key) {
+        Ok(path) => match std::fs::read_to_string(&path) {
+            Ok(contents) => return Ok(contents.trim_end_matches(['\r', '\n']).to_owned()),
+            Err(e) => errors.extend([ProcenvError::file_var_read(file_key, path, e)]),
+        },
+        Err(VarError::NotUnicode(raw)) => {
+            errors.extend([ProcenvError::invalid_utf8(file_key, &raw, false)]);
+        }
+        Err(VarError::NotPresent) => {}
+    }
+
+    var(key)
+}
+
+/// A `validate_regex` or `redact_pattern` pattern, compiled on first use.
+///
+/// The generated loader keeps one of these in a `static`, so each pattern
+/// is compiled at most once per process.
+#[cfg(feature = "regex")]
+#[derive(Debug)]
+pub struct LazyRegex {
+    pattern: &'static str,
+    compiled: std::sync::OnceLock<Result<regex::Regex, String>>,
+}
+
+#[cfg(feature = "regex")]
+impl LazyRegex {
+    /// Creates an uncompiled pattern.
+    #[must_use]
+    pub const fn new(pattern: &'static str) -> Self {
+        Self {
+            pattern,
+            compiled: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Returns the pattern source.
+    #[must_use]
+    pub const fn pattern(&self) -> &'static str {
+        self.pattern
+    }
+
+    /// Whether `value` matches, compiling the pattern if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the compiler's message if the pattern is not a valid regex.
+    pub fn is_match(&self, value: &str) -> Result<bool, String> {
+        self.compiled
+            .get_or_init(|| regex::Regex::new(self.pattern).map_err(|e| e.to_string()))
+            .as_ref()
+            .map(|re| re.is_match(value))
+            .map_err(Clone::clone)
+    }
+
+    /// Masks the parts of `value` captured by the pattern (`redact_pattern`).
+    ///
+    /// A pattern that does not compile masks the whole value, so a typo
+    /// never reveals the secret it was meant to hide.
+    #[must_use]
+    pub fn redact(&self, value: &str) -> MaybeRedacted {
+        self.compiled
+            .get_or_init(|| regex::Regex::new(self.pattern).map_err(|e| e.to_string()))
+            .as_ref()
+            .map_or(MaybeRedacted::Redacted, |re| {
+                MaybeRedacted::partial(value, re)
+            })
+    }
+}
+
+/// Masks the values carried by `errors` with a `redact_pattern`.
+///
+/// The generated loader calls this on the errors a field pushed, so parse
+/// and check failures show the value with only its secret part hidden.
+#[cfg(feature = "regex")]
+pub fn redact_errors(errors: &mut [ProcenvError], pattern: &LazyRegex) {
+    for value in errors.iter_mut().filter_map(ProcenvError::value_mut) {
+        if let Some(redacted) = value.as_str().map(|plain| pattern.redact(plain)) {
+            *value = redacted;
+        }
+    }
+}
+
+/// Reads the list separator for a `sep_env` field from `var`.
+///
+/// An unset or empty var yields [`DEFAULT_LIST_SEPARATOR`], since splitting
+/// on `""` would break the value into single characters.
+#[must_use]
+pub fn list_separator(sep_var: &str) -> String {
+    var(sep_var)
+        .ok()
+        .filter(|sep| !sep.is_empty())
+        .unwrap_or_else(|| DEFAULT_LIST_SEPARATOR.to_string())
+}
+
+/// Converts a merged config value into a list for a `sep_env` field.
+///
+/// Arrays from config files are parsed item by item; anything else (such
+/// as an env var string) is split like [`parse_list`].
+///
+/// # Errors
+///
+/// Returns the parse error of the first item that fails.
+pub fn parse_list_value<T: FromStr>(
+    value: &ConfigValue,
+    separator: &str,
+) -> Result<Vec<T>, Box<dyn std::error::Error + Send + Sync>>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    match value {
+        ConfigValue::List(items) => items.iter().map(|item| item.extract::<T>("")).collect(),
+        other => parse_list(&other.extract_string(), separator).map_err(Into::into),
+    }
+}
+
+/// Deserializes a `merge_env` field's JSON after applying per-key overrides.
+///
+/// Each key of an object in `raw` can be replaced by `<var>_<KEY>`, and
+/// nested keys by chaining segments, so `FEATURES_DB_HOST` replaces
+/// `db.host` in `FEATURES`. Keys are uppercased with other characters than
+/// ASCII letters and digits mapped to `_`. Only keys present in `raw` are
+/// looked up. Overrides win over the base object: a string is replaced
+/// verbatim, anything else by the override parsed as JSON (or as a string
+/// if that fails).
+///
+/// # Errors
+///
+/// Returns the error from parsing `raw` or deserializing the merged value.
+#[cfg(feature = "serde")]
+pub fn merge_env_json<T: serde::de::DeserializeOwned>(
+    raw: &str,
+    var: &str,
+) -> Result<T, serde_json::Error> {
+    let mut value = serde_json::from_str(raw)?;
+    merge_env_overrides(&mut value, var);
+    serde_json::from_value(value)
+}
+
+/// Replaces the keys of `value` from `<prefix>_<KEY>` vars, recursively.
+#[cfg(feature = "serde")]
+fn merge_env_overrides(value: &mut serde_json::Value, prefix: &str) {
+    use serde_json::Value;
+
+    let Value::Object(map) = value else {
+        return;
+    };
+
+    for (key, child) in map.iter_mut() {
+        let key: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let child_var = format!("{prefix}_{key}");
+
+        match var(&child_var) {
+            Ok(raw) if child.is_string() => *child = Value::String(raw),
+            Ok(raw) => *child = serde_json::from_str(&raw).unwrap_or(Value::String(raw)),
+            Err(_) => merge_env_overrides(child, &child_var),
+        }
+    }
+}
+
+/// Why no format could deserialize a `format = "auto"` field value.
+///
+/// Holds one `(format, error)` pair per attempt, in the order tried.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct AutoFormatError {
+    /// Each attempted format's name and error message.
+    pub attempts: Vec<(&'static str, String)>,
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for AutoFormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("no format matched")?;
+
+        for (i, (format, error)) in self.attempts.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { "; " };
+            write!(f, "{sep}{format}: {}", error.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for AutoFormatError {}
+
+/// Deserializes a `format = "auto"` field with the first format that works.
+///
+/// Tries JSON, then YAML (with the `yaml` feature), then TOML (with the
+/// `toml` feature). YAML accepts most plain text as a string, so a `String`
+/// target rarely reaches TOML.
+///
+/// # Errors
+///
+/// Returns an [`AutoFormatError`] listing every attempt if none succeeds.
+#[cfg(feature = "serde")]
+pub fn deserialize_auto<T: serde::de::DeserializeOwned>(raw: &str) -> Result<T, AutoFormatError> {
+    let mut attempts = Vec::new();
+
+    match serde_json::from_str(raw) {
+        Ok(value) => return Ok(value),
+        Err(e) => attempts.push(("JSON", e.to_string())),
+    }
+
+    #[cfg(feature = "yaml")]
+    match serde_saphyr::from_str(raw) {
+        Ok(value) => return Ok(value),
+        Err(e) => attempts.push(("YAML", e.to_string())),
+    }
+
+    #[cfg(feature = "toml")]
+    match toml::from_str(raw) {
+        Ok(value) => return Ok(value),
+        Err(e) => attempts.push(("TOML", e.to_string())),
+    }
+
+    Err(AutoFormatError { attempts })
+}
+
+/// Runs a field's `deserialize_with` function over its parsed value.
+///
+/// `parsed` is the field value read by its `format` into a JSON value, which
+/// `f` then deserializes like any other [`serde::Deserializer`].
+///
+/// # Errors
+///
+/// Returns the parse error from `parsed` as a [`serde_json::Error`], or the
+/// error returned by `f`.
+#[cfg(feature = "serde")]
+pub fn deserialize_with<T, E: fmt::Display>(
+    parsed: Result<serde_json::Value, E>,
+    f: impl FnOnce(serde_json::Value) -> Result<T, serde_json::Error>,
+) -> Result<T, serde_json::Error> {
+    let value = parsed.map_err(serde::de::Error::custom)?;
+    f(value)
+}
+
+/// Writes one `field <- source (VAR)` line per field for `from_env_verbose()`.
+///
+/// Only field names, sources and variable names are written, never values,
+/// so secret fields need no masking.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn write_source_lines(out: &mut impl Write, sources: &ConfigSources) -> io::Result<()> {
+    for (field, source) in sources.iter() {
+        writeln!(out, "{field} <- {} ({})", source.source, source.var_name)?;
+    }
+
+    Ok(())
+}
+
+/// Env var that unmasks secrets for `#[env_config(allow_unmask)]` structs.
+pub const UNMASK_VAR: &str = "PROCENV_UNMASK";
+
+/// Whether [`UNMASK_VAR`] is set to `1` in the process environment.
+///
+/// Only structs compiled with `#[env_config(allow_unmask)]` consult this.
+#[must_use]
+pub fn unmask_requested() -> bool {
+    std::env::var_os(UNMASK_VAR).is_some_and(|value| value == "1")
+}
+
+/// Whether an error should redact a field's value.
+///
+/// Secret values stay redacted unless the struct opted in with
+/// `allow_unmask` and [`unmask_requested`] is `true`.
+#[must_use]
+pub fn redact(secret: bool, allow_unmask: bool) -> bool {
+    secret && !(allow_unmask && unmask_requested())
+}
+
+/// Whether any of an `allow_empty_struct` struct's vars is set.
+///
+/// Each name in `vars` is joined with `prefix` as the loader would, so a
+/// runtime-prefixed nested struct checks the names it actually reads.
+#[must_use]
+pub fn any_var_set(vars: &[&str], prefix: Option<&str>, separator: Option<&str>) -> bool {
+    vars.iter()
+        .any(|name| var_os(join_prefix(prefix.unwrap_or(""), name, separator)).is_some())
+}
+
+/// Bound on structs marked `#[env_config(allow_empty_struct)]`.
+///
+/// Spelled as its own trait so a missing `Default` impl is reported in terms
+/// of the attribute that needs it.
+#[diagnostic::on_unimplemented(
+    message = "`allow_empty_struct` requires `{Self}` to implement `Default`",
+    label = "`{Self}` is loaded as `Default::default()` when none of its vars are set",
+    note = "derive or implement `Default` for `{Self}`"
+)]
+pub trait EmptyStruct: Default {}
+
+impl<T: Default> EmptyStruct for T {}
+
+/// The value an `allow_empty_struct` struct loads as when none of its vars are set.
+#[must_use]
+pub fn empty_struct<T: EmptyStruct>() -> T {
+    T::default()
+}
+
+/// Error collector of `no_accumulate` loaders, keeping only the first error.
+///
+/// The generated code pushes errors into it as it would into a `Vec`, and
+/// follows each loading step with [`FirstError::check`], so loading stops
+/// at the first failure. Derefs to the recorded error as a slice.
+#[derive(Debug, Default)]
+pub struct FirstError(Option<ProcenvError>);
+
+impl FirstError {
+    /// An empty collector.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(None)
+    }
+
+    /// Records `error`, unless an error is already recorded.
+    pub fn push(&mut self, error: ProcenvError) {
+        self.0.get_or_insert(error);
+    }
+
+    /// Takes the recorded error, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns the recorded error.
+    #[allow(clippy::result_large_err)]
+    pub fn check(&mut self) -> Result<(), ProcenvError> {
+        self.0.take().map_or(Ok(()), Err)
+    }
+}
+
+impl Extend<ProcenvError> for FirstError {
+    fn extend<I: IntoIterator<Item = ProcenvError>>(&mut self, errors: I) {
+        if let Some(error) = errors.into_iter().next() {
+            self.push(error);
+        }
+    }
+}
+
+impl Deref for FirstError {
+    type Target = [ProcenvError];
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_slice()
+    }
+}
+
+impl DerefMut for FirstError {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.as_mut_slice()
+    }
+}
+
+/// Constant-time comparison behind the generated `verify_<field>()` of
+/// `sensitive_eq` fields.
+///
+/// Compares the stored string with [`constant_time_eq`]. An unset optional
+/// field matches nothing.
+pub trait SensitiveEq {
+    /// Whether `candidate` equals the stored value.
+    fn sensitive_eq(&self, candidate: &str) -> bool;
+}
+
+impl SensitiveEq for str {
+    fn sensitive_eq(&self, candidate: &str) -> bool {
+        constant_time_eq(self.as_bytes(), candidate.as_bytes())
+    }
+}
+
+impl SensitiveEq for String {
+    fn sensitive_eq(&self, candidate: &str) -> bool {
+        self.as_str().sensitive_eq(candidate)
+    }
+}
+
+impl SensitiveEq for Box<str> {
+    fn sensitive_eq(&self, candidate: &str) -> bool {
+        (**self).sensitive_eq(candidate)
+    }
+}
+
+impl SensitiveEq for std::sync::Arc<str> {
+    fn sensitive_eq(&self, candidate: &str) -> bool {
+        (**self).sensitive_eq(candidate)
+    }
+}
+
+impl SensitiveEq for std::borrow::Cow<'_, str> {
+    fn sensitive_eq(&self, candidate: &str) -> bool {
+        (**self).sensitive_eq(candidate)
+    }
+}
+
+impl<T: SensitiveEq> SensitiveEq for Option<T> {
+    fn sensitive_eq(&self, candidate: &str) -> bool {
+        self.as_ref()
+            .is_some_and(|value| value.sensitive_eq(candidate))
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl SensitiveEq for secrecy::SecretString {
+    fn sensitive_eq(&self, candidate: &str) -> bool {
+        secrecy::ExposeSecret::expose_secret(self).sensitive_eq(candidate)
+    }
+}
+
+/// Hex digits kept from the SHA-256 behind a `log_fingerprint` method.
+#[cfg(feature = "fingerprint")]
+pub const FINGERPRINT_LEN: usize = 12;
+
+/// Fingerprint behind the generated `<field>_fingerprint()` of
+/// `log_fingerprint` fields.
+///
+/// Returns the first [`FINGERPRINT_LEN`] hex digits of the SHA-256 of the
+/// whole value, the same as `printf %s "$VALUE" | sha256sum | cut -c1-12`.
+/// Equal values always share a fingerprint, while 48 bits are too few to
+/// recover the value from; a guessable secret can still be confirmed by
+/// hashing the guess, so this is no substitute for a strong secret.
+#[cfg(feature = "fingerprint")]
+#[must_use]
+pub fn fingerprint(value: &str) -> String {
+    use sha2::Digest;
+
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let hash = sha2::Sha256::digest(value.as_bytes());
+
+    hash[..FINGERPRINT_LEN / 2]
+        .iter()
+        .flat_map(|byte| [HEX[usize::from(byte >> 4)], HEX[usize::from(byte & 0x0f)]])
+        .map(char::from)
+        .collect()
+}
+
+/// Prints [`REDACTED`] verbatim (no quotes) in `Debug` output.
+struct Mask;
+
+impl Debug for Mask {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// Debug view of a secret sequence with this many elements: `[<redacted>, <redacted>]`.
+///
+/// Only the length is kept, so the output never depends on element values.
+pub struct MaskedSeq(pub usize);
+
+impl Debug for MaskedSeq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries((0..self.0).map(|_| Mask)).finish()
+    }
+}
+
+/// Debug view of a secret map with this many entries: `{..: <redacted>}`.
+///
+/// Keys are hidden as well as values.
+pub struct MaskedMap(pub usize);
+
+impl Debug for MaskedMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        struct Elided;
+
+        impl Debug for Elided {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("..")
+            }
+        }
+
+        f.debug_map()
+            .entries((0..self.0).map(|_| (Elided, Mask)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_source_lines() {
+        use crate::{Source, ValueSource};
+
+        let mut sources = ConfigSources::new();
+        sources.add("port", ValueSource::new("PORT", Source::Default));
+        sources.add("db.url", ValueSource::new("DB_URL", Source::Environment));
+
+        let mut out = Vec::new();
+        write_source_lines(&mut out, &sources).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "port <- Default value (PORT)\ndb.url <- Environment variable (DB_URL)\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_lazy_regex() {
+        static VERSION: LazyRegex = LazyRegex::new(r"^v\d+$");
+        static BROKEN: LazyRegex = LazyRegex::new("(unclosed");
+
+        assert_eq!(VERSION.is_match("v12"), Ok(true));
+        assert_eq!(VERSION.is_match("12"), Ok(false));
+        assert_eq!(VERSION.pattern(), r"^v\d+$");
+        assert!(BROKEN.is_match("anything").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "fingerprint")]
+    fn test_fingerprint() {
+        // printf %s hunter2 | sha256sum | cut -c1-12
+        assert_eq!(fingerprint("hunter2"), "f52fbd32b2b3");
+        assert_eq!(fingerprint(""), "e3b0c44298fc");
+        assert_eq!(fingerprint("hunter2").len(), FINGERPRINT_LEN);
+    }
+
+    #[test]
+    fn test_masked_collections() {
+        assert_eq!(format!("{:?}", MaskedSeq(2)), "[<redacted>, <redacted>]");
+        assert_eq!(format!("{:?}", MaskedSeq(0)), "[]");
+        assert_eq!(format!("{:?}", MaskedMap(1)), "{..: <redacted>}");
+        assert_eq!(format!("{:?}", MaskedMap(0)), "{}");
+    }
+
+    #[test]
+    fn test_first_error_keeps_first() {
+        let mut errors = FirstError::new();
+        assert!(errors.check().is_ok());
+
+        errors.push(ProcenvError::missing("FIRST"));
+        errors.extend([ProcenvError::missing("SECOND")]);
+        assert_eq!(errors.len(), 1);
+
+        let err = errors.check().unwrap_err();
+        assert!(matches!(err, ProcenvError::Missing { ref var, .. } if var == "FIRST"));
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_with_vars_replaces_environment() {
+        let vars = HashMap::from([("RUNTIME_TEST_VAR".to_string(), "set".to_string())]);
+
+        let (inner, missing) = with_vars(vars, || {
+            assert!(vars_overridden());
+            (var("RUNTIME_TEST_VAR"), var_os("PATH"))
+        });
+
+        assert_eq!(inner.as_deref(), Ok("set"));
+        assert_eq!(missing, None);
+        assert!(!vars_overridden());
+        assert_eq!(var("RUNTIME_TEST_VAR"), Err(VarError::NotPresent));
+    }
+
+    #[test]
+    fn test_folded_snapshot_is_reused_until_refreshed() {
+        let vars = HashMap::from([
+            ("app_port".to_string(), "1".to_string()),
+            ("App_Port".to_string(), "2".to_string()),
+        ]);
+
+        with_vars(vars, || {
+            let _scope = case_insensitive_scope();
+            assert_eq!(var("APP_PORT").as_deref(), Ok("2"));
+
+            VARS.with_borrow_mut(|vars| {
+                vars.as_mut()
+                    .unwrap()
+                    .insert("app_host".to_string(), "h".to_string())
+            });
+            assert_eq!(var("APP_HOST"), Err(VarError::NotPresent));
+
+            refresh_folded_vars();
+            assert_eq!(var("APP_HOST").as_deref(), Ok("h"));
+        });
+        assert!(FOLDED.with_borrow(Option::is_none));
+    }
+
+    #[test]
+    fn test_folded_collision_keeps_first_sorted_name() {
+        let vars = HashMap::from([
+            ("app_port".to_string(), "1".to_string()),
+            ("APP_port".to_string(), "2".to_string()),
+            ("App_Port".to_string(), "3".to_string()),
+        ]);
+
+        with_vars(vars, || {
+            let folded = fold_vars();
+            let var = &folded["app_port"];
+            assert_eq!(var.name, "APP_port");
+            assert_eq!(var.value, "2");
+            assert_eq!(var.hidden, ["App_Port", "app_port"]);
+
+            assert_eq!(
+                case_collision_message("APP_PORT", &var.name, &var.hidden),
+                "procenv: `APP_PORT` matches APP_port, App_Port, app_port ignoring case; \
+                 using APP_port"
+            );
+        });
+    }
+
+    #[cfg(feature = "dotenv")]
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_with_dotenv_str() {
+        let port = with_dotenv_str("# comment\nPORT=8080\nNAME=\"a b\"\n", || {
+            Ok(format!("{}/{}", var("PORT").unwrap(), var("NAME").unwrap()))
+        });
+        assert_eq!(port.unwrap(), "8080/a b");
+
+        let err = with_dotenv_str("not valid", || Ok(())).unwrap_err();
+        assert!(matches!(err, ProcenvError::Provider { .. }));
+    }
+
+    #[test]
+    fn test_parse_list_value() {
+        let list = ConfigValue::List(vec![ConfigValue::Integer(1), ConfigValue::Integer(2)]);
+        assert_eq!(parse_list_value::<u8>(&list, ";").unwrap(), vec![1, 2]);
+
+        let text = ConfigValue::String("3;4".to_string());
+        assert_eq!(parse_list_value::<u8>(&text, ";").unwrap(), vec![3, 4]);
+    }
+}
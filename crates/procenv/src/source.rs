@@ -127,7 +127,7 @@ impl Display for Source {
 /// let source = ValueSource::new("DATABASE_URL", Source::Environment);
 /// println!("{}", source);  // "DATABASE_URL: Environment variable"
 /// ```
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ValueSource {
     /// The environment variable name (e.g., `"DATABASE_URL"`).
     pub var_name: String,
@@ -211,6 +211,9 @@ impl Display for ValueSource {
 #[derive(Clone, Debug, Default)]
 pub struct ConfigSources {
     entries: Vec<(String, ValueSource)>,
+    profile: Option<String>,
+    skipped_providers: Vec<String>,
+    reload_immutable: Vec<String>,
 }
 
 impl ConfigSources {
@@ -219,6 +222,9 @@ impl ConfigSources {
     pub const fn new() -> Self {
         Self {
             entries: Vec::new(),
+            profile: None,
+            skipped_providers: Vec::new(),
+            reload_immutable: Vec::new(),
         }
     }
 
@@ -249,6 +255,55 @@ impl ConfigSources {
         }
     }
 
+    /// Records the profile that was active while loading.
+    pub fn set_profile(&mut self, profile: Option<String>) {
+        self.profile = profile;
+    }
+
+    /// Returns the profile that was active while loading.
+    ///
+    /// This is the resolved profile, including the `default_profile`
+    /// fallback, or `None` if the struct has no `profile_env` or no
+    /// profile was selected.
+    #[must_use]
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Records that an optional provider was unreachable and skipped.
+    ///
+    /// Each provider is listed once, however many keys it was skipped for.
+    pub fn add_skipped_provider(&mut self, provider: impl Into<String>) {
+        let provider = provider.into();
+        if !self.skipped_providers.contains(&provider) {
+            self.skipped_providers.push(provider);
+        }
+    }
+
+    /// Returns the optional providers that were skipped because they were
+    /// unavailable or could not be reached, in the order first skipped.
+    #[must_use]
+    pub fn skipped_providers(&self) -> &[String] {
+        &self.skipped_providers
+    }
+
+    /// Records the fields marked `reload_immutable`, dotted for nested fields.
+    ///
+    /// Generated loaders record the whole struct's list, so
+    /// [`extend_nested`](Self::extend_nested) leaves it unchanged.
+    pub fn set_reload_immutable(&mut self, fields: Vec<String>) {
+        self.reload_immutable = fields;
+    }
+
+    /// Returns the fields marked `reload_immutable`, dotted for nested fields.
+    ///
+    /// The watcher refuses to start when any are listed and no
+    /// `reject_immutable_changes` check is registered.
+    #[must_use]
+    pub fn reload_immutable(&self) -> &[String] {
+        &self.reload_immutable
+    }
+
     /// Returns all entries as a slice.
     ///
     /// Each entry is a tuple of `(field_name, ValueSource)`.
@@ -281,6 +336,88 @@ impl ConfigSources {
             .iter()
             .map(|(name, source)| (name.as_str(), source))
     }
+
+    /// Reports the fields whose [`Source`] differs between two loads.
+    ///
+    /// `self` is the earlier snapshot and `other` the later one. Fields are
+    /// listed in `self`'s order, followed by fields only present in `other`.
+    /// A field missing from one snapshot has `None` on that side.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use procenv::{ConfigSources, Source, ValueSource};
+    ///
+    /// let mut before = ConfigSources::new();
+    /// before.add("port", ValueSource::new("PORT", Source::Default));
+    ///
+    /// let mut after = ConfigSources::new();
+    /// after.add("port", ValueSource::new("PORT", Source::Environment));
+    ///
+    /// let diff = before.diff(&after);
+    /// assert_eq!(diff.len(), 1);
+    /// assert_eq!(diff[0].to_string(), "port: Default value -> Environment variable");
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Vec<SourceDiff> {
+        let changed = self.iter().filter_map(|(field, old)| {
+            let new = other.get(field);
+
+            (new.map(|n| &n.source) != Some(&old.source)).then(|| SourceDiff {
+                field: field.to_string(),
+                old: Some(old.clone()),
+                new: new.cloned(),
+            })
+        });
+
+        let added = other
+            .iter()
+            .filter(|(field, _)| self.get(field).is_none())
+            .map(|(field, new)| SourceDiff {
+                field: field.to_string(),
+                old: None,
+                new: Some(new.clone()),
+            });
+
+        changed.chain(added).collect()
+    }
+}
+
+/// A field whose source changed between two [`ConfigSources`] snapshots.
+///
+/// Returned by [`ConfigSources::diff`].
+///
+/// # Display Output
+///
+/// ```text
+/// port: Default value -> Environment variable
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SourceDiff {
+    /// The field name (dotted for nested fields).
+    pub field: String,
+
+    /// Source in the earlier snapshot, or `None` if the field was absent.
+    pub old: Option<ValueSource>,
+
+    /// Source in the later snapshot, or `None` if the field was absent.
+    pub new: Option<ValueSource>,
+}
+
+impl Display for SourceDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let side = |source: Option<&ValueSource>| {
+            source.map_or_else(|| "absent".to_string(), |s| s.source.to_string())
+        };
+
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.field,
+            side(self.old.as_ref()),
+            side(self.new.as_ref())
+        )
+    }
 }
 
 impl Display for ConfigSources {
@@ -307,6 +444,10 @@ impl Display for ConfigSources {
             )?;
         }
 
+        for provider in &self.skipped_providers {
+            writeln!(f, "  (skipped unavailable provider: {provider})")?;
+        }
+
         Ok(())
     }
 }
@@ -379,6 +520,33 @@ mod tests {
         assert!(sources.get("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_config_sources_diff() {
+        let mut before = ConfigSources::new();
+        before.add("port", ValueSource::new("PORT", Source::Default));
+        before.add("host", ValueSource::new("HOST", Source::Environment));
+        before.add("debug", ValueSource::new("DEBUG", Source::Default));
+
+        let mut after = ConfigSources::new();
+        after.add("port", ValueSource::new("PORT", Source::Environment));
+        after.add("host", ValueSource::new("HOST", Source::Environment));
+        after.add("db.url", ValueSource::new("DB_URL", Source::Environment));
+
+        let diff = before.diff(&after);
+        let fields: Vec<_> = diff.iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(fields, ["port", "debug", "db.url"]);
+
+        assert_eq!(diff[0].old.as_ref().unwrap().source, Source::Default);
+        assert_eq!(diff[0].new.as_ref().unwrap().source, Source::Environment);
+        assert_eq!(diff[1].to_string(), "debug: Default value -> absent");
+        assert_eq!(
+            diff[2].to_string(),
+            "db.url: absent -> Environment variable"
+        );
+
+        assert!(before.diff(&before).is_empty());
+    }
+
     #[test]
     fn test_config_sources_iter() {
         let mut sources = ConfigSources::new();
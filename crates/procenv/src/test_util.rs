@@ -0,0 +1,155 @@
+//! Helpers for testing `from_env()` against a controlled environment.
+//!
+//! Tests that call `std::env::set_var` run into two footguns: it is
+//! `unsafe` since Rust 2024, because another thread may be reading the
+//! environment at the same time, and the test harness runs tests on
+//! parallel threads, so one test's variables leak into another's load.
+//! Forgetting to remove a variable afterwards leaks it into every later
+//! test as well.
+//!
+//! [`EnvGuard`] and [`with_env`] handle both: every guard holds one
+//! process-wide lock while its variables are set, and dropping it puts
+//! the previous values back, even when the test panics.
+//!
+//! ```rust
+//! use procenv::EnvConfig;
+//! use procenv::test_util::with_env;
+//!
+//! #[derive(EnvConfig)]
+//! struct Config {
+//!     #[env(var = "TEST_UTIL_DOC_PORT")]
+//!     port: u16,
+//! }
+//!
+//! with_env(&[("TEST_UTIL_DOC_PORT", "8080")], || {
+//!     assert_eq!(Config::from_env().unwrap().port, 8080);
+//! });
+//! assert!(std::env::var("TEST_UTIL_DOC_PORT").is_err());
+//! ```
+//!
+//! The module is behind the `test-util` feature, which `full` leaves out so
+//! the process-wide lock never ends up in production builds. Enable it for
+//! tests only:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! procenv = { version = "0.1", features = ["test-util"] }
+//! ```
+//!
+//! The lock only orders code that goes through this module. Reading or
+//! writing the environment on another thread without a guard is as unsafe
+//! as ever.
+
+use std::cell::Cell;
+use std::ffi::OsString;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Serializes every [`EnvGuard`] in the process.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    /// Whether this thread holds [`ENV_LOCK`], to fail fast instead of deadlocking.
+    static HOLDS_LOCK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Sets environment variables for the lifetime of the guard.
+///
+/// Construction waits for any other guard to be dropped, records the
+/// current value of each variable, then sets it. Dropping the guard
+/// restores the recorded values, removing variables that were unset.
+///
+/// Guards don't nest: creating a second one on a thread that already holds
+/// one panics, since it would otherwise wait on itself forever. Put every
+/// variable a test needs in one guard.
+///
+/// ```rust
+/// use procenv::test_util::EnvGuard;
+///
+/// {
+///     let _guard = EnvGuard::new(&[("TEST_UTIL_GUARD_HOST", "db.internal")]);
+///     assert_eq!(std::env::var("TEST_UTIL_GUARD_HOST").unwrap(), "db.internal");
+/// }
+/// assert!(std::env::var("TEST_UTIL_GUARD_HOST").is_err());
+/// ```
+pub struct EnvGuard {
+    saved: Vec<(String, Option<OsString>)>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl EnvGuard {
+    /// Sets each `(key, value)` pair until the guard is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this thread already holds an `EnvGuard`.
+    #[must_use = "the variables are restored as soon as the guard is dropped"]
+    pub fn new(vars: &[(&str, &str)]) -> Self {
+        assert!(
+            !HOLDS_LOCK.get(),
+            "EnvGuard is not reentrant; set all variables in one guard"
+        );
+
+        // A test that panicked while holding the lock has still restored its vars
+        let lock = ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        HOLDS_LOCK.set(true);
+
+        // Built first so a `set_var` panic on a bad key still restores the rest
+        let mut guard = Self {
+            saved: Vec::with_capacity(vars.len()),
+            _lock: lock,
+        };
+
+        for (key, value) in vars {
+            guard
+                .saved
+                .push(((*key).to_string(), std::env::var_os(key)));
+            // SAFETY: `ENV_LOCK` keeps other guards from touching the
+            // environment until this one is dropped
+            unsafe { std::env::set_var(key, value) };
+        }
+
+        guard
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        // Restore in reverse so a key listed twice ends at its original value
+        for (key, previous) in self.saved.drain(..).rev() {
+            // SAFETY: the lock is still held; it is released after this body
+            unsafe {
+                match previous {
+                    Some(value) => std::env::set_var(&key, value),
+                    None => std::env::remove_var(&key),
+                }
+            }
+        }
+        HOLDS_LOCK.set(false);
+    }
+}
+
+impl Debug for EnvGuard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Only the keys; the saved values may be secrets
+        f.debug_struct("EnvGuard")
+            .field(
+                "keys",
+                &self.saved.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+/// Runs `f` with the given variables set, restoring the previous values after.
+///
+/// This is [`EnvGuard`] scoped to a closure, so calls from parallel tests
+/// run one at a time and a panic inside `f` still cleans up.
+///
+/// # Panics
+///
+/// Panics if called while this thread already holds an [`EnvGuard`].
+pub fn with_env<R>(vars: &[(&str, &str)], f: impl FnOnce() -> R) -> R {
+    let _guard = EnvGuard::new(vars);
+    f()
+}
@@ -0,0 +1,195 @@
+//! Load timing for profiling configuration startup.
+//!
+//! Structs marked `#[env_config(timed)]` get a generated `from_env_timed()`
+//! that records how long each field took to resolve, along with the total
+//! load time. This makes slow providers or expensive parsers easy to spot.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! #[derive(EnvConfig)]
+//! #[env_config(timed)]
+//! struct Config {
+//!     #[env(var = "DATABASE_URL")]
+//!     database_url: String,
+//! }
+//!
+//! let (result, timings) = Config::from_env_timed();
+//! eprintln!("{timings}");
+//! let config = result?;
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+/// Time spent resolving a single field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldTiming {
+    /// The struct field name.
+    pub field: String,
+
+    /// Time spent reading, parsing and checking the field's value.
+    pub duration: Duration,
+}
+
+/// Per-field and total timings from a `from_env_timed()` call.
+///
+/// Fields appear in declaration order. Flattened fields are timed as a
+/// single entry covering the whole nested struct.
+///
+/// `Display` renders an aligned table in milliseconds. With the `serde`
+/// feature, the timings serialize to a flat structure with millisecond
+/// floats, suitable for a startup metrics log.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LoadTimings {
+    fields: Vec<FieldTiming>,
+    total: Duration,
+}
+
+impl LoadTimings {
+    /// Creates an empty set of timings.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            fields: Vec::new(),
+            total: Duration::ZERO,
+        }
+    }
+
+    /// Records the resolution time of one field.
+    pub fn record(&mut self, field: impl Into<String>, duration: Duration) {
+        self.fields.push(FieldTiming {
+            field: field.into(),
+            duration,
+        });
+    }
+
+    /// Sets the total load time.
+    pub const fn set_total(&mut self, total: Duration) {
+        self.total = total;
+    }
+
+    /// Returns the per-field timings in declaration order.
+    #[must_use]
+    pub fn fields(&self) -> &[FieldTiming] {
+        &self.fields
+    }
+
+    /// Returns the resolution time of a field, if it was recorded.
+    #[must_use]
+    pub fn get(&self, field: &str) -> Option<Duration> {
+        self.fields
+            .iter()
+            .find(|t| t.field == field)
+            .map(|t| t.duration)
+    }
+
+    /// Returns the total load time, including dotenv and profile setup.
+    #[must_use]
+    pub const fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Returns the field that took longest to resolve.
+    #[must_use]
+    pub fn slowest(&self) -> Option<&FieldTiming> {
+        self.fields.iter().max_by_key(|t| t.duration)
+    }
+}
+
+/// Formats a duration as fractional milliseconds.
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+impl Display for LoadTimings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let width = self
+            .fields
+            .iter()
+            .map(|t| t.field.len())
+            .max()
+            .unwrap_or(0)
+            .max("total".len());
+
+        for timing in &self.fields {
+            writeln!(
+                f,
+                "{:<width$}  {:>10.3}ms",
+                timing.field,
+                millis(timing.duration)
+            )?;
+        }
+
+        write!(f, "{:<width$}  {:>10.3}ms", "total", millis(self.total))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FieldTiming {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("FieldTiming", 2)?;
+        state.serialize_field("field", &self.field)?;
+        state.serialize_field("ms", &millis(self.duration))?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for LoadTimings {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("LoadTimings", 2)?;
+        state.serialize_field("fields", &self.fields)?;
+        state.serialize_field("total_ms", &millis(self.total))?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> LoadTimings {
+        let mut timings = LoadTimings::new();
+        timings.record("host", Duration::from_micros(250));
+        timings.record("database_url", Duration::from_millis(12));
+        timings.set_total(Duration::from_millis(13));
+        timings
+    }
+
+    #[test]
+    fn test_timings_accessors() {
+        let timings = sample();
+
+        assert_eq!(timings.fields().len(), 2);
+        assert_eq!(timings.get("host"), Some(Duration::from_micros(250)));
+        assert_eq!(timings.get("missing"), None);
+        assert_eq!(timings.slowest().unwrap().field, "database_url");
+        assert_eq!(timings.total(), Duration::from_millis(13));
+    }
+
+    #[test]
+    fn test_timings_display() {
+        let display = sample().to_string();
+        let lines: Vec<&str> = display.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "host               0.250ms");
+        assert_eq!(lines[1], "database_url      12.000ms");
+        assert_eq!(lines[2], "total             13.000ms");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_timings_serialize() {
+        let json = serde_json::to_value(sample()).unwrap();
+
+        assert_eq!(json["fields"][0]["field"], "host");
+        assert_eq!(json["fields"][0]["ms"], 0.25);
+        assert_eq!(json["total_ms"], 13.0);
+    }
+}
@@ -1,7 +1,9 @@
 //! Validation error types for the `validator` crate integration.
 //!
 //! This module provides error types for validation failures, converting
-//! from the `validator` crate's error types to procenv's error model.
+//! from the `validator` crate's error types to procenv's error model. The
+//! conversions need the `validator` feature; [`ValidationFieldError`] and the
+//! custom validation constructors on [`Error`](crate::Error) do not.
 //!
 //! # Overview
 //!
@@ -42,6 +44,29 @@
 //! // Validation runs automatically after loading
 //! let config = Config::from_env_validated()?;
 //! ```
+//!
+//! # Custom Validators
+//!
+//! Functions named by `#[env(validate = "fn_name")]` may return either a
+//! [`validator::ValidationError`] or a [`procenv::Error`](crate::Error). Build
+//! the latter with [`Error::validation`](crate::Error::validation) or
+//! [`Error::validation_with_help`](crate::Error::validation_with_help) so the
+//! failure renders like the built-in rules, under the
+//! `procenv::custom_validation_error` diagnostic code. These constructors are
+//! available without the `validator` feature:
+//!
+//! ```rust,ignore
+//! fn validate_port(port: &u16) -> Result<(), procenv::Error> {
+//!     if *port < 1024 {
+//!         return Err(procenv::Error::validation_with_help(
+//!             "port",
+//!             "privileged ports are not allowed",
+//!             "use a port of 1024 or above",
+//!         ));
+//!     }
+//!     Ok(())
+//! }
+//! ```
 
 use std::error::Error as StdError;
 use std::fmt::{self, Display, Formatter};
@@ -49,12 +74,21 @@ use std::string::ToString;
 
 use miette::Diagnostic;
 
+use crate::diagnostic_codes;
+
 /// A validation error for a specific field.
 ///
 /// This struct represents a single validation failure, including the field
 /// name, validation rule code, and human-readable message.
-#[derive(Debug, Diagnostic)]
-#[diagnostic(code(procenv::field_validation_error))]
+///
+/// Errors with the [`CUSTOM`](Self::CUSTOM) rule code report the
+/// `procenv::custom_validation_error` diagnostic code; all others report
+/// `procenv::field_validation_error`.
+///
+/// New fields may be added, so build values with [`new`](Self::new) and the
+/// `with_*` methods rather than a struct literal.
+#[derive(Debug)]
+#[non_exhaustive]
 pub struct ValidationFieldError {
     /// The field name that failed validation.
     pub field: String,
@@ -63,14 +97,19 @@ pub struct ValidationFieldError {
     pub code: String,
 
     /// Human-readable error message.
-    #[help]
     pub message: String,
 
     /// Additional parameters from the validation rule (e.g., min/max values).
     pub params: Option<String>,
+
+    /// Suggestion shown instead of the message as the diagnostic help.
+    pub help: Option<String>,
 }
 
 impl ValidationFieldError {
+    /// Rule code for failures reported by `#[env(validate = "fn_name")]` functions.
+    pub const CUSTOM: &'static str = "custom";
+
     /// Create a new validation field error.
     pub fn new(
         field: impl Into<String>,
@@ -82,6 +121,7 @@ impl ValidationFieldError {
             code: code.into(),
             message: message.into(),
             params: None,
+            help: None,
         }
     }
 
@@ -93,10 +133,19 @@ impl ValidationFieldError {
         self
     }
 
+    /// Add a help suggestion to the error.
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+
+        self
+    }
+
     /// Extract the human-readable message from a validation error.
     ///
     /// Returns the custom message if set, otherwise generates a default
     /// message using the validation code.
+    #[cfg(feature = "validator")]
     fn extract_message(error: &::validator::ValidationError) -> String {
         error.message.as_ref().map_or_else(
             || format!("validation failed: {}", error.code),
@@ -108,6 +157,7 @@ impl ValidationFieldError {
     ///
     /// Filters out the "value" parameter (which contains the actual value)
     /// and formats remaining parameters as "key: value" pairs.
+    #[cfg(feature = "validator")]
     fn extract_params(error: &::validator::ValidationError) -> Option<String> {
         if error.params.is_empty() {
             return None;
@@ -128,6 +178,7 @@ impl ValidationFieldError {
     }
 
     /// Create a `ValidationFieldError` from a validator error.
+    #[cfg(feature = "validator")]
     fn from_validator_error(field: &str, error: &::validator::ValidationError) -> Self {
         let message = Self::extract_message(error);
         let params = Self::extract_params(error);
@@ -142,6 +193,7 @@ impl ValidationFieldError {
     }
 
     /// Convert validator crate errors to our error type.
+    #[cfg(feature = "validator")]
     #[must_use]
     pub fn validation_errors_to_procenv(errors: &::validator::ValidationErrors) -> Vec<Self> {
         // Collect flat field errors
@@ -184,10 +236,73 @@ impl Display for ValidationFieldError {
 
 impl StdError for ValidationFieldError {}
 
+impl Diagnostic for ValidationFieldError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = if self.code == Self::CUSTOM {
+            diagnostic_codes::CUSTOM_VALIDATION_ERROR
+        } else {
+            diagnostic_codes::FIELD_VALIDATION_ERROR
+        };
+
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(self.help.as_deref().unwrap_or(&self.message)))
+    }
+}
+
+/// Error types a custom field validator may return.
+///
+/// Implemented for [`validator::ValidationError`] and [`crate::Error`], so
+/// functions named by `#[env(validate = "fn_name")]` can return either.
+#[cfg(feature = "validator")]
+pub trait CustomValidationError {
+    /// Convert the failure into field errors reported against `field`.
+    fn into_field_errors(self, field: &str) -> Vec<ValidationFieldError>;
+}
+
+#[cfg(feature = "validator")]
+impl CustomValidationError for ::validator::ValidationError {
+    fn into_field_errors(self, field: &str) -> Vec<ValidationFieldError> {
+        let message = self
+            .message
+            .as_ref()
+            .map_or_else(|| self.code.to_string(), ToString::to_string);
+
+        vec![ValidationFieldError::new(
+            field,
+            ValidationFieldError::CUSTOM,
+            message,
+        )]
+    }
+}
+
+#[cfg(feature = "validator")]
+impl CustomValidationError for crate::Error {
+    fn into_field_errors(self, field: &str) -> Vec<ValidationFieldError> {
+        match self {
+            Self::Validation { errors } => errors,
+
+            Self::Multiple { errors } => errors
+                .into_iter()
+                .flat_map(|error| error.into_field_errors(field))
+                .collect(),
+
+            other => vec![ValidationFieldError::new(
+                field,
+                ValidationFieldError::CUSTOM,
+                other.to_string(),
+            )],
+        }
+    }
+}
+
 /// Standalone function to convert validator crate errors to procenv errors.
 ///
 /// This is a convenience wrapper around [`ValidationFieldError::validation_errors_to_procenv`].
 /// It's exported at the crate root for use in generated code.
+#[cfg(feature = "validator")]
 #[must_use]
 pub fn validation_errors_to_procenv(
     errors: &::validator::ValidationErrors,
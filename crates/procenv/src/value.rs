@@ -110,9 +110,9 @@ pub enum ConfigValue {
     /// A boolean value.
     Boolean(bool),
     /// A list of values.
-    List(Vec<ConfigValue>),
+    List(Vec<Self>),
     /// A map of string keys to values.
-    Map(HashMap<String, ConfigValue>),
+    Map(HashMap<String, Self>),
     /// No value (missing optional).
     None,
 }
@@ -571,6 +571,19 @@ impl ConfigValue {
         })
     }
 
+    /// Extracts a boolean for a `coerce_bool_numeric` field.
+    ///
+    /// Accepts booleans, the numbers `0` and `1`, and the strings `"true"`,
+    /// `"false"`, `"0"` and `"1"`, so a file value and an env override
+    /// produce the same result whichever type they were stored as.
+    ///
+    /// # Errors
+    ///
+    /// Returns a boxed error listing the accepted values for anything else.
+    pub fn extract_bool_numeric(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        crate::runtime::parse_bool_numeric(&self.to_string_repr()).map_err(Into::into)
+    }
+
     /// Extracts a boolean value with flexible parsing.
     ///
     /// Accepts: `true`, `false`, `1`, `0`, `"true"`, `"false"`, `"yes"`, `"no"`, etc.
@@ -737,6 +750,22 @@ mod tests {
         assert_eq!(ConfigValue::String("invalid".to_string()).as_bool(), None);
     }
 
+    #[test]
+    fn test_extract_bool_numeric() {
+        let extract = |value: ConfigValue| value.extract_bool_numeric().ok();
+
+        assert_eq!(extract(ConfigValue::Boolean(false)), Some(false));
+        assert_eq!(extract(ConfigValue::Integer(1)), Some(true));
+        assert_eq!(extract(ConfigValue::UnsignedInteger(0)), Some(false));
+        assert_eq!(extract(ConfigValue::String("1".to_string())), Some(true));
+        assert_eq!(
+            extract(ConfigValue::String("false".to_string())),
+            Some(false)
+        );
+        assert_eq!(extract(ConfigValue::Integer(2)), None);
+        assert_eq!(extract(ConfigValue::String("yes".to_string())), None);
+    }
+
     #[test]
     fn test_path_access() {
         let mut db = HashMap::new();
@@ -7,7 +7,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
-use super::handle::ConfigHandle;
+use super::handle::{Callbacks, ConfigHandle};
 use super::types::{ConfigChange, WatchError};
 use super::watcher::{ConfigWatcher, WatcherConfig};
 use crate::{ConfigSources, Error};
@@ -18,6 +18,29 @@ pub type ChangeCallback<T> = Box<dyn Fn(ConfigChange<T>) + Send + Sync + 'static
 /// Callback type for reload errors.
 pub type ErrorCallback = Box<dyn Fn(WatchError) + Send + Sync + 'static>;
 
+/// Callback type for async reload handlers.
+#[cfg(feature = "async")]
+pub type AsyncReloadCallback<T> = Box<
+    dyn Fn(
+            ConfigChange<T>,
+        )
+            -> crate::BoxFuture<'static, Result<(), Box<dyn std::error::Error + Send + Sync>>>
+        + Send
+        + Sync
+        + 'static,
+>;
+
+/// Check comparing the current and reloaded configuration, returning the
+/// first changed field that must not change at runtime.
+pub type ImmutableCheck<T> = Box<dyn Fn(&T, &T) -> Option<String> + Send + Sync + 'static>;
+
+/// Diff naming the fields that differ between the current and reloaded
+/// configuration.
+pub type FieldDiff<T> = Box<dyn Fn(&T, &T) -> Vec<String> + Send + Sync + 'static>;
+
+/// Lookup of a field's value, as a string, in a configuration.
+pub type FieldValue<T> = Box<dyn Fn(&T, &str) -> Option<String> + Send + Sync + 'static>;
+
 /// Builder for configuring hot reload behavior.
 ///
 /// `WatchBuilder` provides a fluent API for setting up file watching with
@@ -54,6 +77,19 @@ pub struct WatchBuilder<T: Clone + Send + Sync + 'static> {
 
     /// Callback for errors.
     on_error: Option<ErrorCallback>,
+
+    /// Check that rejects reloads changing immutable fields.
+    immutable_check: Option<ImmutableCheck<T>>,
+
+    /// Diff filling in the changed fields of each reload.
+    field_diff: Option<FieldDiff<T>>,
+
+    /// Lookup filling in the old and new values of changed fields.
+    field_value: Option<FieldValue<T>>,
+
+    /// Async handler awaited after each reload.
+    #[cfg(feature = "async")]
+    on_reload_async: Option<AsyncReloadCallback<T>>,
 }
 
 impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
@@ -70,6 +106,11 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
             debounce: Duration::from_millis(100),
             on_change: None,
             on_error: None,
+            immutable_check: None,
+            field_diff: None,
+            field_value: None,
+            #[cfg(feature = "async")]
+            on_reload_async: None,
         }
     }
 
@@ -160,6 +201,40 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
         self
     }
 
+    /// Register an async handler for configuration changes.
+    ///
+    /// For side effects that need to `await`, such as notifying another
+    /// service. The handler runs on the tokio runtime that is current when
+    /// the watcher is built, and is awaited after each successful reload
+    /// (after any [`on_change`](Self::on_change) callback) before the next
+    /// change is handled. If it returns an error, or panics, the error
+    /// callback receives [`WatchError::ReloadHandlerFailed`]; the reloaded
+    /// configuration stays active either way.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .on_reload_async(move |change| {
+    ///         let client = client.clone();
+    ///         async move { client.notify(change.new.port).await }
+    ///     })
+    /// ```
+    #[cfg(feature = "async")]
+    #[must_use]
+    pub fn on_reload_async<F, Fut, E>(mut self, handler: F) -> Self
+    where
+        F: Fn(ConfigChange<T>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<(), E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.on_reload_async = Some(Box::new(move |change| {
+            let future = handler(change);
+            Box::pin(async move { future.await.map_err(Into::into) })
+        }));
+        self
+    }
+
     /// Register a callback for reload errors.
     ///
     /// When a reload fails (e.g., due to invalid configuration), the error
@@ -187,6 +262,105 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
         self
     }
 
+    /// Reject reloads that change fields which require a restart.
+    ///
+    /// The check receives the current and the reloaded configuration and
+    /// returns the name of the first field that must not change. When it
+    /// returns `Some`, the reload is discarded, the previous configuration is
+    /// retained and [`WatchError::ImmutableFieldChanged`] is sent to the error
+    /// callback.
+    ///
+    /// The derive generates a suitable check for fields marked
+    /// `reload_immutable` (which must implement `PartialEq`). Derived
+    /// `*_with_sources()` loaders list those fields in their
+    /// [`ConfigSources`], and [`build_sync`](Self::build_sync) fails if any
+    /// are listed and no check is set.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[derive(EnvConfig, Clone)]
+    /// struct Config {
+    ///     #[env(var = "PORT", reload_immutable)]
+    ///     port: u16,
+    /// }
+    ///
+    /// WatchBuilder::new()
+    ///     .reject_immutable_changes(Config::reload_immutable_changed)
+    /// ```
+    #[must_use]
+    pub fn reject_immutable_changes<F>(mut self, check: F) -> Self
+    where
+        F: Fn(&T, &T) -> Option<String> + Send + Sync + 'static,
+    {
+        self.immutable_check = Some(Box::new(check));
+        self
+    }
+
+    /// Report which fields each reload changed.
+    ///
+    /// The diff receives the current and the reloaded configuration and
+    /// returns the changed field names, which are passed to change callbacks
+    /// as the [`ConfigChange::changed_fields`](field@ConfigChange::changed_fields)
+    /// field and, with their sources, by
+    /// [`ConfigChange::changed_fields()`](method@ConfigChange::changed_fields).
+    /// Without it both are empty.
+    ///
+    /// The derive generates a suitable diff, `changed_fields`, which compares
+    /// field values by their string form and so needs no `PartialEq`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .diff_fields(Config::changed_fields)
+    ///     .on_change(|change| {
+    ///         if change.field_changed("port") {
+    ///             println!("Port changed");
+    ///         }
+    ///     })
+    /// ```
+    #[must_use]
+    pub fn diff_fields<F>(mut self, diff: F) -> Self
+    where
+        F: Fn(&T, &T) -> Vec<String> + Send + Sync + 'static,
+    {
+        self.field_diff = Some(Box::new(diff));
+        self
+    }
+
+    /// Report the old and new value of each changed field.
+    ///
+    /// With [`diff_fields`](Self::diff_fields), `get` is called on the
+    /// current and the reloaded configuration for each changed field, filling
+    /// in the values of
+    /// [`ConfigChange::changed_fields()`](method@ConfigChange::changed_fields).
+    /// Without it they are `None`.
+    ///
+    /// Pass the derived `get_str`, which replaces secret values with
+    /// `<redacted>`, so secrets never reach change callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .diff_fields(Config::changed_fields)
+    ///     .field_values(Config::get_str)
+    ///     .on_change(|change| {
+    ///         for field in change.changed_fields() {
+    ///             println!("{}: {:?} -> {:?}", field.name, field.old_value, field.new_value);
+    ///         }
+    ///     })
+    /// ```
+    #[must_use]
+    pub fn field_values<F>(mut self, get: F) -> Self
+    where
+        F: Fn(&T, &str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.field_value = Some(Box::new(get));
+        self
+    }
+
     /// Build and start the file watcher (synchronous).
     ///
     /// This spawns a background thread to watch for file changes. The returned
@@ -205,6 +379,9 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
     ///
     /// Returns [`WatchError`] if:
     /// - No files were specified to watch
+    /// - The loaded sources list `reload_immutable` fields but no
+    ///   [`reject_immutable_changes`](Self::reject_immutable_changes) check is set
+    /// - An async reload handler is set outside a tokio runtime
     /// - Failed to initialize the file watcher
     /// - Initial configuration load failed
     ///
@@ -227,19 +404,54 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
             return Err(WatchError::init_failed("no files specified to watch", None));
         }
 
+        let callbacks = Callbacks {
+            change: self.on_change,
+            error: self.on_error,
+            #[cfg(feature = "async")]
+            reload_async: match self.on_reload_async {
+                Some(handler) => {
+                    let runtime = tokio::runtime::Handle::try_current().map_err(|_| {
+                        WatchError::init_failed("on_reload_async requires a tokio runtime", None)
+                    })?;
+                    Some((handler, runtime))
+                }
+                None => None,
+            },
+        };
+
         // Perform initial load
         let (initial_config, initial_sources) = reload_fn()
             .map_err(|e| WatchError::reload_failed("initial configuration load failed", vec![e]))?;
 
+        // `reload_immutable` fields would otherwise reload silently
+        if self.immutable_check.is_none() && !initial_sources.reload_immutable().is_empty() {
+            return Err(WatchError::init_failed(
+                format!(
+                    "fields marked reload_immutable ({}) need \
+                     reject_immutable_changes({}::reload_immutable_changed)",
+                    initial_sources.reload_immutable().join(", "),
+                    std::any::type_name::<T>()
+                ),
+                None,
+            ));
+        }
+
         let watcher_config = WatcherConfig {
             debounce: self.debounce,
             paths: self.files,
         };
 
-        let watcher =
-            ConfigWatcher::start(initial_config, initial_sources, &watcher_config, reload_fn)?;
+        let watcher = ConfigWatcher::start(
+            initial_config,
+            initial_sources,
+            &watcher_config,
+            reload_fn,
+            self.immutable_check,
+            self.field_diff,
+            self.field_value,
+        )?;
 
-        Ok(ConfigHandle::new(watcher, self.on_change, self.on_error))
+        Ok(ConfigHandle::new(watcher, callbacks))
     }
 }
 
@@ -265,6 +477,7 @@ mod tests {
         assert_eq!(builder.debounce, Duration::from_millis(100));
         assert!(builder.on_change.is_none());
         assert!(builder.on_error.is_none());
+        assert!(builder.immutable_check.is_none());
     }
 
     #[test]
@@ -287,6 +500,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::result_large_err)]
     fn test_build_without_files_fails() {
         let result: Result<ConfigHandle<TestConfig>, _> = WatchBuilder::new()
             .build_sync(|| Ok((TestConfig::default(), ConfigSources::default())));
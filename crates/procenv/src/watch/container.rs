@@ -8,6 +8,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use parking_lot::RwLock;
 
+use super::types::WatchError;
 use crate::ConfigSources;
 
 /// Thread-safe container for watched configuration.
@@ -50,6 +51,9 @@ pub struct WatchedConfig<T> {
     /// Epoch counter - incremented on each update.
     /// Used for efficient change detection without comparing configs.
     epoch: AtomicU64,
+
+    /// Message of the most recent failed reload, cleared on success.
+    last_error: RwLock<Option<String>>,
 }
 
 impl<T> WatchedConfig<T> {
@@ -64,6 +68,7 @@ impl<T> WatchedConfig<T> {
             inner: RwLock::new(Arc::new(config)),
             sources: RwLock::new(sources),
             epoch: AtomicU64::new(0),
+            last_error: RwLock::new(None),
         }
     }
 
@@ -124,6 +129,30 @@ impl<T> WatchedConfig<T> {
         self.sources.read().clone()
     }
 
+    /// Returns `true` if the most recent reload attempt failed.
+    ///
+    /// A stale configuration is still the last valid one, but it no longer
+    /// matches what is on disk.
+    pub fn is_stale(&self) -> bool {
+        self.last_error.read().is_some()
+    }
+
+    /// Get the error from the most recent reload attempt, if it failed.
+    ///
+    /// The returned [`WatchError::ReloadFailed`] carries the failure message
+    /// only; the underlying errors are delivered to the `on_error` callback.
+    pub fn last_error(&self) -> Option<WatchError> {
+        self.last_error
+            .read()
+            .as_ref()
+            .map(|message| WatchError::reload_failed(message.clone(), Vec::new()))
+    }
+
+    /// Record a failed reload attempt.
+    pub(crate) fn record_error(&self, message: impl Into<String>) {
+        *self.last_error.write() = Some(message.into());
+    }
+
     /// Atomically swap in a new configuration.
     ///
     /// This method:
@@ -160,6 +189,9 @@ impl<T> WatchedConfig<T> {
             std::mem::replace(&mut *guard, new_sources)
         };
 
+        // A successful swap means the config is current again
+        *self.last_error.write() = None;
+
         // Increment epoch after both swaps complete
         self.epoch.fetch_add(1, Ordering::Release);
 
@@ -212,6 +244,7 @@ impl<T> std::fmt::Debug for WatchedConfig<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("WatchedConfig")
             .field("epoch", &self.epoch())
+            .field("stale", &self.is_stale())
             .finish_non_exhaustive()
     }
 }
@@ -276,6 +309,23 @@ mod tests {
         assert_eq!(watched.epoch(), initial_epoch + 1);
     }
 
+    #[test]
+    fn test_stale_tracking() {
+        let watched = WatchedConfig::new(TestConfig::default(), ConfigSources::default());
+        assert!(!watched.is_stale());
+        assert!(watched.last_error().is_none());
+
+        watched.record_error("invalid TOML");
+        assert!(watched.is_stale());
+        let err = watched.last_error().expect("error should be recorded");
+        assert!(err.to_string().contains("invalid TOML"));
+
+        // A successful swap clears the stale state
+        watched.swap(Arc::new(TestConfig::default()), ConfigSources::default());
+        assert!(!watched.is_stale());
+        assert!(watched.last_error().is_none());
+    }
+
     #[test]
     fn test_concurrent_reads() {
         use std::thread;
@@ -5,15 +5,77 @@
 
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 use crossbeam_channel::Receiver;
+use parking_lot::RwLock;
 
 use super::WatchedConfig;
+#[cfg(feature = "async")]
+use super::builder::AsyncReloadCallback;
 use super::builder::{ChangeCallback, ErrorCallback};
 use super::types::{ConfigChange, WatchError};
 use super::watcher::{ConfigWatcher, WatchCommand};
 use crate::ConfigSources;
 
+/// Callbacks run on the callback thread after each reload.
+pub struct Callbacks<T> {
+    /// Called with each successful reload.
+    pub change: Option<ChangeCallback<T>>,
+
+    /// Called with each reload error.
+    pub error: Option<ErrorCallback>,
+
+    /// Async handler and the runtime it runs on.
+    #[cfg(feature = "async")]
+    pub reload_async: Option<(AsyncReloadCallback<T>, tokio::runtime::Handle)>,
+}
+
+impl<T: Clone + Send + Sync + 'static> Callbacks<T> {
+    /// Whether no callback is registered.
+    fn is_empty(&self) -> bool {
+        #[cfg(feature = "async")]
+        if self.reload_async.is_some() {
+            return false;
+        }
+
+        self.change.is_none() && self.error.is_none()
+    }
+
+    /// Run the change callbacks, awaiting the async handler last.
+    fn on_change(&self, change: ConfigChange<T>) {
+        #[cfg(feature = "async")]
+        if let Some((handler, runtime)) = &self.reload_async {
+            if let Some(cb) = &self.change {
+                cb(change.clone());
+            }
+
+            // Spawned so the handler runs on the runtime's own threads
+            match runtime.block_on(runtime.spawn(handler(change))) {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    self.on_error(WatchError::reload_handler_failed(e.to_string(), Some(e)));
+                }
+                Err(e) => {
+                    self.on_error(WatchError::reload_handler_failed(e.to_string(), None));
+                }
+            }
+            return;
+        }
+
+        if let Some(cb) = &self.change {
+            cb(change);
+        }
+    }
+
+    /// Run the error callback.
+    fn on_error(&self, error: WatchError) {
+        if let Some(cb) = &self.error {
+            cb(error);
+        }
+    }
+}
+
 /// Handle for accessing and controlling watched configuration.
 ///
 /// `ConfigHandle` is the main user-facing type for hot reload functionality.
@@ -55,15 +117,13 @@ pub struct ConfigHandle<T: Clone + Send + Sync + 'static> {
 
 impl<T: Clone + Send + Sync + 'static> ConfigHandle<T> {
     /// Create a new config handle.
-    pub(crate) fn new(
-        watcher: ConfigWatcher<T>,
-        on_change: Option<ChangeCallback<T>>,
-        on_error: Option<ErrorCallback>,
-    ) -> Self {
+    pub(crate) fn new(watcher: ConfigWatcher<T>, callbacks: Callbacks<T>) -> Self {
         let watcher = Arc::new(watcher);
 
         // Spawn callback processor thread if callbacks are registered
-        let callback_thread = if on_change.is_some() || on_error.is_some() {
+        let callback_thread = if callbacks.is_empty() {
+            None
+        } else {
             let change_rx = watcher.change_receiver().clone();
             let error_rx = watcher.error_receiver().clone();
             let watcher_clone = watcher.clone();
@@ -71,19 +131,11 @@ impl<T: Clone + Send + Sync + 'static> ConfigHandle<T> {
             let handle = thread::Builder::new()
                 .name("procenv-callbacks".to_string())
                 .spawn(move || {
-                    callback_loop(
-                        &change_rx,
-                        &error_rx,
-                        on_change.as_ref(),
-                        on_error.as_ref(),
-                        &watcher_clone,
-                    );
+                    callback_loop(&change_rx, &error_rx, &callbacks, &watcher_clone);
                 })
                 .ok();
 
             handle.map(Arc::new)
-        } else {
-            None
         };
 
         Self {
@@ -169,6 +221,32 @@ impl<T: Clone + Send + Sync + 'static> ConfigHandle<T> {
         self.epoch() != epoch
     }
 
+    /// Check if the current configuration is stale.
+    ///
+    /// Returns `true` if the most recent reload attempt failed, meaning the
+    /// running configuration no longer matches the file on disk. Useful for
+    /// surfacing in health endpoints.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if handle.is_stale() {
+    ///     warn!("config reload failed: {:?}", handle.last_error());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.watcher.config().is_stale()
+    }
+
+    /// Get the error from the most recent reload attempt, if it failed.
+    ///
+    /// Returns `None` once a subsequent reload succeeds.
+    #[must_use]
+    pub fn last_error(&self) -> Option<WatchError> {
+        self.watcher.config().last_error()
+    }
+
     /// Manually trigger a configuration reload.
     ///
     /// This forces an immediate reload of the configuration from all sources,
@@ -216,6 +294,43 @@ impl<T: Clone + Send + Sync + 'static> ConfigHandle<T> {
         self.watcher.is_running()
     }
 
+    /// Create a derived view of the configuration.
+    ///
+    /// The returned [`MappedHandle`] caches the result of `f` and recomputes it
+    /// the first time it is accessed after a reload. This keeps derived state
+    /// (e.g. a `SocketAddr` built from host and port) consistent with the
+    /// watched configuration without manual invalidation.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let addr = handle.map(|cfg| format!("{}:{}", cfg.host, cfg.port));
+    /// println!("Listening on {}", addr.get());
+    /// ```
+    #[must_use]
+    pub fn map<U, F>(&self, f: F) -> MappedHandle<U>
+    where
+        U: Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + Sync + 'static,
+    {
+        let config = self.watcher.config().clone();
+        let epoch_config = config.clone();
+
+        MappedHandle::new(
+            Arc::new(move || epoch_config.epoch()),
+            Arc::new(move || config.read(&f)),
+        )
+    }
+
+    /// Get the effective debounce duration.
+    ///
+    /// File events arriving within this window of each other are coalesced
+    /// into a single reload.
+    #[must_use]
+    pub fn debounce(&self) -> Duration {
+        self.watcher.debounce()
+    }
+
     /// Get a clone of the command sender for advanced use cases.
     ///
     /// This allows sending commands to the watcher from other contexts.
@@ -240,16 +355,98 @@ impl<T: Clone + Send + Sync + 'static> std::fmt::Debug for ConfigHandle<T> {
         f.debug_struct("ConfigHandle")
             .field("epoch", &self.epoch())
             .field("running", &self.is_running())
+            .field("stale", &self.is_stale())
             .finish()
     }
 }
 
+/// Type-erased epoch reader used by [`MappedHandle`].
+type EpochFn = Arc<dyn Fn() -> u64 + Send + Sync>;
+
+/// Type-erased projection used by [`MappedHandle`].
+type ComputeFn<U> = Arc<dyn Fn() -> U + Send + Sync>;
+
+/// Handle to a value derived from watched configuration.
+///
+/// Created by [`ConfigHandle::map`]. The derived value is cached and only
+/// recomputed when the underlying configuration epoch changes. Like
+/// [`ConfigHandle`], it is cheaply cloneable and all clones share the cache.
+pub struct MappedHandle<U> {
+    /// Reads the current epoch of the source configuration.
+    epoch: EpochFn,
+
+    /// Computes the derived value from the current configuration.
+    compute: ComputeFn<U>,
+
+    /// Cached value along with the epoch it was computed at.
+    cache: Arc<RwLock<(u64, Arc<U>)>>,
+}
+
+impl<U> MappedHandle<U> {
+    /// Create a new mapped handle, computing the initial value eagerly.
+    fn new(epoch: EpochFn, compute: ComputeFn<U>) -> Self {
+        let initial = (epoch(), Arc::new(compute()));
+
+        Self {
+            epoch,
+            compute,
+            cache: Arc::new(RwLock::new(initial)),
+        }
+    }
+
+    /// Get the latest derived value.
+    ///
+    /// Recomputes the value if the configuration was reloaded since it was
+    /// last computed; otherwise returns the cached `Arc<U>`.
+    #[must_use]
+    pub fn get(&self) -> Arc<U> {
+        let current = (self.epoch)();
+
+        {
+            let cache = self.cache.read();
+            if cache.0 == current {
+                return cache.1.clone();
+            }
+        }
+
+        let value = Arc::new((self.compute)());
+        let mut cache = self.cache.write();
+        *cache = (current, value.clone());
+
+        value
+    }
+
+    /// Get the epoch of the configuration the cached value was derived from.
+    #[must_use]
+    pub fn epoch(&self) -> u64 {
+        self.cache.read().0
+    }
+}
+
+impl<U> Clone for MappedHandle<U> {
+    fn clone(&self) -> Self {
+        Self {
+            epoch: self.epoch.clone(),
+            compute: self.compute.clone(),
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+// Manual Debug impl to avoid U: Debug bound
+impl<U> std::fmt::Debug for MappedHandle<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MappedHandle")
+            .field("epoch", &self.epoch())
+            .finish_non_exhaustive()
+    }
+}
+
 /// Callback processing loop.
 fn callback_loop<T: Clone + Send + Sync + 'static>(
     change_rx: &Receiver<ConfigChange<T>>,
     error_rx: &Receiver<WatchError>,
-    on_change: Option<&ChangeCallback<T>>,
-    on_error: Option<&ErrorCallback>,
+    callbacks: &Callbacks<T>,
     watcher: &Arc<ConfigWatcher<T>>,
 ) {
     use crossbeam_channel::select;
@@ -257,17 +454,13 @@ fn callback_loop<T: Clone + Send + Sync + 'static>(
     while watcher.is_running() {
         select! {
             recv(change_rx) -> change => {
-                if let Ok(change) = change
-                    && let Some(cb) = on_change
-                {
-                    cb(change);
+                if let Ok(change) = change {
+                    callbacks.on_change(change);
                 }
             }
             recv(error_rx) -> error => {
-                if let Ok(error) = error
-                    && let Some(cb) = on_error
-                {
-                    cb(error);
+                if let Ok(error) = error {
+                    callbacks.on_error(error);
                 }
             }
             default(std::time::Duration::from_millis(100)) => {
@@ -287,6 +480,32 @@ mod tests {
         port: u16,
     }
 
+    #[test]
+    fn test_mapped_handle_recomputes_on_epoch_change() {
+        let watched = Arc::new(WatchedConfig::new(
+            TestConfig { port: 8080 },
+            ConfigSources::default(),
+        ));
+        let epoch_src = watched.clone();
+        let compute_src = watched.clone();
+
+        let mapped = MappedHandle::new(
+            Arc::new(move || epoch_src.epoch()),
+            Arc::new(move || compute_src.read(|c| u32::from(c.port) + 1)),
+        );
+        assert_eq!(*mapped.get(), 8081);
+
+        let first = mapped.get();
+        assert!(Arc::ptr_eq(&first, &mapped.get()), "value should be cached");
+
+        watched.swap(
+            Arc::new(TestConfig { port: 9090 }),
+            ConfigSources::default(),
+        );
+        assert_eq!(*mapped.get(), 9091);
+        assert_eq!(mapped.epoch(), 1);
+    }
+
     #[test]
     fn test_handle_debug() {
         // Just ensure Debug impl compiles
@@ -8,6 +8,7 @@
 //!
 //! - **File watching** - Monitor config files for changes using the `notify` crate
 //! - **Debouncing** - Configurable delay to handle rapid file saves
+//! - **Rotation** - Follows files replaced by rename or symlink swap
 //! - **Error resilience** - Keep last valid config on reload errors
 //! - **Callbacks** - Register handlers for changes and errors
 //! - **Thread-safe** - Concurrent access to configuration
@@ -89,8 +90,78 @@
 //!     .build_sync(|| Config::from_config_with_sources())?;
 //! ```
 //!
+//! # Rotated and Symlinked Files
+//!
+//! Editors that save by renaming a new file over the old one, and mounted
+//! Kubernetes secrets and config maps, replace the file instead of writing
+//! to it. After a remove, rename or symlink swap the watcher re-establishes
+//! its watch on whatever the path resolves to now and reloads. For a
+//! symlinked path the containing directory is watched as well, so the
+//! `..data` swap Kubernetes uses to publish a new version triggers a reload
+//! of the files behind it.
+//!
+//! # Async Reload Handlers
+//!
+//! With the `async` feature, `on_reload_async` registers a handler that can
+//! `await` in response to a reload, for example to notify another service.
+//! Build the watcher inside a tokio runtime; the handler runs there and is
+//! awaited after each reload, and any error it returns goes to `on_error`
+//! as [`WatchError::ReloadHandlerFailed`].
+//!
+//! ```ignore
+//! let handle = WatchBuilder::new()
+//!     .watch_file("config.toml")
+//!     .on_reload_async(move |change| {
+//!         let client = client.clone();
+//!         async move { client.config_changed(&change.new).await }
+//!     })
+//!     .on_error(|err| eprintln!("{err}"))
+//!     .build_sync(|| Config::from_config_with_sources())?;
+//! ```
+//!
+//! # Immutable Fields
+//!
+//! Some settings (a listening port, a database URL) only take effect at
+//! startup. Mark them `reload_immutable` (their types must implement
+//! `PartialEq`) and pass the generated check to the builder; a reload that
+//! changes one is rejected, the previous configuration is kept and `on_error`
+//! receives [`WatchError::ImmutableFieldChanged`]. Building the watcher fails
+//! if the derived sources list `reload_immutable` fields and no check is set.
+//!
+//! ```ignore
+//! #[derive(EnvConfig, Clone)]
+//! struct Config {
+//!     #[env(var = "PORT", default = "8080", reload_immutable)]
+//!     port: u16,
+//! }
+//!
+//! let handle = WatchBuilder::new()
+//!     .watch_file("config.toml")
+//!     .reject_immutable_changes(Config::reload_immutable_changed)
+//!     .build_sync(|| Config::from_config_with_sources())?;
+//! ```
+//!
 //! # Change Detection
 //!
+//! Pass the derived `changed_fields` to `diff_fields` to have each
+//! [`ConfigChange`] list the fields that changed. It compares values by their
+//! string form, so the config needs no `PartialEq` (`SecretString` fields
+//! work too). Passing the derived `get_str` to `field_values` adds each
+//! field's old and new value, with secrets masked.
+//!
+//! ```ignore
+//! let handle = WatchBuilder::new()
+//!     .watch_file("config.toml")
+//!     .diff_fields(Config::changed_fields)
+//!     .field_values(Config::get_str)
+//!     .on_change(|change| {
+//!         for field in change.changed_fields() {
+//!             println!("{}: {:?} -> {:?}", field.name, field.old_value, field.new_value);
+//!         }
+//!     })
+//!     .build_sync(|| Config::from_config_with_sources())?;
+//! ```
+//!
 //! Use the epoch counter for efficient change detection without callbacks:
 //!
 //! ```ignore
@@ -113,7 +184,7 @@ mod watcher;
 // Public API
 pub use builder::WatchBuilder;
 pub use container::WatchedConfig;
-pub use handle::ConfigHandle;
+pub use handle::{ConfigHandle, MappedHandle};
 pub use types::{ChangeTrigger, ChangedField, ConfigChange, WatchError};
 
 // Re-export for convenience
@@ -4,6 +4,7 @@
 //! - [`WatchError`] - Errors specific to file watching and reloading
 //! - [`ConfigChange`] - Represents a configuration change event
 //! - [`ChangeTrigger`] - What caused the configuration to reload
+//! - [`ChangedField`] - Old and new value of a single changed field
 
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -66,6 +67,21 @@ pub enum WatchError {
         errors: Vec<crate::Error>,
     },
 
+    /// A reload changed a field marked `reload_immutable`.
+    ///
+    /// The reload is rejected and the previous configuration is retained.
+    #[error("reload changed immutable field '{field}'")]
+    #[diagnostic(
+        code(procenv::watch::immutable_field_changed),
+        help(
+            "This field cannot change while the application is running. Revert the change or restart to apply it."
+        )
+    )]
+    ImmutableFieldChanged {
+        /// The field that changed (dotted for nested fields).
+        field: String,
+    },
+
     /// The watcher has been stopped.
     #[error("watcher has been stopped")]
     #[diagnostic(
@@ -85,6 +101,23 @@ pub enum WatchError {
         path: PathBuf,
     },
 
+    /// An `on_reload_async` handler failed.
+    ///
+    /// The reload itself was applied; only the handler's side effect failed.
+    #[error("reload handler failed: {message}")]
+    #[diagnostic(
+        code(procenv::watch::reload_handler_failed),
+        help("The new configuration is active. Check the service the reload handler talks to.")
+    )]
+    ReloadHandlerFailed {
+        /// Human-readable error message.
+        message: String,
+
+        /// The error returned by the handler, if it returned one.
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
     /// Channel communication error.
     #[error("internal channel error: {message}")]
     #[diagnostic(code(procenv::watch::channel_error))]
@@ -119,11 +152,29 @@ impl WatchError {
         }
     }
 
+    /// Create a new `ImmutableFieldChanged` error.
+    pub fn immutable_field_changed(field: impl Into<String>) -> Self {
+        Self::ImmutableFieldChanged {
+            field: field.into(),
+        }
+    }
+
     /// Create a new `FileDeleted` error.
     pub fn file_deleted(path: impl Into<PathBuf>) -> Self {
         Self::FileDeleted { path: path.into() }
     }
 
+    /// Create a new `ReloadHandlerFailed` error.
+    pub fn reload_handler_failed(
+        message: impl Into<String>,
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self::ReloadHandlerFailed {
+            message: message.into(),
+            source,
+        }
+    }
+
     /// Create a new `ChannelError`.
     pub fn channel_error(message: impl Into<String>) -> Self {
         Self::ChannelError {
@@ -142,8 +193,10 @@ impl WatchError {
 ///
 /// ```ignore
 /// handle.on_change(|change: ConfigChange<MyConfig>| {
-///     println!("Config reloaded!");
-///     println!("Changed fields: {:?}", change.changed_fields);
+///     println!("Config reloaded ({})", change.trigger());
+///     for field in change.changed_fields() {
+///         println!("  {}: {:?} -> {:?}", field.name, field.old_value, field.new_value);
+///     }
 ///     if change.old.is_some() {
 ///         println!("This was a reload, not initial load");
 ///     }
@@ -168,6 +221,9 @@ pub struct ConfigChange<T> {
 
     /// Source attribution for the new configuration.
     pub sources: ConfigSources,
+
+    /// Per-field details, set via [`with_changed_fields`](Self::with_changed_fields).
+    changed: Vec<ChangedField>,
 }
 
 impl<T> ConfigChange<T> {
@@ -186,9 +242,21 @@ impl<T> ConfigChange<T> {
             trigger,
             timestamp: Instant::now(),
             sources,
+            changed: Vec::new(),
         }
     }
 
+    /// Attach per-field change details.
+    ///
+    /// This also replaces the [`changed_fields`](field@Self::changed_fields)
+    /// field so both views agree.
+    #[must_use]
+    pub fn with_changed_fields(mut self, changed: Vec<ChangedField>) -> Self {
+        self.changed_fields = changed.iter().map(|f| f.name.clone()).collect();
+        self.changed = changed;
+        self
+    }
+
     /// Create a change event for initial configuration load.
     pub fn initial(config: Arc<T>, sources: ConfigSources) -> Self {
         Self::new(None, config, Vec::new(), ChangeTrigger::Initial, sources)
@@ -211,6 +279,22 @@ impl<T> ConfigChange<T> {
     pub fn field_changed(&self, field_name: &str) -> bool {
         self.changed_fields.iter().any(|f| f == field_name)
     }
+
+    /// Returns the changed fields with their old and new values.
+    ///
+    /// Filled in by watchers that diff fields (`WatchBuilder::diff_fields`),
+    /// with values when they also read them (`WatchBuilder::field_values`).
+    /// Empty for the initial load.
+    #[must_use]
+    pub fn changed_fields(&self) -> &[ChangedField] {
+        &self.changed
+    }
+
+    /// Returns what caused this change, e.g. a file edit or an env var poll.
+    #[must_use]
+    pub fn trigger(&self) -> ChangeTrigger {
+        self.trigger.clone()
+    }
 }
 
 /// What triggered a configuration reload.
@@ -351,6 +435,34 @@ mod tests {
         assert!(trigger.to_string().contains("manual"));
     }
 
+    #[test]
+    fn test_config_change_accessors() {
+        let trigger = ChangeTrigger::FileModified(PathBuf::from("config.toml"));
+        let change = ConfigChange::new(
+            Some(Arc::new(1)),
+            Arc::new(2),
+            Vec::new(),
+            trigger.clone(),
+            ConfigSources::new(),
+        );
+        assert_eq!(change.trigger(), trigger);
+        assert!(change.changed_fields().is_empty());
+
+        let change = change.with_changed_fields(vec![ChangedField::new(
+            "port",
+            Some("8080".to_string()),
+            Some("9090".to_string()),
+            Source::ConfigFile(None),
+        )]);
+        assert!(change.has_changes());
+        assert!(change.field_changed("port"));
+        assert_eq!(change.changed_fields().len(), 1);
+        assert_eq!(
+            change.changed_fields()[0].new_value.as_deref(),
+            Some("9090")
+        );
+    }
+
     #[test]
     fn test_change_trigger_helpers() {
         let trigger = ChangeTrigger::FileModified(PathBuf::from("test.toml"));
@@ -3,19 +3,22 @@
 //! This module contains the [`ConfigWatcher`] which manages file system
 //! events using the `notify` crate and triggers configuration reloads.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossbeam_channel::{Receiver, Sender, bounded, select};
+use notify::event::ModifyKind;
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 
 use super::WatchedConfig;
-use super::types::{ChangeTrigger, ConfigChange, WatchError};
-use crate::{ConfigSources, Error};
+use super::builder::{FieldDiff, FieldValue, ImmutableCheck};
+use super::types::{ChangeTrigger, ChangedField, ConfigChange, WatchError};
+use crate::{ConfigSources, Error, Source};
 
 /// Commands sent to the watcher thread.
 #[derive(Debug, Clone)]
@@ -65,6 +68,176 @@ pub enum ReloadResult<T> {
     Skipped,
 }
 
+/// Coalesces bursts of file events into a single reload.
+///
+/// Every recorded event resets the quiet-period timer; a trigger is only
+/// released once `debounce` has elapsed without further events.
+pub struct Debouncer {
+    /// Quiet period required before a reload fires.
+    debounce: Duration,
+    /// Most recent trigger in the current burst, if any.
+    pending: Option<ChangeTrigger>,
+    /// Time of the most recent event in the current burst.
+    last_event: Instant,
+}
+
+impl Debouncer {
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            pending: None,
+            last_event: Instant::now(),
+        }
+    }
+
+    /// Record an event, restarting the quiet period.
+    pub fn record(&mut self, trigger: ChangeTrigger, now: Instant) {
+        self.pending = Some(trigger);
+        self.last_event = now;
+    }
+
+    /// Release the pending trigger if the quiet period has elapsed.
+    pub fn poll(&mut self, now: Instant) -> Option<ChangeTrigger> {
+        if self.pending.is_some() && now.duration_since(self.last_event) >= self.debounce {
+            self.pending.take()
+        } else {
+            None
+        }
+    }
+
+    /// How long to wait before the next call to [`poll`](Self::poll) may fire.
+    ///
+    /// Falls back to the debounce duration when nothing is pending so the
+    /// loop still wakes up periodically to check the running flag.
+    pub fn timeout(&self, now: Instant) -> Duration {
+        if self.pending.is_some() {
+            self.debounce
+                .saturating_sub(now.duration_since(self.last_event))
+        } else {
+            self.debounce
+        }
+    }
+}
+
+/// Name of the symlink Kubernetes swaps to publish a new secret or config map.
+const K8S_DATA_DIR: &str = "..data";
+
+/// Tracks which paths an event may refer to and keeps the watches alive.
+///
+/// A configured path is matched both as given and by its canonical form. If
+/// it is a symlink, its parent directory is watched too: replacing the link
+/// (or the `..data` link behind a Kubernetes volume mount) never touches the
+/// file the old link pointed at, so only the directory sees the swap.
+pub struct WatchedPaths {
+    /// Paths as configured.
+    configured: Vec<PathBuf>,
+    /// Configured and canonical paths an event may name.
+    matching: HashSet<PathBuf>,
+    /// Parent directories of symlinked paths, with the paths they hold.
+    link_dirs: HashMap<PathBuf, PathBuf>,
+}
+
+impl WatchedPaths {
+    pub fn new(paths: &[PathBuf]) -> Self {
+        let mut watched = Self {
+            configured: paths.to_vec(),
+            matching: HashSet::new(),
+            link_dirs: HashMap::new(),
+        };
+        watched.resolve();
+        watched
+    }
+
+    /// Record the current canonical path and link directory of each path.
+    ///
+    /// Earlier canonical paths are kept, so events about a file that was
+    /// just rotated out still match.
+    fn resolve(&mut self) {
+        for path in &self.configured {
+            // Store the original path to match files that don't exist yet
+            self.matching.insert(path.clone());
+            if let Ok(canonical) = path.canonicalize() {
+                self.matching.insert(canonical);
+            }
+
+            let is_link = path
+                .symlink_metadata()
+                .is_ok_and(|meta| meta.file_type().is_symlink());
+            if is_link && let Some(parent) = path.parent() {
+                self.link_dirs.insert(parent.to_path_buf(), path.clone());
+            }
+        }
+    }
+
+    /// Watch every configured path and symlink directory.
+    pub fn watch_all(&self, watcher: &mut RecommendedWatcher) -> Result<(), WatchError> {
+        for path in &self.configured {
+            watch_path(watcher, path)?;
+        }
+        for (dir, path) in &self.link_dirs {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .map_err(|e| WatchError::path_error(path, format!("failed to watch: {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// Re-establish the watches after a file was removed, renamed or swapped.
+    ///
+    /// A watch follows the inode it was placed on, so once the file is
+    /// replaced it would never fire again. Watching the paths afresh picks
+    /// up whatever they resolve to now.
+    pub fn rewatch(&mut self, watcher: &mut RecommendedWatcher) -> Result<(), WatchError> {
+        for path in &self.configured {
+            // Fails when the watch already went away with the old file
+            let _ = watcher.unwatch(path);
+        }
+        self.resolve();
+        self.watch_all(watcher)
+    }
+
+    /// The trigger for an event, if it concerns one of the watched paths.
+    pub fn trigger_for(&self, event: &Event) -> Option<ChangeTrigger> {
+        for path in &event.paths {
+            // Kubernetes publishes new data by renaming a fresh `..data` link
+            // into place; report it as a change to the file behind it
+            if path.file_name() == Some(OsStr::new(K8S_DATA_DIR))
+                && let Some(configured) = path.parent().and_then(|dir| self.link_dirs.get(dir))
+            {
+                return match event.kind {
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
+                        Some(ChangeTrigger::FileModified(configured.clone()))
+                    }
+                    _ => None,
+                };
+            }
+
+            // Check direct path match first (handles newly created files)
+            let is_watched = self.matching.contains(path)
+                // Then try canonical match (handles existing files with symlinks/relative paths)
+                || path.canonicalize().is_ok_and(|c| self.matching.contains(&c));
+
+            if is_watched {
+                return match event.kind {
+                    EventKind::Create(_) => Some(ChangeTrigger::FileCreated(path.clone())),
+                    EventKind::Modify(_) => Some(ChangeTrigger::FileModified(path.clone())),
+                    EventKind::Remove(_) => Some(ChangeTrigger::FileDeleted(path.clone())),
+                    _ => None,
+                };
+            }
+        }
+        None
+    }
+}
+
+/// Whether an event may have replaced a watched file rather than written to it.
+const fn replaces_file(event: &Event) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))
+    )
+}
+
 /// Configuration for the internal watcher.
 pub struct WatcherConfig {
     /// Debounce duration for file events.
@@ -94,6 +267,8 @@ pub struct ConfigWatcher<T: Clone + Send + Sync + 'static> {
     error_rx: Receiver<WatchError>,
     /// Watcher thread handle.
     thread_handle: Option<JoinHandle<()>>,
+    /// Effective debounce duration.
+    debounce: Duration,
 }
 
 impl<T: Clone + Send + Sync + 'static> ConfigWatcher<T> {
@@ -105,11 +280,17 @@ impl<T: Clone + Send + Sync + 'static> ConfigWatcher<T> {
     /// * `initial_sources` - Source attribution for initial config
     /// * `config` - Watcher configuration
     /// * `reload_fn` - Function to reload the configuration
+    /// * `immutable_check` - Rejects reloads that change immutable fields
+    /// * `field_diff` - Names the fields each reload changed
+    /// * `field_value` - Reads the old and new values of changed fields
     pub fn start<F>(
         initial_config: T,
         initial_sources: ConfigSources,
         watcher_config: &WatcherConfig,
         reload_fn: F,
+        immutable_check: Option<ImmutableCheck<T>>,
+        field_diff: Option<FieldDiff<T>>,
+        field_value: Option<FieldValue<T>>,
     ) -> Result<Self, WatchError>
     where
         F: Fn() -> Result<(T, ConfigSources), Error> + Send + Sync + 'static,
@@ -129,32 +310,14 @@ impl<T: Clone + Send + Sync + 'static> ConfigWatcher<T> {
         let (notify_tx, notify_rx) = bounded::<notify::Result<Event>>(100);
         let mut watcher = create_notify_watcher(notify_tx)?;
 
-        // Watch all configured paths
-        for path in &watcher_config.paths {
-            watch_path(&mut watcher, path)?;
-        }
+        // Watch all configured paths, plus the directories of symlinked ones
+        let watched_paths = WatchedPaths::new(&watcher_config.paths);
+        watched_paths.watch_all(&mut watcher)?;
 
         // Spawn watcher thread
         let thread_state = state.clone();
         let debounce = watcher_config.debounce;
 
-        // Store both original paths AND canonical paths (if file exists)
-        // This allows matching newly created files that didn't exist at startup
-        let watched_paths: HashSet<PathBuf> = watcher_config
-            .paths
-            .iter()
-            .flat_map(|p| {
-                let mut paths = vec![p.clone()];
-                // Also store canonical path if file exists
-                if let Ok(canonical) = p.canonicalize()
-                    && canonical != *p
-                {
-                    paths.push(canonical);
-                }
-                paths
-            })
-            .collect();
-
         let thread_handle = thread::Builder::new()
             .name("procenv-watcher".to_string())
             .spawn(move || {
@@ -165,6 +328,9 @@ impl<T: Clone + Send + Sync + 'static> ConfigWatcher<T> {
                     change_tx,
                     error_tx,
                     reload_fn,
+                    immutable_check,
+                    field_diff,
+                    field_value,
                     debounce,
                     watched_paths,
                     watcher,
@@ -180,6 +346,7 @@ impl<T: Clone + Send + Sync + 'static> ConfigWatcher<T> {
             change_rx,
             error_rx,
             thread_handle: Some(thread_handle),
+            debounce,
         })
     }
 
@@ -219,6 +386,11 @@ impl<T: Clone + Send + Sync + 'static> ConfigWatcher<T> {
         self.state.is_running()
     }
 
+    /// Get the effective debounce duration.
+    pub const fn debounce(&self) -> Duration {
+        self.debounce
+    }
+
     /// Clone the command sender for external use.
     pub fn command_sender(&self) -> Sender<WatchCommand> {
         self.command_tx.clone()
@@ -276,17 +448,34 @@ fn watcher_loop<T, F>(
     change_tx: Sender<ConfigChange<T>>,
     error_tx: Sender<WatchError>,
     reload_fn: F,
+    immutable_check: Option<ImmutableCheck<T>>,
+    field_diff: Option<FieldDiff<T>>,
+    field_value: Option<FieldValue<T>>,
     debounce: Duration,
-    watched_paths: HashSet<PathBuf>,
-    _watcher: RecommendedWatcher, // Keep watcher alive
+    mut watched_paths: WatchedPaths,
+    mut watcher: RecommendedWatcher,
 ) where
     T: Clone + Send + Sync + 'static,
     F: Fn() -> Result<(T, ConfigSources), Error> + Send + Sync + 'static,
 {
-    let mut pending_reload: Option<ChangeTrigger> = None;
-    let mut last_event = std::time::Instant::now();
+    let mut debouncer = Debouncer::new(debounce);
 
     while state.is_running() {
+        // Fire a settled burst before waiting again, so a steady stream of
+        // unrelated events cannot starve the pending reload.
+        if let Some(trigger) = debouncer.poll(Instant::now()) {
+            do_reload(
+                &state,
+                &reload_fn,
+                immutable_check.as_ref(),
+                field_diff.as_ref(),
+                field_value.as_ref(),
+                trigger,
+                &change_tx,
+                &error_tx,
+            );
+        }
+
         select! {
             // Handle commands
             recv(command_rx) -> cmd => {
@@ -295,6 +484,9 @@ fn watcher_loop<T, F>(
                         do_reload(
                             &state,
                             &reload_fn,
+                            immutable_check.as_ref(),
+                            field_diff.as_ref(),
+                            field_value.as_ref(),
                             ChangeTrigger::ManualReload,
                             &change_tx,
                             &error_tx,
@@ -307,56 +499,35 @@ fn watcher_loop<T, F>(
                 }
             }
 
-            // Handle file events
+            // Handle file events - each relevant event restarts the quiet period
             recv(notify_rx) -> event_result => {
                 if let Ok(Ok(event)) = event_result
-                    && let Some(trigger) = process_notify_event(&event, &watched_paths)
+                    && let Some(trigger) = watched_paths.trigger_for(&event)
                 {
-                    pending_reload = Some(trigger);
-                    last_event = std::time::Instant::now();
-                }
-            }
-
-            // Debounce timeout - process pending reload
-            default(debounce) => {
-                if let Some(trigger) = pending_reload.take() {
-                    if last_event.elapsed() >= debounce {
-                        do_reload(&state, &reload_fn, trigger, &change_tx, &error_tx);
-                    } else {
-                        // Not enough time passed, re-queue
-                        pending_reload = Some(trigger);
+                    // A rotated file takes its watch with it; watch the new one
+                    if replaces_file(&event)
+                        && let Err(e) = watched_paths.rewatch(&mut watcher)
+                    {
+                        let _ = error_tx.send(e);
                     }
+                    debouncer.record(trigger, Instant::now());
                 }
             }
-        }
-    }
-}
 
-/// Process a notify event and return a trigger if relevant.
-fn process_notify_event(event: &Event, watched_paths: &HashSet<PathBuf>) -> Option<ChangeTrigger> {
-    // Check if any of the event paths are in our watched set
-    for path in &event.paths {
-        // Check direct path match first (handles newly created files)
-        let is_watched = watched_paths.contains(path)
-            // Then try canonical match (handles existing files with symlinks/relative paths)
-            || path.canonicalize().is_ok_and(|c| watched_paths.contains(&c));
-
-        if is_watched {
-            return match event.kind {
-                EventKind::Create(_) => Some(ChangeTrigger::FileCreated(path.clone())),
-                EventKind::Modify(_) => Some(ChangeTrigger::FileModified(path.clone())),
-                EventKind::Remove(_) => Some(ChangeTrigger::FileDeleted(path.clone())),
-                _ => None,
-            };
+            // Quiet period elapsed - the pending reload fires at the top of the loop
+            default(debouncer.timeout(Instant::now())) => {}
         }
     }
-    None
 }
 
 /// Perform a reload and send results to channels.
+#[allow(clippy::too_many_arguments)]
 fn do_reload<T, F>(
     state: &Arc<WatcherState<T>>,
     reload_fn: &F,
+    immutable_check: Option<&ImmutableCheck<T>>,
+    field_diff: Option<&FieldDiff<T>>,
+    field_value: Option<&FieldValue<T>>,
     trigger: ChangeTrigger,
     change_tx: &Sender<ConfigChange<T>>,
     error_tx: &Sender<WatchError>,
@@ -366,22 +537,43 @@ fn do_reload<T, F>(
 {
     match reload_fn() {
         Ok((new_config, new_sources)) => {
+            // Fields that need a restart keep the old config in place
+            if let Some(check) = immutable_check
+                && let Some(field) = check(&state.config.get(), &new_config)
+            {
+                let watch_err = WatchError::immutable_field_changed(field);
+                state.config.record_error(watch_err.to_string());
+                let _ = error_tx.send(watch_err);
+                return;
+            }
+
             let new_arc = Arc::new(new_config);
             let (old_config, _old_sources) =
                 state.config.swap(new_arc.clone(), new_sources.clone());
 
-            let change = ConfigChange::new(
-                Some(old_config),
-                new_arc,
-                Vec::new(), // TODO: Implement field diffing
-                trigger,
-                new_sources,
-            );
+            let changed_fields: Vec<ChangedField> = field_diff
+                .map_or_else(Vec::new, |diff| diff(&old_config, &new_arc))
+                .into_iter()
+                .map(|name| {
+                    let value = |config: &T| field_value.and_then(|get| get(config, &name));
+                    let source = new_sources
+                        .get(&name)
+                        .map_or(Source::NotSet, |value| value.source.clone());
+                    ChangedField::new(&name, value(&old_config), value(&new_arc), source)
+                })
+                .collect();
+
+            let change =
+                ConfigChange::new(Some(old_config), new_arc, Vec::new(), trigger, new_sources)
+                    .with_changed_fields(changed_fields);
 
             let _ = change_tx.send(change);
         }
         Err(e) => {
-            let watch_err = WatchError::reload_failed(e.to_string(), vec![e]);
+            let message = e.to_string();
+            state.config.record_error(message.clone());
+
+            let watch_err = WatchError::reload_failed(message, vec![e]);
             let _ = error_tx.send(watch_err);
         }
     }
@@ -405,6 +597,48 @@ mod tests {
         assert!(config.paths.is_empty());
     }
 
+    #[test]
+    fn test_debouncer_coalesces_burst() {
+        let debounce = Duration::from_millis(100);
+        let mut debouncer = Debouncer::new(debounce);
+        let start = Instant::now();
+        let path = PathBuf::from("config.toml");
+
+        // Ten events 20ms apart: each one lands inside the previous window
+        for i in 0..10 {
+            let at = start + Duration::from_millis(i * 20);
+            debouncer.record(ChangeTrigger::FileModified(path.clone()), at);
+            assert!(debouncer.poll(at + Duration::from_millis(10)).is_none());
+        }
+
+        let last = start + Duration::from_millis(180);
+        assert!(debouncer.poll(last + Duration::from_millis(99)).is_none());
+
+        // Exactly one reload once the burst settles
+        let fired = debouncer.poll(last + debounce);
+        assert_eq!(fired, Some(ChangeTrigger::FileModified(path)));
+        assert!(debouncer.poll(last + debounce * 2).is_none());
+    }
+
+    #[test]
+    fn test_debouncer_timeout() {
+        let debounce = Duration::from_millis(100);
+        let mut debouncer = Debouncer::new(debounce);
+        let start = Instant::now();
+
+        assert_eq!(debouncer.timeout(start), debounce);
+
+        debouncer.record(ChangeTrigger::ManualReload, start);
+        assert_eq!(
+            debouncer.timeout(start + Duration::from_millis(40)),
+            Duration::from_millis(60)
+        );
+        assert_eq!(
+            debouncer.timeout(start + Duration::from_millis(150)),
+            Duration::ZERO
+        );
+    }
+
     #[test]
     fn test_watched_paths_set() {
         let dir = tempdir().unwrap();
@@ -417,4 +651,46 @@ mod tests {
 
         assert!(!paths.is_empty());
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_k8s_data_swap_triggers_linked_path() {
+        use notify::event::{CreateKind, RenameMode};
+        use std::os::unix::fs::symlink;
+
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("..2024_a")).unwrap();
+        fs::write(dir.path().join("..2024_a/config.toml"), "value = 1").unwrap();
+        symlink("..2024_a", dir.path().join("..data")).unwrap();
+        let config_path = dir.path().join("config.toml");
+        symlink("..data/config.toml", &config_path).unwrap();
+
+        let watched = WatchedPaths::new(std::slice::from_ref(&config_path));
+
+        let swap = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::To)))
+            .add_path(dir.path().join("..data"));
+        assert_eq!(
+            watched.trigger_for(&swap),
+            Some(ChangeTrigger::FileModified(config_path))
+        );
+        assert!(replaces_file(&swap));
+
+        // Staging the next version is not a change yet
+        let staged =
+            Event::new(EventKind::Create(CreateKind::Folder)).add_path(dir.path().join("..2024_b"));
+        assert!(watched.trigger_for(&staged).is_none());
+    }
+
+    #[test]
+    fn test_data_dir_ignored_for_regular_files() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "value = 1").unwrap();
+
+        let watched = WatchedPaths::new(std::slice::from_ref(&config_path));
+
+        let swap = Event::new(EventKind::Create(notify::event::CreateKind::Any))
+            .add_path(dir.path().join("..data"));
+        assert!(watched.trigger_for(&swap).is_none());
+    }
 }
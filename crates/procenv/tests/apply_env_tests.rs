@@ -0,0 +1,249 @@
+//! In-place loading tests.
+//!
+//! Tests for the generated `apply_env()`, which overwrites only the fields
+//! of an existing instance whose env vars are set.
+
+#![allow(clippy::pedantic)]
+
+use procenv::{EnvConfig, Error};
+use serial_test::serial;
+
+fn cleanup_env(vars: &[&str]) {
+    unsafe {
+        for k in vars {
+            std::env::remove_var(*k);
+        }
+    }
+}
+
+fn with_env<F, R>(vars: &[(&str, &str)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        for (k, v) in vars {
+            std::env::set_var(*k, *v);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (k, _) in vars {
+            std::env::remove_var(*k);
+        }
+    }
+
+    result
+}
+
+const VARS: &[&str] = &[
+    "AE_HOST",
+    "AE_PORT",
+    "AE_LEVEL",
+    "AE_TOKEN",
+    "AE_DB_URL",
+    "AE_DB_POOL",
+];
+
+#[derive(EnvConfig)]
+struct AppConfig {
+    #[env(var = "AE_HOST")]
+    host: String,
+
+    #[env(var = "AE_PORT", default = "8080")]
+    port: u16,
+
+    #[env(var = "AE_LEVEL", one_of = ["debug", "info"], default = "info")]
+    level: String,
+
+    #[env(var = "AE_TOKEN", optional)]
+    token: Option<String>,
+
+    #[env(flatten, prefix = "AE_")]
+    db: DbConfig,
+}
+
+#[derive(EnvConfig)]
+struct DbConfig {
+    #[env(var = "DB_URL")]
+    url: String,
+
+    #[env(var = "DB_POOL", default = "4")]
+    pool: u32,
+}
+
+fn caller_config() -> AppConfig {
+    AppConfig {
+        host: "caller.local".to_string(),
+        port: 9000,
+        level: "debug".to_string(),
+        token: None,
+        db: DbConfig {
+            url: "postgres://caller".to_string(),
+            pool: 16,
+        },
+    }
+}
+
+#[test]
+#[serial]
+fn test_unset_vars_keep_caller_values() {
+    cleanup_env(VARS);
+
+    let mut config = caller_config();
+    config.apply_env().expect("nothing set is not an error");
+
+    // Required fields need no var, and defaults are not applied
+    assert_eq!(config.host, "caller.local");
+    assert_eq!(config.port, 9000);
+    assert_eq!(config.level, "debug");
+    assert_eq!(config.token, None);
+    assert_eq!(config.db.url, "postgres://caller");
+    assert_eq!(config.db.pool, 16);
+}
+
+#[test]
+#[serial]
+fn test_set_vars_override() {
+    cleanup_env(VARS);
+
+    with_env(
+        &[
+            ("AE_PORT", "7000"),
+            ("AE_TOKEN", "tok"),
+            ("AE_DB_POOL", "32"),
+        ],
+        || {
+            let mut config = caller_config();
+            config.apply_env().expect("valid vars should apply");
+
+            assert_eq!(config.host, "caller.local");
+            assert_eq!(config.port, 7000);
+            assert_eq!(config.token.as_deref(), Some("tok"));
+            assert_eq!(config.db.url, "postgres://caller");
+            assert_eq!(config.db.pool, 32);
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_invalid_value_leaves_fields_untouched() {
+    cleanup_env(VARS);
+
+    with_env(
+        &[("AE_HOST", "env.local"), ("AE_PORT", "not-a-port")],
+        || {
+            let mut config = caller_config();
+            let err = config.apply_env().unwrap_err();
+
+            assert!(matches!(err, Error::Parse { .. }), "{err:?}");
+            assert_eq!(config.host, "caller.local");
+            assert_eq!(config.port, 9000);
+        },
+    );
+}
+
+#[derive(EnvConfig)]
+struct ReplicatedConfig {
+    #[env(flatten, prefix = "AE_PRIMARY_")]
+    primary: DbConfig,
+
+    #[env(flatten, prefix = "AE_REPLICA_")]
+    replica: DbConfig,
+}
+
+#[test]
+#[serial]
+fn test_nested_error_leaves_all_fields_untouched() {
+    cleanup_env(&["AE_PRIMARY_DB_URL", "AE_REPLICA_DB_URL"]);
+
+    // The primary is valid, but is not applied while the replica fails
+    with_env(
+        &[("AE_PRIMARY_DB_POOL", "32"), ("AE_REPLICA_DB_POOL", "many")],
+        || {
+            let db = || DbConfig {
+                url: "postgres://caller".to_string(),
+                pool: 16,
+            };
+            let mut config = ReplicatedConfig {
+                primary: db(),
+                replica: db(),
+            };
+            let err = config.apply_env().unwrap_err();
+
+            assert!(err.to_string().contains("AE_REPLICA_DB_POOL"), "{err}");
+            assert_eq!(config.primary.pool, 16);
+            assert_eq!(config.replica.pool, 16);
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_set_values_are_checked() {
+    cleanup_env(VARS);
+
+    with_env(&[("AE_LEVEL", "trace"), ("AE_PORT", "x")], || {
+        let mut config = caller_config();
+        let err = config.apply_env().unwrap_err();
+
+        // Errors are accumulated like from_env()
+        let message = format!("{err:?}");
+        assert!(matches!(err, Error::Multiple { .. }), "{message}");
+        assert!(message.contains("AE_LEVEL"), "{message}");
+        assert!(message.contains("AE_PORT"), "{message}");
+        assert_eq!(config.level, "debug");
+    });
+}
+
+#[cfg(feature = "secrecy")]
+#[test]
+#[serial]
+fn test_secret_string_is_applied() {
+    use procenv::{ExposeSecret, SecretString};
+
+    #[derive(EnvConfig)]
+    struct SecretConfig {
+        #[env(var = "AE_TOKEN")]
+        token: SecretString,
+    }
+
+    cleanup_env(VARS);
+
+    let mut config = SecretConfig {
+        token: SecretString::from("caller"),
+    };
+    config.apply_env().unwrap();
+    assert_eq!(config.token.expose_secret(), "caller");
+
+    with_env(&[("AE_TOKEN", "from-env")], || {
+        config.apply_env().unwrap();
+        assert_eq!(config.token.expose_secret(), "from-env");
+    });
+}
+
+#[cfg(feature = "secrecy")]
+#[test]
+#[serial]
+fn test_secret_box_parse_error_uses_type_label() {
+    use procenv::SecretBox;
+
+    #[derive(EnvConfig)]
+    struct SecretPinConfig {
+        #[env(var = "AE_PIN", type_label = "pin code")]
+        pin: SecretBox<u16>,
+    }
+
+    let mut config = SecretPinConfig {
+        pin: SecretBox::new(Box::new(1234)),
+    };
+
+    with_env(&[("AE_PIN", "abcd")], || {
+        match config.apply_env().unwrap_err() {
+            Error::Parse { expected_type, .. } => assert_eq!(expected_type, "pin code"),
+            other => panic!("expected Parse, got {other:?}"),
+        }
+    });
+}
@@ -0,0 +1,122 @@
+//! Secret access audit tests.
+//!
+//! Tests for the `#[env_config(audit = "...")]` hook and `sensitive_log`.
+
+#![allow(clippy::pedantic)]
+
+use std::sync::Mutex;
+
+use procenv::EnvConfig;
+use serial_test::serial;
+
+static AUDIT_LOG: Mutex<Vec<(String, bool)>> = Mutex::new(Vec::new());
+
+fn record_access(var: &str, present: bool) {
+    AUDIT_LOG.lock().unwrap().push((var.to_string(), present));
+}
+
+fn take_log() -> Vec<(String, bool)> {
+    std::mem::take(&mut *AUDIT_LOG.lock().unwrap())
+}
+
+fn cleanup_env(vars: &[&str]) {
+    unsafe {
+        for k in vars {
+            std::env::remove_var(*k);
+        }
+    }
+}
+
+fn with_env<F, R>(vars: &[(&str, &str)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        for (k, v) in vars {
+            std::env::set_var(*k, *v);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (k, _) in vars {
+            std::env::remove_var(*k);
+        }
+    }
+
+    result
+}
+
+const VARS: &[&str] = &[
+    "AUD_HOST",
+    "AUD_API_KEY",
+    "AUD_TENANT",
+    "AUD_BACKUP_KEY",
+    "T1_AUD_HOST",
+    "T1_AUD_API_KEY",
+];
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "AUD_", audit = "record_access")]
+#[allow(dead_code)]
+struct AuditedConfig {
+    #[env(var = "HOST", default = "localhost")]
+    host: String,
+
+    #[env(var = "API_KEY", secret)]
+    api_key: String,
+
+    #[env(var = "TENANT", sensitive_log, default = "shared")]
+    tenant: String,
+
+    #[env(var = "BACKUP_KEY", secret, optional)]
+    backup_key: Option<String>,
+}
+
+#[test]
+#[serial]
+fn test_audit_reports_secret_and_sensitive_fields() {
+    cleanup_env(VARS);
+    take_log();
+
+    with_env(&[("AUD_API_KEY", "sk-live-123")], || {
+        AuditedConfig::from_env().expect("should load");
+    });
+
+    assert_eq!(
+        take_log(),
+        [
+            ("AUD_API_KEY".to_string(), true),
+            ("AUD_TENANT".to_string(), false),
+            ("AUD_BACKUP_KEY".to_string(), false),
+        ]
+    );
+}
+
+#[test]
+#[serial]
+fn test_audit_fires_on_failed_load() {
+    cleanup_env(VARS);
+    take_log();
+
+    assert!(AuditedConfig::from_env().is_err());
+
+    let log = take_log();
+    assert!(log.contains(&("AUD_API_KEY".to_string(), false)));
+}
+
+#[test]
+#[serial]
+fn test_audit_uses_runtime_prefix() {
+    cleanup_env(VARS);
+    take_log();
+
+    with_env(&[("T1_AUD_API_KEY", "sk-tenant")], || {
+        AuditedConfig::from_env_with_prefix("T1_").expect("should load");
+    });
+
+    let log = take_log();
+    assert_eq!(log.len(), 3);
+    assert_eq!(log[0], ("T1_AUD_API_KEY".to_string(), true));
+}
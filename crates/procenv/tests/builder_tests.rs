@@ -0,0 +1,208 @@
+//! Builder tests.
+//!
+//! Tests for the generated `builder()`, which constructs a config in code
+//! with the loading semantics but without reading env vars.
+
+#![allow(clippy::pedantic)]
+
+use procenv::{EnvConfig, Error};
+use serial_test::serial;
+
+#[derive(EnvConfig)]
+#[allow(dead_code)]
+struct AppConfig {
+    #[env(var = "BLD_HOST")]
+    host: String,
+
+    #[env(var = "BLD_PORT", default = "8080")]
+    port: u16,
+
+    #[env(var = "BLD_TOKEN", optional)]
+    token: Option<String>,
+
+    #[env(var = "BLD_KEY", secret)]
+    key: String,
+
+    #[env(skip)]
+    runtime_id: u64,
+
+    #[env(flatten, prefix = "BLD_")]
+    db: DbConfig,
+}
+
+#[derive(EnvConfig)]
+struct DbConfig {
+    #[env(var = "DB_URL", default = "postgres://localhost")]
+    url: String,
+
+    #[env(var = "DB_POOL", default = "4")]
+    pool: u32,
+}
+
+#[derive(EnvConfig)]
+struct BadDefaultConfig {
+    #[env(var = "BLD_BAD", default = "not-a-number")]
+    count: u32,
+}
+
+#[test]
+fn test_build_fills_defaults() {
+    let config = AppConfig::builder()
+        .host("example.com".to_string())
+        .key("k".to_string())
+        .build()
+        .expect("required fields are set");
+
+    assert_eq!(config.host, "example.com");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.token, None);
+    assert_eq!(config.runtime_id, 0);
+    assert_eq!(config.db.url, "postgres://localhost");
+    assert_eq!(config.db.pool, 4);
+}
+
+#[test]
+fn test_setters_override_defaults() {
+    let db = DbConfig::builder().pool(16).build().unwrap();
+    let config = AppConfig::builder()
+        .host("example.com".to_string())
+        .key("k".to_string())
+        .port(9000)
+        .token(Some("t".to_string()))
+        .runtime_id(7)
+        .db(db)
+        .build()
+        .unwrap();
+
+    assert_eq!(config.port, 9000);
+    assert_eq!(config.token.as_deref(), Some("t"));
+    assert_eq!(config.runtime_id, 7);
+    assert_eq!(config.db.pool, 16);
+}
+
+#[test]
+fn test_missing_required_fields_are_reported() {
+    let err = AppConfig::builder().port(1).build().unwrap_err();
+
+    let Error::Multiple { errors } = &err else {
+        panic!("expected both required fields, got {err:?}");
+    };
+    let missing: Vec<&str> = err.missing_vars();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(missing, ["BLD_HOST", "BLD_KEY"]);
+    assert!(format!("{err:?}").contains(".host(...)"));
+}
+
+#[test]
+fn test_bad_default_is_a_parse_error() {
+    let err = BadDefaultConfig::builder().build().unwrap_err();
+    assert!(matches!(err, Error::Parse { .. }), "{err:?}");
+
+    assert_eq!(
+        BadDefaultConfig::builder().count(3).build().unwrap().count,
+        3
+    );
+}
+
+#[test]
+#[serial]
+fn test_build_ignores_env() {
+    unsafe {
+        std::env::set_var("BLD_PORT", "1234");
+    }
+
+    let config = AppConfig::builder()
+        .host("h".to_string())
+        .key("k".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(config.port, 8080);
+
+    unsafe {
+        std::env::remove_var("BLD_PORT");
+    }
+}
+
+#[cfg(feature = "secrecy")]
+#[test]
+fn test_secret_types_in_setters() {
+    use procenv::{ExposeSecret, SecretString};
+
+    #[derive(EnvConfig)]
+    struct SecretConfig {
+        #[env(var = "BLD_SECRET")]
+        secret: SecretString,
+    }
+
+    let config = SecretConfig::builder()
+        .secret(SecretString::from("s3cret"))
+        .build()
+        .unwrap();
+    assert_eq!(config.secret.expose_secret(), "s3cret");
+
+    assert!(SecretConfig::builder().build().is_err());
+}
+
+// ============================================================================
+// derive_default
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[env_config(derive_default)]
+struct DefaultedConfig {
+    #[env(var = "BLD_DEF_PORT", default = "8080")]
+    port: u16,
+
+    #[env(var = "BLD_DEF_DEBUG", presence_bool)]
+    debug: bool,
+
+    #[env(var = "BLD_DEF_TOKEN", optional)]
+    token: Option<String>,
+
+    #[env(skip)]
+    runtime_id: u64,
+
+    #[env(flatten, prefix = "BLD_DEF_")]
+    pool: PoolConfig,
+}
+
+#[derive(EnvConfig)]
+#[env_config(derive_default)]
+struct PoolConfig {
+    #[env(var = "POOL_SIZE", default = "4")]
+    size: u32,
+}
+
+#[derive(EnvConfig)]
+#[env_config(derive_default)]
+#[allow(dead_code)]
+struct BadDerivedDefault {
+    #[env(var = "BLD_DEF_BAD", default = "not-a-number")]
+    count: u32,
+}
+
+#[test]
+#[serial]
+fn test_derive_default_uses_attribute_defaults() {
+    unsafe {
+        std::env::set_var("BLD_DEF_PORT", "9090");
+    }
+
+    // Env vars are not read, so this is the attribute default
+    let config = DefaultedConfig::default();
+    assert_eq!(config.port, 8080);
+    assert!(!config.debug);
+    assert_eq!(config.token, None);
+    assert_eq!(config.runtime_id, 0);
+    assert_eq!(config.pool.size, 4);
+
+    unsafe {
+        std::env::remove_var("BLD_DEF_PORT");
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid `default` in `BadDerivedDefault`")]
+fn test_derive_default_panics_on_bad_default() {
+    let _ = BadDerivedDefault::default();
+}
@@ -0,0 +1,151 @@
+//! Case-insensitive lookup tests.
+//!
+//! Tests for `#[env_config(case_insensitive_env)]`, which matches env var
+//! names ignoring ASCII case.
+
+#![allow(clippy::pedantic)]
+
+use procenv::EnvConfig;
+use serial_test::serial;
+
+fn cleanup_env(vars: &[&str]) {
+    unsafe {
+        for k in vars {
+            std::env::remove_var(*k);
+        }
+    }
+}
+
+fn with_env<F, R>(vars: &[(&str, &str)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        for (k, v) in vars {
+            std::env::set_var(*k, *v);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (k, _) in vars {
+            std::env::remove_var(*k);
+        }
+    }
+
+    result
+}
+
+const VARS: &[&str] = &[
+    "CI_HOST",
+    "ci_host",
+    "Ci_Host",
+    "CI_PORT",
+    "ci_port",
+    "ci_db_url",
+];
+
+#[derive(EnvConfig)]
+#[env_config(case_insensitive_env)]
+struct InsensitiveConfig {
+    #[env(var = "CI_HOST")]
+    host: String,
+
+    #[env(var = "CI_PORT", default = "8080")]
+    port: u16,
+
+    #[env(flatten, prefix = "CI_")]
+    db: DbConfig,
+}
+
+#[derive(EnvConfig)]
+struct DbConfig {
+    #[env(var = "DB_URL", default = "postgres://localhost")]
+    url: String,
+}
+
+#[derive(EnvConfig)]
+#[allow(dead_code)]
+struct SensitiveConfig {
+    #[env(var = "CI_HOST")]
+    host: String,
+}
+
+#[test]
+#[serial]
+fn test_lowercase_var_matches() {
+    cleanup_env(VARS);
+
+    with_env(
+        &[
+            ("ci_host", "lower.local"),
+            ("ci_port", "9000"),
+            ("ci_db_url", "postgres://db"),
+        ],
+        || {
+            let config = InsensitiveConfig::from_env().expect("folded names should match");
+
+            assert_eq!(config.host, "lower.local");
+            assert_eq!(config.port, 9000);
+            // Flattened structs read inside the same lookup mode
+            assert_eq!(config.db.url, "postgres://db");
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_exact_match_wins() {
+    cleanup_env(VARS);
+
+    with_env(&[("CI_HOST", "exact"), ("ci_host", "folded")], || {
+        let config = InsensitiveConfig::from_env().unwrap();
+        assert_eq!(config.host, "exact");
+    });
+}
+
+#[test]
+#[serial]
+fn test_collision_uses_first_sorted_name() {
+    cleanup_env(VARS);
+
+    with_env(&[("ci_host", "a"), ("Ci_Host", "b")], || {
+        let config = InsensitiveConfig::from_env().unwrap();
+        assert_eq!(config.host, "b");
+    });
+
+    let config = InsensitiveConfig::from_dotenv_str("ci_host=a\nCi_Host=b\ncI_hOST=c\n").unwrap();
+    assert_eq!(config.host, "b");
+}
+
+#[test]
+#[serial]
+fn test_other_loaders_match() {
+    cleanup_env(VARS);
+
+    with_env(&[("ci_host", "lower.local")], || {
+        let (config, sources) = InsensitiveConfig::from_env_with_sources().unwrap();
+        assert_eq!(config.host, "lower.local");
+        assert!(sources.get("host").is_some());
+
+        let mut config = InsensitiveConfig::from_env().unwrap();
+        config.host = "caller".to_string();
+        config.apply_env().unwrap();
+        assert_eq!(config.host, "lower.local");
+    });
+}
+
+#[test]
+#[serial]
+fn test_mode_is_scoped_to_opted_in_structs() {
+    cleanup_env(VARS);
+
+    with_env(&[("ci_host", "lower.local")], || {
+        assert!(SensitiveConfig::from_env().is_err());
+        assert!(InsensitiveConfig::from_env().is_ok());
+        // The mode is switched off again after loading
+        assert!(!procenv::runtime::case_insensitive_env());
+        assert!(SensitiveConfig::from_env().is_err());
+    });
+}
@@ -544,3 +544,199 @@ fn test_from_args_from_priority_default() {
     // Should use default
     assert_eq!(config.value, "default-value");
 }
+
+// ============================================================================
+// CLI Count Flags: -vvv <-> VERBOSITY=3
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct ArgsCountConfig {
+    #[env(
+        var = "ARGSC_VERBOSITY",
+        default = "0",
+        arg = "verbose",
+        short = 'v',
+        count
+    )]
+    verbosity: u8,
+}
+
+#[test]
+#[serial]
+fn test_from_args_from_count_flag() {
+    cleanup_env(&["ARGSC_VERBOSITY"]);
+
+    let config = ArgsCountConfig::from_args_from(["test", "-vvv"]).expect("should parse");
+    assert_eq!(config.verbosity, 3);
+
+    let config =
+        ArgsCountConfig::from_args_from(["test", "--verbose", "-v"]).expect("should parse");
+    assert_eq!(config.verbosity, 2);
+}
+
+#[test]
+#[serial]
+fn test_from_args_from_count_falls_back_to_env() {
+    cleanup_env(&["ARGSC_VERBOSITY"]);
+
+    with_env(&[("ARGSC_VERBOSITY", "3")], || {
+        let (config, sources) =
+            ArgsCountConfig::from_args_from_with_sources(["test"]).expect("should parse");
+        assert_eq!(config.verbosity, 3);
+        assert_eq!(
+            sources.get("verbosity").unwrap().source,
+            procenv::Source::Environment
+        );
+
+        // Any flag occurrence overrides the env var
+        let config = ArgsCountConfig::from_args_from(["test", "-v"]).expect("should parse");
+        assert_eq!(config.verbosity, 1);
+    });
+
+    let config = ArgsCountConfig::from_args_from(["test"]).expect("should parse");
+    assert_eq!(config.verbosity, 0);
+}
+
+// ============================================================================
+// Pre-Parsed ArgMatches Tests
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_from_arg_matches_reads_subcommand() {
+    cleanup_env(&["ARGS_HOST", "ARGS_PORT", "ARGS_DEBUG"]);
+
+    let cmd = procenv::clap::Command::new("app")
+        .subcommand(procenv::clap::Command::new("serve").args(ArgsTestConfig::clap_args()));
+
+    let matches = cmd
+        .try_get_matches_from(["app", "serve", "--port", "4000"])
+        .expect("should parse");
+    let (name, sub_matches) = matches.subcommand().expect("subcommand given");
+    assert_eq!(name, "serve");
+
+    with_env(&[("ARGS_HOST", "env.example.com")], || {
+        let config = ArgsTestConfig::from_arg_matches(sub_matches).expect("should load");
+        assert_eq!(config.port, 4000);
+        assert_eq!(config.host, "env.example.com");
+        assert!(!config.debug);
+    });
+}
+
+#[test]
+#[serial]
+fn test_from_arg_matches_without_registered_args_uses_env() {
+    cleanup_env(&["ARGS_HOST", "ARGS_PORT", "ARGS_DEBUG"]);
+
+    // A command that never defined the config's args
+    let matches = procenv::clap::Command::new("app")
+        .try_get_matches_from(["app"])
+        .expect("should parse");
+
+    with_env(&[("ARGS_PORT", "5000")], || {
+        let config = ArgsTestConfig::from_arg_matches(&matches).expect("should load");
+        assert_eq!(config.port, 5000);
+        assert_eq!(config.host, "localhost");
+    });
+
+    let matches = procenv::clap::Command::new("app")
+        .args(ArgsCountConfig::clap_args())
+        .try_get_matches_from(["app", "-vv"])
+        .expect("should parse");
+    let config = ArgsCountConfig::from_arg_matches(&matches).expect("should load");
+    assert_eq!(config.verbosity, 2);
+}
+
+// ============================================================================
+// Runtime Prefix (prefix_env) Tests
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "CLINS_", prefix_env = "CLINS_SELECT")]
+struct CliNamespacedConfig {
+    #[env(var = "HOST", default = "localhost", arg = "host")]
+    host: String,
+
+    #[env(var = "PORT", default = "8080")]
+    port: u16,
+}
+
+#[test]
+#[serial]
+fn test_from_args_from_applies_prefix_env() {
+    cleanup_env(&[
+        "CLINS_SELECT",
+        "CLINS_HOST",
+        "CLINS_PORT",
+        "RED_CLINS_HOST",
+        "RED_CLINS_PORT",
+    ]);
+
+    with_env(
+        &[
+            ("CLINS_SELECT", "RED_"),
+            ("CLINS_HOST", "default.internal"),
+            ("CLINS_PORT", "1000"),
+            ("RED_CLINS_HOST", "red.internal"),
+            ("RED_CLINS_PORT", "2000"),
+        ],
+        || {
+            // Env fallback reads under the runtime prefix, as from_env() does
+            let (config, sources) =
+                CliNamespacedConfig::from_args_from_with_sources(["test"]).expect("should load");
+            assert_eq!(config.host, "red.internal");
+            assert_eq!(config.port, 2000);
+            assert_eq!(sources.get("host").unwrap().var_name, "RED_CLINS_HOST");
+            assert_eq!(sources.get("port").unwrap().var_name, "RED_CLINS_PORT");
+
+            // CLI values still win
+            let config = CliNamespacedConfig::from_args_from(["test", "--host", "cli.internal"])
+                .expect("should load");
+            assert_eq!(config.host, "cli.internal");
+            assert_eq!(config.port, 2000);
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_from_args_from_without_prefix_env_uses_compiled_prefix() {
+    cleanup_env(&["CLINS_SELECT", "CLINS_HOST", "CLINS_PORT"]);
+
+    with_env(&[("CLINS_PORT", "1000")], || {
+        let config = CliNamespacedConfig::from_args_from(["test"]).expect("should load");
+        assert_eq!(config.host, "localhost");
+        assert_eq!(config.port, 1000);
+    });
+}
+
+// ============================================================================
+// Empty Optional Subsystems (allow_empty_struct) Tests
+// ============================================================================
+
+#[derive(EnvConfig, Default, PartialEq)]
+#[env_config(allow_empty_struct)]
+struct CliPluginConfig {
+    #[env(var = "CLI_PLUGIN_URL", arg = "plugin-url")]
+    url: String,
+
+    #[env(var = "CLI_PLUGIN_TOKEN")]
+    token: String,
+}
+
+#[test]
+#[serial]
+fn test_from_args_from_allow_empty_struct_uses_default() {
+    cleanup_env(&["CLI_PLUGIN_URL", "CLI_PLUGIN_TOKEN"]);
+
+    let config = CliPluginConfig::from_args_from(["test"]).expect("absent subsystem");
+    assert!(config == CliPluginConfig::default());
+
+    // A CLI value means the subsystem is configured, so missing vars error
+    let result = CliPluginConfig::from_args_from(["test", "--plugin-url", "https://p"]);
+    assert!(result.is_err());
+
+    with_env(&[("CLI_PLUGIN_TOKEN", "t0k3n")], || {
+        assert!(CliPluginConfig::from_args_from(["test"]).is_err());
+    });
+}
@@ -0,0 +1,12 @@
+//! Test: `allow_empty_struct` requires the struct to implement `Default`
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(allow_empty_struct)]
+struct Plugin {
+    #[env(var = "PLUGIN_URL")]
+    url: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test: base64 flag requires Vec<u8> or String type
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "TLS_CERT_B64", base64)]
+    cert: u32,
+}
+
+fn main() {}
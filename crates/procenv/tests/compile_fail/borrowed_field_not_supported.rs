@@ -0,0 +1,11 @@
+//! Test: fields must own their data
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config<'a> {
+    #[env(var = "HOST")]
+    host: &'a str,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test: coerce_bool_numeric requires a bool field
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "DEBUG", coerce_bool_numeric)]
+    debug: u8,
+}
+
+fn main() {}
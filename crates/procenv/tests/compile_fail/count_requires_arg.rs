@@ -0,0 +1,11 @@
+//! Test: `count` requires `arg` to be set
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "VERBOSITY", default = "0", count)]
+    verbosity: u8,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+//! Test: default_profile must be one of the declared profiles
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(profile_env = "APP_ENV", profiles = ["dev", "prod"], default_profile = "qa")]
+struct Config {
+    #[env(var = "LOG_LEVEL")]
+    #[profile(dev = "debug", prod = "warn")]
+    log_level: String,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+//! Test: `derive_default` requires a default for every required field
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(derive_default)]
+struct Config {
+    #[env(var = "PORT", default = "8080")]
+    port: u16,
+
+    #[env(var = "DATABASE_URL")]
+    database_url: String,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+//! Test: deserialize_with requires format
+
+use procenv::EnvConfig;
+
+fn seconds<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    serde::Deserialize::deserialize(deserializer)
+}
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "TIMEOUT", deserialize_with = "seconds")]
+    timeout: u64,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test: distinguish_null needs an Option<Option<T>> field
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "PROXY", optional, distinguish_null)]
+    proxy: Option<String>,
+}
+
+fn main() {}
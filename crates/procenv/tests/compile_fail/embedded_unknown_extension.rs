@@ -0,0 +1,12 @@
+//! Test: Embedded config files must have a recognized extension
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(embedded = "config.ini")]
+struct Config {
+    #[env(var = "PORT", default = "8080")]
+    port: u16,
+}
+
+fn main() {}
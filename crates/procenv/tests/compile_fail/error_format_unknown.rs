@@ -0,0 +1,12 @@
+//! Test: error_format must be "pretty" or "json"
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(error_format = "yaml")]
+struct Config {
+    #[env(var = "PORT")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test: a field `file` requires struct-level config files
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "LICENSE", file = "license.json", file_key = "key")]
+    license: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Test: files_override_env needs at least one file layer
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "APP_", files_override_env)]
+struct Config {
+    #[env(var = "PORT")]
+    port: u16,
+}
+
+fn main() {}
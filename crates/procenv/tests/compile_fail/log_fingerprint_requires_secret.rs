@@ -0,0 +1,11 @@
+//! Test: `log_fingerprint` requires `secret` on a plain string field
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "API_KEY", log_fingerprint)]
+    api_key: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test: merge_env flag requires format = "json"
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "FEATURES", format = "toml", merge_env)]
+    features: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test: min_len cannot be greater than max_len
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "API_KEY", min_len = 64, max_len = 32)]
+    api_key: String,
+}
+
+fn main() {}
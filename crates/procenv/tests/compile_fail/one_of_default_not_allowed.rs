@@ -0,0 +1,11 @@
+//! Test: default must be one of the `one_of` values
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "TIER", default = "gold", one_of = ["free", "pro"])]
+    tier: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test: pointer_access flag requires format = "json"
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "FLAGS", pointer_access)]
+    flags: String,
+}
+
+fn main() {}
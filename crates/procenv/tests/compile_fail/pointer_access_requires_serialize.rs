@@ -0,0 +1,16 @@
+//! Test: pointer_access re-serializes the field, so its type must be Serialize
+
+use procenv::EnvConfig;
+
+#[derive(Debug, serde::Deserialize)]
+struct Flags {
+    beta: bool,
+}
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "FLAGS", format = "json", pointer_access)]
+    flags: Flags,
+}
+
+fn main() {}
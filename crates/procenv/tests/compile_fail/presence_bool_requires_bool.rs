@@ -0,0 +1,11 @@
+//! Test: presence_bool requires a bool field
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "DEBUG", presence_bool)]
+    debug: String,
+}
+
+fn main() {}
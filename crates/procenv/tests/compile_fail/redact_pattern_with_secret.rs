@@ -0,0 +1,11 @@
+//! Test: redact_pattern cannot be combined with secret
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "DATABASE_URL", secret, redact_pattern = ":([^@]+)@")]
+    database_url: String,
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+//! Test: reload_immutable compares values, so the field's type must be PartialEq
+
+use procenv::EnvConfig;
+
+#[derive(Clone, Debug)]
+struct Port(u16);
+
+impl std::str::FromStr for Port {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Port)
+    }
+}
+
+impl std::fmt::Display for Port {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "PORT", reload_immutable)]
+    port: Port,
+}
+
+fn main() {}
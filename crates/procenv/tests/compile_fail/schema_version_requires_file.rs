@@ -0,0 +1,12 @@
+//! Test: schema_version needs a layered loader to read the version from
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(schema_version = 2)]
+struct Config {
+    #[env(var = "PORT")]
+    port: u16,
+}
+
+fn main() {}
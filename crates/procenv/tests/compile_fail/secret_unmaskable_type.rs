@@ -0,0 +1,11 @@
+//! Test: secret requires a type the generated Debug can mask
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "UPSTREAM", secret)]
+    upstream: std::net::SocketAddr,
+}
+
+fn main() {}
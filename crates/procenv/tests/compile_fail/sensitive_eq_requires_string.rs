@@ -0,0 +1,11 @@
+//! Test: `sensitive_eq` requires a string field
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "PIN", sensitive_eq)]
+    pin: u32,
+}
+
+fn main() {}
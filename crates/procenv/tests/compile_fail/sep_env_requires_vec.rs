@@ -0,0 +1,11 @@
+//! Test: sep_env requires a Vec<T> field
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "PORTS", sep_env = "LIST_SEP")]
+    ports: String,
+}
+
+fn main() {}
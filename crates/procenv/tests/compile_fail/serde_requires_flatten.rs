@@ -0,0 +1,11 @@
+//! Test: serde requires flatten
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "TLS", serde)]
+    tls: String,
+}
+
+fn main() {}
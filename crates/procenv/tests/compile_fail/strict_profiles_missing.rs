@@ -0,0 +1,13 @@
+//! Test: strict_profiles requires every declared profile in #[profile]
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(profile_env = "APP_ENV", profiles = ["dev", "staging", "prod"], strict_profiles)]
+struct Config {
+    #[env(var = "DATABASE_URL")]
+    #[profile(dev = "postgres://localhost/dev")]
+    database_url: String,
+}
+
+fn main() {}
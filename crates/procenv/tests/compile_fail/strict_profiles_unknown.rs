@@ -0,0 +1,13 @@
+//! Test: strict_profiles rejects #[profile] names outside the allowlist
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(profile_env = "APP_ENV", profiles = ["dev", "prod"], strict_profiles)]
+struct Config {
+    #[env(var = "LOG_LEVEL")]
+    #[profile(dev = "debug", prod = "warn", qa = "info")]
+    log_level: String,
+}
+
+fn main() {}
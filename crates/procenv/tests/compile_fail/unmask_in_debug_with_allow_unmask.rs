@@ -0,0 +1,12 @@
+//! Test: `unmask_in_debug` cannot be combined with `allow_unmask`
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(unmask_in_debug, allow_unmask)]
+struct Config {
+    #[env(var = "API_KEY", secret)]
+    api_key: String,
+}
+
+fn main() {}
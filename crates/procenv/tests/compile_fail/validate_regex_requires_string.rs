@@ -0,0 +1,11 @@
+//! Test: validate_regex requires a string field
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "PORT", validate_regex = "^[0-9]+$")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test: `when_profile` requires a struct-level `profile_env`
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "TLS_CERT", when_profile = "prod")]
+    tls_cert: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Test: `when_profile` names must be declared in `profiles`
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(profile_env = "APP_ENV", profiles = ["dev", "prod"])]
+struct Config {
+    #[env(var = "TLS_CERT", when_profile = ["prod", "staging"])]
+    tls_cert: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+//! Test: cfg-gated fields compile whether their predicate holds or not
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "PORT", default = "8080")]
+    port: u16,
+
+    // Always enabled
+    #[cfg(all())]
+    #[env(var = "METRICS_PORT", default = "9090")]
+    metrics_port: u16,
+
+    // Always disabled: the field, its env var and its type never exist
+    #[cfg(any())]
+    #[env(var = "TRACING_ENDPOINT")]
+    tracing_endpoint: DoesNotExist,
+}
+
+fn main() {
+    let config = Config::from_env().unwrap();
+    let _ = (config.port, config.metrics_port);
+    assert_eq!(Config::keys(), ["metrics_port", "port"]);
+}
@@ -0,0 +1,191 @@
+//! Fallback env var tests.
+//!
+//! Tests for `#[env(default_from = "...")]`, which reads a sibling env var
+//! when the field's own var is unset.
+
+#![allow(clippy::pedantic)]
+
+use procenv::{EnvConfig, Error, Source};
+use serial_test::serial;
+
+fn cleanup_env(vars: &[&str]) {
+    unsafe {
+        for k in vars {
+            std::env::remove_var(*k);
+        }
+    }
+}
+
+fn with_env<F, R>(vars: &[(&str, &str)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        for (k, v) in vars {
+            std::env::set_var(*k, *v);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (k, _) in vars {
+            std::env::remove_var(*k);
+        }
+    }
+
+    result
+}
+
+const VARS: &[&str] = &[
+    "DF_DATA_DIR",
+    "DF_LOG_DIR",
+    "DF_CACHE_DIR",
+    "DF_PORT",
+    "DF_ADMIN_PORT",
+    "DF_TIMEOUT",
+    "DF_READ_TIMEOUT",
+    "SVC_DF_DATA_DIR",
+    "SVC_DF_LOG_DIR",
+];
+
+#[derive(EnvConfig)]
+struct FallbackConfig {
+    #[env(var = "DF_DATA_DIR")]
+    data_dir: String,
+
+    #[env(var = "DF_LOG_DIR", default_from = "DF_DATA_DIR")]
+    log_dir: String,
+
+    #[env(var = "DF_CACHE_DIR", default_from = "DF_DATA_DIR", optional)]
+    cache_dir: Option<String>,
+}
+
+#[derive(EnvConfig)]
+struct NumericFallbackConfig {
+    #[env(var = "DF_ADMIN_PORT", default_from = "DF_PORT")]
+    admin_port: u16,
+
+    #[env(var = "DF_READ_TIMEOUT", default_from = "DF_TIMEOUT", default = "30")]
+    read_timeout: u32,
+
+    #[env(var = "DF_CACHE_DIR", default_from = "DF_DATA_DIR", optional)]
+    cache_dir: Option<String>,
+}
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "SVC_")]
+struct PrefixedFallbackConfig {
+    #[env(var = "DF_LOG_DIR", default_from = "DF_DATA_DIR")]
+    log_dir: String,
+}
+
+#[derive(EnvConfig)]
+struct OuterConfig {
+    #[env(flatten, prefix = "SVC_")]
+    inner: FallbackConfig,
+}
+
+#[test]
+#[serial]
+fn test_primary_var_wins() {
+    cleanup_env(VARS);
+    with_env(&[("DF_DATA_DIR", "/data"), ("DF_LOG_DIR", "/logs")], || {
+        let config = FallbackConfig::from_env().unwrap();
+        assert_eq!(config.log_dir, "/logs");
+    });
+}
+
+#[test]
+#[serial]
+fn test_falls_back_when_primary_missing() {
+    cleanup_env(VARS);
+    with_env(&[("DF_DATA_DIR", "/data")], || {
+        let config = FallbackConfig::from_env().unwrap();
+        assert_eq!(config.log_dir, "/data");
+        assert_eq!(config.cache_dir.as_deref(), Some("/data"));
+    });
+}
+
+#[test]
+#[serial]
+fn test_required_reports_primary_when_both_missing() {
+    cleanup_env(VARS);
+    with_env(&[("DF_READ_TIMEOUT", "5")], || {
+        let err = NumericFallbackConfig::from_env().unwrap_err();
+        match err {
+            Error::Missing { var, .. } => assert_eq!(var, "DF_ADMIN_PORT"),
+            other => panic!("expected Missing, got {other:?}"),
+        }
+    });
+}
+
+#[test]
+#[serial]
+fn test_optional_and_default_when_both_missing() {
+    cleanup_env(VARS);
+    with_env(&[("DF_PORT", "9000")], || {
+        let config = NumericFallbackConfig::from_env().unwrap();
+        assert_eq!(config.admin_port, 9000);
+        assert_eq!(config.read_timeout, 30);
+        assert_eq!(config.cache_dir, None);
+    });
+}
+
+#[test]
+#[serial]
+fn test_parse_error_names_fallback_var() {
+    cleanup_env(VARS);
+    with_env(&[("DF_PORT", "not-a-port")], || {
+        let err = NumericFallbackConfig::from_env().unwrap_err();
+        match err {
+            Error::Parse { var, .. } => assert_eq!(var, "DF_PORT"),
+            other => panic!("expected Parse, got {other:?}"),
+        }
+    });
+}
+
+#[test]
+#[serial]
+fn test_source_attributed_to_fallback_var() {
+    cleanup_env(VARS);
+    with_env(&[("DF_DATA_DIR", "/data"), ("DF_PORT", "9000")], || {
+        let (_, sources) = FallbackConfig::from_env_with_sources().unwrap();
+        let log_dir = sources.get("log_dir").unwrap();
+        assert_eq!(log_dir.var_name, "DF_DATA_DIR");
+        assert_eq!(log_dir.source, Source::Environment);
+
+        let (_, sources) = NumericFallbackConfig::from_env_with_sources().unwrap();
+        let read_timeout = sources.get("read_timeout").unwrap();
+        assert_eq!(read_timeout.var_name, "DF_READ_TIMEOUT");
+        assert_eq!(read_timeout.source, Source::Default);
+    });
+}
+
+#[test]
+#[serial]
+fn test_fallback_uses_struct_prefix() {
+    cleanup_env(VARS);
+    with_env(
+        &[("SVC_DF_DATA_DIR", "/srv"), ("DF_DATA_DIR", "/data")],
+        || {
+            let config = PrefixedFallbackConfig::from_env().unwrap();
+            assert_eq!(config.log_dir, "/srv");
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_fallback_uses_flatten_prefix() {
+    cleanup_env(VARS);
+    with_env(
+        &[("SVC_DF_DATA_DIR", "/srv"), ("DF_DATA_DIR", "/data")],
+        || {
+            let config = OuterConfig::from_env().unwrap();
+            assert_eq!(config.inner.data_dir, "/srv");
+            assert_eq!(config.inner.log_dir, "/srv");
+            assert_eq!(config.inner.cache_dir.as_deref(), Some("/srv"));
+        },
+    );
+}
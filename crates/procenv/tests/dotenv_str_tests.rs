@@ -0,0 +1,102 @@
+//! `from_dotenv_str()` tests.
+//!
+//! Tests for loading from `.env`-formatted text, which must neither read
+//! nor modify the process environment.
+
+#![allow(clippy::pedantic)]
+
+use procenv::{EnvConfig, Error};
+use serial_test::serial;
+
+fn with_env<F, R>(vars: &[(&str, &str)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        for (k, v) in vars {
+            std::env::set_var(*k, *v);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (k, _) in vars {
+            std::env::remove_var(*k);
+        }
+    }
+
+    result
+}
+
+#[derive(EnvConfig)]
+struct DbConfig {
+    #[env(var = "DS_DB_HOST")]
+    host: String,
+
+    #[env(var = "DS_DB_PORT", default = "5432")]
+    port: u16,
+}
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "APP_", dotenv_required = "tests/fixtures/does-not-exist.env")]
+struct AppConfig {
+    #[env(var = "DS_NAME")]
+    name: String,
+
+    #[env(var = "DS_WORKERS", optional)]
+    workers: Option<u32>,
+
+    #[env(var = "DS_TOKEN", secret)]
+    token: String,
+
+    #[env(flatten)]
+    db: DbConfig,
+}
+
+const FIXTURE: &str = r#"
+# Local development settings
+APP_DS_NAME="demo app"
+APP_DS_TOKEN=hunter2
+DS_DB_HOST=localhost
+"#;
+
+#[test]
+#[serial]
+fn test_from_dotenv_str_loads_fields() {
+    let config = AppConfig::from_dotenv_str(FIXTURE).unwrap();
+
+    assert_eq!(config.name, "demo app");
+    assert_eq!(config.workers, None);
+    assert_eq!(config.token, "hunter2");
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 5432);
+}
+
+#[test]
+#[serial]
+fn test_from_dotenv_str_ignores_process_env() {
+    with_env(
+        &[("APP_DS_NAME", "from-env"), ("APP_DS_WORKERS", "8")],
+        || {
+            let config = AppConfig::from_dotenv_str(FIXTURE).unwrap();
+            assert_eq!(config.name, "demo app");
+            assert_eq!(config.workers, None);
+
+            assert_eq!(std::env::var("APP_DS_NAME").unwrap(), "from-env");
+        },
+    );
+
+    assert!(std::env::var("APP_DS_TOKEN").is_err());
+    assert!(std::env::var("DS_DB_HOST").is_err());
+}
+
+#[test]
+#[serial]
+fn test_from_dotenv_str_reports_missing_and_syntax_errors() {
+    let err = AppConfig::from_dotenv_str("APP_DS_NAME=demo\n").unwrap_err();
+    assert_eq!(err.missing_vars(), ["APP_DS_TOKEN", "DS_DB_HOST"]);
+
+    let err = DbConfig::from_dotenv_str("DS_DB_HOST localhost\n").unwrap_err();
+    assert!(matches!(err, Error::Provider { .. }), "{err:?}");
+}
@@ -166,8 +166,9 @@ fn test_float_infinity() {
     with_env(
         &[("EDGE_U16", "80"), ("EDGE_I32", "0"), ("EDGE_F64", "inf")],
         || {
-            let config = NumericConfig::from_env().expect("infinity should parse");
-            assert!(config.ratio.is_infinite());
+            // Non-finite floats are rejected unless the field opts in
+            let result = NumericConfig::from_env();
+            assert!(result.is_err(), "infinity should be rejected");
         },
     );
 }
@@ -289,6 +290,140 @@ fn test_special_chars_emoji() {
     });
 }
 
+// ============================================================================
+// Base64 Values
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct Base64Config {
+    #[env(var = "EDGE_B64_CERT", base64, secret)]
+    cert: Vec<u8>,
+
+    #[env(var = "EDGE_B64_NAME", base64, default = "ZGVmYXVsdA==")]
+    name: String,
+
+    #[env(var = "EDGE_B64_EXTRA", base64, optional)]
+    extra: Option<Vec<u8>>,
+}
+
+#[test]
+#[serial]
+fn test_base64_decodes_both_alphabets() {
+    cleanup_vars(&["EDGE_B64_NAME", "EDGE_B64_EXTRA"]);
+
+    with_env(&[("EDGE_B64_CERT", "+/8=")], || {
+        let config = Base64Config::from_env().expect("standard base64 should decode");
+        assert_eq!(config.cert, [0xfb, 0xff]);
+        assert_eq!(config.name, "default");
+        assert_eq!(config.extra, None);
+    });
+
+    with_env(
+        &[
+            ("EDGE_B64_CERT", "-_8"),
+            ("EDGE_B64_NAME", "aGVsbG8"),
+            ("EDGE_B64_EXTRA", "AAE="),
+        ],
+        || {
+            let config = Base64Config::from_env().expect("URL-safe base64 should decode");
+            assert_eq!(config.cert, [0xfb, 0xff]);
+            assert_eq!(config.name, "hello");
+            assert_eq!(config.extra, Some(vec![0, 1]));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_base64_errors_redact_secrets() {
+    cleanup_vars(&["EDGE_B64_NAME", "EDGE_B64_EXTRA"]);
+
+    with_env(&[("EDGE_B64_CERT", "not*base64")], || {
+        let err = Base64Config::from_env().unwrap_err();
+        let debug = format!("{err:?}");
+
+        assert!(matches!(err, procenv::Error::Parse { .. }), "{debug}");
+        assert!(!debug.contains("not*base64"), "secret leaked: {debug}");
+    });
+
+    with_env(
+        &[("EDGE_B64_CERT", "AA=="), ("EDGE_B64_NAME", "/w==")],
+        || {
+            let err = Base64Config::from_env().unwrap_err();
+            assert!(
+                format!("{err:?}").contains("/w=="),
+                "non-secret value should be shown: {err:?}"
+            );
+        },
+    );
+}
+
+// ============================================================================
+// Runtime List Separators
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct ListConfig {
+    #[env(var = "EDGE_LIST_PORTS", sep_env = "EDGE_LIST_SEP")]
+    ports: Vec<u16>,
+
+    #[env(var = "EDGE_LIST_HOSTS", sep_env = "EDGE_LIST_SEP", default = "a,b")]
+    hosts: Vec<String>,
+
+    #[env(var = "EDGE_LIST_TAGS", sep_env = "EDGE_LIST_SEP", optional)]
+    tags: Option<Vec<String>>,
+}
+
+#[test]
+#[serial]
+fn test_list_separator_defaults_to_comma() {
+    cleanup_vars(&["EDGE_LIST_SEP", "EDGE_LIST_HOSTS", "EDGE_LIST_TAGS"]);
+
+    with_env(&[("EDGE_LIST_PORTS", "80, 443")], || {
+        let config = ListConfig::from_env().expect("comma list should parse");
+        assert_eq!(config.ports, [80, 443]);
+        assert_eq!(config.hosts, ["a", "b"]);
+        assert_eq!(config.tags, None);
+        assert_eq!(config.get_str("ports"), Some("[80, 443]".to_string()));
+    });
+
+    // An empty separator is treated as unset rather than splitting per character
+    with_env(
+        &[("EDGE_LIST_SEP", ""), ("EDGE_LIST_PORTS", "80,443")],
+        || {
+            let config = ListConfig::from_env().expect("empty separator falls back to comma");
+            assert_eq!(config.ports, [80, 443]);
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_list_separator_read_from_env() {
+    cleanup_vars(&["EDGE_LIST_HOSTS"]);
+
+    with_env(
+        &[
+            ("EDGE_LIST_SEP", ";"),
+            ("EDGE_LIST_PORTS", "80;443"),
+            ("EDGE_LIST_TAGS", "blue;green,teal"),
+        ],
+        || {
+            let config = ListConfig::from_env().expect("semicolon list should parse");
+            assert_eq!(config.ports, [80, 443]);
+            assert_eq!(config.tags, Some(vec!["blue".into(), "green,teal".into()]));
+        },
+    );
+
+    with_env(
+        &[("EDGE_LIST_SEP", ";"), ("EDGE_LIST_PORTS", "80,443")],
+        || {
+            let err = ListConfig::from_env().unwrap_err();
+            assert!(matches!(err, procenv::Error::Parse { .. }), "{err:?}");
+        },
+    );
+}
+
 // ============================================================================
 // Optional Field Edge Cases
 // ============================================================================
@@ -584,3 +719,1124 @@ fn test_source_attribution_for_all_types() {
         assert!(config.optional.is_none());
     });
 }
+
+// ============================================================================
+// Allowlist (`one_of`) Tests
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct OneOfConfig {
+    #[env(var = "EDGE_TIER", default = "free", one_of = ["free", "pro", "enterprise"])]
+    tier: String,
+
+    #[env(var = "EDGE_REGION", optional, one_of = ["eu", "us"])]
+    region: Option<String>,
+}
+
+#[test]
+#[serial]
+fn test_one_of_accepts_listed_value() {
+    cleanup_vars(&["EDGE_TIER", "EDGE_REGION"]);
+
+    with_env(&[("EDGE_TIER", "pro"), ("EDGE_REGION", "eu")], || {
+        let config = OneOfConfig::from_env().expect("listed values should load");
+        assert_eq!(config.tier, "pro");
+        assert_eq!(config.region.as_deref(), Some("eu"));
+    });
+}
+
+#[test]
+#[serial]
+fn test_one_of_default_and_missing_optional() {
+    cleanup_vars(&["EDGE_TIER", "EDGE_REGION"]);
+
+    let config = OneOfConfig::from_env().expect("default should satisfy one_of");
+    assert_eq!(config.tier, "free");
+    assert!(config.region.is_none());
+}
+
+#[test]
+#[serial]
+fn test_one_of_rejects_unlisted_values() {
+    cleanup_vars(&["EDGE_TIER", "EDGE_REGION"]);
+
+    with_env(&[("EDGE_TIER", "gold"), ("EDGE_REGION", "ap")], || {
+        let err = OneOfConfig::from_env().unwrap_err();
+
+        let procenv::Error::Multiple { errors } = err else {
+            panic!("expected both fields to be rejected");
+        };
+        assert_eq!(errors.len(), 2);
+
+        let procenv::Error::NotOneOf {
+            var,
+            value,
+            allowed,
+            ..
+        } = &errors[0]
+        else {
+            panic!("expected NotOneOf, got {:?}", errors[0]);
+        };
+        assert_eq!(var, "EDGE_TIER");
+        assert_eq!(value.as_str(), Some("gold"));
+        assert_eq!(allowed, &["free", "pro", "enterprise"]);
+
+        assert!(format!("{:?}", errors[1]).contains("eu, us"));
+    });
+}
+
+// ============================================================================
+// Float Finiteness Tests
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct FloatConfig {
+    #[env(var = "EDGE_RATE", default = "1.5")]
+    rate: f64,
+
+    #[env(var = "EDGE_RATIO", optional)]
+    ratio: Option<f32>,
+}
+
+#[derive(EnvConfig)]
+struct NonFiniteConfig {
+    #[env(var = "EDGE_LIMIT", allow_non_finite)]
+    limit: f64,
+}
+
+#[test]
+#[serial]
+fn test_float_rejects_non_finite() {
+    cleanup_vars(&["EDGE_RATE", "EDGE_RATIO"]);
+
+    with_env(&[("EDGE_RATE", "inf"), ("EDGE_RATIO", "NaN")], || {
+        let err = FloatConfig::from_env().unwrap_err();
+
+        let procenv::Error::Multiple { errors } = err else {
+            panic!("expected both fields to be rejected");
+        };
+        assert_eq!(errors.len(), 2);
+
+        let procenv::Error::Parse {
+            var,
+            value,
+            expected_type,
+            help,
+            ..
+        } = &errors[0]
+        else {
+            panic!("expected Parse, got {:?}", errors[0]);
+        };
+        assert_eq!(var, "EDGE_RATE");
+        assert_eq!(value.as_str(), Some("inf"));
+        assert_eq!(expected_type, "f64");
+        assert!(help.contains("allow_non_finite"));
+    });
+}
+
+#[test]
+#[serial]
+fn test_float_finite_values_load() {
+    cleanup_vars(&["EDGE_RATE", "EDGE_RATIO"]);
+
+    with_env(&[("EDGE_RATIO", "-0.25")], || {
+        let config = FloatConfig::from_env().expect("finite floats should load");
+        assert!((config.rate - 1.5).abs() < f64::EPSILON);
+        assert_eq!(config.ratio, Some(-0.25));
+    });
+}
+
+#[test]
+#[serial]
+fn test_float_allow_non_finite() {
+    cleanup_vars(&["EDGE_LIMIT"]);
+
+    with_env(&[("EDGE_LIMIT", "inf")], || {
+        let config = NonFiniteConfig::from_env().expect("allow_non_finite should accept inf");
+        assert_eq!(config.limit, f64::INFINITY);
+    });
+}
+
+// ============================================================================
+// String Wrapper Tests
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct StringWrapperConfig {
+    #[env(var = "EDGE_ARC")]
+    shared: std::sync::Arc<str>,
+
+    #[env(var = "EDGE_BOX", default = "boxed-default")]
+    boxed: Box<str>,
+
+    #[env(var = "EDGE_COW")]
+    cow: std::borrow::Cow<'static, str>,
+
+    #[env(var = "EDGE_OPT_ARC", optional)]
+    opt_shared: Option<std::sync::Arc<str>>,
+}
+
+#[test]
+#[serial]
+fn test_string_wrappers_load() {
+    cleanup_vars(&["EDGE_ARC", "EDGE_BOX", "EDGE_COW", "EDGE_OPT_ARC"]);
+
+    with_env(
+        &[
+            ("EDGE_ARC", "shared"),
+            ("EDGE_BOX", "boxed"),
+            ("EDGE_COW", "cow"),
+            ("EDGE_OPT_ARC", "optional"),
+        ],
+        || {
+            let config = StringWrapperConfig::from_env().expect("string wrappers should load");
+            assert_eq!(&*config.shared, "shared");
+            assert_eq!(&*config.boxed, "boxed");
+            assert!(matches!(config.cow, std::borrow::Cow::Owned(ref s) if s == "cow"));
+            assert_eq!(config.opt_shared.as_deref(), Some("optional"));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_string_wrappers_default_and_missing_optional() {
+    cleanup_vars(&["EDGE_ARC", "EDGE_BOX", "EDGE_COW", "EDGE_OPT_ARC"]);
+
+    with_env(&[("EDGE_ARC", "shared"), ("EDGE_COW", "cow")], || {
+        let config = StringWrapperConfig::from_env().expect("defaults should apply");
+        assert_eq!(&*config.boxed, "boxed-default");
+        assert!(config.opt_shared.is_none());
+    });
+}
+
+// ============================================================================
+// Feature-Gated Fields
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct FeatureGatedConfig {
+    #[env(var = "EDGE_CFG_PORT", default = "8080")]
+    port: u16,
+
+    #[cfg(feature = "dotenv")]
+    #[env(var = "EDGE_CFG_ENABLED")]
+    enabled_only: String,
+
+    #[cfg(not(feature = "dotenv"))]
+    #[env(var = "EDGE_CFG_DISABLED")]
+    disabled_only: String,
+}
+
+#[test]
+#[serial]
+fn test_cfg_gated_fields_follow_features() {
+    let (present, absent) = if cfg!(feature = "dotenv") {
+        ("EDGE_CFG_ENABLED", "EDGE_CFG_DISABLED")
+    } else {
+        ("EDGE_CFG_DISABLED", "EDGE_CFG_ENABLED")
+    };
+    cleanup_vars(&["EDGE_CFG_PORT", absent]);
+
+    with_env(&[(present, "on")], || {
+        // The excluded field's var is unset, yet loading succeeds
+        let (config, sources) =
+            FeatureGatedConfig::from_env_with_sources().expect("gated field should load");
+        assert_eq!(config.port, 8080);
+
+        #[cfg(feature = "dotenv")]
+        assert_eq!(config.enabled_only, "on");
+        #[cfg(not(feature = "dotenv"))]
+        assert_eq!(config.disabled_only, "on");
+
+        let example = FeatureGatedConfig::env_example();
+        assert!(example.contains(present));
+        assert!(!example.contains(absent));
+
+        let field = if cfg!(feature = "dotenv") {
+            "enabled_only"
+        } else {
+            "disabled_only"
+        };
+        assert_eq!(FeatureGatedConfig::keys(), {
+            let mut keys = ["port", field];
+            keys.sort_unstable();
+            keys
+        });
+        assert!(sources.get(field).is_some());
+        assert_eq!(sources.iter().count(), 2);
+    });
+}
+
+// ============================================================================
+// String Length Bounds
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct LengthBoundsConfig {
+    #[env(var = "EDGE_LEN_KEY", min_len = 8, secret)]
+    api_key: String,
+
+    #[env(var = "EDGE_LEN_CODE", default = "abcd", min_len = 2, max_len = 4)]
+    code: String,
+
+    #[env(var = "EDGE_LEN_TAG", optional, max_len = 3)]
+    tag: Option<String>,
+}
+
+#[test]
+#[serial]
+fn test_length_bounds_accept_values_within_range() {
+    cleanup_vars(&["EDGE_LEN_CODE", "EDGE_LEN_TAG"]);
+
+    with_env(&[("EDGE_LEN_KEY", "ключ-1234")], || {
+        // Lengths count characters, not bytes
+        let config = LengthBoundsConfig::from_env().expect("values are within bounds");
+        assert_eq!(config.api_key, "ключ-1234");
+        assert_eq!(config.code, "abcd");
+        assert!(config.tag.is_none());
+    });
+}
+
+#[test]
+#[serial]
+fn test_length_bounds_reject_without_echoing_value() {
+    with_env(
+        &[
+            ("EDGE_LEN_KEY", "short"),
+            ("EDGE_LEN_CODE", "toolong"),
+            ("EDGE_LEN_TAG", "abcd"),
+        ],
+        || {
+            let err = LengthBoundsConfig::from_env().unwrap_err();
+            let procenv::Error::Multiple { errors } = &err else {
+                panic!("expected Multiple, got {err:?}");
+            };
+            let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            assert_eq!(
+                messages,
+                [
+                    "EDGE_LEN_KEY must be at least 8 characters long",
+                    "EDGE_LEN_CODE must be between 2 and 4 characters long",
+                    "EDGE_LEN_TAG must be at most 3 characters long",
+                ]
+            );
+            assert!(!format!("{err:?}").contains("short"));
+        },
+    );
+}
+
+#[cfg(feature = "secrecy")]
+#[derive(EnvConfig)]
+struct SecretLengthConfig {
+    #[env(var = "EDGE_LEN_SECRET", min_len = 16)]
+    token: procenv::SecretString,
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "secrecy")]
+fn test_length_bounds_on_secret_string() {
+    use procenv::ExposeSecret;
+
+    with_env(&[("EDGE_LEN_SECRET", "0123456789abcdef")], || {
+        let config = SecretLengthConfig::from_env().expect("secret is long enough");
+        assert_eq!(config.token.expose_secret().len(), 16);
+    });
+
+    with_env(&[("EDGE_LEN_SECRET", "hunter2")], || {
+        let err = SecretLengthConfig::from_env().unwrap_err();
+        assert!(matches!(
+            err,
+            procenv::Error::InvalidLength {
+                min: Some(16),
+                max: None,
+                ..
+            }
+        ));
+        assert!(!format!("{err:?}").contains("hunter2"));
+    });
+}
+
+// ============================================================================
+// Generic Structs
+// ============================================================================
+
+/// Not `Debug`, to check that skipped fields stay out of the `Debug` impl
+#[derive(Default)]
+struct Handle;
+
+#[derive(EnvConfig)]
+struct GenericConfig<'a, T> {
+    #[env(var = "EDGE_GENERIC_PORT", default = "8080")]
+    port: u16,
+
+    #[env(skip)]
+    _marker: std::marker::PhantomData<&'a T>,
+
+    #[env(skip)]
+    _handle: Handle,
+}
+
+#[test]
+#[serial]
+fn test_generic_struct_with_skipped_marker() {
+    with_env(&[("EDGE_GENERIC_PORT", "9090")], || {
+        let config = GenericConfig::<'_, u8>::from_env().expect("generic config loads");
+        assert_eq!(config.port, 9090);
+        assert_eq!(format!("{config:?}"), "GenericConfig { port: 9090 }");
+    });
+}
+
+#[test]
+#[serial]
+fn test_skipped_fields_have_no_keys_or_sources() {
+    cleanup_vars(&["EDGE_GENERIC_PORT"]);
+
+    let (config, sources) =
+        GenericConfig::<'_, String>::from_env_with_sources().expect("default applies");
+    assert_eq!(config.port, 8080);
+    assert_eq!(sources.entries().len(), 1);
+    assert_eq!(GenericConfig::<'_, String>::keys(), ["port"]);
+    assert!(!GenericConfig::<'_, String>::has_key("_marker"));
+    assert!(!GenericConfig::<'_, String>::env_example().contains("_marker"));
+}
+
+// ============================================================================
+// Regex Validation
+// ============================================================================
+
+#[cfg(feature = "regex")]
+#[derive(EnvConfig)]
+struct RegexConfig {
+    #[env(var = "EDGE_RE_VERSION", validate_regex = r"^v\d+$")]
+    version: String,
+
+    #[env(var = "EDGE_RE_TOKEN", validate_regex = "^tok_", secret)]
+    token: String,
+
+    #[env(var = "EDGE_RE_REGION", optional, validate_regex = "^[a-z]{2}-[a-z]+$")]
+    region: Option<String>,
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "regex")]
+fn test_validate_regex_accepts_matching_values() {
+    with_env(
+        &[("EDGE_RE_VERSION", "v12"), ("EDGE_RE_TOKEN", "tok_abc")],
+        || {
+            let config = RegexConfig::from_env().expect("values match their patterns");
+            assert_eq!(config.version, "v12");
+            assert_eq!(config.token, "tok_abc");
+            assert!(config.region.is_none());
+        },
+    );
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "regex")]
+fn test_validate_regex_rejects_with_pattern() {
+    with_env(
+        &[
+            ("EDGE_RE_VERSION", "12"),
+            ("EDGE_RE_TOKEN", "hunter2"),
+            ("EDGE_RE_REGION", "EU"),
+        ],
+        || {
+            let err = RegexConfig::from_env().unwrap_err();
+            let procenv::Error::Multiple { errors } = &err else {
+                panic!("expected Multiple, got {err:?}");
+            };
+            let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            assert_eq!(
+                messages,
+                [
+                    r#"EDGE_RE_VERSION has value "12", which does not match /^v\d+$/"#,
+                    "EDGE_RE_TOKEN has value <redacted>, which does not match /^tok_/",
+                    r#"EDGE_RE_REGION has value "EU", which does not match /^[a-z]{2}-[a-z]+$/"#,
+                ]
+            );
+            assert!(!format!("{err:?}").contains("hunter2"));
+        },
+    );
+}
+
+#[cfg(feature = "regex")]
+#[derive(EnvConfig)]
+struct BrokenRegexConfig {
+    #[env(var = "EDGE_RE_BROKEN", validate_regex = "(unclosed")]
+    value: String,
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "regex")]
+fn test_validate_regex_reports_invalid_pattern() {
+    with_env(&[("EDGE_RE_BROKEN", "anything")], || {
+        let err = BrokenRegexConfig::from_env().unwrap_err();
+        let procenv::Error::InvalidRegex { var, pattern, .. } = &err else {
+            panic!("expected InvalidRegex, got {err:?}");
+        };
+        assert_eq!(var, "EDGE_RE_BROKEN");
+        assert_eq!(pattern, "(unclosed");
+    });
+}
+
+#[cfg(all(feature = "regex", feature = "secrecy"))]
+#[derive(EnvConfig)]
+struct SecretRegexConfig {
+    #[env(var = "EDGE_RE_SECRET", validate_regex = "^sk_[0-9a-f]{8}$")]
+    key: procenv::SecretString,
+}
+
+#[test]
+#[serial]
+#[cfg(all(feature = "regex", feature = "secrecy"))]
+fn test_validate_regex_on_secret_string() {
+    with_env(&[("EDGE_RE_SECRET", "sk_0123abcd")], || {
+        assert!(SecretRegexConfig::from_env().is_ok());
+    });
+
+    with_env(&[("EDGE_RE_SECRET", "hunter2")], || {
+        let err = SecretRegexConfig::from_env().unwrap_err();
+        assert!(matches!(err, procenv::Error::RegexMismatch { .. }));
+        assert!(!format!("{err:?}").contains("hunter2"));
+    });
+}
+
+// ============================================================================
+// Partial Redaction
+// ============================================================================
+
+#[cfg(feature = "regex")]
+#[derive(EnvConfig)]
+struct RedactPatternConfig {
+    #[env(var = "EDGE_REDACT_DSN", redact_pattern = "://[^:/]+:([^@]+)@")]
+    dsn: String,
+
+    #[env(var = "EDGE_REDACT_PORT", default = "5432")]
+    port: u16,
+
+    #[env(var = "EDGE_REDACT_MIRROR", optional, redact_pattern = "token=([^&]+)")]
+    mirror: Option<String>,
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "regex")]
+fn test_redact_pattern_masks_debug() {
+    with_env(
+        &[
+            ("EDGE_REDACT_DSN", "postgres://app:hunter2@db:5432/main"),
+            ("EDGE_REDACT_MIRROR", "https://mirror/?token=abc123&v=2"),
+        ],
+        || {
+            let config = RedactPatternConfig::from_env().expect("values load");
+            assert_eq!(config.dsn, "postgres://app:hunter2@db:5432/main");
+
+            let debug = format!("{config:?}");
+            assert!(debug.contains(r#"dsn: "postgres://app:<redacted>@db:5432/main""#));
+            assert!(debug.contains(r#"mirror: Some("https://mirror/?token=<redacted>&v=2")"#));
+            assert!(!debug.contains("hunter2"));
+            assert!(!debug.contains("abc123"));
+        },
+    );
+}
+
+#[cfg(feature = "regex")]
+#[derive(EnvConfig)]
+struct RedactedCheckConfig {
+    #[env(
+        var = "EDGE_REDACT_URL",
+        redact_pattern = "://[^:/]+:([^@]+)@",
+        validate_regex = "^postgres://"
+    )]
+    url: String,
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "regex")]
+fn test_redact_pattern_masks_errors() {
+    with_env(&[("EDGE_REDACT_URL", "mysql://app:s3cret@db/main")], || {
+        let err = RedactedCheckConfig::from_env().unwrap_err();
+        let procenv::Error::RegexMismatch { value, .. } = &err else {
+            panic!("expected RegexMismatch, got {err:?}");
+        };
+        assert_eq!(value.as_str(), Some("mysql://app:<redacted>@db/main"));
+        assert!(!format!("{err:?}").contains("s3cret"));
+    });
+}
+
+// ============================================================================
+// Example Values
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[allow(dead_code)]
+struct ExampleValuesConfig {
+    #[env(var = "EDGE_EX_URL", example = "postgres://localhost/db")]
+    url: String,
+
+    #[env(var = "EDGE_EX_PORT", default = "8080", example = "9000")]
+    port: u16,
+
+    #[env(var = "EDGE_EX_KEY", secret, example = "sk_live_123")]
+    key: String,
+
+    #[env(var = "EDGE_EX_REGION", optional, example = "eu-west")]
+    region: Option<String>,
+}
+
+#[test]
+fn test_example_values_in_env_example() {
+    let example = ExampleValuesConfig::env_example();
+
+    assert!(
+        example.contains("\nEDGE_EX_URL=postgres://localhost/db\n"),
+        "{example}"
+    );
+    assert!(example.contains("EDGE_EX_REGION=eu-west"), "{example}");
+
+    // The default wins over the example, as that is what loads
+    assert!(example.contains("# EDGE_EX_PORT=8080"), "{example}");
+    assert!(!example.contains("9000"), "{example}");
+
+    // Secret examples are never written out
+    assert!(!example.contains("sk_live_123"), "{example}");
+    assert!(
+        example.contains("# EDGE_EX_KEY=<secret>\nEDGE_EX_KEY=\n"),
+        "{example}"
+    );
+}
+
+#[test]
+#[serial]
+fn test_example_value_is_not_loaded() {
+    unsafe {
+        std::env::remove_var("EDGE_EX_URL");
+        std::env::remove_var("EDGE_EX_KEY");
+    }
+
+    assert!(ExampleValuesConfig::from_env().is_err());
+}
+
+// ============================================================================
+// Presence Booleans
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct PresenceBoolConfig {
+    #[env(var = "EDGE_PRESENCE_DEBUG", presence_bool)]
+    debug: bool,
+
+    #[env(var = "EDGE_PRESENCE_COLOR", presence_bool, default = "true")]
+    color: bool,
+}
+
+#[test]
+#[serial]
+fn test_presence_bool_any_value_is_true() {
+    for value in ["", "0", "false", "yes"] {
+        with_env(&[("EDGE_PRESENCE_DEBUG", value)], || {
+            let config = PresenceBoolConfig::from_env().expect("should load");
+            assert!(config.debug, "value {value:?} should count as set");
+        });
+    }
+}
+
+#[test]
+#[serial]
+fn test_presence_bool_unset_uses_default() {
+    cleanup_vars(&["EDGE_PRESENCE_DEBUG", "EDGE_PRESENCE_COLOR"]);
+
+    let config = PresenceBoolConfig::from_env().expect("should load");
+    assert!(!config.debug);
+    assert!(config.color);
+}
+
+#[test]
+#[serial]
+fn test_presence_bool_apply_env() {
+    cleanup_vars(&["EDGE_PRESENCE_COLOR"]);
+
+    let mut config = PresenceBoolConfig {
+        debug: false,
+        color: false,
+    };
+
+    with_env(&[("EDGE_PRESENCE_DEBUG", "off")], || {
+        config.apply_env().expect("should apply");
+    });
+
+    assert!(config.debug);
+    assert!(!config.color);
+}
+
+// ============================================================================
+// Numeric Booleans
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct NumericBoolConfig {
+    #[env(var = "EDGE_NUMBOOL_DEBUG", coerce_bool_numeric)]
+    debug: bool,
+
+    #[env(var = "EDGE_NUMBOOL_COLOR", coerce_bool_numeric, optional)]
+    color: Option<bool>,
+}
+
+#[test]
+#[serial]
+fn test_coerce_bool_numeric_accepts_digits_and_words() {
+    for (value, expected) in [("1", true), ("0", false), ("TRUE", true), ("false", false)] {
+        with_env(
+            &[("EDGE_NUMBOOL_DEBUG", value), ("EDGE_NUMBOOL_COLOR", value)],
+            || {
+                let config = NumericBoolConfig::from_env().expect("should load");
+                assert_eq!(config.debug, expected, "value {value:?}");
+                assert_eq!(config.color, Some(expected), "value {value:?}");
+            },
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn test_coerce_bool_numeric_rejects_other_values() {
+    cleanup_vars(&["EDGE_NUMBOOL_COLOR"]);
+
+    with_env(&[("EDGE_NUMBOOL_DEBUG", "2")], || {
+        let err = NumericBoolConfig::from_env().unwrap_err();
+        assert!(
+            err.to_string().contains("EDGE_NUMBOOL_DEBUG"),
+            "unexpected error: {err}"
+        );
+    });
+}
+
+// ============================================================================
+// Trimming Raw Values
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct TrimmedConfig {
+    #[env(var = "EDGE_TRIM_PORT", trim_matches = "\"'")]
+    port: u16,
+
+    #[env(var = "EDGE_TRIM_NAME", default = "app", trim, trim_matches = "\"")]
+    name: String,
+
+    #[env(var = "EDGE_TRIM_TOKEN", optional, trim)]
+    token: Option<String>,
+}
+
+#[test]
+#[serial]
+fn test_trim_matches_strips_quotes() {
+    with_env(
+        &[
+            ("EDGE_TRIM_PORT", "\"'8080'\""),
+            ("EDGE_TRIM_NAME", "  \"my app\"  "),
+            ("EDGE_TRIM_TOKEN", "\tabc\n"),
+        ],
+        || {
+            let config = TrimmedConfig::from_env().expect("should load trimmed values");
+            assert_eq!(config.port, 8080);
+            assert_eq!(config.name, "my app");
+            assert_eq!(config.token.as_deref(), Some("abc"));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_trim_matches_keeps_unbalanced_quotes() {
+    cleanup_vars(&["EDGE_TRIM_NAME", "EDGE_TRIM_TOKEN"]);
+
+    with_env(&[("EDGE_TRIM_PORT", "8080\"")], || {
+        let err = TrimmedConfig::from_env().expect_err("unbalanced quote should not be stripped");
+        assert!(format!("{err}").contains("EDGE_TRIM_PORT"));
+    });
+}
+
+#[test]
+#[serial]
+fn test_trim_does_not_apply_to_defaults() {
+    cleanup_vars(&["EDGE_TRIM_NAME", "EDGE_TRIM_TOKEN"]);
+
+    with_env(&[("EDGE_TRIM_PORT", "1")], || {
+        let config = TrimmedConfig::from_env().expect("should load");
+        assert_eq!(config.name, "app");
+        assert!(config.token.is_none());
+    });
+}
+
+// ============================================================================
+// Constant-Time Verification
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct VerifyConfig {
+    #[env(var = "EDGE_VERIFY_ADMIN", secret, sensitive_eq)]
+    admin_token: String,
+
+    #[env(var = "EDGE_VERIFY_HOOK", optional, sensitive_eq)]
+    webhook_secret: Option<Box<str>>,
+}
+
+#[test]
+#[serial]
+fn test_sensitive_eq_verifies_tokens() {
+    cleanup_vars(&["EDGE_VERIFY_HOOK"]);
+
+    with_env(&[("EDGE_VERIFY_ADMIN", "s3cret-token")], || {
+        let config = VerifyConfig::from_env().expect("should load");
+
+        assert!(config.verify_admin_token("s3cret-token"));
+        assert!(!config.verify_admin_token("s3cret-tokeN"));
+        assert!(!config.verify_admin_token("s3cret"));
+        assert!(!config.verify_admin_token(""));
+
+        // An unset optional secret matches nothing, not even ""
+        assert!(!config.verify_webhook_secret(""));
+    });
+
+    with_env(
+        &[("EDGE_VERIFY_ADMIN", "a"), ("EDGE_VERIFY_HOOK", "whsec_1")],
+        || {
+            let config = VerifyConfig::from_env().expect("should load");
+            assert!(config.verify_webhook_secret("whsec_1"));
+            assert!(!config.verify_webhook_secret("whsec_2"));
+        },
+    );
+}
+
+#[cfg(feature = "secrecy")]
+#[derive(EnvConfig)]
+struct SecretVerifyConfig {
+    #[env(var = "EDGE_VERIFY_SECRET", sensitive_eq)]
+    token: procenv::SecretString,
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "secrecy")]
+fn test_sensitive_eq_on_secret_string() {
+    with_env(&[("EDGE_VERIFY_SECRET", "hunter2")], || {
+        let config = SecretVerifyConfig::from_env().expect("should load");
+        assert!(config.verify_token("hunter2"));
+        assert!(!config.verify_token("hunter3"));
+    });
+}
+
+// ============================================================================
+// Secret Fingerprints
+// ============================================================================
+
+#[cfg(feature = "fingerprint")]
+#[derive(EnvConfig)]
+struct FingerprintConfig {
+    #[env(var = "EDGE_FP_KEY", secret, log_fingerprint)]
+    api_key: String,
+
+    #[env(var = "EDGE_FP_OLD_KEY", secret, optional, log_fingerprint)]
+    previous_key: Option<String>,
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "fingerprint")]
+fn test_log_fingerprint_hashes_secret() {
+    cleanup_vars(&["EDGE_FP_OLD_KEY"]);
+
+    with_env(&[("EDGE_FP_KEY", "hunter2")], || {
+        let config = FingerprintConfig::from_env().expect("should load");
+
+        // printf %s hunter2 | sha256sum | cut -c1-12
+        assert_eq!(config.api_key_fingerprint(), "f52fbd32b2b3");
+        assert_eq!(config.previous_key_fingerprint(), None);
+    });
+
+    with_env(
+        &[("EDGE_FP_KEY", "hunter2"), ("EDGE_FP_OLD_KEY", "hunter3")],
+        || {
+            let config = FingerprintConfig::from_env().expect("should load");
+            let previous = config.previous_key_fingerprint().expect("key is set");
+
+            assert_eq!(previous.len(), 12);
+            assert_ne!(previous, config.api_key_fingerprint());
+        },
+    );
+}
+
+#[cfg(all(feature = "fingerprint", feature = "secrecy"))]
+#[derive(EnvConfig)]
+struct SecretFingerprintConfig {
+    #[env(var = "EDGE_FP_SECRET", log_fingerprint)]
+    token: procenv::SecretString,
+}
+
+#[test]
+#[serial]
+#[cfg(all(feature = "fingerprint", feature = "secrecy"))]
+fn test_log_fingerprint_on_secret_string() {
+    with_env(&[("EDGE_FP_SECRET", "hunter2")], || {
+        let config = SecretFingerprintConfig::from_env().expect("should load");
+        assert_eq!(config.token_fingerprint(), "f52fbd32b2b3");
+    });
+}
+
+// ============================================================================
+// Post-Parse Hooks
+// ============================================================================
+
+fn normalize_url(url: String) -> Result<String, String> {
+    if !url.contains("://") {
+        return Err(format!("`{url}` has no scheme"));
+    }
+    Ok(url.trim_end_matches('/').to_ascii_lowercase())
+}
+
+fn clamp_workers(workers: u16) -> Result<u16, std::convert::Infallible> {
+    Ok(workers.clamp(1, 64))
+}
+
+#[derive(EnvConfig)]
+struct AfterParseConfig {
+    #[env(var = "EDGE_AFTER_URL", after_parse = "normalize_url")]
+    base_url: String,
+
+    #[env(
+        var = "EDGE_AFTER_WORKERS",
+        default = "0",
+        after_parse = "clamp_workers"
+    )]
+    workers: u16,
+
+    #[env(var = "EDGE_AFTER_MIRROR", optional, after_parse = "normalize_url")]
+    mirror: Option<String>,
+}
+
+#[test]
+#[serial]
+fn test_after_parse_transforms_values() {
+    cleanup_vars(&["EDGE_AFTER_WORKERS", "EDGE_AFTER_MIRROR"]);
+
+    with_env(&[("EDGE_AFTER_URL", "HTTPS://Example.COM/")], || {
+        let config = AfterParseConfig::from_env().expect("should load");
+        assert_eq!(config.base_url, "https://example.com");
+
+        // Defaults go through the hook too
+        assert_eq!(config.workers, 1);
+        assert_eq!(config.mirror, None);
+    });
+
+    with_env(
+        &[
+            ("EDGE_AFTER_URL", "https://a.test"),
+            ("EDGE_AFTER_WORKERS", "500"),
+            ("EDGE_AFTER_MIRROR", "HTTP://Mirror.test/"),
+        ],
+        || {
+            let config = AfterParseConfig::from_env().expect("should load");
+            assert_eq!(config.workers, 64);
+            assert_eq!(config.mirror.as_deref(), Some("http://mirror.test"));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_after_parse_error_is_reported() {
+    cleanup_vars(&["EDGE_AFTER_WORKERS"]);
+
+    with_env(
+        &[
+            ("EDGE_AFTER_URL", "example.com"),
+            ("EDGE_AFTER_MIRROR", "mirror.test"),
+        ],
+        || {
+            let err = AfterParseConfig::from_env().expect_err("hook should reject both");
+            let procenv::Error::Multiple { errors } = err else {
+                panic!("expected both hook failures, got {err}");
+            };
+            assert_eq!(errors.len(), 2);
+            assert!(matches!(
+                &errors[0],
+                procenv::Error::AfterParse { var, function, .. }
+                    if var == "EDGE_AFTER_URL" && function == "normalize_url"
+            ));
+            assert!(
+                errors[0]
+                    .to_string()
+                    .contains("`example.com` has no scheme")
+            );
+        },
+    );
+}
+
+fn sort_ports(mut ports: Vec<u16>) -> Result<Vec<u16>, std::convert::Infallible> {
+    ports.sort_unstable();
+    Ok(ports)
+}
+
+#[derive(EnvConfig)]
+struct AfterParseFormatConfig {
+    #[env(var = "PORTS", format = "json", after_parse = "sort_ports")]
+    ports: Vec<u16>,
+
+    #[env(var = "MIRROR", format = "json", after_parse = "normalize_url")]
+    mirror: String,
+}
+
+#[derive(EnvConfig)]
+struct AfterParseParentConfig {
+    #[env(flatten, prefix = "EDGE_AFTER_NESTED_")]
+    nested: AfterParseFormatConfig,
+}
+
+#[test]
+#[serial]
+fn test_after_parse_runs_on_flattened_format_fields() {
+    with_env(
+        &[
+            ("EDGE_AFTER_NESTED_PORTS", "[9000, 80, 443]"),
+            ("EDGE_AFTER_NESTED_MIRROR", "\"HTTP://Mirror.test/\""),
+        ],
+        || {
+            let config = AfterParseParentConfig::from_env().expect("should load");
+            assert_eq!(config.nested.ports, [80, 443, 9000]);
+            assert_eq!(config.nested.mirror, "http://mirror.test");
+        },
+    );
+
+    with_env(
+        &[
+            ("EDGE_AFTER_NESTED_PORTS", "[1]"),
+            ("EDGE_AFTER_NESTED_MIRROR", "\"mirror.test\""),
+        ],
+        || {
+            let err = AfterParseParentConfig::from_env().expect_err("hook should reject");
+            assert!(matches!(
+                &err,
+                procenv::Error::AfterParse { var, function, .. }
+                    if var == "EDGE_AFTER_NESTED_MIRROR" && function == "normalize_url"
+            ));
+        },
+    );
+}
+
+// ============================================================================
+// Default Expansion
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct ExpandedDefaultConfig {
+    #[env(var = "EDGE_EXPAND_DIR", default = "${EDGE_EXPAND_HOME}/data")]
+    data_dir: String,
+
+    #[env(var = "EDGE_EXPAND_PORT", default = "${EDGE_EXPAND_BASE_PORT}")]
+    port: u16,
+
+    #[env(var = "EDGE_EXPAND_PRICE", default = "$$5 per ${EDGE_EXPAND_UNIT}")]
+    price: String,
+}
+
+const EXPAND_VARS: &[&str] = &[
+    "EDGE_EXPAND_DIR",
+    "EDGE_EXPAND_HOME",
+    "EDGE_EXPAND_PORT",
+    "EDGE_EXPAND_BASE_PORT",
+    "EDGE_EXPAND_PRICE",
+    "EDGE_EXPAND_UNIT",
+];
+
+#[test]
+#[serial]
+fn test_default_expands_env_references() {
+    cleanup_vars(EXPAND_VARS);
+
+    with_env(
+        &[
+            ("EDGE_EXPAND_HOME", "/home/app"),
+            ("EDGE_EXPAND_BASE_PORT", "9000"),
+            ("EDGE_EXPAND_UNIT", "GB"),
+        ],
+        || {
+            let config = ExpandedDefaultConfig::from_env().expect("should load");
+            assert_eq!(config.data_dir, "/home/app/data");
+            assert_eq!(config.port, 9000);
+            assert_eq!(config.price, "$5 per GB");
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_default_expansion_only_when_default_used() {
+    cleanup_vars(EXPAND_VARS);
+
+    with_env(
+        &[
+            ("EDGE_EXPAND_DIR", "/srv"),
+            ("EDGE_EXPAND_PORT", "80"),
+            ("EDGE_EXPAND_PRICE", "free"),
+        ],
+        || {
+            let config = ExpandedDefaultConfig::from_env().expect("references are never read");
+            assert_eq!(config.data_dir, "/srv");
+            assert_eq!(config.port, 80);
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_default_expansion_reports_unset_reference() {
+    cleanup_vars(EXPAND_VARS);
+
+    with_env(
+        &[("EDGE_EXPAND_PORT", "80"), ("EDGE_EXPAND_PRICE", "free")],
+        || {
+            let err = ExpandedDefaultConfig::from_env().expect_err("EDGE_EXPAND_HOME is unset");
+            assert!(matches!(
+                &err,
+                procenv::Error::UndefinedDefaultVar { var, reference, .. }
+                    if var == "EDGE_EXPAND_DIR" && reference == "EDGE_EXPAND_HOME"
+            ));
+        },
+    );
+}
+
+#[derive(EnvConfig)]
+struct ExpandedFormatDefaultConfig {
+    #[env(var = "HOSTS", format = "json", default = "[\"${EDGE_EXPAND_HOST}\"]")]
+    hosts: Vec<String>,
+}
+
+#[derive(EnvConfig)]
+struct ExpandedFormatParentConfig {
+    #[env(flatten, prefix = "EDGE_EXPAND_NESTED_")]
+    nested: ExpandedFormatDefaultConfig,
+}
+
+#[test]
+#[serial]
+fn test_default_expansion_in_flattened_format_field() {
+    cleanup_vars(&["EDGE_EXPAND_NESTED_HOSTS", "EDGE_EXPAND_HOST"]);
+
+    with_env(&[("EDGE_EXPAND_HOST", "db.internal")], || {
+        let config = ExpandedFormatParentConfig::from_env().expect("should load");
+        assert_eq!(config.nested.hosts, ["db.internal"]);
+    });
+
+    let err = ExpandedFormatParentConfig::from_env().expect_err("EDGE_EXPAND_HOST is unset");
+    assert!(matches!(
+        &err,
+        procenv::Error::UndefinedDefaultVar { var, reference, .. }
+            if var == "EDGE_EXPAND_NESTED_HOSTS" && reference == "EDGE_EXPAND_HOST"
+    ));
+}
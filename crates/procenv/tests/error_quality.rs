@@ -132,6 +132,82 @@ fn test_parse_error_shows_expected_type() {
     });
 }
 
+#[derive(EnvConfig)]
+struct OverflowConfig {
+    #[env(var = "ERR_OVERFLOW_PORT", default = "8080")]
+    port: u16,
+}
+
+#[test]
+#[serial]
+fn test_parse_error_overflow_states_type_range() {
+    with_env(&[("ERR_OVERFLOW_PORT", "99999")], || {
+        let err = OverflowConfig::from_env().unwrap_err();
+
+        let Error::Parse { help, .. } = &err else {
+            panic!("expected Parse error, got {err:?}");
+        };
+        assert_eq!(help, "expected u16 (0..=65535)");
+    });
+}
+
+#[derive(EnvConfig)]
+struct NonZeroConfig {
+    #[env(var = "ERR_POOL_SIZE", default = "4")]
+    pool_size: std::num::NonZeroU16,
+
+    #[env(var = "ERR_WORKERS", default = "1")]
+    workers: std::num::NonZero<u32>,
+}
+
+#[test]
+#[serial]
+fn test_parse_error_non_zero_rejects_zero() {
+    with_env(&[("ERR_POOL_SIZE", "0"), ("ERR_WORKERS", "0")], || {
+        let err = NonZeroConfig::from_env().unwrap_err();
+
+        let Error::Multiple { errors } = &err else {
+            panic!("expected Multiple error, got {err:?}");
+        };
+        let helps: Vec<&str> = errors
+            .iter()
+            .map(|e| match e {
+                Error::Parse { help, .. } => help.as_str(),
+                other => panic!("expected Parse error, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            helps,
+            [
+                "expected a non-zero u16, got 0",
+                "expected a non-zero u32, got 0"
+            ]
+        );
+    });
+}
+
+#[derive(EnvConfig)]
+struct SocketAddrConfig {
+    #[env(var = "ERR_BIND_ADDR")]
+    bind: std::net::SocketAddr,
+}
+
+#[test]
+#[serial]
+fn test_parse_error_socket_addr_suggests_ip() {
+    with_env(&[("ERR_BIND_ADDR", "localhost:8080")], || {
+        let err = SocketAddrConfig::from_env().unwrap_err();
+
+        let Error::Parse { help, .. } = &err else {
+            panic!("expected Parse error, got {err:?}");
+        };
+        assert!(
+            help.contains("not a hostname"),
+            "help should explain SocketAddr needs an IP: {help}"
+        );
+    });
+}
+
 // ============================================================================
 // Secret Redaction in Errors
 // ============================================================================
@@ -156,7 +232,7 @@ fn test_debug_redacts_secret_value() {
             "Debug should not contain secret value: {debug}"
         );
         assert!(
-            debug.contains("***") || debug.contains("[redacted]") || debug.contains("REDACTED"),
+            debug.contains("<redacted>"),
             "Debug should show redaction marker: {debug}"
         );
     });
@@ -194,6 +270,54 @@ fn test_secret_parse_error_redacts_value() {
     });
 }
 
+// ============================================================================
+// Invalid UTF-8 Errors
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[allow(dead_code)]
+struct Utf8ErrorConfig {
+    #[env(var = "ERR_UTF8_NAME")]
+    name: String,
+
+    #[env(var = "ERR_UTF8_TOKEN", secret)]
+    token: String,
+}
+
+#[cfg(unix)]
+#[test]
+#[serial]
+fn test_invalid_utf8_error_previews_value() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    unsafe {
+        std::env::set_var("ERR_UTF8_NAME", OsStr::from_bytes(b"caf\xe9 au lait"));
+        std::env::set_var("ERR_UTF8_TOKEN", OsStr::from_bytes(b"hunter\xff2"));
+    }
+
+    let err = Utf8ErrorConfig::from_env().unwrap_err();
+    cleanup_vars(&["ERR_UTF8_NAME", "ERR_UTF8_TOKEN"]);
+
+    let Error::Multiple { errors } = err else {
+        panic!("expected both fields to fail: {err:?}");
+    };
+    assert!(
+        errors
+            .iter()
+            .all(|e| matches!(e, Error::InvalidUtf8 { .. }))
+    );
+
+    let display = errors[0].to_string();
+    assert!(display.contains("ERR_UTF8_NAME"), "{display}");
+    assert!(display.contains("caf\u{fffd} au lait"), "{display}");
+
+    // Secret values are never previewed
+    let display = errors[1].to_string();
+    assert!(display.contains("<redacted>"), "{display}");
+    assert!(!display.contains("hunter"), "{display}");
+}
+
 // ============================================================================
 // Multiple Error Formatting
 // ============================================================================
@@ -242,6 +366,95 @@ fn test_multiple_errors_is_multiple_variant() {
     }
 }
 
+#[derive(EnvConfig)]
+#[env_config(no_accumulate)]
+struct FailFastConfig {
+    #[env(var = "MULTI_A")]
+    a: String,
+
+    #[env(var = "MULTI_B")]
+    b: String,
+
+    #[env(var = "MULTI_C")]
+    c: u32,
+}
+
+#[test]
+#[serial]
+fn test_no_accumulate_returns_first_error() {
+    cleanup_vars(&["MULTI_A", "MULTI_B", "MULTI_C"]);
+
+    with_env(&[("MULTI_A", "a"), ("MULTI_C", "not-a-number")], || {
+        match FailFastConfig::from_env().unwrap_err() {
+            Error::Missing { var, .. } => assert_eq!(var, "MULTI_B"),
+            other => panic!("Expected Missing error, got: {other:?}"),
+        }
+
+        // The sources path still reports everything
+        match FailFastConfig::from_env_with_sources().unwrap_err() {
+            Error::Multiple { errors } => assert_eq!(errors.len(), 2),
+            other => panic!("Expected Multiple error, got: {other:?}"),
+        }
+    });
+
+    with_env(
+        &[("MULTI_A", "a"), ("MULTI_B", "b"), ("MULTI_C", "7")],
+        || {
+            let config = FailFastConfig::from_env().unwrap();
+            assert_eq!(
+                (config.a.as_str(), config.b.as_str(), config.c),
+                ("a", "b", 7)
+            );
+        },
+    );
+}
+
+#[derive(EnvConfig)]
+#[env_config(dotenv_required = "tests/fixtures/does-not-exist.env")]
+#[allow(dead_code)]
+struct MissingDotenvConfig {
+    #[env(var = "DOTENV_REQ_NAME", optional)]
+    name: Option<String>,
+}
+
+#[derive(EnvConfig)]
+#[env_config(dotenv_required = "tests/fixtures/required.env")]
+struct RequiredDotenvConfig {
+    #[env(var = "DOTENV_REQ_NAME")]
+    name: String,
+}
+
+#[test]
+#[serial]
+fn test_dotenv_required_missing_file() {
+    match MissingDotenvConfig::from_env().unwrap_err() {
+        Error::DotenvMissing { path, .. } => {
+            assert_eq!(path, "tests/fixtures/does-not-exist.env")
+        }
+        other => panic!("Expected DotenvMissing error, got: {other:?}"),
+    }
+
+    assert!(matches!(
+        MissingDotenvConfig::from_env_with_sources().unwrap_err(),
+        Error::DotenvMissing { .. }
+    ));
+}
+
+#[test]
+#[serial]
+fn test_dotenv_required_present_file_loads() {
+    cleanup_vars(&["DOTENV_REQ_NAME"]);
+
+    let (config, sources) = RequiredDotenvConfig::from_env_with_sources().unwrap();
+    assert_eq!(config.name, "from-file");
+    assert_eq!(
+        sources.get("name").unwrap().source,
+        procenv::Source::DotenvFile(Some("tests/fixtures/required.env".into()))
+    );
+
+    cleanup_vars(&["DOTENV_REQ_NAME"]);
+}
+
 // ============================================================================
 // Error Type Variants
 // ============================================================================
@@ -288,6 +501,86 @@ fn test_error_is_multiple_variant() {
     );
 }
 
+// ============================================================================
+// Errors Across Structs
+// ============================================================================
+
+#[derive(EnvConfig)]
+struct AcrossAppConfig {
+    #[env(var = "ACROSS_APP_HOST")]
+    host: String,
+
+    #[env(var = "ACROSS_APP_PORT")]
+    port: u16,
+}
+
+#[derive(EnvConfig)]
+struct AcrossDbConfig {
+    #[env(var = "ACROSS_DB_URL")]
+    url: String,
+}
+
+#[test]
+#[serial]
+fn test_load_all_reports_every_struct() {
+    cleanup_vars(&["ACROSS_APP_HOST", "ACROSS_APP_PORT", "ACROSS_DB_URL"]);
+
+    let err = procenv::load_all!(AcrossAppConfig, AcrossDbConfig).unwrap_err();
+
+    let Error::Multiple { errors } = &err else {
+        panic!("Expected Multiple error, got: {err:?}");
+    };
+    let names: Vec<_> = errors
+        .iter()
+        .map(|e| match e {
+            Error::Struct { name, errors } => (name.as_str(), errors.len()),
+            other => panic!("Expected Struct error, got: {other:?}"),
+        })
+        .collect();
+    assert_eq!(names, [("AcrossAppConfig", 2), ("AcrossDbConfig", 1)]);
+    assert_eq!(
+        err.missing_vars(),
+        ["ACROSS_APP_HOST", "ACROSS_APP_PORT", "ACROSS_DB_URL"]
+    );
+}
+
+#[test]
+#[serial]
+fn test_load_all_returns_loaded_structs() {
+    with_env(
+        &[
+            ("ACROSS_APP_HOST", "localhost"),
+            ("ACROSS_APP_PORT", "8080"),
+            ("ACROSS_DB_URL", "postgres://db"),
+        ],
+        || {
+            let (app, db) = procenv::load_all!(AcrossAppConfig, AcrossDbConfig).unwrap();
+            assert_eq!((app.host.as_str(), app.port), ("localhost", 8080));
+            assert_eq!(db.url, "postgres://db");
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_load_all_keeps_struct_context_in_diagnostic() {
+    cleanup_vars(&["ACROSS_APP_HOST", "ACROSS_APP_PORT"]);
+
+    with_env(&[("ACROSS_DB_URL", "postgres://db")], || {
+        let err = procenv::load_all!(AcrossAppConfig, AcrossDbConfig).unwrap_err();
+
+        assert_eq!(
+            err.code().map(|c| c.to_string()).as_deref(),
+            Some("procenv::struct_errors")
+        );
+        assert_eq!(
+            err.to_string(),
+            "AcrossAppConfig: 2 configuration error(s) occurred"
+        );
+        assert_eq!(err.related().map_or(0, Iterator::count), 2);
+    });
+}
+
 // ============================================================================
 // miette Diagnostic Codes
 // ============================================================================
@@ -342,6 +635,73 @@ fn test_missing_error_has_help() {
     );
 }
 
+// ============================================================================
+// Type Labels
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[allow(dead_code)]
+struct TypeLabelConfig {
+    #[env(var = "ERR_LABEL_PORT", type_label = "port number")]
+    port: u16,
+
+    #[env(var = "ERR_LABEL_ADDR", default = "127.0.0.1:80")]
+    addr: std::net::SocketAddr,
+
+    #[env(var = "ERR_LABEL_WORKERS", optional, type_label = "worker count")]
+    workers: Option<std::num::NonZeroU32>,
+
+    #[env(var = "ERR_LABEL_HOST", optional)]
+    host: Option<std::net::IpAddr>,
+}
+
+fn expected_types(err: Error) -> Vec<String> {
+    let errors = match err {
+        Error::Multiple { errors } => errors,
+        other => vec![other],
+    };
+
+    errors
+        .into_iter()
+        .map(|e| match e {
+            Error::Parse { expected_type, .. } => expected_type,
+            other => panic!("expected Parse, got {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+#[serial]
+fn test_type_label_names_expected_type() {
+    with_env(
+        &[("ERR_LABEL_PORT", "http"), ("ERR_LABEL_WORKERS", "many")],
+        || {
+            let err = TypeLabelConfig::from_env().unwrap_err();
+            assert!(format!("{err:?}").contains("port number"), "{err:?}");
+            assert_eq!(expected_types(err), ["port number", "worker count"]);
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_std_paths_are_shortened() {
+    with_env(
+        &[
+            ("ERR_LABEL_PORT", "80"),
+            ("ERR_LABEL_ADDR", "nope"),
+            ("ERR_LABEL_HOST", "nope"),
+        ],
+        || {
+            let err = TypeLabelConfig::from_env().unwrap_err();
+            let display = format!("{err}");
+
+            assert!(!display.contains("std::"), "{display}");
+            assert_eq!(expected_types(err), ["SocketAddr", "IpAddr"]);
+        },
+    );
+}
+
 // ============================================================================
 // Error Display Stability
 // ============================================================================
@@ -364,3 +724,41 @@ fn test_error_display_is_stable() {
         "Error display should be stable across invocations"
     );
 }
+
+// ============================================================================
+// Formatted Errors
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[env_config(error_format = "json")]
+struct JsonErrorConfig {
+    #[env(var = "ERR_FMT_PORT")]
+    port: u16,
+}
+
+#[derive(EnvConfig)]
+struct PrettyErrorConfig {
+    #[env(var = "ERR_FMT_PORT")]
+    port: u16,
+}
+
+#[test]
+#[serial]
+fn test_from_env_formatted_uses_error_format() {
+    cleanup_vars(&["ERR_FMT_PORT"]);
+
+    let json = JsonErrorConfig::from_env_formatted().err().unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).expect("should be valid JSON");
+    assert_eq!(value["code"], "procenv::missing_var");
+    assert!(value["message"].as_str().unwrap().contains("ERR_FMT_PORT"));
+
+    // Without `error_format`, the miette report is used
+    let pretty = PrettyErrorConfig::from_env_formatted().err().unwrap();
+    assert!(pretty.contains("procenv::missing_var"), "{pretty}");
+    assert!(serde_json::from_str::<serde_json::Value>(&pretty).is_err());
+
+    let config = with_env(&[("ERR_FMT_PORT", "8080")], || {
+        JsonErrorConfig::from_env_formatted().expect("should load")
+    });
+    assert_eq!(config.port, 8080);
+}
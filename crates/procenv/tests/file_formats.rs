@@ -453,6 +453,79 @@ fn test_source_attribution_from_file() {
     cleanup_file("source_attr.toml");
 }
 
+#[test]
+fn test_source_attribution_nested_defaults() {
+    cleanup_env(&[
+        "SRCN_ENV",
+        "SRCN_NAME",
+        "SRCN_DB_HOST",
+        "SRCN_DB_PORT",
+        "SRCN_DB_POOL",
+    ]);
+    cleanup_file("source_nested.toml");
+
+    write_file("source_nested.toml", "[db]\nhost = \"db.internal\"\n");
+
+    #[derive(EnvConfig, Deserialize)]
+    struct NestedDb {
+        #[env(var = "HOST")]
+        host: String,
+
+        #[env(var = "PORT", default = "5432")]
+        port: u16,
+
+        #[env(var = "POOL", default = "4")]
+        #[profile(prod = "32")]
+        pool: u32,
+
+        #[env(var = "TIMEOUT", optional)]
+        timeout: Option<u32>,
+    }
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "SRCN_",
+        profile_env = "SRCN_ENV",
+        file_optional = "/tmp/procenv_fmt_tests/source_nested.toml"
+    )]
+    struct NestedSourceConfig {
+        #[env(var = "NAME", default = "svc")]
+        name: String,
+
+        #[env(flatten, prefix = "DB_")]
+        db: NestedDb,
+    }
+
+    let source_of = |sources: &procenv::ConfigSources, key: &str| {
+        sources.get(key).map(|v| v.source.clone()).unwrap()
+    };
+
+    let (config, sources) =
+        NestedSourceConfig::from_config_with_sources().expect("should load with sources");
+    assert_eq!(config.db.port, 5432);
+    assert_eq!(config.db.pool, 4);
+    assert!(matches!(
+        source_of(&sources, "db.host"),
+        procenv::Source::ConfigFile(_)
+    ));
+    assert_eq!(source_of(&sources, "db.port"), procenv::Source::Default);
+    assert_eq!(source_of(&sources, "db.pool"), procenv::Source::Default);
+    assert_eq!(source_of(&sources, "db.timeout"), procenv::Source::NotSet);
+    assert_eq!(source_of(&sources, "name"), procenv::Source::Default);
+
+    let (config, sources) = with_env(&[("SRCN_ENV", "prod")], || {
+        NestedSourceConfig::from_config_with_sources().expect("should load prod profile")
+    });
+    assert_eq!(config.db.pool, 32);
+    assert_eq!(
+        source_of(&sources, "db.pool"),
+        procenv::Source::Profile("prod".to_string())
+    );
+    assert_eq!(source_of(&sources, "db.port"), procenv::Source::Default);
+
+    cleanup_file("source_nested.toml");
+}
+
 // ============================================================================
 // Unicode in File Content
 // ============================================================================
@@ -481,6 +554,61 @@ fn test_unicode_in_toml() {
     cleanup_file("unicode_test.toml");
 }
 
+// ============================================================================
+// BOM and CRLF Handling
+// ============================================================================
+
+#[derive(Deserialize)]
+struct BomConfig {
+    port: u16,
+    name: String,
+}
+
+fn load_bom_file(name: &str, content: &str) -> BomConfig {
+    write_file(name, content);
+
+    let path = format!("{BASE_DIR}/{name}");
+    let result = procenv::ConfigBuilder::new()
+        .file(&path)
+        .build::<BomConfig>();
+    cleanup_file(name);
+
+    result.unwrap_or_else(|e| panic!("{name} should load: {e:?}"))
+}
+
+#[test]
+fn test_bom_and_crlf_in_toml() {
+    let config = load_bom_file("bom.toml", "\u{FEFF}port = 8080\r\nname = \"win\"\r\n");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.name, "win");
+}
+
+#[test]
+fn test_bom_and_crlf_in_json() {
+    let config = load_bom_file(
+        "bom.json",
+        "\u{FEFF}{\r\n  \"port\": 8080,\r\n  \"name\": \"win\"\r\n}\r\n",
+    );
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.name, "win");
+}
+
+#[test]
+fn test_bom_and_crlf_in_yaml() {
+    let config = load_bom_file("bom.yaml", "\u{FEFF}port: 8080\r\nname: win\r\n");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.name, "win");
+}
+
+#[test]
+fn test_crlf_multiline_string_normalized() {
+    let config = load_bom_file(
+        "crlf_multiline.toml",
+        "port = 8080\r\nname = \"\"\"\r\nline one\r\nline two\"\"\"\r\n",
+    );
+    assert_eq!(config.name, "line one\nline two");
+}
+
 // ============================================================================
 // Empty File Handling
 // ============================================================================
@@ -583,3 +711,1201 @@ fn test_numeric_values_in_json() {
 
     cleanup_file("numbers_test.json");
 }
+
+// ============================================================================
+// Environment Separator Tests
+// ============================================================================
+
+#[test]
+fn test_double_underscore_env_separator() {
+    use procenv::file::ConfigBuilder;
+
+    #[derive(Deserialize)]
+    struct Db {
+        max_connections: u32,
+        database_url: String,
+    }
+
+    #[derive(Deserialize)]
+    struct SepConfig {
+        db: Db,
+    }
+
+    let config: SepConfig = with_env(
+        &[
+            ("SEPAPP__DB__MAX_CONNECTIONS", "25"),
+            ("SEPAPP__DB__DATABASE_URL", "postgres://localhost"),
+        ],
+        || {
+            ConfigBuilder::new()
+                .env_prefix("SEPAPP")
+                .env_separator("__")
+                .build()
+                .expect("should build from double-underscore env vars")
+        },
+    );
+
+    assert_eq!(config.db.max_connections, 25);
+    assert_eq!(config.db.database_url, "postgres://localhost");
+}
+
+#[test]
+fn test_builder_env_filter_excludes_namespace() {
+    use procenv::ConfigBuilder;
+
+    #[derive(Deserialize)]
+    struct FilterConfig {
+        port: u16,
+        secret_token: Option<String>,
+    }
+
+    let config: FilterConfig = with_env(
+        &[("FLT_PORT", "9000"), ("FLT_SECRET_TOKEN", "from-env")],
+        || {
+            ConfigBuilder::new()
+                .env_prefix("FLT_")
+                .env_separator("__")
+                .env_filter(|key| !key.starts_with("SECRET_"))
+                .build()
+                .expect("filtered env should build")
+        },
+    );
+
+    assert_eq!(config.port, 9000);
+    assert_eq!(config.secret_token, None);
+}
+
+#[test]
+fn test_env_overrides_array_of_tables_element() {
+    use procenv::ConfigBuilder;
+
+    #[derive(Deserialize)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Deserialize)]
+    struct ServersConfig {
+        servers: Vec<Server>,
+    }
+
+    write_file(
+        "array_of_tables.toml",
+        "[[servers]]\nhost = \"a\"\nport = 1\n\n[[servers]]\nhost = \"b\"\nport = 2\n",
+    );
+    let path = format!("{BASE_DIR}/array_of_tables.toml");
+
+    let config: ServersConfig = with_env(
+        &[
+            ("AOT_SERVERS_1_HOST", "b.override"),
+            ("AOT_SERVERS_2_HOST", "c"),
+            ("AOT_SERVERS_2_PORT", "3"),
+        ],
+        || {
+            ConfigBuilder::new()
+                .file(&path)
+                .env_prefix("AOT_")
+                .build()
+                .expect("indexed env vars should merge into array elements")
+        },
+    );
+    cleanup_file("array_of_tables.toml");
+
+    let servers: Vec<(&str, u16)> = config
+        .servers
+        .iter()
+        .map(|s| (s.host.as_str(), s.port))
+        .collect();
+    assert_eq!(servers, [("a", 1), ("b.override", 2), ("c", 3)]);
+}
+
+// ============================================================================
+// Embedded Config Tests
+// ============================================================================
+
+#[test]
+fn test_embedded_config_is_lowest_file_layer() {
+    cleanup_env(&["EMB_NAME", "EMB_PORT", "EMB_DEBUG"]);
+    cleanup_file("embedded_override.toml");
+
+    write_file("embedded_override.toml", "port = 7100\n");
+
+    #[derive(EnvConfig)]
+    #[env_config(
+        prefix = "EMB_",
+        embedded = "tests/fixtures/embedded_defaults.toml",
+        file_optional = "/tmp/procenv_fmt_tests/embedded_override.toml"
+    )]
+    struct EmbeddedConfig {
+        #[env(var = "NAME", default = "default-app")]
+        name: String,
+
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+
+        #[env(var = "DEBUG", default = "false")]
+        debug: bool,
+    }
+
+    let config = with_env(&[("EMB_DEBUG", "false")], || {
+        EmbeddedConfig::from_config().expect("should load embedded config")
+    });
+
+    // Embedded overrides macro defaults
+    assert_eq!(config.name, "embedded-app");
+    // Runtime file overrides embedded
+    assert_eq!(config.port, 7100);
+    // Env overrides everything
+    assert!(!config.debug);
+
+    cleanup_file("embedded_override.toml");
+}
+
+#[test]
+fn test_builder_embedded_json() {
+    use procenv::{ConfigBuilder, FileFormat};
+
+    #[derive(Deserialize)]
+    struct Embedded {
+        port: u16,
+        host: String,
+    }
+
+    let config: Embedded = ConfigBuilder::new()
+        .defaults_value(serde_json::json!({"port": 1, "host": "default"}))
+        .embedded(r#"{"port": 9000}"#, FileFormat::Json)
+        .build()
+        .expect("should build from embedded JSON");
+
+    assert_eq!(config.port, 9000);
+    assert_eq!(config.host, "default");
+}
+
+#[test]
+fn test_builder_merge_value_layers() {
+    use procenv::ConfigBuilder;
+    use std::path::PathBuf;
+
+    cleanup_env(&["MVB_NAME"]);
+    cleanup_file("merge_value.json");
+
+    write_file(
+        "merge_value.json",
+        r#"{"name": "file", "pool": {"size": 4, "idle": 1}, "tier": "file"}"#,
+    );
+
+    let (value, origins) = with_env(&[("MVB_NAME", "env")], || {
+        ConfigBuilder::new()
+            .env_prefix("MVB_")
+            .merge_value(serde_json::json!({"name": "high", "pool": {"size": 32}}))
+            .merge_value_low(serde_json::json!({"tier": "low", "region": "eu"}))
+            .file("/tmp/procenv_fmt_tests/merge_value.json")
+            .into_value()
+            .expect("should merge")
+    });
+
+    // Env > merge_value > files > merge_value_low, merged deeply
+    assert_eq!(value["name"], "env");
+    assert_eq!(value["pool"]["size"], 32);
+    assert_eq!(value["pool"]["idle"], 1);
+    assert_eq!(value["tier"], "file");
+    assert_eq!(value["region"], "eu");
+
+    assert_eq!(
+        origins.get_file_source("pool.size"),
+        Some(PathBuf::from("<merge_value>"))
+    );
+    assert_eq!(
+        origins.get_file_source("region"),
+        Some(PathBuf::from("<merge_value_low>"))
+    );
+
+    cleanup_file("merge_value.json");
+}
+
+#[test]
+fn test_builder_secret_type_mismatch_is_redacted() {
+    use procenv::ConfigBuilder;
+    use procenv::file::FileError;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Database {
+        host: String,
+        password: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct SecretApp {
+        database: Database,
+    }
+
+    cleanup_file("secret_mismatch.toml");
+    write_file(
+        "secret_mismatch.toml",
+        "[database]\nhost = \"db\"\npassword = 8675309\n",
+    );
+
+    let err = ConfigBuilder::new()
+        .file("/tmp/procenv_fmt_tests/secret_mismatch.toml")
+        .secret_fields(|path| path == "database.password")
+        .build::<SecretApp>()
+        .unwrap_err();
+
+    let procenv::Error::File {
+        source:
+            FileError::TypeMismatch {
+                path_str,
+                src,
+                message,
+                ..
+            },
+    } = err
+    else {
+        panic!("expected a type mismatch, got {err:?}");
+    };
+
+    // The field is still located, but its value never reaches the diagnostic
+    assert_eq!(path_str, "database.password");
+    assert!(!message.contains("8675309"), "{message}");
+    assert!(!src.inner().contains("8675309"), "{}", src.inner());
+    assert!(
+        src.inner().contains("password = <redacted>"),
+        "{}",
+        src.inner()
+    );
+    assert!(src.inner().contains("host = \"db\""), "{}", src.inner());
+
+    cleanup_file("secret_mismatch.toml");
+}
+
+// ============================================================================
+// Merged Value Tests
+// ============================================================================
+
+#[test]
+fn test_config_merged_value_shows_layered_result() {
+    cleanup_env(&["MRG_PORT", "MRG_NAME"]);
+    cleanup_file("merged_value.toml");
+
+    write_file("merged_value.toml", "name = \"from-file\"\nport = 7000\n");
+
+    #[derive(EnvConfig)]
+    #[env_config(prefix = "MRG_", file = "/tmp/procenv_fmt_tests/merged_value.toml")]
+    struct MergedConfig {
+        #[env(var = "NAME", default = "default-name")]
+        name: String,
+
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+    }
+
+    let value = with_env(&[("MRG_PORT", "9000")], || {
+        MergedConfig::config_merged_value().expect("should merge layers")
+    });
+
+    assert_eq!(value["name"], "from-file");
+    // Env overrides the file value
+    assert_eq!(value["port"], 9000);
+
+    cleanup_file("merged_value.toml");
+}
+
+// ============================================================================
+// Build Into Tests
+// ============================================================================
+
+#[derive(Deserialize)]
+struct ReloadConfig {
+    name: String,
+    hosts: Vec<String>,
+}
+
+#[test]
+fn test_build_into_updates_existing_value() {
+    write_file(
+        "build_into.toml",
+        "name = \"reloaded\"\nhosts = [\"a\", \"b\"]\n",
+    );
+
+    let mut config = ReloadConfig {
+        name: "initial".to_string(),
+        hosts: Vec::with_capacity(16),
+    };
+
+    procenv::ConfigBuilder::new()
+        .file(format!("{BASE_DIR}/build_into.toml"))
+        .build_into(&mut config)
+        .expect("should build into existing value");
+
+    assert_eq!(config.name, "reloaded");
+    assert_eq!(config.hosts, vec!["a", "b"]);
+
+    cleanup_file("build_into.toml");
+}
+
+#[test]
+fn test_build_into_reports_path_on_error() {
+    write_file("build_into_bad.toml", "name = \"svc\"\nhosts = 42\n");
+
+    let mut config = ReloadConfig {
+        name: "initial".to_string(),
+        hosts: Vec::new(),
+    };
+
+    let err = procenv::ConfigBuilder::new()
+        .file(format!("{BASE_DIR}/build_into_bad.toml"))
+        .build_into(&mut config)
+        .expect_err("type mismatch should fail");
+    let msg = format!("{err:?}");
+    assert!(msg.contains("hosts"), "{msg}");
+
+    cleanup_file("build_into_bad.toml");
+}
+
+// ============================================================================
+// Files Override Env Tests
+// ============================================================================
+
+#[test]
+fn test_files_override_env_flips_precedence() {
+    cleanup_env(&["FOE_PORT", "FOE_NAME"]);
+    cleanup_file("files_override_env.toml");
+
+    write_file("files_override_env.toml", "port = 7000\n");
+
+    #[derive(EnvConfig)]
+    #[env_config(
+        prefix = "FOE_",
+        file = "/tmp/procenv_fmt_tests/files_override_env.toml",
+        files_override_env
+    )]
+    struct PinnedConfig {
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+
+        #[env(var = "NAME", default = "default-name")]
+        name: String,
+    }
+
+    let (config, sources) = with_env(&[("FOE_PORT", "9000"), ("FOE_NAME", "from-env")], || {
+        PinnedConfig::from_config_with_sources().expect("should load pinned config")
+    });
+
+    // The file wins over the env var it also sets
+    assert_eq!(config.port, 7000);
+    assert!(matches!(
+        sources.get("port").unwrap().source,
+        procenv::Source::ConfigFile(_)
+    ));
+
+    // Env still overrides keys the file doesn't set
+    assert_eq!(config.name, "from-env");
+    assert!(matches!(
+        sources.get("name").unwrap().source,
+        procenv::Source::Environment
+    ));
+
+    cleanup_file("files_override_env.toml");
+}
+
+#[test]
+fn test_files_override_env_keeps_embedded_below_env() {
+    cleanup_env(&["FOEE_NAME", "FOEE_PORT", "FOEE_DEBUG"]);
+    cleanup_file("files_override_embedded.toml");
+
+    write_file("files_override_embedded.toml", "port = 7100\n");
+
+    #[derive(EnvConfig)]
+    #[env_config(
+        prefix = "FOEE_",
+        embedded = "tests/fixtures/embedded_defaults.toml",
+        file = "/tmp/procenv_fmt_tests/files_override_embedded.toml",
+        files_override_env
+    )]
+    struct PinnedEmbeddedConfig {
+        #[env(var = "NAME", default = "default-app")]
+        name: String,
+
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+
+        #[env(var = "DEBUG", default = "false")]
+        debug: bool,
+    }
+
+    let (config, sources) = with_env(&[("FOEE_NAME", "from-env"), ("FOEE_PORT", "9000")], || {
+        PinnedEmbeddedConfig::from_config_with_sources().expect("should load pinned config")
+    });
+
+    // Env overrides the compiled-in embedded config
+    assert_eq!(config.name, "from-env");
+    assert!(matches!(
+        sources.get("name").unwrap().source,
+        procenv::Source::Environment
+    ));
+
+    // The file on disk still wins over env
+    assert_eq!(config.port, 7100);
+    assert!(matches!(
+        sources.get("port").unwrap().source,
+        procenv::Source::ConfigFile(_)
+    ));
+
+    // Embedded still fills keys nobody else sets
+    assert!(config.debug);
+    assert!(matches!(
+        sources.get("debug").unwrap().source,
+        procenv::Source::ConfigFile(_)
+    ));
+
+    cleanup_file("files_override_embedded.toml");
+}
+
+#[test]
+fn test_builder_files_override_env() {
+    use procenv::{ConfigBuilder, FileFormat};
+
+    cleanup_env(&["FOEB_PORT", "FOEB_HOST"]);
+    cleanup_file("builder_override.json");
+
+    write_file("builder_override.json", r#"{"port": 7000}"#);
+
+    let (value, _) = with_env(&[("FOEB_PORT", "9000"), ("FOEB_HOST", "env-host")], || {
+        ConfigBuilder::new()
+            .env_prefix("FOEB_")
+            .embedded(
+                r#"{"port": 6000, "host": "embedded-host"}"#,
+                FileFormat::Json,
+            )
+            .file("/tmp/procenv_fmt_tests/builder_override.json")
+            .files_override_env(true)
+            .into_value()
+            .expect("should merge")
+    });
+
+    assert_eq!(value["port"], 7000);
+    // Embedded content stays below env
+    assert_eq!(value["host"], "env-host");
+
+    cleanup_file("builder_override.json");
+}
+
+// ============================================================================
+// Loaded Files Tests
+// ============================================================================
+
+#[test]
+fn test_config_loaded_files_skips_missing_optional() {
+    cleanup_file("loaded_base.toml");
+    cleanup_file("loaded_missing.toml");
+
+    write_file("loaded_base.toml", "port = 7000\n");
+
+    #[derive(EnvConfig)]
+    #[env_config(
+        file = "/tmp/procenv_fmt_tests/loaded_base.toml",
+        file_optional = "/tmp/procenv_fmt_tests/loaded_missing.toml"
+    )]
+    struct LoadedConfig {
+        #[env(var = "LOADED_PORT", default = "8080")]
+        port: u16,
+    }
+
+    let loaded = LoadedConfig::config_loaded_files().expect("should merge");
+    assert_eq!(
+        loaded,
+        vec![std::path::PathBuf::from(
+            "/tmp/procenv_fmt_tests/loaded_base.toml"
+        )]
+    );
+
+    cleanup_file("loaded_base.toml");
+}
+
+#[test]
+fn test_builder_records_loaded_and_skipped_files() {
+    use procenv::ConfigBuilder;
+
+    cleanup_file("builder_loaded.json");
+    write_file("builder_loaded.json", r#"{"port": 1}"#);
+
+    let (_, origins) = ConfigBuilder::new()
+        .file_optional("/tmp/procenv_fmt_tests/builder_skipped.json")
+        .file("/tmp/procenv_fmt_tests/builder_loaded.json")
+        .into_value()
+        .expect("should merge");
+
+    assert_eq!(
+        origins.loaded_files(),
+        [std::path::PathBuf::from(
+            "/tmp/procenv_fmt_tests/builder_loaded.json"
+        )]
+    );
+    assert_eq!(
+        origins.skipped_files(),
+        [std::path::PathBuf::from(
+            "/tmp/procenv_fmt_tests/builder_skipped.json"
+        )]
+    );
+
+    cleanup_file("builder_loaded.json");
+}
+
+#[test]
+fn test_config_merged_value_redacts_secrets() {
+    cleanup_env(&["MRGS_TOKEN", "MRGS_PORT"]);
+    cleanup_file("merged_secret.toml");
+
+    write_file(
+        "merged_secret.toml",
+        "token = \"file-token\"\nport = 7000\n",
+    );
+
+    #[derive(EnvConfig)]
+    #[allow(dead_code)]
+    #[env_config(prefix = "MRGS_", file = "/tmp/procenv_fmt_tests/merged_secret.toml")]
+    struct SecretMergedConfig {
+        #[env(var = "TOKEN", secret)]
+        token: String,
+
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+    }
+
+    let value = SecretMergedConfig::config_merged_value().expect("should merge layers");
+    assert_eq!(value["token"], "<redacted>");
+    assert_eq!(value["port"], 7000);
+
+    // Only the exported value is redacted, not the loaded one
+    let config = SecretMergedConfig::from_config().expect("should load");
+    assert_eq!(config.token, "file-token");
+
+    cleanup_file("merged_secret.toml");
+}
+
+#[test]
+fn test_string_wrappers_from_config() {
+    cleanup_env(&["WRAP_NAME", "WRAP_REGION", "WRAP_MODE", "WRAP_LABEL"]);
+    cleanup_file("string_wrappers.toml");
+
+    write_file(
+        "string_wrappers.toml",
+        "name = \"file-name\"\nregion = \"eu\"\n",
+    );
+
+    #[derive(EnvConfig)]
+    #[env_config(prefix = "WRAP_", file = "/tmp/procenv_fmt_tests/string_wrappers.toml")]
+    struct WrapperFileConfig {
+        #[env(var = "NAME")]
+        name: std::sync::Arc<str>,
+
+        #[env(var = "REGION", optional)]
+        region: Option<Box<str>>,
+
+        #[env(var = "MODE", default = "fast")]
+        mode: std::borrow::Cow<'static, str>,
+
+        #[env(var = "LABEL", optional)]
+        label: Option<std::sync::Arc<str>>,
+    }
+
+    with_env(&[("WRAP_MODE", "safe")], || {
+        let config = WrapperFileConfig::from_config().expect("should load string wrappers");
+
+        assert_eq!(&*config.name, "file-name");
+        assert_eq!(config.region.as_deref(), Some("eu"));
+        assert_eq!(config.mode, "safe");
+        assert!(config.label.is_none());
+    });
+
+    cleanup_file("string_wrappers.toml");
+}
+
+// ============================================================================
+// Serde Flatten Tests
+// ============================================================================
+
+/// Stands in for a nested config type from another crate: `Deserialize` only.
+#[derive(Deserialize)]
+struct ExternalTls {
+    cert: String,
+    #[serde(default)]
+    verify: bool,
+}
+
+#[test]
+fn test_serde_flatten_from_config() {
+    cleanup_env(&["SFLAT_NAME"]);
+    cleanup_file("serde_flatten.toml");
+
+    write_file(
+        "serde_flatten.toml",
+        "name = \"svc\"\n\n[tls]\ncert = \"/etc/tls/cert.pem\"\nverify = true\n",
+    );
+
+    #[derive(EnvConfig)]
+    #[env_config(prefix = "SFLAT_", file = "/tmp/procenv_fmt_tests/serde_flatten.toml")]
+    struct SerdeFlattenConfig {
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(flatten, serde)]
+        tls: ExternalTls,
+    }
+
+    let config = SerdeFlattenConfig::from_config().expect("should load serde flatten field");
+
+    assert_eq!(config.name, "svc");
+    assert_eq!(config.tls.cert, "/etc/tls/cert.pem");
+    assert!(config.tls.verify);
+
+    cleanup_file("serde_flatten.toml");
+}
+
+#[test]
+fn test_serde_flatten_errors_explain_file_only() {
+    cleanup_env(&["SFLAT_MISSING_NAME"]);
+    cleanup_file("serde_flatten_missing.toml");
+
+    write_file("serde_flatten_missing.toml", "name = \"svc\"\n");
+
+    #[derive(EnvConfig)]
+    #[allow(dead_code)]
+    #[env_config(
+        prefix = "SFLAT_MISSING_",
+        file = "/tmp/procenv_fmt_tests/serde_flatten_missing.toml"
+    )]
+    struct SerdeFlattenMissingConfig {
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(flatten, serde)]
+        tls: ExternalTls,
+    }
+
+    // The nested key is missing from the file
+    let err = SerdeFlattenMissingConfig::from_config().expect_err("missing nested key should fail");
+    let msg = format!("{err:?}");
+    assert!(msg.contains("cert"), "{msg}");
+    assert!(msg.contains("environment variables cannot set"), "{msg}");
+
+    // Env-only loading has no way to fill the field
+    let err = with_env(&[("SFLAT_MISSING_NAME", "svc")], || {
+        SerdeFlattenMissingConfig::from_env()
+            .expect_err("from_env cannot load a serde flatten field")
+    });
+    let msg = format!("{err:?}");
+    assert!(msg.contains("tls"), "{msg}");
+    assert!(msg.contains("only read from config files"), "{msg}");
+
+    cleanup_file("serde_flatten_missing.toml");
+}
+
+// ============================================================================
+// Serde Rename Tests
+// ============================================================================
+
+#[test]
+fn test_serde_rename_keys_file_lookups() {
+    cleanup_env(&[
+        "SREN_MAX_CONNS",
+        "SREN_DB_URL",
+        "SREN_API_KEY",
+        "SREN_RETRY",
+    ]);
+    cleanup_file("serde_rename.toml");
+
+    write_file(
+        "serde_rename.toml",
+        "maxConns = 50\ndatabase = \"postgres://file\"\napiKey = \"hunter2\"\n",
+    );
+
+    #[derive(EnvConfig, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    #[env_config(prefix = "SREN_", file = "/tmp/procenv_fmt_tests/serde_rename.toml")]
+    struct SerdeRenameConfig {
+        #[env(var = "MAX_CONNS")]
+        max_conns: u32,
+
+        // A field-level rename wins over `rename_all`
+        #[env(var = "DB_URL")]
+        #[serde(rename = "database")]
+        db_url: String,
+
+        #[env(var = "API_KEY", secret)]
+        api_key: String,
+
+        #[env(var = "RETRY", default = "3")]
+        retry_count: u8,
+    }
+
+    let (config, sources) =
+        SerdeRenameConfig::from_config_with_sources().expect("should load renamed keys");
+    assert_eq!(config.max_conns, 50);
+    assert_eq!(config.db_url, "postgres://file");
+    assert_eq!(config.api_key, "hunter2");
+    assert_eq!(config.retry_count, 3);
+
+    // Sources stay keyed by the Rust field name
+    let db_source = sources.get("db_url").expect("should have db_url source");
+    assert!(matches!(db_source.source, procenv::Source::ConfigFile(_)));
+
+    // Defaults are merged under the renamed key, and renamed secrets are redacted
+    let merged = SerdeRenameConfig::config_merged_value().expect("should merge");
+    assert_eq!(merged["retryCount"], 3);
+    assert_eq!(merged["apiKey"], "<redacted>");
+
+    // `env(var)` names the env var independently of the file key
+    let config = with_env(&[("SREN_DB_URL", "postgres://env")], || {
+        SerdeRenameConfig::from_config().expect("env should override file")
+    });
+    assert_eq!(config.db_url, "postgres://env");
+
+    cleanup_file("serde_rename.toml");
+}
+
+#[test]
+fn test_serde_rename_nested_flatten() {
+    cleanup_env(&["SRENN_NAME", "SRENN_DB_HOST", "SRENN_DB_POOL"]);
+    cleanup_file("serde_rename_nested.toml");
+
+    write_file(
+        "serde_rename_nested.toml",
+        "name = \"svc\"\n\n[data-store]\nhost-name = \"db.internal\"\n",
+    );
+
+    #[derive(EnvConfig, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct RenamedDb {
+        #[env(var = "HOST")]
+        host_name: String,
+
+        #[env(var = "POOL", default = "4")]
+        pool_size: u32,
+    }
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "SRENN_",
+        file = "/tmp/procenv_fmt_tests/serde_rename_nested.toml"
+    )]
+    struct RenamedNestedConfig {
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(flatten, prefix = "DB_")]
+        #[serde(rename = "data-store")]
+        db: RenamedDb,
+    }
+
+    let (config, sources) =
+        RenamedNestedConfig::from_config_with_sources().expect("should load nested renames");
+    assert_eq!(config.name, "svc");
+    assert_eq!(config.db.host_name, "db.internal");
+    assert_eq!(config.db.pool_size, 4);
+
+    // Nested source paths follow the file keys
+    let host_source = sources
+        .get("data-store.host-name")
+        .expect("should have nested host source");
+    assert!(matches!(host_source.source, procenv::Source::ConfigFile(_)));
+
+    let config = with_env(&[("SRENN_DB_POOL", "16")], || {
+        RenamedNestedConfig::from_config().expect("nested env should override")
+    });
+    assert_eq!(config.db.pool_size, 16);
+
+    cleanup_file("serde_rename_nested.toml");
+}
+
+// ============================================================================
+// Default Expansion Tests
+// ============================================================================
+
+#[test]
+fn test_config_default_expands_env_references() {
+    cleanup_env(&["DEXP_NAME", "DEXP_CACHE_DIR", "DEXP_ROOT"]);
+    cleanup_file("default_expand.toml");
+
+    write_file("default_expand.toml", "name = \"svc\"\n");
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(prefix = "DEXP_", file = "/tmp/procenv_fmt_tests/default_expand.toml")]
+    struct DefaultExpandConfig {
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(var = "CACHE_DIR", default = "${DEXP_ROOT}/cache")]
+        cache_dir: String,
+    }
+
+    let config = with_env(&[("DEXP_ROOT", "/var/lib/svc")], || {
+        DefaultExpandConfig::from_config().expect("should expand default")
+    });
+    assert_eq!(config.name, "svc");
+    assert_eq!(config.cache_dir, "/var/lib/svc/cache");
+
+    let err = DefaultExpandConfig::from_config().expect_err("DEXP_ROOT is unset");
+    assert!(err.to_string().contains("${DEXP_ROOT}"), "got: {err}");
+
+    cleanup_file("default_expand.toml");
+}
+
+// ============================================================================
+// Distinguish Null Tests
+// ============================================================================
+
+#[test]
+fn test_distinguish_null_keeps_explicit_null() {
+    cleanup_env(&["DNULL_PROXY", "DNULL_TIMEOUT", "DNULL_RETRIES"]);
+    cleanup_file("distinguish_null.json");
+
+    write_file(
+        "distinguish_null.json",
+        r#"{"proxy": null, "timeout": 30, "retries": null}"#,
+    );
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "DNULL_",
+        file = "/tmp/procenv_fmt_tests/distinguish_null.json"
+    )]
+    struct NullableConfig {
+        #[env(var = "PROXY", optional, distinguish_null)]
+        proxy: Option<Option<String>>,
+
+        #[env(var = "TIMEOUT", optional, distinguish_null)]
+        timeout: Option<Option<u32>>,
+
+        #[env(var = "LIMIT", optional, distinguish_null)]
+        limit: Option<Option<u32>>,
+
+        // Without the flag, null and absent both load as `None`
+        #[env(var = "RETRIES", optional)]
+        retries: Option<u8>,
+    }
+
+    let config = NullableConfig::from_config().expect("should load");
+    assert_eq!(config.proxy, Some(None));
+    assert_eq!(config.timeout, Some(Some(30)));
+    assert_eq!(config.limit, None);
+    assert_eq!(config.retries, None);
+
+    // Env vars have no null; a set var is always a value
+    let config = with_env(&[("DNULL_PROXY", "http://proxy:3128")], || {
+        NullableConfig::from_config().expect("env should override null")
+    });
+    assert_eq!(config.proxy, Some(Some("http://proxy:3128".to_string())));
+
+    let config = with_env(&[("DNULL_TIMEOUT", "5")], || {
+        NullableConfig::from_env().expect("from_env should load")
+    });
+    assert_eq!(config.timeout, Some(Some(5)));
+    assert_eq!(config.proxy, None);
+
+    cleanup_file("distinguish_null.json");
+}
+
+// ============================================================================
+// Schema Version Tests
+// ============================================================================
+
+#[test]
+fn test_schema_version_rejects_newer_config() {
+    cleanup_env(&["SCHEMA_PORT"]);
+    cleanup_file("schema_version.json");
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "SCHEMA_",
+        file_optional = "/tmp/procenv_fmt_tests/schema_version.json",
+        schema_version = 2
+    )]
+    struct VersionedConfig {
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+    }
+
+    // No version key counts as version 1
+    write_file("schema_version.json", r#"{"port": 9000}"#);
+    let config = VersionedConfig::from_config().expect("unversioned config should load");
+    assert_eq!(config.port, 9000);
+
+    write_file("schema_version.json", r#"{"version": 2, "port": 9001}"#);
+    let config = VersionedConfig::from_config().expect("current version should load");
+    assert_eq!(config.port, 9001);
+
+    write_file("schema_version.json", r#"{"version": 3, "port": 9002}"#);
+    let err = VersionedConfig::from_config().unwrap_err();
+    let procenv::Error::File { source } = &err else {
+        panic!("expected File error, got {err:?}");
+    };
+    assert!(matches!(
+        source,
+        procenv::file::FileError::UnsupportedSchemaVersion {
+            version: 3,
+            supported: 2,
+            ..
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "configuration file error: configuration schema version 3 is newer than supported version 2"
+    );
+
+    write_file("schema_version.json", r#"{"version": "two"}"#);
+    let err = VersionedConfig::from_config().unwrap_err();
+    assert!(matches!(
+        err,
+        procenv::Error::File {
+            source: procenv::file::FileError::InvalidSchemaVersion { .. }
+        }
+    ));
+
+    cleanup_file("schema_version.json");
+}
+
+#[test]
+fn test_schema_version_custom_key_and_missing_version() {
+    cleanup_file("schema_version_nested.toml");
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        file_optional = "/tmp/procenv_fmt_tests/schema_version_nested.toml",
+        schema_version = 3,
+        version_key = "meta.schema",
+        missing_version = 3
+    )]
+    struct NestedVersionConfig {
+        #[env(var = "SCHEMA_NESTED_NAME", default = "app")]
+        name: String,
+    }
+
+    write_file("schema_version_nested.toml", "name = \"svc\"\n");
+    assert_eq!(NestedVersionConfig::from_config().unwrap().name, "svc");
+
+    // The top-level `version` key is not the one being checked
+    write_file(
+        "schema_version_nested.toml",
+        "version = 9\nname = \"svc\"\n\n[meta]\nschema = 3\n",
+    );
+    assert!(NestedVersionConfig::from_config().is_ok());
+
+    write_file("schema_version_nested.toml", "[meta]\nschema = 4\n");
+    assert!(NestedVersionConfig::from_config().is_err());
+
+    cleanup_file("schema_version_nested.toml");
+}
+
+// ============================================================================
+// Per-Field File Tests
+// ============================================================================
+
+#[derive(EnvConfig, Deserialize)]
+#[env_config(file_optional = "/tmp/procenv_fmt_tests/field_file_main.toml")]
+struct FieldFileConfig {
+    #[env(var = "FIELD_FILE_NAME", default = "app")]
+    name: String,
+
+    #[env(
+        var = "FIELD_FILE_LICENSE",
+        secret,
+        file = "/tmp/procenv_fmt_tests/field_file_license.json",
+        file_key = "license.key"
+    )]
+    license: String,
+
+    #[env(
+        var = "FIELD_FILE_SEATS",
+        default = "1",
+        file = "/tmp/procenv_fmt_tests/field_file_license.json"
+    )]
+    seats: u32,
+}
+
+#[test]
+fn test_field_file_layers_over_struct_files() {
+    // One test, since every step rewrites the same files
+    cleanup_env(&["FIELD_FILE_NAME", "FIELD_FILE_LICENSE", "FIELD_FILE_SEATS"]);
+    write_file(
+        "field_file_main.toml",
+        "name = \"svc\"\nlicense = \"from-main\"\nseats = 2\n",
+    );
+    write_file(
+        "field_file_license.json",
+        r#"{"license": {"key": "LIC-123"}, "seats": 25}"#,
+    );
+
+    let config = FieldFileConfig::from_config().expect("should load");
+    assert_eq!(config.name, "svc");
+    assert_eq!(config.license, "LIC-123");
+    // Without `file_key` the field's own key is read
+    assert_eq!(config.seats, 25);
+
+    let files = FieldFileConfig::config_loaded_files().unwrap();
+    assert!(files.iter().any(|f| f.ends_with("field_file_license.json")));
+
+    // Env vars still win over the field's file
+    with_env(&[("FIELD_FILE_LICENSE", "LIC-ENV")], || {
+        let config = FieldFileConfig::from_config().expect("should load");
+        assert_eq!(config.license, "LIC-ENV");
+    });
+
+    let (_, sources) = FieldFileConfig::from_config_with_sources().unwrap();
+    let license = sources.get("license").expect("license is tracked");
+    assert!(
+        matches!(&license.source, procenv::Source::ConfigFile(Some(path)) if path.ends_with("field_file_license.json"))
+    );
+
+    // A file without the key leaves the field to the other layers
+    cleanup_file("field_file_main.toml");
+    write_file(
+        "field_file_license.json",
+        r#"{"license": {"key": "LIC-9"}}"#,
+    );
+    assert_eq!(FieldFileConfig::from_config().unwrap().seats, 1);
+
+    // The file itself is required
+    cleanup_file("field_file_license.json");
+    let err = FieldFileConfig::from_config().unwrap_err();
+    assert!(matches!(
+        err,
+        procenv::Error::File {
+            source: procenv::file::FileError::NotFound { .. }
+        }
+    ));
+}
+
+// ============================================================================
+// Profile-Required Fields in Files
+// ============================================================================
+
+#[test]
+fn test_when_profile_from_config() {
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        profile_env = "FMT_WHENPROF_ENV",
+        file_optional = "/tmp/procenv_fmt_tests/when_profile.toml"
+    )]
+    struct WhenProfileFileConfig {
+        #[env(var = "FMT_WHENPROF_CERT", when_profile = "prod")]
+        cert: Option<String>,
+    }
+
+    cleanup_env(&["FMT_WHENPROF_ENV", "FMT_WHENPROF_CERT"]);
+    cleanup_file("when_profile.toml");
+
+    let config = WhenProfileFileConfig::from_config().expect("optional outside prod");
+    assert_eq!(config.cert, None);
+
+    with_env(&[("FMT_WHENPROF_ENV", "prod")], || {
+        let err = WhenProfileFileConfig::from_config().unwrap_err();
+        assert_eq!(err.missing_vars(), ["cert"]);
+
+        // Any layer satisfies the requirement
+        write_file("when_profile.toml", "cert = \"/etc/tls/cert.pem\"\n");
+        let config = WhenProfileFileConfig::from_config().expect("set in the file");
+        assert_eq!(config.cert.as_deref(), Some("/etc/tls/cert.pem"));
+    });
+
+    cleanup_file("when_profile.toml");
+}
+
+// ============================================================================
+// Numeric Booleans
+// ============================================================================
+
+#[test]
+fn test_coerce_bool_numeric_from_each_source() {
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "FMT_NUMBOOL_",
+        file_optional = "/tmp/procenv_fmt_tests/numeric_bool.json"
+    )]
+    struct NumericBoolFileConfig {
+        #[env(var = "DEBUG", coerce_bool_numeric)]
+        debug: bool,
+
+        #[env(var = "COLOR", coerce_bool_numeric, optional)]
+        color: Option<bool>,
+    }
+
+    cleanup_env(&["FMT_NUMBOOL_DEBUG", "FMT_NUMBOOL_COLOR"]);
+
+    // File values, stored as a boolean, a number and a string
+    for (content, expected) in [
+        (r#"{"debug": false, "color": true}"#, (false, true)),
+        (r#"{"debug": 1, "color": 0}"#, (true, false)),
+        (r#"{"debug": "0", "color": "true"}"#, (false, true)),
+    ] {
+        write_file("numeric_bool.json", content);
+        let config = NumericBoolFileConfig::from_config().expect(content);
+        assert_eq!((config.debug, config.color), (expected.0, Some(expected.1)));
+    }
+
+    // Env overrides of a file boolean, spelled as digits and as words
+    write_file("numeric_bool.json", r#"{"debug": false, "color": true}"#);
+    for (value, expected) in [("1", true), ("0", false), ("TRUE", true)] {
+        with_env(
+            &[("FMT_NUMBOOL_DEBUG", value), ("FMT_NUMBOOL_COLOR", value)],
+            || {
+                let config = NumericBoolFileConfig::from_config().expect(value);
+                assert_eq!((config.debug, config.color), (expected, Some(expected)));
+            },
+        );
+    }
+
+    write_file("numeric_bool.json", r#"{"debug": 2}"#);
+    assert!(NumericBoolFileConfig::from_config().is_err());
+
+    cleanup_file("numeric_bool.json");
+}
+
+#[test]
+fn test_bool_without_coerce_rejects_numbers() {
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(file_optional = "/tmp/procenv_fmt_tests/strict_bool.json")]
+    struct StrictBoolConfig {
+        #[env(var = "FMT_STRICTBOOL_DEBUG")]
+        debug: bool,
+    }
+
+    cleanup_env(&["FMT_STRICTBOOL_DEBUG"]);
+    write_file("strict_bool.json", r#"{"debug": 1}"#);
+
+    assert!(StrictBoolConfig::from_config().is_err());
+
+    cleanup_file("strict_bool.json");
+}
+
+// ============================================================================
+// Allowlist Checks in Files
+// ============================================================================
+
+#[test]
+fn test_one_of_from_config_names_env_var() {
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "FMT_ONEOF_",
+        file_optional = "/tmp/procenv_fmt_tests/one_of.toml"
+    )]
+    struct OneOfFileConfig {
+        #[env(var = "TIER", default = "free", one_of = ["free", "pro"])]
+        tier: String,
+    }
+
+    cleanup_env(&["FMT_ONEOF_TIER"]);
+    write_file("one_of.toml", "tier = \"gold\"\n");
+
+    let err = OneOfFileConfig::from_config().unwrap_err();
+    let procenv::Error::NotOneOf { var, allowed, .. } = &err else {
+        panic!("expected NotOneOf, got {err:?}");
+    };
+    assert_eq!(var, "FMT_ONEOF_TIER");
+    assert_eq!(allowed, &["free", "pro"]);
+
+    cleanup_file("one_of.toml");
+}
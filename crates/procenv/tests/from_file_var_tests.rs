@@ -0,0 +1,222 @@
+//! File-backed env var tests.
+//!
+//! Tests for `#[env(from_file_var = "...")]`, which reads a field's value
+//! from the file named by a `*_FILE` var before falling back to the field's
+//! own var.
+
+#![allow(clippy::pedantic)]
+
+use std::io::Write;
+
+use procenv::{EnvConfig, Error};
+use serial_test::serial;
+use tempfile::NamedTempFile;
+
+fn cleanup_env(vars: &[&str]) {
+    unsafe {
+        for k in vars {
+            std::env::remove_var(*k);
+        }
+    }
+}
+
+fn with_env<F, R>(vars: &[(&str, &str)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        for (k, v) in vars {
+            std::env::set_var(*k, *v);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (k, _) in vars {
+            std::env::remove_var(*k);
+        }
+    }
+
+    result
+}
+
+fn secret_file(contents: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(contents.as_bytes()).unwrap();
+    file
+}
+
+const VARS: &[&str] = &[
+    "FF_DB_PASSWORD",
+    "FF_DB_PASSWORD_FILE",
+    "FF_PORT",
+    "FF_PORT_FILE",
+    "FF_TOKEN",
+    "FF_TOKEN_FILE",
+    "SVC_FF_DB_PASSWORD",
+    "SVC_FF_DB_PASSWORD_FILE",
+];
+
+#[derive(EnvConfig)]
+struct FileConfig {
+    #[env(var = "FF_DB_PASSWORD", from_file_var = "FF_DB_PASSWORD_FILE", secret)]
+    db_password: String,
+
+    #[env(var = "FF_PORT", from_file_var = "FF_PORT_FILE", default = "5432")]
+    port: u16,
+
+    #[env(var = "FF_TOKEN", from_file_var = "FF_TOKEN_FILE", optional)]
+    token: Option<String>,
+}
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "SVC_")]
+struct PrefixedFileConfig {
+    #[env(var = "FF_DB_PASSWORD", from_file_var = "FF_DB_PASSWORD_FILE")]
+    db_password: String,
+}
+
+#[derive(EnvConfig)]
+struct OuterConfig {
+    #[env(flatten, prefix = "SVC_")]
+    inner: PrefixedInner,
+}
+
+#[derive(EnvConfig)]
+struct PrefixedInner {
+    #[env(var = "FF_DB_PASSWORD", from_file_var = "FF_DB_PASSWORD_FILE")]
+    db_password: String,
+}
+
+#[test]
+#[serial]
+fn test_file_preferred_and_newline_trimmed() {
+    cleanup_env(VARS);
+    let file = secret_file("from-file\n");
+    let path = file.path().to_str().unwrap();
+
+    with_env(
+        &[
+            ("FF_DB_PASSWORD", "from-env"),
+            ("FF_DB_PASSWORD_FILE", path),
+        ],
+        || {
+            let config = FileConfig::from_env().unwrap();
+            assert_eq!(config.db_password, "from-file");
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_falls_back_to_var_then_default() {
+    cleanup_env(VARS);
+    with_env(&[("FF_DB_PASSWORD", "from-env")], || {
+        let config = FileConfig::from_env().unwrap();
+        assert_eq!(config.db_password, "from-env");
+        assert_eq!(config.port, 5432);
+        assert_eq!(config.token, None);
+    });
+}
+
+#[test]
+#[serial]
+fn test_file_value_is_parsed() {
+    cleanup_env(VARS);
+    let port = secret_file("6543\r\n");
+    let token = secret_file("tok");
+
+    with_env(
+        &[
+            ("FF_DB_PASSWORD", "pw"),
+            ("FF_PORT_FILE", port.path().to_str().unwrap()),
+            ("FF_TOKEN_FILE", token.path().to_str().unwrap()),
+        ],
+        || {
+            let config = FileConfig::from_env().unwrap();
+            assert_eq!(config.port, 6543);
+            assert_eq!(config.token.as_deref(), Some("tok"));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_unreadable_file_is_reported() {
+    cleanup_env(VARS);
+    with_env(
+        &[
+            ("FF_DB_PASSWORD", "pw"),
+            ("FF_PORT_FILE", "/nonexistent/procenv/port"),
+        ],
+        || {
+            let err = FileConfig::from_env().unwrap_err();
+            match err {
+                Error::FileVarRead { var, path, .. } => {
+                    assert_eq!(var, "FF_PORT_FILE");
+                    assert_eq!(path, "/nonexistent/procenv/port");
+                }
+                other => panic!("expected FileVarRead, got {other:?}"),
+            }
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_secret_file_contents_redacted_in_errors() {
+    #[derive(EnvConfig)]
+    #[allow(dead_code)]
+    struct SecretPort {
+        #[env(var = "FF_PORT", from_file_var = "FF_PORT_FILE", secret)]
+        port: u16,
+    }
+
+    cleanup_env(VARS);
+    let file = secret_file("hunter2\n");
+
+    with_env(&[("FF_PORT_FILE", file.path().to_str().unwrap())], || {
+        let err = SecretPort::from_env().unwrap_err();
+        assert!(matches!(err, Error::Parse { .. }));
+        assert!(!err.to_string().contains("hunter2"));
+        assert!(!format!("{err:?}").contains("hunter2"));
+    });
+}
+
+#[test]
+#[serial]
+fn test_file_var_is_prefixed() {
+    cleanup_env(VARS);
+    let file = secret_file("prefixed\n");
+    let path = file.path().to_str().unwrap();
+
+    with_env(&[("SVC_FF_DB_PASSWORD_FILE", path)], || {
+        let config = PrefixedFileConfig::from_env().unwrap();
+        assert_eq!(config.db_password, "prefixed");
+
+        let config = OuterConfig::from_env().unwrap();
+        assert_eq!(config.inner.db_password, "prefixed");
+    });
+}
+
+#[cfg(feature = "secrecy")]
+#[test]
+#[serial]
+fn test_secret_string_from_file() {
+    use procenv::{ExposeSecret, SecretString};
+
+    #[derive(EnvConfig)]
+    struct SecretConfig {
+        #[env(var = "FF_TOKEN", from_file_var = "FF_TOKEN_FILE")]
+        token: SecretString,
+    }
+
+    cleanup_env(VARS);
+    let file = secret_file("s3cret\n");
+
+    with_env(&[("FF_TOKEN_FILE", file.path().to_str().unwrap())], || {
+        let config = SecretConfig::from_env().unwrap();
+        assert_eq!(config.token.expose_secret(), "s3cret");
+    });
+}
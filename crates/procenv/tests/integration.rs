@@ -771,6 +771,307 @@ mod serde_format_tests {
             std::env::remove_var("TEST_DEFAULT_JSON");
         }
     }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Matrix {
+        rows: Vec<Vec<u8>>,
+        #[serde(rename = "NAME")]
+        name: String,
+    }
+
+    #[derive(EnvConfig)]
+    #[env_config(prefix = "APP_")]
+    struct PrefixedFormatConfig {
+        #[env(var = "MATRIX", format = "json")]
+        matrix: Matrix,
+
+        #[env(var = "TEST_UNPREFIXED_JSON", format = "json", no_prefix)]
+        unprefixed: Tags,
+    }
+
+    #[test]
+    #[serial]
+    fn test_format_field_uses_struct_prefix() {
+        unsafe {
+            std::env::set_var("APP_MATRIX", r#"{"rows":[[1,2],[3]],"NAME":"m"}"#);
+            std::env::set_var("TEST_UNPREFIXED_JSON", r#"{"values":["bare"]}"#);
+            std::env::remove_var("MATRIX");
+        }
+
+        // Only the field's own var is prefixed; keys inside the JSON are not
+        let config = PrefixedFormatConfig::from_env().expect("should read APP_MATRIX");
+        assert_eq!(config.matrix.rows, vec![vec![1, 2], vec![3]]);
+        assert_eq!(config.matrix.name, "m");
+        assert_eq!(config.unprefixed.values, vec!["bare".to_string()]);
+
+        let (_, sources) = PrefixedFormatConfig::from_env_with_sources().expect("should load");
+        assert_eq!(sources.get("matrix").unwrap().var_name, "APP_MATRIX");
+        assert_eq!(
+            sources.get("unprefixed").unwrap().var_name,
+            "TEST_UNPREFIXED_JSON"
+        );
+
+        unsafe {
+            std::env::remove_var("APP_MATRIX");
+            std::env::remove_var("TEST_UNPREFIXED_JSON");
+        }
+    }
+
+    #[derive(EnvConfig)]
+    struct SecretCollectionConfig {
+        #[env(var = "TEST_SECRET_LIST", format = "json", secret)]
+        list: Vec<String>,
+
+        #[env(var = "TEST_SECRET_MAP", format = "json", secret)]
+        map: std::collections::HashMap<String, String>,
+
+        #[env(var = "TEST_SECRET_EMPTY", format = "json", secret, optional)]
+        empty: Option<Vec<String>>,
+    }
+
+    #[test]
+    #[serial]
+    fn test_secret_collection_debug_masks_elements() {
+        unsafe {
+            std::env::set_var("TEST_SECRET_LIST", r#"["hunter2","swordfish"]"#);
+            std::env::set_var("TEST_SECRET_MAP", r#"{"db_user":"tr0ub4dor"}"#);
+            std::env::set_var("TEST_SECRET_EMPTY", "[]");
+        }
+
+        let config = SecretCollectionConfig::from_env().expect("should load");
+        let debug = format!("{config:?}");
+
+        for value in ["hunter2", "swordfish", "db_user", "tr0ub4dor"] {
+            assert!(
+                !debug.contains(value),
+                "Debug should not contain {value}: {debug}"
+            );
+        }
+        assert!(debug.contains("list: [<redacted>, <redacted>]"), "{debug}");
+        assert!(debug.contains("map: {..: <redacted>}"), "{debug}");
+        assert!(debug.contains("empty: Some([])"), "{debug}");
+
+        unsafe {
+            std::env::remove_var("TEST_SECRET_LIST");
+            std::env::remove_var("TEST_SECRET_MAP");
+            std::env::remove_var("TEST_SECRET_EMPTY");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Features {
+        beta: bool,
+        name: String,
+        limits: Limits,
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Limits {
+        #[serde(alias = "max-conns")]
+        max_conns: u32,
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(EnvConfig)]
+    struct MergeEnvConfig {
+        #[env(
+            var = "TEST_MERGE_FEATURES",
+            format = "json",
+            merge_env,
+            default = r#"{"beta":false,"name":"base","limits":{"max-conns":10}}"#
+        )]
+        features: Features,
+
+        #[env(var = "TEST_MERGE_EXTRA", format = "json", merge_env, optional)]
+        extra: Option<Limits>,
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    #[serial]
+    fn test_merge_env_overrides_json_keys() {
+        unsafe {
+            std::env::remove_var("TEST_MERGE_FEATURES");
+            std::env::remove_var("TEST_MERGE_EXTRA");
+            std::env::set_var("TEST_MERGE_FEATURES_BETA", "true");
+            std::env::set_var("TEST_MERGE_FEATURES_NAME", "123");
+            std::env::set_var("TEST_MERGE_FEATURES_LIMITS_MAX_CONNS", "50");
+            std::env::set_var("TEST_MERGE_EXTRA_MAX_CONNS", "7");
+        }
+
+        // Sub-keys win over the default object; strings stay strings
+        let config = MergeEnvConfig::from_env().expect("should load");
+        assert_eq!(
+            config.features,
+            Features {
+                beta: true,
+                name: "123".to_string(),
+                limits: Limits { max_conns: 50 },
+            }
+        );
+        // Without a base object there are no keys to override
+        assert_eq!(config.extra, None);
+
+        unsafe {
+            std::env::set_var("TEST_MERGE_EXTRA", r#"{"max_conns":1}"#);
+            std::env::set_var("TEST_MERGE_FEATURES_BETA", "yes");
+        }
+
+        // Non-JSON overrides fall back to strings and fail deserialization
+        let err = MergeEnvConfig::from_env().expect_err("beta must be a bool");
+        assert!(err.to_string().contains("TEST_MERGE_FEATURES"), "{err}");
+
+        unsafe {
+            std::env::remove_var("TEST_MERGE_FEATURES_BETA");
+        }
+
+        let config = MergeEnvConfig::from_env().expect("should load");
+        assert_eq!(config.extra, Some(Limits { max_conns: 7 }));
+
+        unsafe {
+            std::env::remove_var("TEST_MERGE_EXTRA");
+            std::env::remove_var("TEST_MERGE_FEATURES_NAME");
+            std::env::remove_var("TEST_MERGE_FEATURES_LIMITS_MAX_CONNS");
+            std::env::remove_var("TEST_MERGE_EXTRA_MAX_CONNS");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    #[serial]
+    fn test_auto_format_detects_each_format() {
+        #[derive(EnvConfig)]
+        struct AutoConfig {
+            #[env(var = "TEST_AUTO_TAGS", format = "auto")]
+            tags: Tags,
+
+            #[env(var = "TEST_AUTO_EXTRA", format = "auto", optional)]
+            extra: Option<Tags>,
+        }
+
+        unsafe {
+            std::env::set_var("TEST_AUTO_TAGS", r#"{"values":["json"]}"#);
+            std::env::remove_var("TEST_AUTO_EXTRA");
+        }
+
+        let config = AutoConfig::from_env().expect("JSON should load");
+        assert_eq!(config.tags.values, vec!["json"]);
+        assert_eq!(config.extra, None);
+
+        #[cfg(feature = "yaml")]
+        {
+            unsafe {
+                std::env::set_var("TEST_AUTO_TAGS", "values:\n  - yaml\n");
+            }
+
+            let config = AutoConfig::from_env().expect("YAML should load");
+            assert_eq!(config.tags.values, vec!["yaml"]);
+        }
+
+        #[cfg(feature = "toml")]
+        {
+            unsafe {
+                std::env::set_var("TEST_AUTO_TAGS", r#"values = ["toml"]"#);
+            }
+
+            let config = AutoConfig::from_env().expect("TOML should load");
+            assert_eq!(config.tags.values, vec!["toml"]);
+        }
+
+        unsafe {
+            std::env::set_var("TEST_AUTO_TAGS", r#"{"values":["ok"]}"#);
+            std::env::set_var("TEST_AUTO_EXTRA", "[not, tags");
+        }
+
+        // Every attempt is reported when no format matches
+        let err = AutoConfig::from_env().expect_err("no format should match");
+        let message = format!("{err:?}");
+        assert!(message.contains("TEST_AUTO_EXTRA"), "{message}");
+        assert!(message.contains("JSON:"), "{message}");
+        #[cfg(feature = "yaml")]
+        assert!(message.contains("YAML:"), "{message}");
+        #[cfg(feature = "toml")]
+        assert!(message.contains("TOML:"), "{message}");
+
+        unsafe {
+            std::env::remove_var("TEST_AUTO_TAGS");
+            std::env::remove_var("TEST_AUTO_EXTRA");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    #[serial]
+    fn test_deserialize_with_replaces_deserialize() {
+        use std::time::Duration;
+
+        fn seconds<'de, D: serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Duration, D::Error> {
+            u64::deserialize(deserializer).map(Duration::from_secs)
+        }
+
+        #[derive(EnvConfig)]
+        struct TimeoutConfig {
+            #[env(
+                var = "TEST_DW_REQUIRED",
+                format = "json",
+                deserialize_with = "seconds"
+            )]
+            required: Duration,
+
+            #[env(
+                var = "TEST_DW_OPTIONAL",
+                format = "json",
+                deserialize_with = "seconds",
+                optional
+            )]
+            optional: Option<Duration>,
+
+            #[env(
+                var = "TEST_DW_DEFAULT",
+                format = "json",
+                deserialize_with = "seconds",
+                default = "5"
+            )]
+            with_default: Duration,
+        }
+
+        unsafe {
+            std::env::set_var("TEST_DW_REQUIRED", "30");
+            std::env::remove_var("TEST_DW_OPTIONAL");
+            std::env::remove_var("TEST_DW_DEFAULT");
+        }
+
+        let config = TimeoutConfig::from_env().expect("should load");
+        assert_eq!(config.required, Duration::from_secs(30));
+        assert_eq!(config.optional, None);
+        assert_eq!(config.with_default, Duration::from_secs(5));
+
+        unsafe {
+            std::env::set_var("TEST_DW_OPTIONAL", "7");
+            std::env::set_var("TEST_DW_DEFAULT", "not json");
+        }
+
+        let err = TimeoutConfig::from_env().expect_err("invalid JSON should fail");
+        assert!(format!("{err:?}").contains("TEST_DW_DEFAULT"));
+
+        unsafe {
+            std::env::set_var("TEST_DW_DEFAULT", "9");
+        }
+
+        let config = TimeoutConfig::from_env().expect("should load");
+        assert_eq!(config.optional, Some(Duration::from_secs(7)));
+        assert_eq!(config.with_default, Duration::from_secs(9));
+
+        unsafe {
+            std::env::remove_var("TEST_DW_REQUIRED");
+            std::env::remove_var("TEST_DW_OPTIONAL");
+            std::env::remove_var("TEST_DW_DEFAULT");
+        }
+    }
 }
 
 // ============================================================================
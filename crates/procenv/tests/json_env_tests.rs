@@ -0,0 +1,152 @@
+//! Whole-struct JSON env var tests.
+//!
+//! Tests for `#[env_config(from_json_var = "...")]`, which generates
+//! `from_json_env()` to load a struct from one JSON document in an env var,
+//! with individual env vars layered on top.
+
+#![allow(clippy::pedantic)]
+#![cfg(feature = "file")]
+
+use procenv::{EnvConfig, Error};
+use serial_test::serial;
+
+fn cleanup_env(vars: &[&str]) {
+    unsafe {
+        for k in vars {
+            std::env::remove_var(*k);
+        }
+    }
+}
+
+fn with_env<F, R>(vars: &[(&str, &str)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        for (k, v) in vars {
+            std::env::set_var(*k, *v);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (k, _) in vars {
+            std::env::remove_var(*k);
+        }
+    }
+
+    result
+}
+
+const VARS: &[&str] = &["JE_CONFIG", "JE_HOST", "JE_PORT", "JE_DEBUG", "JE_DB_URL"];
+
+#[derive(EnvConfig)]
+struct DatabaseConfig {
+    #[env(var = "DB_URL")]
+    url: String,
+
+    #[env(var = "DB_POOL", default = "5")]
+    pool: u32,
+}
+
+#[derive(EnvConfig)]
+#[env_config(from_json_var = "JE_CONFIG")]
+struct JsonEnvConfig {
+    #[env(var = "JE_HOST")]
+    host: String,
+
+    #[env(var = "JE_PORT", default = "8080")]
+    port: u16,
+
+    #[env(var = "JE_DEBUG", optional)]
+    debug: Option<bool>,
+
+    #[env(flatten, prefix = "JE_")]
+    database: DatabaseConfig,
+}
+
+#[test]
+#[serial]
+fn test_loads_struct_from_json_var() {
+    cleanup_env(VARS);
+    with_env(
+        &[(
+            "JE_CONFIG",
+            r#"{"host": "example.com", "port": 9000, "debug": true, "database": {"url": "postgres://db"}}"#,
+        )],
+        || {
+            let config = JsonEnvConfig::from_json_env().unwrap();
+            assert_eq!(config.host, "example.com");
+            assert_eq!(config.port, 9000);
+            assert_eq!(config.debug, Some(true));
+            assert_eq!(config.database.url, "postgres://db");
+            assert_eq!(config.database.pool, 5);
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_env_vars_override_json() {
+    cleanup_env(VARS);
+    with_env(
+        &[
+            (
+                "JE_CONFIG",
+                r#"{"host": "example.com", "port": 9000, "database": {"url": "postgres://db"}}"#,
+            ),
+            ("JE_PORT", "9100"),
+            ("JE_DB_URL", "postgres://override"),
+        ],
+        || {
+            let config = JsonEnvConfig::from_json_env().unwrap();
+            assert_eq!(config.host, "example.com");
+            assert_eq!(config.port, 9100);
+            assert_eq!(config.debug, None);
+            assert_eq!(config.database.url, "postgres://override");
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_defaults_fill_missing_keys() {
+    cleanup_env(VARS);
+    with_env(
+        &[(
+            "JE_CONFIG",
+            r#"{"host": "example.com", "database": {"url": "postgres://db"}}"#,
+        )],
+        || {
+            let config = JsonEnvConfig::from_json_env().unwrap();
+            assert_eq!(config.port, 8080);
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_missing_json_var_is_reported() {
+    cleanup_env(VARS);
+    let err = JsonEnvConfig::from_json_env().unwrap_err();
+    match err {
+        Error::Missing { var, .. } => assert_eq!(var, "JE_CONFIG"),
+        other => panic!("expected Missing, got {other:?}"),
+    }
+}
+
+#[test]
+#[serial]
+fn test_malformed_json_is_redacted() {
+    cleanup_env(VARS);
+    with_env(&[("JE_CONFIG", r#"{"host": "hunter2""#)], || {
+        let err = JsonEnvConfig::from_json_env().unwrap_err();
+        match &err {
+            Error::Parse { var, .. } => assert_eq!(var, "JE_CONFIG"),
+            other => panic!("expected Parse, got {other:?}"),
+        }
+        assert!(!err.to_string().contains("hunter2"));
+        assert!(!format!("{err:?}").contains("hunter2"));
+    });
+}
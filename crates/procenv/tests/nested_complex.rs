@@ -4,6 +4,7 @@
 
 #![allow(clippy::pedantic)]
 #![allow(clippy::manual_strip)]
+#![allow(clippy::approx_constant)]
 
 use procenv::EnvConfig;
 use serial_test::serial;
@@ -552,6 +553,75 @@ fn test_flatten_prefix_without_struct_prefix() {
     );
 }
 
+// ============================================================================
+// Flatten with Inherited Struct Prefix
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "SHOP_")]
+struct InheritPrefixConfig {
+    // No prefix: reads URL / TIMEOUT
+    #[env(flatten)]
+    plain: Endpoint,
+
+    // Own prefix: combined with the struct prefix, reads SHOP_OWN_URL
+    #[env(flatten, prefix = "OWN_")]
+    own: Endpoint,
+
+    // Inherited: struct prefix only, reads SHOP_URL
+    #[env(flatten, inherit_prefix)]
+    inherited: Endpoint,
+
+    // Inherited with own prefix: reads SHOP_BOTH_URL
+    #[env(flatten, prefix = "BOTH_", inherit_prefix)]
+    both: Endpoint,
+}
+
+#[test]
+#[serial]
+fn test_flatten_inherit_prefix() {
+    cleanup_env(&[
+        "URL",
+        "TIMEOUT",
+        "SHOP_OWN_URL",
+        "SHOP_URL",
+        "SHOP_TIMEOUT",
+        "SHOP_BOTH_URL",
+    ]);
+
+    with_env(
+        &[
+            ("URL", "https://plain.example.com"),
+            ("SHOP_OWN_URL", "https://own.example.com"),
+            ("SHOP_URL", "https://inherited.example.com"),
+            ("SHOP_TIMEOUT", "90"),
+            ("SHOP_BOTH_URL", "https://both.example.com"),
+        ],
+        || {
+            let config = InheritPrefixConfig::from_env().expect("should load");
+
+            assert_eq!(config.plain.url, "https://plain.example.com");
+            assert_eq!(config.plain.timeout, 30);
+            assert_eq!(config.own.url, "https://own.example.com");
+            assert_eq!(config.inherited.url, "https://inherited.example.com");
+            assert_eq!(config.inherited.timeout, 90);
+            assert_eq!(config.both.url, "https://both.example.com");
+        },
+    );
+}
+
+#[test]
+fn test_flatten_inherit_prefix_env_example() {
+    let example = InheritPrefixConfig::env_example();
+
+    // Defaulted vars are listed commented out, e.g. `# URL=http://localhost`
+    assert!(example.contains("# URL="), "plain flatten is unprefixed");
+    assert!(example.contains("# SHOP_OWN_URL="));
+    assert!(example.contains("# SHOP_URL="));
+    assert!(example.contains("# SHOP_TIMEOUT="));
+    assert!(example.contains("# SHOP_BOTH_URL="));
+}
+
 // ============================================================================
 // Complex 3-Level Deep Nesting with Prefix Propagation
 // ============================================================================
@@ -1202,3 +1272,281 @@ fn test_mixed_types_parse_error() {
         },
     );
 }
+
+// ============================================================================
+// Runtime Prefix (Multi-Tenant Loading)
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "SVC_")]
+struct TenantConfig {
+    #[env(var = "HOST")]
+    host: String,
+
+    #[env(var = "PORT", default = "8080")]
+    port: u16,
+
+    #[env(var = "REGION", no_prefix, default = "eu")]
+    region: String,
+}
+
+#[test]
+#[serial]
+fn test_from_env_with_prefix_per_tenant() {
+    cleanup_env(&[
+        "TENANT_A_SVC_HOST",
+        "TENANT_A_SVC_PORT",
+        "TENANT_B_SVC_HOST",
+        "TENANT_B_REGION",
+    ]);
+
+    with_env(
+        &[
+            ("TENANT_A_SVC_HOST", "a.internal"),
+            ("TENANT_A_SVC_PORT", "9001"),
+            ("TENANT_B_SVC_HOST", "b.internal"),
+            ("TENANT_B_REGION", "us"),
+        ],
+        || {
+            let a = TenantConfig::from_env_with_prefix("TENANT_A_").expect("tenant A loads");
+            assert_eq!(a.host, "a.internal");
+            assert_eq!(a.port, 9001);
+            assert_eq!(a.region, "eu");
+
+            // The runtime prefix also applies to `no_prefix` fields
+            let b = TenantConfig::from_env_with_prefix("TENANT_B_").expect("tenant B loads");
+            assert_eq!(b.host, "b.internal");
+            assert_eq!(b.port, 8080);
+            assert_eq!(b.region, "us");
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_from_env_with_prefix_reports_prefixed_var() {
+    cleanup_env(&["TENANT_C_SVC_HOST"]);
+
+    let err = TenantConfig::from_env_with_prefix("TENANT_C_").unwrap_err();
+    assert!(
+        err.to_string().contains("TENANT_C_SVC_HOST"),
+        "error should name the prefixed var: {err}"
+    );
+}
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "NS_", prefix_env = "NS_TEST_NAMESPACE")]
+struct NamespacedConfig {
+    #[env(var = "HOST")]
+    host: String,
+
+    #[env(var = "PORT", default = "8080")]
+    port: u16,
+}
+
+#[test]
+#[serial]
+fn test_prefix_env_selects_namespace_at_load_time() {
+    cleanup_env(&["NS_TEST_NAMESPACE", "BLUE_NS_PORT"]);
+
+    with_env(
+        &[
+            ("NS_HOST", "default.internal"),
+            ("BLUE_NS_HOST", "blue.internal"),
+        ],
+        || {
+            // Unset: only the compiled prefix applies
+            let config = NamespacedConfig::from_env().expect("compiled prefix loads");
+            assert_eq!(config.host, "default.internal");
+
+            with_env(&[("NS_TEST_NAMESPACE", "BLUE_")], || {
+                let config = NamespacedConfig::from_env().expect("namespace loads");
+                assert_eq!(config.host, "blue.internal");
+                assert_eq!(config.port, 8080);
+            });
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_prefix_env_sources_name_runtime_vars() {
+    cleanup_env(&["NS_HOST", "GREEN_NS_PORT"]);
+
+    with_env(
+        &[
+            ("NS_TEST_NAMESPACE", "GREEN_"),
+            ("GREEN_NS_HOST", "green.internal"),
+        ],
+        || {
+            let (config, sources) =
+                NamespacedConfig::from_env_with_sources().expect("namespace loads");
+            assert_eq!(config.host, "green.internal");
+
+            let host = sources.get("host").expect("host is tracked");
+            assert_eq!(host.var_name, "GREEN_NS_HOST");
+            assert_eq!(host.source, procenv::Source::Environment);
+            assert_eq!(sources.get("port").unwrap().var_name, "GREEN_NS_PORT");
+        },
+    );
+}
+
+#[derive(EnvConfig)]
+#[env_config(prefix_env = "NS_FAST_NAMESPACE", no_accumulate)]
+struct FailFastNamespacedConfig {
+    #[env(var = "NS_FAST_HOST")]
+    host: String,
+
+    #[env(var = "NS_FAST_PORT")]
+    port: u16,
+}
+
+#[test]
+#[serial]
+fn test_prefix_env_no_accumulate_returns_first_error() {
+    cleanup_env(&["RED_NS_FAST_HOST"]);
+
+    with_env(
+        &[("NS_FAST_NAMESPACE", "RED_"), ("RED_NS_FAST_PORT", "nope")],
+        || {
+            match FailFastNamespacedConfig::from_env().unwrap_err() {
+                procenv::Error::Missing { var, .. } => assert_eq!(var, "RED_NS_FAST_HOST"),
+                other => panic!("Expected Missing error, got: {other:?}"),
+            }
+
+            // The sources path still reports everything
+            match FailFastNamespacedConfig::from_env_with_sources().unwrap_err() {
+                procenv::Error::Multiple { errors } => assert_eq!(errors.len(), 2),
+                other => panic!("Expected Multiple error, got: {other:?}"),
+            }
+        },
+    );
+}
+
+// ============================================================================
+// Empty Optional Subsystems (allow_empty_struct)
+// ============================================================================
+
+#[derive(EnvConfig, Default, PartialEq)]
+#[env_config(allow_empty_struct)]
+struct PluginConfig {
+    #[env(var = "PLUGIN_URL")]
+    url: String,
+
+    #[env(var = "PLUGIN_TOKEN")]
+    token: String,
+
+    #[env(var = "PLUGIN_RETRIES", default = "3")]
+    retries: u32,
+}
+
+#[derive(EnvConfig)]
+struct HostWithPlugins {
+    #[env(var = "HOST_NAME", default = "host")]
+    name: String,
+
+    #[env(flatten)]
+    plugin: PluginConfig,
+
+    #[env(flatten, prefix = "AUDIT_")]
+    audit: PluginConfig,
+}
+
+const PLUGIN_VARS: &[&str] = &[
+    "HOST_NAME",
+    "PLUGIN_URL",
+    "PLUGIN_TOKEN",
+    "PLUGIN_RETRIES",
+    "AUDIT_PLUGIN_URL",
+    "AUDIT_PLUGIN_TOKEN",
+    "AUDIT_PLUGIN_RETRIES",
+];
+
+#[derive(EnvConfig, Default, PartialEq)]
+#[env_config(allow_empty_struct, no_accumulate)]
+struct FailFastPluginConfig {
+    #[env(var = "PLUGIN_FF_URL")]
+    url: String,
+
+    #[env(var = "PLUGIN_FF_TOKEN")]
+    token: String,
+}
+
+#[test]
+#[serial]
+fn test_allow_empty_struct_absent_subsystem_uses_default() {
+    cleanup_env(PLUGIN_VARS);
+
+    let config = HostWithPlugins::from_env().expect("absent plugins fall back to Default");
+
+    assert_eq!(config.name, "host");
+    assert_eq!(config.plugin, PluginConfig::default());
+    assert_eq!(config.audit, PluginConfig::default());
+    // `Default` wins over attribute defaults when nothing is set
+    assert_eq!(config.plugin.retries, 0);
+}
+
+#[test]
+#[serial]
+fn test_allow_empty_struct_present_subsystem_loads() {
+    cleanup_env(PLUGIN_VARS);
+
+    with_env(
+        &[
+            ("AUDIT_PLUGIN_URL", "https://audit.internal"),
+            ("AUDIT_PLUGIN_TOKEN", "t0k3n"),
+        ],
+        || {
+            let config = HostWithPlugins::from_env().expect("configured plugin loads");
+
+            assert_eq!(config.plugin, PluginConfig::default());
+            assert_eq!(config.audit.url, "https://audit.internal");
+            assert_eq!(config.audit.token, "t0k3n");
+            assert_eq!(config.audit.retries, 3);
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_allow_empty_struct_partial_subsystem_errors() {
+    cleanup_env(PLUGIN_VARS);
+
+    // Setting any one var means the subsystem is present, so the rest are required
+    with_env(&[("PLUGIN_RETRIES", "5")], || {
+        let err = HostWithPlugins::from_env().expect_err("partially configured plugin should fail");
+        let msg = format!("{err:?}");
+        assert!(msg.contains("PLUGIN_URL"), "{msg}");
+        assert!(msg.contains("PLUGIN_TOKEN"), "{msg}");
+    });
+}
+
+#[test]
+#[serial]
+fn test_allow_empty_struct_direct_load_and_sources() {
+    cleanup_env(PLUGIN_VARS);
+
+    assert_eq!(PluginConfig::from_env().unwrap(), PluginConfig::default());
+
+    let (config, sources) = PluginConfig::from_env_with_sources().expect("empty loads");
+    assert_eq!(config, PluginConfig::default());
+    assert!(sources.get("url").is_none());
+}
+
+#[test]
+#[serial]
+fn test_allow_empty_struct_no_accumulate() {
+    cleanup_env(&["PLUGIN_FF_URL", "PLUGIN_FF_TOKEN"]);
+
+    let config = FailFastPluginConfig::from_env().expect("absent plugin falls back to Default");
+    assert_eq!(config, FailFastPluginConfig::default());
+
+    // Once present, loading stops at the first missing var
+    with_env(
+        &[("PLUGIN_FF_TOKEN", "t0k3n")],
+        || match FailFastPluginConfig::from_env().unwrap_err() {
+            procenv::Error::Missing { var, .. } => assert_eq!(var, "PLUGIN_FF_URL"),
+            other => panic!("Expected Missing error, got: {other:?}"),
+        },
+    );
+}
@@ -0,0 +1,147 @@
+//! Prefix separator tests.
+//!
+//! Tests for `#[env_config(env_prefix_separator = "_")]`, which joins
+//! prefixes and var names with exactly one separator.
+
+#![allow(clippy::pedantic)]
+
+use procenv::EnvConfig;
+use serial_test::serial;
+
+fn cleanup_env(vars: &[&str]) {
+    unsafe {
+        for k in vars {
+            std::env::remove_var(*k);
+        }
+    }
+}
+
+fn with_env<F, R>(vars: &[(&str, &str)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        for (k, v) in vars {
+            std::env::set_var(*k, *v);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (k, _) in vars {
+            std::env::remove_var(*k);
+        }
+    }
+
+    result
+}
+
+const VARS: &[&str] = &[
+    "SEPAPP_PORT",
+    "SEPAPP_HOST",
+    "SEPAPP_DB_URL",
+    "BLUE_SEPAPP_PORT",
+    "BLUE_SEPAPP_HOST",
+    "BLUE_SEPAPP_DB_URL",
+    "SEPAPPDB_URL",
+];
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "SEPAPP", env_prefix_separator = "_")]
+struct SeparatedConfig {
+    #[env(var = "PORT", default = "8080")]
+    port: u16,
+
+    // A leading separator on the name is not doubled either
+    #[env(var = "_HOST")]
+    host: String,
+
+    #[env(flatten, prefix = "DB")]
+    db: DbConfig,
+}
+
+#[derive(EnvConfig)]
+#[env_config(env_prefix_separator = "_")]
+struct DbConfig {
+    #[env(var = "URL", default = "postgres://localhost")]
+    url: String,
+}
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "SEPAPP_", env_prefix_separator = "_")]
+struct TrailingConfig {
+    #[env(var = "PORT", default = "8080")]
+    port: u16,
+}
+
+#[test]
+#[serial]
+fn test_struct_prefix_is_separated() {
+    cleanup_env(VARS);
+
+    with_env(
+        &[("SEPAPP_HOST", "a.local"), ("SEPAPP_PORT", "9000")],
+        || {
+            let config = SeparatedConfig::from_env().unwrap();
+            assert_eq!(config.host, "a.local");
+            assert_eq!(config.port, 9000);
+
+            // A prefix already ending in the separator is not doubled
+            assert_eq!(TrailingConfig::from_env().unwrap().port, 9000);
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_runtime_and_flatten_prefixes_are_separated() {
+    cleanup_env(VARS);
+
+    with_env(
+        &[
+            ("BLUE_SEPAPP_HOST", "blue.local"),
+            ("BLUE_SEPAPP_PORT", "7000"),
+            ("BLUE_SEPAPP_DB_URL", "postgres://blue"),
+        ],
+        || {
+            let config = SeparatedConfig::from_env_with_prefix("BLUE")
+                .expect("runtime prefix joins with a separator");
+            assert_eq!(config.host, "blue.local");
+            assert_eq!(config.port, 7000);
+            assert_eq!(config.db.url, "postgres://blue");
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_flatten_prefix_is_separated() {
+    cleanup_env(VARS);
+
+    with_env(
+        &[
+            ("SEPAPP_HOST", "a.local"),
+            ("SEPAPPDB_URL", "unseparated"),
+            ("SEPAPP_DB_URL", "postgres://db"),
+        ],
+        || {
+            // The struct prefix and the flatten prefix are joined too
+            let config = SeparatedConfig::from_env().unwrap();
+            assert_eq!(config.db.url, "postgres://db");
+        },
+    );
+}
+
+#[test]
+fn test_env_example_uses_separated_names() {
+    let example = SeparatedConfig::env_example();
+
+    assert!(example.contains("SEPAPP_HOST="), "{example}");
+    assert!(example.contains("# SEPAPP_PORT=8080"), "{example}");
+    assert!(
+        example.contains("# SEPAPP_DB_URL=postgres://localhost"),
+        "{example}"
+    );
+    assert!(!example.contains("SEPAPPPORT"), "{example}");
+}
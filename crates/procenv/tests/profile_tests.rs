@@ -299,3 +299,285 @@ fn test_profile_with_prefix_env_override() {
         },
     );
 }
+
+// ============================================================================
+// Strict Profiles
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[env_config(
+    profile_env = "STRICT_ENV",
+    profiles = ["dev", "prod"],
+    strict_profiles
+)]
+struct StrictProfileConfig {
+    #[env(var = "STRICT_LOG_LEVEL", default = "info")]
+    #[profile(dev = "debug", prod = "warn")]
+    log_level: String,
+
+    #[env(var = "STRICT_PORT", default = "8080")]
+    port: u16,
+}
+
+#[test]
+#[serial]
+fn test_strict_profiles_complete_matrix() {
+    cleanup_env(&["STRICT_ENV", "STRICT_LOG_LEVEL", "STRICT_PORT"]);
+
+    with_env(&[("STRICT_ENV", "prod")], || {
+        let config = StrictProfileConfig::from_env().expect("should load prod profile");
+        assert_eq!(config.log_level, "warn");
+        assert_eq!(config.port, 8080);
+    });
+}
+
+// ============================================================================
+// Profile Defaults in from_env
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[env_config(profile_env = "APP_ENV", profiles = ["dev", "prod"])]
+struct AppEnvProfileConfig {
+    #[env(var = "APPENV_DB_URL")]
+    #[profile(dev = "postgres://localhost/dev", prod = "postgres://prod/app")]
+    database_url: String,
+
+    #[env(var = "APPENV_LOG", default = "info")]
+    #[profile(dev = "debug")]
+    log_level: String,
+
+    #[env(var = "APPENV_CACHE", optional)]
+    #[profile(dev = "64")]
+    cache_mb: Option<u32>,
+}
+
+const APP_ENV_VARS: &[&str] = &["APP_ENV", "APPENV_DB_URL", "APPENV_LOG", "APPENV_CACHE"];
+
+#[test]
+#[serial]
+fn test_from_env_uses_dev_profile_defaults() {
+    cleanup_env(APP_ENV_VARS);
+
+    with_env(&[("APP_ENV", "dev")], || {
+        let config = AppEnvProfileConfig::from_env().expect("dev profile fills missing vars");
+
+        assert_eq!(config.database_url, "postgres://localhost/dev");
+        // Profile values win over `default` and fill optional fields
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.cache_mb, Some(64));
+    });
+}
+
+#[test]
+#[serial]
+fn test_from_env_profile_sources() {
+    cleanup_env(APP_ENV_VARS);
+
+    with_env(&[("APP_ENV", "dev"), ("APPENV_LOG", "warn")], || {
+        let (config, sources) = AppEnvProfileConfig::from_env_with_sources().unwrap();
+        assert_eq!(config.log_level, "warn");
+
+        let db_source = sources.get("database_url").unwrap();
+        assert!(matches!(db_source.source, Source::Profile(ref p) if p == "dev"));
+
+        let cache_source = sources.get("cache_mb").unwrap();
+        assert!(matches!(cache_source.source, Source::Profile(ref p) if p == "dev"));
+
+        let log_source = sources.get("log_level").unwrap();
+        assert!(matches!(log_source.source, Source::Environment));
+    });
+}
+
+#[test]
+#[serial]
+fn test_from_env_without_profile_uses_defaults() {
+    cleanup_env(APP_ENV_VARS);
+
+    with_env(&[("APP_ENV", "prod")], || {
+        let config = AppEnvProfileConfig::from_env().unwrap();
+
+        assert_eq!(config.database_url, "postgres://prod/app");
+        assert_eq!(config.log_level, "info");
+        assert_eq!(config.cache_mb, None);
+    });
+
+    // No profile, so the required field has nothing to fall back on
+    assert!(AppEnvProfileConfig::from_env().is_err());
+}
+
+// ============================================================================
+// Default Profile
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[env_config(profile_env = "DEFPROF_ENV", profiles = ["dev", "prod"], default_profile = "dev")]
+struct DefaultProfileConfig {
+    #[env(var = "DEFPROF_LOG", default = "info")]
+    #[profile(dev = "debug", prod = "warn")]
+    log_level: String,
+}
+
+#[test]
+#[serial]
+fn test_default_profile_applies_when_unset() {
+    cleanup_env(&["DEFPROF_ENV", "DEFPROF_LOG"]);
+
+    let (config, sources) = DefaultProfileConfig::from_env_with_sources().unwrap();
+    assert_eq!(config.log_level, "debug");
+
+    let log_source = sources.get("log_level").unwrap();
+    assert!(matches!(log_source.source, Source::Profile(ref p) if p == "dev"));
+
+    with_env(&[("DEFPROF_ENV", "prod")], || {
+        let config = DefaultProfileConfig::from_env().unwrap();
+        assert_eq!(config.log_level, "warn");
+    });
+}
+
+// ============================================================================
+// Active Profile Access
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_active_profile_reflects_default_profile() {
+    cleanup_env(&["DEFPROF_ENV", "DEFPROF_LOG"]);
+
+    assert_eq!(
+        DefaultProfileConfig::active_profile().as_deref(),
+        Some("dev")
+    );
+    let (_, sources) = DefaultProfileConfig::from_env_with_sources().unwrap();
+    assert_eq!(sources.profile(), Some("dev"));
+
+    with_env(&[("DEFPROF_ENV", "prod")], || {
+        assert_eq!(
+            DefaultProfileConfig::active_profile().as_deref(),
+            Some("prod")
+        );
+        let (_, sources) = DefaultProfileConfig::from_env_with_sources().unwrap();
+        assert_eq!(sources.profile(), Some("prod"));
+    });
+}
+
+#[test]
+#[serial]
+fn test_active_profile_none_without_default() {
+    cleanup_env(&["APP_ENV"]);
+
+    assert_eq!(AppEnvProfileConfig::active_profile(), None);
+
+    with_env(&[("APP_ENV", "dev")], || {
+        assert_eq!(
+            AppEnvProfileConfig::active_profile().as_deref(),
+            Some("dev")
+        );
+        let (_, sources) = AppEnvProfileConfig::from_env_with_sources().unwrap();
+        assert_eq!(sources.profile(), Some("dev"));
+    });
+}
+
+// ============================================================================
+// Profile-Required Fields
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[env_config(profile_env = "WHENPROF_ENV", profiles = ["dev", "staging", "prod"])]
+struct WhenProfileConfig {
+    #[env(var = "WHENPROF_TLS_CERT", when_profile = "prod")]
+    tls_cert: Option<String>,
+
+    #[env(var = "WHENPROF_REPLICAS", when_profile = ["staging", "prod"])]
+    replicas: Option<u32>,
+}
+
+#[test]
+#[serial]
+fn test_when_profile_optional_outside_listed_profiles() {
+    cleanup_env(&["WHENPROF_ENV", "WHENPROF_TLS_CERT", "WHENPROF_REPLICAS"]);
+
+    let config = WhenProfileConfig::from_env().unwrap();
+    assert_eq!(config.tls_cert, None);
+
+    with_env(&[("WHENPROF_ENV", "dev")], || {
+        let config = WhenProfileConfig::from_env().unwrap();
+        assert_eq!(config.tls_cert, None);
+        assert_eq!(config.replicas, None);
+    });
+}
+
+#[test]
+#[serial]
+fn test_when_profile_required_in_listed_profiles() {
+    cleanup_env(&["WHENPROF_TLS_CERT", "WHENPROF_REPLICAS"]);
+
+    with_env(&[("WHENPROF_ENV", "staging")], || {
+        let err = WhenProfileConfig::from_env().unwrap_err();
+        assert_eq!(err.missing_vars(), ["WHENPROF_REPLICAS"]);
+    });
+
+    with_env(&[("WHENPROF_ENV", "prod")], || {
+        let err = WhenProfileConfig::from_env().unwrap_err();
+        assert_eq!(
+            err.missing_vars(),
+            ["WHENPROF_TLS_CERT", "WHENPROF_REPLICAS"]
+        );
+    });
+
+    with_env(
+        &[
+            ("WHENPROF_ENV", "prod"),
+            ("WHENPROF_TLS_CERT", "/etc/tls/cert.pem"),
+            ("WHENPROF_REPLICAS", "3"),
+        ],
+        || {
+            let config = WhenProfileConfig::from_env().unwrap();
+            assert_eq!(config.tls_cert.as_deref(), Some("/etc/tls/cert.pem"));
+            assert_eq!(config.replicas, Some(3));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_when_profile_missing_error_names_profile() {
+    cleanup_env(&["WHENPROF_TLS_CERT"]);
+
+    with_env(
+        &[("WHENPROF_ENV", "prod"), ("WHENPROF_REPLICAS", "3")],
+        || {
+            let err = WhenProfileConfig::from_env().unwrap_err();
+            assert!(
+                matches!(err, procenv::Error::Missing { ref help, .. } if help.contains("`prod` profile"))
+            );
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_when_profile_parse_error_not_reported_missing() {
+    cleanup_env(&["WHENPROF_TLS_CERT"]);
+
+    with_env(
+        &[("WHENPROF_ENV", "staging"), ("WHENPROF_REPLICAS", "many")],
+        || {
+            let err = WhenProfileConfig::from_env().unwrap_err();
+            assert!(matches!(err, procenv::Error::Parse { .. }));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_when_profile_with_external_prefix() {
+    cleanup_env(&["EDGE_WHENPROF_TLS_CERT", "EDGE_WHENPROF_REPLICAS"]);
+
+    with_env(
+        &[("WHENPROF_ENV", "prod"), ("EDGE_WHENPROF_REPLICAS", "2")],
+        || {
+            let err = WhenProfileConfig::from_env_with_prefix("EDGE_").unwrap_err();
+            assert_eq!(err.missing_vars(), ["EDGE_WHENPROF_TLS_CERT"]);
+        },
+    );
+}
@@ -3,9 +3,11 @@
 #![allow(clippy::pedantic)]
 #![allow(clippy::match_wildcard_for_single_variants)] // Test uses wildcard for clarity
 
-use procenv::provider::{Provider, ProviderResult, ProviderSource, ProviderValue};
+use procenv::provider::{Provider, ProviderError, ProviderResult, ProviderSource, ProviderValue};
 use procenv::{ConfigLoader, Source};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 // ============================================================================
 // Test Providers
@@ -16,6 +18,9 @@ struct MemoryProvider {
     name: String,
     values: HashMap<String, String>,
     priority: u32,
+    authoritative: bool,
+    secrets: Vec<String>,
+    failures: Vec<String>,
 }
 
 impl MemoryProvider {
@@ -24,9 +29,17 @@ impl MemoryProvider {
             name: name.to_string(),
             values: HashMap::new(),
             priority: 100,
+            authoritative: false,
+            secrets: Vec::new(),
+            failures: Vec::new(),
         }
     }
 
+    fn authoritative(mut self) -> Self {
+        self.authoritative = true;
+        self
+    }
+
     fn with_value(mut self, key: &str, value: &str) -> Self {
         self.values.insert(key.to_string(), value.to_string());
         self
@@ -36,6 +49,16 @@ impl MemoryProvider {
         self.priority = priority;
         self
     }
+
+    fn with_secret(mut self, key: &str, value: &str) -> Self {
+        self.secrets.push(key.to_string());
+        self.with_value(key, value)
+    }
+
+    fn with_failure(mut self, key: &str) -> Self {
+        self.failures.push(key.to_string());
+        self
+    }
 }
 
 impl Provider for MemoryProvider {
@@ -44,11 +67,19 @@ impl Provider for MemoryProvider {
     }
 
     fn get(&self, key: &str) -> ProviderResult<ProviderValue> {
+        if self.failures.iter().any(|f| f == key) {
+            return Err(ProviderError::InvalidValue {
+                key: key.to_string(),
+                provider: self.name.clone(),
+                message: "not valid UTF-8".to_string(),
+            });
+        }
+
         match self.values.get(key) {
             Some(v) => Ok(Some(ProviderValue {
                 value: v.clone(),
                 source: ProviderSource::custom(&self.name, None),
-                secret: false,
+                secret: self.secrets.iter().any(|s| s == key),
             })),
             None => Ok(None),
         }
@@ -57,6 +88,86 @@ impl Provider for MemoryProvider {
     fn priority(&self) -> u32 {
         self.priority
     }
+
+    fn authoritative(&self) -> bool {
+        self.authoritative
+    }
+}
+
+/// A provider that counts batched lookups, for testing `get_many`.
+struct BatchProvider {
+    inner: MemoryProvider,
+    calls: Arc<AtomicUsize>,
+    fallthrough: bool,
+}
+
+impl BatchProvider {
+    fn new(inner: MemoryProvider, calls: &Arc<AtomicUsize>) -> Self {
+        Self {
+            inner,
+            calls: Arc::clone(calls),
+            fallthrough: true,
+        }
+    }
+
+    fn terminal(mut self) -> Self {
+        self.fallthrough = false;
+        self
+    }
+}
+
+impl Provider for BatchProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn get(&self, key: &str) -> ProviderResult<ProviderValue> {
+        self.inner.get(key)
+    }
+
+    fn get_many(&self, keys: &[&str]) -> HashMap<String, ProviderResult<ProviderValue>> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        keys.iter()
+            .map(|k| ((*k).to_string(), self.inner.get(k)))
+            .collect()
+    }
+
+    fn priority(&self) -> u32 {
+        self.inner.priority()
+    }
+
+    fn fallthrough(&self) -> bool {
+        self.fallthrough
+    }
+}
+
+/// A remote-style provider that is down, for testing graceful degradation.
+struct DownProvider {
+    /// Report the outage from `is_available()` instead of from `get`.
+    unavailable: bool,
+    required: bool,
+}
+
+impl Provider for DownProvider {
+    fn name(&self) -> &str {
+        "vault"
+    }
+
+    fn get(&self, _key: &str) -> ProviderResult<ProviderValue> {
+        Err(ProviderError::connection("vault", "connection refused"))
+    }
+
+    fn is_available(&self) -> bool {
+        !self.unavailable
+    }
+
+    fn priority(&self) -> u32 {
+        10
+    }
+
+    fn required(&self) -> bool {
+        self.required
+    }
 }
 
 // ============================================================================
@@ -84,7 +195,7 @@ fn test_memory_provider_source() {
     let result = provider.get("key").unwrap().unwrap();
 
     match result.source {
-        ProviderSource::Custom { provider, path } => {
+        ProviderSource::Custom { provider, path, .. } => {
             assert_eq!(provider, "my-provider");
             assert!(path.is_none());
         }
@@ -136,6 +247,28 @@ fn test_loader_priority_order() {
     assert_eq!(value.value, "high-value");
 }
 
+#[test]
+fn test_loader_priority_tie_uses_registration_order() {
+    let first = MemoryProvider::new("first")
+        .with_priority(50)
+        .with_value("KEY", "first-value");
+    let second = MemoryProvider::new("second")
+        .with_priority(50)
+        .with_value("KEY", "second-value");
+    let top = MemoryProvider::new("top").with_priority(10);
+
+    let mut loader = ConfigLoader::new()
+        .with_provider(Box::new(first))
+        .with_provider(Box::new(second))
+        .with_provider(Box::new(top));
+
+    let names: Vec<&str> = loader.providers().iter().map(|p| p.name()).collect();
+    assert_eq!(names, ["top", "first", "second"]);
+
+    let value = loader.get("KEY").unwrap();
+    assert_eq!(value.value, "first-value");
+}
+
 #[test]
 fn test_loader_fallthrough() {
     // First provider doesn't have the key, second does
@@ -160,6 +293,73 @@ fn test_loader_fallthrough() {
     assert_eq!(v2.value, "from-second");
 }
 
+#[test]
+fn test_loader_empty_value_wins() {
+    let blank = MemoryProvider::new("blank")
+        .with_priority(10)
+        .with_value("KEY", "");
+    let fallback = MemoryProvider::new("fallback")
+        .with_priority(20)
+        .with_value("KEY", "from-fallback");
+
+    let mut loader = ConfigLoader::new()
+        .with_provider(Box::new(blank))
+        .with_provider(Box::new(fallback));
+
+    // A blank value is explicit: no fallback
+    assert_eq!(loader.get_str("KEY").as_deref(), Some(""));
+    assert_eq!(
+        loader.sources().get("KEY").unwrap().source,
+        Source::CustomProvider("blank".to_string())
+    );
+
+    let found = loader.get_many(&["KEY"]);
+    assert_eq!(found["KEY"].value, "");
+}
+
+#[test]
+fn test_loader_blank_value_blocks_default() {
+    let blank = MemoryProvider::new("blank").with_value("KEY", "");
+    let mut loader = ConfigLoader::new().with_provider(Box::new(blank));
+
+    let value = loader.get_with_default("KEY", "KEY", "compiled");
+    assert_eq!(value.value, "");
+    assert_eq!(loader.get_many(&["KEY"])["KEY"].value, "");
+}
+
+#[test]
+fn test_loader_authoritative_error_ends_chain() {
+    let build = |authoritative: bool| {
+        let mut failing = MemoryProvider::new("failing")
+            .with_priority(10)
+            .with_failure("KEY");
+        if authoritative {
+            failing = failing.authoritative();
+        }
+        let fallback = MemoryProvider::new("fallback")
+            .with_priority(20)
+            .with_value("KEY", "from-fallback");
+
+        ConfigLoader::new()
+            .with_provider(Box::new(failing))
+            .with_provider(Box::new(fallback))
+    };
+
+    // A regular provider's error is recorded and the next one answers
+    let mut loader = build(false);
+    assert_eq!(loader.get_str("KEY").as_deref(), Some("from-fallback"));
+    assert_eq!(loader.take_errors().len(), 1);
+
+    // An authoritative provider's error is final
+    let mut loader = build(true);
+    assert!(loader.get("KEY").is_none());
+    assert_eq!(loader.take_errors().len(), 1);
+
+    let mut loader = build(true);
+    assert!(loader.get_many(&["KEY"]).is_empty());
+    assert_eq!(loader.take_errors().len(), 1);
+}
+
 #[test]
 fn test_loader_caching() {
     let provider = MemoryProvider::new("test").with_value("KEY", "value");
@@ -240,6 +440,243 @@ fn test_loader_finish_with_errors() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_loader_get_many_batches_per_provider() {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let high = MemoryProvider::new("high")
+        .with_priority(10)
+        .with_value("A", "high-a");
+    let low = MemoryProvider::new("low")
+        .with_priority(20)
+        .with_value("A", "low-a")
+        .with_value("B", "low-b");
+
+    let mut loader = ConfigLoader::new()
+        .with_provider(Box::new(BatchProvider::new(low, &calls)))
+        .with_provider(Box::new(BatchProvider::new(high, &calls)));
+
+    let values = loader.get_many(&["A", "B", "C"]);
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(values["A"].value, "high-a");
+    assert_eq!(values["B"].value, "low-b");
+    assert!(!values.contains_key("C"));
+
+    assert_eq!(
+        loader.sources().get("B").unwrap().source,
+        Source::CustomProvider("low".to_string())
+    );
+
+    // Resolved keys are cached, so a second batch skips the providers
+    let values = loader.get_many(&["A", "B"]);
+    assert_eq!(values.len(), 2);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_loader_get_many_matches_get() {
+    let build = || {
+        let terminal = MemoryProvider::new("terminal")
+            .with_priority(10)
+            .with_value("A", "terminal-a");
+        let rest = MemoryProvider::new("rest")
+            .with_priority(20)
+            .with_value("A", "rest-a")
+            .with_value("B", "rest-b");
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        ConfigLoader::new()
+            .with_provider(Box::new(BatchProvider::new(rest, &calls)))
+            .with_provider(Box::new(BatchProvider::new(terminal, &calls).terminal()))
+    };
+
+    let mut batched = build();
+    let values = batched.get_many(&["A", "B"]);
+
+    let mut single = build();
+    for key in ["A", "B"] {
+        assert_eq!(
+            values.get(key).map(|v| v.value.clone()),
+            single.get(key).map(|v| v.value)
+        );
+    }
+
+    // The terminal provider stops the search for keys it lacks
+    assert_eq!(values["A"].value, "terminal-a");
+    assert!(!values.contains_key("B"));
+}
+
+#[test]
+fn test_loader_skips_optional_provider_when_down() {
+    for unavailable in [false, true] {
+        let fallback = MemoryProvider::new("fallback")
+            .with_priority(20)
+            .with_value("KEY", "from-fallback");
+
+        let mut loader = ConfigLoader::new()
+            .with_provider(Box::new(DownProvider {
+                unavailable,
+                required: false,
+            }))
+            .with_provider(Box::new(fallback));
+
+        assert_eq!(loader.get_str("KEY").as_deref(), Some("from-fallback"));
+        assert_eq!(loader.get_many(&["OTHER"]).len(), 0);
+        assert!(!loader.has_errors());
+
+        // Listed once, however many lookups skipped it
+        let sources = loader.finish().expect("optional outage should not fail");
+        assert_eq!(sources.skipped_providers(), ["vault"]);
+        assert!(
+            sources
+                .to_string()
+                .contains("skipped unavailable provider: vault")
+        );
+    }
+}
+
+#[test]
+fn test_loader_required_provider_down_aborts() {
+    for unavailable in [false, true] {
+        let build = || {
+            let fallback = MemoryProvider::new("fallback")
+                .with_priority(20)
+                .with_value("KEY", "from-fallback");
+
+            ConfigLoader::new()
+                .with_provider(Box::new(DownProvider {
+                    unavailable,
+                    required: true,
+                }))
+                .with_provider(Box::new(fallback))
+        };
+
+        // Lower-priority providers are not consulted
+        let mut loader = build();
+        assert!(loader.get("KEY").is_none());
+        let errors = loader.take_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("vault"), "{}", errors[0]);
+        assert!(loader.sources().skipped_providers().is_empty());
+
+        // One outage is one error, however many keys were pending
+        let mut loader = build();
+        assert!(loader.get_many(&["KEY", "OTHER"]).is_empty());
+        assert_eq!(loader.take_errors().len(), 1);
+
+        let mut loader = build();
+        assert!(loader.get_many(&["KEY"]).is_empty());
+        assert!(loader.finish().is_err());
+    }
+}
+
+#[test]
+fn test_loader_snapshot_records_winning_provider() {
+    let high = MemoryProvider::new("high")
+        .with_priority(10)
+        .with_value("A", "high-a");
+    let low = MemoryProvider::new("low")
+        .with_priority(20)
+        .with_value("A", "low-a")
+        .with_value("B", "low-b");
+
+    let mut loader = ConfigLoader::new()
+        .with_provider(Box::new(low))
+        .with_provider(Box::new(high));
+
+    let snapshot = loader.snapshot(&["A", "B", "C"]);
+
+    assert_eq!(snapshot.len(), 2);
+    let (value, source) = &snapshot["A"];
+    assert_eq!(value.value, "high-a");
+    assert_eq!(*source, ProviderSource::custom("high", None));
+    assert_eq!(snapshot["B"].1, ProviderSource::custom("low", None));
+    assert!(!snapshot.contains_key("C"));
+
+    // Resolved the same way as get(), so sources are recorded too
+    assert_eq!(
+        loader.sources().get("B").unwrap().source,
+        Source::CustomProvider("low".to_string())
+    );
+}
+
+#[test]
+fn test_loader_snapshot_redacted_hides_secrets() {
+    let vault = MemoryProvider::new("vault")
+        .with_secret("PASSWORD", "hunter2")
+        .with_value("USER", "admin");
+
+    let mut loader = ConfigLoader::new().with_provider(Box::new(vault));
+
+    let snapshot = loader.snapshot(&["PASSWORD"]);
+    assert!(snapshot["PASSWORD"].0.secret);
+    assert_eq!(snapshot["PASSWORD"].0.value, "hunter2");
+
+    let redacted = loader.snapshot_redacted(&["PASSWORD", "USER"]);
+    assert_eq!(redacted["PASSWORD"].0.value, "<redacted>");
+    assert!(redacted["PASSWORD"].0.secret);
+    assert_eq!(
+        redacted["PASSWORD"].1,
+        ProviderSource::custom("vault", None)
+    );
+    assert_eq!(redacted["USER"].0.value, "admin");
+}
+
+// ============================================================================
+// LoaderBuilder Tests
+// ============================================================================
+
+#[test]
+fn test_builder_sorts_by_priority_and_dedups() {
+    let vault = MemoryProvider::new("vault")
+        .with_priority(5)
+        .with_value("KEY", "vault-value");
+    let duplicate = MemoryProvider::new("vault").with_value("KEY", "duplicate-value");
+
+    let mut loader = ConfigLoader::builder()
+        .env()
+        .provider(Box::new(vault))
+        .env()
+        .provider(Box::new(duplicate))
+        .build()
+        .unwrap();
+
+    let names: Vec<&str> = loader.providers().iter().map(|p| p.name()).collect();
+    assert_eq!(names, ["vault", "environment"]);
+    assert_eq!(loader.get_str("KEY").as_deref(), Some("vault-value"));
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn test_builder_files() {
+    use std::io::Write;
+
+    let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+    file.write_all(br#"{"port": 8080}"#).unwrap();
+
+    let mut loader = ConfigLoader::builder()
+        .file(file.path())
+        .file(file.path())
+        .file_optional("/nonexistent/procenv/optional.toml")
+        .env()
+        .build()
+        .unwrap();
+
+    let names: Vec<&str> = loader.providers().iter().map(|p| p.name()).collect();
+    assert_eq!(names, ["environment", "file"]);
+    assert_eq!(loader.get_str("port").as_deref(), Some("8080"));
+
+    // Every failing file is reported
+    let err = ConfigLoader::builder()
+        .file("/nonexistent/procenv/a.toml")
+        .file("/nonexistent/procenv/b.toml")
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(err, procenv::Error::Multiple { ref errors } if errors.len() == 2));
+}
+
 // ============================================================================
 // ProviderSource Tests
 // ============================================================================
@@ -322,6 +759,31 @@ fn test_env_provider_missing_key() {
     assert!(result.is_none());
 }
 
+#[cfg(feature = "provider")]
+#[test]
+fn test_fn_provider_bridges_lookup() {
+    use procenv::provider::FnProvider;
+
+    let legacy: HashMap<String, String> = HashMap::from([("PORT".to_string(), "9090".to_string())]);
+    let fallback = MemoryProvider::new("fallback")
+        .with_value("PORT", "8080")
+        .with_value("HOST", "localhost")
+        .with_priority(200);
+
+    let mut loader = ConfigLoader::new()
+        .with_provider(Box::new(fallback))
+        .with_provider(Box::new(FnProvider::new("legacy", move |key| {
+            legacy.get(key).cloned()
+        })));
+
+    assert_eq!(loader.get_str("PORT").as_deref(), Some("9090"));
+    assert_eq!(loader.get_str("HOST").as_deref(), Some("localhost"));
+    assert_eq!(
+        loader.sources().get("PORT").unwrap().source,
+        Source::CustomProvider("legacy".to_string())
+    );
+}
+
 // ============================================================================
 // Integration with Existing API
 // ============================================================================
@@ -334,3 +796,39 @@ fn test_source_custom_provider_variant() {
     let source2 = Source::CustomProvider("vault".to_string());
     assert_eq!(source, source2);
 }
+
+// ============================================================================
+// DirProvider Tests
+// ============================================================================
+
+#[test]
+fn test_dir_provider_in_loader_chain() {
+    use procenv::provider::EnvProvider;
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("PROCENV_DIR_DB_URL"), "postgres://secret\n").unwrap();
+    std::fs::write(dir.path().join("PROCENV_DIR_PORT"), "9000").unwrap();
+
+    unsafe {
+        std::env::set_var("PROCENV_DIR_PORT", "8080");
+    }
+
+    let mut loader = ConfigLoader::new()
+        .with_provider(Box::new(EnvProvider::new()))
+        .with_secrets_dir(dir.path());
+
+    // Env (priority 20) beats the directory (CONFIG_FILE, 50)
+    assert_eq!(loader.get_str("PROCENV_DIR_PORT").as_deref(), Some("8080"));
+    assert_eq!(
+        loader.get_str("PROCENV_DIR_DB_URL").as_deref(),
+        Some("postgres://secret")
+    );
+    assert_eq!(
+        loader.sources().get("PROCENV_DIR_DB_URL").unwrap().source,
+        Source::ConfigFile(Some(dir.path().join("PROCENV_DIR_DB_URL")))
+    );
+
+    unsafe {
+        std::env::remove_var("PROCENV_DIR_PORT");
+    }
+}
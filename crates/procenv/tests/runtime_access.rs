@@ -139,6 +139,15 @@ fn test_has_key() {
     assert!(!SimpleConfig::has_key("unknown"));
 }
 
+#[test]
+fn test_keys_sorted_and_const() {
+    const KEYS: &[&str] = SimpleConfig::keys();
+
+    assert_eq!(KEYS, ["debug", "host", "port"]);
+    const { assert!(SimpleConfig::has_key("port")) };
+    const { assert!(!SimpleConfig::has_key("unknown")) };
+}
+
 // ============================================================================
 // Secret Field Tests
 // ============================================================================
@@ -209,6 +218,177 @@ fn test_nested_get_str() {
     );
 }
 
+#[test]
+fn test_nested_has_key() {
+    const { assert!(AppConfig::has_key("database.port")) };
+    assert!(AppConfig::has_key("name"));
+    assert!(!AppConfig::has_key("database.unknown"));
+    assert!(!AppConfig::has_key("database"));
+    assert_eq!(AppConfig::keys(), ["name"]);
+}
+
+#[derive(EnvConfig)]
+#[allow(dead_code)]
+struct SecretNestedConfig {
+    #[env(var = "RT_SN_NAME")]
+    name: String,
+
+    #[env(flatten)]
+    credentials: SecretConfig,
+}
+
+#[test]
+#[serial]
+fn test_is_secret_follows_flatten() {
+    assert!(SecretConfig::__is_secret("password"));
+    assert!(!SecretConfig::__is_secret("username"));
+    assert!(SecretNestedConfig::__is_secret("credentials.password"));
+    assert!(!SecretNestedConfig::__is_secret("credentials.username"));
+    assert!(!SecretNestedConfig::__is_secret("password"));
+
+    with_env(
+        &[
+            ("RT_SN_NAME", "svc"),
+            ("RT_SEC_USER", "admin"),
+            ("RT_SEC_PASS", "secret123"),
+        ],
+        || {
+            let config = SecretNestedConfig::from_env().unwrap();
+            assert_eq!(config.get_str("name"), Some("svc".to_string()));
+            assert_eq!(
+                config.get_str("credentials.password"),
+                Some("<redacted>".to_string())
+            );
+        },
+    );
+}
+
+// ============================================================================
+// Change Detection Tests
+// ============================================================================
+
+fn secret_nested(name: &str, password: &str) -> SecretNestedConfig {
+    SecretNestedConfig {
+        name: name.to_string(),
+        credentials: SecretConfig {
+            username: "admin".to_string(),
+            password: password.to_string(),
+        },
+    }
+}
+
+#[test]
+fn test_changed_fields_without_partial_eq() {
+    let config = secret_nested("svc", "one");
+
+    assert!(
+        config
+            .changed_fields(&secret_nested("svc", "one"))
+            .is_empty()
+    );
+    assert_eq!(
+        config.changed_fields(&secret_nested("api", "two")),
+        ["name", "credentials.password"]
+    );
+}
+
+#[test]
+fn test_digest_covers_secret_fields() {
+    let config = secret_nested("svc", "one");
+
+    assert_eq!(config.digest(), secret_nested("svc", "one").digest());
+    assert_ne!(config.digest(), secret_nested("svc", "two").digest());
+    assert_ne!(config.digest(), secret_nested("api", "one").digest());
+}
+
+#[cfg(feature = "secrecy")]
+#[test]
+fn test_digest_exposes_secrecy_types() {
+    use procenv::SecretString;
+
+    #[derive(EnvConfig)]
+    struct TokenConfig {
+        #[env(var = "RT_TOKEN")]
+        token: SecretString,
+    }
+
+    let token = |value: &str| TokenConfig {
+        token: SecretString::from(value),
+    };
+
+    assert_eq!(token("a").digest(), token("a").digest());
+    assert_ne!(token("a").digest(), token("b").digest());
+    assert_eq!(token("a").changed_fields(&token("b")), ["token"]);
+}
+
+// ============================================================================
+// JSON Pointer Access Tests
+// ============================================================================
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct FeatureFlags {
+    beta: bool,
+    rollout: Rollout,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct Rollout {
+    region: String,
+    percent: u8,
+}
+
+#[derive(EnvConfig)]
+#[allow(dead_code)]
+struct PointerConfig {
+    #[env(var = "RT_PTR_FLAGS", format = "json", pointer_access)]
+    feature_flags: FeatureFlags,
+
+    #[env(var = "RT_PTR_EXTRA", format = "json", pointer_access, optional)]
+    extra: Option<serde_json::Value>,
+
+    #[env(var = "RT_PTR_TOKENS", format = "json", pointer_access, secret)]
+    tokens: serde_json::Value,
+}
+
+#[test]
+#[serial]
+fn test_get_str_follows_json_pointer() {
+    with_env(
+        &[
+            (
+                "RT_PTR_FLAGS",
+                r#"{"beta": true, "rollout": {"region": "eu", "percent": 25}}"#,
+            ),
+            ("RT_PTR_TOKENS", r#"{"api": "hunter2"}"#),
+        ],
+        || {
+            let config = PointerConfig::from_env().unwrap();
+            assert_eq!(
+                config.get_str("feature_flags/beta"),
+                Some("true".to_string())
+            );
+            assert_eq!(
+                config.get_str("feature_flags/rollout/region"),
+                Some("eu".to_string())
+            );
+            assert_eq!(
+                config.get_str("feature_flags/rollout/percent"),
+                Some("25".to_string())
+            );
+            // Key order depends on serde_json's `preserve_order` feature, so
+            // compare structurally rather than by string.
+            let rollout: serde_json::Value =
+                serde_json::from_str(&config.get_str("feature_flags/rollout").unwrap()).unwrap();
+            assert_eq!(rollout, serde_json::json!({"region": "eu", "percent": 25}));
+            assert_eq!(config.get_str("feature_flags/missing"), None);
+            assert_eq!(config.get_str("extra/anything"), None);
+
+            assert!(PointerConfig::__is_secret("tokens/api"));
+            assert_eq!(config.get_str("tokens/api"), Some("<redacted>".to_string()));
+        },
+    );
+}
+
 // ============================================================================
 // ConfigLoader Tests
 // ============================================================================
@@ -7,6 +7,7 @@
 //! This is the "clap pattern" - just `#[derive(EnvConfig)]` is enough.
 
 #![allow(clippy::pedantic)]
+#![allow(clippy::approx_constant)]
 #![cfg(feature = "file-all")]
 
 use procenv::EnvConfig;
@@ -140,6 +140,20 @@ mod basic_sources {
             );
         });
     }
+
+    #[test]
+    fn test_from_env_verbose_loads_like_from_env() {
+        clear_env_vars(&["BASIC_DB_URL", "BASIC_PORT", "BASIC_DEBUG"]);
+
+        with_env_vars(&[("BASIC_DB_URL", "postgres://localhost/test")], || {
+            // Source lines go to stderr; the config itself is unchanged
+            let config = BasicConfig::from_env_verbose().unwrap();
+            assert_eq!(config.db_url, "postgres://localhost/test");
+            assert_eq!(config.port, 8080);
+        });
+
+        assert!(BasicConfig::from_env_verbose().unwrap_err().is_missing());
+    }
 }
 
 // =============================================================================
@@ -333,9 +347,11 @@ mod flatten_sources {
                     "database.port source should be tracked even for defaults"
                 );
 
-                // After the fix, nested defaults show as NotSet (full nested
-                // default tracking requires additional metadata propagation)
-                // This test documents current behavior
+                assert!(
+                    matches!(port_source.unwrap().source, Source::Default),
+                    "Expected Default for database.port, got {:?}",
+                    port_source.unwrap().source
+                );
             },
         );
     }
@@ -0,0 +1,104 @@
+//! Test harness tests.
+//!
+//! Tests for `procenv::test_util`, enabled by the `test-util` feature. None
+//! of these use `#[serial]`: the guards alone must keep them apart.
+
+#![allow(clippy::pedantic)]
+#![cfg(feature = "test-util")]
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use procenv::EnvConfig;
+use procenv::test_util::{EnvGuard, with_env};
+
+#[derive(EnvConfig)]
+struct HarnessConfig {
+    #[env(var = "TU_HOST")]
+    host: String,
+
+    #[env(var = "TU_PORT", default = "8080")]
+    port: u16,
+}
+
+// ============================================================================
+// with_env
+// ============================================================================
+
+#[test]
+fn test_with_env_sets_and_removes_vars() {
+    let port = with_env(&[("TU_HOST", "localhost"), ("TU_PORT", "9000")], || {
+        let config = HarnessConfig::from_env().expect("vars are set");
+        assert_eq!(config.host, "localhost");
+        config.port
+    });
+
+    assert_eq!(port, 9000);
+    with_env(&[], || {
+        assert!(std::env::var("TU_HOST").is_err());
+        assert!(std::env::var("TU_PORT").is_err());
+    });
+}
+
+#[test]
+fn test_with_env_parallel_tests_see_only_their_vars() {
+    // Races against the test above; without the lock one would see the other's host
+    with_env(&[("TU_HOST", "db.internal")], || {
+        let config = HarnessConfig::from_env().expect("host is set");
+        assert_eq!(config.host, "db.internal");
+        assert_eq!(config.port, 8080);
+    });
+}
+
+#[test]
+fn test_with_env_restores_after_panic() {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        with_env(&[("TU_PANIC", "set")], || panic!("test failure"));
+    }));
+
+    assert!(result.is_err());
+    with_env(&[], || assert!(std::env::var("TU_PANIC").is_err()));
+}
+
+// ============================================================================
+// EnvGuard
+// ============================================================================
+
+#[test]
+fn test_env_guard_restores_previous_value() {
+    // PATH is set before the test starts, so dropping must put it back
+    let original = with_env(&[], || std::env::var_os("PATH"));
+    assert!(original.is_some());
+
+    let guard = EnvGuard::new(&[("PATH", "/nonexistent")]);
+    assert_eq!(std::env::var("PATH").unwrap(), "/nonexistent");
+    drop(guard);
+
+    assert_eq!(with_env(&[], || std::env::var_os("PATH")), original);
+}
+
+#[test]
+fn test_env_guard_repeated_key_restores_original() {
+    with_env(&[("TU_REPEAT", "a"), ("TU_REPEAT", "b")], || {
+        assert_eq!(std::env::var("TU_REPEAT").unwrap(), "b");
+    });
+
+    with_env(&[], || assert!(std::env::var("TU_REPEAT").is_err()));
+}
+
+#[test]
+fn test_env_guard_is_not_reentrant() {
+    let _guard = EnvGuard::new(&[("TU_NESTED", "outer")]);
+
+    let nested = catch_unwind(|| EnvGuard::new(&[("TU_NESTED", "inner")]));
+    assert!(nested.is_err());
+    assert_eq!(std::env::var("TU_NESTED").unwrap(), "outer");
+}
+
+#[test]
+fn test_env_guard_debug_hides_values() {
+    let guard = EnvGuard::new(&[("TU_DEBUG_SECRET", "hunter2")]);
+    let debug = format!("{guard:?}");
+
+    assert!(debug.contains("TU_DEBUG_SECRET"));
+    assert!(!debug.contains("hunter2"));
+}
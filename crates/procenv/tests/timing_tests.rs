@@ -0,0 +1,209 @@
+//! Load timing tests.
+//!
+//! Tests for `from_env_timed()` generated by `#[env_config(timed)]`.
+
+#![allow(clippy::pedantic)]
+#![cfg(feature = "timing")]
+
+use procenv::EnvConfig;
+use serial_test::serial;
+
+fn cleanup_env(vars: &[&str]) {
+    unsafe {
+        for k in vars {
+            std::env::remove_var(*k);
+        }
+    }
+}
+
+fn with_env<F, R>(vars: &[(&str, &str)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        for (k, v) in vars {
+            std::env::set_var(*k, *v);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (k, _) in vars {
+            std::env::remove_var(*k);
+        }
+    }
+
+    result
+}
+
+#[derive(EnvConfig)]
+#[env_config(timed)]
+struct InnerTimed {
+    #[env(var = "TIMED_INNER_HOST", default = "localhost")]
+    host: String,
+}
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "TIMED_", timed)]
+struct TimedConfig {
+    #[env(var = "NAME")]
+    name: String,
+
+    #[env(var = "PORT", default = "8080")]
+    port: u16,
+
+    #[env(flatten)]
+    inner: InnerTimed,
+}
+
+#[test]
+#[serial]
+fn test_from_env_timed_records_each_field() {
+    cleanup_env(&["TIMED_NAME", "TIMED_PORT", "TIMED_INNER_HOST"]);
+
+    with_env(&[("TIMED_NAME", "svc")], || {
+        let (result, timings) = TimedConfig::from_env_timed();
+        let config = result.expect("should load");
+
+        assert_eq!(config.name, "svc");
+        assert_eq!(config.port, 8080);
+        assert_eq!(config.inner.host, "localhost");
+
+        let fields: Vec<&str> = timings.fields().iter().map(|t| t.field.as_str()).collect();
+        assert_eq!(fields, ["name", "port", "inner"]);
+
+        let sum: std::time::Duration = timings.fields().iter().map(|t| t.duration).sum();
+        assert!(timings.total() >= sum);
+
+        let display = timings.to_string();
+        assert!(display.contains("port"));
+        assert!(display.lines().last().unwrap().starts_with("total"));
+    });
+}
+
+#[test]
+#[serial]
+fn test_from_env_timed_returns_timings_on_error() {
+    cleanup_env(&["TIMED_NAME", "TIMED_PORT", "TIMED_INNER_HOST"]);
+
+    with_env(&[("TIMED_PORT", "not-a-port")], || {
+        let (result, timings) = TimedConfig::from_env_timed();
+
+        let Err(procenv::Error::Multiple { errors }) = result else {
+            panic!("expected missing name and invalid port");
+        };
+        assert_eq!(errors.len(), 2);
+        assert_eq!(timings.fields().len(), 3);
+    });
+}
+
+#[test]
+#[serial]
+fn test_from_env_timed_matches_from_env() {
+    cleanup_env(&["TIMED_NAME", "TIMED_PORT", "TIMED_INNER_HOST"]);
+
+    with_env(&[("TIMED_NAME", "svc"), ("TIMED_INNER_HOST", "db")], || {
+        let plain = TimedConfig::from_env().expect("should load");
+        let (timed, _) = TimedConfig::from_env_timed();
+        let timed = timed.expect("should load");
+
+        assert_eq!(timed.name, plain.name);
+        assert_eq!(timed.port, plain.port);
+        assert_eq!(timed.inner.host, plain.inner.host);
+    });
+
+    with_env(&[("TIMED_PORT", "99999")], || {
+        let plain = TimedConfig::from_env().unwrap_err();
+        let (timed, _) = TimedConfig::from_env_timed();
+        assert_eq!(timed.unwrap_err().to_string(), plain.to_string());
+    });
+}
+
+#[derive(EnvConfig)]
+#[env_config(prefix = "BASE_", prefix_env = "TIMED_NS_SEL", timed)]
+struct TimedNamespacedConfig {
+    #[env(var = "PORT")]
+    port: u16,
+}
+
+#[test]
+#[serial]
+fn test_from_env_timed_applies_prefix_env() {
+    cleanup_env(&["TIMED_NS_SEL", "BASE_PORT", "TEN_BASE_PORT"]);
+
+    with_env(
+        &[
+            ("TIMED_NS_SEL", "TEN_"),
+            ("BASE_PORT", "1000"),
+            ("TEN_BASE_PORT", "2000"),
+        ],
+        || {
+            let (result, timings) = TimedNamespacedConfig::from_env_timed();
+            assert_eq!(result.expect("namespace loads").port, 2000);
+            assert_eq!(
+                TimedNamespacedConfig::from_env().unwrap().port,
+                2000,
+                "from_env and from_env_timed read the same vars"
+            );
+            assert!(timings.total() > std::time::Duration::ZERO);
+        },
+    );
+}
+
+#[derive(EnvConfig, Default, PartialEq)]
+#[env_config(allow_empty_struct, timed)]
+struct TimedPluginConfig {
+    #[env(var = "TIMED_PLUGIN_URL")]
+    url: String,
+
+    #[env(var = "TIMED_PLUGIN_RETRIES", default = "3")]
+    retries: u32,
+}
+
+#[test]
+#[serial]
+fn test_from_env_timed_allow_empty_struct_uses_default() {
+    cleanup_env(&["TIMED_PLUGIN_URL", "TIMED_PLUGIN_RETRIES"]);
+
+    let (result, timings) = TimedPluginConfig::from_env_timed();
+    assert_eq!(
+        result.expect("absent subsystem"),
+        TimedPluginConfig::default()
+    );
+    assert_eq!(timings.fields().len(), 2);
+
+    // Partially configured is still an error
+    with_env(&[("TIMED_PLUGIN_RETRIES", "5")], || {
+        let (result, _) = TimedPluginConfig::from_env_timed();
+        assert!(result.is_err());
+    });
+}
+
+#[derive(EnvConfig)]
+#[env_config(no_accumulate, timed)]
+struct TimedFailFastConfig {
+    #[env(var = "TIMED_FAIL_A")]
+    a: String,
+
+    #[env(var = "TIMED_FAIL_B")]
+    b: u16,
+}
+
+#[test]
+#[serial]
+fn test_from_env_timed_no_accumulate_returns_first_error() {
+    cleanup_env(&["TIMED_FAIL_A", "TIMED_FAIL_B"]);
+
+    with_env(&[("TIMED_FAIL_B", "not-a-port")], || {
+        let (result, timings) = TimedFailFastConfig::from_env_timed();
+
+        match result {
+            Err(procenv::Error::Missing { var, .. }) => assert_eq!(var, "TIMED_FAIL_A"),
+            Err(other) => panic!("Expected Missing error, got: {other:?}"),
+            Ok(_) => panic!("Expected an error"),
+        }
+        // Loading stopped after the first field
+        assert_eq!(timings.fields().len(), 1);
+    });
+}
@@ -21,6 +21,7 @@ fn compile_pass() {
     t.pass("tests/compile_pass/prefix_support.rs");
     t.pass("tests/compile_pass/env_example_gen.rs");
     t.pass("tests/compile_pass/various_types.rs");
+    t.pass("tests/compile_pass/cfg_fields.rs");
 }
 
 /// Tests requiring clap feature
@@ -0,0 +1,229 @@
+//! Secret unmasking tests.
+//!
+//! Tests for `#[env_config(allow_unmask)]`, which lets `PROCENV_UNMASK=1`
+//! show secret fields in `Debug` output and errors, and for
+//! `#[env_config(unmask_in_debug)]`, which shows them in debug builds.
+
+#![allow(clippy::pedantic)]
+
+use procenv::{EnvConfig, Error};
+use serial_test::serial;
+
+fn cleanup_env(vars: &[&str]) {
+    unsafe {
+        for k in vars {
+            std::env::remove_var(*k);
+        }
+    }
+}
+
+fn with_env<F, R>(vars: &[(&str, &str)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        for (k, v) in vars {
+            std::env::set_var(*k, *v);
+        }
+    }
+
+    let result = f();
+
+    unsafe {
+        for (k, _) in vars {
+            std::env::remove_var(*k);
+        }
+    }
+
+    result
+}
+
+const VARS: &[&str] = &["PROCENV_UNMASK", "UM_API_KEY", "UM_PIN", "UM_TOKENS"];
+
+#[derive(EnvConfig)]
+#[env_config(allow_unmask)]
+struct UnmaskableConfig {
+    #[env(var = "UM_API_KEY", secret)]
+    api_key: String,
+
+    #[env(var = "UM_TOKENS", secret, optional, sep_env = "UM_TOKENS_SEP")]
+    tokens: Option<Vec<String>>,
+}
+
+#[derive(EnvConfig)]
+#[env_config(allow_unmask)]
+#[allow(dead_code)]
+struct UnmaskablePin {
+    #[env(var = "UM_PIN", secret)]
+    pin: u32,
+}
+
+#[derive(EnvConfig)]
+#[allow(dead_code)]
+struct MaskedConfig {
+    #[env(var = "UM_API_KEY", secret)]
+    api_key: String,
+}
+
+#[derive(EnvConfig)]
+#[cfg_attr(debug_assertions, env_config(allow_unmask))]
+#[allow(dead_code)]
+struct DebugOnlyConfig {
+    #[env(var = "UM_API_KEY", secret)]
+    api_key: String,
+}
+
+#[derive(EnvConfig)]
+#[env_config(unmask_in_debug)]
+#[allow(dead_code)]
+struct DebugBuildConfig {
+    #[env(var = "UM_API_KEY", secret)]
+    api_key: String,
+
+    #[env(var = "UM_PIN", secret)]
+    pin: u32,
+}
+
+#[test]
+#[serial]
+fn test_debug_masked_without_flag() {
+    cleanup_env(VARS);
+    with_env(&[("UM_API_KEY", "hunter2"), ("UM_TOKENS", "a,b")], || {
+        let config = UnmaskableConfig::from_env().unwrap();
+        let debug = format!("{config:?}");
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains(r#""<redacted>""#));
+        assert!(debug.contains("[<redacted>, <redacted>]"));
+    });
+}
+
+#[test]
+#[serial]
+fn test_debug_unmasked_with_flag() {
+    cleanup_env(VARS);
+    with_env(
+        &[
+            ("UM_API_KEY", "hunter2"),
+            ("UM_TOKENS", "a,b"),
+            ("PROCENV_UNMASK", "1"),
+        ],
+        || {
+            let config = UnmaskableConfig::from_env().unwrap();
+            let debug = format!("{config:?}");
+            assert!(debug.contains("hunter2"));
+            assert!(debug.contains(r#"Some(["a", "b"])"#));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_flag_must_be_one() {
+    cleanup_env(VARS);
+    with_env(
+        &[("UM_API_KEY", "hunter2"), ("PROCENV_UNMASK", "true")],
+        || {
+            let config = UnmaskableConfig::from_env().unwrap();
+            assert!(!format!("{config:?}").contains("hunter2"));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_errors_unmasked_with_flag() {
+    cleanup_env(VARS);
+    with_env(&[("UM_PIN", "not-a-pin")], || {
+        let err = UnmaskablePin::from_env().unwrap_err();
+        assert!(!format!("{err:?}").contains("not-a-pin"));
+    });
+
+    with_env(&[("UM_PIN", "not-a-pin"), ("PROCENV_UNMASK", "1")], || {
+        let err = UnmaskablePin::from_env().unwrap_err();
+        match &err {
+            Error::Parse { value, .. } => assert_eq!(value.as_str(), Some("not-a-pin")),
+            other => panic!("expected Parse, got {other:?}"),
+        }
+    });
+}
+
+#[test]
+#[serial]
+fn test_flag_ignored_without_opt_in() {
+    cleanup_env(VARS);
+    with_env(
+        &[("UM_API_KEY", "hunter2"), ("PROCENV_UNMASK", "1")],
+        || {
+            let config = MaskedConfig::from_env().unwrap();
+            assert!(!format!("{config:?}").contains("hunter2"));
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_cfg_attr_opt_in() {
+    cleanup_env(VARS);
+    with_env(
+        &[("UM_API_KEY", "hunter2"), ("PROCENV_UNMASK", "1")],
+        || {
+            let config = DebugOnlyConfig::from_env().unwrap();
+            assert_eq!(
+                format!("{config:?}").contains("hunter2"),
+                cfg!(debug_assertions)
+            );
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_unmask_in_debug_follows_build_profile() {
+    cleanup_env(VARS);
+    with_env(&[("UM_API_KEY", "hunter2"), ("UM_PIN", "1234")], || {
+        let config = DebugBuildConfig::from_env().unwrap();
+        let debug = format!("{config:?}");
+
+        assert_eq!(debug.contains("hunter2"), cfg!(debug_assertions));
+        assert_eq!(debug.contains("1234"), cfg!(debug_assertions));
+    });
+}
+
+#[test]
+#[serial]
+fn test_unmask_in_debug_errors_follow_build_profile() {
+    cleanup_env(VARS);
+    with_env(
+        &[("UM_API_KEY", "hunter2"), ("UM_PIN", "not-a-pin")],
+        || {
+            let err = DebugBuildConfig::from_env().unwrap_err();
+            match &err {
+                Error::Parse { value, .. } => {
+                    assert_eq!(value.as_str().is_some(), cfg!(debug_assertions));
+                }
+                other => panic!("expected Parse, got {other:?}"),
+            }
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_unmask_in_debug_ignores_env_flag() {
+    // The build profile decides; PROCENV_UNMASK=0 doesn't re-mask
+    cleanup_env(VARS);
+    with_env(
+        &[
+            ("UM_API_KEY", "hunter2"),
+            ("UM_PIN", "1"),
+            ("PROCENV_UNMASK", "0"),
+        ],
+        || {
+            let config = DebugBuildConfig::from_env().unwrap();
+            assert_eq!(
+                format!("{config:?}").contains("hunter2"),
+                cfg!(debug_assertions)
+            );
+        },
+    );
+}
@@ -4,6 +4,7 @@
 //! `from_env_validated()` method generation.
 
 #![allow(clippy::pedantic)]
+#![allow(clippy::result_large_err)]
 #![cfg(feature = "validator")]
 
 use procenv::EnvConfig;
@@ -131,6 +132,52 @@ fn test_custom_validation_fails_too_short() {
     });
 }
 
+fn validate_port(port: &u16) -> Result<(), procenv::Error> {
+    if *port < 1024 {
+        return Err(procenv::Error::validation_with_help(
+            "port",
+            "privileged ports are not allowed",
+            "use a port of 1024 or above",
+        ));
+    }
+    Ok(())
+}
+
+#[derive(EnvConfig, Validate)]
+#[env_config(validate)]
+struct ProcenvErrorValidatedConfig {
+    #[env(var = "CUSTOM_PORT", validate = "validate_port")]
+    port: u16,
+}
+
+#[test]
+fn test_custom_validator_returning_procenv_error() {
+    use miette::Diagnostic;
+
+    with_env(&[("CUSTOM_PORT", "80")], || {
+        let err = ProcenvErrorValidatedConfig::from_env_validated().unwrap_err();
+        let procenv::Error::Validation { errors } = &err else {
+            panic!("expected Validation error, got {err:?}");
+        };
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "port");
+        assert_eq!(errors[0].message, "privileged ports are not allowed");
+        assert_eq!(
+            errors[0].code().unwrap().to_string(),
+            "procenv::custom_validation_error"
+        );
+        assert_eq!(
+            errors[0].help().unwrap().to_string(),
+            "use a port of 1024 or above"
+        );
+    });
+
+    with_env(&[("CUSTOM_PORT", "8080")], || {
+        assert!(ProcenvErrorValidatedConfig::from_env_validated().is_ok());
+    });
+}
+
 // ============================================================================
 // URL Validation Tests
 // ============================================================================
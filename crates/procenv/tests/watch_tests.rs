@@ -4,6 +4,7 @@
 
 #![allow(clippy::pedantic)]
 #![allow(clippy::manual_strip)]
+#![allow(clippy::result_large_err)]
 #![cfg(feature = "watch")]
 
 use std::fs;
@@ -28,6 +29,30 @@ struct SimpleConfig {
     host: String,
 }
 
+#[derive(EnvConfig, Clone)]
+struct SecretWatchConfig {
+    #[env(var = "WATCH_TOKEN", secret)]
+    token: String,
+
+    #[env(var = "WATCH_PORT", default = "8080")]
+    port: u16,
+}
+
+#[derive(EnvConfig, Clone, PartialEq)]
+struct ImmutableConfig {
+    #[env(var = "IMMUTABLE_PORT", default = "8080", reload_immutable)]
+    port: u16,
+
+    #[env(var = "IMMUTABLE_HOST", default = "localhost")]
+    host: String,
+}
+
+#[derive(EnvConfig, Clone)]
+struct ImmutableParent {
+    #[env(flatten)]
+    server: ImmutableConfig,
+}
+
 #[allow(dead_code)] // Reserved for future secret handling tests
 #[derive(EnvConfig, Clone)]
 struct ConfigWithSecret {
@@ -192,6 +217,126 @@ fn test_callback_on_change() {
     handle.stop();
 }
 
+#[test]
+fn test_diff_fields_reports_changed_fields() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let changed = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let changed_clone = changed.clone();
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .diff_fields(SimpleConfig::changed_fields)
+        .on_change(move |change| {
+            *changed_clone.lock().unwrap() = change.changed_fields.clone();
+        })
+        .build_sync(move || {
+            let count = reload_count_clone.fetch_add(1, Ordering::SeqCst);
+            let config = SimpleConfig {
+                port: 8080 + count as u16,
+                ..SimpleConfig::default()
+            };
+            Ok((config, ConfigSources::default()))
+        })
+        .unwrap();
+
+    handle.reload().unwrap();
+    thread::sleep(Duration::from_millis(200));
+
+    assert_eq!(*changed.lock().unwrap(), ["port"]);
+
+    handle.stop();
+}
+
+#[test]
+fn test_field_values_mask_secrets() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let changed = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let changed_clone = changed.clone();
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = WatchBuilder::<SecretWatchConfig>::new()
+        .watch_file(&config_path)
+        .diff_fields(SecretWatchConfig::changed_fields)
+        .field_values(SecretWatchConfig::get_str)
+        .on_change(move |change| {
+            *changed_clone.lock().unwrap() = change
+                .changed_fields()
+                .iter()
+                .map(|f| (f.name.clone(), f.old_value.clone(), f.new_value.clone()))
+                .collect();
+        })
+        .build_sync(move || {
+            let count = reload_count_clone.fetch_add(1, Ordering::SeqCst);
+            let config = SecretWatchConfig {
+                token: format!("token-{count}"),
+                port: 8080 + count as u16,
+            };
+            Ok((config, ConfigSources::default()))
+        })
+        .unwrap();
+
+    handle.reload().unwrap();
+    thread::sleep(Duration::from_millis(200));
+
+    let value = |v: &str| Some(v.to_string());
+    let masked = value("<redacted>");
+    assert_eq!(
+        *changed.lock().unwrap(),
+        [
+            ("token".to_string(), masked.clone(), masked),
+            ("port".to_string(), value("8080"), value("8081")),
+        ]
+    );
+
+    handle.stop();
+}
+
+#[test]
+fn test_rapid_writes_coalesce_into_one_reload() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let change_count = Arc::new(AtomicU32::new(0));
+    let change_count_clone = change_count.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .debounce(Duration::from_millis(300))
+        .on_change(move |_change| {
+            change_count_clone.fetch_add(1, Ordering::SeqCst);
+        })
+        .build_sync(|| Ok((SimpleConfig::default(), ConfigSources::default())))
+        .unwrap();
+
+    assert_eq!(handle.debounce(), Duration::from_millis(300));
+
+    // Burst of writes, each well within the debounce window
+    thread::sleep(Duration::from_millis(100));
+    for port in 9000..9005 {
+        fs::write(&config_path, format!("port = {port}")).unwrap();
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    // Wait for the burst to settle and the callback to run
+    thread::sleep(Duration::from_millis(800));
+
+    assert_eq!(change_count.load(Ordering::SeqCst), 1);
+
+    handle.stop();
+}
+
 // ============================================================================
 // Error Handling Tests
 // ============================================================================
@@ -287,6 +432,134 @@ fn test_error_callback() {
     handle.stop();
 }
 
+#[test]
+fn test_failed_reload_marks_stale() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .build_sync(move || {
+            let count = reload_count_clone.fetch_add(1, Ordering::SeqCst);
+            if count == 1 {
+                Err(procenv::Error::Missing {
+                    var: "TEST".to_string(),
+                    help: "Set the TEST environment variable".to_string(),
+                })
+            } else {
+                Ok((SimpleConfig::default(), ConfigSources::default()))
+            }
+        })
+        .unwrap();
+
+    assert!(!handle.is_stale());
+    assert!(handle.last_error().is_none());
+
+    // Second load fails
+    handle.reload().unwrap();
+    thread::sleep(Duration::from_millis(100));
+
+    assert!(handle.is_stale());
+    let err = handle.last_error().expect("failure should be recorded");
+    assert!(err.to_string().contains("TEST"));
+
+    // Third load succeeds and clears the stale state
+    handle.reload().unwrap();
+    thread::sleep(Duration::from_millis(100));
+
+    assert!(!handle.is_stale());
+    assert!(handle.last_error().is_none());
+
+    handle.stop();
+}
+
+#[test]
+fn test_immutable_field_change_rejects_reload() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let rejected = Arc::new(std::sync::Mutex::new(None));
+    let rejected_clone = rejected.clone();
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = WatchBuilder::<ImmutableConfig>::new()
+        .watch_file(&config_path)
+        .reject_immutable_changes(ImmutableConfig::reload_immutable_changed)
+        .on_error(move |err| {
+            if let WatchError::ImmutableFieldChanged { field } = err {
+                *rejected_clone.lock().unwrap() = Some(field);
+            }
+        })
+        .build_sync(move || {
+            let config = match reload_count_clone.fetch_add(1, Ordering::SeqCst) {
+                0 => ImmutableConfig {
+                    port: 8080,
+                    host: "localhost".to_string(),
+                },
+                1 => ImmutableConfig {
+                    port: 8080,
+                    host: "example.com".to_string(),
+                },
+                _ => ImmutableConfig {
+                    port: 9090,
+                    host: "other.com".to_string(),
+                },
+            };
+            Ok((config, ConfigSources::default()))
+        })
+        .unwrap();
+
+    // Mutable fields reload as usual
+    handle.reload().unwrap();
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(handle.get().host, "example.com");
+    assert!(rejected.lock().unwrap().is_none());
+
+    // Changing the immutable port keeps the previous config
+    handle.reload().unwrap();
+    thread::sleep(Duration::from_millis(100));
+    let config = handle.get();
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.host, "example.com");
+    assert_eq!(rejected.lock().unwrap().as_deref(), Some("port"));
+    assert!(handle.is_stale());
+
+    handle.stop();
+}
+
+#[test]
+fn test_immutable_fields_require_check() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let (_, sources) = ImmutableParent::from_env_with_sources().unwrap();
+    assert_eq!(sources.reload_immutable(), ["server.port"]);
+
+    // Without the check, derived sources stop the watcher from starting
+    let err = WatchBuilder::<ImmutableConfig>::new()
+        .watch_file(&config_path)
+        .build_sync(ImmutableConfig::from_env_with_sources)
+        .expect_err("missing check should fail the build");
+    assert!(matches!(err, WatchError::InitFailed { .. }));
+    assert!(err.to_string().contains("reload_immutable (port)"));
+
+    let handle = WatchBuilder::<ImmutableConfig>::new()
+        .watch_file(&config_path)
+        .reject_immutable_changes(ImmutableConfig::reload_immutable_changed)
+        .build_sync(ImmutableConfig::from_env_with_sources)
+        .unwrap();
+
+    handle.stop();
+}
+
 // ============================================================================
 // Manual Reload Tests
 // ============================================================================
@@ -362,6 +635,40 @@ fn test_handle_clone_shares_state() {
     assert!(!handle2.is_running());
 }
 
+#[test]
+fn test_mapped_handle_tracks_reloads() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .build_sync(move || {
+            let count = reload_count_clone.fetch_add(1, Ordering::SeqCst);
+            Ok((
+                SimpleConfig {
+                    port: 8080 + count as u16,
+                    host: "localhost".to_string(),
+                },
+                ConfigSources::default(),
+            ))
+        })
+        .unwrap();
+
+    let addr = handle.map(|cfg| format!("{}:{}", cfg.host, cfg.port));
+    assert_eq!(*addr.get(), "localhost:8080");
+
+    handle.reload().unwrap();
+    thread::sleep(Duration::from_millis(100));
+
+    assert_eq!(*addr.get(), "localhost:8081");
+
+    handle.stop();
+}
+
 // ============================================================================
 // Multiple Files Tests
 // ============================================================================
@@ -401,6 +708,106 @@ fn test_watch_multiple_files() {
     handle.stop();
 }
 
+// ============================================================================
+// Rotation Tests
+// ============================================================================
+
+/// Reads `port = N` from a watched file, as a stand-in for a real loader.
+fn read_port(path: &std::path::Path) -> u16 {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| {
+            s.trim()
+                .strip_prefix("port = ")
+                .and_then(|p| p.parse().ok())
+        })
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_atomic_replace_keeps_watching() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let path = config_path.clone();
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .debounce(Duration::from_millis(50))
+        .build_sync(move || {
+            let config = SimpleConfig {
+                port: read_port(&path),
+                host: "localhost".to_string(),
+            };
+            Ok((config, ConfigSources::default()))
+        })
+        .unwrap();
+
+    // Editors save by renaming a new file over the old one, twice here
+    for port in [9090, 9191] {
+        thread::sleep(Duration::from_millis(100));
+        let tmp = dir.path().join("config.toml.tmp");
+        fs::write(&tmp, format!("port = {port}")).unwrap();
+        fs::rename(&tmp, &config_path).unwrap();
+        thread::sleep(Duration::from_millis(300));
+
+        assert_eq!(handle.get().port, port);
+    }
+
+    handle.stop();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_k8s_symlink_rotation_triggers_reload() {
+    use std::os::unix::fs::symlink;
+
+    // The layout of a mounted secret or config map:
+    // config.toml -> ..data/config.toml, ..data -> ..<timestamp>
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir(root.join("..2024_01")).unwrap();
+    fs::write(root.join("..2024_01/config.toml"), "port = 8080").unwrap();
+    symlink("..2024_01", root.join("..data")).unwrap();
+    let config_path = root.join("config.toml");
+    symlink("..data/config.toml", &config_path).unwrap();
+
+    let path = config_path.clone();
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .debounce(Duration::from_millis(50))
+        .build_sync(move || {
+            let config = SimpleConfig {
+                port: read_port(&path),
+                host: "localhost".to_string(),
+            };
+            Ok((config, ConfigSources::default()))
+        })
+        .unwrap();
+    assert_eq!(handle.get().port, 8080);
+
+    // Rotate twice: the second swap only fires if the watch was re-established
+    let mut old = "..2024_01".to_string();
+    for (version, port) in [("..2024_02", 9090), ("..2024_03", 9191)] {
+        thread::sleep(Duration::from_millis(100));
+        fs::create_dir(root.join(version)).unwrap();
+        fs::write(
+            root.join(version).join("config.toml"),
+            format!("port = {port}"),
+        )
+        .unwrap();
+        symlink(version, root.join("..data_tmp")).unwrap();
+        fs::rename(root.join("..data_tmp"), root.join("..data")).unwrap();
+        fs::remove_dir_all(root.join(&old)).unwrap();
+        old = version.to_string();
+        thread::sleep(Duration::from_millis(300));
+
+        assert_eq!(handle.get().port, port);
+    }
+
+    handle.stop();
+}
+
 // ============================================================================
 // WatchError Tests
 // ============================================================================
@@ -415,11 +822,102 @@ fn test_watch_error_display() {
     let msg = err.to_string();
     assert!(msg.contains("config invalid"));
 
+    let err = WatchError::immutable_field_changed("port");
+    let msg = err.to_string();
+    assert!(msg.contains("immutable field 'port'"));
+
     let err = WatchError::Stopped;
     let msg = err.to_string();
     assert!(msg.contains("stopped"));
 }
 
+// ============================================================================
+// Async Reload Handler Tests
+// ============================================================================
+
+#[cfg(feature = "async")]
+#[test]
+fn test_on_reload_async_awaits_handler() {
+    use futures::StreamExt;
+
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let _guard = runtime.enter();
+
+    let (notify_tx, mut notify_rx) = futures::channel::mpsc::unbounded::<u16>();
+    let handler_error = Arc::new(AtomicBool::new(false));
+    let handler_error_clone = handler_error.clone();
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .on_reload_async(move |change| {
+            let notify_tx = notify_tx.clone();
+            async move {
+                notify_tx.unbounded_send(change.new.port).unwrap();
+                if change.new.port == 9002 {
+                    Err("notify failed")
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .on_error(move |err| {
+            if matches!(err, WatchError::ReloadHandlerFailed { .. }) {
+                handler_error_clone.store(true, Ordering::SeqCst);
+            }
+        })
+        .build_sync(move || {
+            let count = reload_count_clone.fetch_add(1, Ordering::SeqCst);
+            let config = SimpleConfig {
+                port: 9000 + count as u16,
+                ..SimpleConfig::default()
+            };
+            Ok((config, ConfigSources::default()))
+        })
+        .unwrap();
+
+    // The handler runs on this runtime, so drive it while waiting
+    handle.reload().unwrap();
+    assert_eq!(runtime.block_on(notify_rx.next()), Some(9001));
+    assert!(!handler_error.load(Ordering::SeqCst));
+
+    handle.reload().unwrap();
+    assert_eq!(runtime.block_on(notify_rx.next()), Some(9002));
+    thread::sleep(Duration::from_millis(100));
+
+    // The failing handler is reported, but the reload still applied
+    assert!(handler_error.load(Ordering::SeqCst));
+    assert_eq!(handle.get().port, 9002);
+
+    handle.stop();
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_on_reload_async_requires_runtime() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let result = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .on_reload_async(|_change| async { Ok::<(), std::io::Error>(()) })
+        .build_sync(|| Ok((SimpleConfig::default(), ConfigSources::default())));
+
+    let Err(err) = result else {
+        panic!("building outside a runtime should fail");
+    };
+    assert!(err.to_string().contains("tokio runtime"), "{err}");
+}
+
 // ============================================================================
 // Default Trait Implementation
 // ============================================================================
@@ -0,0 +1,136 @@
+//! Base64 decoding for `base64` fields.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Why a `base64` field value could not be decoded.
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Base64Error {
+    /// A byte outside both the standard and URL-safe alphabets.
+    InvalidChar(char, usize),
+
+    /// Both `+`/`/` and `-`/`_` appear in the same value.
+    MixedAlphabets,
+
+    /// Padding in the middle, too much padding, or a dangling character.
+    InvalidLength,
+
+    /// The decoded bytes are not UTF-8 (for `String` fields).
+    InvalidUtf8,
+}
+
+impl fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidChar(c, offset) => {
+                write!(f, "invalid base64 character {c:?} at offset {offset}")
+            }
+            Self::MixedAlphabets => {
+                f.write_str("value mixes the standard and URL-safe base64 alphabets")
+            }
+            Self::InvalidLength => f.write_str("invalid base64 length or padding"),
+            Self::InvalidUtf8 => f.write_str("decoded base64 is not valid UTF-8"),
+        }
+    }
+}
+
+impl core::error::Error for Base64Error {}
+
+/// Decodes standard or URL-safe base64, with or without padding.
+///
+/// ASCII whitespace is ignored so line-wrapped values (e.g. PEM bodies) work.
+///
+/// # Errors
+///
+/// Returns a [`Base64Error`] describing the first problem found.
+pub fn decode_base64(value: &str) -> Result<Vec<u8>, Base64Error> {
+    let mut out = Vec::with_capacity(value.len() / 4 * 3);
+    let (mut acc, mut bits, mut digits) = (0u32, 0u32, 0usize);
+    let (mut standard, mut url_safe, mut padding) = (false, false, 0usize);
+
+    for (offset, c) in value.char_indices() {
+        let sextet = match c {
+            c if c.is_ascii_whitespace() => continue,
+            '=' => {
+                padding += 1;
+                continue;
+            }
+            _ if padding > 0 => return Err(Base64Error::InvalidLength),
+            'A'..='Z' => c as u32 - 'A' as u32,
+            'a'..='z' => c as u32 - 'a' as u32 + 26,
+            '0'..='9' => c as u32 - '0' as u32 + 52,
+            '+' | '/' => {
+                standard = true;
+                if c == '+' { 62 } else { 63 }
+            }
+            '-' | '_' => {
+                url_safe = true;
+                if c == '-' { 62 } else { 63 }
+            }
+            _ => return Err(Base64Error::InvalidChar(c, offset)),
+        };
+
+        if standard && url_safe {
+            return Err(Base64Error::MixedAlphabets);
+        }
+
+        acc = (acc << 6) | sextet;
+        bits += 6;
+        digits += 1;
+
+        if bits >= 8 {
+            bits -= 8;
+            #[expect(
+                clippy::cast_possible_truncation,
+                reason = "the masked accumulator holds at most `bits + 8` bits"
+            )]
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+
+    // A lone trailing digit carries no full byte; padding must complete a quad
+    let remainder = digits % 4;
+    if remainder == 1 || (padding > 0 && remainder + padding != 4) {
+        return Err(Base64Error::InvalidLength);
+    }
+
+    Ok(out)
+}
+
+/// Decodes base64 like [`decode_base64`] and validates the bytes as UTF-8.
+///
+/// # Errors
+///
+/// Returns a [`Base64Error`] if decoding fails or the bytes aren't UTF-8.
+pub fn decode_base64_string(value: &str) -> Result<String, Base64Error> {
+    String::from_utf8(decode_base64(value)?).map_err(|_| Base64Error::InvalidUtf8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode_base64("aGVsbG8").unwrap(), b"hello");
+        assert_eq!(decode_base64("aGVs\nbG8=\n").unwrap(), b"hello");
+        assert_eq!(decode_base64("").unwrap(), b"");
+        assert_eq!(decode_base64("+/8=").unwrap(), [0xfb, 0xff]);
+        assert_eq!(decode_base64("-_8").unwrap(), [0xfb, 0xff]);
+        assert_eq!(decode_base64_string("aGVsbG8=").unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_decode_base64_errors() {
+        assert_eq!(decode_base64("aGV*"), Err(Base64Error::InvalidChar('*', 3)));
+        assert_eq!(decode_base64("+_8="), Err(Base64Error::MixedAlphabets));
+        assert_eq!(decode_base64("aGVsb"), Err(Base64Error::InvalidLength));
+        assert_eq!(decode_base64("aG=Vs"), Err(Base64Error::InvalidLength));
+        assert_eq!(decode_base64("aGVsbG8=="), Err(Base64Error::InvalidLength));
+        assert_eq!(decode_base64_string("/w=="), Err(Base64Error::InvalidUtf8));
+    }
+}
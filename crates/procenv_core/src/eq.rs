@@ -0,0 +1,33 @@
+//! Constant-time comparison for `sensitive_eq` fields.
+
+/// Compares two byte strings in time that depends only on their lengths.
+///
+/// Every byte is compared even after a mismatch, so the time taken does
+/// not reveal how long a matching prefix a guess has. Values of different
+/// lengths compare unequal immediately; the length of a secret is not
+/// hidden.
+#[must_use]
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+
+    // Keep the optimizer from turning the fold back into an early exit
+    core::hint::black_box(diff) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"s3cret", b"s3cret"));
+        assert!(constant_time_eq(b"", b""));
+        assert!(!constant_time_eq(b"s3cret", b"s3creT"));
+        assert!(!constant_time_eq(b"s3cret", b"s3cre"));
+        assert!(!constant_time_eq(b"", b"x"));
+    }
+}
@@ -0,0 +1,104 @@
+//! Const key lookups for the generated `keys()` and `has_key()`.
+//!
+//! String comparison and prefix stripping aren't `const` in `core`, so
+//! these reimplement them byte-wise.
+
+use core::cmp::Ordering;
+
+/// Compares two strings byte-wise, matching `Ord for str`.
+#[must_use]
+pub const fn compare(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut i = 0;
+
+    while i < a.len() && i < b.len() {
+        if a[i] < b[i] {
+            return Ordering::Less;
+        }
+        if a[i] > b[i] {
+            return Ordering::Greater;
+        }
+        i += 1;
+    }
+
+    if a.len() < b.len() {
+        Ordering::Less
+    } else if a.len() > b.len() {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Binary searches a sorted slice of keys.
+#[must_use]
+pub const fn sorted_contains(keys: &[&str], key: &str) -> bool {
+    let (mut lo, mut hi) = (0, keys.len());
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match compare(keys[mid], key) {
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+            Ordering::Equal => return true,
+        }
+    }
+
+    false
+}
+
+/// Returns `key` with `prefix` removed, like `str::strip_prefix`.
+#[must_use]
+pub const fn strip_prefix<'a>(key: &'a str, prefix: &str) -> Option<&'a str> {
+    let (k, p) = (key.as_bytes(), prefix.as_bytes());
+
+    if k.len() < p.len() {
+        return None;
+    }
+
+    let mut i = 0;
+    while i < p.len() {
+        if k[i] != p[i] {
+            return None;
+        }
+        i += 1;
+    }
+
+    // `prefix` is valid UTF-8 and matched, so this is a char boundary
+    Some(key.split_at(p.len()).1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_matches_str_ord() {
+        let words = ["", "a", "ab", "b", "B", "host", "hostname", "é"];
+
+        for a in words {
+            for b in words {
+                assert_eq!(compare(a, b), a.cmp(b), "{a:?} vs {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_sorted_contains() {
+        const KEYS: &[&str] = &["debug", "host", "port"];
+
+        assert!(sorted_contains(KEYS, "debug"));
+        assert!(sorted_contains(KEYS, "port"));
+        assert!(!sorted_contains(KEYS, "hos"));
+        assert!(!sorted_contains(KEYS, "zzz"));
+        assert!(!sorted_contains(&[], "host"));
+    }
+
+    #[test]
+    fn test_strip_prefix() {
+        assert_eq!(strip_prefix("database.host", "database."), Some("host"));
+        assert_eq!(strip_prefix("database.", "database."), Some(""));
+        assert_eq!(strip_prefix("db", "database."), None);
+        assert_eq!(strip_prefix("cache.host", "database."), None);
+    }
+}
@@ -0,0 +1,47 @@
+//! # `procenv_core`
+//!
+//! The pure value-parsing helpers behind [`procenv`]'s generated loaders,
+//! split out so they build without `std`.
+//!
+//! This crate is `#![no_std]` and only needs `alloc`. It has no
+//! dependencies, so constrained targets can use the same key lookup,
+//! prefix joining, trimming, `${VAR}` expansion, base64, list and numeric
+//! boolean parsing, and constant-time comparison that the derive macro generates calls to,
+//! without pulling in file loading, dotenv or [`miette`] diagnostics.
+//!
+//! `procenv` re-exports everything here from `procenv::runtime`, which is
+//! the path generated code uses, so depending on this crate directly is
+//! only needed outside `procenv`.
+//!
+//! [`procenv`]: https://docs.rs/procenv
+//! [`miette`]: https://docs.rs/miette
+//!
+//! ## Example
+//!
+//! ```rust
+//! use procenv_core::{decode_base64_string, join_prefix, parse_list};
+//!
+//! assert_eq!(join_prefix("APP", "PORT", Some("_")), "APP_PORT");
+//! assert_eq!(parse_list::<u16>("80, 443", ","), Ok(vec![80, 443]));
+//! assert_eq!(decode_base64_string("aGVsbG8=").as_deref(), Ok("hello"));
+//! ```
+
+#![no_std]
+#![deny(missing_docs)]
+#![warn(clippy::pedantic)]
+#![warn(clippy::nursery)]
+
+extern crate alloc;
+
+mod base64;
+mod eq;
+mod keys;
+mod value;
+
+pub use base64::{Base64Error, decode_base64, decode_base64_string};
+pub use eq::constant_time_eq;
+pub use keys::{compare, sorted_contains, strip_prefix};
+pub use value::{
+    DEFAULT_LIST_SEPARATOR, NumericBoolError, expand_vars, join_prefix, len_in_bounds,
+    parse_bool_numeric, parse_list, trim_value,
+};
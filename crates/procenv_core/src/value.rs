@@ -0,0 +1,235 @@
+//! Raw value handling shared by generated loaders.
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+/// Joins an env var `prefix` and `name`, as generated loaders do.
+///
+/// Without a `separator` this is plain concatenation. With one, exactly one
+/// separator ends up between a non-empty prefix and the name, so `"APP"` and
+/// `"APP_"` both join with `"DB_URL"` to `"APP_DB_URL"` under `"_"`.
+#[must_use]
+pub fn join_prefix(prefix: &str, name: &str, separator: Option<&str>) -> String {
+    match separator {
+        Some(sep) if !prefix.is_empty() => {
+            let prefix = prefix.strip_suffix(sep).unwrap_or(prefix);
+            let name = name.strip_prefix(sep).unwrap_or(name);
+            format!("{prefix}{sep}{name}")
+        }
+        _ => format!("{prefix}{name}"),
+    }
+}
+
+/// Cleans up the raw value of a `trim`/`trim_matches` field before parsing.
+///
+/// With `whitespace`, surrounding whitespace is removed first. Then, while
+/// the value starts and ends with the same character from `matches`, that
+/// pair is stripped, so `"'8080'"` becomes `8080` but an unbalanced `5"` is
+/// left as-is.
+#[must_use]
+pub fn trim_value(value: String, whitespace: bool, matches: Option<&str>) -> String {
+    let mut trimmed = if whitespace { value.trim() } else { &value };
+
+    if let Some(matches) = matches {
+        while let Some(first) = trimmed.chars().next()
+            && matches.contains(first)
+            && trimmed.len() >= 2 * first.len_utf8()
+            && trimmed.ends_with(first)
+        {
+            trimmed = &trimmed[first.len_utf8()..trimmed.len() - first.len_utf8()];
+        }
+    }
+
+    if trimmed.len() == value.len() {
+        value
+    } else {
+        trimmed.to_owned()
+    }
+}
+
+/// Whether `value` satisfies a field's `min_len`/`max_len`.
+///
+/// Lengths count characters, so `"ключ"` has length 4.
+#[must_use]
+pub fn len_in_bounds(value: &str, min: Option<usize>, max: Option<usize>) -> bool {
+    let len = value.chars().count();
+    min.is_none_or(|min| len >= min) && max.is_none_or(|max| len <= max)
+}
+
+/// Separator used when a `sep_env` var is unset or empty.
+pub const DEFAULT_LIST_SEPARATOR: &str = ",";
+
+/// Splits `value` on `separator` and parses each trimmed item.
+///
+/// A blank value is an empty list. An empty separator falls back to
+/// [`DEFAULT_LIST_SEPARATOR`].
+///
+/// # Errors
+///
+/// Returns the parse error of the first item that fails.
+pub fn parse_list<T: FromStr>(value: &str, separator: &str) -> Result<Vec<T>, T::Err> {
+    if value.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let separator = if separator.is_empty() {
+        DEFAULT_LIST_SEPARATOR
+    } else {
+        separator
+    };
+
+    value
+        .split(separator)
+        .map(|item| item.trim().parse())
+        .collect()
+}
+
+/// Why a `coerce_bool_numeric` value is not a boolean.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NumericBoolError;
+
+impl fmt::Display for NumericBoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected `true`, `false`, `1` or `0`")
+    }
+}
+
+impl core::error::Error for NumericBoolError {}
+
+/// Parses `true`/`false` (ignoring ASCII case) or `1`/`0` as a `bool`.
+///
+/// Config files may store a flag as a real boolean or as `0`/`1`, while
+/// env vars are always strings; this accepts every spelling the same way.
+///
+/// # Errors
+///
+/// Returns [`NumericBoolError`] for any other value.
+pub fn parse_bool_numeric(value: &str) -> Result<bool, NumericBoolError> {
+    match value {
+        "1" => Ok(true),
+        "0" => Ok(false),
+        v if v.eq_ignore_ascii_case("true") => Ok(true),
+        v if v.eq_ignore_ascii_case("false") => Ok(false),
+        _ => Err(NumericBoolError),
+    }
+}
+
+/// Expands `${NAME}` references in `value`, resolving each through `lookup`.
+///
+/// `$$` is a literal `$`, so `"$${HOME}"` expands to `"${HOME}"`. Any other
+/// `$`, including an unclosed `${`, is kept as-is.
+///
+/// # Errors
+///
+/// Returns the name of the first reference `lookup` can't resolve.
+pub fn expand_vars(
+    value: &str,
+    mut lookup: impl FnMut(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(at) = rest.find('$') {
+        expanded.push_str(&rest[..at]);
+        rest = &rest[at..];
+
+        if let Some(after) = rest.strip_prefix("$$") {
+            expanded.push('$');
+            rest = after;
+        } else if let Some((name, after)) = rest
+            .strip_prefix("${")
+            .and_then(|inner| inner.split_once('}'))
+        {
+            expanded.push_str(&lookup(name).ok_or_else(|| name.to_owned())?);
+            rest = after;
+        } else {
+            expanded.push('$');
+            rest = &rest[1..];
+        }
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_join_prefix() {
+        assert_eq!(join_prefix("APP_", "PORT", None), "APP_PORT");
+        assert_eq!(join_prefix("APP", "PORT", None), "APPPORT");
+        assert_eq!(join_prefix("APP", "PORT", Some("_")), "APP_PORT");
+        assert_eq!(join_prefix("APP_", "_PORT", Some("_")), "APP_PORT");
+        assert_eq!(join_prefix("", "PORT", Some("_")), "PORT");
+        assert_eq!(join_prefix("APP__", "PORT", Some("__")), "APP__PORT");
+    }
+
+    #[test]
+    fn test_trim_value() {
+        let trim = |value: &str, whitespace, matches| trim_value(value.into(), whitespace, matches);
+
+        assert_eq!(trim("\"8080\"", false, Some("\"")), "8080");
+        assert_eq!(trim("\"'8080'\"", false, Some("\"'")), "8080");
+        assert_eq!(trim(" \"8080\" ", true, Some("\"")), "8080");
+        assert_eq!(trim(" \"8080\" ", false, Some("\"")), " \"8080\" ");
+        assert_eq!(trim("5\"", false, Some("\"")), "5\"");
+        assert_eq!(trim("\"", false, Some("\"")), "\"");
+        assert_eq!(trim("\"\"", false, Some("\"")), "");
+        assert_eq!(trim("  x  ", true, None), "x");
+    }
+
+    #[test]
+    fn test_len_in_bounds() {
+        assert!(len_in_bounds("abcd", Some(4), Some(4)));
+        assert!(len_in_bounds("ключ", None, Some(4)));
+        assert!(!len_in_bounds("abc", Some(4), None));
+        assert!(!len_in_bounds("abcde", None, Some(4)));
+        assert!(len_in_bounds("", None, None));
+    }
+
+    #[test]
+    fn test_expand_vars() {
+        let lookup = |name: &str| (name == "HOME").then(|| "/home/app".to_owned());
+
+        assert_eq!(
+            expand_vars("${HOME}/data", lookup).as_deref(),
+            Ok("/home/app/data")
+        );
+        assert_eq!(expand_vars("$${HOME}", lookup).as_deref(), Ok("${HOME}"));
+        assert_eq!(expand_vars("cost: $5", lookup).as_deref(), Ok("cost: $5"));
+        assert_eq!(expand_vars("${HOME", lookup).as_deref(), Ok("${HOME"));
+        assert_eq!(expand_vars("$$$", lookup).as_deref(), Ok("$$"));
+        assert_eq!(
+            expand_vars("${HOME}:${NOPE}", lookup),
+            Err("NOPE".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(parse_list::<u16>("80, 443", ","), Ok(vec![80, 443]));
+        assert_eq!(parse_list::<u16>("80;443", ";"), Ok(vec![80, 443]));
+        assert_eq!(parse_list::<u16>("80,443", ""), Ok(vec![80, 443]));
+        assert_eq!(parse_list::<u16>("  ", ","), Ok(vec![]));
+        assert!(parse_list::<u16>("80;x", ";").is_err());
+    }
+
+    #[test]
+    fn test_parse_bool_numeric() {
+        assert_eq!(parse_bool_numeric("1"), Ok(true));
+        assert_eq!(parse_bool_numeric("0"), Ok(false));
+        assert_eq!(parse_bool_numeric("TRUE"), Ok(true));
+        assert_eq!(parse_bool_numeric("false"), Ok(false));
+        assert_eq!(parse_bool_numeric("2"), Err(NumericBoolError));
+        assert_eq!(parse_bool_numeric("yes"), Err(NumericBoolError));
+    }
+}
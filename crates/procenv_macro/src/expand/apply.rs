@@ -0,0 +1,329 @@
+//! In-place environment loading code generation.
+//!
+//! This module generates `apply_env()`, which layers environment variables
+//! on top of an existing instance instead of building a new one. Only vars
+//! that are actually set are applied; defaults and profile values are not,
+//! because the instance already holds the fallback values.
+//!
+//! # Generated Code Pattern
+//!
+//! Every set var is parsed into a local first, and flattened structs stage
+//! theirs the same way. Only when all of them are valid is a closure
+//! returned that assigns them, so a failed `apply_env()` changes nothing:
+//!
+//! ```rust,ignore
+//! let port: Option<u16> = match std::env::var("PORT") {
+//!     Ok(val) => /* parse, pushing errors */,
+//!     Err(VarError::NotPresent) => None,
+//!     // ...
+//! };
+//! let __db_apply = /* DbConfig's staged closure, pushing errors */;
+//!
+//! if !__errors.is_empty() { return Err(/* ... */); }
+//!
+//! Ok(move |__self: &mut Self| {
+//!     if let Some(__value) = port { __self.port = __value; }
+//!     if let Some(__apply) = __db_apply { __apply(&mut __self.db); }
+//! })
+//! ```
+
+use proc_macro2::TokenStream as QuoteStream;
+use quote::{format_ident, quote};
+use syn::{Generics, Ident};
+
+use crate::field::{FieldFactory, FieldGenerator};
+use crate::parse::EnvConfigAttr;
+
+use super::env::{
+    generate_after_parse, generate_audit_call, generate_case_insensitive_scope,
+    generate_dotenv_load, generate_error_redaction, generate_value_checks,
+    generate_value_parse_expr,
+};
+
+/// Generate `apply_env()` and the `__stage_env` methods it and flattened
+/// parents call.
+#[expect(
+    clippy::too_many_lines,
+    reason = "proc-macro code generation inherently requires verbose quote! blocks"
+)]
+pub fn generate_apply_env_impl(
+    struct_name: &Ident,
+    generics: &Generics,
+    generators: &[Box<dyn FieldGenerator>],
+    env_config_attr: &EnvConfigAttr,
+) -> QuoteStream {
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let fields: Vec<&dyn FieldGenerator> = generators
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|f| !f.is_skip() && !f.is_flatten() && f.env_var_name().is_some())
+        .collect();
+
+    let readers: Vec<QuoteStream> = fields
+        .iter()
+        .map(|f| generate_field_reader(*f, env_config_attr))
+        .collect();
+
+    let assignments: Vec<QuoteStream> = fields
+        .iter()
+        .map(|f| {
+            let name = f.name();
+            let value = if f.distinguish_null() {
+                quote! { std::option::Option::Some(std::option::Option::Some(__value)) }
+            } else if f.is_optional() {
+                quote! { std::option::Option::Some(__value) }
+            } else {
+                quote! { __value }
+            };
+
+            quote! {
+                if let std::option::Option::Some(__value) = #name {
+                    __self.#name = #value;
+                }
+            }
+        })
+        .collect();
+
+    let flattened: Vec<&dyn FieldGenerator> = generators
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|f| f.is_flatten())
+        .collect();
+
+    let nested: Vec<QuoteStream> = flattened
+        .iter()
+        .map(|f| generate_nested_stage(*f))
+        .collect();
+
+    let nested_assignments: Vec<QuoteStream> = flattened
+        .iter()
+        .map(|f| {
+            let name = f.name();
+            let apply = format_ident!("__{}_apply", name);
+            quote! {
+                if let std::option::Option::Some(__apply) = #apply {
+                    __apply(&mut __self.#name);
+                }
+            }
+        })
+        .collect();
+
+    let return_errors = if env_config_attr.no_accumulate {
+        quote! {
+            if !__errors.is_empty() {
+                return std::result::Result::Err(__errors.swap_remove(0));
+            }
+        }
+    } else {
+        quote! {
+            if !__errors.is_empty() {
+                return std::result::Result::Err(if __errors.len() == 1 {
+                    __errors.pop().unwrap()
+                } else {
+                    ::procenv::Error::Multiple { errors: __errors }
+                });
+            }
+        }
+    };
+
+    let dotenv_load = generate_dotenv_load(env_config_attr.dotenv.as_ref());
+
+    let case_insensitive_scope = generate_case_insensitive_scope(env_config_attr);
+
+    let prefix_env_dispatch = env_config_attr.prefix_env.as_ref().map_or_else(
+        || quote! {},
+        |prefix_env| {
+            quote! {
+                if let std::result::Result::Ok(__runtime_prefix) = ::procenv::runtime::var(#prefix_env) {
+                    return Self::__stage_env_with_external_prefix(
+                        std::option::Option::Some(__runtime_prefix),
+                    );
+                }
+            }
+        },
+    );
+
+    quote! {
+        impl #impl_generics #struct_name #type_generics #where_clause {
+            /// Overwrite fields whose environment variables are set.
+            ///
+            /// Fields whose variables are unset keep their current values,
+            /// so unlike `from_env()` a missing required variable is not an
+            /// error, and defaults and profile values are never applied.
+            /// Set values are parsed and checked as in `from_env()`.
+            ///
+            /// Fields, including those of flattened structs, are only
+            /// changed once every set variable has loaded, so on error the
+            /// instance is left untouched.
+            ///
+            /// # Errors
+            /// Returns an error if any set variable fails to parse or
+            /// validate.
+            pub fn apply_env(&mut self) -> std::result::Result<(), ::procenv::Error> {
+                let __apply = Self::__stage_env()?;
+                __apply(self);
+                std::result::Result::Ok(())
+            }
+
+            /// Load the set env vars, returning a closure that assigns them
+            /// (internal, generated by macro; backs `apply_env()`).
+            #[doc(hidden)]
+            pub fn __stage_env()
+                -> std::result::Result<impl FnOnce(&mut Self), ::procenv::Error>
+            {
+                #case_insensitive_scope
+                #prefix_env_dispatch
+
+                Self::__stage_env_with_external_prefix(std::option::Option::None)
+            }
+
+            /// `__stage_env()` reading env vars under an external prefix.
+            #[doc(hidden)]
+            pub fn __stage_env_with_external_prefix(
+                __prefix: std::option::Option<std::string::String>,
+            ) -> std::result::Result<impl FnOnce(&mut Self), ::procenv::Error> {
+                #case_insensitive_scope
+                #dotenv_load
+
+                let __external_prefix: std::option::Option<&str> = __prefix.as_deref();
+                let mut __errors: std::vec::Vec<::procenv::Error> = std::vec::Vec::new();
+
+                // Read every set var before touching any field
+                #(#readers)*
+                #(#nested)*
+                #return_errors
+
+                std::result::Result::Ok(move |__self: &mut Self| {
+                    #(#assignments)*
+                    #(#nested_assignments)*
+                })
+            }
+        }
+    }
+}
+
+/// Generate code reading one field's var into an `Option` local.
+///
+/// The local is `None` when the var is unset or fails to load, in which
+/// case any error has been pushed to `__errors`.
+fn generate_field_reader(
+    field: &dyn FieldGenerator,
+    env_config_attr: &EnvConfigAttr,
+) -> QuoteStream {
+    let name = field.name();
+    let effective_var_ident = format_ident!("__{}_effective_var", name);
+    let effective_var =
+        FieldFactory::effective_var_expr(field.env_var_name().unwrap_or(""), field.default_from());
+    let read = FieldFactory::read_expr(&quote! { &#effective_var_ident }, field, true);
+    let after_parse = generate_after_parse(field, name, &quote! { &#effective_var_ident }, false);
+    let value_checks =
+        generate_value_checks(field, &quote! { #name }, &quote! { &#effective_var_ident });
+    let audit = generate_audit_call(field, env_config_attr);
+
+    // Secrecy types are always redacted, like their regular loaders
+    let (ty, parse_expr, type_desc, secret) = if field.is_secrecy_type() {
+        let (ty, parse_expr) = field.field_type().map_or_else(
+            || {
+                (
+                    quote! { ::procenv::SecretString },
+                    quote! {
+                        std::result::Result::<_, std::convert::Infallible>::Ok(
+                            ::procenv::SecretString::from(val.as_str())
+                        )
+                    },
+                )
+            },
+            |inner| {
+                (
+                    quote! { ::procenv::SecretBox<#inner> },
+                    quote! { val.parse::<#inner>().map(|v| ::procenv::SecretBox::init_with(|| v)) },
+                )
+            },
+        );
+
+        (ty, parse_expr, field.type_name(), quote! { true })
+    } else {
+        let ty = field.field_type().expect("env-loaded field must have type");
+        let (parse_expr, type_desc) =
+            generate_value_parse_expr(field, &quote! { &#effective_var_ident });
+
+        (
+            quote! { #ty },
+            parse_expr,
+            type_desc,
+            FieldFactory::redact_expr(field.is_secret()),
+        )
+    };
+
+    let reader = quote! {
+        let #name: std::option::Option<#ty> = match #read {
+            std::result::Result::Ok(val) => {
+                match #parse_expr {
+                    std::result::Result::Ok(v) => std::option::Option::Some(v),
+                    std::result::Result::Err(e) => {
+                        __errors.push(::procenv::Error::parse(
+                            &#effective_var_ident,
+                            if #secret { ::procenv::runtime::REDACTED.to_string() } else { val },
+                            #secret,
+                            #type_desc,
+                            std::boxed::Box::new(e),
+                        ));
+                        std::option::Option::None
+                    }
+                }
+            }
+            std::result::Result::Err(std::env::VarError::NotPresent) => std::option::Option::None,
+            std::result::Result::Err(std::env::VarError::NotUnicode(__raw)) => {
+                __errors.push(::procenv::Error::invalid_utf8(&#effective_var_ident, &__raw, #secret));
+                std::option::Option::None
+            }
+        };
+
+        #after_parse
+        #value_checks
+    };
+    let reader = generate_error_redaction(field, reader);
+
+    quote! {
+        let #effective_var_ident: std::string::String = #effective_var;
+
+        #reader
+        #audit
+    }
+}
+
+/// Generate the nested `__stage_env` call for a flattened field.
+///
+/// Mirrors the flatten loader: a `prefix` is applied on top of the external
+/// prefix, otherwise the nested struct loads as it would on its own. The
+/// staged closure lands in `__<field>_apply`, `None` if staging failed.
+fn generate_nested_stage(field: &dyn FieldGenerator) -> QuoteStream {
+    let name = field.name();
+    let ty = field.field_type().expect("flattened field must have type");
+    let apply = format_ident!("__{}_apply", name);
+
+    let stage_call = field.flatten_prefix().map_or_else(
+        || quote! { <#ty>::__stage_env() },
+        |prefix| {
+            let nested_prefix = FieldFactory::external_var_expr(&quote! { #prefix });
+            quote! {
+                <#ty>::__stage_env_with_external_prefix(std::option::Option::Some(#nested_prefix))
+            }
+        },
+    );
+
+    quote! {
+        let #apply = match #stage_call {
+            std::result::Result::Ok(__apply) => std::option::Option::Some(__apply),
+            std::result::Result::Err(::procenv::Error::Multiple { errors }) => {
+                __errors.extend(errors);
+                std::option::Option::None
+            }
+            std::result::Result::Err(other) => {
+                __errors.push(other);
+                std::option::Option::None
+            }
+        };
+    }
+}
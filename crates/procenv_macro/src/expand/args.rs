@@ -9,6 +9,8 @@
 //!   - `from_args()` - Load from `std::env::args()`
 //!   - `from_args_from(iter)` - Load from custom iterator (for testing)
 //!   - `from_args_with_sources()` - With source attribution
+//!   - `from_arg_matches(&matches)` - Load from matches parsed by the caller's `Command`
+//!   - `clap_args()` - The generated `Arg`s, for adding to such a `Command`
 //!
 //! # Priority Order
 //!
@@ -50,19 +52,32 @@
 
 use proc_macro2::TokenStream as QuoteStream;
 use quote::{format_ident, quote};
-use syn::Ident;
+use syn::{Generics, Ident};
 
-use crate::field::FieldGenerator;
+use crate::field::{FieldFactory, FieldGenerator};
 use crate::parse::EnvConfigAttr;
 
-use super::env::{generate_dotenv_load, generate_field_loader, generate_profile_setup};
+use super::env::{
+    generate_after_parse, generate_case_insensitive_scope, generate_dotenv_flags,
+    generate_dotenv_load, generate_empty_struct_fallback, generate_error_return,
+    generate_error_sink, generate_field_loader, generate_field_loader_with_external_prefix,
+    generate_parse_expr, generate_profile_setup, generate_simple_source_tracking,
+    generate_value_checks,
+};
 
 /// Generate the `from_args()` method for CLI argument integration.
+#[expect(
+    clippy::too_many_lines,
+    reason = "proc-macro code generation inherently requires verbose quote! blocks"
+)]
 pub fn generate_from_args_impl(
     struct_name: &Ident,
+    generics: &Generics,
     generators: &[Box<dyn FieldGenerator>],
     env_config: &EnvConfigAttr,
 ) -> QuoteStream {
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
     // Collect clap Arg definitions for CLI-enabled fields
     let clap_args: Vec<QuoteStream> = generators
         .iter()
@@ -75,10 +90,13 @@ pub fn generate_from_args_impl(
         .filter_map(|g| g.generate_cli_extraction())
         .collect();
 
+    // With `prefix_env`, env vars are read under the runtime prefix it names
+    let prefixed = env_config.prefix_env.is_some();
+
     // Generate loaders that check CLI first, then env
     let loaders: Vec<QuoteStream> = generators
         .iter()
-        .map(|g| generate_cli_aware_loader(g.as_ref(), env_config))
+        .map(|g| generate_cli_aware_loader(g.as_ref(), env_config, prefixed))
         .collect();
 
     // Generate profile setup code
@@ -87,7 +105,7 @@ pub fn generate_from_args_impl(
     // Generate source tracking with CLI awareness
     let source_tracking: Vec<QuoteStream> = generators
         .iter()
-        .map(|g| generate_cli_aware_source_tracking(g.as_ref()))
+        .map(|g| generate_cli_aware_source_tracking(g.as_ref(), prefixed))
         .collect();
 
     // Generate assignments
@@ -97,17 +115,108 @@ pub fn generate_from_args_impl(
     // Dotenv loading
     let dotenv_load = generate_dotenv_load(env_config.dotenv.as_ref());
 
-    let dotenv_loaded_flag = if env_config.dotenv.is_some() {
-        quote! { let __dotenv_loaded = true; }
+    let dotenv_loaded_flag = generate_dotenv_flags(env_config.dotenv.as_ref());
+
+    // Collect env var names (including `default_from` fallbacks) for pre-dotenv check
+    let env_var_names: Vec<_> = generators
+        .iter()
+        .flat_map(|g| g.env_var_name().into_iter().chain(g.default_from()))
+        .collect();
+
+    let case_insensitive_scope = generate_case_insensitive_scope(env_config);
+
+    // A struct given any CLI value is not empty, so `allow_empty_struct`
+    // only falls back to `Default` when none of these is set
+    let cli_vars: Vec<Ident> = generators
+        .iter()
+        .filter(|g| g.cli_config().is_some())
+        .map(|g| format_ident!("__{}_cli", g.name()))
+        .collect();
+
+    // Errors always accumulate here, `no_accumulate` only applies to `from_env()`
+    let error_sink = generate_error_sink(false);
+    let error_return = generate_error_return(false);
+
+    let steps = generate_empty_struct_fallback(
+        generators,
+        env_config,
+        true,
+        &cli_vars,
+        quote! {
+            #error_sink
+            let mut __sources = ::procenv::ConfigSources::new();
+            __sources.set_reload_immutable(Self::__reload_immutable_fields());
+
+            // Read and validate profile (if configured)
+            #profile_setup
+
+            // Load each field (CLI first, then env)
+            #(#loaders)*
+
+            // Track sources
+            #(#source_tracking)*
+            __sources.set_profile(__profile.clone());
+
+            // Check for errors
+            #error_return
+
+            std::result::Result::Ok((
+                Self {
+                    #(#assignments),*
+                },
+                __sources
+            ))
+        },
+    );
+
+    // The runtime prefix is read from the process environment, before any
+    // dotenv file, as `from_env()` reads it
+    let external_prefix = env_config.prefix_env.as_ref().map_or_else(
+        || {
+            quote! {
+                // Define external prefix as None for regular from_args calls
+                let __external_prefix: std::option::Option<&str> = std::option::Option::None;
+            }
+        },
+        |prefix_env| {
+            quote! {
+                // Read env vars under the runtime prefix named by `prefix_env`
+                let __runtime_prefix = ::procenv::runtime::var(#prefix_env).ok();
+                let __external_prefix: std::option::Option<&str> = __runtime_prefix.as_deref();
+            }
+        },
+    );
+
+    let pre_dotenv_vars = if prefixed {
+        quote! {
+            let __pre_dotenv_vars: std::collections::HashSet<std::string::String> = [
+                #(#env_var_names),*
+            ]
+            .iter()
+            .map(|var| {
+                ::procenv::runtime::join_prefix(
+                    __external_prefix.unwrap_or(""),
+                    var,
+                    Self::__PREFIX_SEPARATOR,
+                )
+            })
+            .filter(|var| ::procenv::runtime::var(var).is_ok())
+            .collect();
+        }
     } else {
-        quote! { let __dotenv_loaded = false; }
+        quote! {
+            let __pre_dotenv_vars: std::collections::HashSet<&str> = [
+                #(#env_var_names),*
+            ]
+            .iter()
+            .filter(|var| ::procenv::runtime::var(var).is_ok())
+            .copied()
+            .collect();
+        }
     };
 
-    // Collect env var names for pre-dotenv check
-    let env_var_names: Vec<_> = generators.iter().filter_map(|g| g.env_var_name()).collect();
-
     quote! {
-        impl #struct_name {
+        impl #impl_generics #struct_name #type_generics #where_clause {
             /// Load configuration from CLI arguments and environment.
             ///
             /// Priority: CLI arguments > Environment variables > .env files > Defaults
@@ -126,15 +235,36 @@ pub fn generate_from_args_impl(
                 std::result::Result::Ok(config)
             }
 
+            /// Load configuration from matches parsed by an existing clap `Command`.
+            ///
+            /// No command is built and no arguments are read; fields take their
+            /// values from `matches`, falling back to env vars and defaults.
+            /// Register the arguments with `Self::clap_args()` on the command or
+            /// subcommand that produced `matches`; fields whose argument is not
+            /// defined there load as if it were not given.
+            pub fn from_arg_matches(
+                matches: &::procenv::clap::ArgMatches,
+            ) -> std::result::Result<Self, ::procenv::Error> {
+                let (config, _) = Self::__from_args_matches(matches)?;
+                std::result::Result::Ok(config)
+            }
+
+            /// Return the CLI arguments `from_args()` parses, for adding to an
+            /// existing clap `Command` read by `from_arg_matches()`.
+            #[must_use]
+            pub fn clap_args() -> std::vec::Vec<::procenv::clap::Arg> {
+                std::vec![#(#clap_args),*]
+            }
+
             /// Load configuration from CLI arguments with source attribution.
             pub fn from_args_with_sources() -> std::result::Result<(Self, ::procenv::ConfigSources), ::procenv::Error> {
                 let __cmd = ::procenv::clap::Command::new(env!("CARGO_PKG_NAME"))
                     .version(env!("CARGO_PKG_VERSION"))
-                    #(.arg(#clap_args))*;
+                    .args(Self::clap_args());
 
                 let __matches = __cmd.get_matches();
 
-                Self::__from_args_matches(__matches)
+                Self::__from_args_matches(&__matches)
             }
 
             /// Load configuration from a custom argument iterator with source attribution.
@@ -145,107 +275,98 @@ pub fn generate_from_args_impl(
             {
                 let __cmd = ::procenv::clap::Command::new(env!("CARGO_PKG_NAME"))
                     .version(env!("CARGO_PKG_VERSION"))
-                    #(.arg(#clap_args))*;
+                    .args(Self::clap_args());
 
                 let __matches = __cmd.try_get_matches_from(args)
                     .map_err(|e| ::procenv::Error::Cli { message: e.to_string() })?;
 
-                Self::__from_args_matches(__matches)
+                Self::__from_args_matches(&__matches)
             }
 
             /// Internal helper to process clap matches into config.
-            fn __from_args_matches(__matches: ::procenv::clap::ArgMatches) -> std::result::Result<(Self, ::procenv::ConfigSources), ::procenv::Error> {
+            fn __from_args_matches(__matches: &::procenv::clap::ArgMatches) -> std::result::Result<(Self, ::procenv::ConfigSources), ::procenv::Error> {
+                #case_insensitive_scope
 
                 // Extract CLI values
                 #(#cli_extractions)*
 
+                #external_prefix
+
                 // Track pre-dotenv env vars
-                let __pre_dotenv_vars: std::collections::HashSet<&str> = [
-                    #(#env_var_names),*
-                ]
-                .iter()
-                .filter(|var| std::env::var(var).is_ok())
-                .copied()
-                .collect();
+                #pre_dotenv_vars
 
                 // Load dotenv
                 #dotenv_load
                 #dotenv_loaded_flag
 
-                // Define external prefix as None for regular from_args calls
-                let __external_prefix: std::option::Option<&str> = std::option::Option::None;
-
-                // Error accumulator
-                let mut __errors: std::vec::Vec<::procenv::Error> = std::vec::Vec::new();
-                let mut __sources = ::procenv::ConfigSources::new();
-
-                // Read and validate profile (if configured)
-                #profile_setup
-
-                // Load each field (CLI first, then env)
-                #(#loaders)*
-
-                // Track sources
-                #(#source_tracking)*
-
-                // Check for errors
-                if !__errors.is_empty() {
-                    return std::result::Result::Err(if __errors.len() == 1 {
-                        __errors.pop().unwrap()
-                    } else {
-                        ::procenv::Error::Multiple { errors: __errors }
-                    });
-                }
-
-                std::result::Result::Ok((
-                    Self {
-                        #(#assignments),*
-                    },
-                    __sources
-                ))
+                #steps
             }
         }
     }
 }
 
 /// Generate a loader that checks CLI value first, then falls back to env.
+///
+/// With `prefixed`, the env fallback reads under `__external_prefix`.
 fn generate_cli_aware_loader(
     field: &dyn FieldGenerator,
     env_config: &EnvConfigAttr,
+    prefixed: bool,
 ) -> QuoteStream {
     let name = field.name();
     let cli_var = format_ident!("__{}_cli", name);
     let from_cli_var = format_ident!("__{}_from_cli", name);
 
+    // Use the profile-aware field loader for env fallback
+    let env_loader = if prefixed {
+        generate_field_loader_with_external_prefix(field, env_config)
+    } else {
+        generate_field_loader(field, env_config)
+    };
+
     // Check if this field has CLI config
     if field.cli_config().is_some() {
-        // Use the profile-aware field loader for env fallback
-        let env_loader = generate_field_loader(field, env_config);
-
         // For CLI-enabled fields: check CLI first, then env
         let cli_arg_name = format!("--{}", field.cli_config().unwrap().long.as_ref().unwrap());
         let type_name = field.type_name();
-        let secret = field.is_secret();
+        let secret = FieldFactory::redact_expr(field.is_secret());
 
         // Generate the parse expression based on format
         let (parse_expr, format_name) = field.format_config().map_or_else(
-            || (quote! { cli_val.parse() }, type_name.clone()),
+            || {
+                (
+                    generate_parse_expr(field, &quote! { cli_val }),
+                    type_name.clone(),
+                )
+            },
             |format| {
                 let expr = match format {
                     "json" => quote! { ::serde_json::from_str(cli_val) },
                     "toml" => quote! { ::toml::from_str(cli_val) },
                     "yaml" => quote! { ::serde_saphyr::from_str(cli_val) },
+                    "auto" => quote! { ::procenv::runtime::deserialize_auto(cli_val) },
                     _ => quote! { cli_val.parse() },
                 };
-                (expr, format.to_uppercase())
+                let expr = FieldFactory::deserialize_with_expr(expr, field.deserialize_with());
+                (expr, FieldFactory::format_label(format))
             },
         );
 
+        // CLI values get the same `one_of`/finiteness checks as env values
+        let cli_parsed = format_ident!("__{}_cli_parsed", name);
+        let after_parse =
+            generate_after_parse(field, &cli_parsed, &quote! { #cli_arg_name }, false);
+        let value_checks =
+            generate_value_checks(field, &quote! { #cli_parsed }, &quote! { #cli_arg_name });
+        let cli_parsed_ty = field
+            .field_type()
+            .map_or_else(|| quote! {}, |ty| quote! { : std::option::Option<#ty> });
+
         quote! {
             let #from_cli_var: bool;
             let #name = if let std::option::Option::Some(ref cli_val) = #cli_var {
                 #from_cli_var = true;
-                match #parse_expr {
+                let #cli_parsed #cli_parsed_ty = match #parse_expr {
                     std::result::Result::Ok(v) => std::option::Option::Some(v),
                     std::result::Result::Err(e) => {
                         __errors.push(::procenv::Error::parse(
@@ -257,7 +378,10 @@ fn generate_cli_aware_loader(
                         ));
                         std::option::Option::None
                     }
-                }
+                };
+                #after_parse
+                #value_checks
+                #cli_parsed
             } else {
                 #from_cli_var = false;
                 #env_loader
@@ -266,7 +390,7 @@ fn generate_cli_aware_loader(
         }
     } else {
         // Non-CLI fields: use profile-aware field loader
-        generate_field_loader(field, env_config)
+        env_loader
     }
 }
 
@@ -274,21 +398,39 @@ fn generate_cli_aware_loader(
 ///
 /// Determines the source of a field's value with priority:
 /// CLI > Profile > Env/Dotenv > Default > `NotSet`
-fn generate_cli_aware_source_tracking(field: &dyn FieldGenerator) -> QuoteStream {
+///
+/// With `prefixed`, env vars are named under `__external_prefix`.
+fn generate_cli_aware_source_tracking(field: &dyn FieldGenerator, prefixed: bool) -> QuoteStream {
     let name = field.name();
     let name_str = name.to_string();
     let from_cli_var = format_ident!("__{}_from_cli", name);
     let source_ident = format_ident!("__{}_source", name);
 
-    // Flatten fields or fields without env var use standard tracking
-    let Some(env_var) = field.env_var_name() else {
-        return field.generate_source_tracking();
+    // Non-CLI fields, flatten fields and fields without env var use standard tracking
+    let Some(env_var) = field
+        .env_var_name()
+        .filter(|_| field.cli_config().is_some())
+    else {
+        return if prefixed {
+            generate_simple_source_tracking(field)
+        } else {
+            field.generate_source_tracking()
+        };
     };
 
-    // Non-CLI fields use standard tracking
-    if field.cli_config().is_none() {
-        return field.generate_source_tracking();
-    }
+    let (effective_var, env_var) = if prefixed {
+        let effective_var_ident = format_ident!("__{}_effective_var", name);
+        let effective_var = FieldFactory::effective_var_expr(env_var, field.default_from());
+        (
+            quote! { let #effective_var_ident: std::string::String = #effective_var; },
+            quote! { &#effective_var_ident },
+        )
+    } else {
+        (
+            quote! {},
+            FieldFactory::var_expr(env_var, field.default_from()),
+        )
+    };
 
     // CLI-enabled field: check if value came from CLI, profile, env, or default
     let has_profile = field.profile_config().is_some();
@@ -309,6 +451,7 @@ fn generate_cli_aware_source_tracking(field: &dyn FieldGenerator) -> QuoteStream
         };
 
         quote! {
+            #effective_var
             let #source_ident = if #from_cli_var {
                 ::procenv::ValueSource::new(#env_var, ::procenv::Source::Cli)
             } else if #profile_used_ident {
@@ -316,9 +459,9 @@ fn generate_cli_aware_source_tracking(field: &dyn FieldGenerator) -> QuoteStream
                     #env_var,
                     ::procenv::Source::Profile(__profile.clone().unwrap_or_default())
                 )
-            } else if std::env::var(#env_var).is_ok() {
+            } else if ::procenv::runtime::var(#env_var).is_ok() {
                 if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
-                    ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(None))
+                    ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(__dotenv_path.clone()))
                 } else {
                     ::procenv::ValueSource::new(#env_var, ::procenv::Source::Environment)
                 }
@@ -344,11 +487,12 @@ fn generate_cli_aware_source_tracking(field: &dyn FieldGenerator) -> QuoteStream
         };
 
         quote! {
+            #effective_var
             let #source_ident = if #from_cli_var {
                 ::procenv::ValueSource::new(#env_var, ::procenv::Source::Cli)
-            } else if std::env::var(#env_var).is_ok() {
+            } else if ::procenv::runtime::var(#env_var).is_ok() {
                 if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
-                    ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(None))
+                    ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(__dotenv_path.clone()))
                 } else {
                     ::procenv::ValueSource::new(#env_var, ::procenv::Source::Environment)
                 }
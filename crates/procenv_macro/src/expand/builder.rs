@@ -0,0 +1,338 @@
+//! Programmatic construction code generation.
+//!
+//! This module generates `builder()` and a `<Struct>Builder` type with one
+//! setter per field, for building a config in code without reading any
+//! environment variables. `build()` follows the loading semantics: unset
+//! required fields are errors, unset `default` fields parse their default,
+//! unset optional fields are `None`, and unset flattened fields are built
+//! from their own builder.
+//!
+//! # Generated Code Pattern
+//!
+//! ```rust,ignore
+//! pub struct ConfigBuilder {
+//!     port: Option<u16>,
+//!     // ...
+//! }
+//!
+//! impl ConfigBuilder {
+//!     pub fn port(mut self, value: u16) -> Self { /* ... */ }
+//!
+//!     pub fn build(self) -> Result<Config, Error> {
+//!         Config::__from_builder(self)
+//!     }
+//! }
+//! ```
+//!
+//! The checks live in a hidden `__from_builder()` on the config struct, so
+//! they can use the struct's own helpers such as `__ALLOW_UNMASK`.
+//!
+//! With `#[env_config(derive_default)]`, [`generate_default_impl`] also
+//! emits `impl Default` as a `build()` of an empty builder, so the attribute
+//! defaults are the only place default values are written down.
+
+use proc_macro2::TokenStream as QuoteStream;
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{Error as SynError, Field, Generics, Ident, Result as SynResult, Visibility};
+
+use crate::field::{FieldFactory, FieldGenerator};
+use crate::parse::EnvConfigAttr;
+
+use super::env::generate_value_parse_expr;
+
+/// Generate `builder()`, the builder type and its `build()`.
+///
+/// `fields` and `generators` describe the same fields in the same order;
+/// the declared field types come from the former.
+pub fn generate_builder_impl(
+    struct_name: &Ident,
+    vis: &Visibility,
+    generics: &Generics,
+    fields: &Punctuated<Field, Comma>,
+    generators: &[Box<dyn FieldGenerator>],
+    env_config_attr: &EnvConfigAttr,
+) -> QuoteStream {
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let builder_name = format_ident!("{}Builder", struct_name);
+    let turbofish = type_generics.as_turbofish();
+
+    let names: Vec<&Ident> = generators.iter().map(|g| g.name()).collect();
+    let types: Vec<&syn::Type> = fields.iter().map(|f| &f.ty).collect();
+
+    let setters: Vec<QuoteStream> = names
+        .iter()
+        .zip(&types)
+        .map(|(name, ty)| {
+            let doc = format!("Set `{name}`, overriding how `build()` fills it.");
+            quote! {
+                #[doc = #doc]
+                #[must_use]
+                pub fn #name(mut self, value: #ty) -> Self {
+                    self.#name = std::option::Option::Some(value);
+                    self
+                }
+            }
+        })
+        .collect();
+
+    let resolvers: Vec<QuoteStream> = generators
+        .iter()
+        .map(|g| generate_field_resolver(g.as_ref()))
+        .collect();
+
+    let assignments: Vec<QuoteStream> = generators
+        .iter()
+        .map(|g| {
+            let name = g.name();
+            if g.is_optional() || (g.is_skip() && !g.is_serde_flatten()) {
+                quote! { #name }
+            } else {
+                quote! { #name: #name.unwrap() }
+            }
+        })
+        .collect();
+
+    let return_errors = if env_config_attr.no_accumulate {
+        quote! {
+            if !__errors.is_empty() {
+                return std::result::Result::Err(__errors.swap_remove(0));
+            }
+        }
+    } else {
+        quote! {
+            if !__errors.is_empty() {
+                return std::result::Result::Err(if __errors.len() == 1 {
+                    __errors.pop().unwrap()
+                } else {
+                    ::procenv::Error::Multiple { errors: __errors }
+                });
+            }
+        }
+    };
+
+    let builder_doc =
+        format!("Builder for [`{struct_name}`], created by [`{struct_name}::builder()`].");
+
+    quote! {
+        #[doc = #builder_doc]
+        ///
+        /// Fields left unset are filled in by `build()` as loading would,
+        /// without reading any environment variables.
+        #vis struct #builder_name #impl_generics #where_clause {
+            #(#names: std::option::Option<#types>,)*
+        }
+
+        impl #impl_generics std::default::Default for #builder_name #type_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#names: std::option::Option::None,)*
+                }
+            }
+        }
+
+        impl #impl_generics #builder_name #type_generics #where_clause {
+            #(#setters)*
+
+            /// Build the config, filling in unset fields.
+            ///
+            /// Unset `default` fields parse their default and unset optional
+            /// fields are `None`. Flattened fields left unset are built with
+            /// their own builder. Profile values are not applied.
+            ///
+            /// # Errors
+            /// Returns an error for each required field left unset, and for
+            /// any default that fails to parse.
+            pub fn build(self) -> std::result::Result<#struct_name #type_generics, ::procenv::Error> {
+                #struct_name #turbofish::__from_builder(self)
+            }
+        }
+
+        impl #impl_generics #struct_name #type_generics #where_clause {
+            /// Start building this config in code, without environment variables.
+            pub fn builder() -> #builder_name #type_generics {
+                std::default::Default::default()
+            }
+
+            /// Resolve a builder's fields (internal, generated by macro; backs `build()`).
+            #[doc(hidden)]
+            pub fn __from_builder(
+                __builder: #builder_name #type_generics,
+            ) -> std::result::Result<Self, ::procenv::Error> {
+                let mut __errors: std::vec::Vec<::procenv::Error> = std::vec::Vec::new();
+
+                #(#resolvers)*
+                #return_errors
+
+                std::result::Result::Ok(Self {
+                    #(#assignments,)*
+                })
+            }
+        }
+    }
+}
+
+/// Generate `impl Default` for `#[env_config(derive_default)]`.
+///
+/// Flattened and serde-flattened fields are set to their type's `Default`
+/// before building; every other field must be one `build()` can fill on
+/// its own, so a required field without a `default` is an error on that
+/// field.
+pub fn generate_default_impl(
+    struct_name: &Ident,
+    generics: &Generics,
+    generators: &[Box<dyn FieldGenerator>],
+) -> SynResult<QuoteStream> {
+    let mut setters = Vec::new();
+
+    for field in generators {
+        let name = field.name();
+
+        if field.is_flatten() || field.is_serde_flatten() {
+            let ty = field.field_type().expect("flatten field must have type");
+            setters.push(quote! { .#name(<#ty as std::default::Default>::default()) });
+        } else if !field.is_optional() && !field.is_skip() && field.default_value().is_none() {
+            return Err(SynError::new_spanned(
+                name,
+                format!(
+                    "`derive_default` needs a value for `{name}`: add `default = \"...\"` \
+                     or make it `optional`"
+                ),
+            ));
+        }
+    }
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let panic_msg = format!("invalid `default` in `{struct_name}`: {{}}");
+
+    Ok(quote! {
+        impl #impl_generics std::default::Default for #struct_name #type_generics #where_clause {
+            /// Build the config from its `#[env(default = "...")]` values.
+            ///
+            /// Optional fields are `None` and flattened fields use their
+            /// own `Default`. No environment variables are read, except
+            /// those a `${VAR}` default expands.
+            ///
+            /// # Panics
+            /// Panics if a default fails to parse or names an unset `${VAR}`.
+            fn default() -> Self {
+                match Self::builder()#(#setters)*.build() {
+                    std::result::Result::Ok(v) => v,
+                    std::result::Result::Err(e) => panic!(#panic_msg, e),
+                }
+            }
+        }
+    })
+}
+
+/// Generate code resolving one builder field into a local.
+///
+/// Optional and skipped fields resolve to the field value itself; every
+/// other field resolves to an `Option` that is `None` once an error has been
+/// pushed to `__errors`.
+fn generate_field_resolver(field: &dyn FieldGenerator) -> QuoteStream {
+    let name = field.name();
+
+    if field.is_optional() {
+        return quote! { let #name = __builder.#name.flatten(); };
+    }
+
+    if field.is_skip() && !field.is_serde_flatten() {
+        return quote! { let #name = __builder.#name.unwrap_or_default(); };
+    }
+
+    if field.is_flatten() {
+        let ty = field.field_type().expect("flatten field must have type");
+        return quote! {
+            let #name = match __builder.#name {
+                std::option::Option::Some(v) => std::option::Option::Some(v),
+                std::option::Option::None => match <#ty>::builder().build() {
+                    std::result::Result::Ok(v) => std::option::Option::Some(v),
+                    std::result::Result::Err(::procenv::Error::Multiple { errors }) => {
+                        __errors.extend(errors);
+                        std::option::Option::None
+                    }
+                    std::result::Result::Err(e) => {
+                        __errors.push(e);
+                        std::option::Option::None
+                    }
+                },
+            };
+        };
+    }
+
+    // Serde flatten fields have no var, so their errors name the field
+    let var = field
+        .env_var_name()
+        .map_or_else(|| name.to_string(), ToString::to_string);
+
+    let unset = field.default_value().map_or_else(
+        || {
+            let help = format!("call `.{name}(...)` on the builder");
+            quote! {
+                __errors.push(::procenv::Error::Missing {
+                    var: #var.to_string(),
+                    help: #help.to_string(),
+                });
+                std::option::Option::None
+            }
+        },
+        |default| {
+            // Overrides are env vars, so `merge_env` defaults parse as plain JSON
+            let (parse_expr, type_desc) = if field.merges_env() {
+                let expr = FieldFactory::deserialize_with_expr(
+                    FieldFactory::json_expr(&quote! { #var }, false),
+                    field.deserialize_with(),
+                );
+                (expr, "JSON data".to_string())
+            } else {
+                generate_value_parse_expr(field, &quote! { #var })
+            };
+            let secret = FieldFactory::redact_expr(field.is_secret());
+
+            let parse = quote! {
+                match #parse_expr {
+                    std::result::Result::Ok(v) => std::option::Option::Some(v),
+                    std::result::Result::Err(e) => {
+                        __errors.push(::procenv::Error::parse(
+                            #var,
+                            val,
+                            #secret,
+                            #type_desc,
+                            std::boxed::Box::new(e),
+                        ));
+                        std::option::Option::None
+                    }
+                }
+            };
+
+            FieldFactory::expand_default_expr(default, &quote! { #var }).map_or_else(
+                || {
+                    quote! {
+                        let val: std::string::String = #default.to_string();
+                        #parse
+                    }
+                },
+                |expand| {
+                    quote! {
+                        match #expand {
+                            std::result::Result::Ok(val) => #parse,
+                            std::result::Result::Err(e) => {
+                                __errors.push(e);
+                                std::option::Option::None
+                            }
+                        }
+                    }
+                },
+            )
+        },
+    );
+
+    quote! {
+        let #name = match __builder.#name {
+            std::option::Option::Some(v) => std::option::Option::Some(v),
+            std::option::Option::None => { #unset }
+        };
+    }
+}
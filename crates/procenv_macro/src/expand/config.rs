@@ -5,16 +5,24 @@
 //!
 //! # Generated Methods
 //!
-//! - [`generate_from_config_impl`] - Main `from_config()` and `from_config_with_sources()`
-//! - [`generate_config_defaults_impl`] - Internal `__config_defaults()` for nested structs
+//! - [`generate_from_config_impl`] - Main `from_config()`, `from_config_with_sources()`,
+//!   `config_merged_value()` and `config_loaded_files()`
+//! - [`generate_from_json_env_impl`] - `from_json_env()` for `from_json_var`
+//! - [`generate_config_defaults_impl`] - Internal `__config_defaults()` and
+//!   `__config_profile_defaults()` for nested structs
 //!
 //! # Layering Order
 //!
 //! Configuration is loaded in this priority order (lowest to highest):
 //!
 //! 1. **Macro defaults** - `#[env(default = "...")]` attributes
-//! 2. **Config files** - In order specified (later files override earlier)
-//! 3. **Environment variables** - Highest priority
+//! 2. **Embedded config** - `#[env_config(embedded = "...")]`, compiled in
+//! 3. **Config files** - In order specified (later files override earlier),
+//!    then each field's own `#[env(file = "...")]`
+//! 4. **Environment variables** - Highest priority
+//!
+//! With `#[env_config(files_override_env)]`, environment variables are applied
+//! right after the embedded config, so only the config files win over them.
 //!
 //! # Generated Code Pattern
 //!
@@ -42,18 +50,25 @@
 //!
 //! The `__config_defaults()` method is generated for all structs to support
 //! flatten fields. It returns a JSON object with default values that can
-//! be merged into the parent's defaults.
+//! be merged into the parent's defaults, together with the dotted paths it
+//! defined and the [`Source`](procenv::Source) each path should be
+//! attributed to. `from_config_with_sources()` uses those paths to report
+//! `Source::Default` or `Source::Profile` for nested fields that only got a
+//! compiled default.
 
 use std::string::String;
 
 use proc_macro2::TokenStream as QuoteStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{Generics, Ident};
 
-use crate::field::FieldGenerator;
-use crate::parse::EnvConfigAttr;
+use crate::field::{FieldFactory, FieldGenerator};
+use crate::parse::{EnvConfigAttr, embedded_format};
 
-use super::env::generate_dotenv_load;
+use super::env::{
+    generate_after_parse, generate_case_insensitive_scope, generate_dotenv_flags,
+    generate_dotenv_load, generate_value_checks,
+};
 
 /// Generate the `from_config()` method for file-based configuration loading.
 ///
@@ -88,54 +103,63 @@ pub fn generate_from_config_impl(
         })
         .collect();
 
-    // Generate env prefix setup
-    let env_prefix = env_config_attr.prefix.as_ref().map_or_else(
-        || quote! {},
-        |prefix| quote! { builder = builder.env_prefix(#prefix); },
-    );
-
-    // Generate direct env var mappings for fields with custom var names
-    let env_mapping_calls: Vec<QuoteStream> = generators
+    // Fields read from a file of their own, layered over the struct's files
+    let field_file_loads: Vec<QuoteStream> = generators
         .iter()
         .filter_map(|g| {
-            let field_name = g.name().to_string();
-
-            if g.is_flatten() {
-                // For flatten fields, call the nested type's env mappings method
-                let ty = g.field_type()?;
-                let flatten_prefix = g.flatten_prefix().unwrap_or("");
-
-                return Some(quote! {
-                    // Register nested env mappings with combined prefix
-                    for (nested_field, nested_var) in <#ty>::__env_mappings() {
-                        let full_path = format!("{}.{}", #field_name, nested_field);
-                        let full_var = format!("{}{}", #flatten_prefix, nested_var);
-                        builder = builder.env_mapping(&full_path, &full_var);
-                    }
-                });
-            }
-
-            let env_var = g.env_var_name()?;
+            let (path, key) = g.source_file()?;
+            let field_key = g.file_key();
+            let key = key.unwrap_or(&field_key);
 
             Some(quote! {
-                builder = builder.env_mapping(#field_name, #env_var);
+                builder = builder.field_file(#field_key, #path, #key);
             })
         })
         .collect();
 
-    let env_mappings = quote! {
-        #(#env_mapping_calls)*
+    // Generate embedded config (merged below runtime files)
+    let embedded_load = env_config_attr.embedded.as_ref().map_or_else(
+        || quote! {},
+        |path| {
+            let format = format_ident!(
+                "{}",
+                embedded_format(path).expect("embedded extension validated during parsing")
+            );
+            let include = if std::path::Path::new(path).is_absolute() {
+                quote! { include_str!(#path) }
+            } else {
+                quote! { include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #path)) }
+            };
+
+            quote! {
+                builder = builder.embedded(#include, ::procenv::FileFormat::#format);
+            }
+        },
+    );
+
+    // Let files win over env vars when requested
+    let files_override_env = if env_config_attr.files_override_env {
+        quote! { builder = builder.files_override_env(true); }
+    } else {
+        quote! {}
     };
 
+    // Generate env prefix and direct env var mappings
+    let env_layer = generate_env_layer(generators, env_config_attr);
+
+    let schema_version = generate_schema_version(env_config_attr);
+
     // Generate dotenv loading
     let dotenv_load = generate_dotenv_load(env_config_attr.dotenv.as_ref());
 
-    // Generate profile setup for from_config
-    let (profile_setup, profile_defaults) =
-        generate_profile_defaults_for_config(env_config_attr, generators);
+    // Generate profile setup and the defaults layer
+    let (profile_setup, defaults_setup) = generate_defaults_layer(generators, env_config_attr);
 
-    // Collect all env var names for pre-dotenv check
-    let env_var_names: Vec<_> = generators.iter().filter_map(|g| g.env_var_name()).collect();
+    // Collect all env var names (including `default_from` fallbacks) for pre-dotenv check
+    let env_var_names: Vec<_> = generators
+        .iter()
+        .flat_map(|g| g.env_var_name().into_iter().chain(g.default_from()))
+        .collect();
 
     // Generate pre-dotenv var collection
     let pre_dotenv_collection = quote! {
@@ -143,78 +167,13 @@ pub fn generate_from_config_impl(
             #(#env_var_names),*
         ]
         .iter()
-        .filter(|var| std::env::var(var).is_ok())
+        .filter(|var| ::procenv::runtime::var(var).is_ok())
         .copied()
         .collect();
     };
 
     // Track if dotenv was loaded
-    let dotenv_loaded_flag = if env_config_attr.dotenv.is_some() {
-        quote! { let __dotenv_loaded = true; }
-    } else {
-        quote! { let __dotenv_loaded = false; }
-    };
-
-    // Generate default values for fields that have them
-    let default_entries: Vec<QuoteStream> = generators
-        .iter()
-        .filter_map(|g| {
-            if g.is_flatten() {
-                return None;
-            }
-
-            let field_name = g.name().to_string();
-            let json_key = field_name;
-
-            g.default_value().map(|default| {
-                quote! {
-                    __defaults.insert(
-                        #json_key.to_string(),
-                        ::procenv::FileUtils::coerce_value(#default)
-                    );
-                }
-            })
-        })
-        .collect();
-
-    // Generate nested defaults collection for flatten fields
-    let flatten_default_entries: Vec<QuoteStream> = generators
-        .iter()
-        .filter_map(|g| {
-            if !g.is_flatten() {
-                return None;
-            }
-
-            let field_name = g.name().to_string();
-            let ty = g.field_type()?;
-
-            Some(quote! {
-                if let ::serde_json::Value::Object(nested_map) = <#ty>::__config_defaults() {
-                    __defaults.insert(
-                        #field_name.to_string(),
-                        ::serde_json::Value::Object(nested_map)
-                    );
-                }
-            })
-        })
-        .collect();
-
-    // Determine if we need defaults setup
-    let has_flatten = generators.iter().any(|g| g.is_flatten());
-    let has_profile = env_config_attr.profile_env.is_some();
-    let defaults_setup = if default_entries.is_empty() && !has_flatten && !has_profile {
-        quote! {}
-    } else {
-        quote! {
-            let mut __defaults = ::serde_json::Map::new();
-            // Apply macro defaults first (lowest priority)
-            #(#default_entries)*
-            #(#flatten_default_entries)*
-            // Apply profile defaults (override macro defaults)
-            #profile_defaults
-            builder = builder.defaults_value(::serde_json::Value::Object(__defaults));
-        }
-    };
+    let dotenv_loaded_flag = generate_dotenv_flags(env_config_attr.dotenv.as_ref());
 
     // Generate source tracking entries for from_config_with_sources()
     //
@@ -225,10 +184,13 @@ pub fn generate_from_config_impl(
     // The key insight is that we must enumerate ALL possible fields (not just
     // those tracked by OriginTracker, which only tracks file sources) and then
     // determine the source for each field by checking in priority order.
+    let files_override = env_config_attr.files_override_env;
     let source_entries: Vec<QuoteStream> = generators
         .iter()
+        .filter(|g| !g.is_skip())
         .map(|g| {
             let field_name = g.name().to_string();
+            let file_key = g.file_key();
             let has_default = g.default_value().is_some();
             let has_profile = g.profile_config().is_some();
 
@@ -250,12 +212,34 @@ pub fn generate_from_config_impl(
 
                 let flatten_prefix = g.flatten_prefix().unwrap_or("");
 
+                let env_check = quote! { ::procenv::runtime::var(&expected_env_var).is_ok() };
+                let env_source = quote! {
+                    // Value came from environment
+                    if __dotenv_loaded && !__pre_dotenv_vars.contains(expected_env_var.as_str()) {
+                        // Env var was loaded from .env file
+                        ::procenv::Source::DotenvFile(__dotenv_path.clone())
+                    } else {
+                        // Env var was set before dotenv loading
+                        ::procenv::Source::Environment
+                    }
+                };
+                let source_checks = generate_source_checks(
+                    files_override,
+                    &env_check,
+                    &env_source,
+                    &quote! { &full_path },
+                );
+
                 quote! {
                     {
-                        // Get the field name prefix for constructing dotted paths
-                        let base_prefix = #field_name;
+                        // Get the field's file key for constructing dotted paths
+                        let base_prefix = #file_key;
                         let flatten_env_prefix = #flatten_prefix;
 
+                        // Nested paths that received a compiled or profile default
+                        let (_, __nested_defaults) =
+                            <#ty>::__config_profile_defaults(__profile.as_deref());
+
                         // Track which fields we've already processed (to avoid duplicates)
                         let mut processed_fields: std::collections::HashSet<std::string::String> =
                             std::collections::HashSet::new();
@@ -273,30 +257,26 @@ pub fn generate_from_config_impl(
                             processed_fields.insert(full_path.clone());
 
                             // Construct the expected env var name with flatten prefix
-                            let expected_env_var = format!("{}{}", flatten_env_prefix, nested_var);
+                            let expected_env_var = ::procenv::runtime::join_prefix(
+                                flatten_env_prefix,
+                                nested_var,
+                                <#ty>::__PREFIX_SEPARATOR,
+                            );
 
                             // Determine source with correct priority order:
                             // 1. Environment variable (highest priority)
                             // 2. Dotenv file (if dotenv loaded and var wasn't pre-set)
                             // 3. Config file (check origin tracker)
-                            // 4. Profile/Default (requires nested metadata - not yet available)
+                            // 4. Profile/Default (as reported by the nested defaults)
                             // 5. NotSet
-                            let source = if std::env::var(&expected_env_var).is_ok() {
-                                // Value came from environment
-                                if __dotenv_loaded && !__pre_dotenv_vars.contains(expected_env_var.as_str()) {
-                                    // Env var was loaded from .env file
-                                    ::procenv::Source::DotenvFile(None)
-                                } else {
-                                    // Env var was set before dotenv loading
-                                    ::procenv::Source::Environment
-                                }
-                            } else if let Some(file_path) = __origins.get_file_source(&full_path) {
-                                // Value came from a config file
-                                ::procenv::Source::ConfigFile(Some(file_path))
+                            // (config files on disk come first with `files_override_env`)
+                            let source = #source_checks if let Some(default_source) =
+                                __nested_defaults.get(nested_field)
+                            {
+                                // Value came from the nested struct's default or profile default
+                                default_source.clone()
                             } else {
-                                // No env var or file source
-                                // NOTE: Full profile/default tracking for nested fields requires
-                                // additional metadata propagation. For now, mark as NotSet.
+                                // No value source (for optional fields that are None)
                                 ::procenv::Source::NotSet
                             };
 
@@ -308,9 +288,9 @@ pub fn generate_from_config_impl(
 
                         // STEP 2: Also check file-tracked origins for any paths we might have missed
                         // (This handles cases where the file has keys not in env_mappings)
-                        let prefix_dot = format!("{}.", #field_name);
+                        let prefix_dot = format!("{}.", #file_key);
                         for tracked_path in __origins.tracked_fields() {
-                            if tracked_path.starts_with(&prefix_dot) || tracked_path == #field_name {
+                            if tracked_path.starts_with(&prefix_dot) || tracked_path == #file_key {
                                 let full_path = tracked_path.to_string();
 
                                 // Skip if already processed via env_mappings
@@ -340,6 +320,24 @@ pub fn generate_from_config_impl(
                 // =========================================================
                 let env_var = g.env_var_name().unwrap_or("");
 
+                let env_check = quote! { ::procenv::runtime::var(#env_var).is_ok() };
+                let env_source = quote! {
+                    // Value came from environment variable
+                    if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
+                        // Var was loaded from .env file (not set before dotenv)
+                        ::procenv::Source::DotenvFile(__dotenv_path.clone())
+                    } else {
+                        // Var was set in actual environment
+                        ::procenv::Source::Environment
+                    }
+                };
+                let source_checks = generate_source_checks(
+                    files_override,
+                    &env_check,
+                    &env_source,
+                    &quote! { #file_key },
+                );
+
                 quote! {
                     {
                         // Determine source with correct priority order:
@@ -349,19 +347,8 @@ pub fn generate_from_config_impl(
                         // 4. Profile default (if profile is active AND field has profile config)
                         // 5. Regular default
                         // 6. NotSet (for optional fields without value)
-                        let source = if std::env::var(#env_var).is_ok() {
-                            // Value came from environment variable
-                            if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
-                                // Var was loaded from .env file (not set before dotenv)
-                                ::procenv::Source::DotenvFile(None)
-                            } else {
-                                // Var was set in actual environment
-                                ::procenv::Source::Environment
-                            }
-                        } else if let Some(file_path) = __origins.get_file_source(#field_name) {
-                            // Value came from a config file
-                            ::procenv::Source::ConfigFile(Some(file_path))
-                        } else if let Some(ref __p) = __profile && #has_profile {
+                        // (config files on disk come first with `files_override_env`)
+                        let source = #source_checks if let Some(ref __p) = __profile && #has_profile {
                             // Value came from a profile-specific default
                             // Uses if-let chains (Rust 2024 edition)
                             ::procenv::Source::Profile(__p.clone())
@@ -387,6 +374,33 @@ pub fn generate_from_config_impl(
         impl #impl_generics #struct_name #type_generics #where_clause {
             /// Load configuration from files and environment variables.
             pub fn from_config() -> std::result::Result<Self, ::procenv::Error> {
+                // Not `config_merged_value()`, which redacts secret fields
+                let (__value, __origins) = Self::__config_merge()?;
+                Self::__from_json_value(__value)
+            }
+
+            /// Return the merged file and environment value that `from_config()`
+            /// deserializes, without constructing `Self`.
+            ///
+            /// Useful for diagnosing why a key did not take effect in a layered setup.
+            /// Secret fields are replaced with `"<redacted>"`.
+            pub fn config_merged_value() -> std::result::Result<::procenv::file::JsonValue, ::procenv::Error> {
+                let (mut __value, __origins) = Self::__config_merge()?;
+                ::procenv::FileUtils::redact_secrets(&mut __value, Self::__is_secret);
+                std::result::Result::Ok(__value)
+            }
+
+            /// Return the config files that `from_config()` actually loads.
+            ///
+            /// Optional files that don't exist are left out.
+            pub fn config_loaded_files() -> std::result::Result<std::vec::Vec<std::path::PathBuf>, ::procenv::Error> {
+                let (__value, __origins) = Self::__config_merge()?;
+                std::result::Result::Ok(__origins.loaded_files().to_vec())
+            }
+
+            /// Run the layered merge behind `from_config()` (internal, generated by macro).
+            #[doc(hidden)]
+            pub fn __config_merge() -> std::result::Result<(::procenv::file::JsonValue, ::procenv::OriginTracker), ::procenv::Error> {
                 #dotenv_load
 
                 #profile_setup
@@ -395,14 +409,20 @@ pub fn generate_from_config_impl(
 
                 #defaults_setup
 
+                #embedded_load
+
                 #(#file_loads)*
 
-                #env_prefix
+                #(#field_file_loads)*
 
-                #env_mappings
+                #env_layer
+
+                #files_override_env
+
+                #schema_version
 
                 let (__value, __origins) = builder.into_value()?;
-                Self::__from_json_value(__value)
+                std::result::Result::Ok((__value, __origins))
             }
 
             /// Load configuration from files and environment variables with source attribution.
@@ -419,17 +439,25 @@ pub fn generate_from_config_impl(
 
                 #defaults_setup
 
+                #embedded_load
+
                 #(#file_loads)*
 
-                #env_prefix
+                #(#field_file_loads)*
 
-                #env_mappings
+                #env_layer
+
+                #files_override_env
+
+                #schema_version
 
                 let (__value, __origins) = builder.into_value()?;
                 let __config = Self::__from_json_value(__value)?;
 
                 let mut __sources = ::procenv::ConfigSources::new();
+                __sources.set_reload_immutable(Self::__reload_immutable_fields());
                 #(#source_entries)*
+                __sources.set_profile(__profile);
 
                 std::result::Result::Ok((__config, __sources))
             }
@@ -437,6 +465,282 @@ pub fn generate_from_config_impl(
     }
 }
 
+/// Generate the `from_json_env()` method for `#[env_config(from_json_var = "...")]`.
+///
+/// The var's JSON document takes the place of the config files: it is layered
+/// over macro and profile defaults, and env vars are applied on top as in
+/// `from_config()`. The raw document is treated as secret in parse errors.
+pub fn generate_from_json_env_impl(
+    struct_name: &Ident,
+    generics: &Generics,
+    generators: &[Box<dyn FieldGenerator>],
+    env_config_attr: &EnvConfigAttr,
+) -> QuoteStream {
+    let Some(json_var) = env_config_attr.from_json_var.as_deref() else {
+        return quote! {};
+    };
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let dotenv_load = generate_dotenv_load(env_config_attr.dotenv.as_ref());
+    let (profile_setup, defaults_setup) = generate_defaults_layer(generators, env_config_attr);
+    let env_layer = generate_env_layer(generators, env_config_attr);
+    let schema_version = generate_schema_version(env_config_attr);
+    let case_insensitive_scope = generate_case_insensitive_scope(env_config_attr);
+
+    quote! {
+        impl #impl_generics #struct_name #type_generics #where_clause {
+            #[doc = concat!("Load configuration from the JSON document in `", #json_var, "`,")]
+            /// with individual environment variables applied on top.
+            pub fn from_json_env() -> std::result::Result<Self, ::procenv::Error> {
+                #case_insensitive_scope
+                #dotenv_load
+
+                #profile_setup
+
+                let __json = match ::procenv::runtime::var(#json_var) {
+                    std::result::Result::Ok(json) => json,
+                    std::result::Result::Err(std::env::VarError::NotPresent) => {
+                        return std::result::Result::Err(::procenv::Error::missing(#json_var));
+                    }
+                    std::result::Result::Err(std::env::VarError::NotUnicode(raw)) => {
+                        return std::result::Result::Err(
+                            ::procenv::Error::invalid_utf8(#json_var, &raw, true)
+                        );
+                    }
+                };
+
+                // Report malformed JSON against the var rather than an anonymous string
+                if let std::result::Result::Err(e) =
+                    ::serde_json::from_str::<::serde_json::Value>(&__json)
+                {
+                    return std::result::Result::Err(::procenv::Error::parse(
+                        #json_var,
+                        __json,
+                        true,
+                        "JSON",
+                        std::boxed::Box::new(e),
+                    ));
+                }
+
+                let mut builder = ::procenv::ConfigBuilder::new();
+
+                #defaults_setup
+
+                builder = builder.embedded(&__json, ::procenv::FileFormat::Json);
+
+                #env_layer
+
+                #schema_version
+
+                let (__value, _) = builder.into_value()?;
+                Self::__from_json_value(__value)
+            }
+        }
+    }
+}
+
+/// Generate the env and config file checks that open a field's source
+/// attribution chain, ending in `else` for the caller to continue.
+///
+/// Env vars beat config files by default. With `files_override_env` only
+/// files read from disk beat env vars; values from embedded content still
+/// rank below them, as they do in `ConfigBuilder::merge`.
+fn generate_source_checks(
+    files_override: bool,
+    env_check: &QuoteStream,
+    env_source: &QuoteStream,
+    path: &QuoteStream,
+) -> QuoteStream {
+    let file_checks = quote! {
+        if let Some(file_path) = __origins.get_file_source(#path) {
+            // Value came from a config file
+            ::procenv::Source::ConfigFile(Some(file_path))
+        } else
+    };
+
+    if files_override {
+        quote! {
+            if let Some(file_path) = __origins.get_loaded_file_source(#path) {
+                // Value came from a config file on disk
+                ::procenv::Source::ConfigFile(Some(file_path))
+            } else if #env_check {
+                #env_source
+            } else #file_checks
+        }
+    } else {
+        quote! {
+            if #env_check {
+                #env_source
+            } else #file_checks
+        }
+    }
+}
+
+/// Generate the `ConfigBuilder` schema version check for `schema_version`.
+///
+/// The key defaults to `"version"` and a config without it counts as
+/// version 1, unless `version_key`/`missing_version` say otherwise.
+fn generate_schema_version(env_config_attr: &EnvConfigAttr) -> QuoteStream {
+    let Some(supported) = env_config_attr.schema_version else {
+        return quote! {};
+    };
+
+    let key = env_config_attr.version_key.as_deref().unwrap_or("version");
+    let missing = env_config_attr.missing_version.unwrap_or(1);
+
+    quote! {
+        builder = builder.schema_version(#key, #supported, #missing);
+    }
+}
+
+/// Generate the profile setup and the `ConfigBuilder` defaults layer.
+///
+/// Returns `(profile_setup, defaults_setup)`. The defaults layer holds macro
+/// defaults, nested flatten defaults and the active profile's defaults.
+fn generate_defaults_layer(
+    generators: &[Box<dyn FieldGenerator>],
+    env_config_attr: &EnvConfigAttr,
+) -> (QuoteStream, QuoteStream) {
+    // Generate profile setup for from_config
+    let (profile_setup, profile_defaults) =
+        generate_profile_defaults_for_config(env_config_attr, generators);
+
+    // Generate default values for fields that have them
+    let default_entries: Vec<QuoteStream> = generators
+        .iter()
+        .filter_map(|g| {
+            if g.is_flatten() {
+                return None;
+            }
+
+            let json_key = g.file_key();
+
+            g.default_value().map(|default| {
+                // An unset `${VAR}` leaves the key out; extraction reports it
+                // if nothing else sets the field
+                FieldFactory::expand_default_expr(default, &default_var(g.as_ref())).map_or_else(
+                    || {
+                        quote! {
+                            __defaults.insert(
+                                #json_key.to_string(),
+                                ::procenv::FileUtils::coerce_value(#default)
+                            );
+                        }
+                    },
+                    |expand| {
+                        quote! {
+                            if let std::result::Result::Ok(__default) = #expand {
+                                __defaults.insert(
+                                    #json_key.to_string(),
+                                    ::procenv::FileUtils::coerce_value(&__default)
+                                );
+                            }
+                        }
+                    },
+                )
+            })
+        })
+        .collect();
+
+    // Generate nested defaults collection for flatten fields
+    let flatten_default_entries: Vec<QuoteStream> = generators
+        .iter()
+        .filter_map(|g| {
+            if !g.is_flatten() {
+                return None;
+            }
+
+            let file_key = g.file_key();
+            let ty = g.field_type()?;
+
+            Some(quote! {
+                if let (::serde_json::Value::Object(nested_map), _) = <#ty>::__config_defaults() {
+                    __defaults.insert(
+                        #file_key.to_string(),
+                        ::serde_json::Value::Object(nested_map)
+                    );
+                }
+            })
+        })
+        .collect();
+
+    // Determine if we need defaults setup
+    let has_flatten = generators.iter().any(|g| g.is_flatten());
+    let has_profile = env_config_attr.profile_env.is_some();
+    let defaults_setup = if default_entries.is_empty() && !has_flatten && !has_profile {
+        quote! {}
+    } else {
+        quote! {
+            let mut __defaults = ::serde_json::Map::new();
+            // Apply macro defaults first (lowest priority)
+            #(#default_entries)*
+            #(#flatten_default_entries)*
+            // Apply profile defaults (override macro defaults)
+            #profile_defaults
+            builder = builder.defaults_value(::serde_json::Value::Object(__defaults));
+        }
+    };
+
+    (profile_setup, defaults_setup)
+}
+
+/// Generate the `ConfigBuilder` env layer: the struct prefix and the direct
+/// mappings for fields with custom var names.
+fn generate_env_layer(
+    generators: &[Box<dyn FieldGenerator>],
+    env_config_attr: &EnvConfigAttr,
+) -> QuoteStream {
+    // Generate env prefix setup
+    let env_prefix = env_config_attr.prefix.as_ref().map_or_else(
+        || quote! {},
+        |prefix| quote! { builder = builder.env_prefix(#prefix); },
+    );
+
+    // Generate direct env var mappings for fields with custom var names
+    let env_mapping_calls: Vec<QuoteStream> = generators
+        .iter()
+        .filter_map(|g| {
+            let file_key = g.file_key();
+
+            if g.is_flatten() {
+                // For flatten fields, call the nested type's env mappings method
+                let ty = g.field_type()?;
+                let flatten_prefix = g.flatten_prefix().unwrap_or("");
+
+                return Some(quote! {
+                    // Register nested env mappings with combined prefix
+                    for (nested_field, nested_var) in <#ty>::__env_mappings() {
+                        let full_path = format!("{}.{}", #file_key, nested_field);
+                        let full_var = ::procenv::runtime::join_prefix(
+                            #flatten_prefix,
+                            nested_var,
+                            <#ty>::__PREFIX_SEPARATOR,
+                        );
+                        builder = builder.env_mapping(&full_path, &full_var);
+                    }
+                });
+            }
+
+            let env_var = g.env_var_name()?;
+
+            Some(quote! {
+                builder = builder.env_mapping(#file_key, #env_var);
+            })
+        })
+        .collect();
+
+    let env_mappings = quote! {
+        #(#env_mapping_calls)*
+    };
+
+    quote! {
+        #env_prefix
+
+        #env_mappings
+    }
+}
+
 /// Generate field extraction code for `__from_json_value`.
 ///
 /// IMPORTANT: This function requires access to the field's actual type for proper
@@ -446,17 +750,42 @@ pub fn generate_from_config_impl(
 fn generate_field_extractions(generators: &[Box<dyn FieldGenerator>]) -> QuoteStream {
     let extractions: Vec<QuoteStream> = generators
         .iter()
+        .filter(|g| !g.is_skip() || g.is_serde_flatten())
         .map(|g| {
             let name = g.name();
             let field_name_str = name.to_string();
+            let file_key = g.file_key();
             let local_var = quote::format_ident!("__{}", name);
 
-            if g.is_flatten() {
+            if g.is_serde_flatten() {
+                // Serde flatten: deserialize the nested object directly, like a
+                // flatten field a missing key is an empty object
+                let ty = g.field_type().expect("serde flatten field must have type");
+                let type_name = g.type_name();
+                quote! {
+                    let #local_var: std::option::Option<#ty> = {
+                        let nested_value = __obj.get(#file_key)
+                            .cloned()
+                            .unwrap_or(::serde_json::Value::Object(::serde_json::Map::new()));
+                        match ::serde_json::from_value::<#ty>(nested_value) {
+                            std::result::Result::Ok(v) => std::option::Option::Some(v),
+                            std::result::Result::Err(e) => {
+                                __errors.push(::procenv::Error::file_only(
+                                    #field_name_str,
+                                    #type_name,
+                                    e.to_string()
+                                ));
+                                std::option::Option::None
+                            }
+                        }
+                    };
+                }
+            } else if g.is_flatten() {
                 // Flatten field: extract nested object and call nested type's __from_json_value
                 let ty = g.field_type().expect("flatten field must have type");
                 quote! {
                     let #local_var: std::option::Option<#ty> = {
-                        let nested_value = __obj.get(#field_name_str)
+                        let nested_value = __obj.get(#file_key)
                             .cloned()
                             .unwrap_or(::serde_json::Value::Object(::serde_json::Map::new()));
                         match <#ty>::__from_json_value(nested_value) {
@@ -473,13 +802,24 @@ fn generate_field_extractions(generators: &[Box<dyn FieldGenerator>]) -> QuoteSt
                 // Note: For optional fields, field_type() returns the INNER type (T from Option<T>)
                 let inner_ty = g.field_type().expect("optional field must have inner type");
                 let type_name = g.type_name();
+                let extract = generate_extract_expr(inner_ty, &field_name_str, g.as_ref());
+                let from_value = generate_from_value_fn(inner_ty, g.deserialize_with());
 
-                if g.format_config().is_some() {
+                // `distinguish_null` fields also record whether the key is an explicit null
+                let null_flag = g.distinguish_null().then(|| {
+                    let null_ident = quote::format_ident!("__{}_null", g.name());
+                    quote! {
+                        let #null_ident: bool =
+                            __obj.get(#file_key).is_some_and(::serde_json::Value::is_null);
+                    }
+                });
+
+                let extraction = if g.format_config().is_some() {
                     // Optional with serde format
                     quote! {
-                        let #local_var: std::option::Option<std::option::Option<#inner_ty>> = match __obj.get(#field_name_str) {
+                        let #local_var: std::option::Option<std::option::Option<#inner_ty>> = match __obj.get(#file_key) {
                             std::option::Option::Some(v) if !v.is_null() => {
-                                match ::serde_json::from_value::<#inner_ty>(v.clone()) {
+                                match #from_value(v.clone()) {
                                     std::result::Result::Ok(parsed) => std::option::Option::Some(std::option::Option::Some(parsed)),
                                     std::result::Result::Err(e) => {
                                         __errors.push(::procenv::Error::extraction(
@@ -497,10 +837,10 @@ fn generate_field_extractions(generators: &[Box<dyn FieldGenerator>]) -> QuoteSt
                 } else {
                     // Optional with FromStr
                     quote! {
-                        let #local_var: std::option::Option<std::option::Option<#inner_ty>> = match __obj.get(#field_name_str) {
+                        let #local_var: std::option::Option<std::option::Option<#inner_ty>> = match __obj.get(#file_key) {
                             std::option::Option::Some(v) if !v.is_null() => {
                                 let cv = ::procenv::ConfigValue::from_json(v.clone());
-                                match cv.extract::<#inner_ty>(#field_name_str) {
+                                match #extract {
                                     std::result::Result::Ok(parsed) => std::option::Option::Some(std::option::Option::Some(parsed)),
                                     std::result::Result::Err(e) => {
                                         __errors.push(::procenv::Error::extraction(
@@ -515,11 +855,16 @@ fn generate_field_extractions(generators: &[Box<dyn FieldGenerator>]) -> QuoteSt
                             _ => std::option::Option::Some(std::option::Option::None),
                         };
                     }
+                };
+
+                quote! {
+                    #extraction
+                    #null_flag
                 }
             } else if g.is_secrecy_type() && g.field_type().is_none() {
                 // SecretString field - special handling since it doesn't store a Type
                 quote! {
-                    let #local_var: std::option::Option<::procenv::SecretString> = match __obj.get(#field_name_str) {
+                    let #local_var: std::option::Option<::procenv::SecretString> = match __obj.get(#file_key) {
                         std::option::Option::Some(v) if !v.is_null() => {
                             match v.as_str() {
                                 std::option::Option::Some(s) => {
@@ -547,7 +892,7 @@ fn generate_field_extractions(generators: &[Box<dyn FieldGenerator>]) -> QuoteSt
                 let type_name = g.type_name();
 
                 quote! {
-                    let #local_var: std::option::Option<::procenv::SecretBox<#inner_ty>> = match __obj.get(#field_name_str) {
+                    let #local_var: std::option::Option<::procenv::SecretBox<#inner_ty>> = match __obj.get(#file_key) {
                         std::option::Option::Some(v) if !v.is_null() => {
                             let cv = ::procenv::ConfigValue::from_json(v.clone());
                             match cv.extract::<#inner_ty>(#field_name_str) {
@@ -574,11 +919,12 @@ fn generate_field_extractions(generators: &[Box<dyn FieldGenerator>]) -> QuoteSt
                 // Field with format = "json/yaml/toml" - use serde deserialization
                 let ty = g.field_type().expect("format field must have type");
                 let type_name = g.type_name();
+                let from_value = generate_from_value_fn(ty, g.deserialize_with());
 
                 g.default_value().map_or_else(|| quote! {
-                        let #local_var: std::option::Option<#ty> = match __obj.get(#field_name_str) {
+                        let #local_var: std::option::Option<#ty> = match __obj.get(#file_key) {
                             std::option::Option::Some(v) if !v.is_null() => {
-                                match ::serde_json::from_value::<#ty>(v.clone()) {
+                                match #from_value(v.clone()) {
                                     std::result::Result::Ok(parsed) => std::option::Option::Some(parsed),
                                     std::result::Result::Err(e) => {
                                         __errors.push(::procenv::Error::extraction(
@@ -595,10 +941,29 @@ fn generate_field_extractions(generators: &[Box<dyn FieldGenerator>]) -> QuoteSt
                                 std::option::Option::None
                             }
                         };
-                    }, |default| quote! {
-                        let #local_var: std::option::Option<#ty> = match __obj.get(#field_name_str) {
+                    }, |default| {
+                        let from_default = FieldFactory::deserialize_with_expr(
+                            quote! { ::serde_json::from_str(&__default) },
+                            g.deserialize_with(),
+                        );
+                        let use_default = bind_default(g.as_ref(), default, &quote! {
+                            match #from_default {
+                                std::result::Result::Ok(v) => std::option::Option::Some(v),
+                                std::result::Result::Err(e) => {
+                                    __errors.push(::procenv::Error::extraction(
+                                        #field_name_str,
+                                        #type_name,
+                                        format!("failed to parse default: {}", e)
+                                    ));
+                                    std::option::Option::None
+                                }
+                            }
+                        });
+
+                        quote! {
+                        let #local_var: std::option::Option<#ty> = match __obj.get(#file_key) {
                             std::option::Option::Some(v) if !v.is_null() => {
-                                match ::serde_json::from_value::<#ty>(v.clone()) {
+                                match #from_value(v.clone()) {
                                     std::result::Result::Ok(parsed) => std::option::Option::Some(parsed),
                                     std::result::Result::Err(e) => {
                                         __errors.push(::procenv::Error::extraction(
@@ -612,30 +977,22 @@ fn generate_field_extractions(generators: &[Box<dyn FieldGenerator>]) -> QuoteSt
                             }
                             _ => {
                                 // Use default value - parse JSON string
-                                match ::serde_json::from_str::<#ty>(#default) {
-                                    std::result::Result::Ok(v) => std::option::Option::Some(v),
-                                    std::result::Result::Err(e) => {
-                                        __errors.push(::procenv::Error::extraction(
-                                            #field_name_str,
-                                            #type_name,
-                                            format!("failed to parse default: {}", e)
-                                        ));
-                                        std::option::Option::None
-                                    }
-                                }
+                                #use_default
                             }
                         };
+                    }
                     })
             } else {
                 // Required or Default field (using FromStr)
                 let ty = g.field_type().expect("field must have type");
                 let type_name = g.type_name();
+                let extract = generate_extract_expr(ty, &field_name_str, g.as_ref());
 
                 g.default_value().map_or_else(|| quote! {
-                        let #local_var: std::option::Option<#ty> = match __obj.get(#field_name_str) {
+                        let #local_var: std::option::Option<#ty> = match __obj.get(#file_key) {
                             std::option::Option::Some(v) if !v.is_null() => {
                                 let cv = ::procenv::ConfigValue::from_json(v.clone());
-                                match cv.extract::<#ty>(#field_name_str) {
+                                match #extract {
                                     std::result::Result::Ok(parsed) => std::option::Option::Some(parsed),
                                     std::result::Result::Err(e) => {
                                         __errors.push(::procenv::Error::extraction(
@@ -652,43 +1009,189 @@ fn generate_field_extractions(generators: &[Box<dyn FieldGenerator>]) -> QuoteSt
                                 std::option::Option::None
                             }
                         };
-                    }, |default| quote! {
-                        let #local_var: std::option::Option<#ty> = match __obj.get(#field_name_str) {
-                            std::option::Option::Some(v) if !v.is_null() => {
-                                let cv = ::procenv::ConfigValue::from_json(v.clone());
-                                match cv.extract::<#ty>(#field_name_str) {
-                                    std::result::Result::Ok(parsed) => std::option::Option::Some(parsed),
-                                    std::result::Result::Err(e) => {
-                                        __errors.push(::procenv::Error::extraction(
-                                            #field_name_str,
-                                            #type_name,
-                                            e.to_string()
-                                        ));
-                                        std::option::Option::None
-                                    }
+                    }, |default| {
+                        let parse_default = FieldFactory::value_expr(
+                            ty,
+                            &quote! { __default },
+                            g.is_base64(),
+                            g.coerces_bool_numeric(),
+                            g.sep_env(),
+                        );
+                        let use_default = bind_default(g.as_ref(), default, &quote! {
+                            match #parse_default {
+                                std::result::Result::Ok(v) => std::option::Option::Some(v),
+                                std::result::Result::Err(e) => {
+                                    __errors.push(::procenv::Error::extraction(
+                                        #field_name_str,
+                                        #type_name,
+                                        format!("failed to parse default: {}", e)
+                                    ));
+                                    std::option::Option::None
                                 }
                             }
-                            _ => {
-                                // Use default value
-                                match #default.parse::<#ty>() {
-                                    std::result::Result::Ok(v) => std::option::Option::Some(v),
-                                    std::result::Result::Err(e) => {
-                                        __errors.push(::procenv::Error::extraction(
-                                            #field_name_str,
-                                            #type_name,
-                                            format!("failed to parse default: {}", e)
-                                        ));
-                                        std::option::Option::None
+                        });
+
+                        quote! {
+                            let #local_var: std::option::Option<#ty> = match __obj.get(#file_key) {
+                                std::option::Option::Some(v) if !v.is_null() => {
+                                    let cv = ::procenv::ConfigValue::from_json(v.clone());
+                                    match #extract {
+                                        std::result::Result::Ok(parsed) => std::option::Option::Some(parsed),
+                                        std::result::Result::Err(e) => {
+                                            __errors.push(::procenv::Error::extraction(
+                                                #field_name_str,
+                                                #type_name,
+                                                e.to_string()
+                                            ));
+                                            std::option::Option::None
+                                        }
                                     }
                                 }
-                            }
-                        };
+                                _ => {
+                                    // Use default value
+                                    #use_default
+                                }
+                            };
+                        }
                     })
             }
         })
         .collect();
 
-    quote! { #(#extractions)* }
+    // Values merged from files get the same `one_of`/finiteness checks
+    let value_checks: Vec<QuoteStream> = generators
+        .iter()
+        .filter(|g| !g.is_skip())
+        .map(|g| {
+            let local_var = quote::format_ident!("__{}", g.name());
+            let field_name_str = g.name().to_string();
+            // Name the env var in check errors, as `from_env()` does
+            let var = FieldFactory::var_expr(
+                g.env_var_name().unwrap_or(&field_name_str),
+                g.default_from(),
+            );
+            let value = if g.is_optional() {
+                quote! { #local_var.as_ref().and_then(std::option::Option::as_ref) }
+            } else {
+                quote! { #local_var }
+            };
+            let after_parse = generate_after_parse(g.as_ref(), &local_var, &var, g.is_optional());
+            let checks = generate_value_checks(g.as_ref(), &value, &var);
+
+            // A `when_profile` field left unset by every layer
+            let requirement = g.when_profile().map(|profiles| {
+                quote! {
+                    if let std::option::Option::Some(std::option::Option::None) = #local_var {
+                        let __required_in: &[&str] = &[#(#profiles),*];
+                        if let std::option::Option::Some(__p) =
+                            Self::active_profile().filter(|p| __required_in.contains(&p.as_str()))
+                        {
+                            __errors.push(::procenv::Error::missing_in_profile(#field_name_str, __p));
+                        }
+                    }
+                }
+            });
+
+            quote! {
+                #after_parse
+                #checks
+                #requirement
+            }
+        })
+        .collect();
+
+    quote! {
+        #(#extractions)*
+        #(#value_checks)*
+    }
+}
+
+/// Generate the conversion of a file value `cv` into `ty`.
+///
+/// String wrappers (`Arc<str>`, ...) have no `FromStr`, so they take the
+/// value's string form directly. `base64` fields decode that string form.
+/// `sep_env` fields take file arrays item by item and split strings.
+/// `coerce_bool_numeric` fields accept booleans, `0`/`1` and their strings.
+fn generate_extract_expr(
+    ty: &syn::Type,
+    field_name: &str,
+    field: &dyn FieldGenerator,
+) -> QuoteStream {
+    if let Some(sep_var) = field.sep_env() {
+        let item = FieldFactory::extract_vec_inner(ty)
+            .expect("sep_env field types are validated in parse_field");
+
+        return quote! {
+            ::procenv::runtime::parse_list_value::<#item>(
+                &cv,
+                &::procenv::runtime::list_separator(#sep_var),
+            )
+        };
+    }
+
+    if field.coerces_bool_numeric() {
+        quote! { cv.extract_bool_numeric() }
+    } else if field.is_base64() {
+        FieldFactory::value_expr(ty, &quote! { cv.extract_string() }, true, false, None)
+    } else if FieldFactory::is_string_wrapper(ty) {
+        quote! {
+            std::result::Result::<#ty, std::convert::Infallible>::Ok(
+                <#ty as std::convert::From<std::string::String>>::from(cv.extract_string())
+            )
+        }
+    } else {
+        quote! { cv.extract::<#ty>(#field_name) }
+    }
+}
+
+/// The var named by errors about a field's `default`.
+fn default_var(g: &dyn FieldGenerator) -> QuoteStream {
+    let var = g
+        .env_var_name()
+        .map_or_else(|| g.name().to_string(), ToString::to_string);
+
+    quote! { #var }
+}
+
+/// Run `parse` with the field's `default` bound to `__default`.
+///
+/// `${VAR}` references are expanded first; an unset one is pushed as an
+/// error and the field is `None`.
+fn bind_default(g: &dyn FieldGenerator, default: &str, parse: &QuoteStream) -> QuoteStream {
+    FieldFactory::expand_default_expr(default, &default_var(g)).map_or_else(
+        || {
+            quote! {{
+                let __default = #default;
+                #parse
+            }}
+        },
+        |expand| {
+            quote! {
+                match #expand {
+                    std::result::Result::Ok(__default) => #parse,
+                    std::result::Result::Err(e) => {
+                        __errors.push(e);
+                        std::option::Option::None
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Generate the function turning a format field's JSON value into `ty`.
+///
+/// `deserialize_with` fields call their own function instead of
+/// `serde_json::from_value`.
+fn generate_from_value_fn(ty: &syn::Type, deserialize_with: Option<&str>) -> QuoteStream {
+    deserialize_with.map_or_else(
+        || quote! { ::serde_json::from_value::<#ty> },
+        |path| {
+            let path: syn::Path =
+                syn::parse_str(path).expect("deserialize_with path validated during parsing");
+            quote! { #path }
+        },
+    )
 }
 
 /// Generate field assignment expressions for struct construction.
@@ -699,7 +1202,21 @@ fn generate_field_assignments_from_json(generators: &[Box<dyn FieldGenerator>])
             let name = g.name();
             let local_var = quote::format_ident!("__{}", name);
 
-            if g.is_optional() {
+            if g.is_skip() && !g.is_serde_flatten() {
+                // Skipped fields are never extracted
+                let assignment = g.generate_assignment();
+                quote! { #assignment, }
+            } else if g.distinguish_null() {
+                // An explicit null is `Some(None)`, an absent key `None`
+                let null_ident = quote::format_ident!("__{}_null", name);
+                quote! {
+                    #name: if #null_ident {
+                        std::option::Option::Some(std::option::Option::None)
+                    } else {
+                        #local_var.flatten().map(std::option::Option::Some)
+                    },
+                }
+            } else if g.is_optional() {
                 // Optional fields are Option<Option<T>> during extraction
                 // Flatten to Option<T>
                 quote! { #name: #local_var.flatten(), }
@@ -750,9 +1267,14 @@ fn generate_profile_defaults_for_config(
         },
     );
 
+    let fallback = env_config_attr.default_profile.as_ref().map_or_else(
+        || quote! {},
+        |default_profile| quote! { .or_else(|| std::option::Option::Some(#default_profile.to_string())) },
+    );
+
     let profile_setup = quote! {
         // Read profile from environment variable
-        let __profile: std::option::Option<std::string::String> = std::env::var(#profile_env).ok();
+        let __profile: std::option::Option<std::string::String> = ::procenv::runtime::var(#profile_env).ok()#fallback;
         #validation
     };
 
@@ -760,18 +1282,18 @@ fn generate_profile_defaults_for_config(
     let profile_default_entries: Vec<QuoteStream> = generators
         .iter()
         .filter_map(|g| {
-            let field_name = g.name().to_string();
+            let file_key = g.file_key();
 
             if g.is_flatten() {
                 // For flatten fields, call the nested type's profile-aware defaults method
                 let ty = g.field_type()?;
                 return Some(quote! {
                     // Merge nested profile defaults
-                    if let ::procenv::file::JsonValue::Object(nested) =
+                    if let (::procenv::file::JsonValue::Object(nested), _) =
                         <#ty>::__config_profile_defaults(__profile.as_deref())
                     {
                         __defaults.insert(
-                            #field_name.to_string(),
+                            #file_key.to_string(),
                             ::procenv::file::JsonValue::Object(nested)
                         );
                     }
@@ -788,7 +1310,7 @@ fn generate_profile_defaults_for_config(
                     quote! {
                         std::option::Option::Some(#profile_name) => {
                             __defaults.insert(
-                                #field_name.to_string(),
+                                #file_key.to_string(),
                                 ::procenv::FileUtils::coerce_value(#value)
                             );
                         }
@@ -829,14 +1351,30 @@ pub fn generate_config_defaults_impl(
                 return None;
             }
 
-            let field_name = g.name().to_string();
+            let file_key = g.file_key();
             g.default_value().map(|default| {
-                quote! {
-                    __map.insert(
-                        #field_name.to_string(),
-                        ::procenv::FileUtils::coerce_value(#default)
-                    );
-                }
+                FieldFactory::expand_default_expr(default, &default_var(g.as_ref())).map_or_else(
+                    || {
+                        quote! {
+                            __map.insert(
+                                #file_key.to_string(),
+                                ::procenv::FileUtils::coerce_value(#default)
+                            );
+                            __defined.insert(#file_key.to_string(), ::procenv::Source::Default);
+                        }
+                    },
+                    |expand| {
+                        quote! {
+                            if let std::result::Result::Ok(__default) = #expand {
+                                __map.insert(
+                                    #file_key.to_string(),
+                                    ::procenv::FileUtils::coerce_value(&__default)
+                                );
+                                __defined.insert(#file_key.to_string(), ::procenv::Source::Default);
+                            }
+                        }
+                    },
+                )
             })
         })
         .collect();
@@ -849,16 +1387,20 @@ pub fn generate_config_defaults_impl(
                 return None;
             }
 
-            let field_name = g.name().to_string();
+            let file_key = g.file_key();
             let ty = g.field_type()?;
 
             Some(quote! {
-                if let ::procenv::file::JsonValue::Object(nested) = <#ty>::__config_defaults() {
+                let (__nested_value, __nested_defined) = <#ty>::__config_defaults();
+                if let ::procenv::file::JsonValue::Object(nested) = __nested_value {
                     __map.insert(
-                        #field_name.to_string(),
+                        #file_key.to_string(),
                         ::procenv::file::JsonValue::Object(nested)
                     );
                 }
+                for (__path, __source) in __nested_defined {
+                    __defined.insert(format!("{}.{}", #file_key, __path), __source);
+                }
             })
         })
         .collect();
@@ -872,7 +1414,7 @@ pub fn generate_config_defaults_impl(
             }
 
             let profile_config = g.profile_config()?;
-            let field_name = g.name().to_string();
+            let file_key = g.file_key();
 
             let match_arms: Vec<QuoteStream> = profile_config
                 .values
@@ -881,9 +1423,13 @@ pub fn generate_config_defaults_impl(
                     quote! {
                         std::option::Option::Some(#profile_name) => {
                             __map.insert(
-                                #field_name.to_string(),
+                                #file_key.to_string(),
                                 ::procenv::FileUtils::coerce_value(#value)
                             );
+                            __defined.insert(
+                                #file_key.to_string(),
+                                ::procenv::Source::Profile(#profile_name.to_string())
+                            );
                         }
                     }
                 })
@@ -906,18 +1452,20 @@ pub fn generate_config_defaults_impl(
                 return None;
             }
 
-            let field_name = g.name().to_string();
+            let file_key = g.file_key();
             let ty = g.field_type()?;
 
             Some(quote! {
-                if let ::procenv::file::JsonValue::Object(nested) =
-                    <#ty>::__config_profile_defaults(__profile)
-                {
+                let (__nested_value, __nested_defined) = <#ty>::__config_profile_defaults(__profile);
+                if let ::procenv::file::JsonValue::Object(nested) = __nested_value {
                     __map.insert(
-                        #field_name.to_string(),
+                        #file_key.to_string(),
                         ::procenv::file::JsonValue::Object(nested)
                     );
                 }
+                for (__path, __source) in __nested_defined {
+                    __defined.insert(format!("{}.{}", #file_key, __path), __source);
+                }
             })
         })
         .collect();
@@ -928,12 +1476,12 @@ pub fn generate_config_defaults_impl(
         .filter_map(|g| {
             if g.is_flatten() {
                 // For flatten fields, include nested mappings
-                let field_name = g.name().to_string();
+                let file_key = g.file_key();
                 let ty = g.field_type()?;
                 return Some(quote! {
                     for (nested_field, nested_var) in <#ty>::__env_mappings() {
                         __mappings.push((
-                            std::boxed::Box::leak(format!("{}.{}", #field_name, nested_field).into_boxed_str()),
+                            std::boxed::Box::leak(format!("{}.{}", #file_key, nested_field).into_boxed_str()),
                             nested_var
                         ));
                     }
@@ -941,9 +1489,9 @@ pub fn generate_config_defaults_impl(
             }
 
             let env_var = g.env_var_name()?;
-            let field_name = g.name().to_string();
+            let file_key = g.file_key();
             Some(quote! {
-                __mappings.push((#field_name, #env_var));
+                __mappings.push((#file_key, #env_var));
             })
         })
         .collect();
@@ -958,26 +1506,37 @@ pub fn generate_config_defaults_impl(
         // Only generate __config_defaults when file feature is enabled
         #[cfg(feature = "file")]
         impl #impl_generics #struct_name #type_generics #where_clause {
-            /// Returns default values for this config as a JSON object.
+            /// Returns default values for this config as a JSON object, along
+            /// with the dotted path of every field it defined.
             #[doc(hidden)]
-            pub fn __config_defaults() -> ::procenv::file::JsonValue {
+            pub fn __config_defaults() -> (
+                ::procenv::file::JsonValue,
+                std::collections::BTreeMap<std::string::String, ::procenv::Source>,
+            ) {
                 let mut __map = ::procenv::file::JsonMap::new();
+                let mut __defined = std::collections::BTreeMap::new();
                 #(#default_entries)*
                 #(#flatten_entries)*
-                ::procenv::file::JsonValue::Object(__map)
+                (::procenv::file::JsonValue::Object(__map), __defined)
             }
 
-            /// Returns default values including profile-specific defaults.
+            /// Returns default values including profile-specific defaults, along
+            /// with the dotted path of every field they defined and whether the
+            /// value is a macro default or a profile default.
             #[doc(hidden)]
-            pub fn __config_profile_defaults(__profile: std::option::Option<&str>) -> ::procenv::file::JsonValue {
+            pub fn __config_profile_defaults(__profile: std::option::Option<&str>) -> (
+                ::procenv::file::JsonValue,
+                std::collections::BTreeMap<std::string::String, ::procenv::Source>,
+            ) {
                 let mut __map = ::procenv::file::JsonMap::new();
+                let mut __defined = std::collections::BTreeMap::new();
                 // Apply macro defaults first
                 #(#default_entries)*
                 // Apply profile-specific defaults (overrides macro defaults)
                 #(#profile_entries)*
                 // Include nested defaults with profile support
                 #(#flatten_profile_entries)*
-                ::procenv::file::JsonValue::Object(__map)
+                (::procenv::file::JsonValue::Object(__map), __defined)
             }
 
             /// Returns field-to-env-var mappings for this config.
@@ -9,7 +9,7 @@
 //!
 //! # Secret Masking
 //!
-//! Fields marked with `secret` or using secrecy types show `[REDACTED]`:
+//! Fields marked with `secret` show `<redacted>`:
 //!
 //! ```rust,ignore
 //! #[derive(EnvConfig)]
@@ -22,9 +22,30 @@
 //! }
 //!
 //! // Debug output:
-//! // Config { port: 8080, api_key: "[REDACTED]" }
+//! // Config { port: 8080, api_key: "<redacted>" }
 //! ```
 //!
+//! Secret collections keep their shape but mask every element, so a
+//! `Vec` prints `[<redacted>, <redacted>]` and a map prints `{..: <redacted>}`. Only the length
+//! is used, which keeps the output deterministic for sets and hash maps.
+//!
+//! # Partial Masking
+//!
+//! Fields with `redact_pattern` show their value with each part captured
+//! by the pattern replaced, so `postgres://app:hunter2@db/main` prints as
+//! `"postgres://app:<redacted>@db/main"`.
+//!
+//! # Unmasking
+//!
+//! With `#[env_config(allow_unmask)]`, setting `PROCENV_UNMASK=1` at runtime
+//! shows manual secret fields with their real values. Secrecy types are never
+//! unmasked; they still need an explicit `expose_secret()`.
+//!
+//! `#[env_config(unmask_in_debug)]` shows the same fields whenever the
+//! user's crate is built with `debug_assertions`, without reading any env
+//! var. In release builds the check is a constant `false`, so those
+//! fields are always masked.
+//!
 //! # Secrecy Types
 //!
 //! Fields using `SecretString` or `SecretBox<T>` delegate to the type's
@@ -43,25 +64,31 @@
 //! debug formatting.
 
 use proc_macro2::TokenStream as QuoteStream;
-use quote::quote;
-use syn::{Generics, Ident};
+use quote::{format_ident, quote};
+use syn::{Generics, Ident, Type};
 
-use crate::field::FieldGenerator;
+use crate::field::{FieldFactory, FieldGenerator};
 
 /// Generate a custom `Debug` implementation with secret masking.
 pub fn generate_debug_impl(
     struct_name: &Ident,
     generics: &Generics,
     fields: &[Box<dyn FieldGenerator>],
+    allow_unmask: bool,
+    unmask_in_debug: bool,
 ) -> QuoteStream {
     let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
     // Get struct name as string for debug_struct()
     let struct_name_str = struct_name.to_string();
 
-    // Generate .field() calls for each field
+    // Masks for opted-in secret collections, bound ahead of the debug_struct chain
+    let mut mask_bindings: Vec<QuoteStream> = Vec::new();
+
+    // Generate .field() calls for each field; skipped fields need not be Debug
     let field_entries: Vec<QuoteStream> = fields
         .iter()
+        .filter(|f| !f.is_skip())
         .map(|f| {
             let name = f.name();
             let name_str = name.to_string();
@@ -69,9 +96,64 @@ pub fn generate_debug_impl(
             if f.is_secrecy_type() {
                 // Secrecy types handle their own Debug - just reference the field
                 quote! { .field(#name_str, &self.#name) }
+            } else if (allow_unmask || unmask_in_debug) && f.is_secret() {
+                // Opted-in secret field - masked unless unmasked for this run or build
+                let masked = f.field_type().and_then(masked_collection).map_or_else(
+                    || quote! { &::procenv::runtime::REDACTED },
+                    |masked| {
+                        // Bound before the chain so the mask outlives the `if`
+                        let binding = format_ident!("__masked_{}", name);
+                        let value = if f.is_optional() {
+                            let value =
+                                FieldFactory::optional_ref(f.as_ref(), &quote! { self.#name });
+                            quote! { #value.map(|__v| #masked(__v.len())) }
+                        } else {
+                            quote! { #masked(self.#name.len()) }
+                        };
+                        mask_bindings.push(quote! { let #binding = #value; });
+
+                        quote! { &#binding }
+                    },
+                );
+
+                quote! {
+                    .field(
+                        #name_str,
+                        if __unmask { &self.#name as &dyn std::fmt::Debug } else { #masked },
+                    )
+                }
+            } else if let Some(masked) = f
+                .is_secret()
+                .then(|| f.field_type().and_then(masked_collection))
+                .flatten()
+            {
+                // Secret collection - show one mask per element
+                if f.is_optional() {
+                    let value = FieldFactory::optional_ref(f.as_ref(), &quote! { self.#name });
+                    quote! {
+                        .field(#name_str, &#value.map(|__v| #masked(__v.len())))
+                    }
+                } else {
+                    quote! { .field(#name_str, &#masked(self.#name.len())) }
+                }
             } else if f.is_secret() {
                 // Manual secret field - show placeholder
-                quote! { .field(#name_str, &"[REDACTED]") }
+                quote! { .field(#name_str, &::procenv::runtime::REDACTED) }
+            } else if let Some(pattern) = f.redact_pattern() {
+                // Structured secret - mask only the captured parts
+                let redact = quote! {
+                    {
+                        static __PATTERN: ::procenv::runtime::LazyRegex =
+                            ::procenv::runtime::LazyRegex::new(#pattern);
+                        __PATTERN.redact(std::convert::AsRef::<str>::as_ref(__v))
+                    }
+                };
+                if f.is_optional() {
+                    let value = FieldFactory::optional_ref(f.as_ref(), &quote! { self.#name });
+                    quote! { .field(#name_str, &#value.map(|__v| #redact)) }
+                } else {
+                    quote! { .field(#name_str, &{ let __v = &self.#name; #redact }) }
+                }
             } else {
                 // Normal field - show actual value
                 quote! { .field(#name_str, &self.#name) }
@@ -79,9 +161,19 @@ pub fn generate_debug_impl(
         })
         .collect();
 
+    let unmask_check = if allow_unmask {
+        quote! { let __unmask = ::procenv::runtime::unmask_requested(); }
+    } else if unmask_in_debug {
+        quote! { let __unmask = Self::__UNMASKED; }
+    } else {
+        quote! {}
+    };
+
     quote! {
         impl #impl_generics std::fmt::Debug for #struct_name #type_generics #where_clause {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                #unmask_check
+                #(#mask_bindings)*
                 f.debug_struct(#struct_name_str)
                     #(#field_entries)*
                     .finish()
@@ -89,3 +181,19 @@ pub fn generate_debug_impl(
         }
     }
 }
+
+/// Returns the masking wrapper for a collection type, if `ty` is one.
+fn masked_collection(ty: &Type) -> Option<QuoteStream> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+
+    match segment.ident.to_string().as_str() {
+        "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => {
+            Some(quote! { ::procenv::runtime::MaskedSeq })
+        }
+        "HashMap" | "BTreeMap" => Some(quote! { ::procenv::runtime::MaskedMap }),
+        _ => None,
+    }
+}
@@ -6,10 +6,22 @@
 //! # Generated Methods
 //!
 //! - [`generate_from_env_impl`] - Main `from_env()` method
+//! - [`generate_from_env_body`] - Body shared by `from_env()` and `from_env_timed()`
+//! - [`generate_error_sink`] - The `__errors` collector loaders push into
+//! - [`generate_bail`] - `no_accumulate` early return after each loading step
+//! - [`generate_error_return`] - Return of accumulated errors
 //! - [`generate_profile_setup`] - Profile environment variable handling
 //! - [`generate_dotenv_load`] - `.env` file loading code
+//! - [`generate_dotenv_flags`] - Dotenv bindings used by source tracking
 //! - [`generate_field_loader`] - Per-field loading with profile/format support
-//! - [`generate_from_env_with_external_prefix_impl`] - Prefixed loading for nested structs
+//! - [`generate_field_loader_with_external_prefix`] - The same under `__external_prefix`
+//! - [`generate_value_checks`] - Post-load `one_of` and float finiteness checks
+//! - [`generate_error_redaction`] - `redact_pattern` masking of a field's errors
+//! - [`generate_audit_call`] - `audit` hook call for secret/`sensitive_log` fields
+//! - [`generate_prefix_env_dispatch`] - `prefix_env` switch to runtime-prefixed loading
+//! - [`generate_empty_struct_fallback`] - `allow_empty_struct` fallback to `Default`
+//! - [`generate_from_env_with_external_prefix_impl`] - Runtime-prefixed loading
+//!   (`from_env_with_prefix()` and nested structs)
 //!
 //! # Error Accumulation
 //!
@@ -28,6 +40,9 @@
 //! }
 //! ```
 //!
+//! With `no_accumulate`, `__errors` is a `FirstError` instead, and each
+//! step is followed by `__errors.check()?;`, returning its first error.
+//!
 //! # Profile Support
 //!
 //! When `profile_env` is configured, the generated code reads the profile
@@ -39,18 +54,14 @@ use proc_macro2::TokenStream as QuoteStream;
 use quote::{format_ident, quote};
 use syn::{Generics, Ident};
 
-use crate::field::FieldGenerator;
+use crate::field::{FieldFactory, FieldGenerator};
 use crate::parse::{DotenvConfig, EnvConfigAttr};
 
 /// Generate the `from_env()` method implementation.
 ///
-/// This generates code that:
-/// 1. Optionally loads .env file(s) if configured
-/// 2. Reads and validates profile if configured
-/// 3. Creates an error accumulator vector
-/// 4. Loads each field (calling each `FieldGenerator`'s `generate_loader()`)
-/// 5. If any errors occurred, returns the (single or Multiple variant)
-/// 6. Otherwise constructs and returns the struct
+/// The method body comes from [`generate_from_env_body`]; this adds the doc
+/// comments and the methods built on `from_env()` (`from_dotenv_str()`,
+/// `from_env_formatted()` and `active_profile()`).
 pub fn generate_from_env_impl(
     struct_name: &Ident,
     generics: &Generics,
@@ -60,20 +71,20 @@ pub fn generate_from_env_impl(
     // Split generics for the impl block
     let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
-    // Generate loader code for each field
-    let loaders: Vec<QuoteStream> = fields
-        .iter()
-        .map(|f| generate_field_loader(f.as_ref(), env_config_attr))
-        .collect();
-
-    // Generate assignment code for each field
-    let assignments: Vec<QuoteStream> = fields.iter().map(|f| f.generate_assignment()).collect();
+    let body = generate_from_env_body(fields, env_config_attr, false);
 
-    // Generate dotenv loading code (if configured)
-    let dotenv_load = generate_dotenv_load(env_config_attr.dotenv.as_ref());
+    let errors_doc = if env_config_attr.no_accumulate {
+        "Loading stops at the first error, which is returned on its own."
+    } else {
+        "All errors are accumulated and returned together."
+    };
 
-    // Generate profile setup code (if configured)
-    let profile_setup = generate_profile_setup(env_config_attr);
+    let active_profile = generate_active_profile_method(env_config_attr);
+    let error_format = if env_config_attr.error_format.as_deref() == Some("json") {
+        quote! { ::procenv::ErrorFormat::Json }
+    } else {
+        quote! { ::procenv::ErrorFormat::Pretty }
+    };
 
     quote! {
         impl #impl_generics #struct_name #type_generics #where_clause {
@@ -84,38 +95,338 @@ pub fn generate_from_env_impl(
             ///
             /// # Errors
             /// Returns an error if any required variables are missing or
-            /// if any values fail to parse. All errors are accumulated
-            /// and returned together.
+            /// if any values fail to parse.
+            #[doc = #errors_doc]
             pub fn from_env() -> std::result::Result<Self, ::procenv::Error> {
-                // Load .env file(s) if configured (errors are silently ignored)
-                #dotenv_load
+                #body
+            }
 
-                // Define external prefix as None for regular from_env calls
-                let __external_prefix: std::option::Option<&str> = std::option::Option::None;
+            /// Load configuration from `.env`-formatted text.
+            ///
+            /// Behaves like `from_env()`, but variables come only from
+            /// `content`: the process environment is neither read nor
+            /// modified, and no dotenv files are loaded.
+            ///
+            /// # Errors
+            /// Returns an error if `content` is not valid dotenv syntax,
+            /// or any error `from_env()` would return.
+            pub fn from_dotenv_str(content: &str) -> std::result::Result<Self, ::procenv::Error> {
+                ::procenv::runtime::with_dotenv_str(content, Self::from_env)
+            }
+
+            /// Load configuration from environment variables, rendering any
+            /// error as a string.
+            ///
+            /// Like `from_env()`, but errors come back already formatted as
+            /// chosen by `#[env_config(error_format = "...")]`, miette's
+            /// graphical report by default.
+            ///
+            /// # Errors
+            /// Returns the rendered error `from_env()` would return.
+            pub fn from_env_formatted() -> std::result::Result<Self, std::string::String> {
+                Self::from_env().map_err(|e| e.render(#error_format))
+            }
 
-                // Accumulator for all errors encountered during loading
-                let mut __errors: std::vec::Vec<::procenv::Error> = std::vec::Vec::new();
+            #active_profile
+        }
+    }
+}
 
-                // Read and validate profile (if configured)
-                #profile_setup
+/// Generate the body of `from_env()`, returning `Result<Self, Error>`.
+///
+/// This generates code that:
+/// 1. Hands off to runtime-prefixed loading if `prefix_env` is set
+/// 2. Optionally loads .env file(s) if configured
+/// 3. Reads and validates profile if configured
+/// 4. Creates the error collector from [`generate_error_sink`]
+/// 5. Loads each field (calling each `FieldGenerator`'s `generate_loader()`)
+/// 6. If any errors occurred, returns them (single or Multiple variant),
+///    or falls back to `Default` for an empty `allow_empty_struct` struct
+/// 7. Otherwise constructs and returns the struct
+///
+/// `from_env_timed()` shares this body with `timed` set, which wraps each
+/// field loader in a timer recording into a `__timings` in scope.
+pub fn generate_from_env_body(
+    fields: &[Box<dyn FieldGenerator>],
+    env_config_attr: &EnvConfigAttr,
+    timed: bool,
+) -> QuoteStream {
+    let no_accumulate = env_config_attr.no_accumulate;
+    let bail = generate_bail(no_accumulate);
 
-                // Load each field - errors are pushed to __errors
-                #(#loaders)*
+    // Generate loader code for each field
+    let loaders: Vec<QuoteStream> = fields
+        .iter()
+        .map(|f| {
+            let loader = generate_field_loader(f.as_ref(), env_config_attr);
 
-                // If any errors occurred, return them
-                if !__errors.is_empty() {
-                    return std::result::Result::Err(if __errors.len() == 1 {
-                        __errors.pop().unwrap()
-                    } else {
-                        ::procenv::Error::Multiple { errors: __errors }
-                    });
+            // Skipped fields load nothing, so there is nothing to time
+            let loader = if timed && (!f.is_skip() || f.is_serde_flatten()) {
+                let name_str = f.name().to_string();
+                quote! {
+                    let __field_start = std::time::Instant::now();
+                    #loader
+                    __timings.record(#name_str, __field_start.elapsed());
                 }
+            } else {
+                loader
+            };
 
-                // All fields loaded successfully - construct the struct
-                std::result::Result::Ok(Self {
-                    #(#assignments),*
-                })
+            quote! {
+                #loader
+                #bail
             }
+        })
+        .collect();
+
+    // Generate assignment code for each field
+    let assignments: Vec<QuoteStream> = fields.iter().map(|f| f.generate_assignment()).collect();
+
+    // Generate dotenv loading code (if configured)
+    let dotenv_load = generate_dotenv_load(env_config_attr.dotenv.as_ref());
+
+    // Generate profile setup code (if configured)
+    let profile_setup = generate_profile_setup(env_config_attr);
+
+    let case_insensitive_scope = generate_case_insensitive_scope(env_config_attr);
+    let prefix_env_dispatch = generate_prefix_env_dispatch(
+        env_config_attr,
+        no_accumulate,
+        &quote! { .map(|(config, _)| config) },
+    );
+
+    let error_sink = generate_error_sink(no_accumulate);
+    let error_return = generate_error_return(no_accumulate);
+
+    let steps = generate_empty_struct_fallback(
+        fields,
+        env_config_attr,
+        false,
+        &[],
+        quote! {
+            #error_sink
+
+            // Read and validate profile (if configured)
+            #profile_setup
+            #bail
+
+            // Load each field - errors are pushed to __errors
+            #(#loaders)*
+
+            // If any errors occurred, return them
+            #error_return
+
+            // All fields loaded successfully - construct the struct
+            std::result::Result::Ok(Self {
+                #(#assignments),*
+            })
+        },
+    );
+
+    quote! {
+        #case_insensitive_scope
+        #prefix_env_dispatch
+
+        // Load .env file(s) if configured (errors are silently ignored)
+        #dotenv_load
+
+        // Define external prefix as None for regular from_env calls
+        let __external_prefix: std::option::Option<&str> = std::option::Option::None;
+
+        #steps
+    }
+}
+
+/// Generate the `__errors` collector a loader's steps push into.
+///
+/// Errors accumulate in a `Vec`; with `no_accumulate`, a
+/// `::procenv::runtime::FirstError` keeps only the first, for
+/// [`generate_bail`] to return. Only `from_env()` and `from_env_timed()`
+/// honour `no_accumulate`; the other loaders always accumulate.
+pub fn generate_error_sink(no_accumulate: bool) -> QuoteStream {
+    if no_accumulate {
+        quote! {
+            let mut __errors = ::procenv::runtime::FirstError::new();
+        }
+    } else {
+        quote! {
+            let mut __errors: std::vec::Vec<::procenv::Error> = std::vec::Vec::new();
+        }
+    }
+}
+
+/// Generate the `no_accumulate` check that follows each loading step.
+///
+/// With `no_accumulate`, the loader returns the step's error with `?` as
+/// soon as one is recorded. Otherwise nothing is generated and errors
+/// accumulate.
+fn generate_bail(no_accumulate: bool) -> QuoteStream {
+    if !no_accumulate {
+        return quote! {};
+    }
+
+    quote! {
+        __errors.check()?;
+    }
+}
+
+/// Generate the return of accumulated errors after the last loading step.
+///
+/// A single error is returned as is, several as `Error::Multiple`. With
+/// `no_accumulate`, [`generate_bail`] has already returned any error, so
+/// nothing is generated.
+pub fn generate_error_return(no_accumulate: bool) -> QuoteStream {
+    if no_accumulate {
+        return quote! {};
+    }
+
+    quote! {
+        if !__errors.is_empty() {
+            return std::result::Result::Err(if __errors.len() == 1 {
+                __errors.pop().unwrap()
+            } else {
+                ::procenv::Error::Multiple { errors: __errors }
+            });
+        }
+    }
+}
+
+/// Generate the `case_insensitive_env` scope at the top of a loader.
+///
+/// For structs with `case_insensitive_env`, every var read until the loader
+/// returns, including by flattened structs, matches ignoring ASCII case.
+pub fn generate_case_insensitive_scope(env_config_attr: &EnvConfigAttr) -> QuoteStream {
+    if !env_config_attr.case_insensitive_env {
+        return quote! {};
+    }
+
+    quote! {
+        let __case_insensitive = ::procenv::runtime::case_insensitive_scope();
+    }
+}
+
+/// Generate the `allow_empty_struct` fallback around a loader's `steps`.
+///
+/// `steps` declare `__errors`, load the fields and end in the loader's
+/// result; they run in a closure so the errors they return, early or not,
+/// come back here. When loading failed and none of the struct's own vars
+/// (including `default_from` and `from_file_var` vars) are set under
+/// `__external_prefix`, nor any of the `given` CLI values, the loader returns
+/// `Default::default()` instead, paired with empty sources for loaders that
+/// return them. Without `allow_empty_struct`, `steps` are returned as is.
+pub fn generate_empty_struct_fallback(
+    generators: &[Box<dyn FieldGenerator>],
+    env_config_attr: &EnvConfigAttr,
+    with_sources: bool,
+    given: &[Ident],
+    steps: QuoteStream,
+) -> QuoteStream {
+    if !env_config_attr.allow_empty_struct {
+        return steps;
+    }
+
+    let vars: Vec<&str> = generators
+        .iter()
+        .flat_map(|g| {
+            g.env_var_name()
+                .into_iter()
+                .chain(g.default_from())
+                .chain(g.file_var())
+        })
+        .collect();
+
+    let empty = quote! { ::procenv::runtime::empty_struct::<Self>() };
+    let (value, ty) = if with_sources {
+        (
+            quote! { (#empty, ::procenv::ConfigSources::new()) },
+            quote! { (Self, ::procenv::ConfigSources) },
+        )
+    } else {
+        (empty, quote! { Self })
+    };
+
+    quote! {
+        #[allow(clippy::result_large_err)]
+        let __loaded = (|| -> std::result::Result<#ty, ::procenv::Error> { #steps })();
+
+        if __loaded.is_err()
+            #(&& #given.is_none())*
+            && !::procenv::runtime::any_var_set(
+                &[#(#vars),*],
+                __external_prefix,
+                Self::__PREFIX_SEPARATOR,
+            )
+        {
+            return std::result::Result::Ok(#value);
+        }
+
+        __loaded
+    }
+}
+
+/// Generate the `prefix_env` check at the top of a loader.
+///
+/// When the struct has `prefix_env` and that variable is set, loading is
+/// handed to `__from_env_with_external_prefix` with its value, and `finish`
+/// adapts the `(config, sources)` result to the loader's return type. With
+/// `no_accumulate` set, the fail-fast variant of that method is used. The
+/// variable is read from the process environment, before any dotenv file.
+pub fn generate_prefix_env_dispatch(
+    env_config_attr: &EnvConfigAttr,
+    no_accumulate: bool,
+    finish: &QuoteStream,
+) -> QuoteStream {
+    let Some(prefix_env) = &env_config_attr.prefix_env else {
+        return quote! {};
+    };
+
+    let method = if no_accumulate {
+        format_ident!("__from_env_with_external_prefix_fail_fast")
+    } else {
+        format_ident!("__from_env_with_external_prefix")
+    };
+
+    quote! {
+        if let std::result::Result::Ok(__runtime_prefix) = ::procenv::runtime::var(#prefix_env) {
+            return Self::#method(
+                std::option::Option::Some(&__runtime_prefix),
+            )#finish;
+        }
+    }
+}
+
+/// Generate `active_profile()` for structs with `profile_env`.
+///
+/// The profile is resolved as the loaders resolve it: the var's value, or
+/// `default_profile` when it is unset.
+fn generate_active_profile_method(env_config_attr: &EnvConfigAttr) -> QuoteStream {
+    let Some(profile_env) = &env_config_attr.profile_env else {
+        return quote! {};
+    };
+
+    let fallback = env_config_attr.default_profile.as_ref().map_or_else(
+        || quote! {},
+        |default_profile| {
+            quote! { .or_else(|| std::option::Option::Some(#default_profile.to_string())) }
+        },
+    );
+
+    let case_insensitive_scope = generate_case_insensitive_scope(env_config_attr);
+    let doc = format!(
+        "Return the active profile: the value of `{profile_env}`, or the default \
+         profile when it is unset."
+    );
+
+    quote! {
+        #[doc = #doc]
+        ///
+        /// The environment is read as it is now, so call this after loading
+        /// to see variables set by dotenv files. The value is not checked
+        /// against the declared profiles.
+        #[must_use]
+        pub fn active_profile() -> std::option::Option<std::string::String> {
+            #case_insensitive_scope
+            ::procenv::runtime::var(#profile_env).ok()#fallback
         }
     }
 }
@@ -150,15 +461,18 @@ pub fn generate_profile_setup(env_config_attr: &EnvConfigAttr) -> QuoteStream {
         },
     );
 
+    let unset = env_config_attr.default_profile.as_ref().map_or_else(
+        || quote! { std::option::Option::None },
+        |default_profile| quote! { std::option::Option::Some(#default_profile.to_string()) },
+    );
+
     quote! {
         // Read profile from environment variable, reporting UTF-8 errors
-        let __profile: std::option::Option<std::string::String> = match std::env::var(#profile_env) {
+        let __profile: std::option::Option<std::string::String> = match ::procenv::runtime::var(#profile_env) {
             std::result::Result::Ok(val) => std::option::Option::Some(val),
-            std::result::Result::Err(std::env::VarError::NotPresent) => std::option::Option::None,
-            std::result::Result::Err(std::env::VarError::NotUnicode(_)) => {
-                __errors.push(::procenv::Error::InvalidUtf8 {
-                    var: #profile_env.to_string()
-                });
+            std::result::Result::Err(std::env::VarError::NotPresent) => #unset,
+            std::result::Result::Err(std::env::VarError::NotUnicode(__raw)) => {
+                __errors.push(::procenv::Error::invalid_utf8(#profile_env, &__raw, false));
                 std::option::Option::None
             }
         };
@@ -166,25 +480,58 @@ pub fn generate_profile_setup(env_config_attr: &EnvConfigAttr) -> QuoteStream {
     }
 }
 
-/// Generate field loader with profile and format support.
-#[expect(clippy::too_many_lines, reason = "Complex macro logic.")]
+/// Generate field loader with profile, format and `audit` hook support.
 pub fn generate_field_loader(
     field: &dyn FieldGenerator,
-    _env_config_attr: &EnvConfigAttr,
+    env_config_attr: &EnvConfigAttr,
 ) -> QuoteStream {
+    // Skipped fields have nothing to load
+    if field.is_skip() {
+        return field.generate_loader();
+    }
+
+    let env_var = FieldFactory::var_expr(field.env_var_name().unwrap_or(""), field.default_from());
+    let loader = generate_profile_requirement(
+        field,
+        generate_error_redaction(field, generate_env_field_loader(field)),
+        &env_var,
+    );
+    let audit = generate_audit_call(field, env_config_attr);
+
+    quote! {
+        #loader
+        #audit
+    }
+}
+
+/// Generate field loader with profile and format support.
+#[expect(
+    clippy::too_many_lines,
+    reason = "proc-macro code generation inherently requires verbose quote! blocks"
+)]
+fn generate_env_field_loader(field: &dyn FieldGenerator) -> QuoteStream {
     // Determine which loader to use based on format
     let base_loader = field.format_config().map_or_else(
         || field.generate_loader(),
         |format| field.generate_format_loader(format),
     );
 
+    let name = field.name();
+    let env_var = FieldFactory::var_expr(field.env_var_name().unwrap_or(""), field.default_from());
+    let read = FieldFactory::read_expr(&env_var, field, false);
+    let after_parse = generate_after_parse(field, name, &env_var, false);
+    let value_checks = generate_value_checks(field, &quote! { #name }, &env_var);
+
     // Check if this field has profile-specific values
     let Some(profile_config) = field.profile_config() else {
-        return base_loader;
+        return quote! {
+            #base_loader
+            #after_parse
+            #value_checks
+        };
     };
 
     // Field has profile values - generate profile-aware loader
-    let name = field.name();
     let profile_used_ident = format_ident!("__{}_from_profile", name);
 
     // Generate match arms for each profile
@@ -198,30 +545,13 @@ pub fn generate_field_loader(
         })
         .collect();
 
-    // Get env var name and type info for parsing
-    let env_var = field.env_var_name().unwrap_or("");
-    let ty = field.type_name();
-    let secret = field.is_secret();
+    // Get type info for parsing
+    let secret = FieldFactory::redact_expr(field.is_secret());
     let default_value = field.default_value();
     let used_default_ident = format_ident!("__{}_used_default", name);
 
     // Generate the parse/deserialize expression based on format
-    let (parse_expr, type_desc) = field.format_config().map_or_else(
-        || (quote! { val.parse() }, ty.clone()),
-        |format| {
-            let expr = match format {
-                "json" => quote! { ::serde_json::from_str(&val) },
-
-                "toml" => quote! { ::toml::from_str(&val) },
-
-                "yaml" => quote! { ::serde_saphyr::from_str(&val) },
-
-                _ => quote! { val.parse() },
-            };
-
-            (expr, format!("{} data", format.to_uppercase()))
-        },
-    );
+    let (parse_expr, type_desc) = generate_value_parse_expr(field, &env_var);
 
     // Generate fallback code for when no env var and no profile match
     let no_value_handling = default_value.map_or_else(
@@ -231,9 +561,11 @@ pub fn generate_field_loader(
             }
         },
         |default| {
+            let value = FieldFactory::default_option_expr(default, &env_var);
+
             quote! {
                 #used_default_ident = true;
-                (std::option::Option::Some(#default.to_string()), false)
+                (#value, false)
             }
         },
     );
@@ -261,7 +593,7 @@ pub fn generate_field_loader(
 
         // Get value to parse: env var > profile > default
         let (__value_to_parse, #profile_used_ident): (std::option::Option<std::string::String>, bool) =
-            match std::env::var(#env_var) {
+            match #read {
                 std::result::Result::Ok(val) => {
                     (std::option::Option::Some(val), false)
                 }
@@ -275,8 +607,8 @@ pub fn generate_field_loader(
                         }
                     }
                 }
-                std::result::Result::Err(std::env::VarError::NotUnicode(_)) => {
-                    __errors.push(::procenv::Error::InvalidUtf8 { var: #env_var.to_string() });
+                std::result::Result::Err(std::env::VarError::NotUnicode(__raw)) => {
+                    __errors.push(::procenv::Error::invalid_utf8(#env_var, &__raw, #secret));
                     (std::option::Option::None, false)
                 }
             };
@@ -289,7 +621,7 @@ pub fn generate_field_loader(
                     std::result::Result::Err(e) => {
                         __errors.push(::procenv::Error::parse(
                             #env_var,
-                            if #secret { "[REDACTED]".to_string() } else { val },
+                            if #secret { ::procenv::runtime::REDACTED.to_string() } else { val },
                             #secret,
                             #type_desc,
                             std::boxed::Box::new(e),
@@ -302,13 +634,351 @@ pub fn generate_field_loader(
                 #missing_value_handling
             }
         };
+
+        #after_parse
+        #value_checks
+    }
+}
+
+/// Generate the `FromStr` (string-wrapper, base64 or list) parse of `value` for a field.
+pub fn generate_parse_expr(field: &dyn FieldGenerator, value: &QuoteStream) -> QuoteStream {
+    field.field_type().map_or_else(
+        || quote! { #value.parse() },
+        |ty| {
+            FieldFactory::value_expr(
+                ty,
+                value,
+                field.is_base64(),
+                field.coerces_bool_numeric(),
+                field.sep_env(),
+            )
+        },
+    )
+}
+
+/// Generate the expression parsing `val` for a field, and the type named in
+/// its parse errors.
+///
+/// Format fields are deserialized, everything else goes through
+/// [`generate_parse_expr`]. `var` evaluates to the field's env var name,
+/// which `merge_env` fields read their overrides under.
+pub fn generate_value_parse_expr(
+    field: &dyn FieldGenerator,
+    var: &QuoteStream,
+) -> (QuoteStream, String) {
+    let Some(format) = field.format_config() else {
+        return (
+            generate_parse_expr(field, &quote! { val }),
+            field.type_name(),
+        );
+    };
+
+    let expr = match format {
+        "json" => FieldFactory::json_expr(var, field.merges_env()),
+        "toml" => quote! { ::toml::from_str(&val) },
+        "yaml" => quote! { ::serde_saphyr::from_str(&val) },
+        "auto" => quote! { ::procenv::runtime::deserialize_auto(&val) },
+        _ => quote! { val.parse() },
+    };
+    let expr = FieldFactory::deserialize_with_expr(expr, field.deserialize_with());
+
+    (expr, format!("{} data", FieldFactory::format_label(format)))
+}
+
+/// Generate the post-load checks for a loaded field.
+///
+/// Runs after the field's loader, so it covers env, profile and default
+/// values alike. `value` evaluates to an `Option` holding the loaded value
+/// and `var` evaluates to the env var name reported in the error. Returns
+/// an empty stream for fields that need no checks.
+pub fn generate_value_checks(
+    field: &dyn FieldGenerator,
+    value: &QuoteStream,
+    var: &QuoteStream,
+) -> QuoteStream {
+    let one_of_check = generate_one_of_check(field, value, var);
+    let finite_check = generate_finite_check(field, value, var);
+    let length_check = generate_length_check(field, value, var);
+    let regex_check = generate_regex_check(field, value, var);
+
+    quote! {
+        #one_of_check
+        #finite_check
+        #length_check
+        #regex_check
+    }
+}
+
+/// Generate the `after_parse` hook call for a loaded field.
+///
+/// Rebinds `local`, an `Option` holding the parsed value, to the hook's
+/// output. A failing hook pushes `Error::AfterParse` and leaves `None`.
+/// Emitted just before [`generate_value_checks`], so the checks see the
+/// transformed value. `nested` marks the `Option<Option<T>>` locals that
+/// optional fields use in `__from_json_value()`.
+pub fn generate_after_parse(
+    field: &dyn FieldGenerator,
+    local: &Ident,
+    var: &QuoteStream,
+    nested: bool,
+) -> QuoteStream {
+    let Some(hook) = field.after_parse() else {
+        return quote! {};
+    };
+
+    let hook_fn: syn::Path =
+        syn::parse_str(hook).expect("after_parse path validated during parsing");
+    let (pattern, wrap) = if nested {
+        (
+            quote! { std::option::Option::Some(std::option::Option::Some(__value)) },
+            quote! { std::option::Option::Some(std::option::Option::Some(v)) },
+        )
+    } else {
+        (
+            quote! { std::option::Option::Some(__value) },
+            quote! { std::option::Option::Some(v) },
+        )
+    };
+
+    quote! {
+        let #local = match #local {
+            #pattern => match #hook_fn(__value) {
+                std::result::Result::Ok(v) => #wrap,
+                std::result::Result::Err(e) => {
+                    __errors.push(::procenv::Error::after_parse(#var, #hook, e));
+                    std::option::Option::None
+                }
+            },
+            other => other,
+        };
+    }
+}
+
+/// Generate the `audit` hook call for a secret or `sensitive_log` field.
+///
+/// The hook receives the effective env var name (including any runtime
+/// prefix) and whether it is set. Only presence is checked, so the value
+/// never reaches the hook. Expects `__external_prefix` in scope, which every
+/// loading entry point declares.
+pub fn generate_audit_call(
+    field: &dyn FieldGenerator,
+    env_config_attr: &EnvConfigAttr,
+) -> QuoteStream {
+    let Some(audit) = env_config_attr.audit.as_deref() else {
+        return quote! {};
+    };
+
+    if field.is_flatten() || !field.is_audited() {
+        return quote! {};
+    }
+
+    let audit_fn: syn::Path = syn::parse_str(audit).expect("audit path validated during parsing");
+    let env_var = field.env_var_name().unwrap_or("");
+    let audit_var = FieldFactory::external_var_expr(&quote! { #env_var });
+
+    quote! {
+        {
+            let __audit_var: std::string::String = #audit_var;
+            #audit_fn(&__audit_var, ::procenv::runtime::var_os(&__audit_var).is_some());
+        }
+    }
+}
+
+/// Generate the `one_of` allowlist check for a loaded field.
+fn generate_one_of_check(
+    field: &dyn FieldGenerator,
+    value: &QuoteStream,
+    var: &QuoteStream,
+) -> QuoteStream {
+    let Some(allowed) = field.one_of() else {
+        return quote! {};
+    };
+    let secret = FieldFactory::redact_expr(field.is_secret());
+
+    quote! {
+        if let std::option::Option::Some(ref __value) = #value {
+            let __allowed: &[&str] = &[#(#allowed),*];
+            let __value: &str = std::convert::AsRef::<str>::as_ref(__value);
+            if !__allowed.contains(&__value) {
+                __errors.push(::procenv::Error::not_one_of(#var, __value, #secret, __allowed));
+            }
+        }
+    }
+}
+
+/// Generate the `min_len`/`max_len` check for a string field.
+///
+/// Lengths count characters. `SecretString` values are measured through
+/// `expose_secret()` and the error never carries the value.
+fn generate_length_check(
+    field: &dyn FieldGenerator,
+    value: &QuoteStream,
+    var: &QuoteStream,
+) -> QuoteStream {
+    let (min, max) = (field.min_len(), field.max_len());
+    if min.is_none() && max.is_none() {
+        return quote! {};
+    }
+
+    let text = if field.is_secrecy_type() {
+        quote! { ::procenv::ExposeSecret::expose_secret(__value) }
+    } else {
+        quote! { std::convert::AsRef::<str>::as_ref(__value) }
+    };
+    let bound = |b: Option<usize>| {
+        b.map_or_else(
+            || quote! { std::option::Option::None },
+            |b| quote! { std::option::Option::Some(#b) },
+        )
+    };
+    let (min, max) = (bound(min), bound(max));
+
+    quote! {
+        if let std::option::Option::Some(ref __value) = #value {
+            if !::procenv::runtime::len_in_bounds(#text, #min, #max) {
+                __errors.push(::procenv::Error::invalid_length(#var, #min, #max));
+            }
+        }
+    }
+}
+
+/// Generate the `validate_regex` check for a string field.
+///
+/// The pattern lives in a per-field `static`, so it is compiled once on
+/// first use. A pattern that fails to compile is reported as
+/// `Error::InvalidRegex` rather than as a mismatch.
+fn generate_regex_check(
+    field: &dyn FieldGenerator,
+    value: &QuoteStream,
+    var: &QuoteStream,
+) -> QuoteStream {
+    let Some(pattern) = field.validate_regex() else {
+        return quote! {};
+    };
+
+    let text = if field.is_secrecy_type() {
+        quote! { ::procenv::ExposeSecret::expose_secret(__value) }
+    } else {
+        quote! { std::convert::AsRef::<str>::as_ref(__value) }
+    };
+    let secret = FieldFactory::redact_expr(field.is_secret());
+
+    quote! {
+        if let std::option::Option::Some(ref __value) = #value {
+            static __REGEX: ::procenv::runtime::LazyRegex =
+                ::procenv::runtime::LazyRegex::new(#pattern);
+            let __text: &str = #text;
+            match __REGEX.is_match(__text) {
+                std::result::Result::Ok(true) => {}
+                std::result::Result::Ok(false) => {
+                    __errors.push(::procenv::Error::regex_mismatch(
+                        #var,
+                        __text,
+                        #secret,
+                        __REGEX.pattern(),
+                    ));
+                }
+                std::result::Result::Err(__message) => {
+                    __errors.push(::procenv::Error::invalid_regex(
+                        #var,
+                        __REGEX.pattern(),
+                        __message,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Wrap a field loader so the errors it pushes are masked by `redact_pattern`.
+///
+/// The loader's bindings stay in scope; only the values carried by errors
+/// pushed since it started are rewritten.
+pub fn generate_error_redaction(field: &dyn FieldGenerator, loader: QuoteStream) -> QuoteStream {
+    let Some(pattern) = field.redact_pattern() else {
+        return loader;
+    };
+
+    quote! {
+        let __redact_from = __errors.len();
+        #loader
+        {
+            static __PATTERN: ::procenv::runtime::LazyRegex =
+                ::procenv::runtime::LazyRegex::new(#pattern);
+            ::procenv::runtime::redact_errors(&mut __errors[__redact_from..], &__PATTERN);
+        }
+    }
+}
+
+/// Follow a `when_profile` field's loader with the check that it was set.
+///
+/// The field loads as optional; it is only reported missing when the active
+/// profile is one that requires it and loading recorded no other error.
+fn generate_profile_requirement(
+    field: &dyn FieldGenerator,
+    loader: QuoteStream,
+    var: &QuoteStream,
+) -> QuoteStream {
+    let Some(profiles) = field.when_profile() else {
+        return loader;
+    };
+    let name = field.name();
+
+    quote! {
+        let __required_from = __errors.len();
+        #loader
+        if #name.is_none() && __errors.len() == __required_from {
+            let __required_in: &[&str] = &[#(#profiles),*];
+            if let std::option::Option::Some(__p) =
+                __profile.as_deref().filter(|p| __required_in.contains(p))
+            {
+                __errors.push(::procenv::Error::missing_in_profile(#var, __p));
+            }
+        }
+    }
+}
+
+/// Generate the finiteness check for a float field.
+///
+/// `RATE=inf` parses fine as `f64`, so NaN and infinity are rejected here
+/// unless the field opts out with `allow_non_finite`.
+fn generate_finite_check(
+    field: &dyn FieldGenerator,
+    value: &QuoteStream,
+    var: &QuoteStream,
+) -> QuoteStream {
+    if !field.requires_finite() {
+        return quote! {};
+    }
+    if field.field_type().is_none() {
+        return quote! {};
+    }
+    let type_name = field.type_name();
+    let secret = FieldFactory::redact_expr(field.is_secret());
+
+    quote! {
+        if let std::option::Option::Some(ref __value) = #value
+            && !__value.is_finite()
+        {
+            __errors.push(::procenv::Error::non_finite(
+                #var,
+                __value.to_string(),
+                #secret,
+                #type_name,
+            ));
+        }
     }
 }
 
 /// Generate code to load .env file(s) based on configuration.
+///
+/// Load errors are ignored, except that a missing `dotenv_required` file
+/// returns `Error::DotenvMissing` from the enclosing function. Nothing is
+/// loaded while `from_dotenv_str()` stands in for the environment. Loaded
+/// files drop the `case_insensitive_env` snapshot so their vars are seen.
 pub fn generate_dotenv_load(dotenv_config: Option<&DotenvConfig>) -> QuoteStream {
-    match dotenv_config {
-        None => quote! {},
+    let load = match dotenv_config {
+        None => return quote! {},
 
         Some(DotenvConfig::Default) => {
             quote! {
@@ -322,6 +992,16 @@ pub fn generate_dotenv_load(dotenv_config: Option<&DotenvConfig>) -> QuoteStream
             }
         }
 
+        Some(DotenvConfig::Required(path)) => {
+            quote! {
+                if let std::result::Result::Err(__e) = ::dotenvy::from_filename(#path) {
+                    if __e.not_found() {
+                        return std::result::Result::Err(::procenv::Error::dotenv_missing(#path));
+                    }
+                }
+            }
+        }
+
         Some(DotenvConfig::Multiple(paths)) => {
             let load_calls: Vec<QuoteStream> = paths
                 .iter()
@@ -336,10 +1016,42 @@ pub fn generate_dotenv_load(dotenv_config: Option<&DotenvConfig>) -> QuoteStream
                 #(#load_calls)*
             }
         }
+    };
+
+    quote! {
+        if !::procenv::runtime::vars_overridden() {
+            #load
+            ::procenv::runtime::refresh_folded_vars();
+        }
+    }
+}
+
+/// Generate the dotenv bindings source tracking reads.
+///
+/// `__dotenv_loaded` is set when any dotenv file is configured.
+/// `__dotenv_path` holds the declared file of a single-file `dotenv` or
+/// `dotenv_required`, so values from it report `Source::DotenvFile(Some(path))`.
+pub fn generate_dotenv_flags(dotenv_config: Option<&DotenvConfig>) -> QuoteStream {
+    let path =
+        if let Some(DotenvConfig::Custom(path) | DotenvConfig::Required(path)) = dotenv_config {
+            quote! { std::option::Option::Some(std::path::PathBuf::from(#path)) }
+        } else {
+            quote! { std::option::Option::None }
+        };
+    let loaded = dotenv_config.is_some();
+
+    quote! {
+        let __dotenv_loaded = #loaded;
+        let __dotenv_path: std::option::Option<std::path::PathBuf> = #path;
     }
 }
 
-/// Generate the `__from_env_with_external_prefix` method.
+/// Generate the `__from_env_with_external_prefix` method and its public
+/// `from_env_with_prefix` wrapper.
+///
+/// With `no_accumulate` and `prefix_env`, a fail-fast
+/// `__from_env_with_external_prefix_fail_fast` is generated as well, for
+/// `from_env()` to hand runtime-prefixed loads to.
 pub fn generate_from_env_with_external_prefix_impl(
     struct_name: &Ident,
     generics: &Generics,
@@ -348,13 +1060,10 @@ pub fn generate_from_env_with_external_prefix_impl(
 ) -> QuoteStream {
     let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
-    // Collect all env var names for pre-dotenv check
-    let env_var_names: Vec<_> = generators.iter().filter_map(|g| g.env_var_name()).collect();
-
-    // Generate loaders using the prefixed version with profile support
-    let loaders: Vec<QuoteStream> = generators
+    // Collect all env var names (including `default_from` fallbacks) for pre-dotenv check
+    let env_var_names: Vec<_> = generators
         .iter()
-        .map(|g| generate_field_loader_with_prefix(g.as_ref()))
+        .flat_map(|g| g.env_var_name().into_iter().chain(g.default_from()))
         .collect();
 
     // Generate simplified source tracking
@@ -370,28 +1079,81 @@ pub fn generate_from_env_with_external_prefix_impl(
     // Dotenv loading
     let dotenv_load = generate_dotenv_load(env_config.dotenv.as_ref());
 
-    let dotenv_loaded_flag = if env_config.dotenv.is_some() {
-        quote! { let __dotenv_loaded = true; }
-    } else {
-        quote! { let __dotenv_loaded = false; }
-    };
+    let dotenv_loaded_flag = generate_dotenv_flags(env_config.dotenv.as_ref());
 
     // Profile setup
     let profile_setup = generate_profile_setup(env_config);
 
-    quote! {
-        impl #impl_generics #struct_name #type_generics #where_clause {
+    let case_insensitive_scope = generate_case_insensitive_scope(env_config);
+
+    // One method body per error mode: `from_env()` hands `prefix_env` loads
+    // to the fail-fast one with `no_accumulate`, everything else accumulates
+    let loader_method = |method: Ident, no_accumulate: bool| {
+        // Generate loaders using the prefixed version with profile support
+        let bail = generate_bail(no_accumulate);
+        let loaders: Vec<QuoteStream> = generators
+            .iter()
+            .map(|g| {
+                let loader = generate_field_loader_with_external_prefix(g.as_ref(), env_config);
+                quote! {
+                    #loader
+                    #bail
+                }
+            })
+            .collect();
+
+        let error_sink = generate_error_sink(no_accumulate);
+        let error_return = generate_error_return(no_accumulate);
+
+        let steps = generate_empty_struct_fallback(
+            generators,
+            env_config,
+            true,
+            &[],
+            quote! {
+                #error_sink
+                let mut __sources = ::procenv::ConfigSources::new();
+                __sources.set_reload_immutable(Self::__reload_immutable_fields());
+
+                // Read and validate profile (if configured)
+                #profile_setup
+                #bail
+
+                // Load each field with prefixed env var names
+                #(#loaders)*
+
+                // Track sources
+                #(#source_tracking)*
+                __sources.set_profile(__profile.clone());
+
+                // Check for errors
+                #error_return
+
+                std::result::Result::Ok((
+                    Self {
+                        #(#assignments),*
+                    },
+                    __sources
+                ))
+            },
+        );
+
+        quote! {
             /// Load configuration with an external prefix prepended to env var names.
             #[doc(hidden)]
-            pub fn __from_env_with_external_prefix(
+            pub fn #method(
                 __external_prefix: std::option::Option<&str>
             ) -> std::result::Result<(Self, ::procenv::ConfigSources), ::procenv::Error> {
+                #case_insensitive_scope
+
                 // Build effective env var names with prefix applied
                 let __base_env_vars: &[&str] = &[#(#env_var_names),*];
                 let __effective_env_vars: std::vec::Vec<std::string::String> = __base_env_vars
                     .iter()
                     .map(|var| match __external_prefix {
-                        std::option::Option::Some(prefix) => format!("{}{}", prefix, var),
+                        std::option::Option::Some(prefix) => {
+                            ::procenv::runtime::join_prefix(prefix, var, Self::__PREFIX_SEPARATOR)
+                        }
                         std::option::Option::None => (*var).to_string(),
                     })
                     .collect();
@@ -399,7 +1161,7 @@ pub fn generate_from_env_with_external_prefix_impl(
                 // Track pre-dotenv env vars using the EFFECTIVE (prefixed) names
                 let __pre_dotenv_vars: std::collections::HashSet<std::string::String> = __effective_env_vars
                     .iter()
-                    .filter(|var| std::env::var(var).is_ok())
+                    .filter(|var| ::procenv::runtime::var(var).is_ok())
                     .cloned()
                     .collect();
 
@@ -407,41 +1169,66 @@ pub fn generate_from_env_with_external_prefix_impl(
                 #dotenv_load
                 #dotenv_loaded_flag
 
-                // Error accumulator
-                let mut __errors: std::vec::Vec<::procenv::Error> = std::vec::Vec::new();
-                let mut __sources = ::procenv::ConfigSources::new();
+                #steps
+            }
+        }
+    };
 
-                // Read and validate profile (if configured)
-                #profile_setup
+    let accumulating = loader_method(format_ident!("__from_env_with_external_prefix"), false);
+    let fail_fast = if env_config.no_accumulate && env_config.prefix_env.is_some() {
+        loader_method(
+            format_ident!("__from_env_with_external_prefix_fail_fast"),
+            true,
+        )
+    } else {
+        quote! {}
+    };
 
-                // Load each field with prefixed env var names
-                #(#loaders)*
+    quote! {
+        impl #impl_generics #struct_name #type_generics #where_clause {
+            /// Load configuration with a runtime prefix prepended to every env var name.
+            ///
+            /// The prefix goes in front of the full variable name, on top of any
+            /// compiled `#[env_config(prefix = "...")]`: with `prefix = "APP_"`,
+            /// `from_env_with_prefix("TENANT_A_")` reads `TENANT_A_APP_PORT`.
+            /// Fields marked `no_prefix` only skip the compiled prefix, so they
+            /// read `TENANT_A_PORT`.
+            pub fn from_env_with_prefix(prefix: &str) -> std::result::Result<Self, ::procenv::Error> {
+                let (config, _) = Self::__from_env_with_external_prefix(std::option::Option::Some(prefix))?;
+                std::result::Result::Ok(config)
+            }
 
-                // Track sources
-                #(#source_tracking)*
+            #accumulating
+            #fail_fast
+        }
+    }
+}
 
-                // Check for errors
-                if !__errors.is_empty() {
-                    return std::result::Result::Err(if __errors.len() == 1 {
-                        __errors.pop().unwrap()
-                    } else {
-                        ::procenv::Error::Multiple { errors: __errors }
-                    });
-                }
+/// Generate field loader reading under `__external_prefix`, with profile,
+/// format and `audit` hook support.
+pub fn generate_field_loader_with_external_prefix(
+    field: &dyn FieldGenerator,
+    env_config_attr: &EnvConfigAttr,
+) -> QuoteStream {
+    let effective_var_ident = format_ident!("__{}_effective_var", field.name());
+    let loader = generate_profile_requirement(
+        field,
+        generate_error_redaction(field, generate_field_loader_with_prefix(field)),
+        &quote! { &#effective_var_ident },
+    );
+    let audit = generate_audit_call(field, env_config_attr);
 
-                std::result::Result::Ok((
-                    Self {
-                        #(#assignments),*
-                    },
-                    __sources
-                ))
-            }
-        }
+    quote! {
+        #loader
+        #audit
     }
 }
 
 /// Generate simplified source tracking for `__from_env_with_external_prefix`.
-fn generate_simple_source_tracking(field: &dyn FieldGenerator) -> QuoteStream {
+///
+/// Expects `__external_prefix`, `__dotenv_loaded` and a `__pre_dotenv_vars`
+/// set of prefixed names in scope.
+pub fn generate_simple_source_tracking(field: &dyn FieldGenerator) -> QuoteStream {
     let name = field.name();
     let name_str = name.to_string();
 
@@ -457,6 +1244,7 @@ fn generate_simple_source_tracking(field: &dyn FieldGenerator) -> QuoteStream {
     field.env_var_name().map_or_else(|| quote! {}, |env_var| {
         let source_ident = format_ident!("__{}_source", name);
         let effective_var_ident = format_ident!("__{}_effective_var", name);
+        let effective_var = FieldFactory::effective_var_expr(env_var, field.default_from());
         let has_default = field.default_value().is_some();
         let has_profile = field.profile_config().is_some();
 
@@ -479,19 +1267,16 @@ fn generate_simple_source_tracking(field: &dyn FieldGenerator) -> QuoteStream {
 
             quote! {
                 // Build effective env var name with external prefix
-                let #effective_var_ident: std::string::String = match __external_prefix {
-                    std::option::Option::Some(prefix) => format!("{}{}", prefix, #env_var),
-                    std::option::Option::None => #env_var.to_string(),
-                };
+                let #effective_var_ident: std::string::String = #effective_var;
 
                 let #source_ident = if #profile_used_ident {
                     ::procenv::ValueSource::new(
                         &#effective_var_ident,
                         ::procenv::Source::Profile(__profile.clone().unwrap_or_default())
                     )
-                } else if std::env::var(&#effective_var_ident).is_ok() {
+                } else if ::procenv::runtime::var(&#effective_var_ident).is_ok() {
                     if __dotenv_loaded && !__pre_dotenv_vars.contains(&#effective_var_ident) {
-                        ::procenv::ValueSource::new(&#effective_var_ident, ::procenv::Source::DotenvFile(None))
+                        ::procenv::ValueSource::new(&#effective_var_ident, ::procenv::Source::DotenvFile(__dotenv_path.clone()))
                     } else {
                         ::procenv::ValueSource::new(&#effective_var_ident, ::procenv::Source::Environment)
                     }
@@ -517,14 +1302,11 @@ fn generate_simple_source_tracking(field: &dyn FieldGenerator) -> QuoteStream {
 
             quote! {
                 // Build effective env var name with external prefix
-                let #effective_var_ident: std::string::String = match __external_prefix {
-                    std::option::Option::Some(prefix) => format!("{}{}", prefix, #env_var),
-                    std::option::Option::None => #env_var.to_string(),
-                };
+                let #effective_var_ident: std::string::String = #effective_var;
 
-                let #source_ident = if std::env::var(&#effective_var_ident).is_ok() {
+                let #source_ident = if ::procenv::runtime::var(&#effective_var_ident).is_ok() {
                     if __dotenv_loaded && !__pre_dotenv_vars.contains(&#effective_var_ident) {
-                        ::procenv::ValueSource::new(&#effective_var_ident, ::procenv::Source::DotenvFile(None))
+                        ::procenv::ValueSource::new(&#effective_var_ident, ::procenv::Source::DotenvFile(__dotenv_path.clone()))
                     } else {
                         ::procenv::ValueSource::new(&#effective_var_ident, ::procenv::Source::Environment)
                     }
@@ -549,24 +1331,34 @@ fn generate_simple_source_tracking(field: &dyn FieldGenerator) -> QuoteStream {
     reason = "proc-macro code generation inherently requires verbose quote! blocks"
 )]
 fn generate_field_loader_with_prefix(field: &dyn FieldGenerator) -> QuoteStream {
-    // Flatten fields delegate to nested type
-    if field.is_flatten() {
+    // Flatten fields delegate to nested type; skipped fields load nothing
+    if field.is_flatten() || field.is_skip() {
         return field.generate_loader_with_external_prefix();
     }
 
+    let name = field.name();
+    let effective_var_ident = format_ident!("__{}_effective_var", name);
+    let read = FieldFactory::read_expr(&quote! { &#effective_var_ident }, field, true);
+    let after_parse = generate_after_parse(field, name, &quote! { &#effective_var_ident }, false);
+    let value_checks =
+        generate_value_checks(field, &quote! { #name }, &quote! { &#effective_var_ident });
+
     // Check if this field has profile config - if so, generate profile-aware code
     let Some(profile_config) = field.profile_config() else {
         // No profile - use the format-aware prefixed loader
-        if let Some(format) = field.format_config() {
-            return generate_format_loader_with_prefix(field, format);
-        }
-        return field.generate_loader_with_external_prefix();
+        let loader = field.format_config().map_or_else(
+            || field.generate_loader_with_external_prefix(),
+            |format| generate_format_loader_with_prefix(field, format),
+        );
+        return quote! {
+            #loader
+            #after_parse
+            #value_checks
+        };
     };
 
     // Field has profile values - generate profile-aware loader with prefix and format support
-    let name = field.name();
     let profile_used_ident = format_ident!("__{}_from_profile", name);
-    let effective_var_ident = format_ident!("__{}_effective_var", name);
 
     // Generate match arms for each profile
     let match_arms: Vec<QuoteStream> = profile_config
@@ -580,25 +1372,15 @@ fn generate_field_loader_with_prefix(field: &dyn FieldGenerator) -> QuoteStream
         .collect();
 
     // Get env var name and type info for parsing
-    let env_var = field.env_var_name().unwrap_or("");
-    let ty = field.type_name();
-    let secret = field.is_secret();
+    let effective_var =
+        FieldFactory::effective_var_expr(field.env_var_name().unwrap_or(""), field.default_from());
+    let secret = FieldFactory::redact_expr(field.is_secret());
     let default_value = field.default_value();
     let used_default_ident = format_ident!("__{}_used_default", name);
 
     // Generate the parse/deserialize expression based on format
-    let (parse_expr, type_desc) = field.format_config().map_or_else(
-        || (quote! { val.parse() }, ty.clone()),
-        |format| {
-            let expr = match format {
-                "json" => quote! { ::serde_json::from_str(&val) },
-                "toml" => quote! { ::toml::from_str(&val) },
-                "yaml" => quote! { ::serde_saphyr::from_str(&val) },
-                _ => quote! { val.parse() },
-            };
-            (expr, format!("{} data", format.to_uppercase()))
-        },
-    );
+    let (parse_expr, type_desc) =
+        generate_value_parse_expr(field, &quote! { &#effective_var_ident });
 
     // Generate fallback code for when no env var and no profile match
     let no_value_handling = default_value.map_or_else(
@@ -608,9 +1390,12 @@ fn generate_field_loader_with_prefix(field: &dyn FieldGenerator) -> QuoteStream
             }
         },
         |default| {
+            let value =
+                FieldFactory::default_option_expr(default, &quote! { &#effective_var_ident });
+
             quote! {
                 #used_default_ident = true;
-                (std::option::Option::Some(#default.to_string()), false)
+                (#value, false)
             }
         },
     );
@@ -628,11 +1413,7 @@ fn generate_field_loader_with_prefix(field: &dyn FieldGenerator) -> QuoteStream
 
     quote! {
         // Build effective env var name with external prefix
-        let #effective_var_ident: std::string::String = format!(
-            "{}{}",
-            __external_prefix.unwrap_or(""),
-            #env_var
-        );
+        let #effective_var_ident: std::string::String = #effective_var;
 
         // Track if we used the compile-time default
         let mut #used_default_ident = false;
@@ -645,7 +1426,7 @@ fn generate_field_loader_with_prefix(field: &dyn FieldGenerator) -> QuoteStream
 
         // Get value to parse: env var > profile > default
         let (__value_to_parse, #profile_used_ident): (std::option::Option<std::string::String>, bool) =
-            match std::env::var(&#effective_var_ident) {
+            match #read {
                 std::result::Result::Ok(val) => {
                     (std::option::Option::Some(val), false)
                 }
@@ -659,8 +1440,8 @@ fn generate_field_loader_with_prefix(field: &dyn FieldGenerator) -> QuoteStream
                         }
                     }
                 }
-                std::result::Result::Err(std::env::VarError::NotUnicode(_)) => {
-                    __errors.push(::procenv::Error::InvalidUtf8 { var: #effective_var_ident.clone() });
+                std::result::Result::Err(std::env::VarError::NotUnicode(__raw)) => {
+                    __errors.push(::procenv::Error::invalid_utf8(&#effective_var_ident, &__raw, #secret));
                     (std::option::Option::None, false)
                 }
             };
@@ -673,7 +1454,7 @@ fn generate_field_loader_with_prefix(field: &dyn FieldGenerator) -> QuoteStream
                     std::result::Result::Err(e) => {
                         __errors.push(::procenv::Error::parse(
                             &#effective_var_ident,
-                            if #secret { "[REDACTED]".to_string() } else { val },
+                            if #secret { ::procenv::runtime::REDACTED.to_string() } else { val },
                             #secret,
                             #type_desc,
                             std::boxed::Box::new(e),
@@ -686,6 +1467,9 @@ fn generate_field_loader_with_prefix(field: &dyn FieldGenerator) -> QuoteStream
                 #missing_value_handling
             }
         };
+
+        #after_parse
+        #value_checks
     }
 }
 
@@ -694,30 +1478,38 @@ fn generate_field_loader_with_prefix(field: &dyn FieldGenerator) -> QuoteStream
 /// Used for fields with `format = "json/toml/yaml"` in flattened structs.
 fn generate_format_loader_with_prefix(field: &dyn FieldGenerator, format: &str) -> QuoteStream {
     let name = field.name();
-    let env_var = field.env_var_name().unwrap_or("");
-    let secret = field.is_secret();
+    let effective_var =
+        FieldFactory::effective_var_expr(field.env_var_name().unwrap_or(""), field.default_from());
+    let secret = FieldFactory::redact_expr(field.is_secret());
     let is_optional = field.is_optional();
     let default_value = field.default_value();
     let effective_var_ident = format_ident!("__{}_effective_var", name);
+    let read = FieldFactory::read_expr(&quote! { &#effective_var_ident }, field, true);
     let profile_used_ident = format_ident!("__{}_from_profile", name);
     let used_default_ident = format_ident!("__{}_used_default", name);
 
     let deserialize_call = match format {
-        "json" => quote! { ::serde_json::from_str(&val) },
+        "json" => FieldFactory::json_expr(&quote! { &#effective_var_ident }, field.merges_env()),
         "toml" => quote! { ::toml::from_str(&val) },
         "yaml" => quote! { ::serde_saphyr::from_str(&val) },
+        "auto" => quote! { ::procenv::runtime::deserialize_auto(&val) },
         _ => unreachable!("Format validated at parse time"),
     };
+    let deserialize_call =
+        FieldFactory::deserialize_with_expr(deserialize_call, field.deserialize_with());
 
-    let format_name = format.to_uppercase();
+    let format_name = FieldFactory::format_label(format);
 
     // Generate deserialize call for default value (uses `val` variable)
     let default_deserialize_call = match format {
-        "json" => quote! { ::serde_json::from_str(&val) },
+        "json" => FieldFactory::json_expr(&quote! { &#effective_var_ident }, field.merges_env()),
         "toml" => quote! { ::toml::from_str(&val) },
         "yaml" => quote! { ::serde_saphyr::from_str(&val) },
+        "auto" => quote! { ::procenv::runtime::deserialize_auto(&val) },
         _ => unreachable!("Format validated at parse time"),
     };
+    let default_deserialize_call =
+        FieldFactory::deserialize_with_expr(default_deserialize_call, field.deserialize_with());
 
     // Generate handling for missing env var based on field configuration
     let missing_handling = default_value.map_or_else(
@@ -734,22 +1526,27 @@ fn generate_format_loader_with_prefix(field: &dyn FieldGenerator, format: &str)
             }
         },
         |default| {
+            let default =
+                FieldFactory::default_option_expr(default, &quote! { &#effective_var_ident });
+
             quote! {
                 {
                     #used_default_ident = true;
-                    let val = #default.to_string();
-                    match #default_deserialize_call {
-                        std::result::Result::Ok(v) => std::option::Option::Some(v),
-                        std::result::Result::Err(e) => {
-                            __errors.push(::procenv::Error::parse(
-                                &#effective_var_ident,
-                                val,
-                                #secret,
-                                concat!(#format_name, " data"),
-                                std::boxed::Box::new(e),
-                            ));
-                            std::option::Option::None
-                        }
+                    match #default {
+                        std::option::Option::Some(val) => match #default_deserialize_call {
+                            std::result::Result::Ok(v) => std::option::Option::Some(v),
+                            std::result::Result::Err(e) => {
+                                __errors.push(::procenv::Error::parse(
+                                    &#effective_var_ident,
+                                    val,
+                                    #secret,
+                                    concat!(#format_name, " data"),
+                                    std::boxed::Box::new(e),
+                                ));
+                                std::option::Option::None
+                            }
+                        },
+                        std::option::Option::None => std::option::Option::None,
                     }
                 }
             }
@@ -758,17 +1555,13 @@ fn generate_format_loader_with_prefix(field: &dyn FieldGenerator, format: &str)
 
     quote! {
         // Build effective env var name with external prefix
-        let #effective_var_ident: std::string::String = format!(
-            "{}{}",
-            __external_prefix.unwrap_or(""),
-            #env_var
-        );
+        let #effective_var_ident: std::string::String = #effective_var;
 
         // No profile for format-only fields in this path
         let #profile_used_ident: bool = false;
         let mut #used_default_ident: bool = false;
 
-        let #name = match std::env::var(&#effective_var_ident) {
+        let #name = match #read {
             std::result::Result::Ok(val) => {
                 match #deserialize_call {
                     std::result::Result::Ok(v) => std::option::Option::Some(v),
@@ -789,10 +1582,8 @@ fn generate_format_loader_with_prefix(field: &dyn FieldGenerator, format: &str)
                     std::env::VarError::NotPresent => {
                         #missing_handling
                     }
-                    std::env::VarError::NotUnicode(_) => {
-                        __errors.push(::procenv::Error::InvalidUtf8 {
-                            var: #effective_var_ident.clone(),
-                        });
+                    std::env::VarError::NotUnicode(__raw) => {
+                        __errors.push(::procenv::Error::invalid_utf8(&#effective_var_ident, &__raw, #secret));
                         std::option::Option::None
                     }
                 }
@@ -15,10 +15,15 @@
 //! |--------|-------------------|
 //! | `from_env()` | [`env::generate_from_env_impl`] |
 //! | `from_env_with_sources()` | [`sources::generate_from_env_with_sources_impl`] |
+//! | `apply_env()` | [`apply::generate_apply_env_impl`] |
+//! | `builder()` | [`builder::generate_builder_impl`] |
+//! | `impl Default` | [`builder::generate_default_impl`] |
 //! | `from_config()` | [`config::generate_from_config_impl`] |
 //! | `from_args()` | [`args::generate_from_args_impl`] |
+//! | `from_env_timed()` | [`timing::generate_timed_impl`] |
 //! | `env_example()` | [`example::generate_env_example_impl`] |
 //! | `impl Debug` | [`debug::generate_debug_impl`] |
+//! | `verify_<field>()`, `<field>_fingerprint()` | [`verify::generate_verify_impl`] |
 //!
 //! # Error Accumulation Pattern
 //!
@@ -33,23 +38,31 @@ use syn::token::Comma;
 use syn::{Data, DeriveInput, Error as SynError, Field, Fields, Result as SynResult};
 
 use crate::field::FieldFactory;
-use crate::parse::EnvConfigAttr;
+use crate::parse::{EnvConfigAttr, Parser};
 
 // Submodules
+pub mod apply;
 pub mod args;
+pub mod builder;
 pub mod config;
 pub mod debug;
 pub mod env;
 pub mod example;
 pub mod runtime;
 pub mod sources;
+pub mod timing;
 pub mod validation;
+pub mod verify;
 
 /// The main orchestrator for macro expansion.
 pub struct Expander;
 
 impl Expander {
     /// Main entry point for expanding the derive macro.
+    #[expect(
+        clippy::too_many_lines,
+        reason = "one generator call per impl block keeps the orchestration in one place"
+    )]
     pub fn expand(input: &DeriveInput) -> SynResult<TokenStream> {
         let struct_name = &input.ident;
         let generics = &input.generics;
@@ -60,26 +73,77 @@ impl Expander {
         // Validate and extract the struct's named fields
         let fields = Self::extract_struct_fields(input)?;
 
+        if env_config_attr.strict_profiles
+            && let Some(profiles) = &env_config_attr.profiles
+        {
+            Self::check_profile_coverage(fields, profiles)?;
+        }
+
         // Parse each field into a FieldGenerator trait object
         let generators: Vec<Box<dyn crate::field::FieldGenerator>> = fields
             .iter()
-            .map(|f| FieldFactory::parse_field(f, env_config_attr.prefix.as_deref()))
+            .map(|f| {
+                FieldFactory::parse_field(
+                    f,
+                    env_config_attr.prefix.as_deref(),
+                    env_config_attr.env_prefix_separator.as_deref(),
+                    env_config_attr.serde_rename_all,
+                )
+            })
             .collect::<SynResult<Vec<_>>>()?;
 
+        // Only `from_config()` reads field files, and it needs struct-level files
+        if env_config_attr.files.is_empty()
+            && env_config_attr.embedded.is_none()
+            && let Some(field) = generators.iter().find(|g| g.source_file().is_some())
+        {
+            return Err(SynError::new_spanned(
+                field.name(),
+                "`file` on a field requires a struct-level `file`, `file_optional` or `embedded`",
+            ));
+        }
+
+        Self::check_when_profile(&generators, &env_config_attr)?;
+
         let from_env_impl =
             env::generate_from_env_impl(struct_name, generics, &generators, &env_config_attr);
 
-        let debug_impl = debug::generate_debug_impl(struct_name, generics, &generators);
+        let debug_impl = debug::generate_debug_impl(
+            struct_name,
+            generics,
+            &generators,
+            env_config_attr.allow_unmask,
+            env_config_attr.unmask_in_debug,
+        );
 
         let env_example_impl =
             example::generate_env_example_impl(struct_name, generics, &generators);
 
         let sources_impl = sources::generate_from_env_with_sources_impl(
             struct_name,
+            generics,
+            &generators,
+            &env_config_attr,
+        );
+
+        let apply_env_impl =
+            apply::generate_apply_env_impl(struct_name, generics, &generators, &env_config_attr);
+
+        let builder_impl = builder::generate_builder_impl(
+            struct_name,
+            &input.vis,
+            generics,
+            fields,
             &generators,
             &env_config_attr,
         );
 
+        let default_impl = if env_config_attr.derive_default {
+            builder::generate_default_impl(struct_name, generics, &generators)?
+        } else {
+            quote! {}
+        };
+
         // Always generate __config_defaults and __from_json_value for nested struct support.
         // Even if this struct doesn't have file config, it might be used as a nested type
         // in another struct that does. These methods are #[doc(hidden)] internal APIs.
@@ -91,12 +155,22 @@ impl Expander {
             config::generate_from_json_value_impl(struct_name, generics, &generators);
 
         // Generate file config method if files are configured
-        let file_config_impl = if env_config_attr.files.is_empty() {
+        let file_config_impl = if env_config_attr.files.is_empty()
+            && env_config_attr.embedded.is_none()
+        {
             quote! {}
         } else {
             config::generate_from_config_impl(struct_name, generics, &generators, &env_config_attr)
         };
 
+        // Generate single-JSON-var loading if from_json_var is set
+        let json_env_impl = config::generate_from_json_env_impl(
+            struct_name,
+            generics,
+            &generators,
+            &env_config_attr,
+        );
+
         // Generate validation methods if validate attribute is set
         let validated_impl = if env_config_attr.validate {
             validation::generate_validated_impl(
@@ -109,6 +183,13 @@ impl Expander {
             quote! {}
         };
 
+        // Generate timing method if timed attribute is set
+        let timed_impl = if env_config_attr.timed {
+            timing::generate_timed_impl(struct_name, generics, &generators, &env_config_attr)
+        } else {
+            quote! {}
+        };
+
         // Generate external prefix method for flatten support
         let external_prefix_impl = env::generate_from_env_with_external_prefix_impl(
             struct_name,
@@ -118,26 +199,135 @@ impl Expander {
         );
 
         // Generate runtime access methods
-        let runtime_access_impl =
-            runtime::generate_runtime_access_impl(struct_name, generics, &generators);
+        let runtime_access_impl = runtime::generate_runtime_access_impl(
+            struct_name,
+            generics,
+            &generators,
+            env_config_attr.allow_unmask,
+            env_config_attr.unmask_in_debug,
+            env_config_attr.env_prefix_separator.as_deref(),
+        );
+
+        let verify_impl = verify::generate_verify_impl(struct_name, generics, &generators);
 
         let combined = quote! {
             #from_env_impl
             #debug_impl
             #env_example_impl
             #sources_impl
+            #apply_env_impl
+            #builder_impl
+            #default_impl
             #config_defaults_impl
             #from_json_value_impl
             #file_config_impl
+            #json_env_impl
             #validated_impl
+            #timed_impl
             #external_prefix_impl
             #runtime_access_impl
+            #verify_impl
         };
 
         Ok(combined.into())
     }
 
+    /// Check that `when_profile` fields name profiles the struct can select.
+    fn check_when_profile(
+        generators: &[Box<dyn crate::field::FieldGenerator>],
+        env_config_attr: &EnvConfigAttr,
+    ) -> SynResult<()> {
+        for field in generators {
+            let Some(required_in) = field.when_profile() else {
+                continue;
+            };
+
+            if env_config_attr.profile_env.is_none() {
+                return Err(SynError::new_spanned(
+                    field.name(),
+                    "`when_profile` requires `#[env_config(profile_env = \"...\")]`",
+                ));
+            }
+
+            if let Some(profiles) = &env_config_attr.profiles
+                && let Some(unknown) = required_in.iter().find(|p| !profiles.contains(p))
+            {
+                return Err(SynError::new_spanned(
+                    field.name(),
+                    format!(
+                        "unknown profile `{unknown}` in `when_profile`; declared profiles are: {}",
+                        profiles.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that every `#[profile(...)]` covers exactly the declared
+    /// `profiles`, as required by `strict_profiles`.
+    fn check_profile_coverage(
+        fields: &Punctuated<Field, Comma>,
+        profiles: &[String],
+    ) -> SynResult<()> {
+        for field in fields {
+            let Some(profile) = Parser::parse_profile_attr(field)? else {
+                continue;
+            };
+
+            // parse_profile_attr found it, so the attribute exists
+            let attr = field
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("profile"))
+                .expect("profile attribute was just parsed");
+
+            let mut unknown: Vec<&str> = profile
+                .values
+                .keys()
+                .map(String::as_str)
+                .filter(|name| !profiles.iter().any(|p| p == name))
+                .collect();
+            unknown.sort_unstable();
+
+            if !unknown.is_empty() {
+                return Err(SynError::new_spanned(
+                    attr,
+                    format!(
+                        "unknown profile(s) `{}` in #[profile(...)]; declared profiles are: {}",
+                        unknown.join("`, `"),
+                        profiles.join(", ")
+                    ),
+                ));
+            }
+
+            let missing: Vec<&str> = profiles
+                .iter()
+                .map(String::as_str)
+                .filter(|name| !profile.values.contains_key(*name))
+                .collect();
+
+            if !missing.is_empty() {
+                return Err(SynError::new_spanned(
+                    attr,
+                    format!(
+                        "#[profile(...)] has no value for profile(s) `{}` (required by \
+                         `strict_profiles`)",
+                        missing.join("`, `")
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Extract named fields from the struct, rejecting invalid types.
+    ///
+    /// The compiler evaluates `#[cfg(...)]` on fields before invoking the
+    /// derive, so fields excluded by a disabled feature never reach any
+    /// generator and need no handling here.
     fn extract_struct_fields(input: &DeriveInput) -> SynResult<&Punctuated<Field, Comma>> {
         match &input.data {
             Data::Struct(data_struct) => match &data_struct.fields {
@@ -1,56 +1,118 @@
 //! Runtime access code generation.
 //!
 //! This module generates methods for runtime key-based access to configuration:
-//! - `keys()` - Returns all field names as static strings
+//! - `keys()` - Returns all field names as a sorted static slice
 //! - `get_str(&self, key)` - Gets field value as string by key
 //! - `has_key(key)` - Checks if a key exists
+//!
+//! Fields marked `pointer_access` also answer `get_str("field/a/b")` by
+//! re-serializing the field to JSON and following the pointer `/a/b`. The
+//! parsed JSON isn't kept, since a derive can't add storage to the struct,
+//! so the field's type must implement `Serialize` and each lookup serializes
+//! it again.
+//!
+//! `keys()` and `has_key()` are `const fn`: the keys are sorted at expansion
+//! time and `has_key()` binary searches them via `::procenv::runtime`.
+//!
+//! `reload_immutable_changed(&self, other)` names the first `reload_immutable`
+//! field (dotted for nested fields) whose value differs between two loads; the
+//! watcher uses it to reject reloads that need a restart. The hidden
+//! `__reload_immutable_fields()` lists those fields for `ConfigSources`, so
+//! the watcher can refuse to start without the check.
+//!
+//! `digest(&self)` hashes every field value, secrets included, and
+//! `changed_fields(&self, other)` names the fields (dotted for nested fields)
+//! whose values differ. Both compare the fields' string forms, so neither
+//! needs `PartialEq` on the struct; the watcher uses `changed_fields` to fill
+//! in `ConfigChange::changed_fields`.
+//!
+//! It also generates the hidden helpers that hold the secrecy policy. The
+//! exporters of field values, `get_str()` and `config_merged_value()`, both
+//! consult `__is_secret` and replace secrets with `::procenv::runtime::REDACTED`;
+//! any new exporter must do the same:
+//! - `__is_secret(field)` - Whether a (possibly dotted) field is secret
+//! - `__ALLOW_UNMASK` - Whether `PROCENV_UNMASK` may unmask `Debug` and errors
+//! - `__field_hash(&self, hasher)` - Feeds every field name and value to `hasher`
 
 use std::string::ToString;
 
 use proc_macro2::TokenStream as QuoteStream;
-use quote::quote;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{Generics, Ident};
 
-use crate::field::FieldGenerator;
+use crate::field::{FieldFactory, FieldGenerator};
 
-/// Generates runtime access methods: `keys()`, `get_str()`, `has_key()`.
+/// Generates runtime access methods: `keys()`, `get_str()`, `has_key()`,
+/// `reload_immutable_changed()`, `digest()` and `changed_fields()`, plus the
+/// `__is_secret()` redaction helper, `__field_hash()`,
+/// `__reload_immutable_fields()`, the `__ALLOW_UNMASK`/`__UNMASKED` flags and
+/// the `__PREFIX_SEPARATOR` constant.
+#[expect(
+    clippy::too_many_lines,
+    reason = "proc-macro code generation inherently requires verbose quote! blocks"
+)]
 pub fn generate_runtime_access_impl(
     struct_name: &Ident,
     generics: &Generics,
     generators: &[Box<dyn FieldGenerator>],
+    allow_unmask: bool,
+    unmask_in_debug: bool,
+    prefix_separator: Option<&str>,
 ) -> QuoteStream {
     let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
-    // Collect non-flatten field names (including format fields for completeness)
-    let key_names: Vec<String> = generators
+    // Evaluated in the user's crate, so it follows their build profile
+    let unmasked = if unmask_in_debug {
+        quote! { cfg!(debug_assertions) }
+    } else {
+        quote! { false }
+    };
+
+    let prefix_separator = prefix_separator.map_or_else(
+        || quote! { std::option::Option::None },
+        |sep| quote! { std::option::Option::Some(#sep) },
+    );
+
+    // Collect non-flatten field names (including format fields for completeness),
+    // sorted so has_key() can binary search them
+    let mut key_names: Vec<String> = generators
         .iter()
         .filter(|g| !g.is_flatten())
         .filter_map(|g| g.field_name().map(ToString::to_string))
         .collect();
+    key_names.sort_unstable();
 
-    let key_literals: Vec<_> = key_names.iter().map(|k| quote! { #k }).collect();
-    let num_keys = key_names.len();
+    // Secret field names for __is_secret, plus their config file keys so
+    // merged file values are redacted under serde renames too
+    let secret_names: Vec<String> = generators
+        .iter()
+        .filter(|g| !g.is_flatten() && g.is_secret())
+        .flat_map(|g| {
+            std::iter::once(g.name().to_string()).chain(g.serde_rename().map(String::from))
+        })
+        .collect();
 
     // Match arms for get_str
     // Regular fields use Display, format fields use Debug (may be complex structures)
+    // Secret fields never get an arm: get_str masks them via __is_secret first
     let get_str_arms: Vec<_> = generators
         .iter()
-        .filter(|g| !g.is_flatten())
+        .filter(|g| !g.is_flatten() && !g.is_secret())
         .filter_map(|g| {
             let name = g.field_name()?;
             let name_str = name.to_string();
+            let value = FieldFactory::optional_ref(g.as_ref(), &quote! { self.#name });
 
-            if g.is_secret() {
-                Some(quote! { #name_str => std::option::Option::Some("<redacted>".to_string()), })
-            } else if g.format_config().is_some() {
-                // Format fields (json/toml/yaml) use Debug since they may not implement Display
+            if g.format_config().is_some() || g.is_base64() || g.sep_env().is_some() {
+                // Format fields (json/toml/yaml), base64 bytes and lists use Debug since they may not implement Display
                 if g.is_optional() {
-                    Some(quote! { #name_str => self.#name.as_ref().map(|v| format!("{:?}", v)), })
+                    Some(quote! { #name_str => #value.map(|v| format!("{:?}", v)), })
                 } else {
                     Some(quote! { #name_str => std::option::Option::Some(format!("{:?}", self.#name)), })
                 }
             } else if g.is_optional() {
-                Some(quote! { #name_str => self.#name.as_ref().map(|v| v.to_string()), })
+                Some(quote! { #name_str => #value.map(|v| v.to_string()), })
             } else {
                 Some(quote! { #name_str => std::option::Option::Some(self.#name.to_string()), })
             }
@@ -68,12 +130,89 @@ pub fn generate_runtime_access_impl(
 
             quote! {
                 key if key.starts_with(#prefix) => {
-                    self.#name.get_str(&key[#prefix.len()..])
+                    key.strip_prefix(#prefix).and_then(|rest| self.#name.get_str(rest))
                 }
             }
         })
         .collect();
 
+    // JSON pointer lookups into pointer_access fields; secret ones are masked
+    // by __is_secret before get_str reaches these arms. A missing `Serialize`
+    // impl is reported at the field's type.
+    let pointer_get_str_arms: Vec<_> = generators
+        .iter()
+        .filter(|g| !g.is_flatten() && !g.is_secret() && g.has_pointer_access())
+        .filter_map(|g| {
+            let name = g.field_name()?;
+            let span = g.field_type()?.span();
+            let name_str = name.to_string();
+            let prefix = format!("{name_str}/");
+            let value = if g.is_optional() {
+                let value = FieldFactory::optional_ref(g.as_ref(), &quote! { self.#name });
+                quote! { #value? }
+            } else {
+                quote! { &self.#name }
+            };
+
+            let to_value = quote_spanned! {span=> ::serde_json::to_value(#value) };
+
+            Some(quote! {
+                key if key.starts_with(#prefix) => {
+                    let __json = #to_value.ok()?;
+                    __json.pointer(&key[#name_str.len()..]).map(|leaf| match leaf {
+                        ::serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                }
+            })
+        })
+        .collect();
+
+    // Pointer keys into secret fields are as secret as the field itself
+    let pointer_is_secret_checks: Vec<_> = generators
+        .iter()
+        .filter(|g| !g.is_flatten() && g.is_secret() && g.has_pointer_access())
+        .map(|g| {
+            let prefix = format!("{}/", g.name());
+
+            quote! {
+                if field.starts_with(#prefix) {
+                    return true;
+                }
+            }
+        })
+        .collect();
+
+    // Flatten field delegation for __is_secret
+    let flatten_is_secret_arms: Vec<_> = generators
+        .iter()
+        .filter(|g| g.is_flatten())
+        .filter_map(|g| {
+            let ty = g.field_type()?;
+            let prefixes = std::iter::once(g.name().to_string())
+                .chain(g.serde_rename().map(String::from))
+                .map(|key| format!("{key}."));
+
+            Some(quote! {
+                #(
+                    if let std::option::Option::Some(rest) = field.strip_prefix(#prefixes) {
+                        return <#ty>::__is_secret(rest);
+                    }
+                )*
+            })
+        })
+        .collect();
+
+    let secret_check = if secret_names.is_empty() {
+        quote! { let _ = field; }
+    } else {
+        quote! {
+            if matches!(field, #(#secret_names)|*) {
+                return true;
+            }
+        }
+    };
+
     // Flatten field delegation for has_key
     let flatten_has_key_arms: Vec<_> = generators
         .iter()
@@ -84,35 +223,178 @@ pub fn generate_runtime_access_impl(
             let prefix = format!("{name_str}.");
 
             Some(quote! {
-                if key.starts_with(#prefix) {
-                    return <#ty>::has_key(&key[#prefix.len()..]);
+                if let std::option::Option::Some(rest) = ::procenv::runtime::strip_prefix(key, #prefix) {
+                    return <#ty>::has_key(rest);
+                }
+            })
+        })
+        .collect();
+
+    // Immutable fields are compared directly; nested structs answer for their own.
+    // A missing `PartialEq` impl is reported at the field's type.
+    let immutable_checks: Vec<_> = generators
+        .iter()
+        .filter(|g| !g.is_flatten() && g.is_reload_immutable())
+        .filter_map(|g| {
+            let name = g.field_name()?;
+            let span = g.field_type()?.span();
+            let name_str = name.to_string();
+            let changed = quote_spanned! {span=> self.#name != other.#name };
+
+            Some(quote! {
+                if #changed {
+                    return std::option::Option::Some(#name_str.to_string());
                 }
             })
         })
         .collect();
 
+    let flatten_immutable_checks: Vec<_> = generators
+        .iter()
+        .filter(|g| g.is_flatten())
+        .map(|g| {
+            let name = g.name();
+            let prefix = format!("{name}.");
+
+            quote! {
+                if let std::option::Option::Some(field) =
+                    self.#name.reload_immutable_changed(&other.#name)
+                {
+                    return std::option::Option::Some(format!("{}{}", #prefix, field));
+                }
+            }
+        })
+        .collect();
+
+    // Names of the immutable fields, recorded in `ConfigSources` for the watcher
+    let immutable_names: Vec<String> = generators
+        .iter()
+        .filter(|g| !g.is_flatten() && g.is_reload_immutable())
+        .filter_map(|g| g.field_name().map(ToString::to_string))
+        .collect();
+
+    let flatten_immutable_names: Vec<_> = generators
+        .iter()
+        .filter(|g| g.is_flatten())
+        .filter_map(|g| {
+            let ty = g.field_type()?;
+            let prefix = format!("{}.", g.name());
+
+            Some(quote! {
+                .chain(<#ty>::__reload_immutable_fields().into_iter().map(|f| format!("{}{}", #prefix, f)))
+            })
+        })
+        .collect();
+
+    // Field string forms for hashing and diffing, secrets exposed
+    let hashed_fields: Vec<(String, QuoteStream, QuoteStream)> = generators
+        .iter()
+        .filter(|g| !g.is_flatten())
+        .filter_map(|g| {
+            let name = g.field_name()?;
+            Some((
+                name.to_string(),
+                hash_value_expr(g.as_ref(), &quote! { self.#name }),
+                hash_value_expr(g.as_ref(), &quote! { other.#name }),
+            ))
+        })
+        .collect();
+
+    let field_hashes: Vec<_> = hashed_fields
+        .iter()
+        .map(|(name_str, value, _)| {
+            quote! {
+                std::hash::Hash::hash(#name_str, hasher);
+                std::hash::Hash::hash(&#value, hasher);
+            }
+        })
+        .collect();
+
+    let field_diffs: Vec<_> = hashed_fields
+        .iter()
+        .map(|(name_str, value, other_value)| {
+            quote! {
+                if #value != #other_value {
+                    __changed.push(#name_str.to_string());
+                }
+            }
+        })
+        .collect();
+
+    let flatten_hashes: Vec<_> = generators
+        .iter()
+        .filter(|g| g.is_flatten())
+        .map(|g| {
+            let name = g.name();
+            let name_str = name.to_string();
+
+            quote! {
+                std::hash::Hash::hash(#name_str, hasher);
+                self.#name.__field_hash(hasher);
+            }
+        })
+        .collect();
+
+    let flatten_diffs: Vec<_> = generators
+        .iter()
+        .filter(|g| g.is_flatten())
+        .map(|g| {
+            let name = g.name();
+            let prefix = format!("{name}.");
+
+            quote! {
+                for field in self.#name.changed_fields(&other.#name) {
+                    __changed.push(format!("{}{}", #prefix, field));
+                }
+            }
+        })
+        .collect();
+
+    let hash_unused = if field_hashes.is_empty() && flatten_hashes.is_empty() {
+        quote! { let _ = hasher; }
+    } else {
+        quote! {}
+    };
+
+    let diff_unused = if field_diffs.is_empty() && flatten_diffs.is_empty() {
+        quote! { let _ = other; }
+    } else {
+        quote! {}
+    };
+
+    let immutable_unused = if immutable_checks.is_empty() && flatten_immutable_checks.is_empty() {
+        quote! { let _ = other; }
+    } else {
+        quote! {}
+    };
+
     quote! {
         impl #impl_generics #struct_name #type_generics #where_clause {
-            /// Returns all configuration keys.
-            pub fn keys() -> &'static [&'static str] {
-                static KEYS: [&str; #num_keys] = [#(#key_literals),*];
-                &KEYS
+            /// Returns all configuration keys, sorted.
+            pub const fn keys() -> &'static [&'static str] {
+                const KEYS: &[&str] = &[#(#key_names),*];
+                KEYS
             }
 
             /// Gets field value as string by key.
             /// Secret fields return "<redacted>".
             pub fn get_str(&self, key: &str) -> Option<String> {
+                if Self::__is_secret(key) {
+                    return std::option::Option::Some(::procenv::runtime::REDACTED.to_string());
+                }
+
                 match key {
                     #(#get_str_arms)*
+                    #(#pointer_get_str_arms)*
                     #(#flatten_get_str_arms)*
                     _ => None,
                 }
             }
 
             /// Checks if a key exists.
-            pub fn has_key(key: &str) -> bool {
+            pub const fn has_key(key: &str) -> bool {
                 // Check direct keys first
-                if Self::keys().contains(&key) {
+                if ::procenv::runtime::sorted_contains(Self::keys(), key) {
                     return true;
                 }
 
@@ -121,6 +403,116 @@ pub fn generate_runtime_access_impl(
 
                 false
             }
+
+            /// Returns the first `reload_immutable` field whose value differs in `other`.
+            ///
+            /// Nested fields are reported dotted (`database.port`).
+            pub fn reload_immutable_changed(&self, other: &Self) -> Option<String> {
+                #immutable_unused
+                #(#immutable_checks)*
+                #(#flatten_immutable_checks)*
+
+                None
+            }
+
+            /// Names of the `reload_immutable` fields, dotted for nested fields.
+            #[doc(hidden)]
+            pub fn __reload_immutable_fields() -> std::vec::Vec<std::string::String> {
+                let __own: &[&str] = &[#(#immutable_names),*];
+                __own
+                    .iter()
+                    .map(|f| (*f).to_string())
+                    #(#flatten_immutable_names)*
+                    .collect()
+            }
+
+            /// Returns a hash of every field value, secrets included.
+            ///
+            /// Equal configurations always have equal digests, so a changed
+            /// digest means a changed value. The hash is only stable within
+            /// one build of the program; don't persist it.
+            pub fn digest(&self) -> u64 {
+                let mut __hasher = std::collections::hash_map::DefaultHasher::new();
+                self.__field_hash(&mut __hasher);
+                std::hash::Hasher::finish(&__hasher)
+            }
+
+            /// Returns the fields whose values differ in `other`.
+            ///
+            /// Nested fields are reported dotted (`database.port`). Values are
+            /// compared by their string form, so no field needs `PartialEq`.
+            pub fn changed_fields(&self, other: &Self) -> std::vec::Vec<std::string::String> {
+                #diff_unused
+                let mut __changed = std::vec::Vec::new();
+                #(#field_diffs)*
+                #(#flatten_diffs)*
+                __changed
+            }
+
+            /// Feed every field name and value to `hasher`, secrets exposed
+            /// (internal, generated by macro; backs `digest()`).
+            #[doc(hidden)]
+            pub fn __field_hash<__H: std::hash::Hasher>(&self, hasher: &mut __H) {
+                #hash_unused
+                #(#field_hashes)*
+                #(#flatten_hashes)*
+            }
+
+            /// Whether `PROCENV_UNMASK=1` unmasks secret fields in `Debug` and errors
+            /// (internal, generated by macro from `#[env_config(allow_unmask)]`).
+            #[doc(hidden)]
+            pub const __ALLOW_UNMASK: bool = #allow_unmask;
+
+            /// Whether secret fields are shown in `Debug` and errors in this build
+            /// (internal, generated by macro from `#[env_config(unmask_in_debug)]`).
+            #[doc(hidden)]
+            pub const __UNMASKED: bool = #unmasked;
+
+            /// Separator joining prefixes and var names (internal, generated
+            /// by macro from `#[env_config(env_prefix_separator = "...")]`).
+            #[doc(hidden)]
+            pub const __PREFIX_SEPARATOR: std::option::Option<&'static str> = #prefix_separator;
+
+            /// Whether a field (dotted for nested fields) is secret (internal, generated by macro).
+            ///
+            /// `get_str()` and `config_merged_value()` consult this before
+            /// emitting a value; any new exporter must too.
+            #[doc(hidden)]
+            pub fn __is_secret(field: &str) -> bool {
+                #secret_check
+                #(#pointer_is_secret_checks)*
+
+                #(#flatten_is_secret_arms)*
+
+                false
+            }
         }
     }
 }
+
+/// Generate the string form of a field `value` used by `digest()` and
+/// `changed_fields()`.
+///
+/// Mirrors `get_str()`, but secret fields are included: `SecretString` and
+/// `SecretBox` values are read through `ExposeSecret`, which is only
+/// available with the `secrecy` feature that those types need anyway.
+fn hash_value_expr(field: &dyn FieldGenerator, value: &QuoteStream) -> QuoteStream {
+    let body = if field.is_secrecy_type() {
+        if field.field_type().is_some() {
+            quote! { ::procenv::ExposeSecret::expose_secret(v).to_string() }
+        } else {
+            quote! { ::procenv::ExposeSecret::expose_secret(v).to_owned() }
+        }
+    } else if field.format_config().is_some() || field.is_base64() || field.sep_env().is_some() {
+        quote! { format!("{:?}", v) }
+    } else {
+        quote! { v.to_string() }
+    };
+
+    if field.is_optional() {
+        let value = FieldFactory::optional_ref(field, value);
+        quote! { #value.map(|v| #body) }
+    } else {
+        quote! { { let v = &#value; #body } }
+    }
+}
@@ -6,7 +6,8 @@
 //!
 //! # Generated Methods
 //!
-//! - [`generate_from_env_with_sources_impl`] - Main implementation
+//! - [`generate_from_env_with_sources_impl`] - Main implementation, which also
+//!   generates `sources()` and `from_env_verbose()` on top of it
 //!
 //! # Source Tracking
 //!
@@ -16,7 +17,7 @@
 //! let __field_source = if from_profile {
 //!     Source::Profile(profile_name)
 //! } else if __dotenv_loaded && !__pre_dotenv_vars.contains(var) {
-//!     Source::DotenvFile(None)
+//!     Source::DotenvFile(__dotenv_path.clone())
 //! } else if used_default {
 //!     Source::Default
 //! } else {
@@ -39,22 +40,32 @@
 
 use proc_macro2::TokenStream as QuoteStream;
 use quote::quote;
-use syn::Ident;
+use syn::{Generics, Ident};
 
 use crate::field::FieldGenerator;
 use crate::parse::EnvConfigAttr;
 
 use super::args::generate_from_args_impl;
-use super::env::{generate_dotenv_load, generate_field_loader, generate_profile_setup};
+use super::env::{
+    generate_case_insensitive_scope, generate_dotenv_flags, generate_dotenv_load,
+    generate_empty_struct_fallback, generate_error_return, generate_error_sink,
+    generate_field_loader, generate_prefix_env_dispatch, generate_profile_setup,
+};
 
 /// Generate the `from_env_with_sources()` implementation.
 pub fn generate_from_env_with_sources_impl(
     struct_name: &Ident,
+    generics: &Generics,
     generators: &[Box<dyn FieldGenerator>],
     env_config: &EnvConfigAttr,
 ) -> QuoteStream {
-    // Collect all env var names for pre-dotenv check
-    let env_var_names: Vec<_> = generators.iter().filter_map(|g| g.env_var_name()).collect();
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    // Collect all env var names (including `default_from` fallbacks) for pre-dotenv check
+    let env_var_names: Vec<_> = generators
+        .iter()
+        .flat_map(|g| g.env_var_name().into_iter().chain(g.default_from()))
+        .collect();
 
     // Generate pre-dotenv var collection
     let pre_dotenv_collection = quote! {
@@ -62,7 +73,7 @@ pub fn generate_from_env_with_sources_impl(
             #(#env_var_names),*
         ]
         .iter()
-        .filter(|var| std::env::var(var).is_ok())
+        .filter(|var| ::procenv::runtime::var(var).is_ok())
         .copied()
         .collect();
     };
@@ -71,15 +82,16 @@ pub fn generate_from_env_with_sources_impl(
     let dotenv_load = generate_dotenv_load(env_config.dotenv.as_ref());
 
     // Track if dotenv was loaded
-    let dotenv_loaded_flag = if env_config.dotenv.is_some() {
-        quote! { let __dotenv_loaded = true; }
-    } else {
-        quote! { let __dotenv_loaded = false; }
-    };
+    let dotenv_loaded_flag = generate_dotenv_flags(env_config.dotenv.as_ref());
 
     // Generate profile setup code
     let profile_setup = generate_profile_setup(env_config);
 
+    let case_insensitive_scope = generate_case_insensitive_scope(env_config);
+
+    // Sources already come back with the runtime-prefixed var names
+    let prefix_env_dispatch = generate_prefix_env_dispatch(env_config, false, &quote! {});
+
     // Generate loaders
     let loaders: Vec<QuoteStream> = generators
         .iter()
@@ -96,12 +108,45 @@ pub fn generate_from_env_with_sources_impl(
     let assignments: Vec<QuoteStream> =
         generators.iter().map(|g| g.generate_assignment()).collect();
 
+    // Errors always accumulate here, `no_accumulate` only applies to `from_env()`
+    let error_sink = generate_error_sink(false);
+    let error_return = generate_error_return(false);
+
+    let steps = generate_empty_struct_fallback(
+        generators,
+        env_config,
+        true,
+        &[],
+        quote! {
+            #error_sink
+            let mut __sources = ::procenv::ConfigSources::new();
+            __sources.set_reload_immutable(Self::__reload_immutable_fields());
+
+            // Read and validate profile (if configured)
+            #profile_setup
+
+            #(#loaders)*
+
+            #(#source_tracking)*
+            __sources.set_profile(__profile.clone());
+
+            #error_return
+
+            std::result::Result::Ok((
+                Self {
+                    #(#assignments),*
+                },
+                __sources
+            ))
+        },
+    );
+
     // Check if any fields have CLI config
     let has_cli_fields = generators.iter().any(|g| g.cli_config().is_some());
 
     // Generate from_args() if any CLI fields exist
     let from_args_impl = if has_cli_fields {
-        generate_from_args_impl(struct_name, generators, env_config)
+        generate_from_args_impl(struct_name, generics, generators, env_config)
     } else {
         quote! {}
     };
@@ -109,11 +154,14 @@ pub fn generate_from_env_with_sources_impl(
     quote! {
         #from_args_impl
 
-        impl #struct_name {
+        impl #impl_generics #struct_name #type_generics #where_clause {
             /// Load configuration from environment with source attribution.
             ///
             /// Returns both the config and information about where each value came from.
             pub fn from_env_with_sources() -> std::result::Result<(Self, ::procenv::ConfigSources), ::procenv::Error> {
+                #case_insensitive_scope
+                #prefix_env_dispatch
+
                 #pre_dotenv_collection
 
                 #dotenv_load
@@ -123,30 +171,7 @@ pub fn generate_from_env_with_sources_impl(
                 // Define external prefix as None for regular from_env calls
                 let __external_prefix: std::option::Option<&str> = std::option::Option::None;
 
-                let mut __errors: std::vec::Vec<::procenv::Error> = std::vec::Vec::new();
-                let mut __sources = ::procenv::ConfigSources::new();
-
-                // Read and validate profile (if configured)
-                #profile_setup
-
-                #(#loaders)*
-
-                #(#source_tracking)*
-
-                if !__errors.is_empty() {
-                    return std::result::Result::Err(if __errors.len() == 1 {
-                        __errors.pop().unwrap()
-                    } else {
-                        ::procenv::Error::Multiple { errors: __errors }
-                    });
-                }
-
-                std::result::Result::Ok((
-                    Self {
-                        #(#assignments),*
-                    },
-                    __sources
-                ))
+                #steps
             }
 
             /// Load configuration and return only the source attribution.
@@ -154,6 +179,16 @@ pub fn generate_from_env_with_sources_impl(
                 let (_, sources) = Self::from_env_with_sources()?;
                 std::result::Result::Ok(sources)
             }
+
+            /// Load configuration, printing each field's source to stderr.
+            ///
+            /// Writes one `field <- source (VAR)` line per field. Values are
+            /// never printed, so secrets stay hidden.
+            pub fn from_env_verbose() -> std::result::Result<Self, ::procenv::Error> {
+                let (config, sources) = Self::from_env_with_sources()?;
+                let _ = ::procenv::runtime::write_source_lines(&mut std::io::stderr().lock(), &sources);
+                std::result::Result::Ok(config)
+            }
         }
     }
 }
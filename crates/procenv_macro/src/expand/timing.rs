@@ -0,0 +1,77 @@
+//! Load timing code generation.
+//!
+//! This module generates `from_env_timed()` for structs that opt in with
+//! `#[env_config(timed)]`. The generated code requires the `timing` feature
+//! of `procenv`, which provides `LoadTimings`.
+//!
+//! # Generated Code Pattern
+//!
+//! The loading itself is `from_env()`'s body from
+//! [`generate_from_env_body`], generated into a hidden `__from_env_timed()`
+//! with each field loader wrapped with an `Instant`:
+//!
+//! ```rust,ignore
+//! let __field_start = std::time::Instant::now();
+//! let port: Option<u16> = /* field loader */;
+//! __timings.record("port", __field_start.elapsed());
+//! ```
+//!
+//! Loaders stay in the function scope (not a block) because they declare
+//! the field's local variable used when constructing the struct. Sharing the
+//! body keeps every early return of `from_env()` (`prefix_env` dispatch, a
+//! missing `dotenv_required` file, `no_accumulate`, `allow_empty_struct`) in
+//! `from_env_timed()`, whose wrapper records the total either way.
+
+use proc_macro2::TokenStream as QuoteStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+use crate::field::FieldGenerator;
+use crate::parse::EnvConfigAttr;
+
+use super::env::generate_from_env_body;
+
+/// Generate the `from_env_timed` method for structs with `timed`.
+pub fn generate_timed_impl(
+    struct_name: &Ident,
+    generics: &Generics,
+    fields: &[Box<dyn FieldGenerator>],
+    env_config_attr: &EnvConfigAttr,
+) -> QuoteStream {
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let body = generate_from_env_body(fields, env_config_attr, true);
+
+    quote! {
+        impl #impl_generics #struct_name #type_generics #where_clause {
+            /// Load configuration from environment variables, timing each field.
+            ///
+            /// Behaves like `from_env()` and also returns how long each field
+            /// took to resolve, plus the total load time. Timings are returned
+            /// even when loading fails. When `prefix_env` hands loading to the
+            /// runtime prefix, only the total is recorded.
+            pub fn from_env_timed() -> (
+                std::result::Result<Self, ::procenv::Error>,
+                ::procenv::LoadTimings,
+            ) {
+                let __load_start = std::time::Instant::now();
+                let mut __timings = ::procenv::LoadTimings::new();
+
+                let __result = Self::__from_env_timed(&mut __timings);
+
+                __timings.set_total(__load_start.elapsed());
+
+                (__result, __timings)
+            }
+
+            /// `from_env()` recording each field's load time into `__timings`
+            /// (internal, generated by macro; backs `from_env_timed()`).
+            #[doc(hidden)]
+            pub fn __from_env_timed(
+                __timings: &mut ::procenv::LoadTimings,
+            ) -> std::result::Result<Self, ::procenv::Error> {
+                #body
+            }
+        }
+    }
+}
@@ -48,6 +48,10 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! Validators may also return `procenv::Error` built with
+//! `Error::validation(field, message)`; both are converted through
+//! `::procenv::CustomValidationError`.
 
 use proc_macro2::TokenStream as QuoteStream;
 use quote::{format_ident, quote};
@@ -76,14 +80,9 @@ pub fn generate_validated_impl(
 
             Some(quote! {
                 if let Err(e) = #fn_ident(&__config.#field_name) {
-                    __validation_errors.push(::procenv::ValidationFieldError::new(
-                        #field_name_str,
-                        "custom",
-                        e.message
-                            .as_ref()
-                            .map(|m| m.to_string())
-                            .unwrap_or_else(|| e.code.to_string()),
-                    ));
+                    __validation_errors.extend(
+                        ::procenv::CustomValidationError::into_field_errors(e, #field_name_str),
+                    );
                 }
             })
         })
@@ -158,15 +157,9 @@ pub fn generate_from_config_validated_impl(
 
             Some(quote! {
                 if let Err(error) = #fn_ident(&__config.#field_name) {
-                    __validation_errors.push(::procenv::ValidationFieldError::new(
-                        #field_name_str,
-                        "custom",
-                        error
-                            .message
-                            .as_ref()
-                            .map(|m| m.to_string())
-                            .unwrap_or_else(|| error.code.to_string()),
-                    ));
+                    __validation_errors.extend(
+                        ::procenv::CustomValidationError::into_field_errors(error, #field_name_str),
+                    );
                 }
             })
         })
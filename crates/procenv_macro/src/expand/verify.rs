@@ -0,0 +1,107 @@
+//! Constant-time verification code generation.
+//!
+//! For each field marked `#[env(sensitive_eq)]` this generates
+//! `verify_<field>(&self, candidate: &str) -> bool`, which compares
+//! `candidate` with the loaded value through
+//! `::procenv::runtime::SensitiveEq`. Callers can check a token against the
+//! config without reading the secret out or comparing it with `==`, whose
+//! early exit leaks how much of a guess matched.
+//!
+//! For each field marked `#[env(log_fingerprint)]` it also generates
+//! `<field>_fingerprint(&self)`, a truncated SHA-256 from
+//! `::procenv::runtime::fingerprint` that tells two deployed secrets apart
+//! in logs without revealing either.
+
+use proc_macro2::TokenStream as QuoteStream;
+use quote::{format_ident, quote};
+use syn::{Generics, Ident};
+
+use crate::field::FieldGenerator;
+
+/// Generates a `verify_<field>()` method per `sensitive_eq` field and a
+/// `<field>_fingerprint()` method per `log_fingerprint` field.
+pub fn generate_verify_impl(
+    struct_name: &Ident,
+    generics: &Generics,
+    generators: &[Box<dyn FieldGenerator>],
+) -> QuoteStream {
+    let methods: Vec<QuoteStream> = generators
+        .iter()
+        .filter(|g| g.is_sensitive_eq())
+        .map(|g| {
+            let name = g.name();
+            let method = format_ident!("verify_{}", name);
+            let doc = format!(
+                "Whether `candidate` equals `{name}`, compared in constant time.\n\n\
+                 The comparison takes the same time wherever the first mismatch \
+                 is, so it does not leak how much of `candidate` was correct. \
+                 An unset optional value matches nothing."
+            );
+
+            quote! {
+                #[doc = #doc]
+                #[must_use]
+                pub fn #method(&self, candidate: &str) -> bool {
+                    ::procenv::runtime::SensitiveEq::sensitive_eq(&self.#name, candidate)
+                }
+            }
+        })
+        .chain(
+            generators
+                .iter()
+                .filter(|g| g.is_log_fingerprint())
+                .map(|g| generate_fingerprint_method(g.as_ref())),
+        )
+        .collect();
+
+    if methods.is_empty() {
+        return quote! {};
+    }
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #struct_name #type_generics #where_clause {
+            #(#methods)*
+        }
+    }
+}
+
+/// Generates `<field>_fingerprint()`, returning `None` for an unset optional field.
+fn generate_fingerprint_method(generator: &dyn FieldGenerator) -> QuoteStream {
+    let name = generator.name();
+    let method = format_ident!("{}_fingerprint", name);
+    let doc = format!(
+        "First 12 hex digits of the SHA-256 of `{name}`, safe to log.\n\n\
+         The hash is one-way: it shows whether two deployments hold the same \
+         value without revealing it. It does not hide a guessable value, \
+         since anyone can hash the guess and compare."
+    );
+
+    let value = if generator.is_secrecy_type() {
+        quote! { ::procenv::ExposeSecret::expose_secret(__value) }
+    } else {
+        quote! { ::core::convert::AsRef::<str>::as_ref(__value) }
+    };
+
+    if generator.is_optional() {
+        quote! {
+            #[doc = #doc]
+            #[must_use]
+            pub fn #method(&self) -> ::std::option::Option<::std::string::String> {
+                self.#name
+                    .as_ref()
+                    .map(|__value| ::procenv::runtime::fingerprint(#value))
+            }
+        }
+    } else {
+        quote! {
+            #[doc = #doc]
+            #[must_use]
+            pub fn #method(&self) -> ::std::string::String {
+                let __value = &self.#name;
+                ::procenv::runtime::fingerprint(#value)
+            }
+        }
+    }
+}
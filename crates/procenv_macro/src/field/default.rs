@@ -35,7 +35,7 @@ use syn::{Ident, Type};
 
 use crate::parse::{CliAttr, ProfileAttr};
 
-use super::{EnvExampleEntry, FieldGenerator};
+use super::{EnvExampleEntry, FieldFactory, FieldGenerator};
 
 /// A field with a default value used when the environment variable is missing.
 ///
@@ -49,6 +49,10 @@ use super::{EnvExampleEntry, FieldGenerator};
 /// The default value is also parsed at runtime. If the default itself
 /// fails to parse (e.g., `default = "abc"` for a `u16` field), an error
 /// is recorded. This catches configuration mistakes early.
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each boolean mirrors an independent flag option in the attribute"
+)]
 pub struct DefaultField {
     /// The struct field name
     pub name: Ident,
@@ -78,15 +82,102 @@ pub struct DefaultField {
     pub format: Option<String>,
 
     pub validate: Option<String>,
+
+    /// Allowlist of accepted values (`one_of = [...]`)
+    pub one_of: Option<Vec<String>>,
+
+    /// Accept NaN/infinity for float types (`allow_non_finite`)
+    pub allow_non_finite: bool,
+
+    /// Report loads to the struct's audit hook (`sensitive_log`)
+    pub sensitive_log: bool,
+
+    /// Env var to read when `env_var` is unset (`default_from`, prefixed)
+    pub default_from: Option<String>,
+
+    /// Env var naming a file to read the value from (`from_file_var`, prefixed)
+    pub from_file_var: Option<String>,
+
+    /// Config file and key supplying the field in `from_config()` (`file`, `file_key`)
+    pub source_file: Option<(String, Option<String>)>,
+
+    /// Decode the raw value from base64 (`base64`)
+    pub base64: bool,
+
+    /// Resolve `get_str` JSON pointers into this field (`pointer_access`)
+    pub pointer_access: bool,
+
+    /// Merge `<var>_<KEY>` overrides into the JSON value (`merge_env`)
+    pub merge_env: bool,
+
+    /// Serde-style function used instead of `Deserialize` (`deserialize_with`)
+    pub deserialize_with: Option<String>,
+
+    /// Env var holding the list item separator (`sep_env`)
+    pub sep_env: Option<String>,
+
+    /// Reject watch reloads that change this field (`reload_immutable`)
+    pub reload_immutable: bool,
+
+    /// Minimum string length in characters (`min_len`)
+    pub min_len: Option<usize>,
+
+    /// Maximum string length in characters (`max_len`)
+    pub max_len: Option<usize>,
+
+    /// Pattern the string value must match (`validate_regex`)
+    pub validate_regex: Option<String>,
+
+    /// Sample value shown in `.env.example` (`example`)
+    pub example: Option<String>,
+
+    /// Type name shown in parse errors (`type_label`)
+    pub type_label: Option<String>,
+
+    /// Load `true` whenever the var is set, whatever its value (`presence_bool`)
+    pub presence_bool: bool,
+
+    /// Strip surrounding whitespace from the raw value (`trim`)
+    pub trim: bool,
+
+    /// Characters stripped in matching pairs from the raw value (`trim_matches`)
+    pub trim_matches: Option<String>,
+
+    /// Config file key from `#[serde(rename)]`/`rename_all`, if not the field name
+    pub serde_rename: Option<String>,
+
+    /// Generate a constant-time `verify_<field>()` (`sensitive_eq`)
+    pub sensitive_eq: bool,
+
+    /// Generate a `<field>_fingerprint()` hash (`log_fingerprint`)
+    pub log_fingerprint: bool,
+
+    /// Function transforming the parsed value (`after_parse`)
+    pub after_parse: Option<String>,
+
+    /// Regex whose captured parts are masked in `Debug` and errors (`redact_pattern`)
+    pub redact_pattern: Option<String>,
+
+    /// Accept `0`/`1` as well as `true`/`false` (`coerce_bool_numeric`)
+    pub coerce_bool_numeric: bool,
 }
 
 impl FieldGenerator for DefaultField {
     fn generate_loader(&self) -> QuoteStream {
         let field_name = &self.name;
         let ty = &self.ty;
-        let env_var = &self.env_var;
-        let default = &self.default;
-        let secret = self.secret;
+        let parse_val = FieldFactory::value_expr(
+            ty,
+            &quote! { val },
+            self.base64,
+            self.coerce_bool_numeric,
+            self.sep_env.as_deref(),
+        );
+        let env_var = FieldFactory::var_expr(&self.env_var, self.default_from.as_deref());
+        let read = FieldFactory::read_expr(&quote! { #env_var }, self, false);
+        let default = FieldFactory::default_string_expr(&self.default, &env_var);
+        let secret = FieldFactory::redact_expr(self.secret);
+        let type_name = self.type_name();
 
         let used_default_ident = format_ident!("__{}_used_default", field_name);
 
@@ -94,24 +185,22 @@ impl FieldGenerator for DefaultField {
             let mut #used_default_ident = false;
 
             let #field_name: std::option::Option<#ty> = (|| {
-                let val = match std::env::var(#env_var) {
+                let val = match #read {
                     std::result::Result::Ok(v) => v,
 
                     std::result::Result::Err(std::env::VarError::NotPresent) => {
                         #used_default_ident = true;
-                        #default.to_string()
+                        #default
                     },
 
-                    std::result::Result::Err(std::env::VarError::NotUnicode(_)) => {
-                        __errors.push(::procenv::Error::InvalidUtf8 {
-                            var: #env_var.to_string(),
-                        });
+                    std::result::Result::Err(std::env::VarError::NotUnicode(__raw)) => {
+                        __errors.push(::procenv::Error::invalid_utf8(#env_var, &__raw, #secret));
 
                         return std::option::Option::None;
                     }
                 };
 
-                match val.parse::<#ty>() {
+                match #parse_val {
                     std::result::Result::Ok(v) => std::option::Option::Some(v),
 
                     std::result::Result::Err(e) => {
@@ -119,7 +208,7 @@ impl FieldGenerator for DefaultField {
                             #env_var,
                             val,
                             #secret,
-                            std::any::type_name::<#ty>(),
+                            #type_name,
                             std::boxed::Box::new(e),
                         ));
 
@@ -137,12 +226,23 @@ impl FieldGenerator for DefaultField {
     fn generate_loader_with_external_prefix(&self) -> QuoteStream {
         let field_name = &self.name;
         let ty = &self.ty;
-        let base_var = &self.env_var;
-        let default = &self.default;
-        let secret = self.secret;
+        let parse_val = FieldFactory::value_expr(
+            ty,
+            &quote! { val },
+            self.base64,
+            self.coerce_bool_numeric,
+            self.sep_env.as_deref(),
+        );
+        let effective_var =
+            FieldFactory::effective_var_expr(&self.env_var, self.default_from.as_deref());
+        let secret = FieldFactory::redact_expr(self.secret);
+        let type_name = self.type_name();
 
         let used_default_ident = format_ident!("__{}_used_default", field_name);
         let effective_var_ident = format_ident!("__{}_effective_var", field_name);
+        let default =
+            FieldFactory::default_string_expr(&self.default, &quote! { &#effective_var_ident });
+        let read = FieldFactory::read_expr(&quote! { &#effective_var_ident }, self, true);
         let profile_used_ident = format_ident!("__{}_from_profile", field_name);
 
         // Check if this field has profile configuration
@@ -150,34 +250,28 @@ impl FieldGenerator for DefaultField {
             let mut #used_default_ident = false;
 
             // Build effective env var name with external prefix
-            let #effective_var_ident: std::string::String = format!(
-                "{}{}",
-                __external_prefix.unwrap_or(""),
-                #base_var
-            );
+            let #effective_var_ident: std::string::String = #effective_var;
 
             // No profile for this field
             let #profile_used_ident: bool = false;
 
             let #field_name: std::option::Option<#ty> = (|| {
-                let val = match std::env::var(&#effective_var_ident) {
+                let val = match #read {
                     std::result::Result::Ok(v) => v,
 
                     std::result::Result::Err(std::env::VarError::NotPresent) => {
                         #used_default_ident = true;
-                        #default.to_string()
+                        #default
                     },
 
-                    std::result::Result::Err(std::env::VarError::NotUnicode(_)) => {
-                        __errors.push(::procenv::Error::InvalidUtf8 {
-                            var: #effective_var_ident.clone(),
-                        });
+                    std::result::Result::Err(std::env::VarError::NotUnicode(__raw)) => {
+                        __errors.push(::procenv::Error::invalid_utf8(&#effective_var_ident, &__raw, #secret));
 
                         return std::option::Option::None;
                     }
                 };
 
-                match val.parse::<#ty>() {
+                match #parse_val {
                     std::result::Result::Ok(v) => std::option::Option::Some(v),
 
                     std::result::Result::Err(e) => {
@@ -185,7 +279,7 @@ impl FieldGenerator for DefaultField {
                             &#effective_var_ident,
                             val,
                             #secret,
-                            std::any::type_name::<#ty>(),
+                            #type_name,
                             std::boxed::Box::new(e),
                         ));
 
@@ -209,11 +303,7 @@ impl FieldGenerator for DefaultField {
                 let mut #used_default_ident = false;
 
                 // Build effective env var name with external prefix
-                let #effective_var_ident: std::string::String = format!(
-                    "{}{}",
-                    __external_prefix.unwrap_or(""),
-                    #base_var
-                );
+                let #effective_var_ident: std::string::String = #effective_var;
 
                 // Determine profile default value (if profile matches)
                 let __profile_default: std::option::Option<&str> = match __profile.as_deref() {
@@ -221,45 +311,47 @@ impl FieldGenerator for DefaultField {
                     _ => std::option::Option::None,
                 };
 
-                // Get value to parse: env var > profile default > compile-time default
-                let (val, #profile_used_ident): (std::string::String, bool) =
-                    match std::env::var(&#effective_var_ident) {
-                        std::result::Result::Ok(v) => (v, false),
+                let mut #profile_used_ident = false;
+
+                let #field_name: std::option::Option<#ty> = (|| {
+                    // Get value to parse: env var > profile default > compile-time default
+                    let val = match #read {
+                        std::result::Result::Ok(v) => v,
                         std::result::Result::Err(std::env::VarError::NotPresent) => {
                             match __profile_default {
                                 std::option::Option::Some(profile_val) => {
-                                    (profile_val.to_string(), true)
+                                    #profile_used_ident = true;
+                                    profile_val.to_string()
                                 }
                                 std::option::Option::None => {
                                     #used_default_ident = true;
-                                    (#default.to_string(), false)
+                                    #default
                                 }
                             }
                         }
-                        std::result::Result::Err(std::env::VarError::NotUnicode(_)) => {
-                            __errors.push(::procenv::Error::InvalidUtf8 {
-                                var: #effective_var_ident.clone(),
-                            });
+                        std::result::Result::Err(std::env::VarError::NotUnicode(__raw)) => {
+                            __errors.push(::procenv::Error::invalid_utf8(&#effective_var_ident, &__raw, #secret));
                             // Use default on UTF-8 error
                             #used_default_ident = true;
-                            (#default.to_string(), false)
+                            #default
                         }
                     };
 
-                // Parse the value
-                let #field_name: std::option::Option<#ty> = match val.parse::<#ty>() {
-                    std::result::Result::Ok(v) => std::option::Option::Some(v),
-                    std::result::Result::Err(e) => {
-                        __errors.push(::procenv::Error::parse(
-                            &#effective_var_ident,
-                            val,
-                            #secret,
-                            std::any::type_name::<#ty>(),
-                            std::boxed::Box::new(e),
-                        ));
-                        std::option::Option::None
+                    // Parse the value
+                    match #parse_val {
+                        std::result::Result::Ok(v) => std::option::Option::Some(v),
+                        std::result::Result::Err(e) => {
+                            __errors.push(::procenv::Error::parse(
+                                &#effective_var_ident,
+                                val,
+                                #secret,
+                                #type_name,
+                                std::boxed::Box::new(e),
+                            ));
+                            std::option::Option::None
+                        }
                     }
-                };
+                })();
             }
         })
     }
@@ -275,8 +367,9 @@ impl FieldGenerator for DefaultField {
     }
 
     fn type_name(&self) -> String {
-        let ty = &self.ty;
-        quote!(#ty).to_string().replace(' ', "")
+        self.type_label
+            .clone()
+            .unwrap_or_else(|| FieldFactory::type_label(&self.ty))
     }
 
     fn is_secret(&self) -> bool {
@@ -292,13 +385,14 @@ impl FieldGenerator for DefaultField {
             default: Some(self.default.clone()),
             secret: self.secret,
             type_hint: quote!(#ty).to_string().replace(' ', ""),
+            example: self.example.clone(),
         }]
     }
 
     fn generate_source_tracking(&self) -> QuoteStream {
         let field_name = &self.name;
         let field_name_str = field_name.to_string();
-        let env_var = &self.env_var;
+        let env_var = FieldFactory::var_expr(&self.env_var, self.default_from.as_deref());
 
         let source_ident = format_ident!("__{}_source", field_name);
         let used_default_ident = format_ident!("__{}_used_default", field_name);
@@ -317,7 +411,7 @@ impl FieldGenerator for DefaultField {
                 } else if #used_default_ident {
                     ::procenv::ValueSource::new(#env_var, ::procenv::Source::Default)
                 } else if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
-                    ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(None))
+                    ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(__dotenv_path.clone()))
                 } else {
                     ::procenv::ValueSource::new(#env_var, ::procenv::Source::Environment)
                 };
@@ -330,7 +424,7 @@ impl FieldGenerator for DefaultField {
                 let #source_ident = if #used_default_ident {
                     ::procenv::ValueSource::new(#env_var, ::procenv::Source::Default)
                 } else if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
-                    ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(None))
+                    ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(__dotenv_path.clone()))
                 } else {
                     ::procenv::ValueSource::new(#env_var, ::procenv::Source::Environment)
                 };
@@ -342,40 +436,43 @@ impl FieldGenerator for DefaultField {
 
     fn generate_format_loader(&self, format: &str) -> QuoteStream {
         let field_name = &self.name;
-        let env_var = &self.env_var;
-        let default = &self.default;
-        let secret = self.secret;
+        let env_var = FieldFactory::var_expr(&self.env_var, self.default_from.as_deref());
+        let read = FieldFactory::read_expr(&quote! { #env_var }, self, false);
+        let default = FieldFactory::default_string_expr(&self.default, &env_var);
+        let secret = FieldFactory::redact_expr(self.secret);
 
         let used_default_ident = format_ident!("__{}_used_default", field_name);
 
         let deserialize_call: QuoteStream = match format {
-            "json" => quote! { ::serde_json::from_str(&val) },
+            "json" => FieldFactory::json_expr(&env_var, self.merge_env),
 
             "toml" => quote! { ::toml::from_str(&val) },
 
             "yaml" => quote! { ::serde_saphyr::from_str(&val) },
 
+            "auto" => quote! { ::procenv::runtime::deserialize_auto(&val) },
+
             _ => unreachable!("Format validated at parse time"),
         };
+        let deserialize_call =
+            FieldFactory::deserialize_with_expr(deserialize_call, self.deserialize_with.as_deref());
 
-        let format_name: String = format.to_uppercase();
+        let format_name: String = FieldFactory::format_label(format);
 
         quote! {
             let mut #used_default_ident = false;
 
             let #field_name = (|| {
-                let val = match std::env::var(#env_var) {
+                let val = match #read {
                     std::result::Result::Ok(v) => v,
 
                     std::result::Result::Err(std::env::VarError::NotPresent) => {
                         #used_default_ident = true;
-                        #default.to_string()
+                        #default
                     }
 
-                    std::result::Result::Err(std::env::VarError::NotUnicode(_)) => {
-                        __errors.push(::procenv::Error::InvalidUtf8 {
-                            var: #env_var.to_string(),
-                        });
+                    std::result::Result::Err(std::env::VarError::NotUnicode(__raw)) => {
+                        __errors.push(::procenv::Error::invalid_utf8(#env_var, &__raw, #secret));
 
                         return std::option::Option::None;
                     }
@@ -416,6 +513,92 @@ impl FieldGenerator for DefaultField {
         Some(&self.default)
     }
 
+    fn sep_env(&self) -> Option<&str> {
+        self.sep_env.as_deref()
+    }
+
+    fn has_pointer_access(&self) -> bool {
+        self.pointer_access
+    }
+
+    fn merges_env(&self) -> bool {
+        self.merge_env
+    }
+
+    fn deserialize_with(&self) -> Option<&str> {
+        self.deserialize_with.as_deref()
+    }
+
+    fn is_reload_immutable(&self) -> bool {
+        self.reload_immutable
+    }
+
+    fn min_len(&self) -> Option<usize> {
+        self.min_len
+    }
+
+    fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    fn validate_regex(&self) -> Option<&str> {
+        self.validate_regex.as_deref()
+    }
+
+    fn is_base64(&self) -> bool {
+        self.base64
+    }
+
+    fn coerces_bool_numeric(&self) -> bool {
+        self.coerce_bool_numeric
+    }
+
+    fn is_presence_bool(&self) -> bool {
+        self.presence_bool
+    }
+
+    fn default_from(&self) -> Option<&str> {
+        self.default_from.as_deref()
+    }
+
+    fn trims_whitespace(&self) -> bool {
+        self.trim
+    }
+
+    fn trim_matches(&self) -> Option<&str> {
+        self.trim_matches.as_deref()
+    }
+
+    fn serde_rename(&self) -> Option<&str> {
+        self.serde_rename.as_deref()
+    }
+
+    fn is_sensitive_eq(&self) -> bool {
+        self.sensitive_eq
+    }
+
+    fn is_log_fingerprint(&self) -> bool {
+        self.log_fingerprint
+    }
+
+    fn after_parse(&self) -> Option<&str> {
+        self.after_parse.as_deref()
+    }
+
+    fn redact_pattern(&self) -> Option<&str> {
+        self.redact_pattern.as_deref()
+    }
+
+    fn file_var(&self) -> Option<&str> {
+        self.from_file_var.as_deref()
+    }
+
+    fn source_file(&self) -> Option<(&str, Option<&str>)> {
+        self.source_file
+            .as_ref()
+            .map(|(path, key)| (path.as_str(), key.as_deref()))
+    }
+
     fn format_config(&self) -> Option<&str> {
         self.format.as_deref()
     }
@@ -424,6 +607,18 @@ impl FieldGenerator for DefaultField {
         self.validate.as_deref()
     }
 
+    fn one_of(&self) -> Option<&[String]> {
+        self.one_of.as_deref()
+    }
+
+    fn is_audited(&self) -> bool {
+        self.secret || self.sensitive_log
+    }
+
+    fn requires_finite(&self) -> bool {
+        !self.allow_non_finite && self.format.is_none() && FieldFactory::is_float_type(&self.ty)
+    }
+
     fn field_type(&self) -> Option<&Type> {
         Some(&self.ty)
     }
@@ -43,12 +43,31 @@
 //!
 //! This prepends `DB_` to all nested env var names. Prefixes can be combined
 //! with the parent struct's prefix.
+//!
+//! Without `prefix`, the parent struct's prefix is not applied unless the
+//! field opts in with `inherit_prefix`:
+//! ```rust,ignore
+//! #[env(flatten, inherit_prefix)]
+//! database: DatabaseConfig, // APP_ + DB_URL → APP_DB_URL
+//! ```
+//!
+//! # Serde Flatten
+//!
+//! A nested type that only implements `Deserialize` (typically from another
+//! crate) can be flattened with `serde`, handled by [`SerdeFlattenField`]:
+//! ```rust,ignore
+//! #[env(flatten, serde)]
+//! tls: external::TlsConfig,
+//! ```
+//!
+//! `from_config()` deserializes the field's object from the merged config
+//! files. It has no env vars, so every other loader reports an error for it.
 
 use proc_macro2::TokenStream as QuoteStream;
 use quote::{format_ident, quote};
 use syn::{Ident, Type};
 
-use super::{EnvExampleEntry, FieldGenerator};
+use super::{EnvExampleEntry, FieldFactory, FieldGenerator};
 
 /// A flattened nested config field.
 ///
@@ -63,7 +82,8 @@ use super::{EnvExampleEntry, FieldGenerator};
 /// ## Prefix Support
 /// When `prefix` is set (e.g., `#[env(flatten, prefix = "DB_")]`), the nested
 /// type's env vars are prefixed with this value. The prefix is combined with
-/// any parent struct's prefix.
+/// any parent struct's prefix. With `inherit_prefix` and no own prefix, the
+/// parent struct's prefix alone is used.
 pub struct FlattenField {
     /// The struct field name
     pub name: Ident,
@@ -73,6 +93,9 @@ pub struct FlattenField {
 
     /// Optional prefix to prepend to nested env var names
     pub prefix: Option<String>,
+
+    /// Config file key from `#[serde(rename)]`/`rename_all`, if not the field name
+    pub serde_rename: Option<String>,
 }
 
 impl FieldGenerator for FlattenField {
@@ -90,11 +113,10 @@ impl FieldGenerator for FlattenField {
                 }
             },
             |prefix| {
+                let nested_prefix = FieldFactory::external_var_expr(&quote! { #prefix });
                 quote! {
                     <#ty>::__from_env_with_external_prefix(
-                        std::option::Option::Some(
-                            &format!("{}{}", __external_prefix.unwrap_or(""), #prefix)
-                        )
+                        std::option::Option::Some(&#nested_prefix)
                     )
                 }
             },
@@ -168,14 +190,33 @@ impl FieldGenerator for FlattenField {
                         // Prepend prefix to each VAR_NAME= line
                         nested.lines()
                             .map(|line| {
-                                // Skip comments and empty lines
-                                if line.starts_with('#') || line.is_empty() {
-                                    line.to_string()
-                                } else if let std::option::Option::Some(eq_pos) = line.find('=') {
+                                // Defaulted vars are commented out as `# VAR=default`
+                                let join = |s: &str| {
+                                    ::procenv::runtime::join_prefix(
+                                        #prefix,
+                                        s,
+                                        <#ty>::__PREFIX_SEPARATOR,
+                                    )
+                                };
+                                let is_var = |s: &str| {
+                                    s.split_once('=').is_some_and(|(name, _)| {
+                                        !name.is_empty()
+                                            && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+                                    })
+                                };
+
+                                match line.strip_prefix("# ") {
+                                    // Prepend prefix to the commented-out variable name
+                                    std::option::Option::Some(rest) if is_var(rest) => {
+                                        format!("# {}", join(rest))
+                                    }
+                                    // Skip other comments and empty lines
+                                    std::option::Option::Some(_) => line.to_string(),
                                     // Prepend prefix to the variable name
-                                    format!("{}{}", #prefix, line)
-                                } else {
-                                    line.to_string()
+                                    std::option::Option::None if is_var(line) => {
+                                        join(line)
+                                    }
+                                    std::option::Option::None => line.to_string(),
                                 }
                             })
                             .collect::<std::vec::Vec<_>>()
@@ -198,6 +239,10 @@ impl FieldGenerator for FlattenField {
         self.prefix.as_deref()
     }
 
+    fn serde_rename(&self) -> Option<&str> {
+        self.serde_rename.as_deref()
+    }
+
     fn generate_source_tracking(&self) -> QuoteStream {
         let field_name = &self.name;
         let field_name_str = field_name.to_string();
@@ -218,3 +263,95 @@ impl FieldGenerator for FlattenField {
         None // Flatten fields don't participate in direct key access
     }
 }
+
+/// A nested `Deserialize` type flattened with `#[env(flatten, serde)]`.
+///
+/// ## Behavior
+/// - `from_config()` deserializes the field's key of the merged config files
+///   with `serde_json::from_value`
+/// - Env-only loaders (`from_env()`, `from_args()`, ...) push an
+///   `Error::Extraction` explaining that the field is read from files only
+/// - Treated as skipped by every env code path, so no env var, source entry,
+///   example line, key or `Debug` entry is generated for it
+pub struct SerdeFlattenField {
+    /// The struct field name
+    pub name: Ident,
+
+    /// The field's type (must implement `Deserialize`)
+    pub ty: Type,
+
+    /// Config file key from `#[serde(rename)]`/`rename_all`, if not the field name
+    pub serde_rename: Option<String>,
+}
+
+impl FieldGenerator for SerdeFlattenField {
+    fn generate_loader(&self) -> QuoteStream {
+        let field_name = &self.name;
+        let field_name_str = field_name.to_string();
+        let ty = &self.ty;
+        let type_name = self.type_name();
+
+        quote! {
+            __errors.push(::procenv::Error::file_only(
+                #field_name_str,
+                #type_name,
+                "not loaded from environment variables",
+            ));
+            let #field_name: std::option::Option<#ty> = std::option::Option::None;
+        }
+    }
+
+    fn generate_assignment(&self) -> QuoteStream {
+        let name = &self.name;
+
+        quote! { #name: #name.unwrap() }
+    }
+
+    fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    fn type_name(&self) -> String {
+        FieldFactory::type_label(&self.ty)
+    }
+
+    fn is_secret(&self) -> bool {
+        false
+    }
+
+    fn example_entries(&self) -> Vec<EnvExampleEntry> {
+        vec![]
+    }
+
+    fn generate_source_tracking(&self) -> QuoteStream {
+        quote! {}
+    }
+
+    fn generate_loader_with_external_prefix(&self) -> QuoteStream {
+        self.generate_loader()
+    }
+
+    fn env_var_name(&self) -> Option<&str> {
+        None // Only read from config files
+    }
+
+    fn field_name(&self) -> Option<&Ident> {
+        None // Not an EnvConfig type, so there are no keys to access
+    }
+
+    fn field_type(&self) -> Option<&Type> {
+        Some(&self.ty)
+    }
+
+    fn is_skip(&self) -> bool {
+        true
+    }
+
+    fn serde_rename(&self) -> Option<&str> {
+        self.serde_rename.as_deref()
+    }
+
+    fn is_serde_flatten(&self) -> bool {
+        true
+    }
+}
@@ -14,6 +14,8 @@
 //! | [`DefaultField`] | `default = "..."` | Uses default if missing |
 //! | [`OptionalField`] | `optional` | Returns `None` if missing |
 //! | [`FlattenField`] | `flatten` | Loads nested `EnvConfig` struct |
+//! | [`SerdeFlattenField`] | `flatten, serde` | Deserializes nested type from config files |
+//! | [`SkipField`] | `skip` | Not loaded, set to `Default::default()` |
 //! | [`SecretStringField`] | `SecretString` type | Wraps in `SecretString` |
 //! | [`SecretBoxField`] | `SecretBox<T>` type | Wraps in `SecretBox<T>` |
 //!
@@ -60,7 +62,7 @@ use syn::{
     Error as SynError, Field, GenericArgument, Ident, PathArguments, Result as SynResult, Type,
 };
 
-use crate::parse::{CliAttr, FieldConfig, Parser, ProfileAttr, extract_doc_comment};
+use crate::parse::{CliAttr, FieldConfig, Parser, ProfileAttr, RenameRule, extract_doc_comment};
 
 // Field type implementations
 mod default;
@@ -68,12 +70,14 @@ mod flatten;
 mod optional;
 mod required;
 mod secret;
+mod skip;
 
 pub use default::DefaultField;
-pub use flatten::FlattenField;
+pub use flatten::{FlattenField, SerdeFlattenField};
 pub use optional::OptionalField;
 pub use required::RequiredField;
 pub use secret::{SecretBoxField, SecretKind, SecretStringField};
+pub use skip::SkipField;
 
 // ============================================================================
 // EnvExampleEntry - Info for .env.example generation
@@ -95,10 +99,17 @@ pub use secret::{SecretBoxField, SecretKind, SecretStringField};
 ///
 /// # Server port (type: u16)
 /// # PORT=8080
+///
+/// # API token (required, secret, type: SecretString)
+/// # API_TOKEN=<secret>
+/// API_TOKEN=
 /// ```
 ///
-/// - Required fields without defaults show `VAR=`
+/// - Required fields without defaults show `VAR=`, or `VAR=example` with an
+///   `example` value
 /// - Fields with defaults show `# VAR=default` (commented out)
+/// - Secret fields never show their `example`; a commented `<secret>`
+///   placeholder hints that a value is expected instead
 /// - Doc comments, requirements, and type hints appear as comments above
 #[derive(Clone, Debug)]
 pub struct EnvExampleEntry {
@@ -119,6 +130,9 @@ pub struct EnvExampleEntry {
 
     /// Type name for documentation hints (e.g., `"u16"`, `"String"`).
     pub type_hint: String,
+
+    /// Sample value from `example = "..."`, used when there is no default.
+    pub example: Option<String>,
 }
 
 impl EnvExampleEntry {
@@ -155,6 +169,12 @@ impl EnvExampleEntry {
         if let Some(default) = &self.default {
             // Has default - show commented out with default value
             lines.push(format!("# {}={}", self.var_name, default));
+        } else if self.example.is_some() && self.secret {
+            // Never write a secret's example - hint with a placeholder instead
+            lines.push(format!("# {}=<secret>", self.var_name));
+            lines.push(format!("{}=", self.var_name));
+        } else if let Some(example) = &self.example {
+            lines.push(format!("{}={}", self.var_name, example));
         } else {
             // Required or optional without default - show empty
             lines.push(format!("{}=", self.var_name));
@@ -183,6 +203,8 @@ impl EnvExampleEntry {
 /// | [`DefaultField`] | `#[env(var = "...", default = "...")]` |
 /// | [`OptionalField`] | `#[env(var = "...", optional)]` |
 /// | [`FlattenField`] | `#[env(flatten)]` |
+/// | [`SerdeFlattenField`] | `#[env(flatten, serde)]` |
+/// | [`SkipField`] | `#[env(skip)]` |
 /// | [`SecretStringField`] | Field type is `SecretString` |
 /// | [`SecretBoxField`] | Field type is `SecretBox<T>` |
 ///
@@ -205,7 +227,7 @@ pub trait FieldGenerator {
     /// Generate code to load this field's value from the environment.
     ///
     /// The generated code should:
-    /// 1. Call `std::env::var()` to read the environment variable
+    /// 1. Call `::procenv::runtime::var()` to read the environment variable
     /// 2. Parse the value using `.parse::<T>()`
     /// 3. Push any errors to `__errors` vector
     /// 4. Store the result in a local variable as `Option<T>`
@@ -232,7 +254,7 @@ pub trait FieldGenerator {
 
     /// Whether this field is marked as secret.
     ///
-    /// Secret fields are masked in Debug output ("***") and error messages
+    /// Secret fields are masked in Debug output and error messages
     /// ("<redacted>") to prevent accidental exposure of sensitive data.
     fn is_secret(&self) -> bool;
 
@@ -292,7 +314,9 @@ pub trait FieldGenerator {
     ///
     /// The generated code should use:
     /// ```ignore
-    /// let __effective_var = format!("{}{}", __external_prefix.unwrap_or(""), "BASE_VAR");
+    /// let __effective_var = ::procenv::runtime::join_prefix(
+    ///     __external_prefix.unwrap_or(""), "BASE_VAR", Self::__PREFIX_SEPARATOR,
+    /// );
     /// match std::env::var(&__effective_var) { ... }
     /// ```
     fn generate_loader_with_external_prefix(&self) -> QuoteStream {
@@ -319,6 +343,132 @@ pub trait FieldGenerator {
         None
     }
 
+    /// Returns the fallback env var name (`default_from`), already prefixed.
+    fn default_from(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the env var naming a file holding the value (`from_file_var`), already prefixed.
+    fn file_var(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the config file supplying this field in `from_config()` and
+    /// the key read from it (`file`, `file_key`).
+    fn source_file(&self) -> Option<(&str, Option<&str>)> {
+        None
+    }
+
+    /// Whether the raw value is base64-decoded before assignment.
+    fn is_base64(&self) -> bool {
+        false
+    }
+
+    /// Whether a `bool` also accepts `0`/`1` (`coerce_bool_numeric`).
+    fn coerces_bool_numeric(&self) -> bool {
+        false
+    }
+
+    /// Whether a set var loads as `true` regardless of its value (`presence_bool`).
+    fn is_presence_bool(&self) -> bool {
+        false
+    }
+
+    /// Whether surrounding whitespace is stripped from the raw value (`trim`).
+    fn trims_whitespace(&self) -> bool {
+        false
+    }
+
+    /// Characters stripped in matching pairs from the raw value (`trim_matches`).
+    fn trim_matches(&self) -> Option<&str> {
+        None
+    }
+
+    /// Config file key set by `#[serde(rename)]` or `rename_all`, if it differs
+    /// from the field name.
+    fn serde_rename(&self) -> Option<&str> {
+        None
+    }
+
+    /// Key this field is read from in merged config files.
+    ///
+    /// Independent of the env var name: `env(var)` only names the env var.
+    fn file_key(&self) -> String {
+        self.serde_rename()
+            .map_or_else(|| self.name().to_string(), ToString::to_string)
+    }
+
+    /// Whether a constant-time `verify_<field>()` is generated (`sensitive_eq`).
+    fn is_sensitive_eq(&self) -> bool {
+        false
+    }
+
+    /// Whether a `<field>_fingerprint()` is generated (`log_fingerprint`).
+    fn is_log_fingerprint(&self) -> bool {
+        false
+    }
+
+    /// Path of the function transforming the parsed value (`after_parse`).
+    fn after_parse(&self) -> Option<&str> {
+        None
+    }
+
+    /// Profiles in which this optional field must be set (`when_profile`).
+    fn when_profile(&self) -> Option<&[String]> {
+        None
+    }
+
+    /// Whether the field is an `Option<Option<T>>` that keeps an explicit
+    /// config file `null` apart from an absent key (`distinguish_null`).
+    fn distinguish_null(&self) -> bool {
+        false
+    }
+
+    /// Returns the env var holding this list field's separator (`sep_env`).
+    fn sep_env(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether `get_str` resolves `field/json/pointer` keys into this field.
+    fn has_pointer_access(&self) -> bool {
+        false
+    }
+
+    /// Whether `<var>_<KEY>` env vars override keys of this JSON field.
+    fn merges_env(&self) -> bool {
+        false
+    }
+
+    /// Path of the serde-style function deserializing this `format` field.
+    fn deserialize_with(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether watch reloads that change this field are rejected.
+    fn is_reload_immutable(&self) -> bool {
+        false
+    }
+
+    /// Returns the minimum string length in characters (`min_len`).
+    fn min_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the maximum string length in characters (`max_len`).
+    fn max_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the pattern the string value must match (`validate_regex`).
+    fn validate_regex(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the pattern of the value's parts to mask (`redact_pattern`).
+    fn redact_pattern(&self) -> Option<&str> {
+        None
+    }
+
     /// Returns format configuration if this field uses serde deserialization.
     fn format_config(&self) -> Option<&str> {
         None
@@ -342,6 +492,25 @@ pub trait FieldGenerator {
         None
     }
 
+    /// Returns the allowlist of accepted values if `one_of` is specified.
+    fn one_of(&self) -> Option<&[String]> {
+        None
+    }
+
+    /// Whether the loaded value must be a finite float.
+    ///
+    /// True for plain `f32`/`f64` fields unless `allow_non_finite` is set.
+    fn requires_finite(&self) -> bool {
+        false
+    }
+
+    /// Whether loads of this field are reported to the struct's `audit` hook.
+    ///
+    /// True for secret fields and fields marked `sensitive_log`.
+    fn is_audited(&self) -> bool {
+        self.is_secret()
+    }
+
     /// Generate clap Arg definition for this field (if CLI-enabled).
     fn generate_clap_arg(&self) -> Option<QuoteStream> {
         let cli = self.cli_config()?;
@@ -353,16 +522,27 @@ pub trait FieldGenerator {
             .short
             .map_or_else(|| quote! {}, |short| quote! { .short(#short) });
 
+        // Count flags take no value; each occurrence bumps the count
+        let value_attr = if cli.count {
+            quote! { .action(::procenv::clap::ArgAction::Count) }
+        } else {
+            quote! { .value_name(#name_str) }
+        };
+
         Some(quote! {
             ::procenv::clap::Arg::new(#name_str)
                 .long(#long)
                 #short_attr
-                .value_name(#name_str)
+                #value_attr
         })
     }
 
     /// Generate code to extract CLI value for this field.
     /// Returns code that sets a local variable `__{name}_cli: Option<String>`.
+    ///
+    /// For `count` flags, zero occurrences yields `None` so the env var (or
+    /// default) still applies; otherwise the count is rendered as a string and
+    /// parsed into the field type like any other CLI value.
     fn generate_cli_extraction(&self) -> Option<QuoteStream> {
         let cli = self.cli_config()?;
         let _long = cli.long.as_ref()?;
@@ -370,9 +550,24 @@ pub trait FieldGenerator {
         let cli_var = format_ident!("__{}_cli", name);
         let name_str = name.to_string();
 
+        // `try_get_one` so matches from a command without this arg fall back to env
+        if cli.count {
+            return Some(quote! {
+                let #cli_var: std::option::Option<std::string::String> =
+                    match __matches.try_get_one::<u8>(#name_str).ok().flatten() {
+                        std::option::Option::None | std::option::Option::Some(0) => {
+                            std::option::Option::None
+                        }
+                        std::option::Option::Some(n) => std::option::Option::Some(n.to_string()),
+                    };
+            });
+        }
+
         Some(quote! {
             let #cli_var: std::option::Option<std::string::String> = __matches
-                .get_one::<std::string::String>(#name_str)
+                .try_get_one::<std::string::String>(#name_str)
+                .ok()
+                .flatten()
                 .cloned();
         })
     }
@@ -387,6 +582,16 @@ pub trait FieldGenerator {
     fn is_optional(&self) -> bool {
         false
     }
+
+    /// Whether this field is excluded from env loading (`skip`, `flatten, serde`).
+    fn is_skip(&self) -> bool {
+        false
+    }
+
+    /// Whether this field is deserialized from config files only (`flatten, serde`).
+    fn is_serde_flatten(&self) -> bool {
+        false
+    }
 }
 
 // ============================================================================
@@ -406,6 +611,10 @@ pub trait FieldGenerator {
 ///   │
 ///   ├─► Has `flatten` attr? ──► FlattenField
 ///   │
+///   ├─► Has `skip` attr? ──► SkipField
+///   │
+///   ├─► Type is a reference? ──► error (loaded values must be owned)
+///   │
 ///   ├─► Type is SecretString? ──► SecretStringField
 ///   │
 ///   ├─► Type is SecretBox<T>? ──► SecretBoxField
@@ -429,10 +638,24 @@ impl FieldFactory {
     /// ## Field Type Selection
     ///
     /// - `flatten` attribute → `FlattenField` (nested config)
+    /// - `skip` attribute → `SkipField` (not configuration)
     /// - `optional` attribute → `OptionalField` (validates that type is `Option<T>`)
     /// - `default` attribute → `DefaultField`
     /// - Neither → `RequiredField`
-    pub fn parse_field(field: &Field, prefix: Option<&str>) -> SynResult<Box<dyn FieldGenerator>> {
+    ///
+    /// The struct `prefix` is joined to var names with `separator`, see
+    /// [`Self::join_prefix`]. `rename_all` is the struct's serde
+    /// `rename_all` rule, used for config file keys.
+    #[expect(
+        clippy::too_many_lines,
+        reason = "field construction passes every parsed option through explicitly"
+    )]
+    pub fn parse_field(
+        field: &Field,
+        prefix: Option<&str>,
+        separator: Option<&str>,
+        rename_all: Option<RenameRule>,
+    ) -> SynResult<Box<dyn FieldGenerator>> {
         // Extract field name (unwrap is safe for named struct fields)
         let name = field.ident.clone().unwrap();
         let ty = field.ty.clone();
@@ -443,63 +666,387 @@ impl FieldFactory {
         // Parse the #[env(...)] attribute
         let field_config = Parser::parse_field_config(field)?;
 
+        // Config files are keyed by the serde name, not the Rust field name
+        let serde_rename = Parser::parse_serde_rename(field, rename_all)?;
+
         // Handle flatten fields separately - they don't use env vars directly
         if let FieldConfig::Flatten {
             prefix: flatten_prefix,
+            inherit_prefix,
+            serde,
         } = field_config
         {
+            // A `Deserialize`-only type has no env vars, so prefixes don't apply
+            if serde {
+                return Ok(Box::new(SerdeFlattenField {
+                    name,
+                    ty,
+                    serde_rename,
+                }));
+            }
+
             // Flatten fields only get a prefix if explicitly specified via `prefix = "..."`
-            // By default, flatten fields DON'T inherit the parent struct's prefix
-            // (this maintains backwards compatibility)
+            // or opted into with `inherit_prefix`. By default, flatten fields DON'T
+            // inherit the parent struct's prefix (this maintains backwards compatibility)
             //
             // When a prefix IS specified on the flatten field:
             // - If struct has prefix = "APP_" and flatten has prefix = "DB_",
-            //   the effective prefix for nested fields is "APP_DB_"
+            //   the effective prefix for nested fields is "APP_DB_", joined
+            //   with `separator` when the struct sets `env_prefix_separator`
             // - If no struct prefix, just the flatten prefix is used
-            let effective_prefix = flatten_prefix.map(|field_prefix| match prefix {
-                Some(struct_prefix) => format!("{struct_prefix}{field_prefix}"),
-                None => field_prefix,
-            });
+            //
+            // With `inherit_prefix` and no own prefix, the struct prefix alone is used
+            let effective_prefix = match (flatten_prefix, prefix) {
+                (Some(field_prefix), Some(struct_prefix)) => {
+                    Some(Self::join_prefix(struct_prefix, &field_prefix, separator))
+                }
+                (Some(field_prefix), None) => Some(field_prefix),
+                (None, Some(struct_prefix)) if inherit_prefix => Some(struct_prefix.to_string()),
+                (None, _) => None,
+            };
 
             return Ok(Box::new(FlattenField {
                 name,
                 ty,
                 prefix: effective_prefix,
+                serde_rename,
             }));
         }
 
+        if matches!(field_config, FieldConfig::Skip) {
+            return Ok(Box::new(SkipField { name, ty }));
+        }
+
+        // Values are parsed from owned strings, so nothing can be borrowed
+        if let Type::Reference(reference) = &ty {
+            let owned = match &*reference.elem {
+                Type::Path(path) if path.path.is_ident("str") => "`String`".to_string(),
+                Type::Slice(slice) => {
+                    let elem = &slice.elem;
+                    format!("`Vec<{}>`", quote!(#elem).to_string().replace(' ', ""))
+                }
+                other => format!("`{}`", quote!(#other).to_string().replace(' ', "")),
+            };
+
+            return Err(SynError::new_spanned(
+                &ty,
+                format!(
+                    "EnvConfig fields cannot borrow: values are loaded at runtime and must be \
+                     owned. Use {owned} instead, or `#[env(skip)]` for non-config fields"
+                ),
+            ));
+        }
+
         // Extract EnvAttr for regular fields
         let FieldConfig::Env(env_attr) = field_config else {
             unreachable!()
         };
 
-        // Apply prefix to var name (unless no_prefix is set)
-        let env_var = if !env_attr.no_prefix
-            && let Some(prefix_val) = prefix
-        {
-            format!("{}{}", prefix_val, env_attr.var_name)
-        } else {
-            env_attr.var_name
+        // Apply prefix to var name (unless no_prefix is set). The fallback and
+        // file vars are sibling settings, so they are prefixed the same way.
+        let prefixed = |var: String| {
+            if !env_attr.no_prefix
+                && let Some(prefix_val) = prefix
+            {
+                Self::join_prefix(prefix_val, &var, separator)
+            } else {
+                var
+            }
         };
+        let env_var = prefixed(env_attr.var_name);
+        let default_from = env_attr.default_from.map(prefixed);
+        let from_file_var = env_attr.from_file_var.map(prefixed);
+        let source_file = env_attr.file.map(|file| (file, env_attr.file_key));
 
         if let Some(secret_kind) = Self::extract_secret_kind(&ty) {
+            if default_from.is_some()
+                || env_attr.base64
+                || env_attr.sep_env.is_some()
+                || env_attr.reload_immutable
+                || env_attr.after_parse.is_some()
+                || env_attr.redact_pattern.is_some()
+                || source_file.is_some()
+                || env_attr.when_profile.is_some()
+                || env_attr.coerce_bool_numeric
+            {
+                return Err(SynError::new_spanned(
+                    &ty,
+                    "`default_from`, `base64`, `sep_env`, `reload_immutable`, `after_parse`, \
+                     `redact_pattern`, `file`, `when_profile` and `coerce_bool_numeric` are not \
+                     supported on secrecy types",
+                ));
+            }
+
+            let has_len_bounds = env_attr.min_len.is_some() || env_attr.max_len.is_some();
+            let has_regex = env_attr.validate_regex.is_some();
+
             return match secret_kind {
-                SecretKind::String => Ok(Box::new(SecretStringField { name, env_var, doc })),
+                SecretKind::String => Ok(Box::new(SecretStringField {
+                    name,
+                    env_var,
+                    from_file_var,
+                    doc,
+                    min_len: env_attr.min_len,
+                    max_len: env_attr.max_len,
+                    validate_regex: env_attr.validate_regex,
+                    example: env_attr.example,
+                    trim: env_attr.trim,
+                    trim_matches: env_attr.trim_matches,
+                    serde_rename,
+                    sensitive_eq: env_attr.sensitive_eq,
+                    log_fingerprint: env_attr.log_fingerprint,
+                })),
+
+                SecretKind::Box(_) if has_len_bounds => Err(SynError::new_spanned(
+                    &ty,
+                    "`min_len`/`max_len` require a string type such as `String` or `SecretString`",
+                )),
+
+                SecretKind::Box(_) if has_regex => Err(SynError::new_spanned(
+                    &ty,
+                    "`validate_regex` requires a string type such as `String` or `SecretString`",
+                )),
+
+                SecretKind::Box(_) if env_attr.sensitive_eq => Err(SynError::new_spanned(
+                    &ty,
+                    "`sensitive_eq` requires a string type such as `String` or `SecretString`",
+                )),
+
+                SecretKind::Box(_) if env_attr.log_fingerprint => Err(SynError::new_spanned(
+                    &ty,
+                    "`log_fingerprint` requires a string type such as `String` or `SecretString`",
+                )),
 
                 SecretKind::Box(inner_type) => Ok(Box::new(SecretBoxField {
                     name,
                     inner_type: *inner_type,
                     env_var,
+                    from_file_var,
                     doc,
+                    example: env_attr.example,
+                    type_label: env_attr.type_label,
+                    trim: env_attr.trim,
+                    trim_matches: env_attr.trim_matches,
+                    serde_rename,
                 })),
             };
         }
 
+        // The outer `Option` only records an explicit config file `null`, so
+        // everything below checks and loads the `Option<T>` inside it
+        let distinguish_null = env_attr.distinguish_null;
+        let ty = if distinguish_null {
+            Self::extract_option_inner(&ty)
+                .filter(|inner| Self::extract_option_inner(inner).is_some())
+                .ok_or_else(|| {
+                    SynError::new_spanned(
+                        &ty,
+                        "Field marked `distinguish_null` must have type `Option<Option<T>>`",
+                    )
+                })?
+                .clone()
+        } else {
+            ty
+        };
+
         let secret = env_attr.secret;
         let cli = env_attr.cli;
         let profile = env_attr.profile;
         let format = env_attr.format;
         let validate = env_attr.validate;
+        let one_of = env_attr.one_of;
+        let allow_non_finite = env_attr.allow_non_finite;
+        let sensitive_log = env_attr.sensitive_log;
+        let base64 = env_attr.base64;
+        let pointer_access = env_attr.pointer_access;
+        let merge_env = env_attr.merge_env;
+        let deserialize_with = env_attr.deserialize_with;
+        let sep_env = env_attr.sep_env;
+        let reload_immutable = env_attr.reload_immutable;
+        let min_len = env_attr.min_len;
+        let max_len = env_attr.max_len;
+        let validate_regex = env_attr.validate_regex;
+        let example = env_attr.example;
+        let type_label = env_attr.type_label;
+        let presence_bool = env_attr.presence_bool;
+        let trim = env_attr.trim;
+        let trim_matches = env_attr.trim_matches;
+        let sensitive_eq = env_attr.sensitive_eq;
+        let log_fingerprint = env_attr.log_fingerprint;
+        let after_parse = env_attr.after_parse;
+        let redact_pattern = env_attr.redact_pattern;
+        let when_profile = env_attr.when_profile;
+        let coerce_bool_numeric = env_attr.coerce_bool_numeric;
+
+        // Decoding yields bytes, so only byte and (UTF-8 checked) string targets work
+        if base64 {
+            let target = if env_attr.optional {
+                Self::extract_option_inner(&ty).unwrap_or(&ty)
+            } else {
+                &ty
+            };
+
+            if Self::base64_decoder(target).is_none() {
+                return Err(SynError::new_spanned(
+                    target,
+                    "Field marked `base64` must have type `Vec<u8>` or `String`",
+                ));
+            }
+        }
+
+        // Items are split off the raw value, so the field must be a list
+        if sep_env.is_some() {
+            let target = if env_attr.optional {
+                Self::extract_option_inner(&ty).unwrap_or(&ty)
+            } else {
+                &ty
+            };
+
+            if Self::extract_vec_inner(target).is_none() {
+                return Err(SynError::new_spanned(
+                    target,
+                    "Field with `sep_env` must have type `Vec<T>`",
+                ));
+            }
+        }
+
+        // Only booleans have a numeric spelling to accept
+        if coerce_bool_numeric {
+            let target = if env_attr.optional {
+                Self::extract_option_inner(&ty).unwrap_or(&ty)
+            } else {
+                &ty
+            };
+
+            if !Self::is_bool_type(target) {
+                return Err(SynError::new_spanned(
+                    target,
+                    "Field marked `coerce_bool_numeric` must have type `bool` or `Option<bool>`",
+                ));
+            }
+        }
+
+        // Length bounds count the characters of the loaded string
+        if min_len.is_some() || max_len.is_some() {
+            let target = if env_attr.optional {
+                Self::extract_option_inner(&ty).unwrap_or(&ty)
+            } else {
+                &ty
+            };
+
+            if !Self::is_text_type(target) {
+                return Err(SynError::new_spanned(
+                    target,
+                    "`min_len`/`max_len` require a string type such as `String` or `SecretString`",
+                ));
+            }
+        }
+
+        // Patterns match the loaded string
+        if validate_regex.is_some() {
+            let target = if env_attr.optional {
+                Self::extract_option_inner(&ty).unwrap_or(&ty)
+            } else {
+                &ty
+            };
+
+            if !Self::is_text_type(target) {
+                return Err(SynError::new_spanned(
+                    target,
+                    "`validate_regex` requires a string type such as `String` or `SecretString`",
+                ));
+            }
+        }
+
+        // Masking replaces parts of the loaded string
+        if redact_pattern.is_some() {
+            let target = if env_attr.optional {
+                Self::extract_option_inner(&ty).unwrap_or(&ty)
+            } else {
+                &ty
+            };
+
+            if !Self::is_text_type(target) {
+                return Err(SynError::new_spanned(
+                    target,
+                    "`redact_pattern` requires a string type such as `String`",
+                ));
+            }
+        }
+
+        // Verification compares the loaded string byte by byte
+        if sensitive_eq {
+            let target = if env_attr.optional {
+                Self::extract_option_inner(&ty).unwrap_or(&ty)
+            } else {
+                &ty
+            };
+
+            if !Self::is_text_type(target) {
+                return Err(SynError::new_spanned(
+                    target,
+                    "`sensitive_eq` requires a string type such as `String` or `SecretString`",
+                ));
+            }
+        }
+
+        // Hashing a value that is already printed in the clear hides nothing
+        if log_fingerprint {
+            if !secret {
+                return Err(SynError::new_spanned(
+                    &ty,
+                    "`log_fingerprint` requires `secret` or a `SecretString` field",
+                ));
+            }
+
+            let target = if env_attr.optional {
+                Self::extract_option_inner(&ty).unwrap_or(&ty)
+            } else {
+                &ty
+            };
+
+            if !Self::is_text_type(target) {
+                return Err(SynError::new_spanned(
+                    target,
+                    "`log_fingerprint` requires a string type such as `String` or `SecretString`",
+                ));
+            }
+        }
+
+        // The generated Debug can only mask what it knows the shape of; a
+        // `format` value is deserialized whole, so it is always fully masked
+        if secret && format.is_none() {
+            let target = if env_attr.optional {
+                Self::extract_option_inner(&ty).unwrap_or(&ty)
+            } else {
+                &ty
+            };
+
+            if !Self::is_maskable_type(target) {
+                let name = quote!(#target).to_string().replace(' ', "");
+                return Err(SynError::new_spanned(
+                    target,
+                    format!(
+                        "`secret` only masks scalar, string and collection fields; \
+                         use `SecretBox<{name}>` (`secrecy` feature) to keep `{name}` secret"
+                    ),
+                ));
+            }
+        }
+
+        // Presence has no value to parse, and an unset var falls back to `false`
+        let default = if presence_bool {
+            if !Self::is_bool_type(&ty) {
+                return Err(SynError::new_spanned(
+                    &ty,
+                    "Field marked `presence_bool` must have type `bool`",
+                ));
+            }
+
+            Some(env_attr.default.unwrap_or_else(|| "false".to_string()))
+        } else {
+            env_attr.default
+        };
 
         // Choose the appropriate field generator based on attributes
         if env_attr.optional {
@@ -520,8 +1067,35 @@ impl FieldFactory {
                 profile,
                 format,
                 validate,
+                one_of,
+                allow_non_finite,
+                sensitive_log,
+                default_from,
+                from_file_var,
+                source_file,
+                base64,
+                pointer_access,
+                merge_env,
+                deserialize_with,
+                sep_env,
+                reload_immutable,
+                min_len,
+                max_len,
+                validate_regex,
+                example,
+                type_label,
+                trim,
+                trim_matches,
+                serde_rename,
+                sensitive_eq,
+                log_fingerprint,
+                after_parse,
+                redact_pattern,
+                distinguish_null,
+                when_profile,
+                coerce_bool_numeric,
             }))
-        } else if let Some(default) = env_attr.default {
+        } else if let Some(default) = default {
             // Default field
             Ok(Box::new(DefaultField {
                 name,
@@ -534,6 +1108,32 @@ impl FieldFactory {
                 profile,
                 format,
                 validate,
+                one_of,
+                allow_non_finite,
+                sensitive_log,
+                default_from,
+                from_file_var,
+                source_file,
+                base64,
+                pointer_access,
+                merge_env,
+                deserialize_with,
+                sep_env,
+                reload_immutable,
+                min_len,
+                max_len,
+                validate_regex,
+                example,
+                type_label,
+                presence_bool,
+                trim,
+                trim_matches,
+                serde_rename,
+                sensitive_eq,
+                log_fingerprint,
+                after_parse,
+                redact_pattern,
+                coerce_bool_numeric,
             }))
         } else {
             // Required field (the default)
@@ -547,6 +1147,31 @@ impl FieldFactory {
                 profile,
                 format,
                 validate,
+                one_of,
+                allow_non_finite,
+                sensitive_log,
+                default_from,
+                from_file_var,
+                source_file,
+                base64,
+                pointer_access,
+                merge_env,
+                deserialize_with,
+                sep_env,
+                reload_immutable,
+                min_len,
+                max_len,
+                validate_regex,
+                example,
+                type_label,
+                trim,
+                trim_matches,
+                serde_rename,
+                sensitive_eq,
+                log_fingerprint,
+                after_parse,
+                redact_pattern,
+                coerce_bool_numeric,
             }))
         }
     }
@@ -579,6 +1204,488 @@ impl FieldFactory {
         None
     }
 
+    /// Check if a type is `f32` or `f64`.
+    ///
+    /// Used to decide whether a field gets the default finiteness check.
+    pub fn is_float_type(ty: &Type) -> bool {
+        let Type::Path(type_path) = ty else {
+            return false;
+        };
+
+        type_path
+            .path
+            .get_ident()
+            .is_some_and(|ident| ident == "f32" || ident == "f64")
+    }
+
+    /// Check if a type is plain `bool`.
+    ///
+    /// Used to validate `presence_bool` and `coerce_bool_numeric` fields.
+    pub fn is_bool_type(ty: &Type) -> bool {
+        let Type::Path(type_path) = ty else {
+            return false;
+        };
+
+        type_path.path.is_ident("bool")
+    }
+
+    /// Returns the runtime decoder for a `base64` field of type `ty`.
+    ///
+    /// - `Vec<u8>` → `decode_base64` (raw bytes)
+    /// - `String` → `decode_base64_string` (bytes must be UTF-8)
+    /// - anything else → `None`
+    pub fn base64_decoder(ty: &Type) -> Option<QuoteStream> {
+        let Type::Path(type_path) = ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+
+        if segment.ident == "String" && segment.arguments.is_none() {
+            return Some(quote! { ::procenv::runtime::decode_base64_string });
+        }
+
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+
+        let is_bytes = segment.ident == "Vec"
+            && matches!(
+                args.args.first(),
+                Some(GenericArgument::Type(Type::Path(inner))) if inner.path.is_ident("u8")
+            );
+
+        is_bytes.then(|| quote! { ::procenv::runtime::decode_base64 })
+    }
+
+    /// Check if a type holds text that `min_len`/`max_len` and `validate_regex`
+    /// can check.
+    ///
+    /// - `String`, `Box<str>`, `Arc<str>`, `Cow<'static, str>` → `true`
+    /// - `u16`, `Vec<String>` → `false`
+    pub fn is_text_type(ty: &Type) -> bool {
+        let is_string = matches!(ty, Type::Path(type_path)
+        if type_path.path.segments.last().is_some_and(|segment| {
+            segment.ident == "String" && segment.arguments.is_none()
+        }));
+
+        is_string || Self::is_string_wrapper(ty)
+    }
+
+    /// Check if a `secret` field of type `ty` can be masked by the generated `Debug`.
+    ///
+    /// - `u16`, `bool`, `String`, `Arc<str>`, `PathBuf` → `true` (shown as `"<redacted>"`)
+    /// - `Vec<T>`, `HashMap<K, V>` and the other masked collections → `true`
+    /// - `Url`, `IpAddr`, user-defined types → `false`
+    pub fn is_maskable_type(ty: &Type) -> bool {
+        if Self::is_text_type(ty) {
+            return true;
+        }
+
+        let Type::Path(type_path) = ty else {
+            return false;
+        };
+        let Some(segment) = type_path.path.segments.last() else {
+            return false;
+        };
+
+        matches!(
+            segment.ident.to_string().as_str(),
+            "bool"
+                | "char"
+                | "i8"
+                | "i16"
+                | "i32"
+                | "i64"
+                | "i128"
+                | "isize"
+                | "u8"
+                | "u16"
+                | "u32"
+                | "u64"
+                | "u128"
+                | "usize"
+                | "f32"
+                | "f64"
+                | "PathBuf"
+                | "OsString"
+                | "Vec"
+                | "VecDeque"
+                | "HashSet"
+                | "BTreeSet"
+                | "HashMap"
+                | "BTreeMap"
+        )
+    }
+
+    /// Check if a type is a string wrapper built from a `String`.
+    ///
+    /// These types have no `FromStr` impl but convert from `String`:
+    ///
+    /// - `Box<str>`, `Arc<str>`, `Cow<'static, str>` → `true`
+    /// - `String`, `Box<u8>`, `Vec<str>` → `false`
+    pub fn is_string_wrapper(ty: &Type) -> bool {
+        let Type::Path(type_path) = ty else {
+            return false;
+        };
+
+        let Some(segment) = type_path.path.segments.last() else {
+            return false;
+        };
+
+        if !matches!(segment.ident.to_string().as_str(), "Box" | "Arc" | "Cow") {
+            return false;
+        }
+
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return false;
+        };
+
+        // Cow carries a lifetime first, so look for the type argument
+        args.args.iter().any(|arg| {
+            matches!(arg, GenericArgument::Type(Type::Path(inner)) if inner.path.is_ident("str"))
+        })
+    }
+
+    /// Join a compile-time `prefix` and var `name`.
+    ///
+    /// Mirrors `::procenv::runtime::join_prefix`: without a `separator`
+    /// this is plain concatenation, with one exactly one separator ends up
+    /// between them.
+    pub fn join_prefix(prefix: &str, name: &str, separator: Option<&str>) -> String {
+        match separator {
+            Some(sep) if !prefix.is_empty() => {
+                let prefix = prefix.strip_suffix(sep).unwrap_or(prefix);
+                let name = name.strip_prefix(sep).unwrap_or(name);
+                format!("{prefix}{sep}{name}")
+            }
+            _ => format!("{prefix}{name}"),
+        }
+    }
+
+    /// Generate a `String` expression joining `__external_prefix` and `var`.
+    ///
+    /// Needs to be expanded inside an `impl` of the struct, where
+    /// `Self::__PREFIX_SEPARATOR` is defined.
+    pub fn external_var_expr(var: &QuoteStream) -> QuoteStream {
+        quote! {
+            ::procenv::runtime::join_prefix(
+                __external_prefix.unwrap_or(""),
+                #var,
+                Self::__PREFIX_SEPARATOR,
+            )
+        }
+    }
+
+    /// Generate a `&str` expression naming the env var a field reads.
+    ///
+    /// This is `env_var` itself, or with `default_from` the fallback var
+    /// when `env_var` is unset and the fallback is set.
+    pub fn var_expr(env_var: &str, default_from: Option<&str>) -> QuoteStream {
+        default_from.map_or_else(
+            || quote! { #env_var },
+            |fallback| quote! { ::procenv::runtime::resolve_var(#env_var, #fallback) },
+        )
+    }
+
+    /// Generate a `String` expression naming the env var a field reads under
+    /// `__external_prefix`, resolving `default_from` like [`Self::var_expr`].
+    pub fn effective_var_expr(env_var: &str, default_from: Option<&str>) -> QuoteStream {
+        let prefixed = |var: &str| Self::external_var_expr(&quote! { #var });
+        let primary = prefixed(env_var);
+
+        match default_from {
+            Some(fallback) => {
+                let fallback = prefixed(fallback);
+                quote! { ::procenv::runtime::resolve_var(&#primary, &#fallback).to_owned() }
+            }
+            None => primary,
+        }
+    }
+
+    /// Generate a `Result<String, VarError>` expression reading `field`'s value
+    /// from the var `var` names.
+    ///
+    /// With `from_file_var`, the file named by that var is preferred through
+    /// `runtime::var_or_file`, which records read failures in `__errors`.
+    /// `prefixed` reads the file var under `__external_prefix`.
+    ///
+    /// The raw value is then adjusted for the field: `presence_bool` fields
+    /// read any set value as `"true"` (even one that is not valid UTF-8), and
+    /// `trim`/`trim_matches` fields go through `runtime::trim_value`. An unset
+    /// var stays `NotPresent` either way, so defaults still apply.
+    pub fn read_expr(var: &QuoteStream, field: &dyn FieldGenerator, prefixed: bool) -> QuoteStream {
+        let read = match field.file_var() {
+            None => quote! { ::procenv::runtime::var(#var) },
+            Some(file_var) if prefixed => {
+                let file_var = Self::external_var_expr(&quote! { #file_var });
+                quote! {
+                    ::procenv::runtime::var_or_file(#var, &#file_var, &mut __errors)
+                }
+            }
+            Some(file_var) => quote! {
+                ::procenv::runtime::var_or_file(#var, #file_var, &mut __errors)
+            },
+        };
+
+        if field.is_presence_bool() {
+            return quote! {
+                match #read {
+                    std::result::Result::Err(std::env::VarError::NotPresent) => {
+                        std::result::Result::Err(std::env::VarError::NotPresent)
+                    }
+                    _ => std::result::Result::Ok(std::string::String::from("true")),
+                }
+            };
+        }
+
+        let trim = field.trims_whitespace();
+        let trim_matches = field.trim_matches();
+
+        if !trim && trim_matches.is_none() {
+            return read;
+        }
+
+        let trim_matches = trim_matches.map_or_else(
+            || quote! { std::option::Option::None },
+            |chars| quote! { std::option::Option::Some(#chars) },
+        );
+
+        quote! {
+            (#read).map(|__raw| ::procenv::runtime::trim_value(__raw, #trim, #trim_matches))
+        }
+    }
+
+    /// Generate the `secret` argument passed to `Error` constructors.
+    ///
+    /// Secret fields defer to `runtime::redact` so `allow_unmask` structs can
+    /// be unmasked at runtime, and are never redacted in `unmask_in_debug`
+    /// debug builds. Needs to be expanded inside an `impl` of the struct,
+    /// where `Self::__ALLOW_UNMASK` and `Self::__UNMASKED` are defined.
+    pub fn redact_expr(secret: bool) -> QuoteStream {
+        if secret {
+            quote! { !Self::__UNMASKED && ::procenv::runtime::redact(true, Self::__ALLOW_UNMASK) }
+        } else {
+            quote! { false }
+        }
+    }
+
+    /// Generate the `expand_default` call for a `default` that contains `$`.
+    ///
+    /// Returns `None` for defaults without `$`, which are used verbatim, so
+    /// only defaults that may hold `${VAR}` references pay for expansion.
+    pub fn expand_default_expr(default: &str, var: &QuoteStream) -> Option<QuoteStream> {
+        default
+            .contains('$')
+            .then(|| quote! { ::procenv::runtime::expand_default(#var, #default) })
+    }
+
+    /// Generate the `String` a field falls back to when its var is unset.
+    ///
+    /// If expanding a `${VAR}` reference fails, the error is pushed and the
+    /// enclosing closure returns `None`.
+    pub fn default_string_expr(default: &str, var: &QuoteStream) -> QuoteStream {
+        Self::expand_default_expr(default, var).map_or_else(
+            || quote! { #default.to_string() },
+            |expand| {
+                quote! {
+                    match #expand {
+                        std::result::Result::Ok(v) => v,
+                        std::result::Result::Err(e) => {
+                            __errors.push(e);
+                            return std::option::Option::None;
+                        }
+                    }
+                }
+            },
+        )
+    }
+
+    /// Like [`Self::default_string_expr`], but as an `Option<String>` that
+    /// is `None` once a failed expansion has been pushed.
+    pub fn default_option_expr(default: &str, var: &QuoteStream) -> QuoteStream {
+        Self::expand_default_expr(default, var).map_or_else(
+            || quote! { std::option::Option::Some(#default.to_string()) },
+            |expand| {
+                quote! {
+                    match #expand {
+                        std::result::Result::Ok(v) => std::option::Option::Some(v),
+                        std::result::Result::Err(e) => {
+                            __errors.push(e);
+                            std::option::Option::None
+                        }
+                    }
+                }
+            },
+        )
+    }
+
+    /// Generate an `Option<&T>` borrowing an optional field's `value`.
+    ///
+    /// For `distinguish_null` fields an explicit `null` reads as `None`.
+    pub fn optional_ref(field: &dyn FieldGenerator, value: &QuoteStream) -> QuoteStream {
+        if field.distinguish_null() {
+            quote! { #value.as_ref().and_then(std::option::Option::as_ref) }
+        } else {
+            quote! { #value.as_ref() }
+        }
+    }
+
+    /// Human-readable name of a `format` for parse error messages.
+    pub fn format_label(format: &str) -> String {
+        if format == "auto" {
+            "JSON, YAML or TOML".to_string()
+        } else {
+            format.to_uppercase()
+        }
+    }
+
+    /// Human-readable name of a type for parse error messages.
+    ///
+    /// Paths into the standard library are shortened to their last segment,
+    /// so `std::time::Duration` reads as `Duration` and
+    /// `Vec<alloc::string::String>` as `Vec<String>`. Other paths are kept.
+    pub fn type_label(ty: &Type) -> String {
+        let rendered = quote!(#ty).to_string().replace(' ', "");
+        let mut label = String::with_capacity(rendered.len());
+        let mut rest = rendered.as_str();
+
+        while !rest.is_empty() {
+            // A path runs over identifier characters and `::` separators
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+                .unwrap_or(rest.len());
+            let (path, tail) = rest.split_at(end);
+
+            let root = path.trim_start_matches("::");
+            if ["std::", "alloc::", "core::"]
+                .iter()
+                .any(|prefix| root.starts_with(prefix))
+            {
+                label.push_str(root.rsplit("::").next().unwrap_or(root));
+            } else {
+                label.push_str(path);
+            }
+
+            // Copy the punctuation up to the next path unchanged
+            let next = tail
+                .find(|c: char| c.is_alphanumeric() || c == '_' || c == ':')
+                .unwrap_or(tail.len());
+            label.push_str(&tail[..next]);
+            rest = &tail[next..];
+        }
+
+        label
+    }
+
+    /// Generate the call deserializing `val` for a `format = "json"` field.
+    ///
+    /// `merge_env` fields first apply `<var>_<KEY>` overrides, where `var`
+    /// evaluates to the field's env var name.
+    pub fn json_expr(var: &QuoteStream, merge_env: bool) -> QuoteStream {
+        if merge_env {
+            quote! { ::procenv::runtime::merge_env_json(&val, #var) }
+        } else {
+            quote! { ::serde_json::from_str(&val) }
+        }
+    }
+
+    /// Wrap a format's deserialize `call` in the field's `deserialize_with`.
+    ///
+    /// `call` must be generic over its target, which then becomes
+    /// `serde_json::Value` for the function to deserialize from.
+    pub fn deserialize_with_expr(call: QuoteStream, deserialize_with: Option<&str>) -> QuoteStream {
+        let Some(path) = deserialize_with else {
+            return call;
+        };
+        let path: syn::Path =
+            syn::parse_str(path).expect("deserialize_with path validated during parsing");
+
+        quote! { ::procenv::runtime::deserialize_with(#call, #path) }
+    }
+
+    /// Generate an expression that turns raw `value` into `ty`.
+    ///
+    /// Like [`Self::parse_expr`], but `base64` fields decode instead of
+    /// parsing, `coerce_bool_numeric` fields also accept `0`/`1`, and
+    /// `sep_env` fields split into `Vec<T>` on the separator read from that
+    /// var. Evaluates to a `Result<ty, E>` where `E: Error`.
+    pub fn value_expr(
+        ty: &Type,
+        value: &QuoteStream,
+        base64: bool,
+        coerce_bool: bool,
+        sep_env: Option<&str>,
+    ) -> QuoteStream {
+        if let Some(sep_var) = sep_env {
+            let item = Self::extract_vec_inner(ty)
+                .expect("sep_env field types are validated in parse_field");
+
+            return quote! {
+                ::procenv::runtime::parse_list::<#item>(
+                    &#value,
+                    &::procenv::runtime::list_separator(#sep_var),
+                )
+            };
+        }
+
+        if coerce_bool {
+            return quote! { ::procenv::runtime::parse_bool_numeric(&#value) };
+        }
+
+        if !base64 {
+            return Self::parse_expr(ty, value);
+        }
+
+        let decode =
+            Self::base64_decoder(ty).expect("base64 field types are validated in parse_field");
+
+        quote! { #decode(&#value) }
+    }
+
+    /// Generate an expression that parses `value` (a `String` or `&str`) into `ty`.
+    ///
+    /// Evaluates to a `Result<ty, E>` where `E: Error`. Most types use
+    /// `FromStr`; string wrappers convert directly and cannot fail.
+    pub fn parse_expr(ty: &Type, value: &QuoteStream) -> QuoteStream {
+        if Self::is_string_wrapper(ty) {
+            quote! {
+                std::result::Result::<#ty, std::convert::Infallible>::Ok(
+                    <#ty as std::convert::From<std::string::String>>::from(
+                        std::string::ToString::to_string(&#value)
+                    )
+                )
+            }
+        } else {
+            quote! { #value.parse::<#ty>() }
+        }
+    }
+
+    /// Check if a type is `Vec<T>` and extract the item type `T`.
+    ///
+    /// - `Vec<u16>` → `Some(&Type::Path("u16"))`
+    /// - `Option<Vec<u16>>`, `String` → `None`
+    pub fn extract_vec_inner(ty: &Type) -> Option<&Type> {
+        let Type::Path(type_path) = ty else {
+            return None;
+        };
+
+        let segment = type_path.path.segments.last()?;
+
+        if segment.ident != "Vec" {
+            return None;
+        }
+
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+
+        let GenericArgument::Type(inner) = args.args.first()? else {
+            return None;
+        };
+
+        Some(inner)
+    }
+
     /// Check if a type is `Option<T>` and extract the inner type `T`.
     ///
     /// This is used to validate optional fields and to generate correct
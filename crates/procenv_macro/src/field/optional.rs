@@ -26,6 +26,13 @@
 //! - **Missing env var** → `None` (no error, this is expected)
 //! - **Invalid UTF-8** → `Error::InvalidUtf8` pushed
 //! - **Parse failure** → `Error::Parse` pushed
+//!
+//! # Explicit Nulls
+//!
+//! With `distinguish_null` the field is `Option<Option<T>>` and
+//! `inner_type` is `T`. Loaders still build an `Option<T>`, which the
+//! assignment wraps; only `from_config()` can produce `Some(None)`, for a
+//! key that is explicitly `null`.
 
 use proc_macro2::TokenStream as QuoteStream;
 use quote::{format_ident, quote};
@@ -33,7 +40,7 @@ use syn::{Ident, Type};
 
 use crate::parse::{CliAttr, ProfileAttr};
 
-use super::{EnvExampleEntry, FieldGenerator};
+use super::{EnvExampleEntry, FieldFactory, FieldGenerator};
 
 /// An optional field that becomes `None` when the environment variable is missing.
 ///
@@ -45,6 +52,10 @@ use super::{EnvExampleEntry, FieldGenerator};
 /// - If env var exists but fails to parse → `None` + `Error::Parse`
 /// - If env var is missing → `None` (no error!)
 /// - If env var contains invalid UTF-8 → `None` + `Error::InvalidUtf8`
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each boolean mirrors an independent flag option in the attribute"
+)]
 pub struct OptionalField {
     /// The struct field name
     pub name: Ident,
@@ -72,22 +83,111 @@ pub struct OptionalField {
     pub format: Option<String>,
 
     pub validate: Option<String>,
+
+    /// Allowlist of accepted values (`one_of = [...]`)
+    pub one_of: Option<Vec<String>>,
+
+    /// Accept NaN/infinity for float types (`allow_non_finite`)
+    pub allow_non_finite: bool,
+
+    /// Report loads to the struct's audit hook (`sensitive_log`)
+    pub sensitive_log: bool,
+
+    /// Env var to read when `env_var` is unset (`default_from`, prefixed)
+    pub default_from: Option<String>,
+
+    /// Env var naming a file to read the value from (`from_file_var`, prefixed)
+    pub from_file_var: Option<String>,
+
+    /// Config file and key supplying the field in `from_config()` (`file`, `file_key`)
+    pub source_file: Option<(String, Option<String>)>,
+
+    /// Decode the raw value from base64 (`base64`)
+    pub base64: bool,
+
+    /// Resolve `get_str` JSON pointers into this field (`pointer_access`)
+    pub pointer_access: bool,
+
+    /// Merge `<var>_<KEY>` overrides into the JSON value (`merge_env`)
+    pub merge_env: bool,
+
+    /// Serde-style function used instead of `Deserialize` (`deserialize_with`)
+    pub deserialize_with: Option<String>,
+
+    /// Env var holding the list item separator (`sep_env`)
+    pub sep_env: Option<String>,
+
+    /// Reject watch reloads that change this field (`reload_immutable`)
+    pub reload_immutable: bool,
+
+    /// Minimum string length in characters (`min_len`)
+    pub min_len: Option<usize>,
+
+    /// Maximum string length in characters (`max_len`)
+    pub max_len: Option<usize>,
+
+    /// Pattern the string value must match (`validate_regex`)
+    pub validate_regex: Option<String>,
+
+    /// Sample value shown in `.env.example` (`example`)
+    pub example: Option<String>,
+
+    /// Type name shown in parse errors (`type_label`)
+    pub type_label: Option<String>,
+
+    /// Strip surrounding whitespace from the raw value (`trim`)
+    pub trim: bool,
+
+    /// Characters stripped in matching pairs from the raw value (`trim_matches`)
+    pub trim_matches: Option<String>,
+
+    /// Config file key from `#[serde(rename)]`/`rename_all`, if not the field name
+    pub serde_rename: Option<String>,
+
+    /// Generate a constant-time `verify_<field>()` (`sensitive_eq`)
+    pub sensitive_eq: bool,
+
+    /// Generate a `<field>_fingerprint()` hash (`log_fingerprint`)
+    pub log_fingerprint: bool,
+
+    /// Function transforming the parsed value (`after_parse`)
+    pub after_parse: Option<String>,
+
+    /// Regex whose captured parts are masked in `Debug` and errors (`redact_pattern`)
+    pub redact_pattern: Option<String>,
+
+    /// Field is `Option<Option<T>>`, keeping file `null`s (`distinguish_null`)
+    pub distinguish_null: bool,
+
+    /// Profiles in which a missing value is an error (`when_profile`)
+    pub when_profile: Option<Vec<String>>,
+
+    /// Accept `0`/`1` as well as `true`/`false` (`coerce_bool_numeric`)
+    pub coerce_bool_numeric: bool,
 }
 
 impl FieldGenerator for OptionalField {
     fn generate_loader(&self) -> QuoteStream {
         let name = &self.name;
         let inner = &self.inner_type;
-        let env_var = &self.env_var;
-        let secret = self.secret;
-        let type_name = quote!(#inner).to_string();
+        let parse_val = FieldFactory::value_expr(
+            inner,
+            &quote! { val },
+            self.base64,
+            self.coerce_bool_numeric,
+            self.sep_env.as_deref(),
+        );
+        let env_var = FieldFactory::var_expr(&self.env_var, self.default_from.as_deref());
+        let read = FieldFactory::read_expr(&quote! { #env_var }, self, false);
+        let secret = FieldFactory::redact_expr(self.secret);
+        let type_name = self.type_name();
 
         quote! {
             // WARN: The local variable is Option<inner_type>, not Option<Option<inner_type>>
             // The assignment will use this directly since the field is already Option<T>
-            let #name: std::option::Option<#inner> = match std::env::var(#env_var) {
+            let #name: std::option::Option<#inner> = match #read {
                 std::result::Result::Ok(val) => {
-                    match val.parse::<#inner>() {
+                    match #parse_val {
                         std::result::Result::Ok(v) => std::option::Option::Some(v),
 
                         std::result::Result::Err(e) => {
@@ -107,8 +207,8 @@ impl FieldGenerator for OptionalField {
                 std::result::Result::Err(e) => {
                     // Only report error for invalid UTF-8
                     // Missing env var is expected for optional fields
-                    if let std::env::VarError::NotUnicode(_) = e {
-                        __errors.push(::procenv::Error::InvalidUtf8 { var: #env_var.to_string() });
+                    if let std::env::VarError::NotUnicode(__raw) = e {
+                        __errors.push(::procenv::Error::invalid_utf8(#env_var, &__raw, #secret));
                     }
 
                     // Return None for both NotPresent and NotUnicode
@@ -118,34 +218,35 @@ impl FieldGenerator for OptionalField {
         }
     }
 
-    #[expect(
-        clippy::too_many_lines,
-        reason = "proc-macro code generation inherently requires verbose quote! blocks"
-    )]
     fn generate_loader_with_external_prefix(&self) -> QuoteStream {
         let name = &self.name;
         let inner = &self.inner_type;
-        let base_var = &self.env_var;
-        let secret = self.secret;
-        let type_name = quote!(#inner).to_string();
+        let parse_val = FieldFactory::value_expr(
+            inner,
+            &quote! { val },
+            self.base64,
+            self.coerce_bool_numeric,
+            self.sep_env.as_deref(),
+        );
+        let effective_var =
+            FieldFactory::effective_var_expr(&self.env_var, self.default_from.as_deref());
+        let secret = FieldFactory::redact_expr(self.secret);
+        let type_name = self.type_name();
         let effective_var_ident = format_ident!("__{}_effective_var", name);
+        let read = FieldFactory::read_expr(&quote! { &#effective_var_ident }, self, true);
         let profile_used_ident = format_ident!("__{}_from_profile", name);
 
         // Check if this field has profile configuration
         self.profile.as_ref().map_or_else(|| quote! {
                 // Build effective env var name with external prefix
-            let #effective_var_ident: std::string::String = format!(
-                "{}{}",
-                __external_prefix.unwrap_or(""),
-                #base_var
-            );
+            let #effective_var_ident: std::string::String = #effective_var;
 
             // No profile for this field
             let #profile_used_ident: bool = false;
 
-            let #name: std::option::Option<#inner> = match std::env::var(&#effective_var_ident) {
+            let #name: std::option::Option<#inner> = match #read {
                 std::result::Result::Ok(val) => {
-                    match val.parse::<#inner>() {
+                    match #parse_val {
                         std::result::Result::Ok(v) => std::option::Option::Some(v),
 
                         std::result::Result::Err(e) => {
@@ -164,10 +265,8 @@ impl FieldGenerator for OptionalField {
 
                 std::result::Result::Err(e) => {
                     // Only report error for invalid UTF-8
-                    if let std::env::VarError::NotUnicode(_) = e {
-                        __errors.push(::procenv::Error::InvalidUtf8 {
-                            var: #effective_var_ident.clone(),
-                        });
+                    if let std::env::VarError::NotUnicode(__raw) = e {
+                        __errors.push(::procenv::Error::invalid_utf8(&#effective_var_ident, &__raw, #secret));
                     }
 
                     std::option::Option::None
@@ -187,11 +286,7 @@ impl FieldGenerator for OptionalField {
 
             quote! {
                 // Build effective env var name with external prefix
-                let #effective_var_ident: std::string::String = format!(
-                    "{}{}",
-                    __external_prefix.unwrap_or(""),
-                    #base_var
-                );
+                let #effective_var_ident: std::string::String = #effective_var;
 
                 // Determine profile default value (if profile matches)
                 let __profile_default: std::option::Option<&str> = match __profile.as_deref() {
@@ -201,7 +296,7 @@ impl FieldGenerator for OptionalField {
 
                 // Get value to parse: env var > profile default > None
                 let (__value_to_parse, #profile_used_ident): (std::option::Option<std::string::String>, bool) =
-                    match std::env::var(&#effective_var_ident) {
+                    match #read {
                         std::result::Result::Ok(val) => {
                             (std::option::Option::Some(val), false)
                         }
@@ -216,10 +311,8 @@ impl FieldGenerator for OptionalField {
                                 }
                             }
                         }
-                        std::result::Result::Err(std::env::VarError::NotUnicode(_)) => {
-                            __errors.push(::procenv::Error::InvalidUtf8 {
-                                var: #effective_var_ident.clone(),
-                            });
+                        std::result::Result::Err(std::env::VarError::NotUnicode(__raw)) => {
+                            __errors.push(::procenv::Error::invalid_utf8(&#effective_var_ident, &__raw, #secret));
                             (std::option::Option::None, false)
                         }
                     };
@@ -227,7 +320,7 @@ impl FieldGenerator for OptionalField {
                 // Parse the value if present
                 let #name: std::option::Option<#inner> = match __value_to_parse {
                     std::option::Option::Some(val) => {
-                        match val.parse::<#inner>() {
+                        match #parse_val {
                             std::result::Result::Ok(v) => std::option::Option::Some(v),
                             std::result::Result::Err(e) => {
                                 __errors.push(::procenv::Error::parse(
@@ -249,6 +342,12 @@ impl FieldGenerator for OptionalField {
 
     fn generate_assignment(&self) -> QuoteStream {
         let name = &self.name;
+
+        // Env vars have no null, so a set var is always `Some(Some(v))`
+        if self.distinguish_null {
+            return quote! { #name: #name.map(std::option::Option::Some) };
+        }
+
         // No unwrap needed - the field type is Option<T> and our local is Option<T>
         quote! { #name }
     }
@@ -258,8 +357,9 @@ impl FieldGenerator for OptionalField {
     }
 
     fn type_name(&self) -> String {
-        let inner = &self.inner_type;
-        quote!(#inner).to_string().replace(' ', "")
+        self.type_label
+            .clone()
+            .unwrap_or_else(|| FieldFactory::type_label(&self.inner_type))
     }
 
     fn is_secret(&self) -> bool {
@@ -275,13 +375,14 @@ impl FieldGenerator for OptionalField {
             default: None,
             secret: self.secret,
             type_hint: format!("Option<{}>", quote!(#inner).to_string().replace(' ', "")),
+            example: self.example.clone(),
         }]
     }
 
     fn generate_source_tracking(&self) -> QuoteStream {
         let field_name = &self.name;
         let field_name_str = field_name.to_string();
-        let env_var = &self.env_var;
+        let env_var = FieldFactory::var_expr(&self.env_var, self.default_from.as_deref());
 
         let source_ident = format_ident!("__{}_source", field_name);
 
@@ -297,7 +398,7 @@ impl FieldGenerator for OptionalField {
                     )
                 } else if #field_name.is_some() {
                     if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
-                        ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(None))
+                        ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(__dotenv_path.clone()))
                     } else {
                         ::procenv::ValueSource::new(#env_var, ::procenv::Source::Environment)
                     }
@@ -311,7 +412,7 @@ impl FieldGenerator for OptionalField {
             quote! {
                 let #source_ident = if #field_name.is_some() {
                     if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
-                        ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(None))
+                        ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(__dotenv_path.clone()))
                     } else {
                         ::procenv::ValueSource::new(#env_var, ::procenv::Source::Environment)
                     }
@@ -327,23 +428,39 @@ impl FieldGenerator for OptionalField {
     fn generate_format_loader(&self, format: &str) -> QuoteStream {
         let name = &self.name;
         let inner = &self.inner_type;
-        let env_var = &self.env_var;
-        let secret = self.secret;
+        let env_var = FieldFactory::var_expr(&self.env_var, self.default_from.as_deref());
+        let read = FieldFactory::read_expr(&quote! { #env_var }, self, false);
+        let secret = FieldFactory::redact_expr(self.secret);
+
+        // `deserialize_with` takes the parsed value as JSON, not the field type
+        let target = if self.deserialize_with.is_some() {
+            quote! { ::procenv::serde_json::Value }
+        } else {
+            quote! { #inner }
+        };
 
         let deserialize_call = match format {
-            "json" => quote! { ::serde_json::from_str::<#inner>(&val) },
+            "json" if self.merge_env => {
+                quote! { ::procenv::runtime::merge_env_json::<#target>(&val, #env_var) }
+            }
 
-            "toml" => quote! { ::toml::from_str::<#inner>(&val) },
+            "json" => quote! { ::serde_json::from_str::<#target>(&val) },
 
-            "yaml" => quote! { ::serde_saphyr::from_str::<#inner>(&val) },
+            "toml" => quote! { ::toml::from_str::<#target>(&val) },
+
+            "yaml" => quote! { ::serde_saphyr::from_str::<#target>(&val) },
+
+            "auto" => quote! { ::procenv::runtime::deserialize_auto::<#target>(&val) },
 
             _ => unreachable!("Format validated at parse time"),
         };
+        let deserialize_call =
+            FieldFactory::deserialize_with_expr(deserialize_call, self.deserialize_with.as_deref());
 
-        let format_name = format.to_uppercase();
+        let format_name = FieldFactory::format_label(format);
 
         quote! {
-            let #name: std::option::Option<#inner> = match std::env::var(#env_var) {
+            let #name: std::option::Option<#inner> = match #read {
                 std::result::Result::Ok(val) => {
                     match #deserialize_call {
                         std::result::Result::Ok(v) => std::option::Option::Some(v),
@@ -365,10 +482,8 @@ impl FieldGenerator for OptionalField {
                 std::result::Result::Err(e) => {
                     // Only report error invalid UTF-8
                     // Missing env var is expected for optional fields
-                    if let std::env::VarError::NotUnicode(_) = e {
-                        __errors.push(::procenv::Error::InvalidUtf8 {
-                            var: #env_var.to_string(),
-                        });
+                    if let std::env::VarError::NotUnicode(__raw) = e {
+                        __errors.push(::procenv::Error::invalid_utf8(#env_var, &__raw, #secret));
                     }
 
                     std::option::Option::None
@@ -389,6 +504,96 @@ impl FieldGenerator for OptionalField {
         self.profile.as_ref()
     }
 
+    fn sep_env(&self) -> Option<&str> {
+        self.sep_env.as_deref()
+    }
+
+    fn has_pointer_access(&self) -> bool {
+        self.pointer_access
+    }
+
+    fn merges_env(&self) -> bool {
+        self.merge_env
+    }
+
+    fn deserialize_with(&self) -> Option<&str> {
+        self.deserialize_with.as_deref()
+    }
+
+    fn is_reload_immutable(&self) -> bool {
+        self.reload_immutable
+    }
+
+    fn min_len(&self) -> Option<usize> {
+        self.min_len
+    }
+
+    fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    fn validate_regex(&self) -> Option<&str> {
+        self.validate_regex.as_deref()
+    }
+
+    fn is_base64(&self) -> bool {
+        self.base64
+    }
+
+    fn coerces_bool_numeric(&self) -> bool {
+        self.coerce_bool_numeric
+    }
+
+    fn default_from(&self) -> Option<&str> {
+        self.default_from.as_deref()
+    }
+
+    fn trims_whitespace(&self) -> bool {
+        self.trim
+    }
+
+    fn trim_matches(&self) -> Option<&str> {
+        self.trim_matches.as_deref()
+    }
+
+    fn serde_rename(&self) -> Option<&str> {
+        self.serde_rename.as_deref()
+    }
+
+    fn is_sensitive_eq(&self) -> bool {
+        self.sensitive_eq
+    }
+
+    fn is_log_fingerprint(&self) -> bool {
+        self.log_fingerprint
+    }
+
+    fn after_parse(&self) -> Option<&str> {
+        self.after_parse.as_deref()
+    }
+
+    fn redact_pattern(&self) -> Option<&str> {
+        self.redact_pattern.as_deref()
+    }
+
+    fn distinguish_null(&self) -> bool {
+        self.distinguish_null
+    }
+
+    fn when_profile(&self) -> Option<&[String]> {
+        self.when_profile.as_deref()
+    }
+
+    fn file_var(&self) -> Option<&str> {
+        self.from_file_var.as_deref()
+    }
+
+    fn source_file(&self) -> Option<(&str, Option<&str>)> {
+        self.source_file
+            .as_ref()
+            .map(|(path, key)| (path.as_str(), key.as_deref()))
+    }
+
     fn format_config(&self) -> Option<&str> {
         self.format.as_deref()
     }
@@ -397,6 +602,20 @@ impl FieldGenerator for OptionalField {
         self.validate.as_deref()
     }
 
+    fn one_of(&self) -> Option<&[String]> {
+        self.one_of.as_deref()
+    }
+
+    fn is_audited(&self) -> bool {
+        self.secret || self.sensitive_log
+    }
+
+    fn requires_finite(&self) -> bool {
+        !self.allow_non_finite
+            && self.format.is_none()
+            && FieldFactory::is_float_type(&self.inner_type)
+    }
+
     fn is_optional(&self) -> bool {
         true
     }
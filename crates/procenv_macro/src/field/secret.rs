@@ -39,7 +39,7 @@ use proc_macro2::TokenStream as QuoteStream;
 use quote::{format_ident, quote};
 use syn::{Ident, Type};
 
-use super::{EnvExampleEntry, FieldGenerator};
+use super::{EnvExampleEntry, FieldFactory, FieldGenerator};
 
 /// A field of type `SecretString` for secret string values.
 ///
@@ -57,17 +57,48 @@ pub struct SecretStringField {
     /// The environment variable name
     pub env_var: String,
 
+    /// Env var naming a file to read the value from (`from_file_var`, prefixed)
+    pub from_file_var: Option<String>,
+
     /// Doc comment from the field
     pub doc: Option<String>,
+
+    /// Minimum string length in characters (`min_len`)
+    pub min_len: Option<usize>,
+
+    /// Maximum string length in characters (`max_len`)
+    pub max_len: Option<usize>,
+
+    /// Pattern the string value must match (`validate_regex`)
+    pub validate_regex: Option<String>,
+
+    /// Sample value shown in `.env.example` (`example`)
+    pub example: Option<String>,
+
+    /// Strip surrounding whitespace from the raw value (`trim`)
+    pub trim: bool,
+
+    /// Characters stripped in matching pairs from the raw value (`trim_matches`)
+    pub trim_matches: Option<String>,
+
+    /// Config file key from `#[serde(rename)]`/`rename_all`, if not the field name
+    pub serde_rename: Option<String>,
+
+    /// Generate a constant-time `verify_<field>()` (`sensitive_eq`)
+    pub sensitive_eq: bool,
+
+    /// Generate a `<field>_fingerprint()` hash (`log_fingerprint`)
+    pub log_fingerprint: bool,
 }
 
 impl FieldGenerator for SecretStringField {
     fn generate_loader(&self) -> QuoteStream {
         let name = &self.name;
         let env_var = &self.env_var;
+        let read = FieldFactory::read_expr(&quote! { #env_var }, self, false);
 
         quote! {
-            let #name: std::option::Option<::procenv::SecretString> = match std::env::var(#env_var) {
+            let #name: std::option::Option<::procenv::SecretString> = match #read {
                 std::result::Result::Ok(val) => {
                     std::option::Option::Some(::procenv::SecretString::from(val))
                 }
@@ -78,10 +109,8 @@ impl FieldGenerator for SecretStringField {
                             __errors.push(::procenv::Error::missing(#env_var));
                         }
 
-                        std::env::VarError::NotUnicode(_) => {
-                            __errors.push(::procenv::Error::InvalidUtf8 {
-                                var: #env_var.to_string()
-                            });
+                        std::env::VarError::NotUnicode(__raw) => {
+                            __errors.push(::procenv::Error::invalid_utf8(#env_var, &__raw, true));
                         }
                     }
 
@@ -95,16 +124,14 @@ impl FieldGenerator for SecretStringField {
         let name = &self.name;
         let base_var = &self.env_var;
         let effective_var_ident = format_ident!("__{}_effective_var", name);
+        let effective_var = FieldFactory::external_var_expr(&quote! { #base_var });
+        let read = FieldFactory::read_expr(&quote! { &#effective_var_ident }, self, true);
 
         quote! {
             // Build effective env var name with external prefix
-            let #effective_var_ident: std::string::String = format!(
-                "{}{}",
-                __external_prefix.unwrap_or(""),
-                #base_var
-            );
+            let #effective_var_ident: std::string::String = #effective_var;
 
-            let #name: std::option::Option<::procenv::SecretString> = match std::env::var(&#effective_var_ident) {
+            let #name: std::option::Option<::procenv::SecretString> = match #read {
                 std::result::Result::Ok(val) => {
                     std::option::Option::Some(::procenv::SecretString::from(val))
                 }
@@ -115,10 +142,8 @@ impl FieldGenerator for SecretStringField {
                             __errors.push(::procenv::Error::missing(&#effective_var_ident));
                         }
 
-                        std::env::VarError::NotUnicode(_) => {
-                            __errors.push(::procenv::Error::InvalidUtf8 {
-                                var: #effective_var_ident.clone()
-                            });
+                        std::env::VarError::NotUnicode(__raw) => {
+                            __errors.push(::procenv::Error::invalid_utf8(&#effective_var_ident, &__raw, true));
                         }
                     }
 
@@ -158,6 +183,7 @@ impl FieldGenerator for SecretStringField {
             default: None,
             secret: true,
             type_hint: "SecretString".to_string(),
+            example: self.example.clone(),
         }]
     }
 
@@ -176,7 +202,7 @@ impl FieldGenerator for SecretStringField {
                         if __pre_dotenv_vars.contains(#env_var) {
                             ::procenv::Source::Environment
                         } else {
-                            ::procenv::Source::DotenvFile(None)
+                            ::procenv::Source::DotenvFile(__dotenv_path.clone())
                         }
                     } else {
                         ::procenv::Source::Environment
@@ -194,11 +220,47 @@ impl FieldGenerator for SecretStringField {
         Some(&self.env_var)
     }
 
+    fn trims_whitespace(&self) -> bool {
+        self.trim
+    }
+
+    fn trim_matches(&self) -> Option<&str> {
+        self.trim_matches.as_deref()
+    }
+
+    fn serde_rename(&self) -> Option<&str> {
+        self.serde_rename.as_deref()
+    }
+
+    fn is_sensitive_eq(&self) -> bool {
+        self.sensitive_eq
+    }
+
+    fn is_log_fingerprint(&self) -> bool {
+        self.log_fingerprint
+    }
+
+    fn file_var(&self) -> Option<&str> {
+        self.from_file_var.as_deref()
+    }
+
     fn field_type(&self) -> Option<&Type> {
         // SecretString doesn't have a stored Type since it's always SecretString
         // The extraction code handles this specially by checking is_secrecy_type()
         None
     }
+
+    fn min_len(&self) -> Option<usize> {
+        self.min_len
+    }
+
+    fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    fn validate_regex(&self) -> Option<&str> {
+        self.validate_regex.as_deref()
+    }
 }
 
 /// A field of type `SecretBox<T>` for secret values of any parseable type.
@@ -218,8 +280,26 @@ pub struct SecretBoxField {
     /// The environment variable name
     pub env_var: String,
 
+    /// Env var naming a file to read the value from (`from_file_var`, prefixed)
+    pub from_file_var: Option<String>,
+
     /// Doc comment from the field
     pub doc: Option<String>,
+
+    /// Sample value shown in `.env.example` (`example`)
+    pub example: Option<String>,
+
+    /// Type name shown in parse errors (`type_label`)
+    pub type_label: Option<String>,
+
+    /// Strip surrounding whitespace from the raw value (`trim`)
+    pub trim: bool,
+
+    /// Characters stripped in matching pairs from the raw value (`trim_matches`)
+    pub trim_matches: Option<String>,
+
+    /// Config file key from `#[serde(rename)]`/`rename_all`, if not the field name
+    pub serde_rename: Option<String>,
 }
 
 impl FieldGenerator for SecretBoxField {
@@ -227,10 +307,11 @@ impl FieldGenerator for SecretBoxField {
         let name = &self.name;
         let inner = &self.inner_type;
         let env_var = &self.env_var;
-        let type_name = quote!(#inner).to_string();
+        let read = FieldFactory::read_expr(&quote! { #env_var }, self, false);
+        let type_name = self.type_name();
 
         quote! {
-            let #name: std::option::Option<::procenv::SecretBox<#inner>> = match std::env::var(#env_var) {
+            let #name: std::option::Option<::procenv::SecretBox<#inner>> = match #read {
                 std::result::Result::Ok(val) => {
                     match val.parse::<#inner>() {
                         std::result::Result::Ok(v) => {
@@ -258,10 +339,8 @@ impl FieldGenerator for SecretBoxField {
                             __errors.push(::procenv::Error::missing(#env_var));
                         }
 
-                        std::env::VarError::NotUnicode(_) => {
-                            __errors.push(::procenv::Error::InvalidUtf8 {
-                                var: #env_var.to_string()
-                            });
+                        std::env::VarError::NotUnicode(__raw) => {
+                            __errors.push(::procenv::Error::invalid_utf8(#env_var, &__raw, true));
                         }
                     }
 
@@ -275,18 +354,16 @@ impl FieldGenerator for SecretBoxField {
         let name = &self.name;
         let inner = &self.inner_type;
         let base_var = &self.env_var;
-        let type_name = quote!(#inner).to_string();
+        let type_name = self.type_name();
         let effective_var_ident = format_ident!("__{}_effective_var", name);
+        let effective_var = FieldFactory::external_var_expr(&quote! { #base_var });
+        let read = FieldFactory::read_expr(&quote! { &#effective_var_ident }, self, true);
 
         quote! {
             // Build effective env var name with external prefix
-            let #effective_var_ident: std::string::String = format!(
-                "{}{}",
-                __external_prefix.unwrap_or(""),
-                #base_var
-            );
+            let #effective_var_ident: std::string::String = #effective_var;
 
-            let #name: std::option::Option<::procenv::SecretBox<#inner>> = match std::env::var(&#effective_var_ident) {
+            let #name: std::option::Option<::procenv::SecretBox<#inner>> = match #read {
                 std::result::Result::Ok(val) => {
                     match val.parse::<#inner>() {
                         std::result::Result::Ok(v) => {
@@ -313,10 +390,8 @@ impl FieldGenerator for SecretBoxField {
                             __errors.push(::procenv::Error::missing(&#effective_var_ident));
                         }
 
-                        std::env::VarError::NotUnicode(_) => {
-                            __errors.push(::procenv::Error::InvalidUtf8 {
-                                var: #effective_var_ident.clone()
-                            });
+                        std::env::VarError::NotUnicode(__raw) => {
+                            __errors.push(::procenv::Error::invalid_utf8(&#effective_var_ident, &__raw, true));
                         }
                     }
 
@@ -337,8 +412,9 @@ impl FieldGenerator for SecretBoxField {
     }
 
     fn type_name(&self) -> String {
-        let inner = &self.inner_type;
-        quote!(#inner).to_string().replace(' ', "")
+        self.type_label
+            .clone()
+            .unwrap_or_else(|| FieldFactory::type_label(&self.inner_type))
     }
 
     fn is_secret(&self) -> bool {
@@ -358,6 +434,7 @@ impl FieldGenerator for SecretBoxField {
             default: None,
             secret: true,
             type_hint: format!("SecretBox<{}>", quote!(#inner).to_string().replace(' ', "")),
+            example: self.example.clone(),
         }]
     }
 
@@ -376,7 +453,7 @@ impl FieldGenerator for SecretBoxField {
                         if __pre_dotenv_vars.contains(#env_var) {
                             ::procenv::Source::Environment
                         } else {
-                            ::procenv::Source::DotenvFile(None)
+                            ::procenv::Source::DotenvFile(__dotenv_path.clone())
                         }
                     } else {
                         ::procenv::Source::Environment
@@ -394,6 +471,22 @@ impl FieldGenerator for SecretBoxField {
         Some(&self.env_var)
     }
 
+    fn trims_whitespace(&self) -> bool {
+        self.trim
+    }
+
+    fn trim_matches(&self) -> Option<&str> {
+        self.trim_matches.as_deref()
+    }
+
+    fn serde_rename(&self) -> Option<&str> {
+        self.serde_rename.as_deref()
+    }
+
+    fn file_var(&self) -> Option<&str> {
+        self.from_file_var.as_deref()
+    }
+
     fn field_type(&self) -> Option<&Type> {
         Some(&self.inner_type)
     }
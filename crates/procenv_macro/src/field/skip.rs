@@ -0,0 +1,99 @@
+//! Skipped field implementation.
+//!
+//! This module provides [`SkipField`], the code generator for fields that
+//! are not configuration at all, such as a `PhantomData<T>` marker on a
+//! generic config struct.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! #[derive(EnvConfig)]
+//! struct Config<T> {
+//!     #[env(var = "PORT")]
+//!     port: u16,
+//!
+//!     #[env(skip)]
+//!     _marker: PhantomData<T>,
+//! }
+//! ```
+//!
+//! # Generated Code Pattern
+//!
+//! No loader, source entry, example line or key is generated. The field is
+//! initialized in the struct literal with:
+//! ```rust,ignore
+//! _marker: std::default::Default::default()
+//! ```
+
+use proc_macro2::TokenStream as QuoteStream;
+use quote::quote;
+use syn::{Ident, Type};
+
+use super::{EnvExampleEntry, FieldGenerator};
+
+/// A field excluded from loading with `#[env(skip)]`.
+///
+/// ## Behavior
+/// - Never reads the environment, files or CLI arguments
+/// - Always initialized with `Default::default()`
+/// - Omitted from `keys()`, `env_example()`, source tracking and `Debug`
+pub struct SkipField {
+    /// The struct field name
+    pub name: Ident,
+
+    /// The field's type (must implement `Default`)
+    pub ty: Type,
+}
+
+impl FieldGenerator for SkipField {
+    fn generate_loader(&self) -> QuoteStream {
+        quote! {}
+    }
+
+    fn generate_assignment(&self) -> QuoteStream {
+        let name = &self.name;
+
+        quote! { #name: std::default::Default::default() }
+    }
+
+    fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    fn type_name(&self) -> String {
+        let ty = &self.ty;
+        quote!(#ty).to_string().replace(' ', "")
+    }
+
+    fn is_secret(&self) -> bool {
+        false
+    }
+
+    fn example_entries(&self) -> Vec<EnvExampleEntry> {
+        vec![]
+    }
+
+    fn generate_source_tracking(&self) -> QuoteStream {
+        quote! {}
+    }
+
+    fn generate_loader_with_external_prefix(&self) -> QuoteStream {
+        quote! {}
+    }
+
+    fn env_var_name(&self) -> Option<&str> {
+        None // Skipped fields are never loaded
+    }
+
+    fn field_name(&self) -> Option<&Ident> {
+        None // Skipped fields don't participate in key access
+    }
+
+    fn field_type(&self) -> Option<&Type> {
+        None // Not loaded, so no type-driven code is generated
+    }
+
+    fn is_skip(&self) -> bool {
+        true
+    }
+}
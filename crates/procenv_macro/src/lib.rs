@@ -22,9 +22,16 @@
 //! | Method | Description |
 //! |--------|-------------|
 //! | `from_env()` | Load from environment variables |
+//! | `from_dotenv_str(content)` | Load from `.env`-formatted text, without touching the environment |
 //! | `from_env_with_sources()` | Load with source attribution |
+//! | `from_env_verbose()` | Load, printing `field <- source (VAR)` per field to stderr |
+//! | `from_env_formatted()` | Load, returning errors rendered per `error_format` |
+//! | `from_env_with_prefix(prefix)` | Load with a runtime prefix on every var name |
+//! | `apply_env(&mut self)` | Overwrite only fields whose env vars are set |
+//! | `builder()` | Construct in code, with defaults filled in by `build()` |
 //! | `from_config()` | Load from files + env (requires `file` feature) |
 //! | `from_args()` | Load from CLI + env (requires CLI attributes) |
+//! | `from_arg_matches(&matches)` | Load from an existing clap `Command`'s matches + env |
 //! | `env_example()` | Generate `.env.example` template |
 //!
 //! It also generates a custom `Debug` implementation that masks secret fields.
@@ -54,14 +61,46 @@ mod parse;
 /// | Attribute | Description |
 /// |-----------|-------------|
 /// | `var = "NAME"` | Environment variable name (required) |
-/// | `default = "value"` | Default value if env var is missing |
+/// | `default = "value"` | Default value if env var is missing; `${VAR}` references expand when it is used (`$$` is a literal `$`) |
+/// | `default_from = "OTHER_VAR"` | Read another env var (also prefixed) if this one is missing |
+/// | `from_file_var = "VAR_FILE"` | Read the value from the file that env var names (also prefixed), trimming trailing newlines |
+/// | `file = "license.json"` | In `from_config()`, read this field from its own required file, over the struct's files but below env vars |
+/// | `file_key = "key"` | Dotted key read from the field's `file` (default: the field's config key) |
 /// | `optional` | Field is `Option<T>`, becomes `None` if missing |
-/// | `secret` | Masks value in Debug output and error messages |
+/// | `when_profile = "prod"` | Field is `Option<T>`, required only while a listed profile (a name or a list) is active; needs `profile_env` |
+/// | `distinguish_null` | With `optional` on an `Option<Option<T>>`, a config file `null` loads as `Some(None)` instead of `None` |
+/// | `secret` | Masks value in Debug output and error messages (scalars, strings, collections and `format` fields; use `SecretBox<T>` for other types) |
 /// | `no_prefix` | Skip struct-level prefix for this field |
 /// | `flatten` | Embed a nested config struct |
-/// | `format = "json"` | Parse value as JSON/TOML/YAML |
+/// | `prefix = "DB_"` | Prefix for a `flatten` field's nested vars |
+/// | `inherit_prefix` | Apply the struct prefix to a `flatten` field's nested vars |
+/// | `serde` | With `flatten`, deserialize a `Deserialize`-only type from the config files in `from_config()`; its fields cannot be set by env vars, and env-only loaders report an error |
+/// | `skip` | Not configuration (e.g. `PhantomData<T>`); set to `Default::default()` |
+/// | `format = "json"` | Parse value as JSON/TOML/YAML (`"auto"`: first of JSON, YAML, TOML that deserializes) |
+/// | `one_of = ["a", "b"]` | Reject values outside the allowlist |
+/// | `allow_non_finite` | Accept NaN/infinity in `f32`/`f64` fields (rejected by default) |
+/// | `sensitive_log` | Report loads to the struct's `audit` hook (implied by `secret`) |
+/// | `base64` | Decode the value (standard or URL-safe) into `Vec<u8>` or `String` |
+/// | `pointer_access` | With `format = "json"`, `get_str("field/a/b")` returns the leaf at `/a/b` (type must be `Serialize`) |
+/// | `merge_env` | With `format = "json"`, `VAR_KEY` (and `VAR_KEY_SUBKEY`) env vars override keys of the parsed object; overrides win (needs the `serde` feature) |
+/// | `deserialize_with = "path"` | With `format`, deserialize through a serde-style `fn(D) -> Result<T, D::Error>` instead of `T: Deserialize`; it receives the parsed value (needs the `serde` feature) |
+/// | `sep_env = "LIST_SEP"` | Parse a `Vec<T>` field by splitting on the separator read from `LIST_SEP` (default `,`) |
+/// | `reload_immutable` | Reject watch reloads that change this field (type must be `PartialEq`) |
+/// | `min_len = 32` / `max_len = 64` | Bound a `String`/`SecretString` value's length in characters |
+/// | `validate_regex = "^v\\d+$"` | Require a `String`/`SecretString` value to match a regex (requires the `regex` feature) |
+/// | `redact_pattern = ":([^:@]+)@"` | Show a `String` value in `Debug` and errors with each regex capture group replaced by `<redacted>` (requires the `regex` feature) |
+/// | `example = "value"` | Sample value written to `env_example()` when there is no default; documentation only, and secrets show a `<secret>` placeholder instead |
+/// | `type_label = "duration"` | Name the expected type in parse errors (`expected duration`); by default `std::`/`alloc::`/`core::` paths are shortened to the type name |
+/// | `presence_bool` | A `bool` field is `true` whenever the var is set, even to `""` or `0`, and `default` (or `false`) when unset |
+/// | `coerce_bool_numeric` | A `bool` (or `Option<bool>`) field also accepts `1`/`0`, from env strings and from numbers in config files |
+/// | `trim` | Strip surrounding whitespace from the env value before parsing |
+/// | `trim_matches = "\"'"` | Strip matching pairs of these characters from both ends of the env value (after `trim`), so `"8080"` parses as `8080`; unbalanced quotes are kept |
+/// | `sensitive_eq` | Generate `verify_<field>(&self, candidate: &str) -> bool`, a constant-time comparison for string and `SecretString` fields |
+/// | `log_fingerprint` | Generate `<field>_fingerprint(&self)`, the first 12 hex digits of the SHA-256 of a `secret` string or `SecretString` field (requires the `fingerprint` feature) |
+/// | `after_parse = "fn"` | Run `fn(T) -> Result<T, E>` on the parsed (or default) value before `one_of`/length checks; an `Err` is reported as `procenv::after_parse` |
 /// | `arg = "name"` | CLI argument name (enables `from_args()`) |
 /// | `short = 'n'` | CLI short flag (requires `arg`) |
+/// | `count` | CLI flag counts occurrences, e.g. `-vvv` (requires `arg`) |
 ///
 /// # Struct Attributes
 ///
@@ -70,12 +109,32 @@ mod parse;
 /// | Attribute | Description |
 /// |-----------|-------------|
 /// | `prefix = "APP_"` | Prefix all env var names |
+/// | `prefix_env = "APP_NAMESPACE"` | Read a runtime prefix from `APP_NAMESPACE` in `from_env()`, `from_env_with_sources()`, `from_env_timed()`, `from_args()` and `apply_env()` |
+/// | `env_prefix_separator = "_"` | Join prefixes and var names with exactly one `_` |
 /// | `dotenv` | Load `.env` file automatically |
 /// | `dotenv = ".env.local"` | Load specific dotenv file |
+/// | `dotenv_required = ".env.prod"` | Load a dotenv file, failing with `Error::DotenvMissing` if absent |
 /// | `file = "config.toml"` | Load required config file |
 /// | `file_optional = "..."` | Load optional config file |
+/// | `embedded = "config.default.toml"` | Compile a config file into the binary |
+/// | `files_override_env` | Config files take precedence over env vars |
+/// | `schema_version = 2` | `from_config()` fails if the config's `version` key is above 2 |
+/// | `version_key = "meta.version"` | Dotted key `schema_version` reads (default `"version"`) |
+/// | `missing_version = 1` | Version assumed for a config without the key (default `1`) |
+/// | `from_json_var = "APP_CONFIG"` | Generate `from_json_env()` loading the struct from one JSON env var |
 /// | `profile_env = "APP_ENV"` | Env var for profile selection |
 /// | `profiles = ["dev", "prod"]` | Valid profile names |
+/// | `default_profile = "dev"` | Profile used when the profile env var is unset |
+/// | `strict_profiles` | Compile error unless every `#[profile(...)]` covers exactly `profiles` |
+/// | `timed` | Generate `from_env_timed()` (requires `timing` feature) |
+/// | `no_accumulate` | `from_env()` stops at the first error instead of collecting all |
+/// | `allow_empty_struct` | Load as `Default::default()` when none of the struct's vars are set (requires `Default`) |
+/// | `derive_default` | Generate `impl Default` using each field's `default`, `None` for optional fields and `Default` for flattened ones; a field without a default is a compile error |
+/// | `error_format = "json"` | Render `from_env_formatted()` errors as miette JSON instead of the default `"pretty"` report |
+/// | `audit = "audit_fn"` | Call `audit_fn(var, present)` on each secret field load |
+/// | `allow_unmask` | Let `PROCENV_UNMASK=1` show `secret` fields in `Debug` and errors (never enable in production) |
+/// | `unmask_in_debug` | Show `secret` fields in `Debug` and errors when built with `debug_assertions`; release builds always mask |
+/// | `case_insensitive_env` | Env var names match ignoring ASCII case (`from_config()` file layers excepted) |
 ///
 /// # Profile Attributes
 ///
@@ -154,9 +213,20 @@ mod parse;
 ///
 /// - `from_env()` - Load from environment variables
 /// - `from_env_with_sources()` - Load with source attribution
+/// - `from_env_verbose()` - Load, printing each field's source to stderr
+/// - `from_env_formatted()` - Load, returning errors pre-rendered as a `String`
+/// - `from_env_with_prefix(prefix)` - Load with a runtime prefix (e.g. per tenant)
+/// - `apply_env(&mut self)` - Override an existing instance's fields whose vars are set
+/// - `builder()` - A `<Struct>Builder` with a setter per field, without env vars
 /// - `from_config()` - Load from files + env (when files configured)
 /// - `from_config_with_sources()` - Layered loading with sources
+/// - `config_merged_value()` - Raw merged value behind `from_config()`
+/// - `config_loaded_files()` - Config files `from_config()` actually loads
+/// - `from_json_env()` - Load from one JSON env var + env (with `#[env_config(from_json_var = "...")]`)
 /// - `from_args()` - Load from CLI + env (when `arg` attributes present)
+/// - `from_arg_matches(&matches)` / `clap_args()` - Load from, and register args on, your own clap `Command`
+/// - `from_env_timed()` - Load with per-field timings (with `#[env_config(timed)]`)
+/// - `active_profile()` - The profile loading resolves to (with `profile_env`)
 /// - `env_example()` - Generate `.env.example` template
 /// - Custom `Debug` impl with secret masking
 #[proc_macro_derive(EnvConfig, attributes(env, env_config, profile))]
@@ -51,12 +51,15 @@
 //! #[env(var = "ENV_VAR_NAME", secret, default = "key")]  // Combinable
 //! #[env(flatten)]                                        // Nested config
 //! #[env(flatten, prefix = "DB_")]                        // Nested with prefix
+//! #[env(flatten, inherit_prefix)]                        // Nested with struct prefix
+//! #[env(flatten, serde)]                                 // Nested `Deserialize` type, files only
 //! ```
 //!
 //! ## Struct-level attributes
 //!
 //! ```ignore
 //! #[env_config(prefix = "APP_")]                         // Prefix for all vars
+//! #[env_config(prefix_env = "APP_NAMESPACE")]            // Prefix read at load time
 //! #[env_config(dotenv)]                                  // Load .env file
 //! #[env_config(file = "config.toml")]                    // Load config file
 //! #[env_config(profile_env = "APP_ENV", profiles = ["dev", "prod"])]
@@ -75,11 +78,12 @@
 
 use std::collections::{HashMap, HashSet};
 
+use syn::ext::IdentExt;
 use syn::meta::ParseNestedMeta;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{
-    Attribute, DeriveInput, Error as SynError, Expr, ExprLit, Field, Lit, LitStr, Meta,
+    Attribute, DeriveInput, Error as SynError, Expr, ExprLit, Field, Lit, LitInt, LitStr, Meta,
     Result as SynResult, bracketed,
 };
 
@@ -141,6 +145,7 @@ pub fn extract_doc_comment(field: &Field) -> Option<String> {
 ///
 /// - `Env(EnvAttr)` - Regular field loaded from an environment variable
 /// - `Flatten` - Nested config struct whose fields are loaded recursively
+/// - `Skip` - Field not loaded at all, initialized with `Default::default()`
 ///
 /// # Example
 ///
@@ -148,12 +153,25 @@ pub fn extract_doc_comment(field: &Field) -> Option<String> {
 /// #[env(var = "DATABASE_URL")]  // → FieldConfig::Env(...)
 /// database_url: String,
 ///
-/// #[env(flatten)]               // → FieldConfig::Flatten { prefix: None }
+/// #[env(flatten)]               // → FieldConfig::Flatten { prefix: None, .. }
 /// database: DatabaseConfig,
 ///
-/// #[env(flatten, prefix = "DB_")]  // → FieldConfig::Flatten { prefix: Some("DB_") }
+/// #[env(flatten, prefix = "DB_")]  // → FieldConfig::Flatten { prefix: Some("DB_"), .. }
 /// database: DatabaseConfig,
+///
+/// #[env(flatten, inherit_prefix)]  // → FieldConfig::Flatten { inherit_prefix: true, .. }
+/// database: DatabaseConfig,
+///
+/// #[env(flatten, serde)]        // → FieldConfig::Flatten { serde: true, .. }
+/// tls: external::TlsConfig,
+///
+/// #[env(skip)]                  // → FieldConfig::Skip
+/// _marker: PhantomData<T>,
 /// ```
+#[expect(
+    clippy::large_enum_variant,
+    reason = "built once per field at expansion time and immediately destructured"
+)]
 pub enum FieldConfig {
     /// Regular field loaded from an environment variable.
     ///
@@ -170,7 +188,20 @@ pub enum FieldConfig {
         /// Optional prefix to prepend to nested field env var names.
         /// Combined with any parent prefix and the struct's own prefix.
         prefix: Option<String>,
+
+        /// Whether the parent struct's prefix applies even without `prefix`.
+        inherit_prefix: bool,
+
+        /// Whether the nested type is only `Deserialize` rather than `EnvConfig`.
+        /// It is then read from config files only, never from env vars.
+        serde: bool,
     },
+
+    /// Field that is not configuration, such as a `PhantomData` marker.
+    ///
+    /// It is never read from the environment and is initialized with
+    /// `Default::default()`.
+    Skip,
 }
 
 /// CLI argument configuration for a field.
@@ -192,6 +223,9 @@ pub struct CliAttr {
 
     /// Optional short flag (e.g., `'p'` for `-p`).
     pub short: Option<char>,
+
+    /// Whether the argument counts occurrences (e.g., `-vvv` → `3`).
+    pub count: bool,
 }
 
 /// Profile-specific default values for a field.
@@ -228,12 +262,45 @@ pub struct ProfileAttr {
 /// |--------|------|-------------|
 /// | `var` | Required | Environment variable name |
 /// | `default` | Optional | Default value if env var missing |
+/// | `default_from` | Optional | Env var to read when `var` is missing |
+/// | `from_file_var` | Optional | Env var naming a file to read the value from |
+/// | `file` | Optional | Config file supplying this field in `from_config()` |
+/// | `file_key` | Optional | Dotted key read from `file` (default: the field's config key) |
 /// | `optional` | Flag | Field becomes `Option<T>` |
 /// | `secret` | Flag | Mask value in output |
 /// | `no_prefix` | Flag | Skip struct-level prefix |
 /// | `arg` | Optional | CLI argument name |
 /// | `short` | Optional | CLI short flag |
+/// | `count` | Flag | CLI flag counts occurrences (`-vvv`) |
 /// | `format` | Optional | Serde format (json/toml/yaml) |
+/// | `one_of` | Optional | Allowlist of accepted string values |
+/// | `allow_non_finite` | Flag | Accept NaN/infinity in float fields |
+/// | `sensitive_log` | Flag | Report loads to the struct's `audit` hook |
+/// | `base64` | Flag | Decode the value from base64 (`Vec<u8>` or `String`) |
+/// | `pointer_access` | Flag | `get_str("field/a/b")` reads into a JSON field |
+/// | `merge_env` | Flag | `VAR_KEY` env vars override keys of a JSON field |
+/// | `deserialize_with` | Optional | Serde-style function deserializing a `format` field |
+/// | `sep_env` | Optional | Env var holding the `Vec<T>` item separator (default `,`) |
+/// | `reload_immutable` | Flag | Watch reloads that change this field are rejected (type must be `PartialEq`) |
+/// | `min_len` | Optional | Minimum length in characters of a string value |
+/// | `max_len` | Optional | Maximum length in characters of a string value |
+/// | `validate_regex` | Optional | Regex a string value must match |
+/// | `example` | Optional | Sample value shown in `.env.example` |
+/// | `type_label` | Optional | Type name shown in parse errors |
+/// | `presence_bool` | Flag | `bool` is `true` whenever the var is set, whatever its value |
+/// | `trim` | Flag | Strip surrounding whitespace from the raw value |
+/// | `trim_matches` | Optional | Strip matching pairs of these characters from both ends |
+/// | `sensitive_eq` | Flag | Generate a constant-time `verify_<field>(&self, candidate)` |
+/// | `log_fingerprint` | Flag | Generate `<field>_fingerprint(&self)`, a truncated SHA-256 of the secret |
+/// | `after_parse` | Optional | Function transforming the parsed value, `fn(T) -> Result<T, E>` |
+/// | `distinguish_null` | Flag | With `optional`, keep an explicit file `null` as `Some(None)` in an `Option<Option<T>>` |
+/// | `redact_pattern` | Optional | Regex whose capture groups are masked in `Debug` and errors |
+/// | `when_profile` | Optional | Profile (or list) in which an `Option<T>` field is required |
+/// | `coerce_bool_numeric` | Flag | `bool` accepts `0`/`1` as well as `true`/`false`, from files and env |
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each boolean mirrors an independent flag option in the attribute"
+)]
 pub struct EnvAttr {
     /// The name of the environment variable to read (required).
     /// Example: `var = "DATABASE_URL"` → `var_name = "DATABASE_URL"`
@@ -243,13 +310,29 @@ pub struct EnvAttr {
     /// Example: `default = "8080"` → `default = Some("8080")`
     pub default: Option<String>,
 
+    /// Env var to read when `var_name` is unset (the struct prefix applies).
+    /// Example: `default_from = "DATA_DIR"` → `default_from = Some("DATA_DIR")`
+    pub default_from: Option<String>,
+
+    /// Env var naming a file holding the value, preferred over `var_name`.
+    /// Example: `from_file_var = "DB_PASSWORD_FILE"` → `from_file_var = Some("DB_PASSWORD_FILE")`
+    pub from_file_var: Option<String>,
+
+    /// Config file supplying this field in `from_config()`, over the struct's files.
+    /// Example: `file = "license.json"` → `file = Some("license.json")`
+    pub file: Option<String>,
+
+    /// Dotted key read from `file`; the field's config key when unset.
+    /// Example: `file_key = "key"` → `file_key = Some("key")`
+    pub file_key: Option<String>,
+
     /// Whether this field is optional (field type must be `Option<T>`).
     /// If true, missing env var results in `None` instead of error.
     pub optional: bool,
 
     /// Whether this field contains sensitive data.
-    /// If true, the value is masked as "***" in Debug output and
-    /// "<redacted>" in error messages.
+    /// If true, the value is masked as "<redacted>" in Debug output
+    /// and error messages.
     pub secret: bool,
 
     /// Skip the struct-level prefix for this field
@@ -265,12 +348,115 @@ pub struct EnvAttr {
 
     /// Deserialization format for structured data (Phase 17)
     /// Example: `#[env(var = "HOSTS", format = "json")]`
-    /// Supported: "json", "toml", "yaml"
+    /// Supported: "json", "toml", "yaml", "auto" (first of JSON, YAML, TOML that works)
+    ///
+    /// The struct prefix still applies to `var_name` (unless `no_prefix` is
+    /// set); it never applies to keys inside the deserialized value.
     pub format: Option<String>,
 
     /// Custom validation function name.
     /// Example: `#[env(var = "...", validate = "my_validator")]`
     pub validate: Option<String>,
+
+    /// Allowlist of accepted values.
+    /// Example: `#[env(var = "TIER", one_of = ["free", "pro"])]`
+    pub one_of: Option<Vec<String>>,
+
+    /// Accept NaN and infinity for `f32`/`f64` fields.
+    /// Example: `#[env(var = "RATE", allow_non_finite)]`
+    pub allow_non_finite: bool,
+
+    /// Report loads of this field to the struct-level `audit` hook.
+    /// Example: `#[env(var = "TENANT_ID", sensitive_log)]`
+    ///
+    /// Secret fields are always audited; this opts in non-secret fields.
+    pub sensitive_log: bool,
+
+    /// Decode the raw value from base64 before assigning it.
+    /// Example: `#[env(var = "TLS_CERT_B64", base64)]`
+    pub base64: bool,
+
+    /// Let `get_str` follow a JSON pointer into this `format = "json"` field.
+    /// Example: `#[env(var = "FLAGS", format = "json", pointer_access)]`
+    pub pointer_access: bool,
+
+    /// Let `<var>_<KEY>` env vars override keys of this `format = "json"` field.
+    /// Example: `#[env(var = "FEATURES", format = "json", merge_env)]`
+    pub merge_env: bool,
+
+    /// Serde-style function deserializing this `format` field.
+    /// Example: `#[env(var = "TIMEOUT", format = "json", deserialize_with = "parse_timeout")]`
+    pub deserialize_with: Option<String>,
+
+    /// Env var naming the separator for a `Vec<T>` field (read verbatim).
+    /// Example: `sep_env = "LIST_SEP"` → `sep_env = Some("LIST_SEP")`
+    pub sep_env: Option<String>,
+
+    /// Reject watch reloads that change this field's value.
+    /// The field's type must implement `PartialEq`, which the generated
+    /// `reload_immutable_changed()` compares values with.
+    /// Example: `#[env(var = "PORT", reload_immutable)]`
+    pub reload_immutable: bool,
+
+    /// Minimum accepted length of a string value, in characters.
+    /// Example: `min_len = 32` → `min_len = Some(32)`
+    pub min_len: Option<usize>,
+
+    /// Maximum accepted length of a string value, in characters.
+    /// Example: `max_len = 64` → `max_len = Some(64)`
+    pub max_len: Option<usize>,
+
+    /// Regex a string value must match, compiled on first use.
+    /// Example: `validate_regex = "^v\\d+$"` → `validate_regex = Some("^v\\d+$")`
+    pub validate_regex: Option<String>,
+
+    /// Sample value for `.env.example`; documentation only, never loaded.
+    /// Example: `example = "postgres://localhost/db"` → `example = Some("postgres://localhost/db")`
+    pub example: Option<String>,
+
+    /// Type name shown in parse errors instead of the Rust type.
+    /// Example: `type_label = "duration"` → `type_label = Some("duration")`
+    pub type_label: Option<String>,
+
+    /// Treat the var being set, with any value, as `true`.
+    /// Example: `#[env(var = "DEBUG", presence_bool)]`
+    pub presence_bool: bool,
+
+    /// Strip surrounding whitespace from the raw value before parsing.
+    /// Example: `#[env(var = "PORT", trim)]`
+    pub trim: bool,
+
+    /// Characters stripped in matching pairs from both ends of the raw value.
+    /// Example: `trim_matches = "\"'"` → `trim_matches = Some("\"'")`
+    pub trim_matches: Option<String>,
+
+    /// Generate a constant-time `verify_<field>()` for a string field.
+    /// Example: `#[env(var = "ADMIN_TOKEN", secret, sensitive_eq)]`
+    pub sensitive_eq: bool,
+
+    /// Generate a `<field>_fingerprint()` hash of a secret string field.
+    /// Example: `#[env(var = "API_KEY", secret, log_fingerprint)]`
+    pub log_fingerprint: bool,
+
+    /// Function run on the successfully parsed value, returning it transformed.
+    /// Example: `after_parse = "normalize_url"` → `after_parse = Some("normalize_url")`
+    pub after_parse: Option<String>,
+
+    /// Keep an explicit `null` in config files apart from an absent key.
+    /// Example: `#[env(var = "PROXY", optional, distinguish_null)]`
+    pub distinguish_null: bool,
+
+    /// Regex whose capture groups are masked, leaving the rest of the value visible.
+    /// Example: `redact_pattern = ":([^@]+)@"` → `redact_pattern = Some(":([^@]+)@")`
+    pub redact_pattern: Option<String>,
+
+    /// Profiles in which the field is required; it is optional in all others.
+    /// Example: `when_profile = ["staging", "prod"]` → `when_profile = Some(vec!["staging", "prod"])`
+    pub when_profile: Option<Vec<String>>,
+
+    /// Accept `0`/`1` numbers and strings for a `bool` field.
+    /// Example: `#[env(var = "DEBUG", coerce_bool_numeric)]`
+    pub coerce_bool_numeric: bool,
 }
 
 /// Builder pattern parser for `#[env(...)]` attributes.
@@ -322,11 +508,32 @@ pub struct EnvAttr {
 /// # Validation Rules
 ///
 /// The parser enforces these semantic constraints:
-/// - `var` is required for non-flatten fields
+/// - `var` is required for non-flatten, non-skip fields
 /// - `default` and `optional` are mutually exclusive (different "missing" semantics)
 /// - `short` requires `arg` to be set (short flag needs a long name)
-/// - `flatten` can only be combined with `prefix` (all other options are field-specific)
+/// - `count` requires `arg` and cannot be combined with `format` or `one_of`
+/// - `flatten` can only be combined with `prefix` and `inherit_prefix` (all other
+///   options are field-specific)
+/// - `inherit_prefix` requires `flatten`
+/// - `serde` requires `flatten` and cannot be combined with `prefix` or
+///   `inherit_prefix`
+/// - `skip` cannot be combined with any other option
 /// - `format` must be one of: `json`, `toml`, `yaml`
+/// - `one_of` cannot be combined with `format`, and `default` must be in the list
+/// - `base64` cannot be combined with `format`
+/// - `pointer_access` requires `format = "json"`
+/// - `merge_env` requires `format = "json"`
+/// - `deserialize_with` requires `format`
+/// - `sep_env` cannot be combined with `format`, `base64`, `one_of` or `count`
+/// - `min_len` cannot exceed `max_len`, and neither works with `format`
+/// - `validate_regex` cannot be combined with `format`
+/// - `redact_pattern` cannot be combined with `format` or `secret`
+/// - `when_profile` makes the field optional, so it cannot be combined with `default`
+/// - `coerce_bool_numeric` cannot be combined with `format`
+/// - `type_label` cannot be combined with `format`
+/// - `presence_bool` cannot be combined with `optional`, `format`, `base64`,
+///   `sep_env`, `one_of`, `count`, `trim`, `trim_matches` or `coerce_bool_numeric`
+/// - `trim_matches` cannot be empty
 #[derive(Default)]
 #[expect(
     clippy::struct_excessive_bools,
@@ -339,6 +546,18 @@ pub struct Parser {
     /// Accumulated default value (from `default = "..."`).
     default: Option<String>,
 
+    /// Fallback env var (from `default_from = "..."`).
+    default_from: Option<String>,
+
+    /// Env var naming a file to read the value from (from `from_file_var = "..."`).
+    from_file_var: Option<String>,
+
+    /// Per-field config file (from `file = "..."`).
+    file: Option<String>,
+
+    /// Key read from the per-field config file (from `file_key = "..."`).
+    file_key: Option<String>,
+
     /// Whether `optional` flag was seen.
     optional: bool,
 
@@ -362,18 +581,104 @@ pub struct Parser {
     /// Whether this is a flattened nested config.
     flatten: bool,
 
+    /// Whether `skip` flag was seen.
+    skip: bool,
+
     /// Prefix for flatten fields (from `prefix = "..."`).
     /// Only valid when `flatten` is true.
     flatten_prefix: Option<String>,
 
+    /// Whether a flatten field inherits the struct prefix (from `inherit_prefix`).
+    /// Only valid when `flatten` is true.
+    inherit_prefix: bool,
+
+    /// Whether a flatten field is deserialized with serde (from `serde`).
+    /// Only valid when `flatten` is true.
+    serde: bool,
+
     /// CLI long argument name (from `arg = "..."`).
     arg_long: Option<String>,
 
     /// CLI short flag (from `short = 'x'`).
     arg_short: Option<char>,
 
+    /// Whether the CLI flag counts occurrences (from `count`).
+    arg_count: bool,
+
     /// Custom validation function (from `validate = "..."`).
     validate: Option<String>,
+
+    /// Allowlist of accepted values (from `one_of = ["a", "b"]`).
+    one_of: Option<Vec<String>>,
+
+    /// Whether `allow_non_finite` flag was seen.
+    allow_non_finite: bool,
+
+    /// Whether `sensitive_log` flag was seen.
+    sensitive_log: bool,
+
+    /// Whether `base64` flag was seen.
+    base64: bool,
+
+    /// Whether `pointer_access` flag was seen.
+    pointer_access: bool,
+
+    /// Whether `merge_env` flag was seen.
+    merge_env: bool,
+
+    /// Deserializer function path (from `deserialize_with = "..."`).
+    deserialize_with: Option<String>,
+
+    /// List separator env var (from `sep_env = "..."`).
+    sep_env: Option<String>,
+
+    /// Whether `reload_immutable` flag was seen.
+    reload_immutable: bool,
+
+    /// Minimum string length (from `min_len = N`).
+    min_len: Option<usize>,
+
+    /// Maximum string length (from `max_len = N`).
+    max_len: Option<usize>,
+
+    /// Pattern the value must match (from `validate_regex = "..."`).
+    validate_regex: Option<String>,
+
+    /// Sample value for `.env.example` (from `example = "..."`).
+    example: Option<String>,
+
+    /// Type name for parse errors (from `type_label = "..."`).
+    type_label: Option<String>,
+
+    /// Whether `presence_bool` flag was seen.
+    presence_bool: bool,
+
+    /// Whether `trim` flag was seen.
+    trim: bool,
+
+    /// Characters to strip in pairs (from `trim_matches = "..."`).
+    trim_matches: Option<String>,
+
+    /// Whether `sensitive_eq` flag was seen.
+    sensitive_eq: bool,
+
+    /// Whether `log_fingerprint` flag was seen.
+    log_fingerprint: bool,
+
+    /// Post-parse hook path (from `after_parse = "..."`).
+    after_parse: Option<String>,
+
+    /// Whether `distinguish_null` flag was seen.
+    distinguish_null: bool,
+
+    /// Pattern of the value's secret parts (from `redact_pattern = "..."`).
+    redact_pattern: Option<String>,
+
+    /// Profiles requiring the field (from `when_profile = "prod"` or a list).
+    when_profile: Option<Vec<String>>,
+
+    /// Whether `coerce_bool_numeric` flag was seen.
+    coerce_bool_numeric: bool,
 }
 
 impl Parser {
@@ -398,6 +703,10 @@ impl Parser {
         clippy::needless_pass_by_value,
         reason = "ParseNestedMeta is passed by value per syn's parse_nested_meta callback signature"
     )]
+    #[expect(
+        clippy::too_many_lines,
+        reason = "one match arm per supported option keeps parsing in a single place"
+    )]
     fn parse_meta(&mut self, meta: ParseNestedMeta) -> SynResult<()> {
         // Extract the option name (e.g., "var", "default", "optional", "secret")
         let ident = meta
@@ -412,15 +721,47 @@ impl Parser {
         let key: &'static str = match name.as_str() {
             "var" => "var",
             "default" => "default",
+            "default_from" => "default_from",
+            "from_file_var" => "from_file_var",
+            "file" => "file",
+            "file_key" => "file_key",
             "optional" => "optional",
             "secret" => "secret",
             "no_prefix" => "no_prefix",
             "flatten" => "flatten",
+            "skip" => "skip",
             "prefix" => "prefix",
+            "inherit_prefix" => "inherit_prefix",
+            "serde" => "serde",
             "arg" => "arg",
             "short" => "short",
+            "count" => "count",
             "format" => "format",
             "validate" => "validate",
+            "one_of" => "one_of",
+            "allow_non_finite" => "allow_non_finite",
+            "sensitive_log" => "sensitive_log",
+            "base64" => "base64",
+            "pointer_access" => "pointer_access",
+            "merge_env" => "merge_env",
+            "deserialize_with" => "deserialize_with",
+            "sep_env" => "sep_env",
+            "reload_immutable" => "reload_immutable",
+            "min_len" => "min_len",
+            "max_len" => "max_len",
+            "validate_regex" => "validate_regex",
+            "example" => "example",
+            "type_label" => "type_label",
+            "presence_bool" => "presence_bool",
+            "trim" => "trim",
+            "trim_matches" => "trim_matches",
+            "sensitive_eq" => "sensitive_eq",
+            "log_fingerprint" => "log_fingerprint",
+            "after_parse" => "after_parse",
+            "distinguish_null" => "distinguish_null",
+            "redact_pattern" => "redact_pattern",
+            "when_profile" => "when_profile",
+            "coerce_bool_numeric" => "coerce_bool_numeric",
             _ => return Err(meta.error(format!("Unknown option `{name}`"))),
         };
 
@@ -445,6 +786,30 @@ impl Parser {
                 self.default = Some(lit_str.value());
             }
 
+            // default_from = "OTHER_VAR" - env var to read when `var` is unset
+            "default_from" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                self.default_from = Some(lit_str.value());
+            }
+
+            // from_file_var = "VAR_FILE" - env var naming a file holding the value
+            "from_file_var" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                self.from_file_var = Some(lit_str.value());
+            }
+
+            // file = "license.json" - config file supplying this field
+            "file" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                self.file = Some(lit_str.value());
+            }
+
+            // file_key = "key" - dotted key read from `file`
+            "file_key" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                self.file_key = Some(lit_str.value());
+            }
+
             // optional - just a flag, no value
             "optional" => {
                 self.optional = true;
@@ -463,12 +828,27 @@ impl Parser {
                 self.flatten = true;
             }
 
+            // skip - not configuration, initialized with Default::default()
+            "skip" => {
+                self.skip = true;
+            }
+
             // prefix = "DB_" - prefix for flatten fields
             "prefix" => {
                 let lit_str: LitStr = meta.value()?.parse()?;
                 self.flatten_prefix = Some(lit_str.value());
             }
 
+            // inherit_prefix - flatten field uses the struct prefix
+            "inherit_prefix" => {
+                self.inherit_prefix = true;
+            }
+
+            // serde - flatten field deserialized from config files only
+            "serde" => {
+                self.serde = true;
+            }
+
             // arg = "port" - CLI long argument name
             "arg" => {
                 let lit_str: LitStr = meta.value()?.parse()?;
@@ -481,16 +861,21 @@ impl Parser {
                 self.arg_short = Some(lit_char.value());
             }
 
+            // count - CLI flag counts occurrences, e.g. `-vvv` → 3
+            "count" => {
+                self.arg_count = true;
+            }
+
             // format = "json" - parse env value as structured data
             "format" => {
                 let lit_str: LitStr = meta.value()?.parse()?;
                 let format_val = lit_str.value();
                 // Validate format is supported
                 match format_val.as_str() {
-                    "json" | "toml" | "yaml" => {}
+                    "json" | "toml" | "yaml" | "auto" => {}
                     _ => {
                         return Err(meta.error(format!(
-                            "Unknown format '{format_val}'. Supported: json, toml, yaml"
+                            "Unknown format '{format_val}'. Supported: json, toml, yaml, auto"
                         )));
                     }
                 }
@@ -503,6 +888,168 @@ impl Parser {
                 self.validate = Some(lit_str.value());
             }
 
+            // one_of = ["a", "b"] - allowlist of accepted values
+            "one_of" => {
+                let input = meta.value()?;
+                let content;
+                bracketed!(content in input);
+
+                let values: Punctuated<LitStr, Comma> = Punctuated::parse_terminated(&content)?;
+                let values: Vec<_> = values.iter().map(LitStr::value).collect();
+
+                if values.is_empty() {
+                    return Err(meta.error("one_of list cannot be empty"));
+                }
+
+                self.one_of = Some(values);
+            }
+
+            // allow_non_finite - accept NaN/inf for float fields
+            "allow_non_finite" => {
+                self.allow_non_finite = true;
+            }
+
+            // sensitive_log - report loads to the struct's audit hook
+            "sensitive_log" => {
+                self.sensitive_log = true;
+            }
+
+            // base64 - decode the value before assigning it
+            "base64" => {
+                self.base64 = true;
+            }
+
+            // pointer_access - get_str can address leaves of a JSON field
+            "pointer_access" => {
+                self.pointer_access = true;
+            }
+
+            // merge_env - `<var>_<KEY>` vars override keys of a JSON field
+            "merge_env" => {
+                self.merge_env = true;
+            }
+
+            // deserialize_with = "path::to_fn" - custom serde deserializer
+            "deserialize_with" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                lit_str.parse::<syn::Path>()?;
+                self.deserialize_with = Some(lit_str.value());
+            }
+
+            // sep_env = "LIST_SEP" - env var holding the list separator
+            "sep_env" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                self.sep_env = Some(lit_str.value());
+            }
+
+            // reload_immutable - watch reloads must not change this field
+            "reload_immutable" => {
+                self.reload_immutable = true;
+            }
+
+            // min_len = 32 / max_len = 64 - string length bounds
+            "min_len" => {
+                let lit_int: LitInt = meta.value()?.parse()?;
+                self.min_len = Some(lit_int.base10_parse()?);
+            }
+
+            "max_len" => {
+                let lit_int: LitInt = meta.value()?.parse()?;
+                self.max_len = Some(lit_int.base10_parse()?);
+            }
+
+            // validate_regex = "^v\\d+$" - pattern checked at load time
+            "validate_regex" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                self.validate_regex = Some(lit_str.value());
+            }
+
+            // example = "..." - sample value for .env.example only
+            "example" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                self.example = Some(lit_str.value());
+            }
+
+            // type_label = "duration" - type name used in parse errors
+            "type_label" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                self.type_label = Some(lit_str.value());
+            }
+
+            // presence_bool - any set value means `true`
+            "presence_bool" => {
+                self.presence_bool = true;
+            }
+
+            // trim - strip surrounding whitespace before parsing
+            "trim" => {
+                self.trim = true;
+            }
+
+            // trim_matches = "\"'" - strip matching quote pairs before parsing
+            "trim_matches" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+
+                if lit_str.value().is_empty() {
+                    return Err(meta.error("trim_matches cannot be empty"));
+                }
+
+                self.trim_matches = Some(lit_str.value());
+            }
+
+            // sensitive_eq - generate a constant-time `verify_<field>()`
+            "sensitive_eq" => {
+                self.sensitive_eq = true;
+            }
+
+            // log_fingerprint - generate a `<field>_fingerprint()` hash
+            "log_fingerprint" => {
+                self.log_fingerprint = true;
+            }
+
+            // after_parse = "path::to_fn" - transform the parsed value
+            "after_parse" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                lit_str.parse::<syn::Path>()?;
+                self.after_parse = Some(lit_str.value());
+            }
+
+            // distinguish_null - tri-state `Option<Option<T>>` for file nulls
+            "distinguish_null" => {
+                self.distinguish_null = true;
+            }
+
+            // redact_pattern = ":([^@]+)@" - captured parts are masked
+            "redact_pattern" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                self.redact_pattern = Some(lit_str.value());
+            }
+
+            // when_profile = "prod" or ["staging", "prod"] - required only there
+            "when_profile" => {
+                let input = meta.value()?;
+                let profiles = if input.peek(LitStr) {
+                    vec![input.parse::<LitStr>()?.value()]
+                } else {
+                    let content;
+                    bracketed!(content in input);
+
+                    let values: Punctuated<LitStr, Comma> = Punctuated::parse_terminated(&content)?;
+                    values.iter().map(LitStr::value).collect()
+                };
+
+                if profiles.is_empty() {
+                    return Err(meta.error("when_profile list cannot be empty"));
+                }
+
+                self.when_profile = Some(profiles);
+            }
+
+            // coerce_bool_numeric - bool accepts 0/1 from any source
+            "coerce_bool_numeric" => {
+                self.coerce_bool_numeric = true;
+            }
+
             // We validated the key above
             _ => unreachable!(),
         }
@@ -522,6 +1069,22 @@ impl Parser {
     ///   - `default`: "use this value if env var is missing"
     ///   - `optional`: "be `None` if env var is missing"
     /// - `short` requires `arg` to be set (can't have `-p` without `--port`)
+    /// - `count` requires `arg` and a numeric value, so no `format` or `one_of`
+    /// - `one_of` cannot be combined with `format` (values are compared as strings)
+    /// - `base64` cannot be combined with `format` (the decoded value is not text)
+    /// - `pointer_access` requires `format = "json"` (pointers address JSON values)
+    /// - `merge_env` requires `format = "json"` (overrides merge into JSON objects)
+    /// - `deserialize_with` requires `format` (it replaces the serde `Deserialize`)
+    /// - `sep_env` only splits plain `FromStr` items, so no `format`, `base64`,
+    ///   `one_of` or `count`
+    /// - `min_len` must not exceed `max_len`, and length bounds need a plain
+    ///   string value, so no `format`
+    /// - `validate_regex` matches the plain string value, so no `format`
+    /// - `type_label` names the parsed type, while `format` errors name the format
+    /// - `coerce_bool_numeric` replaces the `FromStr` parse, so no `format`
+    /// - `presence_bool` ignores the value, so nothing that reads or cleans it
+    ///   applies, and an unset var is already `false`, so no `optional`
+    /// - a `default` used with `one_of` must itself be in the list
     ///
     /// # CLI Construction Optimization
     ///
@@ -539,6 +1102,10 @@ impl Parser {
     ///
     /// This is more concise and expresses intent clearly: "if there's a long arg,
     /// create a `CliAttr`; otherwise None".
+    #[expect(
+        clippy::too_many_lines,
+        reason = "one check per option combination keeps validation in a single place"
+    )]
     fn build(self, attr: &Attribute) -> SynResult<EnvAttr> {
         // Ensure `var` was provided - this is the only required option
         let var_name = self
@@ -556,6 +1123,21 @@ impl Parser {
             ));
         }
 
+        // Outside the listed profiles the field is optional, so nothing falls back to a default
+        if self.when_profile.is_some() && self.default.is_some() {
+            return Err(SynError::new_spanned(
+                attr,
+                "Cannot use both `default` and `when_profile` on the same field",
+            ));
+        }
+
+        if self.distinguish_null && !self.optional {
+            return Err(SynError::new_spanned(
+                attr,
+                "`distinguish_null` requires `optional`",
+            ));
+        }
+
         // Validate CLI attributes: short flag requires long name
         // (clap convention: can't have just `-p`, need `--port` too)
         if self.arg_short.is_some() && self.arg_long.is_none() {
@@ -565,23 +1147,230 @@ impl Parser {
             ));
         }
 
+        // A count flag only has meaning on the CLI and always yields a number
+        if self.arg_count {
+            if self.arg_long.is_none() {
+                return Err(SynError::new_spanned(
+                    attr,
+                    "`count` requires `arg` to be set",
+                ));
+            }
+
+            if self.format.is_some() || self.one_of.is_some() {
+                return Err(SynError::new_spanned(
+                    attr,
+                    "`count` cannot be combined with `format` or `one_of`",
+                ));
+            }
+        }
+
+        // `one_of` compares the raw string value, which has no meaning for
+        // structured (json/toml/yaml) fields.
+        if let Some(ref allowed) = self.one_of {
+            if self.format.is_some() {
+                return Err(SynError::new_spanned(
+                    attr,
+                    "Cannot use both `one_of` and `format` on the same field",
+                ));
+            }
+
+            // Catch a default that could never pass the runtime check
+            if let Some(ref default) = self.default
+                && !allowed.contains(default)
+            {
+                return Err(SynError::new_spanned(
+                    attr,
+                    format!(
+                        "default value `{default}` is not in `one_of` list: {}",
+                        allowed.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        // Decoded bytes have nothing for a serde format to work on
+        if self.base64 && self.format.is_some() {
+            return Err(SynError::new_spanned(
+                attr,
+                "Cannot use both `base64` and `format` on the same field",
+            ));
+        }
+
+        // Pointers are resolved against the field re-serialized as JSON
+        if self.pointer_access && self.format.as_deref() != Some("json") {
+            return Err(SynError::new_spanned(
+                attr,
+                "`pointer_access` requires `format = \"json\"`",
+            ));
+        }
+
+        // Overrides are merged into the parsed JSON before deserializing
+        if self.merge_env && self.format.as_deref() != Some("json") {
+            return Err(SynError::new_spanned(
+                attr,
+                "`merge_env` requires `format = \"json\"`",
+            ));
+        }
+
+        // The function runs in place of the format's `Deserialize` impl
+        if self.deserialize_with.is_some() && self.format.is_none() {
+            return Err(SynError::new_spanned(
+                attr,
+                "`deserialize_with` requires `format`",
+            ));
+        }
+
+        // The value is read as a plain bool, not deserialized
+        if self.coerce_bool_numeric && self.format.is_some() {
+            return Err(SynError::new_spanned(
+                attr,
+                "Cannot use both `coerce_bool_numeric` and `format` on the same field",
+            ));
+        }
+
+        // List items are split and parsed with FromStr, nothing else applies
+        if self.sep_env.is_some() {
+            let conflicting = [
+                self.format.is_some().then_some("format"),
+                self.base64.then_some("base64"),
+                self.one_of.is_some().then_some("one_of"),
+                self.arg_count.then_some("count"),
+            ]
+            .into_iter()
+            .flatten()
+            .next();
+
+            if let Some(option) = conflicting {
+                return Err(SynError::new_spanned(
+                    attr,
+                    format!("Cannot use both `sep_env` and `{option}` on the same field"),
+                ));
+            }
+        }
+
+        // Length bounds apply to the raw string, not a deserialized structure
+        if let (Some(min), Some(max)) = (self.min_len, self.max_len)
+            && min > max
+        {
+            return Err(SynError::new_spanned(
+                attr,
+                format!("`min_len` ({min}) cannot be greater than `max_len` ({max})"),
+            ));
+        }
+
+        if (self.min_len.is_some() || self.max_len.is_some()) && self.format.is_some() {
+            return Err(SynError::new_spanned(
+                attr,
+                "Cannot use `min_len`/`max_len` with `format`",
+            ));
+        }
+
+        if self.validate_regex.is_some() && self.format.is_some() {
+            return Err(SynError::new_spanned(
+                attr,
+                "Cannot use `validate_regex` with `format`",
+            ));
+        }
+
+        if self.redact_pattern.is_some() && self.format.is_some() {
+            return Err(SynError::new_spanned(
+                attr,
+                "Cannot use `redact_pattern` with `format`",
+            ));
+        }
+
+        if self.file_key.is_some() && self.file.is_none() {
+            return Err(SynError::new_spanned(attr, "`file_key` requires `file`"));
+        }
+
+        // A secret is masked whole, so there is nothing left to show
+        if self.redact_pattern.is_some() && self.secret {
+            return Err(SynError::new_spanned(
+                attr,
+                "Cannot use both `redact_pattern` and `secret` on the same field",
+            ));
+        }
+
+        if self.type_label.is_some() && self.format.is_some() {
+            return Err(SynError::new_spanned(
+                attr,
+                "Cannot use `type_label` with `format`",
+            ));
+        }
+
+        // Only presence is checked, so options that interpret the value have nothing to work on
+        if self.presence_bool {
+            let conflicting = [
+                self.optional.then_some("optional"),
+                self.when_profile.is_some().then_some("when_profile"),
+                self.format.is_some().then_some("format"),
+                self.base64.then_some("base64"),
+                self.sep_env.is_some().then_some("sep_env"),
+                self.one_of.is_some().then_some("one_of"),
+                self.arg_count.then_some("count"),
+                self.trim.then_some("trim"),
+                self.trim_matches.is_some().then_some("trim_matches"),
+                self.coerce_bool_numeric.then_some("coerce_bool_numeric"),
+            ]
+            .into_iter()
+            .flatten()
+            .next();
+
+            if let Some(option) = conflicting {
+                return Err(SynError::new_spanned(
+                    attr,
+                    format!("Cannot use both `presence_bool` and `{option}` on the same field"),
+                ));
+            }
+        }
+
         // Build CLI config using Option::map for idiomatic construction.
         // If arg_long is Some, we create CliAttr; otherwise cli is None.
         let cli = self.arg_long.map(|long| CliAttr {
             long: Some(long),
             short: self.arg_short,
+            count: self.arg_count,
         });
 
         Ok(EnvAttr {
             var_name,
             default: self.default,
-            optional: self.optional,
+            default_from: self.default_from,
+            from_file_var: self.from_file_var,
+            file: self.file,
+            file_key: self.file_key,
+            // `when_profile` decides at load time whether a missing value is an error
+            optional: self.optional || self.when_profile.is_some(),
             secret: self.secret,
             no_prefix: self.no_prefix,
             cli,
             profile: None, // Parsed separately via #[profile(...)] attribute
             format: self.format,
             validate: self.validate,
+            one_of: self.one_of,
+            allow_non_finite: self.allow_non_finite,
+            sensitive_log: self.sensitive_log,
+            base64: self.base64,
+            pointer_access: self.pointer_access,
+            merge_env: self.merge_env,
+            deserialize_with: self.deserialize_with,
+            sep_env: self.sep_env,
+            reload_immutable: self.reload_immutable,
+            min_len: self.min_len,
+            max_len: self.max_len,
+            validate_regex: self.validate_regex,
+            example: self.example,
+            type_label: self.type_label,
+            presence_bool: self.presence_bool,
+            trim: self.trim,
+            trim_matches: self.trim_matches,
+            sensitive_eq: self.sensitive_eq,
+            log_fingerprint: self.log_fingerprint,
+            after_parse: self.after_parse,
+            distinguish_null: self.distinguish_null,
+            redact_pattern: self.redact_pattern,
+            when_profile: self.when_profile,
+            coerce_bool_numeric: self.coerce_bool_numeric,
         })
     }
 
@@ -623,6 +1412,74 @@ impl Parser {
         Ok(None)
     }
 
+    /// Read the deserialize-side name of `key` (`rename` or `rename_all`) from
+    /// a `#[serde(...)]` attribute.
+    ///
+    /// Accepts both `key = "..."` and `key(deserialize = "...")`; every other
+    /// serde option is skipped, as serde validates those itself.
+    pub fn parse_serde_name(attr: &Attribute, key: &str) -> SynResult<Option<LitStr>> {
+        let mut name = None;
+
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident(key) {
+                return Self::skip_serde_meta(&meta);
+            }
+
+            if meta.input.peek(syn::Token![=]) {
+                name = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+
+            meta.parse_nested_meta(|side| {
+                if side.path.is_ident("deserialize") {
+                    name = Some(side.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Self::skip_serde_meta(&side)
+                }
+            })
+        })?;
+
+        Ok(name)
+    }
+
+    /// Consume the value of a serde option this crate does not interpret.
+    fn skip_serde_meta(meta: &ParseNestedMeta) -> SynResult<()> {
+        if meta.input.peek(syn::Token![=]) {
+            let _: Expr = meta.value()?.parse()?;
+        } else if meta.input.peek(syn::token::Paren) {
+            meta.parse_nested_meta(|nested| Self::skip_serde_meta(&nested))?;
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the config file key a field is deserialized from under serde.
+    ///
+    /// A field-level `#[serde(rename = "...")]` wins over the struct's
+    /// `rename_all` rule. Returns `None` when the key is the field name.
+    pub fn parse_serde_rename(
+        field: &Field,
+        rename_all: Option<RenameRule>,
+    ) -> SynResult<Option<String>> {
+        for attr in &field.attrs {
+            if !attr.path().is_ident("serde") {
+                continue;
+            }
+
+            if let Some(name) = Self::parse_serde_name(attr, "rename")? {
+                return Ok(Some(name.value()));
+            }
+        }
+
+        let (Some(rule), Some(ident)) = (rename_all, &field.ident) else {
+            return Ok(None);
+        };
+
+        let key = rule.apply(&ident.unraw().to_string());
+        Ok((*ident != key).then_some(key))
+    }
+
     /// Parse the `#[env(...)]` attribute and return the field configuration.
     ///
     /// This is the preferred entry point as it handles both regular env fields
@@ -684,40 +1541,81 @@ impl Parser {
     /// for conditionally including items. Each `bool::then_some()` returns `Some(&str)`
     /// if the condition is true, `None` otherwise. `flatten()` removes the `None`s.
     fn build_config(self, attr: &Attribute) -> SynResult<FieldConfig> {
-        // If flatten is set, validate only `prefix` is allowed as additional option
+        // A skipped field is not configuration, so no other option applies
+        if self.skip {
+            let mut incompatible = self.field_options();
+            incompatible.extend(
+                [
+                    self.flatten.then_some("flatten"),
+                    self.flatten_prefix.is_some().then_some("prefix"),
+                    self.inherit_prefix.then_some("inherit_prefix"),
+                    self.serde.then_some("serde"),
+                ]
+                .into_iter()
+                .flatten(),
+            );
+
+            if !incompatible.is_empty() {
+                return Err(SynError::new_spanned(
+                    attr,
+                    format!("Cannot use `{}` with `skip`", incompatible.join("`, `")),
+                ));
+            }
+
+            return Ok(FieldConfig::Skip);
+        }
+
+        // If flatten is set, validate only `prefix` and `inherit_prefix` are allowed
         if self.flatten {
             // Collect ALL incompatible options to report them together.
             // This improves UX: users see everything to fix in one error message.
-            //
-            // Pattern: [Option<&str>; N] → Iterator<Item=Option<&str>> → Iterator<Item=&str> → Vec<&str>
-            // The flatten() call removes None values, keeping only Some(name) items.
-            let incompatible: Vec<&str> = [
-                self.var_name.is_some().then_some("var"),
-                self.default.is_some().then_some("default"),
-                self.optional.then_some("optional"),
-                self.secret.then_some("secret"),
-                self.no_prefix.then_some("no_prefix"),
-                (self.arg_long.is_some() || self.arg_short.is_some()).then_some("arg/short"),
-                self.format.is_some().then_some("format"),
-                self.validate.is_some().then_some("validate"),
-            ]
-            .into_iter()
-            .flatten()
-            .collect();
+            let mut incompatible = self.field_options();
+
+            // A serde flatten has no env vars of its own for a prefix to apply to
+            if self.serde {
+                incompatible.extend(
+                    [
+                        self.flatten_prefix.is_some().then_some("prefix"),
+                        self.inherit_prefix.then_some("inherit_prefix"),
+                    ]
+                    .into_iter()
+                    .flatten(),
+                );
+            }
 
             if !incompatible.is_empty() {
                 // Join with "`, `" to produce: "Cannot use `var`, `default` with `flatten`"
                 return Err(SynError::new_spanned(
                     attr,
-                    format!("Cannot use `{}` with `flatten`", incompatible.join("`, `")),
+                    format!(
+                        "Cannot use `{}` with `flatten{}`",
+                        incompatible.join("`, `"),
+                        if self.serde { ", serde" } else { "" }
+                    ),
                 ));
             }
 
             return Ok(FieldConfig::Flatten {
                 prefix: self.flatten_prefix,
+                inherit_prefix: self.inherit_prefix,
+                serde: self.serde,
             });
         }
 
+        if self.serde {
+            return Err(SynError::new_spanned(
+                attr,
+                "`serde` can only be used with `flatten`",
+            ));
+        }
+
+        if self.inherit_prefix {
+            return Err(SynError::new_spanned(
+                attr,
+                "`inherit_prefix` can only be used with `flatten`",
+            ));
+        }
+
         // `prefix` requires `flatten` - catch misuse early with clear error
         if self.flatten_prefix.is_some() {
             return Err(SynError::new_spanned(
@@ -729,6 +1627,60 @@ impl Parser {
         // Otherwise, build a regular EnvAttr via the build() method
         Ok(FieldConfig::Env(self.build(attr)?))
     }
+
+    /// Names of the options set that configure how a field is loaded.
+    ///
+    /// These are rejected on `flatten` and `skip` fields, which are not
+    /// loaded from a variable of their own.
+    ///
+    /// Pattern: [Option<&str>; N] → Iterator<Item=Option<&str>> → Iterator<Item=&str> → Vec<&str>
+    /// The `flatten()` call removes `None` values, keeping only `Some(name)` items.
+    fn field_options(&self) -> Vec<&'static str> {
+        [
+            self.var_name.is_some().then_some("var"),
+            self.default.is_some().then_some("default"),
+            self.default_from.is_some().then_some("default_from"),
+            self.from_file_var.is_some().then_some("from_file_var"),
+            self.file.is_some().then_some("file"),
+            self.file_key.is_some().then_some("file_key"),
+            self.optional.then_some("optional"),
+            self.secret.then_some("secret"),
+            self.no_prefix.then_some("no_prefix"),
+            (self.arg_long.is_some() || self.arg_short.is_some() || self.arg_count)
+                .then_some("arg/short/count"),
+            self.format.is_some().then_some("format"),
+            self.validate.is_some().then_some("validate"),
+            self.one_of.is_some().then_some("one_of"),
+            self.allow_non_finite.then_some("allow_non_finite"),
+            self.sensitive_log.then_some("sensitive_log"),
+            self.base64.then_some("base64"),
+            self.pointer_access.then_some("pointer_access"),
+            self.merge_env.then_some("merge_env"),
+            self.deserialize_with
+                .is_some()
+                .then_some("deserialize_with"),
+            self.sep_env.is_some().then_some("sep_env"),
+            self.reload_immutable.then_some("reload_immutable"),
+            self.min_len.is_some().then_some("min_len"),
+            self.max_len.is_some().then_some("max_len"),
+            self.validate_regex.is_some().then_some("validate_regex"),
+            self.example.is_some().then_some("example"),
+            self.type_label.is_some().then_some("type_label"),
+            self.presence_bool.then_some("presence_bool"),
+            self.trim.then_some("trim"),
+            self.trim_matches.is_some().then_some("trim_matches"),
+            self.sensitive_eq.then_some("sensitive_eq"),
+            self.log_fingerprint.then_some("log_fingerprint"),
+            self.after_parse.is_some().then_some("after_parse"),
+            self.distinguish_null.then_some("distinguish_null"),
+            self.redact_pattern.is_some().then_some("redact_pattern"),
+            self.when_profile.is_some().then_some("when_profile"),
+            self.coerce_bool_numeric.then_some("coerce_bool_numeric"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
 }
 
 // ============================================================================
@@ -751,6 +1703,10 @@ pub enum DotenvConfig {
     /// Load from multiple files in order (later files override earlier).
     /// Generated from: `#[env_config(dotenv = [".env", ".env.local"])]`
     Multiple(Vec<String>),
+
+    /// Load from a specific file path, failing if it does not exist.
+    /// Generated from: `#[env_config(dotenv_required = ".env.prod")]`
+    Required(String),
 }
 
 /// Configuration for a config file source.
@@ -792,12 +1748,33 @@ impl FileConfig {
 /// | Option | Description |
 /// |--------|-------------|
 /// | `prefix = "APP_"` | Prefix added to all env var names |
+/// | `prefix_env = "APP_NAMESPACE"` | Env var holding a runtime prefix for `from_env()`, `from_env_with_sources()`, `from_env_timed()`, `from_args()` and `apply_env()` |
+/// | `env_prefix_separator = "_"` | Join prefixes and names with exactly one separator |
 /// | `dotenv` | Load `.env` file from current directory |
 /// | `dotenv = ".env.local"` | Load specific dotenv file |
+/// | `dotenv_required = ".env.prod"` | Load dotenv file, error if it is missing |
 /// | `file = "config.toml"` | Load required config file |
 /// | `file_optional = "..."` | Load optional config file |
+/// | `embedded = "config.default.toml"` | Compile a config file into the binary |
+/// | `files_override_env` | Config files take precedence over env vars |
+/// | `schema_version = 2` | `from_config()` rejects configs declaring a newer version |
+/// | `version_key = "meta.version"` | Key holding the config's version (default `"version"`) |
+/// | `missing_version = 1` | Version of a config without the key (default `1`) |
+/// | `from_json_var = "APP_CONFIG"` | Generate `from_json_env()` reading one JSON env var |
 /// | `profile_env = "APP_ENV"` | Env var for profile selection |
 /// | `profiles = ["dev", "prod"]` | Valid profile names |
+/// | `default_profile = "dev"` | Profile used when the profile env var is unset |
+/// | `strict_profiles` | Every `#[profile(...)]` must cover exactly `profiles` |
+/// | `validate` | Generate validated loading methods |
+/// | `timed` | Generate `from_env_timed()` |
+/// | `no_accumulate` | `from_env()` returns the first error |
+/// | `allow_empty_struct` | Load as `Default` when none of the struct's vars are set |
+/// | `derive_default` | Generate `impl Default` from each field's `default` |
+/// | `error_format = "json"` | Error format of `from_env_formatted()` (`"pretty"` or `"json"`) |
+/// | `audit = "path::to_fn"` | Hook called on each secret field load |
+/// | `allow_unmask` | Let `PROCENV_UNMASK=1` show `secret` fields in `Debug` and errors |
+/// | `unmask_in_debug` | Show `secret` fields in `Debug` and errors in debug builds only |
+/// | `case_insensitive_env` | Env var names match ignoring ASCII case |
 ///
 /// # Example
 ///
@@ -815,6 +1792,10 @@ impl FileConfig {
 /// }
 /// ```
 #[derive(Clone, Debug, Default)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each boolean mirrors an independent flag option in the attribute"
+)]
 pub struct EnvConfigAttr {
     /// Dotenv configuration: `None` (disabled) or `Some(DotenvConfig)` (enabled).
     pub dotenv: Option<DotenvConfig>,
@@ -825,11 +1806,52 @@ pub struct EnvConfigAttr {
     /// will read from `APP_PORT`.
     pub prefix: Option<String>,
 
+    /// Environment variable holding a prefix chosen at load time.
+    ///
+    /// When set, `from_env()` and `from_env_with_sources()` load as
+    /// `from_env_with_prefix(value)` would; when unset, only `prefix` applies.
+    /// Generated from: `#[env_config(prefix_env = "APP_NAMESPACE")]`
+    pub prefix_env: Option<String>,
+
+    /// Separator placed between a prefix and a var name.
+    ///
+    /// When set, joining ensures exactly one separator between them, so
+    /// `prefix = "APP"` and `prefix = "APP_"` both read `PORT` as `APP_PORT`.
+    /// When unset, prefixes are concatenated as written.
+    /// Generated from: `#[env_config(env_prefix_separator = "_")]`
+    pub env_prefix_separator: Option<String>,
+
     /// Config files to load (in order, later files override earlier).
     ///
     /// Supports both required and optional files.
     pub files: Vec<FileConfig>,
 
+    /// Config file embedded at compile time via `include_str!`.
+    ///
+    /// The path is resolved relative to `CARGO_MANIFEST_DIR` and the
+    /// content is merged below all runtime config files.
+    pub embedded: Option<String>,
+
+    /// Let config files override environment variables in `from_config()`.
+    /// Generated from: `#[env_config(files_override_env)]`
+    pub files_override_env: bool,
+
+    /// Newest config schema version `from_config()` accepts.
+    /// Generated from: `#[env_config(schema_version = 2)]`
+    pub schema_version: Option<u64>,
+
+    /// Dotted key of the merged value holding the config's version.
+    /// Generated from: `#[env_config(version_key = "version")]`
+    pub version_key: Option<String>,
+
+    /// Version assumed for a config without the version key.
+    /// Generated from: `#[env_config(missing_version = 1)]`
+    pub missing_version: Option<u64>,
+
+    /// Env var holding the whole config as one JSON document.
+    /// Generated from: `#[env_config(from_json_var = "APP_CONFIG")]`
+    pub from_json_var: Option<String>,
+
     /// Environment variable that selects the active profile.
     ///
     /// For example, `profile_env = "APP_ENV"` means the value of
@@ -842,9 +1864,63 @@ pub struct EnvConfigAttr {
     /// a value not in this list.
     pub profiles: Option<Vec<String>>,
 
+    /// Profile used when the profile env var is unset.
+    /// Generated from: `#[env_config(default_profile = "dev")]`
+    pub default_profile: Option<String>,
+
+    /// Require each `#[profile(...)]` to give a value for every declared
+    /// profile and no others, checked at compile time.
+    /// Generated from: `#[env_config(strict_profiles)]`
+    pub strict_profiles: bool,
+
+    /// Honour `PROCENV_UNMASK=1`, showing `secret` fields in the clear.
+    /// Generated from: `#[env_config(allow_unmask)]`
+    pub allow_unmask: bool,
+
+    /// Show `secret` fields in the clear when `debug_assertions` is on.
+    /// Generated from: `#[env_config(unmask_in_debug)]`
+    pub unmask_in_debug: bool,
+
+    /// Look env vars up ignoring ASCII case when no exact match is set.
+    /// Generated from: `#[env_config(case_insensitive_env)]`
+    pub case_insensitive_env: bool,
+
     /// Enable automatic validation after loading.
     /// Generated from: `#[env_config(validate)]`
     pub validate: bool,
+
+    /// Generate `from_env_timed()` for per-field load timing.
+    /// Generated from: `#[env_config(timed)]`
+    pub timed: bool,
+
+    /// Make `from_env()` return on the first error instead of collecting all.
+    /// Generated from: `#[env_config(no_accumulate)]`
+    pub no_accumulate: bool,
+
+    /// Load as `Default::default()` instead of erroring when none of the
+    /// struct's own env vars are set.
+    /// Generated from: `#[env_config(allow_empty_struct)]`
+    pub allow_empty_struct: bool,
+
+    /// Generate `impl Default` from each field's `default`.
+    /// Generated from: `#[env_config(derive_default)]`
+    pub derive_default: bool,
+
+    /// How `from_env_formatted()` renders errors: `"pretty"` or `"json"`.
+    /// Generated from: `#[env_config(error_format = "json")]`
+    pub error_format: Option<String>,
+
+    /// Function called with `(var_name, present)` whenever a secret or
+    /// `sensitive_log` field is loaded from the environment.
+    /// Generated from: `#[env_config(audit = "audit_fn")]`
+    ///
+    /// Stored as the path's source text; validated as a `syn::Path` when parsed.
+    pub audit: Option<String>,
+
+    /// Case convention applied to config file keys of fields without a
+    /// `#[serde(rename = "...")]`.
+    /// Read from: `#[serde(rename_all = "camelCase")]`
+    pub serde_rename_all: Option<RenameRule>,
 }
 
 impl EnvConfigAttr {
@@ -856,20 +1932,55 @@ impl EnvConfigAttr {
     /// #[env_config(dotenv)]                              // Default .env loading
     /// #[env_config(dotenv = ".env.local")]               // Custom file path
     /// #[env_config(dotenv = [".env", ".env.local"])]     // Multiple files
+    /// #[env_config(dotenv_required = ".env.prod")]       // Error if file is missing
     /// #[env_config(file = "config.toml")]                // Single config file
     /// #[env_config(file = ["config.toml", "config.local.toml"])]  // Multiple files
     /// #[env_config(file_optional = "config.local.toml")] // Optional config file
     /// ```
+    #[expect(
+        clippy::too_many_lines,
+        reason = "one branch per supported option keeps parsing in a single place"
+    )]
     pub fn parse_from_struct(input: &DeriveInput) -> SynResult<Self> {
         let mut result = Self::default();
 
         for attr in &input.attrs {
+            if attr.path().is_ident("serde") {
+                // Only `rename_all` matters here; serde itself checks the rest
+                if let Some(rule) = Parser::parse_serde_name(attr, "rename_all")? {
+                    let rule_str = rule.value();
+                    let parsed = RenameRule::from_name(&rule_str).ok_or_else(|| {
+                        SynError::new_spanned(
+                            &rule,
+                            format!("unknown serde rename rule `{rule_str}`"),
+                        )
+                    })?;
+                    result.serde_rename_all = Some(parsed);
+                }
+                continue;
+            }
+
             if !attr.path().is_ident("env_config") {
                 continue;
             }
 
             attr.parse_nested_meta(|meta| {
-                if meta.path.is_ident("dotenv") {
+                let required_set = matches!(result.dotenv, Some(DotenvConfig::Required(_)));
+                if (meta.path.is_ident("dotenv_required") && result.dotenv.is_some())
+                    || (meta.path.is_ident("dotenv") && required_set)
+                {
+                    return Err(meta.error(
+                        "`dotenv` and `dotenv_required` cannot both be set; use one of them",
+                    ));
+                }
+
+                if meta.path.is_ident("dotenv_required") {
+                    // Fail-fast dotenv: dotenv_required = ".env.prod"
+                    let lit_str: LitStr = meta.value()?.parse()?;
+                    result.dotenv = Some(DotenvConfig::Required(lit_str.value()));
+
+                    Ok(())
+                } else if meta.path.is_ident("dotenv") {
                     // Check if it has a value: #[env_config(dotenv = "...")]
                     if meta.input.peek(syn::Token![=]) {
                         let _eq: syn::Token![=] = meta.input.parse()?;
@@ -900,11 +2011,107 @@ impl EnvConfigAttr {
                 } else if meta.path.is_ident("validate") {
                     result.validate = true;
 
+                    Ok(())
+                } else if meta.path.is_ident("timed") {
+                    result.timed = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("no_accumulate") {
+                    result.no_accumulate = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("allow_empty_struct") {
+                    result.allow_empty_struct = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("derive_default") {
+                    result.derive_default = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("error_format") {
+                    // Error rendering: error_format = "json"
+                    let lit_str: LitStr = meta.value()?.parse()?;
+                    let format = lit_str.value();
+
+                    if !matches!(format.as_str(), "pretty" | "json") {
+                        return Err(SynError::new_spanned(
+                            lit_str,
+                            "error_format must be \"pretty\" or \"json\"",
+                        ));
+                    }
+
+                    result.error_format = Some(format);
+
+                    Ok(())
+                } else if meta.path.is_ident("strict_profiles") {
+                    result.strict_profiles = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("allow_unmask") {
+                    result.allow_unmask = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("unmask_in_debug") {
+                    result.unmask_in_debug = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("case_insensitive_env") {
+                    result.case_insensitive_env = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("audit") {
+                    // Secret access hook: audit = "audit_fn"
+                    let lit_str: LitStr = meta.value()?.parse()?;
+                    lit_str.parse::<syn::Path>()?;
+                    result.audit = Some(lit_str.value());
+
+                    Ok(())
+                } else if meta.path.is_ident("files_override_env") {
+                    result.files_override_env = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("schema_version") {
+                    // Forward-compatibility guard: schema_version = 2
+                    let lit_int: LitInt = meta.value()?.parse()?;
+                    result.schema_version = Some(lit_int.base10_parse()?);
+
+                    Ok(())
+                } else if meta.path.is_ident("version_key") {
+                    let lit_str: LitStr = meta.value()?.parse()?;
+
+                    if lit_str.value().is_empty() {
+                        return Err(meta.error("version_key cannot be empty"));
+                    }
+
+                    result.version_key = Some(lit_str.value());
+
+                    Ok(())
+                } else if meta.path.is_ident("missing_version") {
+                    let lit_int: LitInt = meta.value()?.parse()?;
+                    result.missing_version = Some(lit_int.base10_parse()?);
+
                     Ok(())
                 } else if meta.path.is_ident("prefix") {
                     let lit_str: LitStr = meta.value()?.parse()?;
                     result.prefix = Some(lit_str.value());
 
+                    Ok(())
+                } else if meta.path.is_ident("prefix_env") {
+                    // Runtime prefix: prefix_env = "APP_NAMESPACE"
+                    let lit_str: LitStr = meta.value()?.parse()?;
+                    result.prefix_env = Some(lit_str.value());
+
+                    Ok(())
+                } else if meta.path.is_ident("env_prefix_separator") {
+                    // Prefix/name separator: env_prefix_separator = "_"
+                    let lit_str: LitStr = meta.value()?.parse()?;
+
+                    if lit_str.value().is_empty() {
+                        return Err(meta.error("env_prefix_separator cannot be empty"));
+                    }
+
+                    result.env_prefix_separator = Some(lit_str.value());
+
                     Ok(())
                 } else if meta.path.is_ident("file") {
                     // Required config file(s)
@@ -914,11 +2121,35 @@ impl EnvConfigAttr {
                     // Optional config file(s)
                     Self::parse_file_config(&meta, &mut result.files, false)?;
                     Ok(())
+                } else if meta.path.is_ident("embedded") {
+                    // Compile-time config file: embedded = "config.default.toml"
+                    let lit_str: LitStr = meta.value()?.parse()?;
+                    let path = lit_str.value();
+
+                    if embedded_format(&path).is_none() {
+                        return Err(SynError::new_spanned(
+                            lit_str,
+                            "embedded file must have a .json, .toml, .yaml or .yml extension",
+                        ));
+                    }
+
+                    result.embedded = Some(path);
+                    Ok(())
+                } else if meta.path.is_ident("from_json_var") {
+                    // Whole-struct JSON env var: from_json_var = "APP_CONFIG"
+                    let lit_str: LitStr = meta.value()?.parse()?;
+                    result.from_json_var = Some(lit_str.value());
+                    Ok(())
                 } else if meta.path.is_ident("profile_env") {
                     // Profile selection env var: profile_env = "APP_ENV"
                     let lit_str: LitStr = meta.value()?.parse()?;
                     result.profile_env = Some(lit_str.value());
                     Ok(())
+                } else if meta.path.is_ident("default_profile") {
+                    // Fallback profile: default_profile = "dev"
+                    let lit_str: LitStr = meta.value()?.parse()?;
+                    result.default_profile = Some(lit_str.value());
+                    Ok(())
                 } else if meta.path.is_ident("profiles") {
                     // Valid profile names: profiles = ["dev", "staging", "prod"]
                     let _eq: syn::Token![=] = meta.input.parse()?;
@@ -957,6 +2188,81 @@ impl EnvConfigAttr {
             ));
         }
 
+        if let Some(default_profile) = &result.default_profile {
+            if result.profile_env.is_none() {
+                return Err(SynError::new_spanned(
+                    &input.ident,
+                    "default_profile requires profile_env to be set",
+                ));
+            }
+
+            if let Some(profiles) = &result.profiles
+                && !profiles.contains(default_profile)
+            {
+                return Err(SynError::new_spanned(
+                    &input.ident,
+                    format!(
+                        "default_profile `{default_profile}` is not one of profiles: {}",
+                        profiles.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        if result.strict_profiles && result.profiles.is_none() {
+            // Exhaustiveness is checked against the declared profile list
+            return Err(SynError::new_spanned(
+                &input.ident,
+                "strict_profiles requires profiles to be set",
+            ));
+        }
+
+        if result.unmask_in_debug && result.allow_unmask {
+            // PROCENV_UNMASK would reopen release builds that this keeps masked
+            return Err(SynError::new_spanned(
+                &input.ident,
+                "`unmask_in_debug` cannot be combined with `allow_unmask`",
+            ));
+        }
+
+        if result.files_override_env && result.files.is_empty() && result.embedded.is_none() {
+            // Without file layers there is nothing to take precedence
+            return Err(SynError::new_spanned(
+                &input.ident,
+                "files_override_env requires `file`, `file_optional` or `embedded`",
+            ));
+        }
+
+        if let Some(schema_version) = result.schema_version {
+            // Only the layered loaders have a merged value to read the version from
+            if result.files.is_empty()
+                && result.embedded.is_none()
+                && result.from_json_var.is_none()
+            {
+                return Err(SynError::new_spanned(
+                    &input.ident,
+                    "schema_version requires `file`, `file_optional`, `embedded` or `from_json_var`",
+                ));
+            }
+
+            // A config without the key counts as version 1 unless told otherwise
+            let missing_version = result.missing_version.unwrap_or(1);
+            if missing_version > schema_version {
+                return Err(SynError::new_spanned(
+                    &input.ident,
+                    format!(
+                        "missing_version {missing_version} exceeds schema_version {schema_version}, \
+                         so every config without a version would be rejected"
+                    ),
+                ));
+            }
+        } else if result.version_key.is_some() || result.missing_version.is_some() {
+            return Err(SynError::new_spanned(
+                &input.ident,
+                "version_key and missing_version require schema_version to be set",
+            ));
+        }
+
         Ok(result)
     }
 
@@ -1016,3 +2322,88 @@ impl EnvConfigAttr {
         Ok(())
     }
 }
+
+/// Map an embedded file path to the name of its `FileFormat` variant.
+///
+/// Returns `None` for unrecognized extensions so the error can be reported
+/// at compile time rather than when the binary runs.
+pub fn embedded_format(path: &str) -> Option<&'static str> {
+    let extension = std::path::Path::new(path).extension()?.to_str()?;
+
+    match extension.to_ascii_lowercase().as_str() {
+        "json" => Some("Json"),
+        "toml" => Some("Toml"),
+        "yaml" | "yml" => Some("Yaml"),
+        _ => None,
+    }
+}
+
+/// A serde `rename_all` case convention, applied to `snake_case` field names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenameRule {
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// Parse a rule from the string serde accepts for it.
+    pub fn from_name(rule: &str) -> Option<Self> {
+        match rule {
+            "lowercase" => Some(Self::Lower),
+            "UPPERCASE" => Some(Self::Upper),
+            "PascalCase" => Some(Self::Pascal),
+            "camelCase" => Some(Self::Camel),
+            "snake_case" => Some(Self::Snake),
+            "SCREAMING_SNAKE_CASE" => Some(Self::ScreamingSnake),
+            "kebab-case" => Some(Self::Kebab),
+            "SCREAMING-KEBAB-CASE" => Some(Self::ScreamingKebab),
+            _ => None,
+        }
+    }
+
+    /// Rename a field the way serde does for this rule.
+    pub fn apply(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => {
+                let mut pascal = String::with_capacity(field.len());
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            Self::Camel => {
+                let pascal = Self::Pascal.apply(field);
+                let mut chars = pascal.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_ascii_lowercase().to_string() + chars.as_str()
+                })
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}